@@ -33,6 +33,9 @@ pub struct LintCommand {
     #[bpaf(external)]
     pub output_options: OutputOptions,
 
+    #[bpaf(external)]
+    pub baseline_options: BaselineOptions,
+
     /// list all the rules that are currently registered
     #[bpaf(long("rules"), switch, hide_usage)]
     pub list_rules: bool,
@@ -175,6 +178,20 @@ pub struct WarningOptions {
     pub max_warnings: Option<usize>,
 }
 
+/// Baseline
+#[derive(Debug, Clone, Bpaf)]
+pub struct BaselineOptions {
+    /// Baseline file of known violations, which are not reported.
+    /// Use it to enable rules in a large codebase before fixing all existing violations
+    #[bpaf(argument("./oxlint-baseline.json"), hide_usage)]
+    pub baseline: Option<PathBuf>,
+
+    /// Write the violations of the linted files to the baseline file instead of reporting them.
+    /// Violations of files which are not linted are kept
+    #[bpaf(switch, hide_usage)]
+    pub update_baseline: bool,
+}
+
 /// Output
 #[derive(Debug, Clone, Bpaf)]
 pub struct OutputOptions {
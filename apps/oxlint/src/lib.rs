@@ -2,7 +2,6 @@ mod command;
 mod lint;
 mod result;
 mod runner;
-mod walk;
 
 pub use crate::{
     command::*,
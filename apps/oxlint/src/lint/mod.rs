@@ -1,15 +1,12 @@
 use std::{env, io::BufWriter, time::Instant};
 
-use ignore::gitignore::Gitignore;
 use oxc_diagnostics::{DiagnosticService, GraphicalReportHandler};
 use oxc_linter::{
-    partial_loader::LINT_PARTIAL_LOADER_EXT, LintOptions, LintService, LintServiceOptions, Linter,
+    Baseline, LintOptions, LintService, LintServiceOptions, Linter, Symlinks, Walk, WalkOptions,
 };
-use oxc_span::VALID_EXTENSIONS;
 
 use crate::{
-    command::{LintCommand, OutputFormat, OutputOptions, WarningOptions},
-    walk::{Extensions, Walk},
+    command::{IgnoreOptions, LintCommand, OutputFormat, OutputOptions, WarningOptions},
     CliRunResult, LintResult, MiscOptions, Runner,
 };
 
@@ -40,6 +37,7 @@ impl Runner for LintRunner {
             fix_options,
             enable_plugins,
             output_options,
+            baseline_options,
             misc_options,
             ..
         } = self.options;
@@ -48,15 +46,8 @@ impl Runner for LintRunner {
         let provided_path_count = paths.len();
         let now = Instant::now();
 
-        // The ignore crate whitelists explicit paths, but priority
-        // should be given to the ignore file. Many users lint
-        // automatically and pass a list of changed files explicitly.
-        // To accommodate this, unless `--no-ignore` is passed,
-        // pre-filter the paths.
-        if !paths.is_empty() && !ignore_options.no_ignore {
-            let (ignore, _err) = Gitignore::new(&ignore_options.ignore_path);
-            paths.retain(|p| if p.is_dir() { true } else { !ignore.matched(p, false).is_ignore() });
-        }
+        let walk_options = walk_options(&ignore_options);
+        walk_options.retain_unignored(&mut paths);
 
         if paths.is_empty() {
             // If explicit paths were provided, but all have been
@@ -78,14 +69,7 @@ impl Runner for LintRunner {
             }
         }
 
-        let extensions = VALID_EXTENSIONS
-            .iter()
-            .chain(LINT_PARTIAL_LOADER_EXT.iter())
-            .copied()
-            .collect::<Vec<&'static str>>();
-
-        let paths =
-            Walk::new(&paths, &ignore_options).with_extensions(Extensions(extensions)).paths();
+        let paths = Walk::new(&paths, &walk_options).paths();
 
         let number_of_files = paths.len();
 
@@ -131,7 +115,34 @@ impl Runner for LintRunner {
             }
         }
 
-        let options = LintServiceOptions { cwd, paths, tsconfig };
+        let baseline_path = baseline_options.baseline;
+        let baseline = match &baseline_path {
+            None if baseline_options.update_baseline => {
+                return CliRunResult::InvalidOptions {
+                    message: "`--update-baseline` requires a `--baseline` file.".to_string(),
+                };
+            }
+            Some(path) if !baseline_options.update_baseline || path.is_file() => {
+                match Baseline::from_file(path) {
+                    Ok(baseline) => Some(baseline),
+                    Err(diagnostic) => {
+                        return CliRunResult::InvalidOptions { message: diagnostic.to_string() };
+                    }
+                }
+            }
+            _ => None,
+        };
+        // When updating, the violations of the linted files replace theirs in the previous baseline.
+        let (baseline, previous_baseline) =
+            if baseline_options.update_baseline { (None, baseline) } else { (baseline, None) };
+
+        let options = LintServiceOptions {
+            cwd,
+            paths,
+            tsconfig,
+            baseline,
+            record_baseline: baseline_options.update_baseline,
+        };
         let lint_service = LintService::new(linter, options);
         let mut diagnostic_service =
             Self::get_diagnostic_service(&warning_options, &output_options, &misc_options);
@@ -146,6 +157,14 @@ impl Runner for LintRunner {
         });
         diagnostic_service.run();
 
+        if let Some(path) = baseline_path.filter(|_| baseline_options.update_baseline) {
+            let mut baseline = previous_baseline.unwrap_or_default();
+            baseline.extend(lint_service.take_recorded_baseline());
+            if let Err(diagnostic) = baseline.write_file(&path) {
+                return CliRunResult::InvalidOptions { message: diagnostic.to_string() };
+            }
+        }
+
         CliRunResult::LintResult(LintResult {
             duration: now.elapsed(),
             number_of_rules: lint_service.linter().number_of_rules(),
@@ -181,6 +200,17 @@ impl LintRunner {
     }
 }
 
+/// `--ignore-path` replaces `.eslintignore`, and `.oxlintignore` is always read.
+fn walk_options(ignore_options: &IgnoreOptions) -> WalkOptions {
+    WalkOptions {
+        ignore_files: vec![ignore_options.ignore_path.clone(), ".oxlintignore".into()],
+        ignore_patterns: ignore_options.ignore_pattern.clone(),
+        no_ignore: ignore_options.no_ignore,
+        symlinks: if ignore_options.symlinks { Symlinks::Follow } else { Symlinks::Skip },
+        ..WalkOptions::default()
+    }
+}
+
 #[cfg(all(test, not(target_os = "windows")))]
 mod test {
     use super::LintRunner;
@@ -512,4 +542,27 @@ mod test {
         assert_eq!(result.number_of_files, 1);
         assert_eq!(result.number_of_errors, 1);
     }
+
+    #[test]
+    fn test_baseline_option() {
+        let baseline = std::env::temp_dir().join("oxlint-test-baseline.json");
+        let baseline = baseline.to_str().unwrap();
+        let _ = std::fs::remove_file(baseline);
+
+        let result = test(&["--update-baseline", "--baseline", baseline, "fixtures/linter"]);
+        assert_eq!(result.number_of_warnings, 0);
+        assert_eq!(result.number_of_errors, 0);
+
+        let result = test(&["--baseline", baseline, "fixtures/linter"]);
+        assert_eq!(result.number_of_warnings, 0);
+        assert_eq!(result.number_of_errors, 0);
+        let result = test(&["fixtures/linter"]);
+        assert!(result.number_of_warnings > 0);
+        std::fs::remove_file(baseline).unwrap();
+
+        assert!(test_invalid_options(&["--update-baseline", "fixtures/linter"])
+            .contains("requires a `--baseline` file"));
+        assert!(test_invalid_options(&["--baseline", "fixtures/baseline.json", "fixtures/linter"])
+            .contains("Failed to read baseline"));
+    }
 }
@@ -47,7 +47,11 @@ impl<'a, const MINIFY: bool> Gen<MINIFY> for Program<'a> {
     fn gen(&self, p: &mut Codegen<{ MINIFY }>, ctx: Context) {
         if let Some(hashbang) = &self.hashbang {
             hashbang.gen(p, ctx);
+            if p.banner.is_some() && !hashbang.value.ends_with('\n') {
+                p.print_hard_newline();
+            }
         }
+        p.print_banner();
         for directive in &self.directives {
             directive.gen(p, ctx);
         }
@@ -659,8 +663,8 @@ impl<'a, const MINIFY: bool> Gen<MINIFY> for Function<'a> {
                 this_param.gen(p, ctx);
                 if !self.params.is_empty() || self.params.rest.is_some() {
                     p.print_str(",");
+                    p.print_soft_space();
                 }
-                p.print_soft_space();
             }
             self.params.gen(p, ctx);
             p.print_char(b')');
@@ -1374,9 +1378,10 @@ impl<'a, const MINIFY: bool> Gen<MINIFY> for StringLiteral<'a> {
     fn gen(&self, p: &mut Codegen<{ MINIFY }>, _ctx: Context) {
         p.add_source_mapping(self.span.start);
         let s = self.value.as_str();
-        p.wrap_quote(|p, quote| {
-            print_unquoted_str(s, quote, p);
-        });
+        let quote = p.string_quote(s);
+        p.print_char(quote);
+        print_unquoted_str(s, quote, p);
+        p.print_char(quote);
     }
 }
 
@@ -1498,7 +1503,10 @@ impl<'a, const MINIFY: bool> Gen<MINIFY> for ArrayExpression<'a> {
         p.add_source_mapping(self.span.start);
         p.print_char(b'[');
         p.print_list(&self.elements, ctx);
-        if self.trailing_comma.is_some() {
+        // A hole at the end needs a comma of its own, `[a, ,]` has two elements.
+        if self.trailing_comma.is_some()
+            || self.elements.last().is_some_and(ArrayExpressionElement::is_elision)
+        {
             p.print_comma();
         }
         p.print_char(b']');
@@ -1626,7 +1634,16 @@ impl<'a, const MINIFY: bool> Gen<MINIFY> for ObjectProperty<'a> {
 impl<'a, const MINIFY: bool> Gen<MINIFY> for PropertyKey<'a> {
     fn gen(&self, p: &mut Codegen<{ MINIFY }>, ctx: Context) {
         match self {
-            Self::StaticIdentifier(ident) => ident.gen(p, ctx),
+            Self::StaticIdentifier(ident) => {
+                if !p.print_member_name_as_written(ident.span, &ident.name, false) {
+                    ident.gen(p, ctx);
+                }
+            }
+            Self::StringLiteral(lit) => {
+                if !p.print_member_name_as_written(lit.span, &lit.value, true) {
+                    lit.gen(p, ctx);
+                }
+            }
             Self::PrivateIdentifier(ident) => ident.gen(p, ctx),
             match_expression!(Self) => {
                 self.to_expression().gen_expr(p, Precedence::Comma, Context::empty());
@@ -2484,6 +2501,13 @@ impl<'a, const MINIFY: bool> Gen<MINIFY> for MethodDefinition<'a> {
             type_parameters.gen(p, ctx);
         }
         p.print_char(b'(');
+        if let Some(this_param) = &self.value.this_param {
+            this_param.gen(p, ctx);
+            if !self.value.params.is_empty() || self.value.params.rest.is_some() {
+                p.print_str(",");
+                p.print_soft_space();
+            }
+        }
         self.value.params.gen(p, ctx);
         p.print_char(b')');
         if let Some(return_type) = &self.value.return_type {
@@ -3107,8 +3131,8 @@ impl<'a, const MINIFY: bool> Gen<MINIFY> for TSFunctionType<'a> {
             this_param.gen(p, ctx);
             if !self.params.is_empty() || self.params.rest.is_some() {
                 p.print_str(",");
+                p.print_soft_space();
             }
-            p.print_soft_space();
         }
         self.params.gen(p, ctx);
         p.print_str(")");
@@ -3143,15 +3167,10 @@ impl<'a, const MINIFY: bool> Gen<MINIFY> for TSSignature<'a> {
                     p.print_char(b']');
                 } else {
                     match &signature.key {
-                        PropertyKey::StaticIdentifier(key) => {
-                            key.gen(p, ctx);
-                        }
                         PropertyKey::PrivateIdentifier(key) => {
                             p.print_str(key.name.as_str());
                         }
-                        key @ match_expression!(PropertyKey) => {
-                            key.to_expression().gen_expr(p, Precedence::Comma, ctx);
-                        }
+                        key => key.gen(p, ctx),
                     }
                 }
                 if signature.optional {
@@ -3172,8 +3191,8 @@ impl<'a, const MINIFY: bool> Gen<MINIFY> for TSSignature<'a> {
                     this_param.gen(p, ctx);
                     if !signature.params.is_empty() || signature.params.rest.is_some() {
                         p.print_str(",");
+                        p.print_soft_space();
                     }
-                    p.print_soft_space();
                 }
                 signature.params.gen(p, ctx);
                 p.print_str(")");
@@ -3209,15 +3228,10 @@ impl<'a, const MINIFY: bool> Gen<MINIFY> for TSSignature<'a> {
                     p.print_char(b']');
                 } else {
                     match &signature.key {
-                        PropertyKey::StaticIdentifier(key) => {
-                            key.gen(p, ctx);
-                        }
                         PropertyKey::PrivateIdentifier(key) => {
                             p.print_str(key.name.as_str());
                         }
-                        key @ match_expression!(PropertyKey) => {
-                            key.to_expression().gen_expr(p, Precedence::Comma, ctx);
-                        }
+                        key => key.gen(p, ctx),
                     }
                 }
                 if signature.optional {
@@ -3231,8 +3245,8 @@ impl<'a, const MINIFY: bool> Gen<MINIFY> for TSSignature<'a> {
                     this_param.gen(p, ctx);
                     if !signature.params.is_empty() || signature.params.rest.is_some() {
                         p.print_str(",");
+                        p.print_soft_space();
                     }
-                    p.print_soft_space();
                 }
                 signature.params.gen(p, ctx);
                 p.print_str(")");
@@ -3537,8 +3551,16 @@ impl<'a, const MINIFY: bool> Gen<MINIFY> for TSEnumDeclaration<'a> {
 impl<'a, const MINIFY: bool> Gen<MINIFY> for TSEnumMember<'a> {
     fn gen(&self, p: &mut Codegen<{ MINIFY }>, ctx: Context) {
         match &self.id {
-            TSEnumMemberName::StaticIdentifier(decl) => decl.gen(p, ctx),
-            TSEnumMemberName::StaticStringLiteral(decl) => decl.gen(p, ctx),
+            TSEnumMemberName::StaticIdentifier(decl) => {
+                if !p.print_member_name_as_written(decl.span, &decl.name, false) {
+                    decl.gen(p, ctx);
+                }
+            }
+            TSEnumMemberName::StaticStringLiteral(decl) => {
+                if !p.print_member_name_as_written(decl.span, &decl.value, true) {
+                    decl.gen(p, ctx);
+                }
+            }
             TSEnumMemberName::StaticTemplateLiteral(decl) => decl.gen(p, ctx),
             TSEnumMemberName::StaticNumericLiteral(decl) => decl.gen(p, ctx),
             decl @ match_expression!(TSEnumMemberName) => {
@@ -43,8 +43,34 @@ pub type WhitespaceRemover<'a> = Codegen<'a, true>;
 
 #[derive(Default, Clone, Copy)]
 pub struct CodegenOptions {
-    /// Use single quotes instead of double quotes.
+    /// Use single quotes instead of double quotes. String literals which contain more of the
+    /// preferred quote than of the other one are printed with the other one, which needs fewer
+    /// escapes.
     pub single_quote: bool,
+
+    /// Indent with this number of spaces instead of a tab.
+    pub indent_width: Option<u8>,
+
+    /// Line breaks to print.
+    pub newline: Newline,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Newline {
+    /// `\n`
+    #[default]
+    Lf,
+    /// `\r\n`
+    CrLf,
+}
+
+impl Newline {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Lf => "\n",
+            Self::CrLf => "\r\n",
+        }
+    }
 }
 
 #[derive(Default, Clone, Copy)]
@@ -64,10 +90,16 @@ pub struct Codegen<'a, const MINIFY: bool> {
 
     source_text: &'a str,
 
+    /// See [Codegen::with_source_text]
+    member_names_as_written: bool,
+
     trivias: Trivias,
 
     mangler: Option<Mangler>,
 
+    /// Printed before the first statement, see [Codegen::with_banner]
+    banner: Option<String>,
+
     /// Output Code
     code: Vec<u8>,
 
@@ -129,8 +161,10 @@ impl<'a, const MINIFY: bool> Codegen<'a, MINIFY> {
             options: CodegenOptions::default(),
             comment_options: CommentOptions::default(),
             source_text: "",
+            member_names_as_written: false,
             trivias: Trivias::default(),
             mangler: None,
+            banner: None,
             code: vec![],
             needs_semicolon: false,
             need_space_before_dot: 0,
@@ -179,6 +213,20 @@ impl<'a, const MINIFY: bool> Codegen<'a, MINIFY> {
         self
     }
 
+    /// Print member names as they are written in `source_text`, the text the program was parsed
+    /// from: string literal keys keep their quotes and escapes, `{ 'a': 1 }`, and identifier
+    /// keys their unicode escapes, `{ \u0061: 1 }`. Declaration files need this, since tsc emits
+    /// member names as declared.
+    ///
+    /// Keys whose text in `source_text` does not evaluate to their name, such as keys created or
+    /// renamed by a transform, are printed as usual. Ignored when minifying.
+    #[must_use]
+    pub fn with_source_text(mut self, source_text: &'a str) -> Self {
+        self.source_text = source_text;
+        self.member_names_as_written = true;
+        self
+    }
+
     #[must_use]
     pub fn enable_source_map(mut self, source_name: &str, source_text: &str) -> Self {
         let mut sourcemap_builder = SourcemapBuilder::default();
@@ -193,6 +241,17 @@ impl<'a, const MINIFY: bool> Codegen<'a, MINIFY> {
         self
     }
 
+    /// Print `banner` at the start of the output, after the hashbang, e.g. a license comment or
+    /// a `// @ts-nocheck` directive.
+    ///
+    /// Each line of the banner is printed with [CodegenOptions::newline], also when minifying,
+    /// and source map mappings account for the lines it adds. An empty banner is ignored.
+    #[must_use]
+    pub fn with_banner(mut self, banner: &str) -> Self {
+        self.banner = (!banner.is_empty()).then(|| banner.to_string());
+        self
+    }
+
     #[must_use]
     pub fn build(mut self, program: &Program<'_>) -> CodegenReturn {
         program.gen(&mut self, Context::default());
@@ -246,7 +305,20 @@ impl<'a, const MINIFY: bool> Codegen<'a, MINIFY> {
     #[inline]
     fn print_soft_newline(&mut self) {
         if !MINIFY {
-            self.print_char(b'\n');
+            self.print_hard_newline();
+        }
+    }
+
+    #[inline]
+    fn print_hard_newline(&mut self) {
+        self.print_str(self.options.newline.as_str());
+    }
+
+    fn print_banner(&mut self) {
+        let Some(banner) = self.banner.take() else { return };
+        for line in banner.lines() {
+            self.print_str(line);
+            self.print_hard_newline();
         }
     }
 
@@ -300,7 +372,12 @@ impl<'a, const MINIFY: bool> Codegen<'a, MINIFY> {
             self.print_next_indent_as_space = false;
             return;
         }
-        self.code.extend(std::iter::repeat(b'\t').take(self.indent as usize));
+        match self.options.indent_width {
+            Some(width) => self
+                .code
+                .extend(std::iter::repeat(b' ').take(self.indent as usize * width as usize)),
+            None => self.code.extend(std::iter::repeat(b'\t').take(self.indent as usize)),
+        }
     }
 
     #[inline]
@@ -308,7 +385,8 @@ impl<'a, const MINIFY: bool> Codegen<'a, MINIFY> {
         if MINIFY {
             self.needs_semicolon = true;
         } else {
-            self.print_str(";\n");
+            self.print_semicolon();
+            self.print_hard_newline();
         }
     }
 
@@ -506,6 +584,52 @@ impl<'a, const MINIFY: bool> Codegen<'a, MINIFY> {
         self.print_char(self.quote);
     }
 
+    /// The quote for a string literal with the value `s`, which is the configured quote unless
+    /// `s` contains more of it than of the other quote.
+    fn string_quote(&self, s: &str) -> u8 {
+        let (single, double) = s.bytes().fold((0usize, 0usize), |(single, double), b| match b {
+            b'\'' => (single + 1, double),
+            b'"' => (single, double + 1),
+            _ => (single, double),
+        });
+        match self.quote {
+            b'\'' if single > double => b'"',
+            b'"' if double > single => b'\'',
+            quote => quote,
+        }
+    }
+
+    /// Prints the text of a member name at `span` in the source text, if it is a string literal,
+    /// or an identifier with escapes when `is_string` is `false`, which evaluates to `name`.
+    fn print_member_name_as_written(&mut self, span: Span, name: &str, is_string: bool) -> bool {
+        if MINIFY || !self.member_names_as_written {
+            return false;
+        }
+        let Some(text) = self.source_text.get(span.start as usize..span.end as usize) else {
+            return false;
+        };
+        let value = if is_string {
+            let quote = text.as_bytes().first().copied();
+            if text.len() < 2
+                || !matches!(quote, Some(b'\'' | b'"'))
+                || text.as_bytes().last().copied() != quote
+            {
+                return false;
+            }
+            &text[1..text.len() - 1]
+        } else if text.contains('\\') {
+            text
+        } else {
+            return false;
+        };
+        if unescape(value).as_deref() != Some(name) {
+            return false;
+        }
+        self.add_source_mapping(span.start);
+        self.print_str(text);
+        true
+    }
+
     fn add_source_mapping(&mut self, position: u32) {
         if let Some(sourcemap_builder) = self.sourcemap_builder.as_mut() {
             sourcemap_builder.add_source_mapping(&self.code, position, None);
@@ -519,6 +643,55 @@ impl<'a, const MINIFY: bool> Codegen<'a, MINIFY> {
     }
 }
 
+/// The value of the text of a string literal without its quotes, or of an identifier. `None` for
+/// legacy octal escapes and invalid text.
+fn unescape(text: &str) -> Option<String> {
+    let mut units = Vec::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            units.extend(c.encode_utf16(&mut [0; 2]).iter());
+            continue;
+        }
+        let hex = |chars: &mut std::iter::Peekable<std::str::Chars>, len: usize| {
+            let digits = chars.by_ref().take(len).collect::<String>();
+            (digits.len() == len).then(|| u32::from_str_radix(&digits, 16).ok()).flatten()
+        };
+        let code_point = match chars.next()? {
+            'n' => 0x0A,
+            't' => 0x09,
+            'r' => 0x0D,
+            'b' => 0x08,
+            'f' => 0x0C,
+            'v' => 0x0B,
+            '0' if !chars.peek().is_some_and(char::is_ascii_digit) => 0,
+            '0'..='9' => return None,
+            'x' => hex(&mut chars, 2)?,
+            'u' if chars.peek() == Some(&'{') => {
+                chars.next();
+                let digits = chars.by_ref().take_while(|&c| c != '}').collect::<String>();
+                u32::from_str_radix(&digits, 16).ok().filter(|&c| c <= 0x10_FFFF)?
+            }
+            'u' => hex(&mut chars, 4)?,
+            // Line continuations.
+            '\r' => {
+                if chars.peek() == Some(&'\n') {
+                    chars.next();
+                }
+                continue;
+            }
+            '\n' | '\u{2028}' | '\u{2029}' => continue,
+            c => u32::from(c),
+        };
+        match char::from_u32(code_point) {
+            Some(c) => units.extend(c.encode_utf16(&mut [0; 2]).iter()),
+            // Surrogates, which form a character with the next escape.
+            None => units.push(u16::try_from(code_point).ok()?),
+        }
+    }
+    String::from_utf16(&units).ok()
+}
+
 pub(crate) type MoveCommentMap = FxHashMap<u32, Comment>;
 
 // Comment related
@@ -283,7 +283,7 @@ fn test_string() {
     test("let x = '\\v'", "let x = \"\\v\";\n");
     test("let x = '\\n'", "let x = \"\\n\";\n");
     test("let x = '\\''", "let x = \"'\";\n");
-    test("let x = '\\\"'", "let x = \"\\\"\";\n");
+    test("let x = '\\\"'", "let x = '\"';\n");
     test("let x = '\\'\"'", "let x = \"'\\\"\";\n");
     test("let x = '\\\\'", "let x = \"\\\\\";\n");
     test("let x = '\x00'", "let x = \"\\0\";\n");
@@ -36,6 +36,8 @@ fn ts() {
         "class A {constructor(public readonly a: number) {}}",
         "abstract class A {private abstract static m() {}}",
         "abstract class A {private abstract static readonly prop: string}",
+        "class A {m(this: A, a: number): this {}}",
+        "let f: (this: Date) => Date",
     ];
 
     let snapshot = cases.into_iter().fold(String::new(), |mut w, case| {
@@ -53,7 +55,7 @@ fn codegen(source_text: &str) -> String {
     let source_type = SourceType::default().with_typescript(true).with_module(true);
     let ret = Parser::new(&allocator, source_text, source_type).parse();
     CodeGenerator::new()
-        .with_options(CodegenOptions { single_quote: true })
+        .with_options(CodegenOptions { single_quote: true, ..CodegenOptions::default() })
         .build(&ret.program)
         .source_text
 }
@@ -1,3 +1,8 @@
+use oxc_allocator::Allocator;
+use oxc_codegen::{CodeGenerator, CodegenOptions, Newline, WhitespaceRemover};
+use oxc_parser::Parser;
+use oxc_span::SourceType;
+
 use crate::tester::{test, test_minify};
 
 #[test]
@@ -211,3 +216,53 @@ fn equality() {
     test_minify("a, b == c , d", "a,b==c,d;");
     test_minify("(a, b) == (c , d)", "(a,b)==(c,d);");
 }
+
+#[test]
+fn layout_options() {
+    let source_text = "#!/usr/bin/env node\nif (a) { b() }";
+    let allocator = Allocator::default();
+    let ret = Parser::new(&allocator, source_text, SourceType::default()).parse();
+    let options = CodegenOptions {
+        indent_width: Some(4),
+        newline: Newline::CrLf,
+        ..CodegenOptions::default()
+    };
+    let print = |banner| {
+        CodeGenerator::new()
+            .with_options(options)
+            .with_banner(banner)
+            .build(&ret.program)
+            .source_text
+    };
+    assert_eq!(print(""), "#!/usr/bin/env node\nif (a) {\r\n    b();\r\n}\r\n");
+    assert_eq!(
+        print("// a\n// b\n"),
+        "#!/usr/bin/env node\n// a\r\n// b\r\nif (a) {\r\n    b();\r\n}\r\n"
+    );
+    let minified =
+        WhitespaceRemover::new().with_banner("/*! license */").build(&ret.program).source_text;
+    assert_eq!(minified, "#!/usr/bin/env node\n/*! license */\nif(a){b()}");
+}
+
+#[test]
+fn string_quotes() {
+    test(r#"x = 'a"b'"#, "x = 'a\"b';\n");
+    test(r#"x = "a'b""#, "x = \"a'b\";\n");
+    test(r#"x = 'a"b\'c'"#, "x = \"a\\\"b'c\";\n");
+    test_minify(r#"x = "a\"b\"c'""#, "x='a\"b\"c\\'';");
+}
+
+#[test]
+fn member_names_as_written() {
+    let source_text = r#"x = { 'a': 1, "\x62": 2, \u0063: 3, 0x10: 4 }; y = '\x62'"#;
+    let allocator = Allocator::default();
+    let ret = Parser::new(&allocator, source_text, SourceType::default()).parse();
+    let code = CodeGenerator::new().with_source_text(source_text).build(&ret.program).source_text;
+    assert_eq!(
+        code,
+        "x = {\n\t'a': 1,\n\t\"\\x62\": 2,\n\t\\u0063: 3,\n\t0x10: 4\n};\ny = \"b\";\n"
+    );
+    let minified =
+        WhitespaceRemover::new().with_source_text(source_text).build(&ret.program).source_text;
+    assert_eq!(minified, "x={\"a\":1,\"b\":2,c:3,16:4};y=\"b\";");
+}
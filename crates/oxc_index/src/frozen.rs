@@ -0,0 +1,228 @@
+use alloc::{sync::Arc, vec::Vec};
+use core::{cmp, fmt, iter::FusedIterator, marker::PhantomData, ops::Deref, ops::Range};
+
+use crate::{Idx, IndexSlice, IndexVec};
+
+/// An immutable [`IndexVec`] which is cheap to clone and to share between
+/// threads.
+///
+/// The elements live behind an [`Arc`], so cloning only increments a reference
+/// count, and a `FrozenIndexVec` is `Send` and `Sync` whenever `T` is. Tables
+/// which are built once, such as the symbols and scopes of a program, can be
+/// frozen and handed to every worker thread without copying them.
+///
+/// It derefs to [`IndexSlice`], so all of its reading methods are available.
+/// [`FrozenIndexVec::par_chunks`] splits the elements into owned
+/// [`FrozenChunk`]s, which can be moved to threads of their own and still
+/// know the index of every element.
+pub struct FrozenIndexVec<I: Idx, T> {
+    raw: Arc<[T]>,
+    _marker: PhantomData<fn(&I)>,
+}
+
+impl<I: Idx, T> FrozenIndexVec<I, T> {
+    /// Freeze `vec`, see [`IndexVec::freeze`].
+    #[inline]
+    pub fn new(vec: IndexVec<I, T>) -> Self {
+        FrozenIndexVec { raw: vec.raw.into(), _marker: PhantomData }
+    }
+
+    /// The elements as an `IndexSlice`, which is also available through
+    /// `Deref`.
+    #[inline]
+    pub fn as_index_slice(&self) -> &IndexSlice<I, [T]> {
+        IndexSlice::new(&*self.raw)
+    }
+
+    /// Whether `this` and `other` are clones of the same frozen vec.
+    #[inline]
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        Arc::ptr_eq(&this.raw, &other.raw)
+    }
+
+    /// Copy the elements into a new, mutable `IndexVec`.
+    #[inline]
+    pub fn to_index_vec(&self) -> IndexVec<I, T>
+    where
+        T: Clone,
+    {
+        IndexVec::from_vec(self.raw.to_vec())
+    }
+
+    /// Split the elements into [`FrozenChunk`]s of `chunk_size` elements, the
+    /// last of which may be shorter. Every chunk shares the elements of this
+    /// vec, and can be sent to another thread.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `chunk_size` is 0.
+    #[inline]
+    pub fn par_chunks(&self, chunk_size: usize) -> IndexedParallelChunks<I, T> {
+        assert!(chunk_size != 0, "chunk size must be non-zero");
+        IndexedParallelChunks { vec: self.clone(), chunk_size, start: 0 }
+    }
+}
+
+impl<I: Idx, T> Clone for FrozenIndexVec<I, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        FrozenIndexVec { raw: Arc::clone(&self.raw), _marker: PhantomData }
+    }
+}
+
+impl<I: Idx, T> Default for FrozenIndexVec<I, T> {
+    #[inline]
+    fn default() -> Self {
+        FrozenIndexVec::new(IndexVec::new())
+    }
+}
+
+impl<I: Idx, T> Deref for FrozenIndexVec<I, T> {
+    type Target = IndexSlice<I, [T]>;
+
+    #[inline]
+    fn deref(&self) -> &IndexSlice<I, [T]> {
+        self.as_index_slice()
+    }
+}
+
+impl<I: Idx, T> AsRef<IndexSlice<I, [T]>> for FrozenIndexVec<I, T> {
+    #[inline]
+    fn as_ref(&self) -> &IndexSlice<I, [T]> {
+        self.as_index_slice()
+    }
+}
+
+impl<I: Idx, T> From<IndexVec<I, T>> for FrozenIndexVec<I, T> {
+    #[inline]
+    fn from(vec: IndexVec<I, T>) -> Self {
+        FrozenIndexVec::new(vec)
+    }
+}
+
+impl<I: Idx, T> From<Vec<T>> for FrozenIndexVec<I, T> {
+    #[inline]
+    fn from(vec: Vec<T>) -> Self {
+        FrozenIndexVec::new(IndexVec::from_vec(vec))
+    }
+}
+
+impl<I: Idx, T: fmt::Debug> fmt::Debug for FrozenIndexVec<I, T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&*self.raw, fmt)
+    }
+}
+
+/// Iterator returned by [`FrozenIndexVec::par_chunks`].
+#[derive(Clone)]
+pub struct IndexedParallelChunks<I: Idx, T> {
+    vec: FrozenIndexVec<I, T>,
+    chunk_size: usize,
+    start: usize,
+}
+
+impl<I: Idx, T> Iterator for IndexedParallelChunks<I, T> {
+    type Item = FrozenChunk<I, T>;
+
+    fn next(&mut self) -> Option<FrozenChunk<I, T>> {
+        if self.start >= self.vec.len() {
+            return None;
+        }
+        let end = cmp::min(self.start.saturating_add(self.chunk_size), self.vec.len());
+        let range = self.start..end;
+        self.start = end;
+        Some(FrozenChunk { vec: self.vec.clone(), range })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (self.vec.len() - self.start).div_ceil(self.chunk_size);
+        (len, Some(len))
+    }
+}
+
+impl<I: Idx, T> ExactSizeIterator for IndexedParallelChunks<I, T> {}
+
+impl<I: Idx, T> FusedIterator for IndexedParallelChunks<I, T> {}
+
+/// A range of the elements of a [`FrozenIndexVec`], see
+/// [`FrozenIndexVec::par_chunks`].
+///
+/// A chunk keeps the whole vec alive, so it is `'static` if `T` is, and can be
+/// moved to another thread. Its elements are identified by their indices in
+/// the whole vec.
+#[derive(Clone)]
+pub struct FrozenChunk<I: Idx, T> {
+    vec: FrozenIndexVec<I, T>,
+    range: Range<usize>,
+}
+
+impl<I: Idx, T> FrozenChunk<I, T> {
+    /// The index of the first element of the chunk.
+    #[inline]
+    pub fn start(&self) -> I {
+        I::from_usize(self.range.start)
+    }
+
+    /// The index after the last element of the chunk.
+    #[inline]
+    pub fn end(&self) -> I {
+        I::from_usize(self.range.end)
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.range.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.range.is_empty()
+    }
+
+    /// Whether `index` is one of the elements of the chunk.
+    #[inline]
+    pub fn contains(&self, index: I) -> bool {
+        self.range.contains(&index.index())
+    }
+
+    /// The element at `index` of the whole vec, if it is in the chunk.
+    #[inline]
+    pub fn get(&self, index: I) -> Option<&T> {
+        self.contains(index).then(|| &self.vec.raw[index.index()])
+    }
+
+    /// The elements of the chunk, indexed from 0.
+    #[inline]
+    pub fn as_raw_slice(&self) -> &[T] {
+        &self.vec.raw[self.range.clone()]
+    }
+
+    /// The frozen vec this is a chunk of.
+    #[inline]
+    pub fn frozen(&self) -> &FrozenIndexVec<I, T> {
+        &self.vec
+    }
+
+    /// Iterate over the indices of the chunk in the whole vec.
+    #[inline]
+    pub fn indices(&self) -> impl ExactSizeIterator<Item = I> {
+        self.range.clone().map(I::from_usize)
+    }
+
+    /// Iterate over the elements of the chunk and their indices in the whole
+    /// vec.
+    #[inline]
+    pub fn iter_enumerated(&self) -> impl ExactSizeIterator<Item = (I, &T)> + '_ {
+        self.indices().zip(self.as_raw_slice())
+    }
+}
+
+impl<I: Idx, T: fmt::Debug> fmt::Debug for FrozenChunk<I, T> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("FrozenChunk")
+            .field("start", &self.start())
+            .field("elements", &self.as_raw_slice())
+            .finish()
+    }
+}
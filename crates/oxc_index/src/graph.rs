@@ -0,0 +1,218 @@
+use alloc::{collections::BinaryHeap, vec, vec::Vec};
+use core::{cmp::Reverse, fmt, mem, ops::Range};
+
+use crate::{Idx, IndexVec};
+
+/// A directed graph whose nodes and edges are stored in [`IndexVec`]s, and
+/// identified by the index types `N` and `E`.
+///
+/// Nodes carry a weight of type `T` and edges a weight of type `W`. Both
+/// default to `()` for graphs which only need the structure, such as a module
+/// graph whose data lives in other `IndexVec`s indexed by the same `N`.
+///
+/// Nodes and edges can not be removed, so their indices stay valid for the
+/// lifetime of the graph.
+#[derive(Clone)]
+pub struct IndexGraph<N: Idx, E: Idx, T = (), W = ()> {
+    nodes: IndexVec<N, Node<E, T>>,
+    edges: IndexVec<E, Edge<N, W>>,
+}
+
+#[derive(Clone)]
+struct Node<E, T> {
+    weight: T,
+    outgoing: Vec<E>,
+    incoming: Vec<E>,
+}
+
+#[derive(Clone)]
+struct Edge<N, W> {
+    weight: W,
+    source: N,
+    target: N,
+}
+
+/// Error of [`IndexGraph::topological_sort`], with a node which is part of a
+/// cycle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cycle<N>(pub N);
+
+impl<N: Idx, E: Idx, T, W> IndexGraph<N, E, T, W> {
+    /// Construct an empty graph.
+    #[inline]
+    pub fn new() -> Self {
+        Self { nodes: IndexVec::new(), edges: IndexVec::new() }
+    }
+
+    /// Construct an empty graph with room for `nodes` nodes and `edges` edges.
+    #[inline]
+    pub fn with_capacity(nodes: usize, edges: usize) -> Self {
+        Self { nodes: IndexVec::with_capacity(nodes), edges: IndexVec::with_capacity(edges) }
+    }
+
+    /// Add a node, and return its index.
+    pub fn add_node(&mut self, weight: T) -> N {
+        self.nodes.push(Node { weight, outgoing: Vec::new(), incoming: Vec::new() })
+    }
+
+    /// Add an edge from `source` to `target`, and return its index. Parallel
+    /// edges and self loops are allowed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `source` or `target` is not a node of the graph.
+    pub fn add_edge(&mut self, source: N, target: N, weight: W) -> E {
+        assert!(target.index() < self.nodes.len(), "target is not a node of the graph");
+        let edge = self.edges.push(Edge { weight, source, target });
+        self.nodes[source].outgoing.push(edge);
+        self.nodes[target].incoming.push(edge);
+        edge
+    }
+
+    #[inline]
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    #[inline]
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// Iterate over the indices of all nodes, in the order they were added.
+    #[inline]
+    pub fn nodes(&self) -> impl ExactSizeIterator<Item = N> {
+        indices(0..self.nodes.len())
+    }
+
+    /// Iterate over the indices of all edges, in the order they were added.
+    #[inline]
+    pub fn edges(&self) -> impl ExactSizeIterator<Item = E> {
+        indices(0..self.edges.len())
+    }
+
+    #[inline]
+    pub fn node_weight(&self, node: N) -> &T {
+        &self.nodes[node].weight
+    }
+
+    #[inline]
+    pub fn node_weight_mut(&mut self, node: N) -> &mut T {
+        &mut self.nodes[node].weight
+    }
+
+    #[inline]
+    pub fn edge_weight(&self, edge: E) -> &W {
+        &self.edges[edge].weight
+    }
+
+    #[inline]
+    pub fn edge_weight_mut(&mut self, edge: E) -> &mut W {
+        &mut self.edges[edge].weight
+    }
+
+    /// The source and target nodes of `edge`.
+    #[inline]
+    pub fn edge_endpoints(&self, edge: E) -> (N, N) {
+        let edge = &self.edges[edge];
+        (edge.source, edge.target)
+    }
+
+    /// Iterate over the edges starting at `node`, in the order they were added.
+    #[inline]
+    pub fn outgoing_edges(&self, node: N) -> impl Iterator<Item = E> + '_ {
+        self.nodes[node].outgoing.iter().copied()
+    }
+
+    /// Iterate over the edges ending at `node`, in the order they were added.
+    #[inline]
+    pub fn incoming_edges(&self, node: N) -> impl Iterator<Item = E> + '_ {
+        self.nodes[node].incoming.iter().copied()
+    }
+
+    /// Iterate over the targets of the edges starting at `node`. A node is
+    /// yielded once for every edge to it.
+    pub fn successors(&self, node: N) -> impl Iterator<Item = N> + '_ {
+        self.outgoing_edges(node).map(|edge| self.edges[edge].target)
+    }
+
+    /// Iterate over the sources of the edges ending at `node`. A node is
+    /// yielded once for every edge from it.
+    pub fn predecessors(&self, node: N) -> impl Iterator<Item = N> + '_ {
+        self.incoming_edges(node).map(|edge| self.edges[edge].source)
+    }
+
+    /// Sort the nodes so that every node comes before the targets of its
+    /// edges. Of the nodes which could come next, the one added first is
+    /// taken, so the result is deterministic.
+    ///
+    /// # Errors
+    ///
+    /// Returns a node on a cycle if the graph is not acyclic, including self
+    /// loops.
+    pub fn topological_sort(&self) -> Result<Vec<N>, Cycle<N>> {
+        // Kahn's algorithm, taking the smallest node without remaining incoming
+        // edges first.
+        let mut in_degrees =
+            self.nodes.iter().map(|node| node.incoming.len()).collect::<IndexVec<N, _>>();
+        let mut ready = self
+            .nodes()
+            .filter(|node| in_degrees[*node] == 0)
+            .map(Reverse)
+            .collect::<BinaryHeap<_>>();
+        let mut sorted = Vec::with_capacity(self.nodes.len());
+        while let Some(Reverse(node)) = ready.pop() {
+            sorted.push(node);
+            for successor in self.successors(node) {
+                in_degrees[successor] -= 1;
+                if in_degrees[successor] == 0 {
+                    ready.push(Reverse(successor));
+                }
+            }
+        }
+        let Some(mut node) = self.nodes().find(|node| in_degrees[*node] > 0) else {
+            return Ok(sorted);
+        };
+        // Every remaining node has a remaining predecessor, so following them
+        // backwards must arrive at a node for the second time, which is on a
+        // cycle.
+        let mut visited = vec![false; self.nodes.len()];
+        visited[node.index()] = true;
+        while let Some(predecessor) =
+            self.predecessors(node).find(|predecessor| in_degrees[*predecessor] > 0)
+        {
+            node = predecessor;
+            if mem::replace(&mut visited[node.index()], true) {
+                break;
+            }
+        }
+        Err(Cycle(node))
+    }
+}
+
+impl<N: Idx, E: Idx, T, W> Default for IndexGraph<N, E, T, W> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<N: Idx, E: Idx, T: fmt::Debug, W: fmt::Debug> fmt::Debug for IndexGraph<N, E, T, W> {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.debug_struct("IndexGraph")
+            .field("nodes", &self.nodes.iter().map(|node| &node.weight).collect::<Vec<_>>())
+            .field(
+                "edges",
+                &self
+                    .edges
+                    .iter()
+                    .map(|edge| (edge.source, edge.target, &edge.weight))
+                    .collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+fn indices<I: Idx>(range: Range<usize>) -> impl ExactSizeIterator<Item = I> {
+    range.map(I::from_usize)
+}
@@ -51,6 +51,7 @@ impl<I: Idx, T: fmt::Debug + ?Sized> fmt::Debug for IndexSlice<I, T> {
         fmt::Debug::fmt(&self.raw, fmt)
     }
 }
+#[cfg(feature = "alloc")]
 /// `IndexBox<I, [T]>`: An alias for indexed boxed slice.
 pub type IndexBox<I, T> = Box<IndexSlice<I, T>>;
 
@@ -86,6 +87,7 @@ impl<I: Idx, T> IndexSlice<I, [T]> {
         unsafe { &mut *(s as *mut [T] as *mut Self) }
     }
 
+    #[cfg(feature = "alloc")]
     /// Copies `self` into a new `IndexVec`.
     #[inline]
     pub fn to_vec(&self) -> IndexVec<I, T>
@@ -95,6 +97,7 @@ impl<I: Idx, T> IndexSlice<I, [T]> {
         IndexVec::from_vec(self.raw.to_vec())
     }
 
+    #[cfg(feature = "alloc")]
     /// Converts `self` into a vector without clones or allocation.
     ///
     /// The resulting vector can be converted back into a box via
@@ -207,6 +210,7 @@ impl<I: Idx, T> IndexSlice<I, [T]> {
     }
 
     /// Forwards to the slice's `sort` implementation.
+    #[cfg(feature = "alloc")]
     #[inline]
     pub fn sort(&mut self)
     where
@@ -216,18 +220,21 @@ impl<I: Idx, T> IndexSlice<I, [T]> {
     }
 
     /// Forwards to the slice's `sort_by` implementation.
+    #[cfg(feature = "alloc")]
     #[inline]
     pub fn sort_by<F: FnMut(&T, &T) -> core::cmp::Ordering>(&mut self, compare: F) {
         self.raw.sort_by(compare);
     }
 
     /// Forwards to the slice's `sort_by_key` implementation.
+    #[cfg(feature = "alloc")]
     #[inline]
     pub fn sort_by_key<F: FnMut(&T) -> K, K: Ord>(&mut self, f: F) {
         self.raw.sort_by_key(f);
     }
 
     /// Forwards to the slice's `sort_by_cached_key` implementation.
+    #[cfg(feature = "alloc")]
     #[inline]
     pub fn sort_by_cached_key<F: FnMut(&T) -> K, K: Ord>(&mut self, f: F) {
         self.raw.sort_by_cached_key(f);
@@ -728,6 +735,7 @@ impl<I: Idx, T: core::hash::Hash> core::hash::Hash for IndexSlice<I, [T]> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<I: Idx, T> alloc::borrow::ToOwned for IndexSlice<I, [T]>
 where
     T: Clone,
@@ -788,6 +796,7 @@ impl<'a, I: Idx, T> From<&'a mut [T]> for &'a mut IndexSlice<I, [T]> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<I: Idx, T> From<Box<[T]>> for Box<IndexSlice<I, [T]>> {
     #[inline]
     fn from(b: Box<[T]>) -> Self {
@@ -811,6 +820,7 @@ impl<I: Idx, A> AsMut<[A]> for IndexSlice<I, [A]> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<I: Idx, T: Clone> Clone for Box<IndexSlice<I, [T]>> {
     #[inline]
     fn clone(&self) -> Self {
@@ -819,6 +829,7 @@ impl<I: Idx, T: Clone> Clone for Box<IndexSlice<I, [T]>> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<I: Idx, A> FromIterator<A> for Box<IndexSlice<I, [A]>> {
     #[inline]
     fn from_iter<T: IntoIterator<Item = A>>(iter: T) -> Self {
@@ -826,6 +837,7 @@ impl<I: Idx, A> FromIterator<A> for Box<IndexSlice<I, [A]>> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<I: Idx, A> IntoIterator for Box<IndexSlice<I, [A]>> {
     type IntoIter = vec::IntoIter<A>;
     type Item = A;
@@ -837,6 +849,7 @@ impl<I: Idx, A> IntoIterator for Box<IndexSlice<I, [A]>> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<I: Idx, A> Default for Box<IndexSlice<I, [A]>> {
     #[inline(always)]
     fn default() -> Self {
@@ -106,7 +106,10 @@
 //!
 //! #### Does it support no_std?
 //!
-//! Yes, although it uses `extern crate alloc;`, of course.
+//! Yes. [`IndexVec`], [`IndexBox`], [`IndexGraph`] and [`FrozenIndexVec`] need
+//! `alloc`, so they are behind the `alloc` feature, which is enabled by default.
+//! Without it, [`Idx`], [`IndexSlice`] and [`define_index_type!`] are still
+//! available, as they only depend on `core`.
 //!
 //! #### Does it support serde?
 //!
@@ -131,29 +134,43 @@
 #![allow(clippy::inline_always)]
 #![allow(clippy::partialeq_ne_impl)]
 #![no_std]
+#[cfg(feature = "alloc")]
 extern crate alloc;
 
+#[cfg(feature = "alloc")]
 use alloc::{
     borrow::{Cow, ToOwned},
     boxed::Box,
     vec,
     vec::Vec,
 };
+#[cfg(feature = "alloc")]
 use core::{
     borrow::{Borrow, BorrowMut},
-    fmt,
-    fmt::Debug,
-    hash::Hash,
-    iter::{self, FromIterator},
-    marker::PhantomData,
-    ops::Range,
-    slice,
+    iter::FromIterator,
 };
+use core::{fmt, fmt::Debug, hash::Hash, iter, marker::PhantomData, ops::Range, slice};
+#[cfg(feature = "alloc")]
+mod frozen;
+#[cfg(feature = "alloc")]
+mod graph;
 mod idxslice;
 mod indexing;
-pub use idxslice::{IndexBox, IndexSlice};
+#[cfg(feature = "alloc")]
+pub use frozen::{FrozenChunk, FrozenIndexVec, IndexedParallelChunks};
+#[cfg(feature = "alloc")]
+pub use graph::{Cycle, IndexGraph};
+#[cfg(feature = "alloc")]
+pub use idxslice::IndexBox;
+pub use idxslice::IndexSlice;
 pub use indexing::{IdxRangeBounds, IdxSliceIndex};
 
+/// Used by [`index_vec!`] and [`index_box!`], which can't rely on `vec!` being in scope in
+/// `no_std` crates.
+#[cfg(feature = "alloc")]
+#[doc(hidden)]
+pub use alloc::vec as __vec;
+
 #[macro_use]
 mod macros;
 
@@ -169,13 +186,17 @@ mod macros;
 ///
 /// The way overflow is handled is up to the implementation of `Idx`, but it's
 /// generally panicking, unless it was turned off via the
-/// `DISABLE_MAX_INDEX_CHECK` option in [`define_index_type!`]. If you need more
-/// subtle handling than this, then you're on your own (or, well, either handle
-/// it earlier, or pick a bigger index type).
+/// `DISABLE_MAX_INDEX_CHECK` option in [`define_index_type!`]. The arithmetic
+/// operators of generated types can be made to always panic, or to wrap, with
+/// the `ARITHMETIC` option, and `checked_add`/`checked_sub` return `None`
+/// instead. If you need more subtle handling than this, then you're on your own
+/// (or, well, either handle it earlier, or pick a bigger index type).
 ///
 /// Note: I'm open for suggestions on how to handle this case, but do not want
 /// the typical cases (E.g. Idx is a newtyped `usize` or `u32`), to become more
 /// complex.
+///
+/// Index types can also belong to a domain, see [`IdxDomain`].
 pub trait Idx: Copy + 'static + Ord + Debug + Hash {
     /// Construct an Index from a `usize`. This is equivalent to `From<usize>`.
     ///
@@ -186,22 +207,111 @@ pub trait Idx: Copy + 'static + Ord + Debug + Hash {
 
     /// Get the underlying index. This is equivalent to `Into<usize>`
     fn index(self) -> usize;
+
+    /// Convert to an index type of any domain.
+    ///
+    /// This is never needed to access a table with its own index type, use it
+    /// only where an index of one domain is known to be valid in another.
+    #[inline]
+    fn cast_cross_domain<J: Idx>(self) -> J {
+        J::from_usize(self.index())
+    }
+}
+
+/// An [`Idx`] which belongs to a domain, a marker type naming the table it
+/// points into.
+///
+/// Index types generated by [`define_index_type!`] implement this, and are
+/// their own domain unless the `DOMAIN` option says otherwise. Generic code
+/// should convert between index types with [`IdxDomain::cast`], which only
+/// compiles when both types share a domain, so e.g. a `ScopeId` can't be passed
+/// through a generic helper into a table of symbols. Intentional conversions
+/// across domains have to be spelled out with [`Idx::cast_cross_domain`].
+pub trait IdxDomain: Idx {
+    /// Marker type for the table this index points into.
+    type Domain: ?Sized + 'static;
+
+    /// Convert to another index type of the same domain.
+    #[inline]
+    fn cast<J: IdxDomain<Domain = Self::Domain>>(self) -> J {
+        J::from_usize(self.index())
+    }
+}
+
+/// Overflow policy of the arithmetic operators of an index type, set with the
+/// `ARITHMETIC` option of [`define_index_type!`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndexArithmetic {
+    /// Always panic if the result is negative or larger than `MAX_INDEX`.
+    Checked,
+    /// Wrap around in the raw type.
+    Wrapping,
+    /// Panic unless `DISABLE_MAX_INDEX_CHECK` is set.
+    Panicking,
+}
+
+/// Lowercase names of the [`IndexArithmetic`] variants, so that the
+/// `ARITHMETIC` option can be written as `ARITHMETIC = checked;`.
+#[doc(hidden)]
+#[allow(non_upper_case_globals)]
+pub mod __index_arithmetic {
+    use super::IndexArithmetic;
+
+    pub const checked: IndexArithmetic = IndexArithmetic::Checked;
+    pub const wrapping: IndexArithmetic = IndexArithmetic::Wrapping;
+    pub const panicking: IndexArithmetic = IndexArithmetic::Panicking;
+}
+
+/// Assert at compile time that two index types share a [`IdxDomain::Domain`].
+///
+/// ```rust,ignore
+/// oxc_index::assert_same_index_domain!(NodeIdx, LocalNodeIdx);
+/// ```
+#[macro_export]
+macro_rules! assert_same_index_domain {
+    ($a:ty, $b:ty $(,)?) => {
+        const _: fn() = || {
+            fn assert_same_index_domain<
+                A: $crate::IdxDomain,
+                B: $crate::IdxDomain<Domain = A::Domain>,
+            >() {
+            }
+            assert_same_index_domain::<$a, $b>();
+        };
+    };
+}
+
+/// Assert at compile time that an index type belongs to the given domain.
+///
+/// ```rust,ignore
+/// oxc_index::assert_index_domain!(LocalNodeIdx, NodeIdx);
+/// ```
+#[macro_export]
+macro_rules! assert_index_domain {
+    ($idx:ty, $domain:ty $(,)?) => {
+        const _: fn() = || {
+            fn assert_index_domain<I: $crate::IdxDomain<Domain = $domain>>() {}
+            assert_index_domain::<$idx>();
+        };
+    };
 }
 
 /// A macro equivalent to the stdlib's `vec![]`, but producing an `IndexVec`.
+#[cfg(feature = "alloc")]
 #[macro_export]
 macro_rules! index_vec {
     ($($tokens:tt)*) => {
-        $crate::IndexVec::from_vec(vec![$($tokens)*])
+        $crate::IndexVec::from_vec($crate::__vec![$($tokens)*])
     }
 }
 
 /// A macro similar to the stdlib's `vec![]`, but producing an
 /// `Box<IndexSlice<I, [T]>>` (That is, an `IndexBox<I, [T]>`).
+#[cfg(feature = "alloc")]
 #[macro_export]
 macro_rules! index_box {
     ($($tokens:tt)*) => {
-        $crate::IndexVec::from_vec(vec![$($tokens)*]).into_boxed_slice()
+        $crate::IndexVec::from_vec($crate::__vec![$($tokens)*]).into_boxed_slice()
     }
 }
 
@@ -222,6 +332,7 @@ macro_rules! index_box {
 /// - [`IndexVec::next_idx`], [`IndexSlice::last_idx`] give the next and most
 ///   recent index returned by `push`.
 /// - [`IndexVec::push`] returns the index the item was inserted at.
+#[cfg(feature = "alloc")]
 #[derive(PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct IndexVec<I: Idx, T> {
     /// Our wrapped Vec.
@@ -231,8 +342,10 @@ pub struct IndexVec<I: Idx, T> {
 
 // SAFETY: Whether `IndexVec` is `Send` depends only on the data,
 // not the phantom data.
+#[cfg(feature = "alloc")]
 unsafe impl<I: Idx, T> Send for IndexVec<I, T> where T: Send {}
 
+#[cfg(feature = "alloc")]
 impl<I: Idx, T: fmt::Debug> fmt::Debug for IndexVec<I, T> {
     fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
         fmt::Debug::fmt(&self.raw, fmt)
@@ -240,6 +353,7 @@ impl<I: Idx, T: fmt::Debug> fmt::Debug for IndexVec<I, T> {
 }
 type Enumerated<Iter, I, T> = iter::Map<iter::Enumerate<Iter>, fn((usize, T)) -> (I, T)>;
 
+#[cfg(feature = "alloc")]
 impl<I: Idx, T> IndexVec<I, T> {
     /// Construct a new IndexVec.
     #[inline]
@@ -264,6 +378,22 @@ impl<I: Idx, T> IndexVec<I, T> {
         IndexVec { raw: Vec::with_capacity(capacity), _marker: PhantomData }
     }
 
+    /// Re-key this vec by another index type of the same domain.
+    #[inline]
+    pub fn cast_index<J: IdxDomain<Domain = I::Domain>>(self) -> IndexVec<J, T>
+    where
+        I: IdxDomain,
+    {
+        IndexVec::from_vec(self.raw)
+    }
+
+    /// Re-key this vec by an index type of any domain. See
+    /// [`Idx::cast_cross_domain`].
+    #[inline]
+    pub fn cast_index_cross_domain<J: Idx>(self) -> IndexVec<J, T> {
+        IndexVec::from_vec(self.raw)
+    }
+
     /// Similar to `self.into_iter().enumerate()` but with indices of `I` and
     /// not `usize`.
     #[inline(always)]
@@ -352,6 +482,13 @@ impl<I: Idx, T> IndexVec<I, T> {
         unsafe { Box::from_raw(Box::into_raw(b) as *mut IndexSlice<I, [T]>) }
     }
 
+    /// Converts the vector into an immutable [`FrozenIndexVec`], which is cheap
+    /// to clone and can be shared between threads.
+    #[inline]
+    pub fn freeze(self) -> FrozenIndexVec<I, T> {
+        FrozenIndexVec::new(self)
+    }
+
     /// Return an iterator that removes the items from the requested range. See
     /// [`Vec::drain`].
     ///
@@ -502,6 +639,7 @@ impl<I: Idx, T> IndexVec<I, T> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<I: Idx, T> Default for IndexVec<I, T> {
     #[inline]
     fn default() -> Self {
@@ -509,6 +647,7 @@ impl<I: Idx, T> Default for IndexVec<I, T> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<I: Idx, T> Extend<T> for IndexVec<I, T> {
     #[inline]
     fn extend<J: IntoIterator<Item = T>>(&mut self, iter: J) {
@@ -516,6 +655,7 @@ impl<I: Idx, T> Extend<T> for IndexVec<I, T> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<'a, I: Idx, T: 'a + Copy> Extend<&'a T> for IndexVec<I, T> {
     #[inline]
     fn extend<J: IntoIterator<Item = &'a T>>(&mut self, iter: J) {
@@ -523,6 +663,7 @@ impl<'a, I: Idx, T: 'a + Copy> Extend<&'a T> for IndexVec<I, T> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<I: Idx, T> FromIterator<T> for IndexVec<I, T> {
     #[inline]
     fn from_iter<J>(iter: J) -> Self
@@ -533,6 +674,7 @@ impl<I: Idx, T> FromIterator<T> for IndexVec<I, T> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<I: Idx, T> IntoIterator for IndexVec<I, T> {
     type IntoIter = vec::IntoIter<T>;
     type Item = T;
@@ -543,6 +685,7 @@ impl<I: Idx, T> IntoIterator for IndexVec<I, T> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<'a, I: Idx, T> IntoIterator for &'a IndexVec<I, T> {
     type IntoIter = slice::Iter<'a, T>;
     type Item = &'a T;
@@ -553,6 +696,7 @@ impl<'a, I: Idx, T> IntoIterator for &'a IndexVec<I, T> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<'a, I: Idx, T> IntoIterator for &'a mut IndexVec<I, T> {
     type IntoIter = slice::IterMut<'a, T>;
     type Item = &'a mut T;
@@ -563,6 +707,7 @@ impl<'a, I: Idx, T> IntoIterator for &'a mut IndexVec<I, T> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<I: Idx, T> From<IndexVec<I, T>> for Box<IndexSlice<I, [T]>> {
     #[inline]
     fn from(src: IndexVec<I, T>) -> Self {
@@ -570,6 +715,7 @@ impl<I: Idx, T> From<IndexVec<I, T>> for Box<IndexSlice<I, [T]>> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<I: Idx, T> From<Box<IndexSlice<I, [T]>>> for IndexVec<I, T> {
     #[inline]
     fn from(src: Box<IndexSlice<I, [T]>>) -> Self {
@@ -577,6 +723,7 @@ impl<I: Idx, T> From<Box<IndexSlice<I, [T]>>> for IndexVec<I, T> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<'a, I: Idx, T> From<Cow<'a, IndexSlice<I, [T]>>> for IndexVec<I, T>
 where
     IndexSlice<I, [T]>: ToOwned<Owned = IndexVec<I, T>>,
@@ -587,12 +734,14 @@ where
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<'a, I: Idx, T: Clone> From<&'a IndexSlice<I, [T]>> for IndexVec<I, T> {
     #[inline]
     fn from(src: &'a IndexSlice<I, [T]>) -> Self {
         src.to_owned()
     }
 }
+#[cfg(feature = "alloc")]
 impl<'a, I: Idx, T: Clone> From<&'a mut IndexSlice<I, [T]>> for IndexVec<I, T> {
     #[inline]
     fn from(src: &'a mut IndexSlice<I, [T]>) -> Self {
@@ -600,6 +749,7 @@ impl<'a, I: Idx, T: Clone> From<&'a mut IndexSlice<I, [T]>> for IndexVec<I, T> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<I: Idx, T> From<Vec<T>> for IndexVec<I, T> {
     #[inline]
     fn from(v: Vec<T>) -> Self {
@@ -607,6 +757,7 @@ impl<I: Idx, T> From<Vec<T>> for IndexVec<I, T> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<I: Idx, T: Clone> Clone for IndexVec<I, T> {
     #[inline]
     fn clone(&self) -> Self {
@@ -619,6 +770,7 @@ impl<I: Idx, T: Clone> Clone for IndexVec<I, T> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<I: Idx, A> AsRef<[A]> for IndexVec<I, A> {
     #[inline]
     fn as_ref(&self) -> &[A] {
@@ -626,6 +778,7 @@ impl<I: Idx, A> AsRef<[A]> for IndexVec<I, A> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<I: Idx, A> AsMut<[A]> for IndexVec<I, A> {
     #[inline]
     fn as_mut(&mut self) -> &mut [A] {
@@ -633,6 +786,7 @@ impl<I: Idx, A> AsMut<[A]> for IndexVec<I, A> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<I: Idx, A> AsRef<IndexSlice<I, [A]>> for IndexVec<I, A> {
     #[inline]
     fn as_ref(&self) -> &IndexSlice<I, [A]> {
@@ -640,6 +794,7 @@ impl<I: Idx, A> AsRef<IndexSlice<I, [A]>> for IndexVec<I, A> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<I: Idx, A> AsMut<IndexSlice<I, [A]>> for IndexVec<I, A> {
     #[inline]
     fn as_mut(&mut self) -> &mut IndexSlice<I, [A]> {
@@ -647,6 +802,7 @@ impl<I: Idx, A> AsMut<IndexSlice<I, [A]>> for IndexVec<I, A> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<I: Idx, A> core::ops::Deref for IndexVec<I, A> {
     type Target = IndexSlice<I, [A]>;
 
@@ -656,6 +812,7 @@ impl<I: Idx, A> core::ops::Deref for IndexVec<I, A> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<I: Idx, A> core::ops::DerefMut for IndexVec<I, A> {
     #[inline]
     fn deref_mut(&mut self) -> &mut IndexSlice<I, [A]> {
@@ -663,6 +820,7 @@ impl<I: Idx, A> core::ops::DerefMut for IndexVec<I, A> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<I: Idx, T> Borrow<IndexSlice<I, [T]>> for IndexVec<I, T> {
     #[inline]
     fn borrow(&self) -> &IndexSlice<I, [T]> {
@@ -670,6 +828,7 @@ impl<I: Idx, T> Borrow<IndexSlice<I, [T]>> for IndexVec<I, T> {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl<I: Idx, T> BorrowMut<IndexSlice<I, [T]>> for IndexVec<I, T> {
     #[inline]
     fn borrow_mut(&mut self) -> &mut IndexSlice<I, [T]> {
@@ -715,20 +874,29 @@ macro_rules! impl_partialeq2 {
     };
 }
 
+#[cfg(feature = "alloc")]
 impl_partialeq! { IndexVec<I, A>, Vec<B> }
+#[cfg(feature = "alloc")]
 impl_partialeq! { IndexVec<I, A>, &'b [B] }
+#[cfg(feature = "alloc")]
 impl_partialeq! { IndexVec<I, A>, &'b mut [B] }
 
+#[cfg(feature = "alloc")]
 impl_partialeq2! { IndexVec<I, A>, &'b IndexSlice<J, [B]> }
+#[cfg(feature = "alloc")]
 impl_partialeq2! { IndexVec<I, A>, &'b mut IndexSlice<J, [B]> }
 
+#[cfg(feature = "alloc")]
 impl_partialeq! { &'a IndexSlice<I, [A]>, Vec<B> }
+#[cfg(feature = "alloc")]
 impl_partialeq! { &'a mut IndexSlice<I, [A]>, Vec<B> }
 
 impl_partialeq! { IndexSlice<I, [A]>, &'b [B] }
 impl_partialeq! { IndexSlice<I, [A]>, &'b mut [B] }
 
+#[cfg(feature = "alloc")]
 impl_partialeq2! { &'a IndexSlice<I, [A]>, IndexVec<J, B> }
+#[cfg(feature = "alloc")]
 impl_partialeq2! { &'a mut IndexSlice<I, [A]>, IndexVec<J, B> }
 
 impl_partialeq2! { IndexSlice<I, [A]>, &'a IndexSlice<J, [B]> }
@@ -736,7 +904,9 @@ impl_partialeq2! { IndexSlice<I, [A]>, &'a mut IndexSlice<J, [B]> }
 
 macro_rules! array_impls {
     ($($N: expr)+) => {$(
+        #[cfg(feature = "alloc")]
         impl_partialeq! { IndexVec<I, A>, [B; $N] }
+        #[cfg(feature = "alloc")]
         impl_partialeq! { IndexVec<I, A>, &'b [B; $N] }
         impl_partialeq! { IndexSlice<I, [A]>, [B; $N] }
         impl_partialeq! { IndexSlice<I, [A]>, &'b [B; $N] }
@@ -759,6 +929,13 @@ pub fn __max_check_fail(u: usize, max: usize) -> ! {
     panic!("index_vec index overflow: {} is outside the range [0, {})", u, max,)
 }
 
+#[inline(never)]
+#[cold]
+#[doc(hidden)]
+pub fn __arithmetic_overflow_fail(op: &str, lhs: usize, rhs: usize, max: usize) -> ! {
+    panic!("index_vec arithmetic overflow: {lhs} {op} {rhs} is outside the range [0, {max}]")
+}
+
 #[cfg(feature = "serialize")]
 impl<I: Idx, T: serde::ser::Serialize> serde::ser::Serialize for IndexVec<I, T> {
     fn serialize<S: serde::ser::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
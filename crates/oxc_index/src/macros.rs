@@ -133,6 +133,52 @@
 /// # }
 /// ```
 ///
+/// #### `DOMAIN = <type>;`
+///
+/// Set the [`IdxDomain::Domain`](crate::IdxDomain::Domain) of the index type,
+/// so that it can be converted to and from other index types of the same domain
+/// with [`IdxDomain::cast`](crate::IdxDomain::cast). If provided, it must be the
+/// first option.
+/// By default, the index type is its own domain.
+///
+/// ```rust
+/// index_vec::define_index_type! {
+///     struct NodeIdx = u32;
+/// }
+/// index_vec::define_index_type! {
+///     struct ChildIdx = u32;
+///     DOMAIN = NodeIdx;
+/// }
+/// index_vec::assert_same_index_domain!(NodeIdx, ChildIdx);
+/// ```
+///
+/// #### `ARITHMETIC = checked | wrapping | panicking;`
+///
+/// How the `+` and `-` operators handle results which are negative or larger
+/// than `MAX_INDEX`:
+///
+/// - `panicking` (the default): the result is constructed with `from_usize`,
+///   so it asserts unless `DISABLE_MAX_INDEX_CHECK` is set.
+/// - `checked`: always asserts, in release builds and even if
+///   `DISABLE_MAX_INDEX_CHECK` is set. Use this for indices into tables where
+///   a wrapped index would point at a valid but wrong entry.
+/// - `wrapping`: wraps around in the raw type, like `u32::wrapping_add`.
+///
+/// Regardless of this option, the generated type has `checked_add`,
+/// `checked_sub`, `saturating_add` and `saturating_sub` methods.
+///
+/// ```rust
+/// index_vec::define_index_type! {
+///     struct FooIdx = u32;
+///     ARITHMETIC = checked;
+/// }
+///
+/// # fn main() {
+/// assert_eq!(FooIdx::new(1).checked_sub(2), None);
+/// assert_eq!(FooIdx::new(1).saturating_sub(2), FooIdx::new(0));
+/// # }
+/// ```
+///
 /// #### `IMPL_RAW_CONVERSIONS = true;`
 ///
 /// We always automatically implement `From<usize> for YourIndex` and
@@ -155,11 +201,53 @@
 #[macro_export]
 macro_rules! define_index_type {
     // public api
+    (
+        $(#[$attrs:meta])*
+        $v:vis struct $type:ident = $raw:ident;
+        DOMAIN = $domain:ty;
+        $($CONFIG_NAME:ident = $value:expr;)* $(;)?
+    ) => {
+        $crate::define_index_type!{
+            @domain [$domain]
+            $(#[$attrs])*
+            $v struct $type = $raw;
+            $($CONFIG_NAME = $value;)*
+        }
+    };
     (
         $(#[$attrs:meta])*
         $v:vis struct $type:ident = $raw:ident;
         $($CONFIG_NAME:ident = $value:expr;)* $(;)?
     ) => {
+        $crate::define_index_type!{
+            @domain [$type]
+            $(#[$attrs])*
+            $v struct $type = $raw;
+            $($CONFIG_NAME = $value;)*
+        }
+    };
+    (
+        @domain [$domain:ty]
+        $(#[$attrs:meta])*
+        $v:vis struct $type:ident = $raw:ident;
+        $($CONFIG_NAME:ident = $value:expr;)*
+    ) => {
+        impl $crate::IdxDomain for $type {
+            type Domain = $domain;
+        }
+
+        impl $crate::Idx for $type {
+            #[inline]
+            fn from_usize(value: usize) -> Self {
+                Self::from(value)
+            }
+
+            #[inline]
+            fn index(self) -> usize {
+                usize::from(self)
+            }
+        }
+
         $crate::__define_index_type_inner!{
             @configs [$(($CONFIG_NAME; $value))*]
             @attrs [$(#[$attrs])*]
@@ -168,6 +256,7 @@ macro_rules! define_index_type {
             @debug_fmt ["{}"]
             @max [(<$raw>::max_value() as usize)]
             @no_check_max [false]
+            @arithmetic [panicking]
         }
     };
 }
@@ -221,6 +310,7 @@ macro_rules! __define_index_type_inner {
         @debug_fmt [$dbg:expr]
         @max [$max:expr]
         @no_check_max [$_old_no_check_max:expr]
+        @arithmetic [$arithmetic:expr]
     ) => {
         $crate::__define_index_type_inner!{
             @configs [$(($CONFIG_NAME; $value))*]
@@ -230,6 +320,7 @@ macro_rules! __define_index_type_inner {
             @debug_fmt [$dbg]
             @max [$max]
             @no_check_max [$no_check_max]
+            @arithmetic [$arithmetic]
         }
     };
 
@@ -242,6 +333,7 @@ macro_rules! __define_index_type_inner {
         @debug_fmt [$dbg:expr]
         @max [$max:expr]
         @no_check_max [$cm:expr]
+        @arithmetic [$arithmetic:expr]
     ) => {
         $crate::__define_index_type_inner!{
             @configs [$(($CONFIG_NAME; $value))*]
@@ -251,6 +343,7 @@ macro_rules! __define_index_type_inner {
             @debug_fmt [$dbg]
             @max [$new_max]
             @no_check_max [$cm]
+            @arithmetic [$arithmetic]
         }
     };
 
@@ -263,6 +356,7 @@ macro_rules! __define_index_type_inner {
         @debug_fmt [$dbg:expr]
         @max [$max:expr]
         @no_check_max [$no_check_max:expr]
+        @arithmetic [$arithmetic:expr]
     ) => {
         $crate::__define_index_type_inner!{
             @configs [$(($CONFIG_NAME; $value))*]
@@ -272,6 +366,7 @@ macro_rules! __define_index_type_inner {
             @debug_fmt [$dbg]
             @max [$max]
             @no_check_max [$no_check_max]
+            @arithmetic [$arithmetic]
         }
         impl Default for $type {
             #[inline]
@@ -290,6 +385,7 @@ macro_rules! __define_index_type_inner {
         @debug_fmt [$old_dbg:expr]
         @max [$max:expr]
         @no_check_max [$no_check_max:expr]
+        @arithmetic [$arithmetic:expr]
     ) => {
         $crate::__define_index_type_inner!{
             @configs [$(($CONFIG_NAME; $value))*]
@@ -299,6 +395,7 @@ macro_rules! __define_index_type_inner {
             @debug_fmt [$dbg]
             @max [$max]
             @no_check_max [$no_check_max]
+            @arithmetic [$arithmetic]
         }
     };
 
@@ -311,6 +408,7 @@ macro_rules! __define_index_type_inner {
         @debug_fmt [$dbg:expr]
         @max [$max:expr]
         @no_check_max [$no_check_max:expr]
+        @arithmetic [$arithmetic:expr]
     ) => {
         $crate::__define_index_type_inner!{
             @configs [$(($CONFIG_NAME; $value))*]
@@ -320,6 +418,7 @@ macro_rules! __define_index_type_inner {
             @debug_fmt [$dbg]
             @max [$max]
             @no_check_max [$no_check_max]
+            @arithmetic [$arithmetic]
         }
 
         impl core::fmt::Display for $type {
@@ -338,6 +437,7 @@ macro_rules! __define_index_type_inner {
         @debug_fmt [$dbg:expr]
         @max [$max:expr]
         @no_check_max [$no_check_max:expr]
+        @arithmetic [$arithmetic:expr]
     ) => {
         $crate::__define_index_type_inner!{
             @configs [$(($CONFIG_NAME; $value))*]
@@ -347,6 +447,7 @@ macro_rules! __define_index_type_inner {
             @debug_fmt [$dbg]
             @max [$max]
             @no_check_max [$no_check_max]
+            @arithmetic [$arithmetic]
         }
         // Ensure they passed in true. This is... cludgey.
         const _: [(); 1] = [(); $val as usize];
@@ -365,6 +466,28 @@ macro_rules! __define_index_type_inner {
             }
         }
     };
+    // ARITHMETIC
+    (
+        @configs [(ARITHMETIC; $new_arithmetic:expr) $(($CONFIG_NAME:ident; $value:expr))*]
+        @attrs [$(#[$attrs:meta])*]
+        @derives [$(#[$derive:meta])*]
+        @decl [$v:vis struct $type:ident ($raw:ident)]
+        @debug_fmt [$dbg:expr]
+        @max [$max:expr]
+        @no_check_max [$no_check_max:expr]
+        @arithmetic [$arithmetic:expr]
+    ) => {
+        $crate::__define_index_type_inner!{
+            @configs [$(($CONFIG_NAME; $value))*]
+            @attrs [$(#[$attrs])*]
+            @derives [$(#[$derive])*]
+            @decl [$v struct $type ($raw)]
+            @debug_fmt [$dbg]
+            @max [$max]
+            @no_check_max [$no_check_max]
+            @arithmetic [$new_arithmetic]
+        }
+    };
     // Try to make rust emit a decent error message...
     (
         @configs [($other:ident; $format:expr) $(($CONFIG_NAME:ident; $value:expr))*]
@@ -374,6 +497,7 @@ macro_rules! __define_index_type_inner {
         @debug_fmt [$dbg:expr]
         @max [$max:expr]
         @no_check_max [$no_check_max:expr]
+        @arithmetic [$arithmetic:expr]
     ) => {
         $crate::unknown_define_index_type_option!($other);
     };
@@ -386,6 +510,7 @@ macro_rules! __define_index_type_inner {
         @debug_fmt [$dbg:expr]
         @max [$max:expr]
         @no_check_max [$no_check_max:expr]
+        @arithmetic [$arithmetic:expr]
     ) => {
 
         $(#[$derive])*
@@ -403,6 +528,14 @@ macro_rules! __define_index_type_inner {
             /// larger than MAX_INDEX?
             $v const CHECKS_MAX_INDEX: bool = !$no_check_max;
 
+            /// How the `+` and `-` operators handle results outside of
+            /// `0..=MAX_INDEX`.
+            $v const ARITHMETIC: $crate::IndexArithmetic = {
+                #[allow(unused_imports)]
+                use $crate::__index_arithmetic::*;
+                $arithmetic
+            };
+
             /// Construct this index type from a usize. Alias for `from_usize`.
             #[inline(always)]
             $v fn new(value: usize) -> Self {
@@ -462,6 +595,73 @@ macro_rules! __define_index_type_inner {
                 }
             }
 
+            /// Add to this index, returning `None` if the result is larger
+            /// than `MAX_INDEX`. Checked even if `CHECKS_MAX_INDEX` is false.
+            #[inline]
+            $v fn checked_add(self, other: usize) -> Option<Self> {
+                match self.index().checked_add(other) {
+                    Some(v) if v <= Self::MAX_INDEX => Some(Self::from_usize_unchecked(v)),
+                    _ => None,
+                }
+            }
+
+            /// Subtract from this index, returning `None` if the result is
+            /// negative.
+            #[inline]
+            $v fn checked_sub(self, other: usize) -> Option<Self> {
+                match self.index().checked_sub(other) {
+                    Some(v) if v <= Self::MAX_INDEX => Some(Self::from_usize_unchecked(v)),
+                    _ => None,
+                }
+            }
+
+            /// Add to this index, clamping the result to `MAX_INDEX`.
+            #[inline]
+            $v fn saturating_add(self, other: usize) -> Self {
+                Self::from_usize_unchecked(self.index().saturating_add(other).min(Self::MAX_INDEX))
+            }
+
+            /// Subtract from this index, clamping the result to `0`.
+            #[inline]
+            $v fn saturating_sub(self, other: usize) -> Self {
+                Self::from_usize_unchecked(self.index().saturating_sub(other).min(Self::MAX_INDEX))
+            }
+
+            /// `lhs + rhs` according to `Self::ARITHMETIC`.
+            #[allow(clippy::cast_possible_truncation)]
+            #[inline]
+            fn arithmetic_add(lhs: usize, rhs: usize) -> Self {
+                match Self::ARITHMETIC {
+                    $crate::IndexArithmetic::Checked => match lhs.checked_add(rhs) {
+                        Some(v) if v <= Self::MAX_INDEX => Self::from_usize_unchecked(v),
+                        _ => $crate::__arithmetic_overflow_fail("+", lhs, rhs, Self::MAX_INDEX),
+                    },
+                    $crate::IndexArithmetic::Wrapping => {
+                        Self::from_raw((lhs as $raw).wrapping_add(rhs as $raw))
+                    }
+                    // use wrapping add so that it's up to the index type whether or
+                    // not to check -- e.g. if checks are disabled, they're disabled
+                    // on both debug and release.
+                    $crate::IndexArithmetic::Panicking => Self::new(lhs.wrapping_add(rhs)),
+                }
+            }
+
+            /// `lhs - rhs` according to `Self::ARITHMETIC`.
+            #[allow(clippy::cast_possible_truncation)]
+            #[inline]
+            fn arithmetic_sub(lhs: usize, rhs: usize) -> Self {
+                match Self::ARITHMETIC {
+                    $crate::IndexArithmetic::Checked => match lhs.checked_sub(rhs) {
+                        Some(v) if v <= Self::MAX_INDEX => Self::from_usize_unchecked(v),
+                        _ => $crate::__arithmetic_overflow_fail("-", lhs, rhs, Self::MAX_INDEX),
+                    },
+                    $crate::IndexArithmetic::Wrapping => {
+                        Self::from_raw((lhs as $raw).wrapping_sub(rhs as $raw))
+                    }
+                    $crate::IndexArithmetic::Panicking => Self::new(lhs.wrapping_sub(rhs)),
+                }
+            }
+
             const _ENSURE_RAW_IS_UNSIGNED: [(); 0] = [(); <$raw>::MIN as usize];
         }
 
@@ -503,10 +703,7 @@ macro_rules! __define_index_type_inner {
             type Output = Self;
             #[inline]
             fn add(self, other: usize) -> Self {
-                // use wrapping add so that it's up to the index type whether or
-                // not to check -- e.g. if checks are disabled, they're disabled
-                // on both debug and release.
-                Self::new(self.index().wrapping_add(other))
+                Self::arithmetic_add(self.index(), other)
             }
         }
 
@@ -514,10 +711,7 @@ macro_rules! __define_index_type_inner {
             type Output = Self;
             #[inline]
             fn sub(self, other: usize) -> Self {
-                // use wrapping sub so that it's up to the index type whether or
-                // not to check -- e.g. if checks are disabled, they're disabled
-                // on both debug and release.
-                Self::new(self.index().wrapping_sub(other))
+                Self::arithmetic_sub(self.index(), other)
             }
         }
 
@@ -555,7 +749,7 @@ macro_rules! __define_index_type_inner {
             type Output = $type;
             #[inline]
             fn sub(self, other: $type) -> $type {
-                $type::new(self.wrapping_sub(other.index()))
+                $type::arithmetic_sub(self, other.index())
             }
         }
 
@@ -563,7 +757,7 @@ macro_rules! __define_index_type_inner {
             type Output = $type;
             #[inline]
             fn add(self, other: $type) -> $type {
-                $type::new(other.index() + self.index())
+                $type::arithmetic_add(self.index(), other.index())
             }
         }
 
@@ -571,7 +765,7 @@ macro_rules! __define_index_type_inner {
             type Output = $type;
             #[inline]
             fn sub(self, other: $type) -> $type {
-                $type::new(self.index().wrapping_sub(other.index()))
+                $type::arithmetic_sub(self.index(), other.index())
             }
         }
 
@@ -589,18 +783,6 @@ macro_rules! __define_index_type_inner {
             }
         }
 
-        impl $crate::Idx for $type {
-            #[inline]
-            fn from_usize(value: usize) -> Self {
-                Self::from(value)
-            }
-
-            #[inline]
-            fn index(self) -> usize {
-                usize::from(self)
-            }
-        }
-
         impl From<$type> for usize {
             #[inline]
             fn from(v: $type) -> usize {
@@ -0,0 +1,55 @@
+use oxc_index::{Cycle, IndexGraph};
+
+oxc_index::define_index_type! {
+    pub struct NodeId = u32;
+}
+
+oxc_index::define_index_type! {
+    pub struct EdgeId = u32;
+}
+
+type Graph = IndexGraph<NodeId, EdgeId, &'static str, u32>;
+
+#[test]
+fn adjacency() {
+    let mut graph = Graph::new();
+    let a = graph.add_node("a");
+    let b = graph.add_node("b");
+    let c = graph.add_node("c");
+    let ab = graph.add_edge(a, b, 1);
+    graph.add_edge(a, c, 2);
+    graph.add_edge(b, c, 3);
+    assert_eq!(graph.node_count(), 3);
+    assert_eq!(graph.edge_count(), 3);
+    assert_eq!(graph.successors(a).collect::<Vec<_>>(), [b, c]);
+    assert_eq!(graph.predecessors(c).collect::<Vec<_>>(), [a, b]);
+    assert_eq!(graph.predecessors(a).count(), 0);
+    assert_eq!(graph.edge_endpoints(ab), (a, b));
+    assert_eq!(graph.outgoing_edges(a).map(|edge| *graph.edge_weight(edge)).sum::<u32>(), 3);
+    *graph.node_weight_mut(b) = "d";
+    assert_eq!(
+        graph.nodes().map(|node| *graph.node_weight(node)).collect::<Vec<_>>(),
+        ["a", "d", "c"]
+    );
+}
+
+#[test]
+fn topological_sort() {
+    let mut graph = Graph::new();
+    let nodes = ["a", "b", "c", "d"].map(|name| graph.add_node(name));
+    graph.add_edge(nodes[2], nodes[0], 0);
+    graph.add_edge(nodes[3], nodes[1], 0);
+    graph.add_edge(nodes[0], nodes[1], 0);
+    // `c` must come before `a`, and `d` is not ordered until `b`.
+    assert_eq!(graph.topological_sort(), Ok(vec![nodes[2], nodes[0], nodes[3], nodes[1]]));
+
+    graph.add_edge(nodes[1], nodes[2], 0);
+    assert!(matches!(graph.topological_sort(), Err(Cycle(node)) if node != nodes[3]));
+
+    let mut graph = Graph::new();
+    let a = graph.add_node("a");
+    let b = graph.add_node("b");
+    graph.add_edge(a, b, 0);
+    graph.add_edge(b, b, 0);
+    assert_eq!(graph.topological_sort(), Err(Cycle(b)));
+}
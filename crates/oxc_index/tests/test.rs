@@ -6,7 +6,7 @@
     clippy::cast_possible_truncation
 )]
 
-use oxc_index::{index_vec, IndexSlice, IndexVec};
+use oxc_index::{index_vec, FrozenIndexVec, Idx, IdxDomain, IndexArithmetic, IndexSlice, IndexVec};
 
 oxc_index::define_index_type! {
     pub struct USize16 = usize;
@@ -29,6 +29,16 @@ oxc_index::define_index_type! {
     pub struct IdxSz = usize;
 }
 
+oxc_index::define_index_type! {
+    pub struct SameDomainIdx = u32;
+    DOMAIN = IdxSz;
+    DEBUG_FORMAT = "Same({:?})";
+}
+
+oxc_index::assert_same_index_domain!(IdxSz, SameDomainIdx);
+oxc_index::assert_index_domain!(SameDomainIdx, IdxSz);
+oxc_index::assert_index_domain!(Idx32, Idx32);
+
 oxc_index::define_index_type! {
     pub struct Idx32 = u32;
     DEBUG_FORMAT = "Test({:?})";
@@ -64,6 +74,18 @@ oxc_index::define_index_type! {
     MAX_INDEX = 0x7f;
 }
 
+oxc_index::define_index_type! {
+    pub struct SmallArithChecked = u8;
+    MAX_INDEX = 0x7f;
+    DISABLE_MAX_INDEX_CHECK = true;
+    ARITHMETIC = checked;
+}
+
+oxc_index::define_index_type! {
+    pub struct SmallArithWrapping = u8;
+    ARITHMETIC = wrapping;
+}
+
 #[test]
 fn test_idx_default_max() {
     assert_eq!(Idx32::MAX_INDEX, u32::MAX as usize);
@@ -268,6 +290,47 @@ fn test_idx_sce_subassign() {
     z3 -= 1;
 }
 
+#[test]
+fn test_idx_checked_arith() {
+    assert_eq!(Idx32::ARITHMETIC, IndexArithmetic::Panicking);
+    assert_eq!(SmallArithChecked::ARITHMETIC, IndexArithmetic::Checked);
+
+    assert_eq!(SmallChecked::new(254).checked_add(1), Some(SmallChecked::new(255)));
+    assert_eq!(SmallChecked::new(255).checked_add(1), None);
+    assert_eq!(SmallChecked::new(1).checked_add(usize::MAX), None);
+    assert_eq!(SmallCheckedEarly::new(0x7f).checked_add(1), None);
+    assert_eq!(SmallChecked::new(1).checked_sub(1), Some(SmallChecked::new(0)));
+    assert_eq!(SmallChecked::new(0).checked_sub(1), None);
+    // Checked even if max index checks are disabled.
+    assert_eq!(SmallUncheckedEarly::new(0x7f).checked_add(1), None);
+
+    assert_eq!(SmallCheckedEarly::new(0x7e).saturating_add(5), 0x7f);
+    assert_eq!(SmallChecked::new(1).saturating_add(usize::MAX), 255);
+    assert_eq!(SmallChecked::new(1).saturating_sub(5), 0);
+
+    assert_eq!(SmallArithChecked::new(0x7e) + 1, 0x7f);
+    assert_eq!(SmallArithChecked::new(1) - SmallArithChecked::new(1), 0);
+
+    assert_eq!(SmallArithWrapping::new(255) + 1, 0);
+    assert_eq!(SmallArithWrapping::new(0) - 1, 255);
+    assert_eq!(1 - SmallArithWrapping::new(2), 255);
+}
+#[test]
+#[should_panic]
+fn test_idx_ac_of_add() {
+    let _ = SmallArithChecked::new(0x7f) + 1;
+}
+#[test]
+#[should_panic]
+fn test_idx_ac_of_sub() {
+    let _ = SmallArithChecked::new(0) - 1;
+}
+#[test]
+#[should_panic]
+fn test_idx_ac_of_rsub() {
+    let _ = 0 - SmallArithChecked::new(1);
+}
+
 #[test]
 fn test_vec() {
     let mut strs: IndexVec<Idx32, &'static str> = index_vec!["strs", "bar", "baz"];
@@ -555,3 +618,91 @@ fn test_splits() {
     assert!(v.split_first_mut().is_none());
     assert!(v.split_last_mut().is_none());
 }
+
+#[test]
+fn test_domains() {
+    fn get_in<I: IdxDomain, J: IdxDomain<Domain = I::Domain>, T>(
+        v: &IndexSlice<I, [T]>,
+        idx: J,
+    ) -> &T {
+        &v[idx.cast::<I>()]
+    }
+
+    let v: IndexVec<IdxSz, i32> = index_vec![0, 1, 2, 3];
+    assert_eq!(get_in(&v, SameDomainIdx::new(2)), &2);
+    assert_eq!(get_in(&v, IdxSz::new(3)), &3);
+    assert_eq!(SameDomainIdx::new(1).cast::<IdxSz>(), IdxSz::new(1));
+    assert_eq!(Idx32::new(1).cast_cross_domain::<IdxSz>(), IdxSz::new(1));
+    assert_eq!(format!("{:?}", SameDomainIdx::new(1)), "Same(1)");
+
+    let v: IndexVec<SameDomainIdx, i32> = v.cast_index();
+    assert_eq!(v[SameDomainIdx::new(1)], 1);
+    let v: IndexVec<Idx32, i32> = v.cast_index_cross_domain();
+    assert_eq!(v[Idx32::new(2)], 2);
+}
+
+#[test]
+fn test_manual_idx() {
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+    struct ManualIdx(u32);
+
+    impl Idx for ManualIdx {
+        fn from_usize(idx: usize) -> Self {
+            Self(u32::try_from(idx).unwrap())
+        }
+
+        fn index(self) -> usize {
+            self.0 as usize
+        }
+    }
+
+    let v: IndexVec<ManualIdx, i32> = index_vec![0, 1, 2];
+    assert_eq!(v[ManualIdx(1)], 1);
+    assert_eq!(ManualIdx(2).cast_cross_domain::<IdxSz>(), IdxSz::new(2));
+}
+
+#[test]
+fn test_frozen() {
+    let v: IndexVec<Idx32, i32> = index_vec![0, 1, 2, 3, 4];
+    let frozen = v.freeze();
+    let clone = frozen.clone();
+    assert!(FrozenIndexVec::ptr_eq(&frozen, &clone));
+    assert!(!FrozenIndexVec::ptr_eq(&frozen, &frozen.to_index_vec().freeze()));
+    assert_eq!(clone[Idx32::new(3)], 3);
+    assert_eq!(clone.last_idx(), Idx32::new(4));
+    assert_eq!(clone.get(Idx32::new(5)), None);
+    assert_eq!(frozen.to_index_vec(), index_vec![0, 1, 2, 3, 4]);
+    assert!(FrozenIndexVec::<Idx32, i32>::default().is_empty());
+}
+
+#[test]
+fn test_par_chunks() {
+    let frozen: FrozenIndexVec<Idx32, u32> = (0..10).collect::<IndexVec<_, _>>().freeze();
+    let chunks = frozen.par_chunks(4);
+    assert_eq!(chunks.len(), 3);
+    let handles = chunks
+        .map(|chunk| {
+            std::thread::spawn(move || {
+                assert!(chunk.contains(chunk.start()));
+                assert!(!chunk.contains(chunk.end()));
+                assert_eq!(chunk.get(chunk.start()), chunk.as_raw_slice().first());
+                chunk.iter_enumerated().map(|(i, &x)| (i, x * 2)).collect::<Vec<_>>()
+            })
+        })
+        .collect::<Vec<_>>();
+    let doubled = handles.into_iter().flat_map(|handle| handle.join().unwrap()).collect::<Vec<_>>();
+    assert_eq!(doubled.len(), 10);
+    for (i, x) in doubled {
+        assert_eq!(x, frozen[i] * 2);
+    }
+    let last = frozen.par_chunks(4).last().unwrap();
+    assert_eq!((last.start(), last.len()), (Idx32::new(8), 2));
+    assert_eq!(last.get(Idx32::new(7)), None);
+}
+
+#[test]
+#[should_panic]
+fn test_par_chunks_zero() {
+    let frozen: FrozenIndexVec<Idx32, u32> = index_vec![1, 2].freeze();
+    let _ = frozen.par_chunks(0);
+}
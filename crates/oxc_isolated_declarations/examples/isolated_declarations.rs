@@ -33,7 +33,8 @@ fn main() {
     println!("{source_text}\n");
 
     let ret = IsolatedDeclarations::new(&allocator).build(&ret.program);
-    let printed = CodeGenerator::new().build(&ret.program).source_text;
+    let printed =
+        CodeGenerator::new().with_source_text(&source_text).build(&ret.program).source_text;
 
     println!("Dts Emit:\n");
     println!("{printed}\n");
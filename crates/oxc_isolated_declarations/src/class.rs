@@ -1,7 +1,7 @@
 use oxc_allocator::Box;
 #[allow(clippy::wildcard_imports)]
 use oxc_ast::ast::*;
-use oxc_span::{Atom, GetSpan, SPAN};
+use oxc_span::{Atom, GetSpan, Span, SPAN};
 use rustc_hash::FxHashMap;
 
 use crate::{
@@ -84,6 +84,7 @@ impl<'a> IsolatedDeclarations<'a> {
 
             if type_annotations.is_none() && value.is_none() {
                 self.error(property_must_have_explicit_type(property.key.span()));
+                type_annotations = Some(self.unknown_type_annotation());
             }
         }
 
@@ -286,7 +287,7 @@ impl<'a> IsolatedDeclarations<'a> {
                 let function = &method.value;
                 match method.kind {
                     MethodDefinitionKind::Get => {
-                        let return_type = self.infer_function_return_type(function);
+                        let return_type = self.infer_method_return_type(function, method.r#static);
                         if let Some(return_type) = return_type {
                             inferred_accessor_types.insert(name, self.ast.copy(&return_type));
                         }
@@ -323,18 +324,19 @@ impl<'a> IsolatedDeclarations<'a> {
             return None;
         }
 
-        if let Some(super_class) = &decl.super_class {
-            let is_not_allowed = match super_class {
-                Expression::Identifier(_) => false,
-                Expression::StaticMemberExpression(expr) => {
-                    !expr.get_first_object().is_identifier_reference()
-                }
-                _ => true,
-            };
-            if is_not_allowed {
-                self.error(extends_clause_expression(super_class.span()));
+        // Other expressions can not be referenced from a declaration file, so the extends clause
+        // is left out after reporting the error.
+        let super_class = decl.super_class.as_ref().filter(|super_class| {
+            let is_allowed = Self::is_entity_name_expression(super_class);
+            if !is_allowed {
+                let span =
+                    decl.super_type_parameters.as_ref().map_or(super_class.span(), |params| {
+                        Span::new(super_class.span().start, params.span.end)
+                    });
+                self.error(extends_clause_expression(span));
             }
-        }
+            is_allowed
+        });
 
         let mut has_private_key = false;
         let mut elements = self.ast.vec();
@@ -392,13 +394,14 @@ impl<'a> IsolatedDeclarations<'a> {
 
                     let return_type = match method.kind {
                         MethodDefinitionKind::Method => {
-                            let rt = self.infer_function_return_type(function);
-                            if rt.is_none() {
+                            let rt = self.infer_method_return_type(function, method.r#static);
+                            rt.or_else(|| {
                                 self.error(method_must_have_explicit_return_type(
                                     method.key.span(),
                                 ));
-                            }
-                            rt
+                                // Overloads without a return type return `any`.
+                                function.body.is_some().then(|| self.unknown_type_annotation())
+                            })
                         }
                         MethodDefinitionKind::Get => {
                             let rt = method.key.static_name().and_then(|name| {
@@ -406,12 +409,12 @@ impl<'a> IsolatedDeclarations<'a> {
                                     .get(&self.ast.atom(&name))
                                     .map(|t| self.ast.copy(t))
                             });
-                            if rt.is_none() {
+                            Some(rt.unwrap_or_else(|| {
                                 self.error(accessor_must_have_explicit_return_type(
                                     method.key.span(),
                                 ));
-                            }
-                            rt
+                                self.unknown_type_annotation()
+                            }))
                         }
                         MethodDefinitionKind::Set | MethodDefinitionKind::Constructor => None,
                     };
@@ -432,7 +435,7 @@ impl<'a> IsolatedDeclarations<'a> {
                 }
                 ClassElement::AccessorProperty(property) => {
                     if self.report_property_key(&property.key, property.computed) {
-                        return None;
+                        continue;
                     }
 
                     if property.key.is_private_identifier() {
@@ -460,7 +463,8 @@ impl<'a> IsolatedDeclarations<'a> {
             // <https://github.com/microsoft/TypeScript/blob/64d2eeea7b9c7f1a79edf42cb99f302535136a2e/src/compiler/transformers/declarations.ts#L1699-L1709>
             // When the class has at least one private identifier, create a unique constant identifier to retain the nominal typing behavior
             // Prevents other classes with the same public members from being used in place of the current class
-            let ident = self.ast.property_key_private_identifier(SPAN, "private");
+            let name = self.create_unique_private_name(&decl.body);
+            let ident = self.ast.property_key_private_identifier(SPAN, name);
             let r#type = PropertyDefinitionType::PropertyDefinition;
             let decorators = self.ast.vec();
             let element = self.ast.class_element_property_definition(
@@ -491,8 +495,8 @@ impl<'a> IsolatedDeclarations<'a> {
             self.ast.vec(),
             self.ast.copy(&decl.id),
             self.ast.copy(&decl.type_parameters),
-            self.ast.copy(&decl.super_class),
-            self.ast.copy(&decl.super_type_parameters),
+            super_class.map(|super_class| self.ast.copy(super_class)),
+            super_class.and_then(|_| self.ast.copy(&decl.super_type_parameters)),
             self.ast.copy(&decl.implements),
             body,
             decl.r#abstract,
@@ -500,6 +504,34 @@ impl<'a> IsolatedDeclarations<'a> {
         ))
     }
 
+    /// `Foo` or `foo.Bar`
+    fn is_entity_name_expression(expr: &Expression<'a>) -> bool {
+        match expr {
+            Expression::Identifier(_) => true,
+            Expression::StaticMemberExpression(member) => {
+                Self::is_entity_name_expression(&member.object)
+            }
+            _ => false,
+        }
+    }
+
+    /// Name of the `#private` brand, which must not be one of the class's own private names.
+    fn create_unique_private_name(&self, body: &ClassBody<'a>) -> Atom<'a> {
+        let private_names = body
+            .body
+            .iter()
+            .filter_map(|element| element.property_key().and_then(PropertyKey::private_name))
+            .collect::<Vec<_>>();
+        let name = self.options.private_brand_name.as_str();
+        let mut brand = self.ast.atom(name);
+        let mut i = 1;
+        while private_names.contains(&brand) {
+            brand = self.ast.atom(format!("{name}_{i}").as_str());
+            i += 1;
+        }
+        brand
+    }
+
     pub fn transform_set_accessor_params(
         &self,
         params: &Box<'a, FormalParameters<'a>>,
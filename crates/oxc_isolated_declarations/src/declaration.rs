@@ -180,7 +180,7 @@ impl<'a> IsolatedDeclarations<'a> {
         }
     }
 
-    pub fn transform_declaration(
+    pub fn transform_declaration_with_binding_check(
         &mut self,
         decl: &Declaration<'a>,
         check_binding: bool,
@@ -139,3 +139,7 @@ pub fn type_containing_private_name(name: &str, span: Span) -> OxcDiagnostic {
     ))
     .with_label(span)
 }
+
+pub fn not_a_declaration(span: Span) -> OxcDiagnostic {
+    OxcDiagnostic::error("Only declarations can be transformed.").with_label(span)
+}
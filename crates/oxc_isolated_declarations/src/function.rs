@@ -22,10 +22,11 @@ impl<'a> IsolatedDeclarations<'a> {
         if func.declare {
             None
         } else {
-            let return_type = self.infer_function_return_type(func);
-            if return_type.is_none() {
+            let return_type = self.infer_function_return_type(func).or_else(|| {
                 self.error(function_must_have_explicit_return_type(get_function_span(func)));
-            }
+                // Overloads without a return type return `any`.
+                func.body.is_some().then(|| self.unknown_type_annotation())
+            });
             let params = self.transform_formal_parameters(&func.params);
             Some(self.ast.alloc_function(
                 func.r#type,
@@ -54,7 +55,18 @@ impl<'a> IsolatedDeclarations<'a> {
                 && pattern.left.type_annotation.is_none()
             {
                 self.error(parameter_must_have_explicit_type(param.span));
-                return None;
+                let mut kind = self.ast.copy(&pattern.left.kind);
+                FormalParameterBindingPattern::remove_assignments_from_kind(self.ast, &mut kind);
+                let pattern =
+                    self.ast.binding_pattern(kind, Some(self.unknown_type_annotation()), true);
+                return Some(self.ast.formal_parameter(
+                    param.span,
+                    self.ast.vec(),
+                    pattern,
+                    None,
+                    false,
+                    false,
+                ));
             }
         }
 
@@ -78,6 +90,7 @@ impl<'a> IsolatedDeclarations<'a> {
                     let new_type = self.infer_type_from_formal_parameter(param);
                     if new_type.is_none() {
                         self.error(parameter_must_have_explicit_type(param.span));
+                        return Some(self.ast.ts_type_unknown_keyword(SPAN));
                     }
                     new_type
                 })
@@ -133,18 +146,18 @@ impl<'a> IsolatedDeclarations<'a> {
                 self.transform_formal_parameter(item, is_remaining_params_have_required)
             }));
 
-        if let Some(rest) = &params.rest {
+        let mut rest = self.ast.copy(&params.rest);
+        if let Some(rest) = &mut rest {
             if rest.argument.type_annotation.is_none() {
                 self.error(parameter_must_have_explicit_type(rest.span));
+                let unknown_array =
+                    self.ast.ts_type_array_type(SPAN, self.ast.ts_type_unknown_keyword(SPAN));
+                rest.argument.type_annotation =
+                    Some(self.ast.alloc_ts_type_annotation(SPAN, unknown_array));
             }
         }
 
-        self.ast.alloc_formal_parameters(
-            params.span,
-            FormalParameterKind::Signature,
-            items,
-            self.ast.copy(&params.rest),
-        )
+        self.ast.alloc_formal_parameters(params.span, FormalParameterKind::Signature, items, rest)
     }
 }
 
@@ -31,11 +31,9 @@ impl<'a> IsolatedDeclarations<'a> {
                 "undefined" => Some(self.ast.ts_type_undefined_keyword(SPAN)),
                 _ => None,
             },
-            Expression::FunctionExpression(func) => {
-                self.transform_function_to_ts_type(func).map(|x| self.ast.copy(&x))
-            }
+            Expression::FunctionExpression(func) => Some(self.transform_function_to_ts_type(func)),
             Expression::ArrowFunctionExpression(func) => {
-                self.transform_arrow_function_to_ts_type(func).map(|x| self.ast.copy(&x))
+                Some(self.transform_arrow_function_to_ts_type(func))
             }
             Expression::ObjectExpression(expr) => {
                 Some(self.transform_object_expression_to_ts_type(expr, false))
@@ -45,11 +43,7 @@ impl<'a> IsolatedDeclarations<'a> {
                 Some(self.ast.ts_type_unknown_keyword(expr.span))
             }
             Expression::TSAsExpression(expr) => {
-                if expr.type_annotation.is_const_type_reference() {
-                    self.transform_expression_to_ts_type(&expr.expression)
-                } else {
-                    Some(self.ast.copy(&expr.type_annotation))
-                }
+                self.infer_type_from_type_assertion(&expr.expression, &expr.type_annotation)
             }
             Expression::ClassExpression(expr) => {
                 self.error(inferred_type_of_class_expression(expr.span));
@@ -61,10 +55,13 @@ impl<'a> IsolatedDeclarations<'a> {
             Expression::TSNonNullExpression(expr) => {
                 self.infer_type_from_expression(&expr.expression)
             }
+            // `satisfies` does not change the type of the expression, so `T` is not used.
             Expression::TSSatisfiesExpression(expr) => {
                 self.infer_type_from_expression(&expr.expression)
             }
-            Expression::TSTypeAssertion(expr) => Some(self.ast.copy(&expr.type_annotation)),
+            Expression::TSTypeAssertion(expr) => {
+                self.infer_type_from_type_assertion(&expr.expression, &expr.type_annotation)
+            }
             Expression::UnaryExpression(expr) => {
                 if Self::can_infer_unary_expression(expr) {
                     self.infer_type_from_expression(&expr.argument)
@@ -76,6 +73,20 @@ impl<'a> IsolatedDeclarations<'a> {
         }
     }
 
+    /// The type of `expr as T` or `<T>expr`, which is `T`, or the literal type of `expr` for
+    /// `as const` and `<const>`.
+    pub fn infer_type_from_type_assertion(
+        &self,
+        expr: &Expression<'a>,
+        type_annotation: &TSType<'a>,
+    ) -> Option<TSType<'a>> {
+        if type_annotation.is_const_type_reference() {
+            self.transform_expression_to_ts_type(expr)
+        } else {
+            Some(self.ast.copy(type_annotation))
+        }
+    }
+
     pub fn infer_type_from_formal_parameter(
         &self,
         param: &FormalParameter<'a>,
@@ -87,9 +98,16 @@ impl<'a> IsolatedDeclarations<'a> {
             if let Some(annotation) = pattern.left.type_annotation.as_ref() {
                 Some(self.ast.copy(&annotation.type_annotation))
             } else {
-                if let Expression::TSAsExpression(expr) = &pattern.right {
-                    if !expr.type_annotation.is_keyword_or_literal() {
-                        self.error(parameter_must_have_explicit_type(expr.type_annotation.span()));
+                let type_annotation = match &pattern.right {
+                    Expression::TSAsExpression(expr) => Some(&expr.type_annotation),
+                    Expression::TSTypeAssertion(expr) => Some(&expr.type_annotation),
+                    _ => None,
+                };
+                if let Some(type_annotation) = type_annotation {
+                    if !type_annotation.is_keyword_or_literal()
+                        && !type_annotation.is_const_type_reference()
+                    {
+                        self.error(parameter_must_have_explicit_type(type_annotation.span()));
                     }
                 }
 
@@ -100,21 +118,56 @@ impl<'a> IsolatedDeclarations<'a> {
         }
     }
 
+    /// `return this` is inferred as the type of the `this` parameter, `function (this: T)`.
     pub fn infer_function_return_type(
         &self,
         function: &Function<'a>,
+    ) -> Option<Box<'a, TSTypeAnnotation<'a>>> {
+        self.infer_function_return_type_with_this(function, self.this_parameter_type(function))
+    }
+
+    /// Same as [`IsolatedDeclarations::infer_function_return_type`] for the methods and getters
+    /// of classes, where `return this` without a `this` parameter is the polymorphic `this` type,
+    /// e.g. for the methods of fluent builders.
+    pub fn infer_method_return_type(
+        &self,
+        function: &Function<'a>,
+        is_static: bool,
+    ) -> Option<Box<'a, TSTypeAnnotation<'a>>> {
+        let this_type = self.this_parameter_type(function).or_else(|| {
+            // `this` of static methods is the class itself, `typeof Class`
+            (!is_static).then(|| self.ast.ts_type_this_type(SPAN))
+        });
+        self.infer_function_return_type_with_this(function, this_type)
+    }
+
+    fn this_parameter_type(&self, function: &Function<'a>) -> Option<TSType<'a>> {
+        let type_annotation = function.this_param.as_ref()?.type_annotation.as_ref()?;
+        Some(self.ast.copy(&type_annotation.type_annotation))
+    }
+
+    fn infer_function_return_type_with_this(
+        &self,
+        function: &Function<'a>,
+        this_type: Option<TSType<'a>>,
     ) -> Option<Box<'a, TSTypeAnnotation<'a>>> {
         if function.return_type.is_some() {
             return self.ast.copy(&function.return_type);
         }
 
-        if function.r#async || function.generator {
-            return None;
-        }
-
         function.body.as_ref().and_then(|body| {
-            FunctionReturnType::infer(self, body)
-                .map(|type_annotation| self.ast.alloc_ts_type_annotation(SPAN, type_annotation))
+            if function.r#async || function.generator {
+                FunctionReturnType::infer_async_or_generator(
+                    self,
+                    body,
+                    this_type,
+                    function.r#async,
+                    function.generator,
+                )
+            } else {
+                FunctionReturnType::infer(self, body, this_type)
+            }
+            .map(|type_annotation| self.ast.alloc_ts_type_annotation(SPAN, type_annotation))
         })
     }
 
@@ -126,20 +179,36 @@ impl<'a> IsolatedDeclarations<'a> {
             return self.ast.copy(&function.return_type);
         }
 
-        if function.r#async {
-            return None;
-        }
-
         if function.expression {
             if let Some(Statement::ExpressionStatement(stmt)) = function.body.statements.first() {
-                return self.infer_type_from_expression(&stmt.expression).map(|type_annotation| {
-                    self.ast.alloc_ts_type_annotation(SPAN, type_annotation)
-                });
+                let type_annotation = self.infer_type_from_expression(&stmt.expression)?;
+                let type_annotation = if function.r#async {
+                    self.type_reference("Promise", [type_annotation])
+                } else {
+                    type_annotation
+                };
+                return Some(self.ast.alloc_ts_type_annotation(SPAN, type_annotation));
             }
         }
 
-        FunctionReturnType::infer(self, &function.body)
-            .map(|type_annotation| self.ast.alloc_ts_type_annotation(SPAN, type_annotation))
+        if function.r#async {
+            FunctionReturnType::infer_async_or_generator(self, &function.body, None, true, false)
+        } else {
+            FunctionReturnType::infer(self, &function.body, None)
+        }
+        .map(|type_annotation| self.ast.alloc_ts_type_annotation(SPAN, type_annotation))
+    }
+
+    /// `name<...params>`, e.g. `Promise<number>`
+    pub fn type_reference<const N: usize>(
+        &self,
+        name: &'static str,
+        params: [TSType<'a>; N],
+    ) -> TSType<'a> {
+        let type_name = self.ast.ts_type_name_identifier_reference(SPAN, name);
+        let params =
+            self.ast.alloc_ts_type_parameter_instantiation(SPAN, self.ast.vec_from_iter(params));
+        self.ast.ts_type_type_reference(SPAN, type_name, Some(params))
     }
 
     pub fn is_need_to_infer_type_from_expression(expr: &Expression<'a>) -> bool {
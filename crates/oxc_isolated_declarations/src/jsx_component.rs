@@ -0,0 +1,221 @@
+#[allow(clippy::wildcard_imports)]
+use oxc_ast::{ast::*, Visit};
+use oxc_span::SPAN;
+use oxc_syntax::scope::ScopeFlags;
+
+use crate::IsolatedDeclarations;
+
+/// An exported function component: a function named in PascalCase which returns JSX, without a
+/// return type annotation and with at most one parameter.
+struct JsxComponent<'b, 'a> {
+    id: &'b BindingIdentifier<'a>,
+    params: &'b FormalParameters<'a>,
+}
+
+impl<'b, 'a> JsxComponent<'b, 'a> {
+    fn from_declaration(decl: &'b Declaration<'a>) -> Option<Self> {
+        let (id, params, is_jsx_component) = match decl {
+            Declaration::FunctionDeclaration(func) => {
+                (func.id.as_ref()?, &*func.params, is_jsx_function(func))
+            }
+            Declaration::VariableDeclaration(decl) if decl.kind.is_const() => {
+                let [declarator] = decl.declarations.as_slice() else { return None };
+                let BindingPatternKind::BindingIdentifier(id) = &declarator.id.kind else {
+                    return None;
+                };
+                if declarator.id.type_annotation.is_some() {
+                    return None;
+                }
+                match declarator.init.as_ref()?.without_parenthesized() {
+                    Expression::FunctionExpression(func) => {
+                        (&**id, &*func.params, is_jsx_function(func))
+                    }
+                    Expression::ArrowFunctionExpression(arrow) => {
+                        (&**id, &*arrow.params, is_jsx_arrow_function(arrow))
+                    }
+                    _ => return None,
+                }
+            }
+            _ => return None,
+        };
+        let is_pascal_case = id.name.starts_with(|c: char| c.is_ascii_uppercase());
+        let has_props_param = params.rest.is_none()
+            && params.items.len() <= 1
+            && params.items.iter().all(|param| props_type_annotation(param).is_some());
+        (is_jsx_component && is_pascal_case && has_props_param).then_some(Self { id, params })
+    }
+}
+
+fn is_jsx_function(func: &Function<'_>) -> bool {
+    func.return_type.is_none()
+        && func.type_parameters.is_none()
+        && func.this_param.is_none()
+        && !func.generator
+        && !func.r#async
+        && func.body.as_ref().is_some_and(|body| returns_jsx(body))
+}
+
+fn is_jsx_arrow_function(arrow: &ArrowFunctionExpression<'_>) -> bool {
+    if arrow.return_type.is_some() || arrow.type_parameters.is_some() || arrow.r#async {
+        return false;
+    }
+    arrow.get_expression().map_or_else(|| returns_jsx(&arrow.body), is_jsx)
+}
+
+fn is_jsx(expr: &Expression<'_>) -> bool {
+    matches!(expr.without_parenthesized(), Expression::JSXElement(_) | Expression::JSXFragment(_))
+}
+
+/// Whether `body` always returns JSX: every `return` statement returns JSX, and the body ends
+/// with one, so it does not return `undefined` either.
+fn returns_jsx(body: &FunctionBody<'_>) -> bool {
+    if !matches!(body.statements.last(), Some(Statement::ReturnStatement(_))) {
+        return false;
+    }
+    let mut finder = JsxReturnFinder { returns_jsx: true };
+    finder.visit_function_body(body);
+    finder.returns_jsx
+}
+
+/// Whether every `return` statement of a function body, outside of nested functions, returns
+/// JSX.
+struct JsxReturnFinder {
+    returns_jsx: bool,
+}
+
+impl<'a> Visit<'a> for JsxReturnFinder {
+    fn visit_return_statement(&mut self, stmt: &ReturnStatement<'a>) {
+        self.returns_jsx &= stmt.argument.as_ref().is_some_and(is_jsx);
+    }
+
+    fn visit_function(&mut self, _func: &Function<'a>, _flags: ScopeFlags) {}
+
+    fn visit_arrow_function_expression(&mut self, _arrow: &ArrowFunctionExpression<'a>) {}
+}
+
+/// The type annotation of the props parameter, and whether the parameter is optional.
+fn props_type_annotation<'b, 'a>(
+    param: &'b FormalParameter<'a>,
+) -> Option<(&'b TSTypeAnnotation<'a>, bool)> {
+    match &param.pattern.kind {
+        BindingPatternKind::AssignmentPattern(pattern) => {
+            pattern.left.type_annotation.as_deref().map(|annotation| (annotation, true))
+        }
+        _ => param
+            .pattern
+            .type_annotation
+            .as_deref()
+            .map(|annotation| (annotation, param.pattern.optional)),
+    }
+}
+
+impl<'a> IsolatedDeclarations<'a> {
+    /// Declarations of an exported function component, see
+    /// [`crate::IsolatedDeclarationsOptions::jsx_components`].
+    ///
+    /// ```tsx
+    /// export function Button({ label }: { label: string }) {
+    ///   return <button>{label}</button>;
+    /// }
+    /// // export type ButtonProps = { label: string };
+    /// // export declare const Button: (props: ButtonProps) => JSX.Element;
+    /// ```
+    ///
+    /// `None` if the declaration is not a function component.
+    pub fn transform_export_jsx_component(
+        &mut self,
+        decl: &ExportNamedDeclaration<'a>,
+    ) -> Option<Vec<ExportNamedDeclaration<'a>>> {
+        let component = JsxComponent::from_declaration(decl.declaration.as_ref()?)?;
+        let mut declarations = vec![];
+
+        let props = component.params.items.first().and_then(props_type_annotation);
+        let params = match props {
+            Some((annotation, optional)) => {
+                // A type reference already names the props, `(props: ButtonProps)`.
+                let props_type = if let TSType::TSTypeReference(_) = annotation.type_annotation {
+                    self.ast.copy(&annotation.type_annotation)
+                } else {
+                    let name = self.create_unique_name(&format!("{}Props", component.id.name));
+                    declarations.push(self.export_declaration(self.ast.declaration_ts_type_alias(
+                        SPAN,
+                        self.ast.binding_identifier(SPAN, name.clone()),
+                        Option::<TSTypeParameterDeclaration>::None,
+                        self.ast.copy(&annotation.type_annotation),
+                        false,
+                    )));
+                    self.ast.ts_type_type_reference(
+                        SPAN,
+                        self.ast.ts_type_name_identifier_reference(SPAN, name),
+                        Option::<TSTypeParameterInstantiation>::None,
+                    )
+                };
+                let pattern = self.ast.binding_pattern(
+                    self.ast.binding_pattern_kind_binding_identifier(SPAN, "props"),
+                    Some(self.ast.ts_type_annotation(SPAN, props_type)),
+                    optional,
+                );
+                self.ast.vec1(self.ast.formal_parameter(
+                    SPAN,
+                    self.ast.vec(),
+                    pattern,
+                    None,
+                    false,
+                    false,
+                ))
+            }
+            None => self.ast.vec(),
+        };
+
+        // JSX.Element
+        let jsx_element = self.ast.ts_type_type_reference(
+            SPAN,
+            self.ast.ts_type_name_qualified_name(
+                SPAN,
+                self.ast.ts_type_name_identifier_reference(SPAN, "JSX"),
+                self.ast.identifier_name(SPAN, "Element"),
+            ),
+            Option::<TSTypeParameterInstantiation>::None,
+        );
+        let function_type = self.ast.ts_type_function_type(
+            SPAN,
+            None,
+            self.ast.alloc_formal_parameters(
+                SPAN,
+                FormalParameterKind::Signature,
+                params,
+                Option::<BindingRestElement>::None,
+            ),
+            self.ast.alloc_ts_type_annotation(SPAN, jsx_element),
+            Option::<TSTypeParameterDeclaration>::None,
+        );
+        let id = self.ast.binding_pattern(
+            self.ast.binding_pattern_kind_binding_identifier(
+                component.id.span,
+                component.id.name.clone(),
+            ),
+            Some(self.ast.ts_type_annotation(SPAN, function_type)),
+            false,
+        );
+        let kind = VariableDeclarationKind::Const;
+        let declarator = self.ast.variable_declarator(SPAN, kind, id, None, false);
+        declarations.push(self.export_declaration(self.ast.declaration_variable(
+            decl.span,
+            kind,
+            self.ast.vec1(declarator),
+            self.is_declare(),
+        )));
+        Some(declarations)
+    }
+
+    fn export_declaration(&self, declaration: Declaration<'a>) -> ExportNamedDeclaration<'a> {
+        self.ast.export_named_declaration(
+            SPAN,
+            Some(declaration),
+            self.ast.vec(),
+            None,
+            ImportOrExportKind::Value,
+            None,
+        )
+    }
+}
@@ -12,54 +12,144 @@ mod r#enum;
 mod formal_parameter_binding_pattern;
 mod function;
 mod inferrer;
+mod jsx_component;
 mod literal;
 mod module;
+mod normalize;
+mod options;
 mod return_type;
 mod scope;
 mod types;
 
 use std::{cell::RefCell, collections::VecDeque, mem};
 
-use diagnostics::function_with_assigning_properties;
+use diagnostics::{function_with_assigning_properties, not_a_declaration};
 use oxc_allocator::Allocator;
 #[allow(clippy::wildcard_imports)]
-use oxc_ast::{ast::*, AstBuilder, Visit};
+use oxc_ast::{ast::*, AstBuilder, Visit, VisitMut};
 use oxc_diagnostics::OxcDiagnostic;
-use oxc_span::{Atom, SourceType, SPAN};
+use oxc_span::{Atom, GetSpan, SourceType, SPAN};
 use rustc_hash::FxHashSet;
 
-use crate::scope::ScopeTree;
+pub use crate::options::IsolatedDeclarationsOptions;
+use crate::{normalize::Normalizer, scope::ScopeTree};
 
 pub struct IsolatedDeclarationsReturn<'a> {
     pub program: Program<'a>,
     pub errors: Vec<OxcDiagnostic>,
+    /// Lines to print before the program, see [`IsolatedDeclarationsOptions::header`].
+    ///
+    /// Print it with the codegen, `CodeGenerator::new().with_banner(&ret.header)`, so source
+    /// maps of the declarations account for it.
+    pub header: String,
+}
+
+/// The result of [`IsolatedDeclarations::transform_declaration`].
+pub struct TransformDeclarationReturn<'a> {
+    /// The declaration, `None` if the statement is not a declaration.
+    pub statement: Option<Statement<'a>>,
+    pub errors: Vec<OxcDiagnostic>,
 }
 
 pub struct IsolatedDeclarations<'a> {
     ast: AstBuilder<'a>,
+    options: IsolatedDeclarationsOptions,
     // state
     scope: ScopeTree<'a>,
     errors: RefCell<Vec<OxcDiagnostic>>,
+    /// Every identifier name in the source program, used to avoid collisions with synthesized names.
+    used_names: FxHashSet<Atom<'a>>,
 }
 
 impl<'a> IsolatedDeclarations<'a> {
     pub fn new(allocator: &'a Allocator) -> Self {
         Self {
             ast: AstBuilder::new(allocator),
+            options: IsolatedDeclarationsOptions::default(),
             scope: ScopeTree::new(allocator),
             errors: RefCell::new(vec![]),
+            used_names: FxHashSet::default(),
         }
     }
 
-    /// # Errors
-    ///
-    /// Returns `Vec<Error>` if any errors were collected during the transformation.
+    #[must_use]
+    pub fn with_options(mut self, options: IsolatedDeclarationsOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Declarations are emitted even when errors are reported. Types which can not be inferred
+    /// become `unknown`, e.g. `export function f() { return g() }` becomes
+    /// `export declare function f(): unknown;` alongside the error, so editors and
+    /// bundlers still get the rest of the file. Only what can not be written in a `.d.ts` at
+    /// all is left out, such as destructured variables and members with invalid computed keys.
     pub fn build(mut self, program: &Program<'a>) -> IsolatedDeclarationsReturn<'a> {
+        self.collect_used_names(program);
         let source_type = SourceType::default().with_module(true).with_typescript_definition(true);
         let directives = self.ast.vec();
-        let stmts = self.transform_program(program);
+        let mut stmts = self.transform_program(program);
+        if self.options.normalize {
+            Normalizer::new(self.ast).visit_statements(&mut stmts);
+        }
         let program = self.ast.program(SPAN, source_type, None, directives, stmts);
-        IsolatedDeclarationsReturn { program, errors: self.take_errors() }
+        IsolatedDeclarationsReturn {
+            program,
+            errors: self.take_errors(),
+            header: self.options.header(),
+        }
+    }
+
+    /// Transform a single statement, for editors which need the declaration of one node, e.g. to
+    /// show its signature on hover or to suggest adding an explicit type annotation.
+    ///
+    /// Unlike [`IsolatedDeclarations::build`], the rest of the file is not looked at, so
+    /// function overloads are not merged, unexported declarations referenced by the statement are
+    /// not emitted, and synthesized names are only unique within the statement.
+    /// For `export default <expression>`, the `declare const _default: T;` declaration holding
+    /// the inferred type is returned.
+    ///
+    /// Like [`IsolatedDeclarations::build`], the declaration is returned alongside the errors
+    /// collected while transforming it. An error and no statement are returned if the statement
+    /// is not a declaration.
+    pub fn transform_declaration(
+        &mut self,
+        stmt: &Statement<'a>,
+    ) -> TransformDeclarationReturn<'a> {
+        UsedNames(&mut self.used_names).visit_statement(stmt);
+        let new_stmt = match stmt {
+            match_declaration!(Statement) => {
+                let decl = stmt.to_declaration();
+                self.transform_declaration_with_binding_check(decl, false)
+                    .map_or_else(|| self.ast.copy(stmt), Statement::from)
+            }
+            Statement::ExportNamedDeclaration(decl) => {
+                self.transform_export_named_declaration(decl).map_or_else(
+                    || self.ast.copy(stmt),
+                    |decl| Statement::ExportNamedDeclaration(self.ast.alloc(decl)),
+                )
+            }
+            Statement::ExportDefaultDeclaration(decl) => {
+                match self.transform_export_default_declaration(decl) {
+                    Some((Some(var_decl), _)) => {
+                        Statement::VariableDeclaration(self.ast.alloc(var_decl))
+                    }
+                    Some((None, decl)) => Statement::ExportDefaultDeclaration(self.ast.alloc(decl)),
+                    None => self.ast.copy(stmt),
+                }
+            }
+            match_module_declaration!(Statement) => self.ast.copy(stmt),
+            _ => {
+                return TransformDeclarationReturn {
+                    statement: None,
+                    errors: vec![not_a_declaration(stmt.span())],
+                }
+            }
+        };
+        TransformDeclarationReturn { statement: Some(new_stmt), errors: self.take_errors() }
+    }
+
+    fn collect_used_names(&mut self, program: &Program<'a>) {
+        UsedNames(&mut self.used_names).visit_program(program);
     }
 
     fn take_errors(&self) -> Vec<OxcDiagnostic> {
@@ -70,6 +160,24 @@ impl<'a> IsolatedDeclarations<'a> {
     fn error(&self, error: OxcDiagnostic) {
         self.errors.borrow_mut().push(error);
     }
+
+    /// `: unknown`, in place of a type annotation which could not be inferred, so the
+    /// declaration is still emitted along with the error.
+    fn unknown_type_annotation(&self) -> oxc_allocator::Box<'a, TSTypeAnnotation<'a>> {
+        self.ast.alloc_ts_type_annotation(SPAN, self.ast.ts_type_unknown_keyword(SPAN))
+    }
+}
+
+struct UsedNames<'a, 'b>(&'b mut FxHashSet<Atom<'a>>);
+
+impl<'a> Visit<'a> for UsedNames<'a, '_> {
+    fn visit_binding_identifier(&mut self, ident: &BindingIdentifier<'a>) {
+        self.0.insert(ident.name.clone());
+    }
+
+    fn visit_identifier_reference(&mut self, ident: &IdentifierReference<'a>) {
+        self.0.insert(ident.name.clone());
+    }
 }
 
 impl<'a> IsolatedDeclarations<'a> {
@@ -101,7 +209,7 @@ impl<'a> IsolatedDeclarations<'a> {
         let mut new_ast_stmts = self.ast.vec::<Statement<'a>>();
         for stmt in Self::remove_function_overloads_implementation(self.ast.copy(stmts)) {
             if let Some(decl) = stmt.as_declaration() {
-                if let Some(decl) = self.transform_declaration(decl, false) {
+                if let Some(decl) = self.transform_declaration_with_binding_check(decl, false) {
                     new_ast_stmts.push(Statement::from(decl));
                 } else {
                     new_ast_stmts.push(Statement::from(self.ast.copy(decl)));
@@ -170,6 +278,9 @@ impl<'a> IsolatedDeclarations<'a> {
                                 }
 
                                 self.scope.visit_export_default_declaration(&new_decl);
+                                if let Some(id) = Self::export_default_declaration_id(&new_decl) {
+                                    self.scope.add_merged_declaration_reference(id.name.clone());
+                                }
                                 new_stmts.push(Statement::ExportDefaultDeclaration(
                                     self.ast.alloc(new_decl),
                                 ));
@@ -181,11 +292,30 @@ impl<'a> IsolatedDeclarations<'a> {
                         }
 
                         ModuleDeclaration::ExportNamedDeclaration(decl) => {
+                            if self.options.jsx_components {
+                                if let Some(new_decls) = self.transform_export_jsx_component(decl) {
+                                    for new_decl in new_decls {
+                                        let declaration = new_decl
+                                            .declaration
+                                            .as_ref()
+                                            .unwrap_or_else(|| unreachable!());
+                                        self.scope.visit_declaration(declaration);
+                                        transformed_indexes.insert(new_stmts.len());
+                                        new_stmts.push(Statement::ExportNamedDeclaration(
+                                            self.ast.alloc(new_decl),
+                                        ));
+                                    }
+                                    continue;
+                                }
+                            }
                             transformed_indexes.insert(new_stmts.len());
                             if let Some(new_decl) = self.transform_export_named_declaration(decl) {
-                                self.scope.visit_declaration(
-                                    new_decl.declaration.as_ref().unwrap_or_else(|| unreachable!()),
-                                );
+                                let declaration =
+                                    new_decl.declaration.as_ref().unwrap_or_else(|| unreachable!());
+                                self.scope.visit_declaration(declaration);
+                                if let Some(id) = declaration.id() {
+                                    self.scope.add_merged_declaration_reference(id.name.clone());
+                                }
 
                                 new_stmts.push(Statement::ExportNamedDeclaration(
                                     self.ast.alloc(new_decl),
@@ -246,7 +376,8 @@ impl<'a> IsolatedDeclarations<'a> {
                             *declarator = decl;
                         }
                     }
-                } else if let Some(decl) = self.transform_declaration(decl, true) {
+                } else if let Some(decl) = self.transform_declaration_with_binding_check(decl, true)
+                {
                     self.scope.visit_declaration(&decl);
                     transformed_indexes.insert(i);
                     *stmt = Statement::from(decl);
@@ -322,7 +453,7 @@ impl<'a> IsolatedDeclarations<'a> {
             need_empty_export_marker = false;
         }
 
-        if need_empty_export_marker {
+        if need_empty_export_marker && self.options.emit_empty_export {
             let specifiers = self.ast.vec();
             let kind = ImportOrExportKind::Value;
             let empty_export =
@@ -10,7 +10,8 @@ impl<'a> IsolatedDeclarations<'a> {
         &mut self,
         decl: &ExportNamedDeclaration<'a>,
     ) -> Option<ExportNamedDeclaration<'a>> {
-        let decl = self.transform_declaration(decl.declaration.as_ref()?, false)?;
+        let decl =
+            self.transform_declaration_with_binding_check(decl.declaration.as_ref()?, false)?;
 
         Some(ExportNamedDeclaration {
             span: decl.span(),
@@ -22,13 +23,16 @@ impl<'a> IsolatedDeclarations<'a> {
         })
     }
 
+    /// Create a name that is not used anywhere in the source program, by appending `_1`, `_2`, ...
+    /// to `name` until it is unique. The result is reserved so it is never handed out twice.
     pub fn create_unique_name(&mut self, name: &str) -> Atom<'a> {
         let mut binding = self.ast.atom(name);
         let mut i = 1;
-        while self.scope.has_reference(&binding) {
+        while self.used_names.contains(&binding) {
             binding = self.ast.atom(format!("{name}_{i}").as_str());
             i += 1;
         }
+        self.used_names.insert(binding.clone());
         binding
     }
 
@@ -53,17 +57,18 @@ impl<'a> IsolatedDeclarations<'a> {
                 } else {
                     // declare const _default: Type
                     let kind = VariableDeclarationKind::Const;
-                    let name = self.create_unique_name("_default");
+                    let default_export_name = self.options.default_export_name.clone();
+                    let name = self.create_unique_name(&default_export_name);
                     let id = self.ast.binding_pattern_kind_binding_identifier(SPAN, &name);
-                    let type_annotation = self
-                        .infer_type_from_expression(expr)
-                        .map(|ts_type| self.ast.ts_type_annotation(SPAN, ts_type));
+                    let type_annotation = self.infer_type_from_expression(expr).map_or_else(
+                        || {
+                            self.error(default_export_inferred(expr.span()));
+                            self.unknown_type_annotation()
+                        },
+                        |ts_type| self.ast.alloc_ts_type_annotation(SPAN, ts_type),
+                    );
 
-                    if type_annotation.is_none() {
-                        self.error(default_export_inferred(expr.span()));
-                    }
-
-                    let id = self.ast.binding_pattern(id, type_annotation, false);
+                    let id = self.ast.binding_pattern(id, Some(type_annotation), false);
                     let declarations =
                         self.ast.vec1(self.ast.variable_declarator(SPAN, kind, id, None, true));
 
@@ -89,6 +94,18 @@ impl<'a> IsolatedDeclarations<'a> {
         })
     }
 
+    /// Name of the function, class or interface declared by `export default`.
+    pub fn export_default_declaration_id<'b>(
+        decl: &'b ExportDefaultDeclaration<'a>,
+    ) -> Option<&'b BindingIdentifier<'a>> {
+        match &decl.declaration {
+            ExportDefaultDeclarationKind::FunctionDeclaration(func) => func.id.as_ref(),
+            ExportDefaultDeclarationKind::ClassDeclaration(class) => class.id.as_ref(),
+            ExportDefaultDeclarationKind::TSInterfaceDeclaration(decl) => Some(&decl.id),
+            _ => None,
+        }
+    }
+
     pub fn transform_import_declaration(
         &self,
         decl: &ImportDeclaration<'a>,
@@ -0,0 +1,130 @@
+use std::mem;
+
+#[allow(clippy::wildcard_imports)]
+use oxc_ast::{ast::*, visit::walk_mut, AstBuilder, VisitMut};
+use oxc_codegen::{Context, Gen, WhitespaceRemover};
+use rustc_hash::{FxHashMap, FxHashSet};
+
+/// Rewrites the emitted declarations into a canonical form, see
+/// [`crate::IsolatedDeclarationsOptions::normalize`].
+///
+/// Nodes are compared by their printed code, so two members are identical when they print the
+/// same, regardless of spans or comments.
+pub struct Normalizer<'a> {
+    ast: AstBuilder<'a>,
+}
+
+impl<'a> Normalizer<'a> {
+    pub fn new(ast: AstBuilder<'a>) -> Self {
+        Self { ast }
+    }
+
+    /// Merges the declarations of an interface which is declared more than once in `stmts`
+    /// into the first declaration.
+    ///
+    /// Declarations are only merged when they are all exported or all local, and declare the
+    /// same type parameters, as TypeScript requires. Later declarations are merged in front,
+    /// because their overloads take precedence over the overloads of earlier declarations.
+    fn merge_interfaces(&self, stmts: &mut oxc_allocator::Vec<'a, Statement<'a>>) {
+        let mut first_indexes = FxHashMap::default();
+        let mut merged = self.ast.vec_with_capacity(stmts.len());
+        for mut stmt in mem::replace(stmts, self.ast.vec()) {
+            let Some((decl, exported)) = interface_mut(&mut stmt) else {
+                merged.push(stmt);
+                continue;
+            };
+            let key = (decl.id.name.clone(), exported, decl.type_parameters.as_ref().map(print));
+            let Some(&index) = first_indexes.get(&key) else {
+                first_indexes.insert(key, merged.len());
+                merged.push(stmt);
+                continue;
+            };
+            let Some((first, _)) = merged.get_mut(index).and_then(interface_mut) else {
+                unreachable!()
+            };
+            merge_interface(first, decl, self.ast);
+        }
+        *stmts = merged;
+    }
+}
+
+impl<'a> VisitMut<'a> for Normalizer<'a> {
+    fn visit_statements(&mut self, stmts: &mut oxc_allocator::Vec<'a, Statement<'a>>) {
+        self.merge_interfaces(stmts);
+        walk_mut::walk_statements(self, stmts);
+    }
+
+    fn visit_ts_union_type(&mut self, ty: &mut TSUnionType<'a>) {
+        walk_mut::walk_ts_union_type(self, ty);
+        ty.types.sort_by_cached_key(print);
+    }
+
+    fn visit_ts_interface_body(&mut self, body: &mut TSInterfaceBody<'a>) {
+        walk_mut::walk_ts_interface_body(self, body);
+        sort_property_signatures(&mut body.body);
+    }
+
+    fn visit_ts_type_literal(&mut self, ty: &mut TSTypeLiteral<'a>) {
+        walk_mut::walk_ts_type_literal(self, ty);
+        sort_property_signatures(&mut ty.members);
+    }
+}
+
+fn interface_mut<'s, 'a>(
+    stmt: &'s mut Statement<'a>,
+) -> Option<(&'s mut TSInterfaceDeclaration<'a>, bool)> {
+    match stmt {
+        Statement::TSInterfaceDeclaration(decl) => Some((decl, false)),
+        Statement::ExportNamedDeclaration(export_decl) => match &mut export_decl.declaration {
+            Some(Declaration::TSInterfaceDeclaration(decl)) => Some((decl, true)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Moves the heritage clauses and members of `later` into `first`, dropping the ones which are
+/// already in `first`.
+fn merge_interface<'a>(
+    first: &mut TSInterfaceDeclaration<'a>,
+    later: &mut TSInterfaceDeclaration<'a>,
+    ast: AstBuilder<'a>,
+) {
+    if let Some(later_extends) = later.extends.take() {
+        let extends = first.extends.get_or_insert_with(|| ast.vec());
+        let mut printed = extends.iter().map(print).collect::<FxHashSet<_>>();
+        extends
+            .extend(later_extends.into_iter().filter(|heritage| printed.insert(print(heritage))));
+    }
+    let mut printed = FxHashSet::default();
+    let members = later.body.body.drain(..).chain(first.body.body.drain(..)).collect::<Vec<_>>();
+    first.body.body.extend(members.into_iter().filter(|member| printed.insert(print(member))));
+}
+
+/// Sorts the property signatures by name. Other members keep their positions, since the order
+/// of call signatures and method overloads is significant.
+fn sort_property_signatures(members: &mut oxc_allocator::Vec<'_, TSSignature<'_>>) {
+    let is_property = |member: &TSSignature| matches!(member, TSSignature::TSPropertySignature(_));
+    let layout = members.iter().map(is_property).collect::<Vec<_>>();
+    let (mut properties, others): (Vec<_>, Vec<_>) = members.drain(..).partition(is_property);
+    properties.sort_by_cached_key(|member| match member {
+        TSSignature::TSPropertySignature(signature) => {
+            signature.key.static_name().map_or_else(|| print(&signature.key), Into::into)
+        }
+        _ => unreachable!(),
+    });
+    let (mut properties, mut others) = (properties.into_iter(), others.into_iter());
+    members.extend(layout.into_iter().filter_map(|is_property| {
+        if is_property {
+            properties.next()
+        } else {
+            others.next()
+        }
+    }));
+}
+
+fn print<T: Gen<true>>(node: &T) -> String {
+    let mut codegen = WhitespaceRemover::new();
+    node.gen(&mut codegen, Context::empty());
+    codegen.into_source_text()
+}
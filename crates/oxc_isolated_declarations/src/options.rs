@@ -0,0 +1,118 @@
+/// Options for [`crate::IsolatedDeclarations`].
+#[derive(Debug, Clone)]
+pub struct IsolatedDeclarationsOptions {
+    /// Name of the variable declared for `export default <expression>`,
+    /// e.g. `declare const _default: number; export default _default;`.
+    ///
+    /// `_1`, `_2`, ... is appended when the name is already used in the source.
+    ///
+    /// Default `_default`
+    pub default_export_name: String,
+
+    /// Name of the private brand added to classes with `#private` members,
+    /// e.g. `declare class Foo { #private; }`.
+    ///
+    /// `_1`, `_2`, ... is appended when the class already has a private member with this name.
+    ///
+    /// Default `private`
+    pub private_brand_name: String,
+
+    /// Emit `export {}` when a module would otherwise lose all of its exports, or would
+    /// implicitly export its non-exported declarations.
+    ///
+    /// Without any `import` or `export`, a `.d.ts` file is a global script, which changes the
+    /// scope of every declaration in it. Disable this if the output is bundled or post-processed
+    /// into a module anyway.
+    ///
+    /// Default `true`
+    pub emit_empty_export: bool,
+
+    /// Emit declarations in a canonical form, so the output of equivalent sources diffs
+    /// cleanly, e.g. when generated `.d.ts` files are checked in and reviewed.
+    ///
+    /// * Members of union types are sorted.
+    /// * Property signatures of interfaces and type literals are sorted by name. Other members
+    ///   keep their positions, because the order of overloads is significant.
+    /// * Re-opened interfaces in the same scope are merged into their first declaration, and
+    ///   duplicate identical members and heritage clauses are removed.
+    ///
+    /// Only applies to [`crate::IsolatedDeclarations::build`].
+    ///
+    /// Default `false`
+    pub normalize: bool,
+
+    /// Declare exported function components by the type of their props, so `.d.ts` files of
+    /// component libraries can be generated without annotating the return type of every
+    /// component.
+    ///
+    /// A component is a function or a `const` function expression named in PascalCase, which
+    /// returns JSX and has no return type annotation, and whose only parameter, if any, has a
+    /// type annotation. A type alias `<Name>Props` is exported for the annotation, unless it is a
+    /// type reference already, and the component is declared as a function of it:
+    ///
+    /// ```tsx
+    /// export function Button({ label }: { label: string }) {
+    ///   return <button>{label}</button>;
+    /// }
+    /// // export type ButtonProps = { label: string };
+    /// // export declare const Button: (props: ButtonProps) => JSX.Element;
+    /// ```
+    ///
+    /// Only applies to named exports in [`crate::IsolatedDeclarations::build`].
+    ///
+    /// Default `false`
+    pub jsx_components: bool,
+
+    /// Text to print before the declarations, e.g. a license comment. It is printed as is, so
+    /// every line has to be a comment.
+    ///
+    /// Default `None`
+    pub banner: Option<String>,
+
+    /// Add `// @ts-nocheck`, so type errors in the declarations, e.g. of missing dependencies,
+    /// are not reported in projects which do not skip library checks.
+    ///
+    /// Default `false`
+    pub ts_nocheck: bool,
+
+    /// Add `// <auto-generated />`, which marks the file as generated for editors and linters.
+    ///
+    /// Default `false`
+    pub auto_generated: bool,
+}
+
+impl IsolatedDeclarationsOptions {
+    /// Lines to print before the declarations, from [`Self::auto_generated`], [`Self::banner`]
+    /// and [`Self::ts_nocheck`].
+    pub fn header(&self) -> String {
+        let mut header = String::new();
+        if self.auto_generated {
+            header.push_str("// <auto-generated />\n");
+        }
+        if let Some(banner) = &self.banner {
+            for line in banner.lines() {
+                header.push_str(line);
+                header.push('\n');
+            }
+        }
+        if self.ts_nocheck {
+            header.push_str("// @ts-nocheck\n");
+        }
+        header
+    }
+}
+
+impl Default for IsolatedDeclarationsOptions {
+    fn default() -> Self {
+        Self {
+            default_export_name: "_default".to_string(),
+            private_brand_name: "private".to_string(),
+            emit_empty_export: true,
+            normalize: false,
+            jsx_components: false,
+            banner: None,
+            ts_nocheck: false,
+            auto_generated: false,
+        }
+    }
+}
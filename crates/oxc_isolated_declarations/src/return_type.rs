@@ -1,10 +1,12 @@
-use std::cell::Cell;
+use std::{cell::Cell, mem};
 
 use oxc_ast::{
     ast::{
         ArrowFunctionExpression, BindingIdentifier, Expression, Function, FunctionBody,
         ReturnStatement, TSType, TSTypeAliasDeclaration, TSTypeName, TSTypeQueryExprName,
+        YieldExpression,
     },
+    visit::walk,
     AstBuilder, Visit,
 };
 use oxc_span::{Atom, GetSpan, SPAN};
@@ -34,14 +36,25 @@ use crate::{diagnostics::type_containing_private_name, IsolatedDeclarations};
 ///  return 1;
 /// }
 /// // We can't infer return type if there are multiple return statements with different types
+///
+/// class Builder {
+///   add() {
+///     return this;
+///   }
+/// }
+/// // inferred type is this
 /// ```
 #[allow(clippy::option_option)]
 pub struct FunctionReturnType<'a> {
     ast: AstBuilder<'a>,
     return_expression: Option<Option<Expression<'a>>>,
+    /// Type of `this`, see [`IsolatedDeclarations::infer_method_return_type`].
+    this_type: Option<TSType<'a>>,
     value_bindings: Vec<Atom<'a>>,
     type_bindings: Vec<Atom<'a>>,
     return_statement_count: u8,
+    yield_expressions: Vec<Option<Expression<'a>>>,
+    has_delegated_yield: bool,
     scope_depth: u32,
 }
 
@@ -49,20 +62,86 @@ impl<'a> FunctionReturnType<'a> {
     pub fn infer(
         transformer: &IsolatedDeclarations<'a>,
         body: &FunctionBody<'a>,
+        this_type: Option<TSType<'a>>,
+    ) -> Option<TSType<'a>> {
+        Self::collect(transformer, body, this_type).infer_return_type(transformer)
+    }
+
+    /// Infer the return type of an `async` function or a generator.
+    /// ```ts
+    /// async function foo() {
+    ///   return 1;
+    /// }
+    /// // inferred type is Promise<number>
+    ///
+    /// async function bar() {}
+    /// // inferred type is Promise<void>
+    ///
+    /// function* baz() {
+    ///   yield 1;
+    /// }
+    /// // inferred type is Generator<number, void, unknown>
+    ///
+    /// async function* qux() {
+    ///   yield "a";
+    ///   return 1;
+    /// }
+    /// // inferred type is AsyncGenerator<string, number, unknown>
+    /// ```
+    pub fn infer_async_or_generator(
+        transformer: &IsolatedDeclarations<'a>,
+        body: &FunctionBody<'a>,
+        this_type: Option<TSType<'a>>,
+        is_async: bool,
+        is_generator: bool,
     ) -> Option<TSType<'a>> {
+        let visitor = Self::collect(transformer, body, this_type);
+
+        let return_type = match &visitor.return_expression {
+            Some(Some(_)) => visitor.infer_return_type(transformer)?,
+            // Conflicting return statements
+            None if visitor.return_statement_count > 0 => return None,
+            // No return statements, or only empty ones
+            _ => transformer.ast.ts_type_void_keyword(SPAN),
+        };
+
+        if !is_generator {
+            return Some(transformer.type_reference("Promise", [return_type]));
+        }
+
+        let yield_type = visitor.infer_yield_type(transformer)?;
+        let name = if is_async { "AsyncGenerator" } else { "Generator" };
+        let next_type = transformer.ast.ts_type_unknown_keyword(SPAN);
+        Some(transformer.type_reference(name, [yield_type, return_type, next_type]))
+    }
+
+    fn collect(
+        transformer: &IsolatedDeclarations<'a>,
+        body: &FunctionBody<'a>,
+        this_type: Option<TSType<'a>>,
+    ) -> Self {
         let mut visitor = FunctionReturnType {
             ast: transformer.ast,
             return_expression: None,
+            this_type,
             return_statement_count: 0,
+            yield_expressions: Vec::default(),
+            has_delegated_yield: false,
             scope_depth: 0,
             value_bindings: Vec::default(),
             type_bindings: Vec::default(),
         };
-
         visitor.visit_function_body(body);
+        visitor
+    }
 
-        let expr = visitor.return_expression??;
-        let Some(mut expr_type) = transformer.infer_type_from_expression(&expr) else {
+    fn infer_return_type(&self, transformer: &IsolatedDeclarations<'a>) -> Option<TSType<'a>> {
+        let expr = self.return_expression.as_ref()?.as_ref()?;
+        let expr_type = match (expr.without_parenthesized(), &self.this_type) {
+            (Expression::ThisExpression(_), Some(this_type)) => Some(self.ast.copy(this_type)),
+            _ => transformer.infer_type_from_expression(expr),
+        };
+        let Some(mut expr_type) = expr_type else {
             // Avoid report error in parent function
             return if expr.is_function() {
                 Some(transformer.ast.ts_type_unknown_keyword(SPAN))
@@ -89,9 +168,9 @@ impl<'a> FunctionReturnType<'a> {
             _ => None,
         } {
             let is_defined_in_current_scope = if is_value {
-                visitor.value_bindings.contains(&reference_name)
+                self.value_bindings.contains(&reference_name)
             } else {
-                visitor.type_bindings.contains(&reference_name)
+                self.type_bindings.contains(&reference_name)
             };
 
             if is_defined_in_current_scope {
@@ -105,7 +184,7 @@ impl<'a> FunctionReturnType<'a> {
         }
 
         //
-        if visitor.return_statement_count > 1 {
+        if self.return_statement_count > 1 {
             let types = transformer
                 .ast
                 .vec_from_iter([expr_type, transformer.ast.ts_type_undefined_keyword(SPAN)]);
@@ -113,6 +192,31 @@ impl<'a> FunctionReturnType<'a> {
         }
         Some(expr_type)
     }
+
+    /// `never` without yields. Multiple yields must have the same keyword type, e.g. `number`.
+    fn infer_yield_type(&self, transformer: &IsolatedDeclarations<'a>) -> Option<TSType<'a>> {
+        // `yield*` yields the values of another iterable
+        if self.has_delegated_yield {
+            return None;
+        }
+        let mut yield_type: Option<TSType<'a>> = None;
+        for expr in &self.yield_expressions {
+            let ty = match expr {
+                Some(expr) => transformer.infer_type_from_expression(expr)?,
+                None => transformer.ast.ts_type_undefined_keyword(SPAN),
+            };
+            if let Some(yield_type) = &yield_type {
+                let is_same_type = yield_type.is_keyword()
+                    && mem::discriminant(yield_type) == mem::discriminant(&ty);
+                if !is_same_type {
+                    return None;
+                }
+            } else {
+                yield_type = Some(ty);
+            }
+        }
+        Some(yield_type.unwrap_or_else(|| transformer.ast.ts_type_never_keyword(SPAN)))
+    }
 }
 
 impl<'a> Visit<'a> for FunctionReturnType<'a> {
@@ -159,4 +263,13 @@ impl<'a> Visit<'a> for FunctionReturnType<'a> {
         }
         self.return_expression = Some(self.ast.copy(&stmt.argument));
     }
+
+    fn visit_yield_expression(&mut self, expr: &YieldExpression<'a>) {
+        if expr.delegate {
+            self.has_delegated_yield = true;
+        } else {
+            self.yield_expressions.push(self.ast.copy(&expr.argument));
+        }
+        walk::walk_yield_expression(self, expr);
+    }
 }
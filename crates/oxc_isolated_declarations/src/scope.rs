@@ -59,6 +59,14 @@ impl<'a> ScopeTree<'a> {
         scope.value_references.len() + scope.type_references.len()
     }
 
+    /// Reference `name` as a value and a type, for the name of an exported declaration, so that
+    /// local declarations merged with it are emitted as well, e.g. the namespace of
+    /// `export default function f() {} namespace f {}`.
+    pub fn add_merged_declaration_reference(&mut self, name: Atom<'a>) {
+        self.add_type_reference(name.clone());
+        self.add_value_reference(name);
+    }
+
     fn add_value_binding(&mut self, ident: Atom<'a>) {
         let scope = self.levels.last_mut().unwrap();
         scope.value_bindings.insert(ident);
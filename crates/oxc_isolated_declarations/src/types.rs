@@ -15,49 +15,44 @@ use crate::{
 };
 
 impl<'a> IsolatedDeclarations<'a> {
-    pub fn transform_function_to_ts_type(&self, func: &Function<'a>) -> Option<TSType<'a>> {
-        let return_type = self.infer_function_return_type(func);
-        if return_type.is_none() {
+    pub fn transform_function_to_ts_type(&self, func: &Function<'a>) -> TSType<'a> {
+        let return_type = self.infer_function_return_type(func).unwrap_or_else(|| {
             self.error(function_must_have_explicit_return_type(get_function_span(func)));
-        }
+            self.unknown_type_annotation()
+        });
 
         let params = self.transform_formal_parameters(&func.params);
 
-        return_type.map(|return_type| {
-            self.ast.ts_type_function_type(
-                func.span,
-                self.ast.copy(&func.this_param),
-                params,
-                return_type,
-                self.ast.copy(&func.type_parameters),
-            )
-        })
+        self.ast.ts_type_function_type(
+            func.span,
+            self.ast.copy(&func.this_param),
+            params,
+            return_type,
+            self.ast.copy(&func.type_parameters),
+        )
     }
 
     pub fn transform_arrow_function_to_ts_type(
         &self,
         func: &ArrowFunctionExpression<'a>,
-    ) -> Option<TSType<'a>> {
-        let return_type = self.infer_arrow_function_return_type(func);
-
-        if return_type.is_none() {
+    ) -> TSType<'a> {
+        let return_type = self.infer_arrow_function_return_type(func).unwrap_or_else(|| {
             self.error(function_must_have_explicit_return_type(Span::new(
                 func.params.span.start,
                 func.body.span.start + 1,
             )));
-        }
+            self.unknown_type_annotation()
+        });
 
         let params = self.transform_formal_parameters(&func.params);
 
-        return_type.map(|return_type| {
-            self.ast.ts_type_function_type(
-                func.span,
-                None,
-                params,
-                return_type,
-                self.ast.copy(&func.type_parameters),
-            )
-        })
+        self.ast.ts_type_function_type(
+            func.span,
+            None,
+            params,
+            return_type,
+            self.ast.copy(&func.type_parameters),
+        )
     }
 
     /// Transform object expression to TypeScript type
@@ -86,7 +81,14 @@ impl<'a> IsolatedDeclarations<'a> {
 
                     if object.shorthand {
                         self.error(shorthand_property(object.span));
-                        return None;
+                        return Some(self.ast.ts_signature_property_signature(
+                            object.span,
+                            false,
+                            false,
+                            is_const,
+                            self.ast.copy(&object.key),
+                            Some(self.unknown_type_annotation()),
+                        ));
                     }
 
                     if let Expression::FunctionExpression(function) = &object.value {
@@ -113,10 +115,13 @@ impl<'a> IsolatedDeclarations<'a> {
                         self.infer_type_from_expression(&object.value)
                     };
 
-                    if type_annotation.is_none() {
-                        self.error(inferred_type_of_expression(object.value.span()));
-                        return None;
-                    }
+                    let type_annotation = type_annotation.map_or_else(
+                        || {
+                            self.error(inferred_type_of_expression(object.value.span()));
+                            self.unknown_type_annotation()
+                        },
+                        |type_annotation| self.ast.alloc_ts_type_annotation(SPAN, type_annotation),
+                    );
 
                     let property_signature = self.ast.ts_signature_property_signature(
                         object.span,
@@ -124,9 +129,7 @@ impl<'a> IsolatedDeclarations<'a> {
                         false,
                         is_const,
                         self.ast.copy(&object.key),
-                        type_annotation.map(|type_annotation| {
-                            self.ast.ts_type_annotation(SPAN, type_annotation)
-                        }),
+                        Some(type_annotation),
                     );
                     Some(property_signature)
                 }
@@ -207,16 +210,18 @@ impl<'a> IsolatedDeclarations<'a> {
             Expression::ObjectExpression(expr) => {
                 Some(self.transform_object_expression_to_ts_type(expr, true))
             }
-            Expression::FunctionExpression(func) => self.transform_function_to_ts_type(func),
+            Expression::FunctionExpression(func) => Some(self.transform_function_to_ts_type(func)),
             Expression::ArrowFunctionExpression(func) => {
-                self.transform_arrow_function_to_ts_type(func)
+                Some(self.transform_arrow_function_to_ts_type(func))
             }
             Expression::TSAsExpression(expr) => {
-                if expr.type_annotation.is_const_type_reference() {
-                    self.transform_expression_to_ts_type(&expr.expression)
-                } else {
-                    Some(self.ast.copy(&expr.type_annotation))
-                }
+                self.infer_type_from_type_assertion(&expr.expression, &expr.type_annotation)
+            }
+            Expression::TSTypeAssertion(expr) => {
+                self.infer_type_from_type_assertion(&expr.expression, &expr.type_annotation)
+            }
+            Expression::TSSatisfiesExpression(expr) => {
+                self.transform_expression_to_ts_type(&expr.expression)
             }
             _ => None,
         }
@@ -11,8 +11,8 @@ class AsyncClassGood {
   }
 }
 
-// Need to explicit return type for async functions
-// Incorrect
+// Return types are inferred as `Promise<...>`
+// from the return statements
 async function asyncFunction() {
   return 42;
 }
@@ -25,4 +25,22 @@ class AsyncClassBad {
   async method() {
     return 42;
   }
-}
\ No newline at end of file
+}
+
+// Inferred
+async function asyncFunctionVoid() {}
+
+async function asyncFunctionEmptyReturn() {
+  if (a) {
+    return;
+  }
+}
+
+const asyncArrowExpression = async () => 42;
+
+async function asyncFunctionMultipleReturns() {
+  if (a) {
+    return;
+  }
+  return "a";
+}
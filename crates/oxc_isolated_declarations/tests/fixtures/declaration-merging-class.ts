@@ -0,0 +1,7 @@
+export default class C {}
+namespace C {
+  export type T = string;
+}
+interface C {
+  x: number;
+}
@@ -0,0 +1,29 @@
+export default function f(): void {}
+namespace f {
+  export const x = 1;
+}
+
+export function g(): void {}
+export namespace g {
+  export const y: number = 2;
+}
+
+function h(): void {}
+namespace h {
+  export const z = "z";
+}
+export { h };
+
+export function o(a: string): void;
+export function o(a: number): void;
+export function o(a: any): void {}
+export namespace o {
+  export const n = 0;
+}
+
+export enum E {
+  A,
+}
+export namespace E {
+  export function f(): void {}
+}
@@ -10,8 +10,8 @@ class GeneratorClassGood {
 
 
 
-// Need to explicit return type for async functions
-// Incorrect
+// Return types are inferred as `Generator<...>`
+// from the yield and return statements
 function *generatorBad() {
   yield 50;
   return 42;
@@ -22,4 +22,27 @@ class GeneratorClassBad {
     yield 50;
     return 42;
   }
-}
\ No newline at end of file
+}
+
+// Inferred
+function *generatorYieldOnly() {
+  yield 1;
+  yield 2;
+}
+
+function *generatorEmpty() {}
+
+async function *asyncGenerator() {
+  yield "a";
+  return 42;
+}
+
+// Mixed yield types and `yield*` need an explicit return type
+function *generatorMixed() {
+  yield 1;
+  yield "a";
+}
+
+function *generatorDelegate() {
+  yield* generatorEmpty();
+}
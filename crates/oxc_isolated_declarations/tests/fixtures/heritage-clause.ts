@@ -0,0 +1,19 @@
+declare function mixin<T>(base: T): T;
+declare const condition: boolean;
+declare const ns: { Base: new () => object; nested: { Base: new () => object } };
+declare class Base<T> {}
+declare class Other {}
+
+export class A extends Base<string> {}
+
+export class B extends ns.Base {}
+
+export class C extends ns.nested.Base {}
+
+export class D extends mixin(Base)<number> {}
+
+export class E extends (condition ? Base : Other) {}
+
+export class F extends ns["Base"] {}
+
+export class G extends mixin(Base) implements Other {}
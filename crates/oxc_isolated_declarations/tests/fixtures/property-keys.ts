@@ -0,0 +1,8 @@
+export const o = { 'a-b': 1, "c'd": 2, 0x10: 3, 1e3: 4, "é": 5, abc: 6, 'plain': 7, 1_000: 8 };
+export class K { 'x-y': number = 1; 0b11 = 2; "\x41" = 3; b = 4 }
+export interface I { 'q-r': string; 0o7: number; "\u{1F600}": 1 }
+export type T = { 'a': 1, 2: 2 };
+export enum E { 'a-b' = 1, "c" = 2 }
+export const p = { abc: 1, "b": 2 };
+export interface J { \u0061b: 1; "\
+c": 2 }
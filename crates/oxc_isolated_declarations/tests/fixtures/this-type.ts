@@ -0,0 +1,36 @@
+export class Builder {
+  value = "";
+
+  add(text: string) {
+    this.value += text;
+    return this;
+  }
+
+  get self() {
+    return (this);
+  }
+
+  async flush() {
+    return this;
+  }
+
+  withThis(this: Builder) {
+    return this;
+  }
+
+  static create() {
+    return this;
+  }
+}
+
+export function withThisParam(this: Window, value: number) {
+  return this;
+}
+
+export const method = function (this: Date) {
+  return this;
+};
+
+export function withoutThisParam() {
+  return this;
+}
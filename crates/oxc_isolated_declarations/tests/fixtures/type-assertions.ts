@@ -0,0 +1,13 @@
+export const asType = 1 as number;
+export const angleType = <string>"a";
+export const asConst = "a" as const;
+export const asConstObject = { a: 1, b: [1, "b"] } as const;
+export const angleConst = <const>["a", 1];
+export const satisfiesObject = { a: 1 } satisfies Record<string, number>;
+export const satisfiesConst = { a: 1 } as const satisfies Record<string, number>;
+export const satisfiesCall = foo() satisfies string;
+export let letAsType = bar() as string;
+export let letAngleConst = <const>"b";
+export var nested = (baz() as unknown) as Map<string, number>;
+export const satisfiesCallConst = foo() as const satisfies string;
+export function params(a = <number>1, b = "b" as const, c = <Foo>{}): void {}
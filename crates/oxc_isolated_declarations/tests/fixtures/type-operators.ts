@@ -0,0 +1,21 @@
+export type Mutable<T> = { -readonly [K in keyof T]: T[K] };
+export type Frozen<T> = { +readonly [K in keyof T]+?: T[K] };
+export type Required<T> = { readonly [K in keyof T]-?: T[K] };
+export type Getters<T> = { [K in keyof T as `get${Capitalize<string & K>}`]: () => T[K] };
+export type Keys<T> = { [K in keyof T]?: K }[keyof T];
+
+export type Unwrap<T> = T extends Promise<infer U> ? Unwrap<U> : T;
+export type Nested<T> = T extends string ? "string" : T extends number ? "number" : "other";
+export type Distribute<T> = (T extends unknown ? T[] : never) | undefined;
+export type Checked<T> = (T extends string ? 1 : 2) extends 1 ? true : false;
+export type ElementOf<T> = (T extends readonly (infer E)[] ? E : never)[];
+export type Fn<T> = (() => T) | T;
+export type Constrained<T> = T extends [infer Head extends string, ...infer Tail] ? Head : never;
+
+export type Route = `/${string}/${number}`;
+export type EventName<T extends string> = `on${Capitalize<T>}` | `${T}Changed`;
+
+export interface Options<T> {
+  readonly map: { -readonly [K in keyof T]-?: T[K] extends Function ? never : K };
+  route: `${Lowercase<keyof T & string>}:${number}`;
+}
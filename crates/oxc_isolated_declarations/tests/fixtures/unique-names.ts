@@ -0,0 +1,13 @@
+const _default = 1;
+type _default_1 = string;
+
+export class Foo {
+  #private = 1;
+  #private_1(): void {}
+}
+
+export class Bar {
+  #bar = 1;
+}
+
+export default { value: 1 };
@@ -3,10 +3,11 @@ mod deno;
 use std::{fs, path::Path, sync::Arc};
 
 use oxc_allocator::Allocator;
-use oxc_codegen::CodeGenerator;
-use oxc_isolated_declarations::IsolatedDeclarations;
+use oxc_ast::AstBuilder;
+use oxc_codegen::{CodeGenerator, CodegenOptions, Newline};
+use oxc_isolated_declarations::{IsolatedDeclarations, IsolatedDeclarationsOptions};
 use oxc_parser::Parser;
-use oxc_span::SourceType;
+use oxc_span::{SourceType, Span};
 
 fn transform(path: &Path, source_text: &str) -> String {
     let allocator = Allocator::default();
@@ -14,7 +15,7 @@ fn transform(path: &Path, source_text: &str) -> String {
     let program = Parser::new(&allocator, source_text, source_type).parse().program;
 
     let ret = IsolatedDeclarations::new(&allocator).build(&program);
-    let code = CodeGenerator::new().build(&ret.program).source_text;
+    let code = CodeGenerator::new().with_source_text(source_text).build(&ret.program).source_text;
 
     let mut snapshot = format!("==================== .D.TS ====================\n\n{code}\n\n");
     if !ret.errors.is_empty() {
@@ -46,3 +47,219 @@ fn snapshots() {
         });
     });
 }
+
+#[test]
+fn synthesized_names() {
+    let allocator = Allocator::default();
+    let source_text = "export class Foo { #foo = 1 }\nexport default 1 as number;";
+    let program = Parser::new(&allocator, source_text, SourceType::default()).parse().program;
+    let options = IsolatedDeclarationsOptions {
+        default_export_name: "__default".to_string(),
+        private_brand_name: "brand".to_string(),
+        ..IsolatedDeclarationsOptions::default()
+    };
+    let ret = IsolatedDeclarations::new(&allocator).with_options(options).build(&program);
+    let code = CodeGenerator::new().build(&ret.program).source_text;
+    assert!(code.contains("#brand;"), "{code}");
+    assert!(code.contains("declare const __default: number;"), "{code}");
+}
+
+#[test]
+fn empty_export() {
+    let allocator = Allocator::default();
+    let source_text = "import * as a from 'mod';\ntype A = string;\nexport function f(): A {}";
+    let program =
+        Parser::new(&allocator, source_text, SourceType::from_path("empty-export.ts").unwrap())
+            .parse()
+            .program;
+    let transform = |options| {
+        let ret = IsolatedDeclarations::new(&allocator).with_options(options).build(&program);
+        CodeGenerator::new().build(&ret.program).source_text
+    };
+
+    let code = transform(IsolatedDeclarationsOptions::default());
+    assert!(code.contains("export {};"), "{code}");
+    let options = IsolatedDeclarationsOptions {
+        emit_empty_export: false,
+        ..IsolatedDeclarationsOptions::default()
+    };
+    let code = transform(options);
+    assert!(!code.contains("export {};"), "{code}");
+    assert!(code.contains("type A = string;"), "{code}");
+}
+
+#[test]
+fn transform_declaration() {
+    let allocator = Allocator::default();
+    let source_text = "export function f() { return g() }\nexport default [1] as const;\nf(1);";
+    let program = Parser::new(&allocator, source_text, SourceType::from_path("a.ts").unwrap())
+        .parse()
+        .program;
+    let mut transformer = IsolatedDeclarations::new(&allocator);
+    let ast = AstBuilder::new(&allocator);
+    let print = |stmt| {
+        let program =
+            ast.program(Span::default(), SourceType::default(), None, ast.vec(), ast.vec1(stmt));
+        CodeGenerator::new().build(&program).source_text
+    };
+
+    let ret = transformer.transform_declaration(&program.body[0]);
+    assert_eq!(ret.errors.len(), 1);
+    assert!(ret.errors[0].to_string().contains("TS9007"), "{}", ret.errors[0]);
+    assert_eq!(print(ret.statement.unwrap()), "export declare function f(): unknown;\n");
+
+    let ret = transformer.transform_declaration(&program.body[1]);
+    assert!(ret.errors.is_empty());
+    assert_eq!(print(ret.statement.unwrap()), "declare const _default: readonly [1];\n");
+
+    let ret = transformer.transform_declaration(&program.body[2]);
+    assert!(ret.statement.is_none());
+    assert_eq!(ret.errors[0].to_string(), "Only declarations can be transformed.");
+}
+
+#[test]
+fn normalize() {
+    let allocator = Allocator::default();
+    let source_text = "
+export interface A extends B { b: 1 | 'x' | string; a(): void; (): void; c?: number }
+interface C { y: 1 }
+export interface A extends B, D { c?: number; a(x: number): void; d: { z: 1; y: 2 } }
+declare global { interface C { x: 2 } }
+";
+    let program = Parser::new(&allocator, source_text, SourceType::from_path("a.ts").unwrap())
+        .parse()
+        .program;
+    let transform = |options| {
+        let ret = IsolatedDeclarations::new(&allocator).with_options(options).build(&program);
+        CodeGenerator::new().build(&ret.program).source_text
+    };
+
+    let code = transform(IsolatedDeclarationsOptions::default());
+    assert_eq!(code.matches("export interface A").count(), 2, "{code}");
+
+    let options =
+        IsolatedDeclarationsOptions { normalize: true, ..IsolatedDeclarationsOptions::default() };
+    let code = transform(options);
+    // Later declarations are merged in front, and the duplicate `c` is removed.
+    let expected = "export interface A extends B, D {
+\tb: \"x\" | 1 | string;
+\ta(x: number): void;
+\tc?: number;
+\td: {
+\t\ty: 2;
+\t\tz: 1;
+\t};
+\ta(): void;
+\t(): void;
+}
+declare global {
+\tinterface C {
+\t\tx: 2;
+\t}
+}
+";
+    assert_eq!(code, expected);
+}
+
+#[test]
+fn jsx_components() {
+    let allocator = Allocator::default();
+    let source_text = "
+export function Button({ label }: { label: string }) {
+  return <button>{label}</button>;
+}
+export const Card = (props: CardOptions = {}) => <div>{props.title}</div>;
+export function Empty() {
+  if (x) return <br />;
+  return <></>;
+}
+export function Maybe() {
+  if (x) return null;
+  return <></>;
+}
+export function format(value: string) {
+  return <span>{value}</span>;
+}
+";
+    let program = Parser::new(&allocator, source_text, SourceType::from_path("a.tsx").unwrap())
+        .parse()
+        .program;
+    let transform = |options| {
+        let ret = IsolatedDeclarations::new(&allocator).with_options(options).build(&program);
+        CodeGenerator::new().build(&ret.program).source_text
+    };
+
+    let code = transform(IsolatedDeclarationsOptions::default());
+    assert!(!code.contains("JSX.Element"), "{code}");
+
+    let options = IsolatedDeclarationsOptions {
+        jsx_components: true,
+        ..IsolatedDeclarationsOptions::default()
+    };
+    let code = transform(options);
+    let expected = "export type ButtonProps = {label: string};
+export declare const Button: (props: ButtonProps) => JSX.Element;
+export declare const Card: (props?: CardOptions) => JSX.Element;
+export declare const Empty: () => JSX.Element;
+export declare function Maybe(): unknown;
+export declare function format(value: string): unknown;
+";
+    assert_eq!(code, expected);
+}
+
+#[test]
+fn header() {
+    let allocator = Allocator::default();
+    let source_text = "export class A { a(): void {} }";
+    let program = Parser::new(&allocator, source_text, SourceType::from_path("a.ts").unwrap())
+        .parse()
+        .program;
+    let options = IsolatedDeclarationsOptions {
+        banner: Some("/**\n * @license MIT\n */".to_string()),
+        ts_nocheck: true,
+        auto_generated: true,
+        ..IsolatedDeclarationsOptions::default()
+    };
+    let ret = IsolatedDeclarations::new(&allocator).with_options(options).build(&program);
+    let codegen_options = CodegenOptions {
+        indent_width: Some(2),
+        newline: Newline::CrLf,
+        ..CodegenOptions::default()
+    };
+    let ret = CodeGenerator::new()
+        .with_options(codegen_options)
+        .with_banner(&ret.header)
+        .enable_source_map("a.ts", source_text)
+        .build(&ret.program);
+    assert_eq!(
+        ret.source_text,
+        "// <auto-generated />\r\n/**\r\n * @license MIT\r\n */\r\n// @ts-nocheck\r\nexport declare class A {\r\n  a(): void;\r\n}\r\n"
+    );
+    // The class is mapped to the line after the header.
+    let source_map = ret.source_map.unwrap();
+    let token = source_map.get_token(0).unwrap();
+    assert_eq!((token.get_dst_line(), token.get_src_line()), (5, 0));
+}
+
+#[test]
+fn type_operators_round_trip() {
+    // Mapped, conditional and template literal types are copied as they are, so transforming the
+    // declarations again must give the same output.
+    let path = Path::new("tests/fixtures/type-operators.ts");
+    let source_text = fs::read_to_string(path).unwrap();
+    let transform = |source_text: &str, source_type| {
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source_text, source_type).parse();
+        assert!(ret.errors.is_empty(), "{:?}\n{source_text}", ret.errors);
+        let ret = IsolatedDeclarations::new(&allocator).build(&ret.program);
+        assert!(ret.errors.is_empty(), "{:?}", ret.errors);
+        CodeGenerator::new().build(&ret.program).source_text
+    };
+
+    let code = transform(&source_text, SourceType::from_path(path).unwrap());
+    for modifier in ["-readonly [", "+readonly [", "]+? :", "]-? :", " as `get${"] {
+        assert!(code.contains(modifier), "{modifier}\n{code}");
+    }
+    let d_ts = SourceType::from_path("type-operators.d.ts").unwrap();
+    assert_eq!(transform(&code, d_ts), code);
+}
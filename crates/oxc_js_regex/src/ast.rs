@@ -1,6 +1,7 @@
 //! [`@eslint-community/regexpp`](https://github.com/eslint-community/regexpp/blob/2e8f1af992fb12eae46a446253e8fa3f6cede92a/src/ast.ts)
 
 use oxc_allocator::{Box, Vec};
+use oxc_macros::ArenaNew;
 use oxc_span::{CompactStr, Span};
 
 /// The type which includes all nodes.
@@ -108,13 +109,31 @@ pub struct Alternative<'a> {
 }
 
 /// The uncapturing group.
-/// E.g. `(?:ab)`
+/// E.g. `(?:ab)`, `(?i:ab)`
 #[derive(Debug)]
 pub struct Group<'a> {
     pub span: Span,
+    pub modifiers: Option<Modifiers>,
     pub alternatives: Vec<'a, Alternative<'a>>,
 }
 
+/// The modifiers of a group, which enable or disable flags for its alternatives.
+/// E.g. `i-s` in `(?i-s:ab)`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Modifiers {
+    pub span: Span,
+    pub enabling: ModifierFlags,
+    pub disabling: ModifierFlags,
+}
+
+/// The flags which modifiers can change.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ModifierFlags {
+    pub ignore_case: bool,
+    pub multiline: bool,
+    pub dot_all: bool,
+}
+
 /// The capturing group.
 /// E.g. `(ab)`, `(?<name>ab)`
 #[derive(Debug)]
@@ -134,7 +153,7 @@ pub enum LookaroundAssertion<'a> {
 
 /// The lookahead assertion.
 /// E.g. `(?=ab)`, `(?!ab)`
-#[derive(Debug)]
+#[derive(Debug, ArenaNew)]
 pub struct LookaheadAssertion<'a> {
     pub span: Span,
     pub negate: bool,
@@ -143,7 +162,7 @@ pub struct LookaheadAssertion<'a> {
 
 /// The lookbehind assertion.
 /// E.g. `(?<=ab)`, `(?<!ab)`
-#[derive(Debug)]
+#[derive(Debug, ArenaNew)]
 pub struct LookbehindAssertion<'a> {
     pub span: Span,
     pub negate: bool,
@@ -152,7 +171,7 @@ pub struct LookbehindAssertion<'a> {
 
 /// The quantifier.
 /// E.g. `a?`, `a*`, `a+`, `a{1,2}`, `a??`, `a*?`, `a+?`, `a{1,2}?`
-#[derive(Debug)]
+#[derive(Debug, ArenaNew)]
 pub struct Quantifier<'a> {
     pub span: Span,
     pub min: f64,
@@ -172,18 +191,20 @@ pub enum CharacterClass<'a> {
 /// The character class used in legacy (neither `u` nor `v` flag) and Unicode mode (`u` flag).
 /// This character class is guaranteed to **not** contain strings.
 /// In Unicode sets mode (`v` flag), {@link UnicodeSetsCharacterClass} is used.
-#[derive(Debug)]
+#[derive(Debug, ArenaNew)]
 pub struct ClassRangesCharacterClass<'a> {
     pub span: Span,
+    pub negate: bool,
     pub unicode_sets: bool,
     pub elements: Vec<'a, ClassRangesCharacterClassElement<'a>>,
 }
 
 /// The character class used in Unicode sets mode (`v` flag).
 /// This character class may contain strings.
-#[derive(Debug)]
+#[derive(Debug, ArenaNew)]
 pub struct UnicodeSetsCharacterClass<'a> {
     pub span: Span,
+    pub negate: bool,
     pub elements: Vec<'a, UnicodeSetsCharacterClassElement<'a>>,
 }
 
@@ -235,11 +256,18 @@ pub struct WordBoundaryAssertion {
 /// The character set.
 #[derive(Debug)]
 pub enum CharacterSet<'a> {
-    AnyCharacterSet,
+    AnyCharacterSet(Box<'a, AnyCharacterSet>),
     EscapeCharacterSet(Box<'a, EscapeCharacterSet>),
     UnicodePropertyCharacterSet(Box<'a, UnicodePropertyCharacterSet<'a>>),
 }
 
+/// The dot.
+/// E.g. `.`
+#[derive(Debug)]
+pub struct AnyCharacterSet {
+    pub span: Span,
+}
+
 /// The character class escape.
 /// E.g. `\d`, `\s`, `\w`, `\D`, `\S`, `\W`
 #[derive(Debug)]
@@ -0,0 +1,543 @@
+//! Arena-aware constructors for regular expression AST nodes.
+//!
+//! Mirrors `oxc_ast::AstBuilder`: `foo(..)` builds a `Foo` node, and `enum_variant(..)` builds the
+//! node for a variant and wraps it in the enum, e.g. [`AstBuilder::element_character`].
+//! Synthesized nodes can use [`oxc_span::SPAN`].
+
+#![allow(
+    clippy::fn_params_excessive_bools,
+    clippy::must_use_candidate, // must_use_candidate is too annoying for this file
+    clippy::too_many_arguments,
+)]
+
+use oxc_allocator::{Allocator, Box, Vec};
+use oxc_span::{CompactStr, Span};
+
+#[allow(clippy::wildcard_imports)]
+use crate::ast::*;
+
+/// AST builder for creating regular expression AST nodes
+#[derive(Clone, Copy)]
+pub struct AstBuilder<'a> {
+    pub allocator: &'a Allocator,
+}
+
+impl<'a> AstBuilder<'a> {
+    #[inline]
+    pub fn new(allocator: &'a Allocator) -> Self {
+        Self { allocator }
+    }
+
+    #[inline]
+    pub fn alloc<T>(self, value: T) -> Box<'a, T> {
+        Box::new_in(value, self.allocator)
+    }
+
+    #[inline]
+    pub fn vec<T>(self) -> Vec<'a, T> {
+        Vec::new_in(self.allocator)
+    }
+
+    #[inline]
+    pub fn vec1<T>(self, value: T) -> Vec<'a, T> {
+        let mut vec = Vec::with_capacity_in(1, self.allocator);
+        vec.push(value);
+        vec
+    }
+
+    #[inline]
+    pub fn vec_from_iter<T, I: IntoIterator<Item = T>>(self, iter: I) -> Vec<'a, T> {
+        Vec::from_iter_in(iter, self.allocator)
+    }
+
+    /* ---------- Pattern ---------- */
+
+    #[inline]
+    pub fn regexp_literal(
+        self,
+        span: Span,
+        pattern: Pattern<'a>,
+        flags: Flags,
+    ) -> RegExpLiteral<'a> {
+        RegExpLiteral { span, pattern, flags }
+    }
+
+    #[inline]
+    pub fn pattern(self, span: Span, alternatives: Vec<'a, Alternative<'a>>) -> Pattern<'a> {
+        Pattern { span, alternatives }
+    }
+
+    #[inline]
+    pub fn alternative(self, span: Span, elements: Vec<'a, Element<'a>>) -> Alternative<'a> {
+        Alternative { span, elements }
+    }
+
+    #[inline]
+    pub fn flags(
+        self,
+        span: Span,
+        dot_all: bool,
+        global: bool,
+        has_indices: bool,
+        ignore_case: bool,
+        multiline: bool,
+        sticky: bool,
+        unicode: bool,
+        unicode_sets: bool,
+    ) -> Flags {
+        Flags {
+            span,
+            dot_all,
+            global,
+            has_indices,
+            ignore_case,
+            multiline,
+            sticky,
+            unicode,
+            unicode_sets,
+        }
+    }
+
+    /* ---------- Elements ---------- */
+
+    #[inline]
+    pub fn element_assertion(self, assertion: Assertion<'a>) -> Element<'a> {
+        Element::Assertion(self.alloc(assertion))
+    }
+
+    #[inline]
+    pub fn element_quantifiable_element(self, element: QuantifiableElement<'a>) -> Element<'a> {
+        Element::QuantifiableElement(self.alloc(element))
+    }
+
+    /// `a`
+    #[inline]
+    pub fn element_character(self, span: Span, value: u16) -> Element<'a> {
+        self.element_quantifiable_element(self.quantifiable_element_character(span, value))
+    }
+
+    /// `(ab)`, `(?<name>ab)`
+    #[inline]
+    pub fn element_capturing_group(
+        self,
+        span: Span,
+        name: Option<CompactStr>,
+        alternatives: Vec<'a, Alternative<'a>>,
+    ) -> Element<'a> {
+        self.element_quantifiable_element(self.quantifiable_element_capturing_group(
+            span,
+            name,
+            alternatives,
+        ))
+    }
+
+    /// `(?:ab)`
+    #[inline]
+    pub fn element_group(self, span: Span, alternatives: Vec<'a, Alternative<'a>>) -> Element<'a> {
+        self.element_quantifiable_element(self.quantifiable_element_group(span, alternatives))
+    }
+
+    /// `(?i-s:ab)`
+    #[inline]
+    pub fn element_group_with_modifiers(
+        self,
+        span: Span,
+        modifiers: Modifiers,
+        alternatives: Vec<'a, Alternative<'a>>,
+    ) -> Element<'a> {
+        let group = self.group_with_modifiers(span, modifiers, alternatives);
+        self.element_quantifiable_element(QuantifiableElement::Group(self.alloc(group)))
+    }
+
+    /// `a*`, `a{1,2}?`
+    #[inline]
+    pub fn element_quantifier(
+        self,
+        span: Span,
+        min: f64,
+        max: f64,
+        greedy: bool,
+        element: QuantifiableElement<'a>,
+    ) -> Element<'a> {
+        Element::Quantifier(Quantifier::new_in(self.allocator, span, min, max, greedy, element))
+    }
+
+    /// `^`, `$`
+    #[inline]
+    pub fn element_edge_assertion(self, span: Span, kind: EdgeAssertionKind) -> Element<'a> {
+        let assertion =
+            BoundaryAssertion::EdgeAssertion(self.alloc(self.edge_assertion(span, kind)));
+        self.element_assertion(Assertion::BoundaryAssertion(self.alloc(assertion)))
+    }
+
+    /// `\b`, `\B`
+    #[inline]
+    pub fn element_word_boundary_assertion(self, span: Span, negate: bool) -> Element<'a> {
+        let assertion = BoundaryAssertion::WordBoundaryAssertion(
+            self.alloc(self.word_boundary_assertion(span, negate)),
+        );
+        self.element_assertion(Assertion::BoundaryAssertion(self.alloc(assertion)))
+    }
+
+    /// `(?=ab)`, `(?!ab)`
+    #[inline]
+    pub fn element_lookahead_assertion(
+        self,
+        span: Span,
+        negate: bool,
+        alternatives: Vec<'a, Alternative<'a>>,
+    ) -> Element<'a> {
+        let assertion = LookaroundAssertion::LookaheadAssertion(LookaheadAssertion::new_in(
+            self.allocator,
+            span,
+            negate,
+            alternatives,
+        ));
+        self.element_assertion(Assertion::LookaroundAssertion(self.alloc(assertion)))
+    }
+
+    /// `(?<=ab)`, `(?<!ab)`
+    #[inline]
+    pub fn element_lookbehind_assertion(
+        self,
+        span: Span,
+        negate: bool,
+        alternatives: Vec<'a, Alternative<'a>>,
+    ) -> Element<'a> {
+        let assertion = LookaroundAssertion::LookbehindAssertion(LookbehindAssertion::new_in(
+            self.allocator,
+            span,
+            negate,
+            alternatives,
+        ));
+        self.element_assertion(Assertion::LookaroundAssertion(self.alloc(assertion)))
+    }
+
+    #[inline]
+    pub fn quantifiable_element_character(self, span: Span, value: u16) -> QuantifiableElement<'a> {
+        QuantifiableElement::Character(self.alloc(self.character(span, value)))
+    }
+
+    #[inline]
+    pub fn quantifiable_element_capturing_group(
+        self,
+        span: Span,
+        name: Option<CompactStr>,
+        alternatives: Vec<'a, Alternative<'a>>,
+    ) -> QuantifiableElement<'a> {
+        QuantifiableElement::CapturingGroup(self.alloc(self.capturing_group(
+            span,
+            name,
+            alternatives,
+        )))
+    }
+
+    #[inline]
+    pub fn quantifiable_element_group(
+        self,
+        span: Span,
+        alternatives: Vec<'a, Alternative<'a>>,
+    ) -> QuantifiableElement<'a> {
+        QuantifiableElement::Group(self.alloc(self.group(span, alternatives)))
+    }
+
+    /// `.`
+    #[inline]
+    pub fn quantifiable_element_any_character_set(self, span: Span) -> QuantifiableElement<'a> {
+        let set = self.alloc(self.any_character_set(span));
+        QuantifiableElement::CharacterSet(self.alloc(CharacterSet::AnyCharacterSet(set)))
+    }
+
+    /// `\d`, `\s`, `\w`, `\D`, `\S`, `\W`
+    #[inline]
+    pub fn quantifiable_element_escape_character_set(
+        self,
+        span: Span,
+        kind: EscapeCharacterSetKind,
+        negate: bool,
+    ) -> QuantifiableElement<'a> {
+        let set = self.alloc(self.escape_character_set(span, kind, negate));
+        QuantifiableElement::CharacterSet(self.alloc(CharacterSet::EscapeCharacterSet(set)))
+    }
+
+    #[inline]
+    pub fn quantifiable_element_character_class(
+        self,
+        class: CharacterClass<'a>,
+    ) -> QuantifiableElement<'a> {
+        QuantifiableElement::CharacterClass(self.alloc(class))
+    }
+
+    /* ---------- Groups and assertions ---------- */
+
+    #[inline]
+    pub fn group(self, span: Span, alternatives: Vec<'a, Alternative<'a>>) -> Group<'a> {
+        Group { span, modifiers: None, alternatives }
+    }
+
+    #[inline]
+    pub fn group_with_modifiers(
+        self,
+        span: Span,
+        modifiers: Modifiers,
+        alternatives: Vec<'a, Alternative<'a>>,
+    ) -> Group<'a> {
+        Group { span, modifiers: Some(modifiers), alternatives }
+    }
+
+    /// `i-s` in `(?i-s:ab)`
+    #[inline]
+    pub fn modifiers(
+        self,
+        span: Span,
+        enabling: ModifierFlags,
+        disabling: ModifierFlags,
+    ) -> Modifiers {
+        Modifiers { span, enabling, disabling }
+    }
+
+    #[inline]
+    pub fn capturing_group(
+        self,
+        span: Span,
+        name: Option<CompactStr>,
+        alternatives: Vec<'a, Alternative<'a>>,
+    ) -> CapturingGroup<'a> {
+        CapturingGroup { span, name, alternatives, references: self.vec() }
+    }
+
+    #[inline]
+    pub fn lookahead_assertion(
+        self,
+        span: Span,
+        negate: bool,
+        alternatives: Vec<'a, Alternative<'a>>,
+    ) -> LookaheadAssertion<'a> {
+        LookaheadAssertion { span, negate, alternatives }
+    }
+
+    #[inline]
+    pub fn lookbehind_assertion(
+        self,
+        span: Span,
+        negate: bool,
+        alternatives: Vec<'a, Alternative<'a>>,
+    ) -> LookbehindAssertion<'a> {
+        LookbehindAssertion { span, negate, alternatives }
+    }
+
+    /// `max` can be `f64::INFINITY`.
+    #[inline]
+    pub fn quantifier(
+        self,
+        span: Span,
+        min: f64,
+        max: f64,
+        greedy: bool,
+        element: QuantifiableElement<'a>,
+    ) -> Quantifier<'a> {
+        Quantifier { span, min, max, greedy, element }
+    }
+
+    #[inline]
+    pub fn edge_assertion(self, span: Span, kind: EdgeAssertionKind) -> EdgeAssertion {
+        EdgeAssertion { span, kind }
+    }
+
+    #[inline]
+    pub fn word_boundary_assertion(self, span: Span, negate: bool) -> WordBoundaryAssertion {
+        WordBoundaryAssertion { span, negate }
+    }
+
+    #[inline]
+    pub fn backreference(
+        self,
+        span: Span,
+        reference: BackreferenceRef,
+        resolved: CapturingGroup<'a>,
+    ) -> Backreference<'a> {
+        Backreference { span, reference, resolved }
+    }
+
+    /* ---------- Characters ---------- */
+
+    #[inline]
+    pub fn character(self, span: Span, value: u16) -> Character {
+        Character { span, value }
+    }
+
+    #[inline]
+    pub fn any_character_set(self, span: Span) -> AnyCharacterSet {
+        AnyCharacterSet { span }
+    }
+
+    #[inline]
+    pub fn escape_character_set(
+        self,
+        span: Span,
+        kind: EscapeCharacterSetKind,
+        negate: bool,
+    ) -> EscapeCharacterSet {
+        EscapeCharacterSet { span, kind, negate }
+    }
+
+    #[inline]
+    pub fn character_unicode_property_character_set(
+        self,
+        span: Span,
+        key: CompactStr,
+        value: Option<CompactStr>,
+        negate: bool,
+    ) -> CharacterUnicodePropertyCharacterSet {
+        CharacterUnicodePropertyCharacterSet { span, key, value, negate }
+    }
+
+    #[inline]
+    pub fn strings_unicode_property_character_set(
+        self,
+        span: Span,
+        key: CompactStr,
+    ) -> StringsUnicodePropertyCharacterSet {
+        StringsUnicodePropertyCharacterSet { span, key }
+    }
+
+    /* ---------- Character classes ---------- */
+
+    /// `[ab]` or `[^ab]` without the `v` flag
+    #[inline]
+    pub fn character_class_class_ranges(
+        self,
+        span: Span,
+        negate: bool,
+        unicode_sets: bool,
+        elements: Vec<'a, ClassRangesCharacterClassElement<'a>>,
+    ) -> CharacterClass<'a> {
+        CharacterClass::ClassRangesCharacterClass(ClassRangesCharacterClass::new_in(
+            self.allocator,
+            span,
+            negate,
+            unicode_sets,
+            elements,
+        ))
+    }
+
+    /// `[ab]` or `[^ab]` with the `v` flag
+    #[inline]
+    pub fn character_class_unicode_sets(
+        self,
+        span: Span,
+        negate: bool,
+        elements: Vec<'a, UnicodeSetsCharacterClassElement<'a>>,
+    ) -> CharacterClass<'a> {
+        CharacterClass::UnicodeSetsCharacterClass(UnicodeSetsCharacterClass::new_in(
+            self.allocator,
+            span,
+            negate,
+            elements,
+        ))
+    }
+
+    #[inline]
+    pub fn class_ranges_character_class(
+        self,
+        span: Span,
+        negate: bool,
+        unicode_sets: bool,
+        elements: Vec<'a, ClassRangesCharacterClassElement<'a>>,
+    ) -> ClassRangesCharacterClass<'a> {
+        ClassRangesCharacterClass { span, negate, unicode_sets, elements }
+    }
+
+    #[inline]
+    pub fn unicode_sets_character_class(
+        self,
+        span: Span,
+        negate: bool,
+        elements: Vec<'a, UnicodeSetsCharacterClassElement<'a>>,
+    ) -> UnicodeSetsCharacterClass<'a> {
+        UnicodeSetsCharacterClass { span, negate, elements }
+    }
+
+    #[inline]
+    pub fn class_ranges_character_class_element_character(
+        self,
+        span: Span,
+        value: u16,
+    ) -> ClassRangesCharacterClassElement<'a> {
+        ClassRangesCharacterClassElement::Character(self.alloc(self.character(span, value)))
+    }
+
+    /// `a-z`
+    #[inline]
+    pub fn class_ranges_character_class_element_range(
+        self,
+        span: Span,
+        min: Character,
+        max: Character,
+    ) -> ClassRangesCharacterClassElement<'a> {
+        ClassRangesCharacterClassElement::CharacterClassRange(
+            self.alloc(self.character_class_range(span, min, max)),
+        )
+    }
+
+    #[inline]
+    pub fn character_class_range(
+        self,
+        span: Span,
+        min: Character,
+        max: Character,
+    ) -> CharacterClassRange {
+        CharacterClassRange { span, min, max }
+    }
+
+    #[inline]
+    pub fn expression_character_class(
+        self,
+        span: Span,
+        negate: bool,
+        expression: ExpressionCharacterClassExpr<'a>,
+    ) -> ExpressionCharacterClass<'a> {
+        ExpressionCharacterClass { span, negate, expression }
+    }
+
+    /// `a&&b`
+    #[inline]
+    pub fn class_intersection(
+        self,
+        span: Span,
+        left: ClassIntersectionLeft<'a>,
+        right: ClassSetOperand<'a>,
+    ) -> ClassIntersection<'a> {
+        ClassIntersection { span, left, right }
+    }
+
+    /// `a--b`
+    #[inline]
+    pub fn class_subtraction(
+        self,
+        span: Span,
+        left: ClassSubtractionLeft<'a>,
+        right: ClassSetOperand<'a>,
+    ) -> ClassSubtraction<'a> {
+        ClassSubtraction { span, left, right }
+    }
+
+    /// `\q{a|b}`
+    #[inline]
+    pub fn class_string_disjunction(
+        self,
+        span: Span,
+        alternatives: Vec<'a, StringAlternative<'a>>,
+    ) -> ClassStringDisjunction<'a> {
+        ClassStringDisjunction { span, alternatives }
+    }
+
+    #[inline]
+    pub fn string_alternative(
+        self,
+        span: Span,
+        elements: Vec<'a, Character>,
+    ) -> StringAlternative<'a> {
+        StringAlternative { span, elements }
+    }
+}
@@ -0,0 +1,320 @@
+//! Untyped references to regular expression AST nodes, for tools which walk the tree by spans,
+//! like `oxc_ast::AstKind` does for the JavaScript AST.
+
+use oxc_span::{GetSpan, Span};
+
+#[allow(clippy::wildcard_imports)]
+use crate::ast::*;
+
+/// Untyped AST Node Kind
+///
+/// Has a variant for every node struct. Enums which only select a node, e.g. [`Element`], are
+/// converted to the kind of the selected node with [`From`].
+#[derive(Debug, Clone, Copy)]
+pub enum AstKind<'a> {
+    RegExpLiteral(&'a RegExpLiteral<'a>),
+    Pattern(&'a Pattern<'a>),
+    Alternative(&'a Alternative<'a>),
+    Group(&'a Group<'a>),
+    CapturingGroup(&'a CapturingGroup<'a>),
+    LookaheadAssertion(&'a LookaheadAssertion<'a>),
+    LookbehindAssertion(&'a LookbehindAssertion<'a>),
+    Quantifier(&'a Quantifier<'a>),
+    ClassRangesCharacterClass(&'a ClassRangesCharacterClass<'a>),
+    UnicodeSetsCharacterClass(&'a UnicodeSetsCharacterClass<'a>),
+    CharacterClassRange(&'a CharacterClassRange),
+    EdgeAssertion(&'a EdgeAssertion),
+    WordBoundaryAssertion(&'a WordBoundaryAssertion),
+    AnyCharacterSet(&'a AnyCharacterSet),
+    EscapeCharacterSet(&'a EscapeCharacterSet),
+    CharacterUnicodePropertyCharacterSet(&'a CharacterUnicodePropertyCharacterSet),
+    StringsUnicodePropertyCharacterSet(&'a StringsUnicodePropertyCharacterSet),
+    ExpressionCharacterClass(&'a ExpressionCharacterClass<'a>),
+    ClassIntersection(&'a ClassIntersection<'a>),
+    ClassSubtraction(&'a ClassSubtraction<'a>),
+    ClassStringDisjunction(&'a ClassStringDisjunction<'a>),
+    StringAlternative(&'a StringAlternative<'a>),
+    Character(&'a Character),
+    Backreference(&'a Backreference<'a>),
+    Flags(&'a Flags),
+}
+
+impl<'a> AstKind<'a> {
+    /// The child nodes, sorted by the start of their spans.
+    ///
+    /// [`Backreference::resolved`] and [`CapturingGroup::references`] link to other nodes of the
+    /// tree and are not children.
+    pub fn children(self) -> std::vec::Vec<AstKind<'a>> {
+        let mut children = vec![];
+        match self {
+            Self::RegExpLiteral(it) => {
+                children.push(Self::from(&it.pattern));
+                children.push(Self::from(&it.flags));
+            }
+            Self::Pattern(it) => children.extend(it.alternatives.iter().map(Self::from)),
+            Self::Alternative(it) => children.extend(it.elements.iter().map(Self::from)),
+            Self::Group(it) => children.extend(it.alternatives.iter().map(Self::from)),
+            Self::CapturingGroup(it) => children.extend(it.alternatives.iter().map(Self::from)),
+            Self::LookaheadAssertion(it) => {
+                children.extend(it.alternatives.iter().map(Self::from));
+            }
+            Self::LookbehindAssertion(it) => {
+                children.extend(it.alternatives.iter().map(Self::from));
+            }
+            Self::Quantifier(it) => children.push(Self::from(&it.element)),
+            Self::ClassRangesCharacterClass(it) => {
+                children.extend(it.elements.iter().map(Self::from));
+            }
+            Self::UnicodeSetsCharacterClass(it) => {
+                children.extend(it.elements.iter().map(Self::from));
+            }
+            Self::CharacterClassRange(it) => {
+                children.push(Self::from(&it.min));
+                children.push(Self::from(&it.max));
+            }
+            Self::ExpressionCharacterClass(it) => children.push(Self::from(&it.expression)),
+            Self::ClassIntersection(it) => {
+                children.push(Self::from(&it.left));
+                children.push(Self::from(&it.right));
+            }
+            Self::ClassSubtraction(it) => {
+                children.push(Self::from(&it.left));
+                children.push(Self::from(&it.right));
+            }
+            Self::ClassStringDisjunction(it) => {
+                children.extend(it.alternatives.iter().map(Self::from));
+            }
+            Self::StringAlternative(it) => children.extend(it.elements.iter().map(Self::from)),
+            Self::EdgeAssertion(_)
+            | Self::WordBoundaryAssertion(_)
+            | Self::AnyCharacterSet(_)
+            | Self::EscapeCharacterSet(_)
+            | Self::CharacterUnicodePropertyCharacterSet(_)
+            | Self::StringsUnicodePropertyCharacterSet(_)
+            | Self::Character(_)
+            | Self::Backreference(_)
+            | Self::Flags(_) => {}
+        }
+        children.sort_by_key(|child| child.span().start);
+        children
+    }
+
+    /// The innermost node whose span contains `offset`, starting from this node.
+    pub fn node_at_offset(self, offset: u32) -> Option<AstKind<'a>> {
+        let span = self.span();
+        if offset < span.start || offset >= span.end {
+            return None;
+        }
+        let mut node = self;
+        while let Some(child) = node.children().into_iter().find(|child| {
+            let span = child.span();
+            span.start <= offset && offset < span.end
+        }) {
+            node = child;
+        }
+        Some(node)
+    }
+}
+
+impl GetSpan for AstKind<'_> {
+    #[allow(clippy::match_same_arms)]
+    fn span(&self) -> Span {
+        match self {
+            Self::RegExpLiteral(it) => it.span(),
+            Self::Pattern(it) => it.span(),
+            Self::Alternative(it) => it.span(),
+            Self::Group(it) => it.span(),
+            Self::CapturingGroup(it) => it.span(),
+            Self::LookaheadAssertion(it) => it.span(),
+            Self::LookbehindAssertion(it) => it.span(),
+            Self::Quantifier(it) => it.span(),
+            Self::ClassRangesCharacterClass(it) => it.span(),
+            Self::UnicodeSetsCharacterClass(it) => it.span(),
+            Self::CharacterClassRange(it) => it.span(),
+            Self::EdgeAssertion(it) => it.span(),
+            Self::WordBoundaryAssertion(it) => it.span(),
+            Self::AnyCharacterSet(it) => it.span(),
+            Self::EscapeCharacterSet(it) => it.span(),
+            Self::CharacterUnicodePropertyCharacterSet(it) => it.span(),
+            Self::StringsUnicodePropertyCharacterSet(it) => it.span(),
+            Self::ExpressionCharacterClass(it) => it.span(),
+            Self::ClassIntersection(it) => it.span(),
+            Self::ClassSubtraction(it) => it.span(),
+            Self::ClassStringDisjunction(it) => it.span(),
+            Self::StringAlternative(it) => it.span(),
+            Self::Character(it) => it.span(),
+            Self::Backreference(it) => it.span(),
+            Self::Flags(it) => it.span(),
+        }
+    }
+}
+
+macro_rules! impl_from_structs {
+    ($($ty:ident),+ $(,)?) => {
+        $(
+            impl<'a> From<&'a $ty> for AstKind<'a> {
+                fn from(it: &'a $ty) -> Self {
+                    Self::$ty(it)
+                }
+            }
+        )+
+    };
+    (@lifetime $($ty:ident),+ $(,)?) => {
+        $(
+            impl<'a> From<&'a $ty<'a>> for AstKind<'a> {
+                fn from(it: &'a $ty<'a>) -> Self {
+                    Self::$ty(it)
+                }
+            }
+        )+
+    };
+}
+
+macro_rules! impl_from_enums {
+    ($($ty:ident { $($variant:ident),+ $(,)? }),+ $(,)?) => {
+        $(
+            impl<'a> From<&'a $ty<'a>> for AstKind<'a> {
+                fn from(it: &'a $ty<'a>) -> Self {
+                    match it {
+                        $($ty::$variant(it) => Self::from(&**it),)+
+                    }
+                }
+            }
+        )+
+    };
+}
+
+impl_from_structs!(
+    @lifetime
+    RegExpLiteral,
+    Pattern,
+    Alternative,
+    Group,
+    CapturingGroup,
+    LookaheadAssertion,
+    LookbehindAssertion,
+    Quantifier,
+    ClassRangesCharacterClass,
+    UnicodeSetsCharacterClass,
+    ExpressionCharacterClass,
+    ClassIntersection,
+    ClassSubtraction,
+    ClassStringDisjunction,
+    StringAlternative,
+    Backreference,
+);
+
+impl_from_structs!(
+    CharacterClassRange,
+    EdgeAssertion,
+    WordBoundaryAssertion,
+    AnyCharacterSet,
+    EscapeCharacterSet,
+    CharacterUnicodePropertyCharacterSet,
+    StringsUnicodePropertyCharacterSet,
+    Character,
+    Flags,
+);
+
+impl_from_enums!(
+    Element { Assertion, QuantifiableElement, Quantifier },
+    QuantifiableElement {
+        Backreference,
+        CapturingGroup,
+        Character,
+        CharacterClass,
+        CharacterSet,
+        ExpressionCharacterClass,
+        Group,
+        LookaheadAssertion,
+    },
+    ClassRangesCharacterClassElement {
+        Character,
+        CharacterClassRange,
+        CharacterUnicodePropertyCharacterSet,
+        EscapeCharacterSet,
+    },
+    UnicodeSetsCharacterClassElement {
+        Character,
+        CharacterClassRange,
+        ClassStringDisjunction,
+        EscapeCharacterSet,
+        ExpressionCharacterClass,
+        UnicodePropertyCharacterSet,
+        UnicodeSetsCharacterClass,
+    },
+    LookaroundAssertion { LookaheadAssertion, LookbehindAssertion },
+    CharacterClass { ClassRangesCharacterClass, UnicodeSetsCharacterClass },
+    Assertion { BoundaryAssertion, LookaroundAssertion },
+    BoundaryAssertion { EdgeAssertion, WordBoundaryAssertion },
+    CharacterSet { AnyCharacterSet, EscapeCharacterSet, UnicodePropertyCharacterSet },
+    UnicodePropertyCharacterSet {
+        CharacterUnicodePropertyCharacterSet,
+        StringsUnicodePropertyCharacterSet,
+    },
+    ExpressionCharacterClassExpr { ClassIntersection, ClassSubtraction },
+    ClassSetOperand {
+        Character,
+        ClassStringDisjunction,
+        EscapeCharacterSet,
+        ExpressionCharacterClass,
+        UnicodePropertyCharacterSet,
+        UnicodeSetsCharacterClass,
+    },
+    ClassIntersectionLeft { ClassIntersection, ClassSetOperand },
+    ClassSubtractionLeft { ClassSetOperand, ClassSubtraction },
+);
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+    use oxc_span::{GetSpan, Span};
+
+    use super::AstKind;
+    use crate::AstBuilder;
+
+    #[test]
+    fn children_and_node_at_offset() {
+        // a(?:b|.)+
+        let allocator = Allocator::default();
+        let ast = AstBuilder::new(&allocator);
+        let group = ast.quantifiable_element_group(
+            Span::new(1, 8),
+            ast.vec_from_iter([
+                ast.alternative(
+                    Span::new(4, 5),
+                    ast.vec1(ast.element_character(Span::new(4, 5), u16::from(b'b'))),
+                ),
+                ast.alternative(
+                    Span::new(6, 7),
+                    ast.vec1(ast.element_quantifiable_element(
+                        ast.quantifiable_element_any_character_set(Span::new(6, 7)),
+                    )),
+                ),
+            ]),
+        );
+        let pattern = ast.pattern(
+            Span::new(0, 9),
+            ast.vec1(ast.alternative(
+                Span::new(0, 9),
+                ast.vec_from_iter([
+                    ast.element_character(Span::new(0, 1), u16::from(b'a')),
+                    ast.element_quantifier(Span::new(1, 9), 1.0, f64::INFINITY, true, group),
+                ]),
+            )),
+        );
+
+        let root = AstKind::from(&pattern);
+        let alternative = root.children()[0];
+        let spans = alternative.children().iter().map(GetSpan::span).collect::<Vec<_>>();
+        assert_eq!(spans, [Span::new(0, 1), Span::new(1, 9)]);
+
+        let node = root.node_at_offset(6).unwrap();
+        assert!(matches!(node, AstKind::AnyCharacterSet(_)));
+        assert_eq!(node.span(), Span::new(6, 7));
+        // `(?:` and `)+` belong to the group and the quantifier.
+        assert!(matches!(root.node_at_offset(2), Some(AstKind::Group(_))));
+        assert!(matches!(root.node_at_offset(8), Some(AstKind::Quantifier(_))));
+        assert!(root.node_at_offset(9).is_none());
+    }
+}
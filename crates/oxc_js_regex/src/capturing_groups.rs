@@ -0,0 +1,155 @@
+//! Capturing group numbering.
+//!
+//! Capturing groups are numbered by the position of their opening parenthesis, starting at `1`.
+//! The same numbering is used by the match result returned from `RegExp.prototype.exec`:
+//!
+//! * `match[n]` and, with the `d` flag, `match.indices[n]` hold group `n`. Index `0` is the whole match.
+//! * `match.groups[name]` and `match.indices.groups[name]` hold named groups. A name can be used by
+//!   more than one group in different alternatives (duplicate named groups), in which case the
+//!   property holds the group which participated in the match.
+//!
+//! See <https://tc39.es/ecma262/#sec-makeindicesarray>
+
+use oxc_span::{CompactStr, Span};
+
+use crate::{
+    ast::{CapturingGroup, Pattern},
+    visitor::{walk, Visit},
+};
+
+/// A capturing group of a pattern.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CapturingGroupInfo {
+    /// Group number, which is the index into the match result and the `indices` array.
+    pub index: usize,
+    /// `name` in `(?<name>...)`.
+    pub name: Option<CompactStr>,
+    pub span: Span,
+}
+
+/// Mapping from group numbers and names to match result indices.
+#[derive(Debug, Default, Clone)]
+pub struct CapturingGroupTable {
+    groups: Vec<CapturingGroupInfo>,
+}
+
+impl CapturingGroupTable {
+    pub fn new(pattern: &Pattern<'_>) -> Self {
+        let mut table = Self::default();
+        table.visit_pattern(pattern);
+        table
+    }
+
+    /// Number of capturing groups.
+    pub fn len(&self) -> usize {
+        self.groups.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.groups.is_empty()
+    }
+
+    /// Length of the match result array and of its `indices` array, which includes the whole
+    /// match at index `0`.
+    pub fn indices_len(&self) -> usize {
+        self.groups.len() + 1
+    }
+
+    /// Capturing group `index`, where the first group is `1`.
+    pub fn get(&self, index: usize) -> Option<&CapturingGroupInfo> {
+        index.checked_sub(1).and_then(|i| self.groups.get(i))
+    }
+
+    /// Capturing groups in order of their opening parenthesis.
+    pub fn iter(&self) -> impl Iterator<Item = &CapturingGroupInfo> + '_ {
+        self.groups.iter()
+    }
+
+    pub fn has_named_groups(&self) -> bool {
+        self.groups.iter().any(|group| group.name.is_some())
+    }
+
+    /// Group numbers sharing `name`. Empty if there is no group with this name.
+    pub fn indices_of<'b>(&'b self, name: &'b str) -> impl Iterator<Item = usize> + 'b {
+        self.groups
+            .iter()
+            .filter(move |group| group.name.as_deref() == Some(name))
+            .map(|group| group.index)
+    }
+
+    /// Keys of the `groups` and `indices.groups` objects, in property order, that is the order of
+    /// the first group with each name.
+    pub fn group_names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = vec![];
+        for name in self.groups.iter().filter_map(|group| group.name.as_deref()) {
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+        names
+    }
+}
+
+impl<'a> Visit<'a> for CapturingGroupTable {
+    fn visit_capturing_group(&mut self, it: &CapturingGroup<'a>) {
+        self.groups.push(CapturingGroupInfo {
+            index: self.groups.len() + 1,
+            name: it.name.clone(),
+            span: it.span,
+        });
+        walk::walk_capturing_group(self, it);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+    use oxc_span::Span;
+
+    use super::CapturingGroupTable;
+    use crate::{ast::Alternative, AstBuilder};
+
+    fn character<'a>(ast: AstBuilder<'a>, start: u32, value: char) -> Alternative<'a> {
+        let span = Span::new(start, start + 1);
+        ast.alternative(span, ast.vec1(ast.element_character(span, value as u16)))
+    }
+
+    #[test]
+    fn numbering() {
+        // (a)(?:(?<x>b)|(?<x>c))
+        let allocator = Allocator::default();
+        let ast = AstBuilder::new(&allocator);
+        let first =
+            ast.element_capturing_group(Span::new(0, 3), None, ast.vec1(character(ast, 1, 'a')));
+        let x1 = ast.element_capturing_group(
+            Span::new(6, 13),
+            Some("x".into()),
+            ast.vec1(character(ast, 11, 'b')),
+        );
+        let x2 = ast.element_capturing_group(
+            Span::new(14, 21),
+            Some("x".into()),
+            ast.vec1(character(ast, 19, 'c')),
+        );
+        let alternatives = ast.vec_from_iter([
+            ast.alternative(Span::new(6, 13), ast.vec1(x1)),
+            ast.alternative(Span::new(14, 21), ast.vec1(x2)),
+        ]);
+        let group = ast.element_group(Span::new(3, 22), alternatives);
+        let pattern = ast.pattern(
+            Span::new(0, 22),
+            ast.vec1(ast.alternative(Span::new(0, 22), ast.vec_from_iter([first, group]))),
+        );
+
+        let table = CapturingGroupTable::new(&pattern);
+        assert_eq!(table.len(), 3);
+        assert_eq!(table.indices_len(), 4);
+        assert!(table.get(0).is_none());
+        assert_eq!(table.get(1).unwrap().span, Span::new(0, 3));
+        assert_eq!(table.get(3).unwrap().span, Span::new(14, 21));
+        assert!(table.has_named_groups());
+        assert_eq!(table.group_names(), ["x"]);
+        assert_eq!(table.indices_of("x").collect::<Vec<_>>(), [2, 3]);
+        assert_eq!(table.indices_of("y").count(), 0);
+    }
+}
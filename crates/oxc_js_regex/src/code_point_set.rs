@@ -0,0 +1,623 @@
+//! Sets of code points, for comparing and rewriting character classes by what they match instead
+//! of how they are written, e.g. `[0-9]` and `\d` are the same set.
+//!
+//! Unicode property escapes (`\p{...}`) and strings (`\q{ab}`) are not supported, since they need
+//! the Unicode character database. Case folding under the `i` flag is not applied either.
+
+use std::fmt::Write;
+
+use crate::ast::{
+    Character, CharacterClass, CharacterClassRange, CharacterSet, ClassIntersection,
+    ClassIntersectionLeft, ClassRangesCharacterClass, ClassRangesCharacterClassElement,
+    ClassSetOperand, ClassStringDisjunction, ClassSubtraction, ClassSubtractionLeft,
+    EscapeCharacterSet, EscapeCharacterSetKind, ExpressionCharacterClass,
+    ExpressionCharacterClassExpr, Flags, UnicodeSetsCharacterClass,
+    UnicodeSetsCharacterClassElement,
+};
+
+pub const MAX_CODE_POINT: u32 = 0x10_FFFF;
+pub const MAX_CODE_UNIT: u32 = 0xFFFF;
+
+/// `\d`
+const DIGIT: &[(u32, u32)] = &[(0x30, 0x39)];
+/// `\w`
+const WORD: &[(u32, u32)] = &[(0x30, 0x39), (0x41, 0x5A), (0x5F, 0x5F), (0x61, 0x7A)];
+/// `\s`, the WhiteSpace and LineTerminator code points.
+const SPACE: &[(u32, u32)] = &[
+    (0x09, 0x0D),
+    (0x20, 0x20),
+    (0xA0, 0xA0),
+    (0x1680, 0x1680),
+    (0x2000, 0x200A),
+    (0x2028, 0x2029),
+    (0x202F, 0x202F),
+    (0x205F, 0x205F),
+    (0x3000, 0x3000),
+    (0xFEFF, 0xFEFF),
+];
+/// LineTerminator, which `.` does not match without the `s` flag.
+const LINE_TERMINATOR: &[(u32, u32)] = &[(0x0A, 0x0A), (0x0D, 0x0D), (0x2028, 0x2029)];
+
+/// Escapes tried when writing a class, with the set each one matches.
+const CLASS_ESCAPES: [(&str, &[(u32, u32)]); 3] = [("\\w", WORD), ("\\d", DIGIT), ("\\s", SPACE)];
+
+/// How a pattern is parsed, which decides what a class can match and how it is written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PatternMode {
+    /// Neither the `u` nor the `v` flag. Classes match UTF-16 code units.
+    Legacy,
+    /// The `u` flag.
+    Unicode,
+    /// The `v` flag.
+    UnicodeSets,
+}
+
+impl PatternMode {
+    pub fn from_flags(flags: &Flags) -> Self {
+        if flags.unicode_sets {
+            Self::UnicodeSets
+        } else if flags.unicode {
+            Self::Unicode
+        } else {
+            Self::Legacy
+        }
+    }
+
+    /// The largest code point a class can match.
+    pub fn max(self) -> u32 {
+        match self {
+            Self::Legacy => MAX_CODE_UNIT,
+            Self::Unicode | Self::UnicodeSets => MAX_CODE_POINT,
+        }
+    }
+}
+
+/// A set of code points.
+///
+/// Two sets are equal if and only if they contain the same code points.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct CodePointSet {
+    /// Sorted, disjoint and non-adjacent inclusive ranges.
+    ranges: Vec<(u32, u32)>,
+}
+
+impl CodePointSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_code_point(code_point: u32) -> Self {
+        Self { ranges: vec![(code_point, code_point)] }
+    }
+
+    /// Code points from `min` to `max`, inclusive. Empty if `min` is greater than `max`.
+    pub fn from_range(min: u32, max: u32) -> Self {
+        Self::from_ranges([(min, max)])
+    }
+
+    /// Inclusive ranges in any order, which may overlap. Ranges whose start is greater than their
+    /// end are empty.
+    pub fn from_ranges<I: IntoIterator<Item = (u32, u32)>>(ranges: I) -> Self {
+        let mut ranges = ranges.into_iter().filter(|(min, max)| min <= max).collect::<Vec<_>>();
+        ranges.sort_unstable();
+        let mut merged: Vec<(u32, u32)> = Vec::with_capacity(ranges.len());
+        for (min, max) in ranges {
+            match merged.last_mut() {
+                Some(last) if min <= last.1.saturating_add(1) => last.1 = last.1.max(max),
+                _ => merged.push((min, max)),
+            }
+        }
+        Self { ranges: merged }
+    }
+
+    /// Every code point a class can match in `mode`, e.g. `[^]`.
+    pub fn full(mode: PatternMode) -> Self {
+        Self::from_range(0, mode.max())
+    }
+
+    /// `\d`
+    pub fn digit() -> Self {
+        Self { ranges: DIGIT.to_vec() }
+    }
+
+    /// `\w`
+    pub fn word() -> Self {
+        Self { ranges: WORD.to_vec() }
+    }
+
+    /// `\s`
+    pub fn space() -> Self {
+        Self { ranges: SPACE.to_vec() }
+    }
+
+    /// Inclusive ranges of the set, in ascending order.
+    pub fn ranges(&self) -> &[(u32, u32)] {
+        &self.ranges
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    pub fn contains(&self, code_point: u32) -> bool {
+        self.ranges
+            .binary_search_by(|&(min, max)| {
+                if max < code_point {
+                    std::cmp::Ordering::Less
+                } else if min > code_point {
+                    std::cmp::Ordering::Greater
+                } else {
+                    std::cmp::Ordering::Equal
+                }
+            })
+            .is_ok()
+    }
+
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.difference(other).is_empty()
+    }
+
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        Self::from_ranges(self.ranges.iter().chain(&other.ranges).copied())
+    }
+
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = vec![];
+        let (mut i, mut j) = (0, 0);
+        while let (Some(&(a_min, a_max)), Some(&(b_min, b_max))) =
+            (self.ranges.get(i), other.ranges.get(j))
+        {
+            let (min, max) = (a_min.max(b_min), a_max.min(b_max));
+            if min <= max {
+                ranges.push((min, max));
+            }
+            if a_max < b_max {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        Self { ranges }
+    }
+
+    #[must_use]
+    pub fn difference(&self, other: &Self) -> Self {
+        self.intersection(&other.complement(MAX_CODE_POINT))
+    }
+
+    /// The code points a class can match in `mode` which are not in the set, e.g. `[^a]` for `a`.
+    #[must_use]
+    pub fn negate(&self, mode: PatternMode) -> Self {
+        self.complement(mode.max())
+    }
+
+    fn complement(&self, max: u32) -> Self {
+        let mut ranges = vec![];
+        let mut start = 0;
+        for &(min, end) in &self.ranges {
+            if min > max {
+                break;
+            }
+            if min > start {
+                ranges.push((start, min - 1));
+            }
+            start = end.saturating_add(1);
+        }
+        if start <= max {
+            ranges.push((start, max));
+        }
+        Self { ranges }
+    }
+
+    fn negate_if(self, negate: bool, mode: PatternMode) -> Self {
+        if negate {
+            self.negate(mode)
+        } else {
+            self
+        }
+    }
+
+    /* ---------- Conversion from the AST ---------- */
+
+    pub fn from_character(character: &Character) -> Self {
+        Self::from_code_point(u32::from(character.value))
+    }
+
+    pub fn from_character_class_range(range: &CharacterClassRange) -> Self {
+        Self::from_range(u32::from(range.min.value), u32::from(range.max.value))
+    }
+
+    pub fn from_escape_character_set(set: &EscapeCharacterSet, mode: PatternMode) -> Self {
+        let ranges = match set.kind {
+            EscapeCharacterSetKind::Digit => DIGIT,
+            EscapeCharacterSetKind::Space => SPACE,
+            EscapeCharacterSetKind::Word => WORD,
+        };
+        Self { ranges: ranges.to_vec() }.negate_if(set.negate, mode)
+    }
+
+    /// `.`, `\d` and similar escapes. `None` for Unicode property escapes.
+    pub fn from_character_set(
+        set: &CharacterSet<'_>,
+        dot_all: bool,
+        mode: PatternMode,
+    ) -> Option<Self> {
+        match set {
+            CharacterSet::AnyCharacterSet(_) if dot_all => Some(Self::full(mode)),
+            CharacterSet::AnyCharacterSet(_) => {
+                Some(Self { ranges: LINE_TERMINATOR.to_vec() }.negate(mode))
+            }
+            CharacterSet::EscapeCharacterSet(set) => {
+                Some(Self::from_escape_character_set(set, mode))
+            }
+            CharacterSet::UnicodePropertyCharacterSet(_) => None,
+        }
+    }
+
+    /// `None` if the class contains a Unicode property escape or a string.
+    pub fn from_character_class(class: &CharacterClass<'_>, mode: PatternMode) -> Option<Self> {
+        match class {
+            CharacterClass::ClassRangesCharacterClass(class) => {
+                Self::from_class_ranges_character_class(class, mode)
+            }
+            CharacterClass::UnicodeSetsCharacterClass(class) => {
+                Self::from_unicode_sets_character_class(class, mode)
+            }
+        }
+    }
+
+    pub fn from_class_ranges_character_class(
+        class: &ClassRangesCharacterClass<'_>,
+        mode: PatternMode,
+    ) -> Option<Self> {
+        let mut set = Self::new();
+        for element in &class.elements {
+            let element = match element {
+                ClassRangesCharacterClassElement::Character(character) => {
+                    Self::from_character(character)
+                }
+                ClassRangesCharacterClassElement::CharacterClassRange(range) => {
+                    Self::from_character_class_range(range)
+                }
+                ClassRangesCharacterClassElement::EscapeCharacterSet(set) => {
+                    Self::from_escape_character_set(set, mode)
+                }
+                ClassRangesCharacterClassElement::CharacterUnicodePropertyCharacterSet(_) => {
+                    return None
+                }
+            };
+            set = set.union(&element);
+        }
+        Some(set.negate_if(class.negate, mode))
+    }
+
+    pub fn from_unicode_sets_character_class(
+        class: &UnicodeSetsCharacterClass<'_>,
+        mode: PatternMode,
+    ) -> Option<Self> {
+        let mut set = Self::new();
+        for element in &class.elements {
+            let element = match element {
+                UnicodeSetsCharacterClassElement::Character(character) => {
+                    Self::from_character(character)
+                }
+                UnicodeSetsCharacterClassElement::CharacterClassRange(range) => {
+                    Self::from_character_class_range(range)
+                }
+                UnicodeSetsCharacterClassElement::ClassStringDisjunction(disjunction) => {
+                    Self::from_class_string_disjunction(disjunction)?
+                }
+                UnicodeSetsCharacterClassElement::EscapeCharacterSet(set) => {
+                    Self::from_escape_character_set(set, mode)
+                }
+                UnicodeSetsCharacterClassElement::ExpressionCharacterClass(class) => {
+                    Self::from_expression_character_class(class, mode)?
+                }
+                UnicodeSetsCharacterClassElement::UnicodePropertyCharacterSet(_) => return None,
+                UnicodeSetsCharacterClassElement::UnicodeSetsCharacterClass(class) => {
+                    Self::from_unicode_sets_character_class(class, mode)?
+                }
+            };
+            set = set.union(&element);
+        }
+        Some(set.negate_if(class.negate, mode))
+    }
+
+    /// `[a&&b]` or `[a--b]`
+    pub fn from_expression_character_class(
+        class: &ExpressionCharacterClass<'_>,
+        mode: PatternMode,
+    ) -> Option<Self> {
+        let set = match &class.expression {
+            ExpressionCharacterClassExpr::ClassIntersection(intersection) => {
+                Self::from_class_intersection(intersection, mode)?
+            }
+            ExpressionCharacterClassExpr::ClassSubtraction(subtraction) => {
+                Self::from_class_subtraction(subtraction, mode)?
+            }
+        };
+        Some(set.negate_if(class.negate, mode))
+    }
+
+    fn from_class_intersection(
+        intersection: &ClassIntersection<'_>,
+        mode: PatternMode,
+    ) -> Option<Self> {
+        let left = match &intersection.left {
+            ClassIntersectionLeft::ClassIntersection(left) => {
+                Self::from_class_intersection(left, mode)?
+            }
+            ClassIntersectionLeft::ClassSetOperand(left) => {
+                Self::from_class_set_operand(left, mode)?
+            }
+        };
+        let right = Self::from_class_set_operand(&intersection.right, mode)?;
+        Some(left.intersection(&right))
+    }
+
+    fn from_class_subtraction(
+        subtraction: &ClassSubtraction<'_>,
+        mode: PatternMode,
+    ) -> Option<Self> {
+        let left = match &subtraction.left {
+            ClassSubtractionLeft::ClassSubtraction(left) => {
+                Self::from_class_subtraction(left, mode)?
+            }
+            ClassSubtractionLeft::ClassSetOperand(left) => {
+                Self::from_class_set_operand(left, mode)?
+            }
+        };
+        let right = Self::from_class_set_operand(&subtraction.right, mode)?;
+        Some(left.difference(&right))
+    }
+
+    fn from_class_set_operand(operand: &ClassSetOperand<'_>, mode: PatternMode) -> Option<Self> {
+        match operand {
+            ClassSetOperand::Character(character) => Some(Self::from_character(character)),
+            ClassSetOperand::ClassStringDisjunction(disjunction) => {
+                Self::from_class_string_disjunction(disjunction)
+            }
+            ClassSetOperand::EscapeCharacterSet(set) => {
+                Some(Self::from_escape_character_set(set, mode))
+            }
+            ClassSetOperand::ExpressionCharacterClass(class) => {
+                Self::from_expression_character_class(class, mode)
+            }
+            ClassSetOperand::UnicodePropertyCharacterSet(_) => None,
+            ClassSetOperand::UnicodeSetsCharacterClass(class) => {
+                Self::from_unicode_sets_character_class(class, mode)
+            }
+        }
+    }
+
+    /// `\q{a|b}`, which is a set of code points if every alternative is a single character.
+    fn from_class_string_disjunction(disjunction: &ClassStringDisjunction<'_>) -> Option<Self> {
+        let mut set = Self::new();
+        for alternative in &disjunction.alternatives {
+            let [character] = alternative.elements.as_slice() else { return None };
+            set = set.union(&Self::from_character(character));
+        }
+        Some(set)
+    }
+
+    /* ---------- Serialization ---------- */
+
+    /// The shortest pattern text matching exactly this set, e.g. `\d` for `[0-9]`, `a` for `[a]`
+    /// and `[^a]` for every code point except `a`.
+    pub fn to_pattern(&self, mode: PatternMode) -> String {
+        let negated = self.negate(mode);
+        for (escape, ranges) in CLASS_ESCAPES {
+            if self.ranges == ranges {
+                return escape.to_string();
+            }
+            if negated.ranges == ranges {
+                return escape.to_ascii_uppercase();
+            }
+        }
+        if let [(min, max)] = self.ranges.as_slice() {
+            if min == max {
+                let mut text = String::new();
+                write_code_point(&mut text, *min, false, mode);
+                return text;
+            }
+        }
+        let class = self.to_class(false, mode);
+        let negated_class = negated.to_class(true, mode);
+        if negated_class.len() < class.len() {
+            negated_class
+        } else {
+            class
+        }
+    }
+
+    /// Writes the set as a class, using `\w`, `\d` and `\s` where that is shorter.
+    fn to_class(&self, negate: bool, mode: PatternMode) -> String {
+        let escape_sets =
+            CLASS_ESCAPES.map(|(escape, ranges)| (escape, Self { ranges: ranges.to_vec() }));
+        // `\d` is a subset of `\w`, so there is no point in using both.
+        let combinations: [&[usize]; 6] = [&[], &[0], &[1], &[2], &[0, 2], &[1, 2]];
+        combinations
+            .iter()
+            .filter(|escapes| escapes.iter().all(|&i| escape_sets[i].1.is_subset(self)))
+            .map(|escapes| {
+                let mut rest = self.clone();
+                let mut text = String::from(if negate { "[^" } else { "[" });
+                for &i in *escapes {
+                    let (escape, set) = &escape_sets[i];
+                    text.push_str(escape);
+                    rest = rest.difference(set);
+                }
+                for &(min, max) in &rest.ranges {
+                    write_code_point(&mut text, min, true, mode);
+                    if max > min {
+                        if max > min + 1 {
+                            text.push('-');
+                        }
+                        write_code_point(&mut text, max, true, mode);
+                    }
+                }
+                text.push(']');
+                text
+            })
+            .min_by_key(String::len)
+            .unwrap_or_default()
+    }
+}
+
+fn write_code_point(text: &mut String, code_point: u32, in_class: bool, mode: PatternMode) {
+    let escape = match code_point {
+        0x09 => Some('t'),
+        0x0A => Some('n'),
+        0x0B => Some('v'),
+        0x0C => Some('f'),
+        0x0D => Some('r'),
+        _ => None,
+    };
+    if let Some(escape) = escape {
+        text.push('\\');
+        text.push(escape);
+        return;
+    }
+    match char::from_u32(code_point) {
+        Some(c) if is_syntax_character(c, in_class, mode) => {
+            text.push('\\');
+            text.push(c);
+        }
+        Some(c) if !c.is_control() && (c == ' ' || !c.is_whitespace()) && c != '\u{FEFF}' => {
+            text.push(c);
+        }
+        _ if code_point <= 0xFF => {
+            let _ = write!(text, "\\x{code_point:02X}");
+        }
+        _ if code_point <= MAX_CODE_UNIT => {
+            let _ = write!(text, "\\u{code_point:04X}");
+        }
+        _ => {
+            let _ = write!(text, "\\u{{{code_point:X}}}");
+        }
+    }
+}
+
+/// Characters which have to be escaped to stand for themselves. `/` is included so the pattern
+/// can be written in a regular expression literal.
+fn is_syntax_character(c: char, in_class: bool, mode: PatternMode) -> bool {
+    if in_class {
+        matches!(c, '\\' | ']' | '[' | '-' | '^' | '/')
+            || (mode == PatternMode::UnicodeSets && matches!(c, '(' | ')' | '{' | '}' | '|'))
+    } else {
+        matches!(
+            c,
+            '^' | '$'
+                | '\\'
+                | '.'
+                | '*'
+                | '+'
+                | '?'
+                | '('
+                | ')'
+                | '['
+                | ']'
+                | '{'
+                | '}'
+                | '|'
+                | '/'
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+    use oxc_span::{Span, SPAN};
+
+    use super::{CodePointSet, PatternMode};
+    use crate::{
+        ast::{CharacterClass, ClassRangesCharacterClassElement, EscapeCharacterSetKind},
+        AstBuilder,
+    };
+
+    fn set(ranges: &[(char, char)]) -> CodePointSet {
+        CodePointSet::from_ranges(ranges.iter().map(|&(min, max)| (min as u32, max as u32)))
+    }
+
+    /// `[0-9]` or `[^0-9]`
+    fn digit_class(ast: AstBuilder<'_>, negate: bool) -> CharacterClass<'_> {
+        let range = ast.class_ranges_character_class_element_range(
+            Span::new(1, 4),
+            ast.character(Span::new(1, 2), u16::from(b'0')),
+            ast.character(Span::new(3, 4), u16::from(b'9')),
+        );
+        ast.character_class_class_ranges(SPAN, negate, false, ast.vec1(range))
+    }
+
+    #[test]
+    fn set_operations() {
+        let a = set(&[('a', 'f'), ('x', 'z')]);
+        let b = set(&[('d', 'y')]);
+        assert_eq!(a.union(&b), set(&[('a', 'z')]));
+        assert_eq!(a.intersection(&b), set(&[('d', 'f'), ('x', 'y')]));
+        assert_eq!(a.difference(&b), set(&[('a', 'c'), ('z', 'z')]));
+        assert!(a.contains('e' as u32));
+        assert!(!a.contains('g' as u32));
+        assert!(CodePointSet::digit().is_subset(&CodePointSet::word()));
+        // Adjacent and overlapping ranges are merged.
+        assert_eq!(set(&[('c', 'd'), ('a', 'b'), ('b', 'c')]).ranges(), &[(0x61, 0x64)]);
+
+        let negated = set(&[('a', 'a')]).negate(PatternMode::Legacy);
+        assert_eq!(negated.ranges(), &[(0, 0x60), (0x62, 0xFFFF)]);
+        assert_eq!(negated.negate(PatternMode::Legacy), set(&[('a', 'a')]));
+        assert_eq!(
+            CodePointSet::new().negate(PatternMode::Unicode),
+            CodePointSet::full(PatternMode::Unicode)
+        );
+    }
+
+    #[test]
+    fn from_ast() {
+        let allocator = Allocator::default();
+        let ast = AstBuilder::new(&allocator);
+        let mode = PatternMode::Legacy;
+
+        let digit = CodePointSet::from_character_class(&digit_class(ast, false), mode).unwrap();
+        assert_eq!(digit, CodePointSet::digit());
+        let not_digit = CodePointSet::from_character_class(&digit_class(ast, true), mode).unwrap();
+        let escape = ast.escape_character_set(SPAN, EscapeCharacterSetKind::Digit, true);
+        assert_eq!(not_digit, CodePointSet::from_escape_character_set(&escape, mode));
+
+        let property = ast.character_unicode_property_character_set(SPAN, "L".into(), None, false);
+        let class = ast.character_class_class_ranges(
+            SPAN,
+            false,
+            false,
+            ast.vec1(ClassRangesCharacterClassElement::CharacterUnicodePropertyCharacterSet(
+                ast.alloc(property),
+            )),
+        );
+        assert!(CodePointSet::from_character_class(&class, mode).is_none());
+    }
+
+    #[test]
+    fn to_pattern() {
+        let mode = PatternMode::Unicode;
+        assert_eq!(set(&[('0', '9')]).to_pattern(mode), "\\d");
+        assert_eq!(CodePointSet::word().negate(mode).to_pattern(mode), "\\W");
+        assert_eq!(set(&[('a', 'a')]).to_pattern(mode), "a");
+        assert_eq!(set(&[('.', '.')]).to_pattern(mode), "\\.");
+        assert_eq!(set(&[('a', 'b'), ('x', 'z')]).to_pattern(mode), "[abx-z]");
+        assert_eq!(set(&[('a', 'a')]).negate(mode).to_pattern(mode), "[^a]");
+        assert_eq!(CodePointSet::full(mode).to_pattern(mode), "[^]");
+        assert_eq!(CodePointSet::new().to_pattern(mode), "[]");
+        assert_eq!(CodePointSet::word().union(&set(&[('-', '-')])).to_pattern(mode), "[\\w\\-]");
+        // `[\d/:]` is longer than the range.
+        assert_eq!(set(&[('/', ':')]).to_pattern(mode), "[\\/-:]");
+        assert_eq!(set(&[(']', ']'), ('\n', '\n')]).to_pattern(mode), "[\\n\\]]");
+        assert_eq!(set(&[('(', ')')]).to_pattern(PatternMode::UnicodeSets), "[\\(\\)]");
+        assert_eq!(set(&[('(', ')')]).to_pattern(mode), "[()]");
+        assert_eq!(
+            CodePointSet::from_ranges([(0, 0), (0x2028, 0x2028)]).to_pattern(mode),
+            "[\\x00\\u2028]"
+        );
+    }
+}
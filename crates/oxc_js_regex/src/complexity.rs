@@ -0,0 +1,465 @@
+//! Metrics of how much a pattern can backtrack, for flagging patterns which are vulnerable to
+//! regular expression denial of service (ReDoS).
+//!
+//! Backtracking takes super-linear time when the same input can be matched in many ways:
+//!
+//! * Quadratic or worse when adjacent unbounded quantifiers can match the same characters, e.g.
+//!   `\d+\d*`, which splits a run of digits between them in every possible way.
+//! * Exponential when a repeated group can match the same input in more than one way on each
+//!   repetition, e.g. `(a+)+` or `(\w|\d)*`.
+//!
+//! The analysis only looks at which characters each quantifier can match, so it is an estimate:
+//! it can report a pattern which a following element keeps from being ambiguous.
+
+use oxc_span::Span;
+
+use crate::{
+    ast::{
+        Alternative, CapturingGroup, Element, Flags, Group, LookaheadAssertion,
+        LookbehindAssertion, Pattern, QuantifiableElement, Quantifier, RegExpLiteral,
+    },
+    code_point_set::{CodePointSet, PatternMode},
+    visitor::{walk, Visit},
+};
+
+/// Estimated worst case time of matching a pattern against an input of length `n`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum BacktrackingComplexity {
+    #[default]
+    Linear,
+    /// `n` to the given power, the number of adjacent quantifiers which can match the same
+    /// characters.
+    Polynomial(u32),
+    Exponential,
+}
+
+impl BacktrackingComplexity {
+    /// Whether backtracking can take more than linear time.
+    pub fn is_super_linear(self) -> bool {
+        self != Self::Linear
+    }
+}
+
+/// Two unbounded quantifiers, only separated by elements which can match the empty string, which
+/// can match the same characters, e.g. `\d+` and `\d*` in `\d+,?\d*`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OverlappingQuantifiers {
+    pub first: Span,
+    pub second: Span,
+}
+
+/// Metrics of a pattern.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct PatternMetrics {
+    pub capturing_groups: usize,
+    /// Deepest nesting of unbounded quantifiers, e.g. `2` for `(a*b)+`.
+    pub star_height: usize,
+    /// Largest number of alternatives of a disjunction, e.g. `3` for `a(b|c|d)`.
+    pub max_alternatives: usize,
+    /// Evidence of [`BacktrackingComplexity::Polynomial`].
+    pub overlapping_quantifiers: Vec<OverlappingQuantifiers>,
+    /// Unbounded quantifiers of groups which can match the same input in more than one way,
+    /// evidence of [`BacktrackingComplexity::Exponential`].
+    pub ambiguous_repetitions: Vec<Span>,
+    pub complexity: BacktrackingComplexity,
+}
+
+impl PatternMetrics {
+    /// Metrics of a regular expression literal, whose flags decide what `.` and classes match.
+    pub fn from_reg_exp_literal(literal: &RegExpLiteral<'_>) -> Self {
+        Self::new(&literal.pattern, Some(&literal.flags))
+    }
+
+    /// Metrics of a pattern without flags, e.g. the first argument of `new RegExp()`.
+    pub fn from_pattern(pattern: &Pattern<'_>) -> Self {
+        Self::new(pattern, None)
+    }
+
+    fn new(pattern: &Pattern<'_>, flags: Option<&Flags>) -> Self {
+        let mut analyzer = Analyzer {
+            mode: flags.map_or(PatternMode::Legacy, PatternMode::from_flags),
+            dot_all: flags.is_some_and(|flags| flags.dot_all),
+            metrics: Self::default(),
+            star_depth: 0,
+            max_degree: 1,
+        };
+        analyzer.visit_pattern(pattern);
+        let mut metrics = analyzer.metrics;
+        metrics.complexity = if !metrics.ambiguous_repetitions.is_empty() {
+            BacktrackingComplexity::Exponential
+        } else if analyzer.max_degree > 1 {
+            BacktrackingComplexity::Polynomial(analyzer.max_degree)
+        } else {
+            BacktrackingComplexity::Linear
+        };
+        metrics
+    }
+}
+
+struct Analyzer {
+    mode: PatternMode,
+    dot_all: bool,
+    metrics: PatternMetrics,
+    /// Number of unbounded quantifiers enclosing the visited node.
+    star_depth: usize,
+    /// Longest run of overlapping quantifiers.
+    max_degree: u32,
+}
+
+impl Analyzer {
+    fn check_alternatives(&mut self, alternatives: &[Alternative<'_>]) {
+        self.metrics.max_alternatives = self.metrics.max_alternatives.max(alternatives.len());
+    }
+
+    /// Code points `element` can consume. Elements whose characters are not known, such as
+    /// backreferences and Unicode property escapes, can consume any.
+    fn characters(&self, element: &QuantifiableElement<'_>) -> CodePointSet {
+        let set = match element {
+            QuantifiableElement::Character(character) => {
+                Some(CodePointSet::from_character(character))
+            }
+            QuantifiableElement::CharacterSet(set) => {
+                CodePointSet::from_character_set(set, self.dot_all, self.mode)
+            }
+            QuantifiableElement::CharacterClass(class) => {
+                CodePointSet::from_character_class(class, self.mode)
+            }
+            QuantifiableElement::ExpressionCharacterClass(class) => {
+                CodePointSet::from_expression_character_class(class, self.mode)
+            }
+            QuantifiableElement::Group(group) => {
+                Some(self.alternatives_characters(&group.alternatives))
+            }
+            QuantifiableElement::CapturingGroup(group) => {
+                Some(self.alternatives_characters(&group.alternatives))
+            }
+            QuantifiableElement::LookaheadAssertion(_) => Some(CodePointSet::new()),
+            QuantifiableElement::Backreference(_) => None,
+        };
+        set.unwrap_or_else(|| CodePointSet::full(self.mode))
+    }
+
+    fn alternatives_characters(&self, alternatives: &[Alternative<'_>]) -> CodePointSet {
+        let mut set = CodePointSet::new();
+        for element in alternatives.iter().flat_map(|alternative| &alternative.elements) {
+            let characters = match element {
+                Element::QuantifiableElement(element) => self.characters(element),
+                Element::Quantifier(quantifier) => self.characters(&quantifier.element),
+                Element::Assertion(_) => continue,
+            };
+            set = set.union(&characters);
+        }
+        set
+    }
+
+    /// Finds runs of adjacent unbounded quantifiers which can match the same characters.
+    fn check_overlapping_quantifiers(&mut self, alternative: &Alternative<'_>) {
+        // The last unbounded quantifier, its characters, and the length of its run.
+        let mut previous: Option<(Span, CodePointSet, u32)> = None;
+        for element in &alternative.elements {
+            let Element::Quantifier(quantifier) = element else {
+                previous = None;
+                continue;
+            };
+            if quantifier.max.is_finite() {
+                if quantifier.min > 0.0 {
+                    previous = None;
+                }
+                continue;
+            }
+            let characters = self.characters(&quantifier.element);
+            let degree = match &previous {
+                Some((span, previous_characters, degree))
+                    if !previous_characters.intersection(&characters).is_empty() =>
+                {
+                    self.metrics
+                        .overlapping_quantifiers
+                        .push(OverlappingQuantifiers { first: *span, second: quantifier.span });
+                    degree + 1
+                }
+                _ => 1,
+            };
+            self.max_degree = self.max_degree.max(degree);
+            previous = Some((quantifier.span, characters, degree));
+        }
+    }
+
+    /// Whether each repetition of `quantifier` can match the same input in more than one way:
+    /// an alternative which is an unbounded quantifier apart from elements which can match the
+    /// empty string, `(a+)+` or `(\w+\s?)*`, or alternatives of single characters which overlap,
+    /// `(\w|\d)*`.
+    fn is_ambiguous_repetition(&self, quantifier: &Quantifier<'_>) -> bool {
+        if quantifier.max.is_finite() {
+            return false;
+        }
+        let alternatives = match &quantifier.element {
+            QuantifiableElement::Group(group) => &group.alternatives,
+            QuantifiableElement::CapturingGroup(group) => &group.alternatives,
+            _ => return false,
+        };
+        let has_nested_quantifier = alternatives.iter().any(|alternative| {
+            let mut unbounded = alternative.elements.iter().filter(
+                |element| matches!(element, Element::Quantifier(inner) if inner.max.is_infinite()),
+            );
+            unbounded.next().is_some()
+                && alternative.elements.iter().all(|element| match element {
+                    Element::Quantifier(inner) => inner.min == 0.0 || inner.max.is_infinite(),
+                    Element::Assertion(_) => true,
+                    Element::QuantifiableElement(_) => false,
+                })
+        });
+        if has_nested_quantifier {
+            return true;
+        }
+        let mut characters = vec![];
+        for alternative in alternatives {
+            match alternative.elements.as_slice() {
+                [Element::QuantifiableElement(element)]
+                    if matches!(
+                        **element,
+                        QuantifiableElement::Character(_)
+                            | QuantifiableElement::CharacterSet(_)
+                            | QuantifiableElement::CharacterClass(_)
+                            | QuantifiableElement::ExpressionCharacterClass(_)
+                    ) =>
+                {
+                    characters.push(self.characters(element));
+                }
+                _ => {}
+            }
+        }
+        characters.iter().enumerate().any(|(i, set)| {
+            characters[i + 1..].iter().any(|other| !set.intersection(other).is_empty())
+        })
+    }
+}
+
+impl<'a> Visit<'a> for Analyzer {
+    fn visit_pattern(&mut self, it: &Pattern<'a>) {
+        self.check_alternatives(&it.alternatives);
+        walk::walk_pattern(self, it);
+    }
+
+    fn visit_alternative(&mut self, it: &Alternative<'a>) {
+        self.check_overlapping_quantifiers(it);
+        walk::walk_alternative(self, it);
+    }
+
+    fn visit_group(&mut self, it: &Group<'a>) {
+        self.check_alternatives(&it.alternatives);
+        walk::walk_group(self, it);
+    }
+
+    fn visit_capturing_group(&mut self, it: &CapturingGroup<'a>) {
+        self.metrics.capturing_groups += 1;
+        self.check_alternatives(&it.alternatives);
+        walk::walk_capturing_group(self, it);
+    }
+
+    fn visit_lookahead_assertion(&mut self, it: &LookaheadAssertion<'a>) {
+        self.check_alternatives(&it.alternatives);
+        walk::walk_lookahead_assertion(self, it);
+    }
+
+    fn visit_lookbehind_assertion(&mut self, it: &LookbehindAssertion<'a>) {
+        self.check_alternatives(&it.alternatives);
+        walk::walk_lookbehind_assertion(self, it);
+    }
+
+    fn visit_quantifier(&mut self, it: &Quantifier<'a>) {
+        if self.is_ambiguous_repetition(it) {
+            self.metrics.ambiguous_repetitions.push(it.span);
+        }
+        let is_unbounded = it.max.is_infinite();
+        if is_unbounded {
+            self.star_depth += 1;
+            self.metrics.star_height = self.metrics.star_height.max(self.star_depth);
+        }
+        walk::walk_quantifier(self, it);
+        if is_unbounded {
+            self.star_depth -= 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+    use oxc_span::Span;
+
+    use super::{BacktrackingComplexity, OverlappingQuantifiers, PatternMetrics};
+    use crate::{
+        ast::{Alternative, Element, EscapeCharacterSetKind, QuantifiableElement},
+        AstBuilder,
+    };
+
+    fn pattern<'a>(ast: AstBuilder<'a>, elements: Vec<Element<'a>>) -> PatternMetrics {
+        let span = Span::new(0, 10);
+        let pattern =
+            ast.pattern(span, ast.vec1(ast.alternative(span, ast.vec_from_iter(elements))));
+        PatternMetrics::from_pattern(&pattern)
+    }
+
+    fn alternative<'a>(ast: AstBuilder<'a>, elements: Vec<Element<'a>>) -> Alternative<'a> {
+        ast.alternative(Span::new(0, 0), ast.vec_from_iter(elements))
+    }
+
+    fn digit(ast: AstBuilder<'_>) -> QuantifiableElement<'_> {
+        ast.quantifiable_element_escape_character_set(
+            Span::new(0, 0),
+            EscapeCharacterSetKind::Digit,
+            false,
+        )
+    }
+
+    fn word(ast: AstBuilder<'_>) -> QuantifiableElement<'_> {
+        ast.quantifiable_element_escape_character_set(
+            Span::new(0, 0),
+            EscapeCharacterSetKind::Word,
+            false,
+        )
+    }
+
+    fn character(ast: AstBuilder<'_>, value: char) -> QuantifiableElement<'_> {
+        ast.quantifiable_element_character(Span::new(0, 0), value as u16)
+    }
+
+    fn quantifier<'a>(
+        ast: AstBuilder<'a>,
+        start: u32,
+        min: f64,
+        max: f64,
+        element: QuantifiableElement<'a>,
+    ) -> Element<'a> {
+        ast.element_quantifier(Span::new(start, start + 1), min, max, true, element)
+    }
+
+    #[test]
+    fn linear() {
+        let allocator = Allocator::default();
+        let ast = AstBuilder::new(&allocator);
+        // \d+a\d+
+        let metrics = pattern(
+            ast,
+            vec![
+                quantifier(ast, 0, 1.0, f64::INFINITY, digit(ast)),
+                ast.element_character(Span::new(3, 4), u16::from(b'a')),
+                quantifier(ast, 4, 1.0, f64::INFINITY, digit(ast)),
+            ],
+        );
+        assert_eq!(metrics.complexity, BacktrackingComplexity::Linear);
+        assert!(!metrics.complexity.is_super_linear());
+        assert_eq!(metrics.star_height, 1);
+        assert_eq!(metrics.max_alternatives, 1);
+
+        // a*\d+
+        let metrics = pattern(
+            ast,
+            vec![
+                quantifier(ast, 0, 0.0, f64::INFINITY, character(ast, 'a')),
+                quantifier(ast, 2, 1.0, f64::INFINITY, digit(ast)),
+            ],
+        );
+        assert_eq!(metrics.complexity, BacktrackingComplexity::Linear);
+    }
+
+    #[test]
+    fn overlapping_quantifiers() {
+        let allocator = Allocator::default();
+        let ast = AstBuilder::new(&allocator);
+        // \d+,?\w*
+        let metrics = pattern(
+            ast,
+            vec![
+                quantifier(ast, 0, 1.0, f64::INFINITY, digit(ast)),
+                quantifier(ast, 3, 0.0, 1.0, character(ast, ',')),
+                quantifier(ast, 5, 0.0, f64::INFINITY, word(ast)),
+            ],
+        );
+        assert_eq!(metrics.complexity, BacktrackingComplexity::Polynomial(2));
+        assert!(metrics.complexity.is_super_linear());
+        assert_eq!(
+            metrics.overlapping_quantifiers,
+            [OverlappingQuantifiers { first: Span::new(0, 1), second: Span::new(5, 6) }]
+        );
+
+        // \d*\d*\d*
+        let metrics = pattern(
+            ast,
+            (0..3).map(|i| quantifier(ast, i * 3, 0.0, f64::INFINITY, digit(ast))).collect(),
+        );
+        assert_eq!(metrics.complexity, BacktrackingComplexity::Polynomial(3));
+        assert_eq!(metrics.overlapping_quantifiers.len(), 2);
+
+        // \d+,\d+ is separated by a character which must match.
+        let metrics = pattern(
+            ast,
+            vec![
+                quantifier(ast, 0, 1.0, f64::INFINITY, digit(ast)),
+                ast.element_character(Span::new(3, 4), u16::from(b',')),
+                quantifier(ast, 4, 1.0, f64::INFINITY, digit(ast)),
+            ],
+        );
+        assert!(metrics.overlapping_quantifiers.is_empty());
+    }
+
+    #[test]
+    fn ambiguous_repetitions() {
+        let allocator = Allocator::default();
+        let ast = AstBuilder::new(&allocator);
+
+        // (a+)+
+        let group = ast.quantifiable_element_capturing_group(
+            Span::new(0, 4),
+            None,
+            ast.vec1(alternative(
+                ast,
+                vec![quantifier(ast, 1, 1.0, f64::INFINITY, character(ast, 'a'))],
+            )),
+        );
+        let metrics = pattern(ast, vec![quantifier(ast, 0, 1.0, f64::INFINITY, group)]);
+        assert_eq!(metrics.complexity, BacktrackingComplexity::Exponential);
+        assert_eq!(metrics.ambiguous_repetitions, [Span::new(0, 1)]);
+        assert_eq!(metrics.star_height, 2);
+        assert_eq!(metrics.capturing_groups, 1);
+
+        // (?:\w|\d)*
+        let group = ast.quantifiable_element_group(
+            Span::new(0, 9),
+            ast.vec_from_iter([
+                alternative(ast, vec![ast.element_quantifiable_element(word(ast))]),
+                alternative(ast, vec![ast.element_quantifiable_element(digit(ast))]),
+            ]),
+        );
+        let metrics = pattern(ast, vec![quantifier(ast, 0, 0.0, f64::INFINITY, group)]);
+        assert_eq!(metrics.complexity, BacktrackingComplexity::Exponential);
+        assert_eq!(metrics.max_alternatives, 2);
+        assert_eq!(metrics.star_height, 1);
+
+        // (?:a|b)*
+        let group = ast.quantifiable_element_group(
+            Span::new(0, 7),
+            ast.vec_from_iter([
+                alternative(ast, vec![ast.element_quantifiable_element(character(ast, 'a'))]),
+                alternative(ast, vec![ast.element_quantifiable_element(character(ast, 'b'))]),
+            ]),
+        );
+        let metrics = pattern(ast, vec![quantifier(ast, 0, 0.0, f64::INFINITY, group)]);
+        assert_eq!(metrics.complexity, BacktrackingComplexity::Linear);
+
+        // (?:a+b)+ is unambiguous, every repetition ends with `b`.
+        let group = ast.quantifiable_element_group(
+            Span::new(0, 7),
+            ast.vec1(alternative(
+                ast,
+                vec![
+                    quantifier(ast, 3, 1.0, f64::INFINITY, character(ast, 'a')),
+                    ast.element_character(Span::new(5, 6), u16::from(b'b')),
+                ],
+            )),
+        );
+        let metrics = pattern(ast, vec![quantifier(ast, 0, 1.0, f64::INFINITY, group)]);
+        assert_eq!(metrics.complexity, BacktrackingComplexity::Linear);
+        assert_eq!(metrics.star_height, 2);
+    }
+}
@@ -0,0 +1,215 @@
+//! Flags in effect inside a pattern.
+//!
+//! The flags of a literal apply to the whole pattern, except inside groups with modifiers, which
+//! enable or disable `i`, `m` and `s` for their alternatives. In `/a(?i:b(?-i:c))/` only `b`
+//! matches case-insensitively, and the group `(?-i:c)` itself is inside the `i` group.
+//!
+//! See <https://github.com/tc39/proposal-regexp-modifiers>
+
+use oxc_span::Span;
+
+use crate::{
+    ast::{Flags, Group, Modifiers, Pattern, RegExpLiteral},
+    visitor::{walk, Visit},
+};
+
+/// The flags which affect how a part of a pattern matches.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct EffectiveFlags {
+    pub ignore_case: bool,
+    pub multiline: bool,
+    pub dot_all: bool,
+    /// `u`, which modifiers can not change.
+    pub unicode: bool,
+    /// `v`, which modifiers can not change.
+    pub unicode_sets: bool,
+}
+
+impl EffectiveFlags {
+    /// The flags of a literal, which apply outside of groups with modifiers.
+    pub fn new(flags: &Flags) -> Self {
+        Self {
+            ignore_case: flags.ignore_case,
+            multiline: flags.multiline,
+            dot_all: flags.dot_all,
+            unicode: flags.unicode,
+            unicode_sets: flags.unicode_sets,
+        }
+    }
+
+    /// The flags inside a group with `modifiers`.
+    #[must_use]
+    pub fn with_modifiers(self, modifiers: &Modifiers) -> Self {
+        let apply = |flag: bool, enabling: bool, disabling: bool| (flag || enabling) && !disabling;
+        let (enabling, disabling) = (modifiers.enabling, modifiers.disabling);
+        Self {
+            ignore_case: apply(self.ignore_case, enabling.ignore_case, disabling.ignore_case),
+            multiline: apply(self.multiline, enabling.multiline, disabling.multiline),
+            dot_all: apply(self.dot_all, enabling.dot_all, disabling.dot_all),
+            ..self
+        }
+    }
+
+    /// Whether patterns use Unicode semantics, `u` or `v`.
+    pub fn is_unicode_mode(&self) -> bool {
+        self.unicode || self.unicode_sets
+    }
+}
+
+/// Effective flags of the groups around the current position of a parser or visitor.
+///
+/// Outside of all groups the flags are those of the literal, and every group pushes its flags
+/// when entered, changed by its modifiers if it has any.
+#[derive(Debug, Clone)]
+pub struct FlagState {
+    root: EffectiveFlags,
+    stack: Vec<EffectiveFlags>,
+}
+
+impl FlagState {
+    pub fn new(flags: EffectiveFlags) -> Self {
+        Self { root: flags, stack: vec![] }
+    }
+
+    /// The flags at the current position.
+    pub fn current(&self) -> EffectiveFlags {
+        self.stack.last().copied().unwrap_or(self.root)
+    }
+
+    pub fn enter_group(&mut self, modifiers: Option<&Modifiers>) {
+        let flags = self.current();
+        self.stack.push(modifiers.map_or(flags, |modifiers| flags.with_modifiers(modifiers)));
+    }
+
+    pub fn leave_group(&mut self) {
+        let left = self.stack.pop();
+        debug_assert!(left.is_some(), "left more groups than were entered");
+    }
+}
+
+/// Effective flags of every node of a pattern.
+#[derive(Debug, Clone)]
+pub struct FlagTable {
+    flags: EffectiveFlags,
+    /// Groups with modifiers and the flags of their alternatives, outer groups first.
+    groups: Vec<(Span, EffectiveFlags)>,
+}
+
+impl FlagTable {
+    pub fn new(literal: &RegExpLiteral<'_>) -> Self {
+        Self::with_flags(&literal.pattern, EffectiveFlags::new(&literal.flags))
+    }
+
+    /// Table of `pattern` with the flags `flags` of its literal.
+    pub fn with_flags(pattern: &Pattern<'_>, flags: EffectiveFlags) -> Self {
+        let mut builder = FlagTableBuilder {
+            state: FlagState::new(flags),
+            table: Self { flags, groups: vec![] },
+        };
+        builder.visit_pattern(pattern);
+        builder.table
+    }
+
+    /// Flags of the node at `span`. A group with modifiers has the flags around it, and only
+    /// the nodes inside it have the modified flags.
+    pub fn get(&self, span: Span) -> EffectiveFlags {
+        self.groups
+            .iter()
+            .rev()
+            .find(|(group, _)| {
+                group.start < span.start && span.start < group.end && span.end <= group.end
+            })
+            .map_or(self.flags, |(_, flags)| *flags)
+    }
+
+    /// Flags at the character at `offset`.
+    pub fn at_offset(&self, offset: u32) -> EffectiveFlags {
+        self.get(Span::new(offset, offset + 1))
+    }
+}
+
+struct FlagTableBuilder {
+    state: FlagState,
+    table: FlagTable,
+}
+
+impl<'a> Visit<'a> for FlagTableBuilder {
+    fn visit_group(&mut self, it: &Group<'a>) {
+        self.state.enter_group(it.modifiers.as_ref());
+        if it.modifiers.is_some() {
+            self.table.groups.push((it.span, self.state.current()));
+        }
+        walk::walk_group(self, it);
+        self.state.leave_group();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+    use oxc_span::Span;
+
+    use super::{EffectiveFlags, FlagState, FlagTable};
+    use crate::{
+        ast::{Alternative, ModifierFlags},
+        AstBuilder,
+    };
+
+    fn character(ast: AstBuilder<'_>, start: u32, value: u8) -> Alternative<'_> {
+        let span = Span::new(start, start + 1);
+        ast.alternative(span, ast.vec1(ast.element_character(span, u16::from(value))))
+    }
+
+    const I: ModifierFlags = ModifierFlags { ignore_case: true, multiline: false, dot_all: false };
+    const NONE: ModifierFlags =
+        ModifierFlags { ignore_case: false, multiline: false, dot_all: false };
+
+    #[test]
+    fn nested_modifiers() {
+        // /a(?i:b(?-i:c))/m
+        let allocator = Allocator::default();
+        let ast = AstBuilder::new(&allocator);
+        let inner = ast.element_group_with_modifiers(
+            Span::new(7, 14),
+            ast.modifiers(Span::new(9, 11), NONE, I),
+            ast.vec1(character(ast, 12, b'c')),
+        );
+        let b = ast.element_character(Span::new(6, 7), u16::from(b'b'));
+        let outer = ast.element_group_with_modifiers(
+            Span::new(1, 15),
+            ast.modifiers(Span::new(3, 4), I, NONE),
+            ast.vec1(ast.alternative(Span::new(6, 14), ast.vec_from_iter([b, inner]))),
+        );
+        let a = ast.element_character(Span::new(0, 1), u16::from(b'a'));
+        let pattern = ast.pattern(
+            Span::new(0, 15),
+            ast.vec1(ast.alternative(Span::new(0, 15), ast.vec_from_iter([a, outer]))),
+        );
+        let flags = EffectiveFlags { multiline: true, ..EffectiveFlags::default() };
+        let table = FlagTable::with_flags(&pattern, flags);
+
+        let case_insensitive = EffectiveFlags { ignore_case: true, ..flags };
+        assert_eq!(table.at_offset(0), flags);
+        assert_eq!(table.get(Span::new(1, 15)), flags);
+        assert_eq!(table.at_offset(6), case_insensitive);
+        assert_eq!(table.get(Span::new(7, 14)), case_insensitive);
+        assert_eq!(table.at_offset(12), flags);
+        assert_eq!(table.get(Span::new(0, 15)), flags);
+    }
+
+    #[test]
+    fn state() {
+        let mut state = FlagState::new(EffectiveFlags::default());
+        let allocator = Allocator::default();
+        let ast = AstBuilder::new(&allocator);
+        let modifiers =
+            ast.modifiers(Span::new(0, 1), ModifierFlags { dot_all: true, ..NONE }, NONE);
+        state.enter_group(Some(&modifiers));
+        assert!(state.current().dot_all);
+        state.enter_group(None);
+        assert!(state.current().dot_all);
+        state.leave_group();
+        state.leave_group();
+        assert!(!state.current().dot_all);
+    }
+}
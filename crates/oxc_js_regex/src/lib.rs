@@ -1,5 +1,22 @@
 pub mod ast;
+mod ast_builder;
+pub mod ast_kind;
+pub mod capturing_groups;
+pub mod code_point_set;
+pub mod complexity;
+pub mod flag_state;
 mod lexer;
+pub mod node_index;
 pub mod parser;
+pub mod printer;
+pub mod reader;
+pub mod rename;
+mod span;
+pub mod span_factory;
 pub mod validator;
 pub mod visitor;
+pub mod warnings;
+
+pub use oxc_syntax::regexp_flags::{self as flags, is_valid_regex_flags};
+
+pub use crate::ast_builder::AstBuilder;
@@ -0,0 +1,160 @@
+//! Lookup of the nodes of a pattern by offset, for editor features such as hover and for linter
+//! fixes which start from a position in the source, without walking the tree for every query.
+
+use oxc_span::{GetSpan, Span};
+
+use crate::{ast::Pattern, ast_kind::AstKind};
+
+/// The nodes of a pattern ordered by their spans, built once and queried by offset.
+///
+/// Nodes are stored in pre-order, where children are sorted by their start, so starts never
+/// decrease and the last node which starts at or before an offset is the innermost candidate.
+/// If it ends before the offset, the node containing the offset is one of its ancestors.
+#[derive(Debug)]
+pub struct NodeIndex<'a> {
+    nodes: Vec<IndexedNode<'a>>,
+}
+
+#[derive(Debug)]
+struct IndexedNode<'a> {
+    kind: AstKind<'a>,
+    span: Span,
+    /// Position of the parent in [`NodeIndex::nodes`].
+    parent: Option<usize>,
+}
+
+impl<'a> NodeIndex<'a> {
+    pub fn new(pattern: &'a Pattern<'a>) -> Self {
+        let mut index = Self { nodes: vec![] };
+        index.add(AstKind::from(pattern), None);
+        index
+    }
+
+    fn add(&mut self, kind: AstKind<'a>, parent: Option<usize>) {
+        let position = self.nodes.len();
+        self.nodes.push(IndexedNode { kind, span: kind.span(), parent });
+        for child in kind.children() {
+            self.add(child, Some(position));
+        }
+    }
+
+    /// Number of nodes, including the pattern.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// The innermost node whose span contains `offset`, like [`AstKind::node_at_offset`].
+    pub fn node_at(&self, offset: u32) -> Option<AstKind<'a>> {
+        self.position_at(offset).map(|position| self.nodes[position].kind)
+    }
+
+    /// The nodes whose spans contain `offset`, from the innermost to the pattern.
+    pub fn ancestors_at(&self, offset: u32) -> impl Iterator<Item = AstKind<'a>> + '_ {
+        let mut position = self.position_at(offset);
+        std::iter::from_fn(move || {
+            let node = &self.nodes[position?];
+            position = node.parent;
+            Some(node.kind)
+        })
+    }
+
+    fn position_at(&self, offset: u32) -> Option<usize> {
+        let end = self.nodes.partition_point(|node| node.span.start <= offset);
+        let mut position = end.checked_sub(1)?;
+        loop {
+            let node = &self.nodes[position];
+            if offset < node.span.end {
+                return Some(position);
+            }
+            position = node.parent?;
+        }
+    }
+}
+
+impl<'a> Pattern<'a> {
+    /// The innermost node whose span contains `offset`.
+    ///
+    /// This builds a [`NodeIndex`], which should be kept for repeated queries.
+    pub fn node_at(&'a self, offset: u32) -> Option<AstKind<'a>> {
+        NodeIndex::new(self).node_at(offset)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+    use oxc_span::{GetSpan, Span};
+
+    use super::NodeIndex;
+    use crate::{ast_kind::AstKind, AstBuilder};
+
+    #[test]
+    fn node_at() {
+        // a(?:b|.)+|c
+        let allocator = Allocator::default();
+        let ast = AstBuilder::new(&allocator);
+        let group = ast.quantifiable_element_group(
+            Span::new(1, 8),
+            ast.vec_from_iter([
+                ast.alternative(
+                    Span::new(4, 5),
+                    ast.vec1(ast.element_character(Span::new(4, 5), u16::from(b'b'))),
+                ),
+                ast.alternative(
+                    Span::new(6, 7),
+                    ast.vec1(ast.element_quantifiable_element(
+                        ast.quantifiable_element_any_character_set(Span::new(6, 7)),
+                    )),
+                ),
+            ]),
+        );
+        let pattern = ast.pattern(
+            Span::new(0, 11),
+            ast.vec_from_iter([
+                ast.alternative(
+                    Span::new(0, 9),
+                    ast.vec_from_iter([
+                        ast.element_character(Span::new(0, 1), u16::from(b'a')),
+                        ast.element_quantifier(Span::new(1, 9), 1.0, f64::INFINITY, true, group),
+                    ]),
+                ),
+                ast.alternative(
+                    Span::new(10, 11),
+                    ast.vec1(ast.element_character(Span::new(10, 11), u16::from(b'c'))),
+                ),
+            ]),
+        );
+
+        let index = NodeIndex::new(&pattern);
+        assert_eq!(index.len(), 11);
+        let root = AstKind::from(&pattern);
+        for offset in 0..12 {
+            let expected = root.node_at_offset(offset).map(|node| node.span());
+            assert_eq!(index.node_at(offset).map(|node| node.span()), expected, "{offset}");
+        }
+        assert!(matches!(index.node_at(6), Some(AstKind::AnyCharacterSet(_))));
+        // `)+` belongs to the quantifier, after the last alternative of the group ended.
+        assert!(matches!(index.node_at(8), Some(AstKind::Quantifier(_))));
+        // `|` belongs to the pattern.
+        assert!(matches!(index.node_at(9), Some(AstKind::Pattern(_))));
+        assert!(index.node_at(11).is_none());
+
+        let spans = index.ancestors_at(4).map(|node| node.span()).collect::<Vec<_>>();
+        assert_eq!(
+            spans,
+            [
+                Span::new(4, 5),
+                Span::new(4, 5),
+                Span::new(1, 8),
+                Span::new(1, 9),
+                Span::new(0, 9),
+                Span::new(0, 11)
+            ]
+        );
+        assert!(matches!(pattern.node_at(10), Some(AstKind::Character(_))));
+    }
+}
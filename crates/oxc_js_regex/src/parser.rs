@@ -1 +1,10 @@
+use crate::span_factory::SpanEncoding;
 
+/// Options for parsing a pattern.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct ParserOptions {
+    /// Added to every span offset, e.g. the offset of the pattern in its source file.
+    pub span_offset: u32,
+    /// Unit of span offsets. Default `Utf8`
+    pub span_encoding: SpanEncoding,
+}
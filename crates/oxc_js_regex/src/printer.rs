@@ -0,0 +1,396 @@
+//! Compact tree dump of patterns, for snapshot tests and AST explorers.
+//!
+//! Every node is printed on its own line, indented by its depth, as its kind, its span and the
+//! values which are not child nodes:
+//!
+//! ```text
+//! Pattern 0..10
+//!   Alternative 0..10
+//!     CapturingGroup 0..7 name=a
+//!       Alternative 5..6
+//!         Character 5..6 'x'
+//!     Quantifier 7..10 {0,} lazy
+//!       AnyCharacterSet 7..8
+//! ```
+//!
+//! Unlike the `Debug` output, the format does not depend on how the AST structs and enums are
+//! laid out, so it only changes when the parsed tree does.
+
+use std::fmt::{self, Write};
+
+use oxc_span::Span;
+
+use crate::{
+    ast::{
+        Alternative, AnyCharacterSet, Backreference, BackreferenceRef, BoundaryAssertion,
+        CapturingGroup, Character, CharacterClassRange, CharacterUnicodePropertyCharacterSet,
+        ClassIntersection, ClassRangesCharacterClass, ClassRangesCharacterClassElement,
+        ClassStringDisjunction, ClassSubtraction, EdgeAssertionKind, EscapeCharacterSet,
+        EscapeCharacterSetKind, ExpressionCharacterClass, Flags, Group, LookaheadAssertion,
+        LookbehindAssertion, ModifierFlags, Pattern, Quantifier, RegExpLiteral, StringAlternative,
+        StringsUnicodePropertyCharacterSet, UnicodePropertyCharacterSet, UnicodeSetsCharacterClass,
+    },
+    visitor::{walk, Visit},
+};
+
+/// Tree dump of `literal`, its pattern followed by its flags.
+pub fn print_regexp_literal(literal: &RegExpLiteral<'_>) -> String {
+    let mut printer = TreePrinter::default();
+    printer.visit_reg_exp_literal(literal);
+    printer.text
+}
+
+/// Tree dump of `pattern`.
+pub fn print_pattern(pattern: &Pattern<'_>) -> String {
+    let mut printer = TreePrinter::default();
+    printer.visit_pattern(pattern);
+    printer.text
+}
+
+#[derive(Default)]
+struct TreePrinter {
+    text: String,
+    depth: usize,
+}
+
+impl TreePrinter {
+    /// Print the line of a node, `values` are appended separated by spaces, skipping empty ones.
+    fn line(&mut self, kind: &str, span: Span, values: &[&dyn fmt::Display]) {
+        for _ in 0..self.depth {
+            self.text.push_str("  ");
+        }
+        write!(self.text, "{kind} {}..{}", span.start, span.end).unwrap();
+        for value in values {
+            let len = self.text.len();
+            write!(self.text, " {value}").unwrap();
+            if self.text.len() == len + 1 {
+                self.text.truncate(len);
+            }
+        }
+        self.text.push('\n');
+    }
+
+    /// Print the line of a node, and the lines of its children indented below it.
+    fn node(
+        &mut self,
+        kind: &str,
+        span: Span,
+        values: &[&dyn fmt::Display],
+        children: impl FnOnce(&mut Self),
+    ) {
+        self.line(kind, span, values);
+        self.depth += 1;
+        children(self);
+        self.depth -= 1;
+    }
+
+    fn character_unicode_property_character_set(
+        &mut self,
+        it: &CharacterUnicodePropertyCharacterSet,
+    ) {
+        let property = match &it.value {
+            Some(value) => format!("{}={value}", it.key),
+            None => it.key.to_string(),
+        };
+        self.line(
+            "CharacterUnicodePropertyCharacterSet",
+            it.span,
+            &[&property, &negate(it.negate)],
+        );
+    }
+
+    fn strings_unicode_property_character_set(&mut self, it: &StringsUnicodePropertyCharacterSet) {
+        self.line("StringsUnicodePropertyCharacterSet", it.span, &[&it.key]);
+    }
+}
+
+/// `'a'` for printable characters, `U+000A` for others and for lone surrogates.
+fn character_value(value: u16) -> String {
+    match char::from_u32(u32::from(value)) {
+        Some(c) if !c.is_control() && !c.is_whitespace() => format!("{c:?}"),
+        _ => format!("U+{value:04X}"),
+    }
+}
+
+fn negate(negate: bool) -> &'static str {
+    if negate {
+        "negate"
+    } else {
+        ""
+    }
+}
+
+impl<'a> Visit<'a> for TreePrinter {
+    fn visit_reg_exp_literal(&mut self, it: &RegExpLiteral<'a>) {
+        self.node("RegExpLiteral", it.span, &[], |p| walk::walk_reg_exp_literal(p, it));
+    }
+
+    fn visit_pattern(&mut self, it: &Pattern<'a>) {
+        self.node("Pattern", it.span, &[], |p| walk::walk_pattern(p, it));
+    }
+
+    fn visit_alternative(&mut self, it: &Alternative<'a>) {
+        self.node("Alternative", it.span, &[], |p| walk::walk_alternative(p, it));
+    }
+
+    fn visit_boundary_assertion(&mut self, it: &BoundaryAssertion<'a>) {
+        match it {
+            BoundaryAssertion::EdgeAssertion(it) => {
+                let kind = match it.kind {
+                    EdgeAssertionKind::Start => "start",
+                    EdgeAssertionKind::End => "end",
+                };
+                self.line("EdgeAssertion", it.span, &[&kind]);
+            }
+            BoundaryAssertion::WordBoundaryAssertion(it) => {
+                self.line("WordBoundaryAssertion", it.span, &[&negate(it.negate)]);
+            }
+        }
+    }
+
+    fn visit_lookahead_assertion(&mut self, it: &LookaheadAssertion<'a>) {
+        self.node("LookaheadAssertion", it.span, &[&negate(it.negate)], |p| {
+            walk::walk_lookahead_assertion(p, it);
+        });
+    }
+
+    fn visit_lookbehind_assertion(&mut self, it: &LookbehindAssertion<'a>) {
+        self.node("LookbehindAssertion", it.span, &[&negate(it.negate)], |p| {
+            walk::walk_lookbehind_assertion(p, it);
+        });
+    }
+
+    fn visit_quantifier(&mut self, it: &Quantifier<'a>) {
+        let range = if it.max.is_infinite() {
+            format!("{{{},}}", it.min)
+        } else {
+            format!("{{{},{}}}", it.min, it.max)
+        };
+        let greedy = if it.greedy { "" } else { "lazy" };
+        self.node("Quantifier", it.span, &[&range, &greedy], |p| walk::walk_quantifier(p, it));
+    }
+
+    fn visit_backreference(&mut self, it: &Backreference<'a>) {
+        match &it.reference {
+            BackreferenceRef::Number(number) => {
+                self.line("Backreference", it.span, &[&format_args!("ref={number}")]);
+            }
+            BackreferenceRef::CompactStr(name) => {
+                self.line("Backreference", it.span, &[&format_args!("ref={name}")]);
+            }
+        }
+    }
+
+    fn visit_capturing_group(&mut self, it: &CapturingGroup<'a>) {
+        let name = it.name.as_ref().map(|name| format!("name={name}")).unwrap_or_default();
+        self.node("CapturingGroup", it.span, &[&name], |p| walk::walk_capturing_group(p, it));
+    }
+
+    fn visit_group(&mut self, it: &Group<'a>) {
+        let modifiers = it
+            .modifiers
+            .map(|modifiers| {
+                let disabling = modifier_flags(modifiers.disabling);
+                let enabling = modifier_flags(modifiers.enabling);
+                if disabling.is_empty() {
+                    format!("modifiers={enabling}")
+                } else {
+                    format!("modifiers={enabling}-{disabling}")
+                }
+            })
+            .unwrap_or_default();
+        self.node("Group", it.span, &[&modifiers], |p| walk::walk_group(p, it));
+    }
+
+    fn visit_character(&mut self, it: &Character) {
+        self.line("Character", it.span, &[&character_value(it.value)]);
+    }
+
+    fn visit_class_ranges_character_class(&mut self, it: &ClassRangesCharacterClass<'a>) {
+        self.node("ClassRangesCharacterClass", it.span, &[&negate(it.negate)], |p| {
+            for element in &it.elements {
+                match element {
+                    ClassRangesCharacterClassElement::Character(it) => p.visit_character(it),
+                    ClassRangesCharacterClassElement::CharacterClassRange(it) => {
+                        p.visit_character_class_range(it);
+                    }
+                    ClassRangesCharacterClassElement::CharacterUnicodePropertyCharacterSet(it) => {
+                        p.character_unicode_property_character_set(it);
+                    }
+                    ClassRangesCharacterClassElement::EscapeCharacterSet(it) => {
+                        p.visit_escape_character_set(it);
+                    }
+                }
+            }
+        });
+    }
+
+    fn visit_unicode_sets_character_class(&mut self, it: &UnicodeSetsCharacterClass<'a>) {
+        self.node("UnicodeSetsCharacterClass", it.span, &[&negate(it.negate)], |p| {
+            walk::walk_unicode_sets_character_class(p, it);
+        });
+    }
+
+    fn visit_character_class_range(&mut self, it: &CharacterClassRange) {
+        self.node("CharacterClassRange", it.span, &[], |p| {
+            walk::walk_character_class_range(p, it);
+        });
+    }
+
+    fn visit_any_character_set(&mut self, it: &AnyCharacterSet) {
+        self.line("AnyCharacterSet", it.span, &[]);
+    }
+
+    fn visit_escape_character_set(&mut self, it: &EscapeCharacterSet) {
+        let kind = match it.kind {
+            EscapeCharacterSetKind::Digit => "digit",
+            EscapeCharacterSetKind::Space => "space",
+            EscapeCharacterSetKind::Word => "word",
+        };
+        self.line("EscapeCharacterSet", it.span, &[&kind, &negate(it.negate)]);
+    }
+
+    fn visit_unicode_property_character_set(&mut self, it: &UnicodePropertyCharacterSet<'a>) {
+        match it {
+            UnicodePropertyCharacterSet::CharacterUnicodePropertyCharacterSet(it) => {
+                self.character_unicode_property_character_set(it);
+            }
+            UnicodePropertyCharacterSet::StringsUnicodePropertyCharacterSet(it) => {
+                self.strings_unicode_property_character_set(it);
+            }
+        }
+    }
+
+    fn visit_expression_character_class(&mut self, it: &ExpressionCharacterClass<'a>) {
+        self.node("ExpressionCharacterClass", it.span, &[&negate(it.negate)], |p| {
+            walk::walk_expression_character_class(p, it);
+        });
+    }
+
+    fn visit_class_intersection(&mut self, it: &ClassIntersection<'a>) {
+        self.node("ClassIntersection", it.span, &[], |p| walk::walk_class_intersection(p, it));
+    }
+
+    fn visit_class_subtraction(&mut self, it: &ClassSubtraction<'a>) {
+        self.node("ClassSubtraction", it.span, &[], |p| walk::walk_class_subtraction(p, it));
+    }
+
+    fn visit_class_string_disjunction(&mut self, it: &ClassStringDisjunction<'a>) {
+        self.node("ClassStringDisjunction", it.span, &[], |p| {
+            walk::walk_class_string_disjunction(p, it);
+        });
+    }
+
+    fn visit_string_alternative(&mut self, it: &StringAlternative<'a>) {
+        self.node("StringAlternative", it.span, &[], |p| walk::walk_string_alternative(p, it));
+    }
+
+    fn visit_flags(&mut self, it: &Flags) {
+        let flags = [
+            (it.has_indices, 'd'),
+            (it.global, 'g'),
+            (it.ignore_case, 'i'),
+            (it.multiline, 'm'),
+            (it.dot_all, 's'),
+            (it.unicode, 'u'),
+            (it.unicode_sets, 'v'),
+            (it.sticky, 'y'),
+        ]
+        .into_iter()
+        .filter_map(|(set, flag)| set.then_some(flag))
+        .collect::<String>();
+        self.line("Flags", it.span, &[&format_args!("{flags:?}")]);
+    }
+}
+
+/// `ims`, in the order of the flags of a literal.
+fn modifier_flags(flags: ModifierFlags) -> String {
+    [(flags.ignore_case, 'i'), (flags.multiline, 'm'), (flags.dot_all, 's')]
+        .into_iter()
+        .filter_map(|(set, flag)| set.then_some(flag))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+    use oxc_span::Span;
+
+    use super::{print_pattern, print_regexp_literal};
+    use crate::{
+        ast::{EdgeAssertionKind, EscapeCharacterSetKind},
+        AstBuilder,
+    };
+
+    #[test]
+    fn print() {
+        // `(?<a>x).*?`
+        let allocator = Allocator::default();
+        let ast = AstBuilder::new(&allocator);
+        let character = ast.element_character(Span::new(5, 6), u16::from(b'x'));
+        let elements = ast.vec_from_iter([
+            ast.element_capturing_group(
+                Span::new(0, 7),
+                Some("a".into()),
+                ast.vec1(ast.alternative(Span::new(5, 6), ast.vec1(character))),
+            ),
+            ast.element_quantifier(
+                Span::new(7, 10),
+                0.0,
+                f64::INFINITY,
+                false,
+                ast.quantifiable_element_any_character_set(Span::new(7, 8)),
+            ),
+        ]);
+        let pattern =
+            ast.pattern(Span::new(0, 10), ast.vec1(ast.alternative(Span::new(0, 10), elements)));
+        let expected = "\
+Pattern 0..10
+  Alternative 0..10
+    CapturingGroup 0..7 name=a
+      Alternative 5..6
+        Character 5..6 'x'
+    Quantifier 7..10 {0,} lazy
+      AnyCharacterSet 7..8
+";
+        assert_eq!(print_pattern(&pattern), expected);
+    }
+
+    #[test]
+    fn print_regexp_literal_with_flags() {
+        // `/^\\D{2}\n/gu`
+        let allocator = Allocator::default();
+        let ast = AstBuilder::new(&allocator);
+        let elements = ast.vec_from_iter([
+            ast.element_edge_assertion(Span::new(1, 2), EdgeAssertionKind::Start),
+            ast.element_quantifier(
+                Span::new(2, 7),
+                2.0,
+                2.0,
+                true,
+                ast.quantifiable_element_escape_character_set(
+                    Span::new(2, 4),
+                    EscapeCharacterSetKind::Digit,
+                    true,
+                ),
+            ),
+            ast.element_character(Span::new(7, 9), u16::from(b'\n')),
+        ]);
+        let pattern =
+            ast.pattern(Span::new(1, 9), ast.vec1(ast.alternative(Span::new(1, 9), elements)));
+        let flags =
+            ast.flags(Span::new(10, 12), false, true, false, false, false, false, true, false);
+        let literal = ast.regexp_literal(Span::new(0, 12), pattern, flags);
+        let expected = "\
+RegExpLiteral 0..12
+  Pattern 1..9
+    Alternative 1..9
+      EdgeAssertion 1..2 start
+      Quantifier 2..7 {2,2}
+        EscapeCharacterSet 2..4 digit negate
+      Character 7..9 U+000A
+  Flags 10..12 \"gu\"
+";
+        assert_eq!(print_regexp_literal(&literal), expected);
+    }
+}
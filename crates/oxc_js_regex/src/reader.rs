@@ -0,0 +1,250 @@
+//! [`@eslint-community/regexpp`](https://github.com/eslint-community/regexpp/blob/2e8f1af992fb12eae46a446253e8fa3f6cede92a/src/reader.ts)
+//!
+//! Patterns are usually a single line, but a pattern built from a template literal, e.g.
+//! ``new RegExp(String.raw`...`)``, can span several. Line and column tracking lets diagnostics
+//! point at the visual location of a character instead of its offset.
+
+use oxc_span::Span;
+
+use crate::{parser::ParserOptions, span_factory::SpanFactory};
+
+/// Zero-based line and column in a pattern.
+///
+/// Columns are counted in UTF-16 code units, like JavaScript string indices and editor positions.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct LineCol {
+    pub line: u32,
+    pub column: u32,
+}
+
+/// Offsets of the line starts of a pattern.
+///
+/// Lines are separated by the ECMAScript line terminators: `\n`, `\r`, `\r\n`, U+2028 and U+2029.
+#[derive(Debug, Clone)]
+pub struct LineIndex<'a> {
+    source: &'a str,
+    line_starts: Vec<u32>,
+}
+
+impl<'a> LineIndex<'a> {
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn new(source: &'a str) -> Self {
+        let mut line_starts = vec![0];
+        let mut chars = source.char_indices().peekable();
+        while let Some((offset, c)) = chars.next() {
+            if !is_line_terminator(c) {
+                continue;
+            }
+            if c == '\r' && chars.peek().is_some_and(|&(_, next)| next == '\n') {
+                continue;
+            }
+            line_starts.push((offset + c.len_utf8()) as u32);
+        }
+        Self { source, line_starts }
+    }
+
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    /// Line and column of a byte offset in the pattern.
+    ///
+    /// # Panics
+    ///
+    /// If `offset` is out of bounds or not on a character boundary.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn line_col(&self, offset: u32) -> LineCol {
+        let line = self.line_starts.partition_point(|&start| start <= offset) - 1;
+        let line_start = self.line_starts[line] as usize;
+        let column = self.source[line_start..offset as usize].encode_utf16().count();
+        LineCol { line: line as u32, column: column as u32 }
+    }
+}
+
+/// Line and column positions of a [`Span`] in a pattern.
+///
+/// Only for spans created with the default [`ParserOptions`], which are byte offsets into the
+/// pattern.
+pub trait SpanLineCol {
+    /// Start and end position.
+    fn line_col(&self, index: &LineIndex<'_>) -> (LineCol, LineCol);
+}
+
+impl SpanLineCol for Span {
+    fn line_col(&self, index: &LineIndex<'_>) -> (LineCol, LineCol) {
+        (index.line_col(self.start), index.line_col(self.end))
+    }
+}
+
+/// Reads a pattern one code point at a time.
+///
+/// Offsets are byte offsets into the pattern. Spans are converted to the unit selected by
+/// [`ParserOptions`] when they are created.
+#[derive(Debug, Clone)]
+pub struct Reader<'a> {
+    source: &'a str,
+    /// Offset of the next character.
+    offset: usize,
+    span_factory: SpanFactory,
+    /// Only built with [`Reader::with_line_tracking`], since most patterns are a single line.
+    line_index: Option<LineIndex<'a>>,
+}
+
+impl<'a> Reader<'a> {
+    pub fn new(source: &'a str) -> Self {
+        let span_factory = SpanFactory::new(source, &ParserOptions::default());
+        Self { source, offset: 0, span_factory, line_index: None }
+    }
+
+    /// Create spans as configured by `options`.
+    #[must_use]
+    pub fn with_options(mut self, options: &ParserOptions) -> Self {
+        self.span_factory = SpanFactory::new(self.source, options);
+        self
+    }
+
+    /// Track line and column positions, see [`Reader::line_col`].
+    #[must_use]
+    pub fn with_line_tracking(mut self, yes: bool) -> Self {
+        self.line_index = yes.then(|| LineIndex::new(self.source));
+        self
+    }
+
+    pub fn source(&self) -> &'a str {
+        self.source
+    }
+
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn offset(&self) -> u32 {
+        self.offset as u32
+    }
+
+    /// Line and column of the next character. `None` without line tracking.
+    pub fn line_col(&self) -> Option<LineCol> {
+        self.line_index.as_ref().map(|index| index.line_col(self.offset()))
+    }
+
+    /// The line index, with line tracking.
+    pub fn line_index(&self) -> Option<&LineIndex<'a>> {
+        self.line_index.as_ref()
+    }
+
+    /// Span from the byte offset `start` to the current offset.
+    pub fn span_from(&self, start: u32) -> Span {
+        self.span_factory.create(start, self.offset())
+    }
+
+    pub fn peek(&self) -> Option<char> {
+        self.source[self.offset..].chars().next()
+    }
+
+    /// The `n`th character after the next one, `peek_nth(0)` is `peek()`.
+    pub fn peek_nth(&self, n: usize) -> Option<char> {
+        self.source[self.offset..].chars().nth(n)
+    }
+
+    pub fn advance(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.offset += c.len_utf8();
+        Some(c)
+    }
+
+    /// Advances past `c` if it is the next character.
+    pub fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.offset += c.len_utf8();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Advances past `s` if the remaining pattern starts with it.
+    pub fn eat_str(&mut self, s: &str) -> bool {
+        if self.source[self.offset..].starts_with(s) {
+            self.offset += s.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Moves back to an earlier offset, e.g. after a failed lookahead.
+    ///
+    /// # Panics
+    ///
+    /// If `offset` is out of bounds or not on a character boundary.
+    pub fn rewind(&mut self, offset: u32) {
+        assert!(self.source.is_char_boundary(offset as usize));
+        self.offset = offset as usize;
+    }
+}
+
+fn is_line_terminator(c: char) -> bool {
+    matches!(c, '\n' | '\r' | '\u{2028}' | '\u{2029}')
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_span::Span;
+
+    use super::{LineCol, LineIndex, Reader, SpanLineCol};
+    use crate::{parser::ParserOptions, span_factory::SpanEncoding};
+
+    fn line_col(line: u32, column: u32) -> LineCol {
+        LineCol { line, column }
+    }
+
+    #[test]
+    #[allow(clippy::cast_possible_truncation)]
+    fn line_index() {
+        let source = "a\nb\r\nc\rd\u{2028}\u{1F600}e";
+        let index = LineIndex::new(source);
+        assert_eq!(index.line_count(), 5);
+        assert_eq!(index.line_col(0), line_col(0, 0));
+        assert_eq!(index.line_col(1), line_col(0, 1));
+        assert_eq!(index.line_col(2), line_col(1, 0));
+        // `\r\n` is a single line break.
+        assert_eq!(index.line_col(4), line_col(1, 2));
+        assert_eq!(index.line_col(5), line_col(2, 0));
+        assert_eq!(index.line_col(7), line_col(3, 0));
+        // Columns are counted in UTF-16 code units.
+        let e = source.find('e').unwrap() as u32;
+        assert_eq!(index.line_col(e), line_col(4, 2));
+
+        let span = Span::new(2, e);
+        assert_eq!(span.line_col(&index), (line_col(1, 0), line_col(4, 2)));
+    }
+
+    #[test]
+    fn reader() {
+        let mut reader = Reader::new("(?:a)\nb");
+        assert!(reader.line_col().is_none());
+        assert!(reader.eat_str("(?:"));
+        assert!(!reader.eat('b'));
+        assert_eq!(reader.peek_nth(1), Some(')'));
+        assert_eq!(reader.advance(), Some('a'));
+        assert_eq!(reader.span_from(3), Span::new(3, 4));
+
+        let mut reader = Reader::new("(?:a)\nb").with_line_tracking(true);
+        while reader.peek() != Some('b') {
+            reader.advance();
+        }
+        assert_eq!(reader.line_col(), Some(line_col(1, 0)));
+        reader.rewind(3);
+        assert_eq!(reader.line_col(), Some(line_col(0, 3)));
+        assert_eq!(reader.advance(), Some('a'));
+    }
+
+    #[test]
+    fn reader_utf16_spans() {
+        let options = ParserOptions { span_offset: 1, span_encoding: SpanEncoding::Utf16 };
+        let mut reader = Reader::new("\u{1F600}a").with_options(&options);
+        let start = reader.offset();
+        reader.advance();
+        assert_eq!(reader.span_from(start), Span::new(1, 3));
+        let start = reader.offset();
+        assert_eq!(reader.advance(), Some('a'));
+        assert_eq!(reader.span_from(start), Span::new(3, 4));
+    }
+}
@@ -0,0 +1,146 @@
+//! Renaming of named capturing groups, for "rename capture group" refactorings and lint fixes.
+
+use std::{collections::HashMap, hash::BuildHasher};
+
+use oxc_span::{CompactStr, Span};
+
+use crate::{
+    ast::{Backreference, BackreferenceRef, CapturingGroup, Pattern},
+    visitor::{walk, Visit},
+};
+
+/// Replacement of the source text at `span`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenameEdit {
+    pub span: Span,
+    pub replacement: CompactStr,
+}
+
+/// Edits which rename the named capturing groups of `pattern` with `renames`, a map from old to
+/// new group names.
+///
+/// Only the names between `<` and `>` of group specifiers, `(?<name>`, and backreferences,
+/// `\k<name>`, are replaced, so the edits do not overlap any other edit of the pattern. They are
+/// sorted by position. Names which are mapped to themselves are not edited. All groups sharing a
+/// name (duplicate named groups) are renamed together.
+///
+/// `source_text` is the text the spans of `pattern` are byte offsets into, which is needed to
+/// find the end of names written with escapes, `(?<\u0061>`.
+/// The new names are not validated: they must be valid group names and must not clash with the
+/// names of other groups in the same alternative.
+pub fn rename_capturing_groups<S: BuildHasher>(
+    pattern: &Pattern<'_>,
+    source_text: &str,
+    renames: &HashMap<&str, &str, S>,
+) -> Vec<RenameEdit> {
+    let mut visitor = GroupRenamer { source_text, renames, edits: vec![] };
+    visitor.visit_pattern(pattern);
+    visitor.edits.sort_by_key(|edit| edit.span.start);
+    visitor.edits
+}
+
+struct GroupRenamer<'s, S> {
+    source_text: &'s str,
+    renames: &'s HashMap<&'s str, &'s str, S>,
+    edits: Vec<RenameEdit>,
+}
+
+impl<S: BuildHasher> GroupRenamer<'_, S> {
+    fn rename(&mut self, name: &str, span: Option<Span>) {
+        let (Some(&new_name), Some(span)) = (self.renames.get(name), span) else { return };
+        if new_name != name {
+            self.edits.push(RenameEdit { span, replacement: new_name.into() });
+        }
+    }
+
+    /// Span of `name` in `(?<name>...)`.
+    #[allow(clippy::cast_possible_truncation)]
+    fn group_name_span(&self, span: Span) -> Option<Span> {
+        let start = span.start + 3;
+        let text = self.source_text.get(span.start as usize..span.end as usize)?;
+        let end = span.start + text.strip_prefix("(?<")?.find('>')? as u32 + 3;
+        Some(Span::new(start, end))
+    }
+
+    /// Span of `name` in `\k<name>`.
+    fn backreference_name_span(&self, span: Span) -> Option<Span> {
+        let text = self.source_text.get(span.start as usize..span.end as usize)?;
+        (text.starts_with("\\k<") && text.ends_with('>'))
+            .then(|| Span::new(span.start + 3, span.end - 1))
+    }
+}
+
+impl<'a, S: BuildHasher> Visit<'a> for GroupRenamer<'_, S> {
+    fn visit_capturing_group(&mut self, it: &CapturingGroup<'a>) {
+        if let Some(name) = &it.name {
+            self.rename(name, self.group_name_span(it.span));
+        }
+        walk::walk_capturing_group(self, it);
+    }
+
+    fn visit_backreference(&mut self, it: &Backreference<'a>) {
+        if let BackreferenceRef::CompactStr(name) = &it.reference {
+            self.rename(name, self.backreference_name_span(it.span));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use oxc_allocator::Allocator;
+    use oxc_span::Span;
+
+    use super::rename_capturing_groups;
+    use crate::{
+        ast::{BackreferenceRef, QuantifiableElement},
+        AstBuilder,
+    };
+
+    #[test]
+    fn rename() {
+        let source_text = r"(?<a>x)(?<\u0062>y)\k<a>\k<b>";
+        let allocator = Allocator::default();
+        let ast = AstBuilder::new(&allocator);
+        let character = |start: u32, value: u8| {
+            let span = Span::new(start, start + 1);
+            ast.alternative(span, ast.vec1(ast.element_character(span, u16::from(value))))
+        };
+        let backreference = |start: u32, name: &str| {
+            let span = Span::new(start, start + 5);
+            let resolved = ast.capturing_group(Span::default(), Some(name.into()), ast.vec());
+            let reference = BackreferenceRef::CompactStr(name.into());
+            ast.element_quantifiable_element(QuantifiableElement::Backreference(
+                ast.alloc(ast.backreference(span, reference, resolved)),
+            ))
+        };
+        let elements = ast.vec_from_iter([
+            ast.element_capturing_group(
+                Span::new(0, 7),
+                Some("a".into()),
+                ast.vec1(character(5, b'x')),
+            ),
+            ast.element_capturing_group(
+                Span::new(7, 19),
+                Some("b".into()),
+                ast.vec1(character(17, b'y')),
+            ),
+            backreference(19, "a"),
+            backreference(24, "b"),
+        ]);
+        let pattern =
+            ast.pattern(Span::new(0, 29), ast.vec1(ast.alternative(Span::new(0, 29), elements)));
+
+        let renames = HashMap::from([("a", "first"), ("b", "second"), ("c", "third")]);
+        let edits = rename_capturing_groups(&pattern, source_text, &renames);
+        let mut text = source_text.to_string();
+        for edit in edits.iter().rev() {
+            text.replace_range(edit.span.start as usize..edit.span.end as usize, &edit.replacement);
+        }
+        assert_eq!(text, r"(?<first>x)(?<second>y)\k<first>\k<second>");
+
+        let renames = HashMap::from([("a", "a")]);
+        assert!(rename_capturing_groups(&pattern, source_text, &renames).is_empty());
+    }
+}
@@ -0,0 +1,145 @@
+//! [`GetSpan`] for every regular expression AST node.
+
+#![allow(clippy::match_same_arms)]
+
+use oxc_span::{GetSpan, Span};
+
+#[allow(clippy::wildcard_imports)]
+use crate::ast::*;
+
+macro_rules! impl_get_span_for_structs {
+    ($($ty:ident),+ $(,)?) => {
+        $(
+            impl GetSpan for $ty<'_> {
+                #[inline]
+                fn span(&self) -> Span {
+                    self.span
+                }
+            }
+        )+
+    };
+    (@no_lifetime $($ty:ident),+ $(,)?) => {
+        $(
+            impl GetSpan for $ty {
+                #[inline]
+                fn span(&self) -> Span {
+                    self.span
+                }
+            }
+        )+
+    };
+}
+
+macro_rules! impl_get_span_for_enums {
+    ($($ty:ident { $($variant:ident),+ $(,)? }),+ $(,)?) => {
+        $(
+            impl GetSpan for $ty<'_> {
+                fn span(&self) -> Span {
+                    match self {
+                        $(Self::$variant(it) => it.span(),)+
+                    }
+                }
+            }
+        )+
+    };
+}
+
+impl_get_span_for_structs!(
+    RegExpLiteral,
+    Pattern,
+    Alternative,
+    Group,
+    CapturingGroup,
+    LookaheadAssertion,
+    LookbehindAssertion,
+    Quantifier,
+    ClassRangesCharacterClass,
+    UnicodeSetsCharacterClass,
+    ExpressionCharacterClass,
+    ClassIntersection,
+    ClassSubtraction,
+    ClassStringDisjunction,
+    StringAlternative,
+    Backreference,
+);
+
+impl_get_span_for_structs!(
+    @no_lifetime
+    CharacterClassRange,
+    EdgeAssertion,
+    WordBoundaryAssertion,
+    AnyCharacterSet,
+    EscapeCharacterSet,
+    CharacterUnicodePropertyCharacterSet,
+    StringsUnicodePropertyCharacterSet,
+    Character,
+    Flags,
+);
+
+impl_get_span_for_enums!(
+    Node { Branch, Leaf },
+    Branch {
+        Alternative,
+        CapturingGroup,
+        CharacterClass,
+        CharacterClassRange,
+        ClassIntersection,
+        ClassStringDisjunction,
+        ClassSubtraction,
+        ExpressionCharacterClass,
+        Group,
+        LookaroundAssertion,
+        Pattern,
+        Quantifier,
+        RegExpLiteral,
+        StringAlternative,
+    },
+    Leaf { Backreference, BoundaryAssertion, Character, CharacterSet, Flags },
+    Element { Assertion, QuantifiableElement, Quantifier },
+    QuantifiableElement {
+        Backreference,
+        CapturingGroup,
+        Character,
+        CharacterClass,
+        CharacterSet,
+        ExpressionCharacterClass,
+        Group,
+        LookaheadAssertion,
+    },
+    CharacterClassElement { ClassRangesCharacterClassElement, UnicodeSetsCharacterClassElement },
+    ClassRangesCharacterClassElement {
+        Character,
+        CharacterClassRange,
+        CharacterUnicodePropertyCharacterSet,
+        EscapeCharacterSet,
+    },
+    UnicodeSetsCharacterClassElement {
+        Character,
+        CharacterClassRange,
+        ClassStringDisjunction,
+        EscapeCharacterSet,
+        ExpressionCharacterClass,
+        UnicodePropertyCharacterSet,
+        UnicodeSetsCharacterClass,
+    },
+    LookaroundAssertion { LookaheadAssertion, LookbehindAssertion },
+    CharacterClass { ClassRangesCharacterClass, UnicodeSetsCharacterClass },
+    Assertion { BoundaryAssertion, LookaroundAssertion },
+    BoundaryAssertion { EdgeAssertion, WordBoundaryAssertion },
+    CharacterSet { AnyCharacterSet, EscapeCharacterSet, UnicodePropertyCharacterSet },
+    UnicodePropertyCharacterSet {
+        CharacterUnicodePropertyCharacterSet,
+        StringsUnicodePropertyCharacterSet,
+    },
+    ExpressionCharacterClassExpr { ClassIntersection, ClassSubtraction },
+    ClassSetOperand {
+        Character,
+        ClassStringDisjunction,
+        EscapeCharacterSet,
+        ExpressionCharacterClass,
+        UnicodePropertyCharacterSet,
+        UnicodeSetsCharacterClass,
+    },
+    ClassIntersectionLeft { ClassIntersection, ClassSetOperand },
+    ClassSubtractionLeft { ClassSetOperand, ClassSubtraction },
+);
@@ -0,0 +1,89 @@
+//! Creates AST spans from byte offsets in a pattern.
+//!
+//! The [`Reader`](crate::reader::Reader) always works with byte offsets. JavaScript tools, e.g.
+//! ESLint `range`s, index strings by UTF-16 code units instead, so the unit of span offsets is
+//! selected with [`ParserOptions`] and every conversion goes through [`SpanFactory`].
+
+use oxc_span::Span;
+
+use crate::parser::ParserOptions;
+
+/// Unit of span offsets.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum SpanEncoding {
+    /// Byte offsets into the UTF-8 pattern, for indexing Rust strings.
+    #[default]
+    Utf8,
+    /// UTF-16 code unit offsets, for indexing JavaScript strings.
+    Utf16,
+}
+
+#[derive(Debug, Clone)]
+pub struct SpanFactory {
+    span_offset: u32,
+    /// Byte offset after each character which is shorter in UTF-16 than in UTF-8, and the
+    /// number of bytes saved up to and including it. Empty for UTF-8 spans and ASCII patterns.
+    utf16_adjustments: Vec<(u32, u32)>,
+}
+
+impl SpanFactory {
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn new(source: &str, options: &ParserOptions) -> Self {
+        let mut utf16_adjustments = vec![];
+        if options.span_encoding == SpanEncoding::Utf16 && !source.is_ascii() {
+            let mut saved = 0;
+            for (offset, c) in source.char_indices() {
+                let diff = c.len_utf8() - c.len_utf16();
+                if diff > 0 {
+                    saved += diff as u32;
+                    utf16_adjustments.push(((offset + c.len_utf8()) as u32, saved));
+                }
+            }
+        }
+        Self { span_offset: options.span_offset, utf16_adjustments }
+    }
+
+    /// Converts a byte offset in the pattern to a span offset.
+    pub fn offset(&self, byte_offset: u32) -> u32 {
+        let index = self.utf16_adjustments.partition_point(|&(end, _)| end <= byte_offset);
+        let saved = index.checked_sub(1).map_or(0, |index| self.utf16_adjustments[index].1);
+        self.span_offset + byte_offset - saved
+    }
+
+    /// Span between two byte offsets in the pattern.
+    pub fn create(&self, start: u32, end: u32) -> Span {
+        Span::new(self.offset(start), self.offset(end))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_span::Span;
+
+    use super::{SpanEncoding, SpanFactory};
+    use crate::parser::ParserOptions;
+
+    #[test]
+    #[allow(clippy::cast_possible_truncation)]
+    fn span_factory() {
+        let source = "a\u{e9}b\u{1F600}c";
+        let c = source.find('c').unwrap() as u32;
+
+        let utf8 = SpanFactory::new(source, &ParserOptions::default());
+        assert_eq!(utf8.create(0, c), Span::new(0, c));
+
+        let options =
+            ParserOptions { span_encoding: SpanEncoding::Utf16, ..ParserOptions::default() };
+        let utf16 = SpanFactory::new(source, &options);
+        // `é` is 2 bytes and 1 code unit, the emoji is 4 bytes and 2 code units.
+        assert_eq!(utf16.offset(1), 1);
+        assert_eq!(utf16.offset(3), 2);
+        assert_eq!(utf16.offset(4), 3);
+        assert_eq!(utf16.create(4, c), Span::new(3, 5));
+        assert_eq!(utf16.offset(c + 1), 6);
+
+        let options = ParserOptions { span_offset: 10, span_encoding: SpanEncoding::Utf16 };
+        let offset = SpanFactory::new(source, &options);
+        assert_eq!(offset.create(0, c), Span::new(10, 15));
+    }
+}
@@ -1 +1,262 @@
+//! Early errors which depend on more than one node.
+//!
+//! In unicode sets mode (`v` flag) a class may match strings as well as characters, e.g.
+//! `[\q{abc}]` matches `abc`. A negated class matches a single character which none of its
+//! elements match, so it must not contain strings: `/[^\q{abc}]/v` is a syntax error, while
+//! `/[^\q{a|b}]/v` is not.
+//!
+//! See <https://tc39.es/ecma262/#sec-patterns-static-semantics-early-errors>
 
+use std::fmt;
+
+use oxc_span::Span;
+
+use crate::{
+    ast::{
+        ClassIntersection, ClassIntersectionLeft, ClassSetOperand, ClassStringDisjunction,
+        ClassSubtraction, ClassSubtractionLeft, ExpressionCharacterClass,
+        ExpressionCharacterClassExpr, Pattern, RegExpLiteral, StringAlternative,
+        UnicodePropertyCharacterSet, UnicodeSetsCharacterClass, UnicodeSetsCharacterClassElement,
+    },
+    visitor::{walk, Visit},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegExpSyntaxErrorKind {
+    /// `\q{...}` without the `v` flag.
+    ClassStringDisjunctionWithoutUnicodeSets,
+    /// A negated class which may contain strings, e.g. `[^\q{ab}]` or `[^\p{RGI_Emoji}]`.
+    NegatedClassMayContainStrings,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RegExpSyntaxError {
+    pub kind: RegExpSyntaxErrorKind,
+    pub span: Span,
+}
+
+impl fmt::Display for RegExpSyntaxError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            RegExpSyntaxErrorKind::ClassStringDisjunctionWithoutUnicodeSets => {
+                write!(f, "`\\q{{...}}` is only valid with the `v` flag")
+            }
+            RegExpSyntaxErrorKind::NegatedClassMayContainStrings => {
+                write!(f, "Negated character class may contain strings")
+            }
+        }
+    }
+}
+
+/// Early errors of a regular expression literal, including its flags.
+pub fn validate_reg_exp_literal(literal: &RegExpLiteral<'_>) -> Vec<RegExpSyntaxError> {
+    let mut validator = Validator { unicode_sets: literal.flags.unicode_sets, errors: vec![] };
+    validator.visit_pattern(&literal.pattern);
+    validator.errors
+}
+
+/// Early errors of a pattern, e.g. the first argument of `new RegExp()`, with or without the `v`
+/// flag.
+pub fn validate_pattern(pattern: &Pattern<'_>, unicode_sets: bool) -> Vec<RegExpSyntaxError> {
+    let mut validator = Validator { unicode_sets, errors: vec![] };
+    validator.visit_pattern(pattern);
+    validator.errors
+}
+
+/// Whether a string alternative is not exactly one code point, e.g. `ab` or the empty
+/// alternative in `\q{a|}`.
+pub fn is_string(alternative: &StringAlternative<'_>) -> bool {
+    match alternative.elements.as_slice() {
+        [_] => false,
+        [high, low] => {
+            !((0xD800..0xDC00).contains(&high.value) && (0xDC00..0xE000).contains(&low.value))
+        }
+        _ => true,
+    }
+}
+
+/// `MayContainStrings` of a class or one of its operands.
+pub trait MayContainStrings {
+    fn may_contain_strings(&self) -> bool;
+}
+
+impl<'a> MayContainStrings for ClassStringDisjunction<'a> {
+    fn may_contain_strings(&self) -> bool {
+        self.alternatives.iter().any(is_string)
+    }
+}
+
+impl<'a> MayContainStrings for UnicodePropertyCharacterSet<'a> {
+    fn may_contain_strings(&self) -> bool {
+        matches!(self, Self::StringsUnicodePropertyCharacterSet(_))
+    }
+}
+
+impl<'a> MayContainStrings for UnicodeSetsCharacterClass<'a> {
+    fn may_contain_strings(&self) -> bool {
+        // A negated class is itself an error if it contains strings, and is otherwise a set of
+        // characters.
+        !self.negate && self.elements.iter().any(MayContainStrings::may_contain_strings)
+    }
+}
+
+impl<'a> MayContainStrings for UnicodeSetsCharacterClassElement<'a> {
+    fn may_contain_strings(&self) -> bool {
+        match self {
+            Self::ClassStringDisjunction(it) => it.may_contain_strings(),
+            Self::ExpressionCharacterClass(it) => it.may_contain_strings(),
+            Self::UnicodePropertyCharacterSet(it) => it.may_contain_strings(),
+            Self::UnicodeSetsCharacterClass(it) => it.may_contain_strings(),
+            Self::Character(_) | Self::CharacterClassRange(_) | Self::EscapeCharacterSet(_) => {
+                false
+            }
+        }
+    }
+}
+
+impl<'a> MayContainStrings for ExpressionCharacterClass<'a> {
+    fn may_contain_strings(&self) -> bool {
+        !self.negate
+            && match &self.expression {
+                ExpressionCharacterClassExpr::ClassIntersection(it) => it.may_contain_strings(),
+                ExpressionCharacterClassExpr::ClassSubtraction(it) => it.may_contain_strings(),
+            }
+    }
+}
+
+impl<'a> MayContainStrings for ClassSetOperand<'a> {
+    fn may_contain_strings(&self) -> bool {
+        match self {
+            Self::ClassStringDisjunction(it) => it.may_contain_strings(),
+            Self::ExpressionCharacterClass(it) => it.may_contain_strings(),
+            Self::UnicodePropertyCharacterSet(it) => it.may_contain_strings(),
+            Self::UnicodeSetsCharacterClass(it) => it.may_contain_strings(),
+            Self::Character(_) | Self::EscapeCharacterSet(_) => false,
+        }
+    }
+}
+
+/// An intersection contains strings only if both sides do.
+impl<'a> MayContainStrings for ClassIntersection<'a> {
+    fn may_contain_strings(&self) -> bool {
+        let left = match &self.left {
+            ClassIntersectionLeft::ClassIntersection(it) => it.may_contain_strings(),
+            ClassIntersectionLeft::ClassSetOperand(it) => it.may_contain_strings(),
+        };
+        left && self.right.may_contain_strings()
+    }
+}
+
+/// A subtraction contains strings only if its left side does.
+impl<'a> MayContainStrings for ClassSubtraction<'a> {
+    fn may_contain_strings(&self) -> bool {
+        match &self.left {
+            ClassSubtractionLeft::ClassSetOperand(it) => it.may_contain_strings(),
+            ClassSubtractionLeft::ClassSubtraction(it) => it.may_contain_strings(),
+        }
+    }
+}
+
+struct Validator {
+    unicode_sets: bool,
+    errors: Vec<RegExpSyntaxError>,
+}
+
+impl Validator {
+    fn error(&mut self, kind: RegExpSyntaxErrorKind, span: Span) {
+        self.errors.push(RegExpSyntaxError { kind, span });
+    }
+}
+
+impl<'a> Visit<'a> for Validator {
+    fn visit_unicode_sets_character_class(&mut self, it: &UnicodeSetsCharacterClass<'a>) {
+        if it.negate && it.elements.iter().any(MayContainStrings::may_contain_strings) {
+            self.error(RegExpSyntaxErrorKind::NegatedClassMayContainStrings, it.span);
+        }
+        walk::walk_unicode_sets_character_class(self, it);
+    }
+
+    fn visit_expression_character_class(&mut self, it: &ExpressionCharacterClass<'a>) {
+        let may_contain_strings = match &it.expression {
+            ExpressionCharacterClassExpr::ClassIntersection(it) => it.may_contain_strings(),
+            ExpressionCharacterClassExpr::ClassSubtraction(it) => it.may_contain_strings(),
+        };
+        if it.negate && may_contain_strings {
+            self.error(RegExpSyntaxErrorKind::NegatedClassMayContainStrings, it.span);
+        }
+        walk::walk_expression_character_class(self, it);
+    }
+
+    fn visit_class_string_disjunction(&mut self, it: &ClassStringDisjunction<'a>) {
+        if !self.unicode_sets {
+            self.error(RegExpSyntaxErrorKind::ClassStringDisjunctionWithoutUnicodeSets, it.span);
+        }
+        walk::walk_class_string_disjunction(self, it);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+    use oxc_span::Span;
+
+    use super::{validate_pattern, RegExpSyntaxErrorKind};
+    use crate::{
+        ast::{Pattern, UnicodeSetsCharacterClassElement},
+        AstBuilder,
+    };
+
+    /// `[\q{...}]` or `[^\q{...}]` where each alternative is a string of `a`s of the given length.
+    fn class_with_strings<'a>(ast: AstBuilder<'a>, negate: bool, lengths: &[u32]) -> Pattern<'a> {
+        let alternatives = ast.vec_from_iter(lengths.iter().map(|&length| {
+            let characters = ast.vec_from_iter(
+                (0..length).map(|i| ast.character(Span::new(i, i + 1), u16::from(b'a'))),
+            );
+            ast.string_alternative(Span::new(0, length), characters)
+        }));
+        let disjunction = ast.class_string_disjunction(Span::new(1, 9), alternatives);
+        let class = ast.character_class_unicode_sets(
+            Span::new(0, 10),
+            negate,
+            ast.vec1(UnicodeSetsCharacterClassElement::ClassStringDisjunction(
+                ast.alloc(disjunction),
+            )),
+        );
+        let element =
+            ast.element_quantifiable_element(ast.quantifiable_element_character_class(class));
+        ast.pattern(
+            Span::new(0, 10),
+            ast.vec1(ast.alternative(Span::new(0, 10), ast.vec1(element))),
+        )
+    }
+
+    #[test]
+    fn class_string_disjunction_requires_unicode_sets() {
+        let allocator = Allocator::default();
+        let ast = AstBuilder::new(&allocator);
+        let pattern = class_with_strings(ast, false, &[2]);
+        assert!(validate_pattern(&pattern, true).is_empty());
+        let errors = validate_pattern(&pattern, false);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].kind, RegExpSyntaxErrorKind::ClassStringDisjunctionWithoutUnicodeSets);
+        assert_eq!(errors[0].span, Span::new(1, 9));
+        assert_eq!(errors[0].to_string(), "`\\q{...}` is only valid with the `v` flag");
+    }
+
+    #[test]
+    fn negated_class_may_contain_strings() {
+        let allocator = Allocator::default();
+        let ast = AstBuilder::new(&allocator);
+        // [^\q{a|b}]
+        assert!(validate_pattern(&class_with_strings(ast, true, &[1, 1]), true).is_empty());
+        // [\q{aa}]
+        assert!(validate_pattern(&class_with_strings(ast, false, &[2]), true).is_empty());
+        // [^\q{a|aa}] and [^\q{a|}]
+        for lengths in [&[1, 2], &[1, 0]] {
+            let errors = validate_pattern(&class_with_strings(ast, true, lengths), true);
+            assert_eq!(errors.len(), 1);
+            assert_eq!(errors[0].kind, RegExpSyntaxErrorKind::NegatedClassMayContainStrings);
+            assert_eq!(errors[0].span, Span::new(0, 10));
+        }
+    }
+}
@@ -0,0 +1,274 @@
+//! Warnings for patterns which are valid but almost certainly mistakes.
+//!
+//! Warnings do not affect parsing. They are collected in a separate list alongside the AST so
+//! that consumers such as the linter can decide how to report them.
+
+use std::fmt;
+
+use oxc_span::Span;
+
+use crate::{
+    ast::{
+        Alternative, BoundaryAssertion, CapturingGroup, CharacterSet, Element, Group,
+        LookaheadAssertion, LookbehindAssertion, Pattern, QuantifiableElement, Quantifier,
+        RegExpLiteral,
+    },
+    visitor::{walk, Visit},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegExpWarningKind {
+    /// An empty alternative in a disjunction, e.g. `a||b`.
+    /// Matches the empty string, which is usually a typo.
+    EmptyAlternative,
+    /// An unbounded quantifier directly inside a repeated group, e.g. `(a+)+`.
+    /// Backtracking tries every way of splitting the input between the two quantifiers,
+    /// which takes exponential time when the match fails.
+    NestedQuantifier,
+    /// A flag which does not change the behavior of the pattern,
+    /// e.g. `m` without `^` or `$`, or `s` without `.`.
+    UselessFlag(char),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RegExpWarning {
+    pub kind: RegExpWarningKind,
+    pub span: Span,
+}
+
+impl fmt::Display for RegExpWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            RegExpWarningKind::EmptyAlternative => {
+                write!(f, "Empty alternative matches the empty string")
+            }
+            RegExpWarningKind::NestedQuantifier => {
+                write!(f, "Nested quantifiers can cause exponential backtracking")
+            }
+            RegExpWarningKind::UselessFlag('m') => {
+                write!(f, "The `m` flag has no effect without `^` or `$`")
+            }
+            RegExpWarningKind::UselessFlag('s') => {
+                write!(f, "The `s` flag has no effect without `.`")
+            }
+            RegExpWarningKind::UselessFlag(flag) => write!(f, "The `{flag}` flag has no effect"),
+        }
+    }
+}
+
+/// Warnings for a regular expression literal, including its flags.
+pub fn check_reg_exp_literal(literal: &RegExpLiteral<'_>) -> Vec<RegExpWarning> {
+    let mut checker = Checker::default();
+    checker.visit_pattern(&literal.pattern);
+    if literal.flags.multiline && !checker.has_edge_assertion {
+        checker.warn(RegExpWarningKind::UselessFlag('m'), literal.flags.span);
+    }
+    if literal.flags.dot_all && !checker.has_any_character {
+        checker.warn(RegExpWarningKind::UselessFlag('s'), literal.flags.span);
+    }
+    checker.warnings
+}
+
+/// Warnings for a pattern, without flags, e.g. the first argument of `new RegExp()`.
+pub fn check_pattern(pattern: &Pattern<'_>) -> Vec<RegExpWarning> {
+    let mut checker = Checker::default();
+    checker.visit_pattern(pattern);
+    checker.warnings
+}
+
+#[derive(Default)]
+struct Checker {
+    warnings: Vec<RegExpWarning>,
+    has_edge_assertion: bool,
+    has_any_character: bool,
+}
+
+impl Checker {
+    fn warn(&mut self, kind: RegExpWarningKind, span: Span) {
+        self.warnings.push(RegExpWarning { kind, span });
+    }
+
+    fn check_alternatives(&mut self, alternatives: &[Alternative<'_>]) {
+        if alternatives.len() < 2 {
+            return;
+        }
+        for alternative in alternatives.iter().filter(|alternative| alternative.elements.is_empty())
+        {
+            self.warn(RegExpWarningKind::EmptyAlternative, alternative.span);
+        }
+    }
+
+    fn check_nested_quantifier(&mut self, quantifier: &Quantifier<'_>) {
+        if quantifier.max <= 1.0 {
+            return;
+        }
+        let alternatives = match &quantifier.element {
+            QuantifiableElement::Group(group) => &group.alternatives,
+            QuantifiableElement::CapturingGroup(group) => &group.alternatives,
+            _ => return,
+        };
+        let is_nested = alternatives.iter().any(|alternative| {
+            matches!(
+                alternative.elements.as_slice(),
+                [Element::Quantifier(inner)] if inner.max.is_infinite()
+            )
+        });
+        if is_nested {
+            self.warn(RegExpWarningKind::NestedQuantifier, quantifier.span);
+        }
+    }
+}
+
+impl<'a> Visit<'a> for Checker {
+    fn visit_pattern(&mut self, it: &Pattern<'a>) {
+        self.check_alternatives(&it.alternatives);
+        walk::walk_pattern(self, it);
+    }
+
+    fn visit_group(&mut self, it: &Group<'a>) {
+        self.check_alternatives(&it.alternatives);
+        walk::walk_group(self, it);
+    }
+
+    fn visit_capturing_group(&mut self, it: &CapturingGroup<'a>) {
+        self.check_alternatives(&it.alternatives);
+        walk::walk_capturing_group(self, it);
+    }
+
+    fn visit_lookahead_assertion(&mut self, it: &LookaheadAssertion<'a>) {
+        self.check_alternatives(&it.alternatives);
+        walk::walk_lookahead_assertion(self, it);
+    }
+
+    fn visit_lookbehind_assertion(&mut self, it: &LookbehindAssertion<'a>) {
+        self.check_alternatives(&it.alternatives);
+        walk::walk_lookbehind_assertion(self, it);
+    }
+
+    fn visit_quantifier(&mut self, it: &Quantifier<'a>) {
+        self.check_nested_quantifier(it);
+        walk::walk_quantifier(self, it);
+    }
+
+    fn visit_boundary_assertion(&mut self, it: &BoundaryAssertion<'a>) {
+        if matches!(it, BoundaryAssertion::EdgeAssertion(_)) {
+            self.has_edge_assertion = true;
+        }
+    }
+
+    fn visit_character_set(&mut self, it: &CharacterSet<'a>) {
+        if matches!(it, CharacterSet::AnyCharacterSet(_)) {
+            self.has_any_character = true;
+        }
+        walk::walk_character_set(self, it);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+    use oxc_span::Span;
+
+    use super::{check_pattern, check_reg_exp_literal, RegExpWarningKind};
+    use crate::{
+        ast::{Alternative, EdgeAssertionKind},
+        AstBuilder,
+    };
+
+    fn character<'a>(ast: AstBuilder<'a>, start: u32, value: char) -> Alternative<'a> {
+        let span = Span::new(start, start + 1);
+        ast.alternative(span, ast.vec1(ast.element_character(span, value as u16)))
+    }
+
+    #[test]
+    fn empty_alternative() {
+        // a||b
+        let allocator = Allocator::default();
+        let ast = AstBuilder::new(&allocator);
+        let pattern = ast.pattern(
+            Span::new(0, 4),
+            ast.vec_from_iter([
+                character(ast, 0, 'a'),
+                ast.alternative(Span::new(2, 2), ast.vec()),
+                character(ast, 3, 'b'),
+            ]),
+        );
+        let warnings = check_pattern(&pattern);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].kind, RegExpWarningKind::EmptyAlternative);
+        assert_eq!(warnings[0].span, Span::new(2, 2));
+
+        // An empty pattern has a single empty alternative.
+        let pattern =
+            ast.pattern(Span::new(0, 0), ast.vec1(ast.alternative(Span::new(0, 0), ast.vec())));
+        assert!(check_pattern(&pattern).is_empty());
+    }
+
+    #[test]
+    fn nested_quantifier() {
+        let allocator = Allocator::default();
+        let ast = AstBuilder::new(&allocator);
+        let pattern = |inner_max: f64, outer_max: f64| {
+            // (?:a+)+
+            let inner = ast.element_quantifier(
+                Span::new(3, 5),
+                1.0,
+                inner_max,
+                true,
+                ast.quantifiable_element_character(Span::new(3, 4), u16::from(b'a')),
+            );
+            let group = ast.quantifiable_element_group(
+                Span::new(0, 6),
+                ast.vec1(ast.alternative(Span::new(3, 5), ast.vec1(inner))),
+            );
+            let outer = ast.element_quantifier(Span::new(0, 7), 1.0, outer_max, true, group);
+            ast.pattern(
+                Span::new(0, 7),
+                ast.vec1(ast.alternative(Span::new(0, 7), ast.vec1(outer))),
+            )
+        };
+
+        let warnings = check_pattern(&pattern(f64::INFINITY, f64::INFINITY));
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].kind, RegExpWarningKind::NestedQuantifier);
+        assert_eq!(warnings[0].span, Span::new(0, 7));
+        // (?:a{1,2})+
+        assert!(check_pattern(&pattern(2.0, f64::INFINITY)).is_empty());
+        // (?:a+)?
+        assert!(check_pattern(&pattern(f64::INFINITY, 1.0)).is_empty());
+    }
+
+    #[test]
+    fn useless_flags() {
+        let allocator = Allocator::default();
+        let ast = AstBuilder::new(&allocator);
+        let literal = |elements| {
+            let pattern =
+                ast.pattern(Span::new(1, 3), ast.vec1(ast.alternative(Span::new(1, 3), elements)));
+            let flags =
+                ast.flags(Span::new(4, 6), true, false, false, false, true, false, false, false);
+            ast.regexp_literal(Span::new(0, 6), pattern, flags)
+        };
+
+        // /ab/ms
+        let warnings = check_reg_exp_literal(&literal(ast.vec_from_iter([
+            ast.element_character(Span::new(1, 2), u16::from(b'a')),
+            ast.element_character(Span::new(2, 3), u16::from(b'b')),
+        ])));
+        let kinds = warnings.iter().map(|warning| warning.kind).collect::<Vec<_>>();
+        assert_eq!(
+            kinds,
+            [RegExpWarningKind::UselessFlag('m'), RegExpWarningKind::UselessFlag('s')]
+        );
+        assert_eq!(warnings[0].to_string(), "The `m` flag has no effect without `^` or `$`");
+
+        // /^./ms
+        let warnings = check_reg_exp_literal(&literal(ast.vec_from_iter([
+            ast.element_edge_assertion(Span::new(1, 2), EdgeAssertionKind::Start),
+            ast.element_quantifiable_element(
+                ast.quantifiable_element_any_character_set(Span::new(2, 3)),
+            ),
+        ])));
+        assert!(warnings.is_empty());
+    }
+}
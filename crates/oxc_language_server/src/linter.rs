@@ -314,12 +314,12 @@ impl IsolatedLintHandler {
                         range: Range {
                             start: offset_to_position(
                                 f.span.start as usize + start,
-                                javascript_source_text,
+                                &original_source_text,
                             )
                             .unwrap_or_default(),
                             end: offset_to_position(
                                 f.span.end as usize + start,
-                                javascript_source_text,
+                                &original_source_text,
                             )
                             .unwrap_or_default(),
                         },
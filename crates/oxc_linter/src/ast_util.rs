@@ -3,7 +3,10 @@ use std::hash::{Hash, Hasher};
 use oxc_ast::AstKind;
 use oxc_semantic::{AstNode, SymbolId};
 use oxc_span::{GetSpan, Span};
-use oxc_syntax::operator::{AssignmentOperator, BinaryOperator, LogicalOperator, UnaryOperator};
+use oxc_syntax::{
+    operator::{AssignmentOperator, BinaryOperator, LogicalOperator, UnaryOperator},
+    regexp_flags::is_valid_regex_flags,
+};
 use rustc_hash::FxHasher;
 
 pub fn calculate_hash<T: Hash>(t: &T) -> u64 {
@@ -277,6 +280,10 @@ pub fn extract_regex_flags<'a>(
     let Argument::StringLiteral(flag_arg) = &args[1] else {
         return None;
     };
+    // `new RegExp("a", "gg")` throws.
+    if !is_valid_regex_flags(&flag_arg.value) {
+        return None;
+    }
     let mut flags = RegExpFlags::empty();
     for ch in flag_arg.value.chars() {
         let flag = RegExpFlags::try_from(ch).ok()?;
@@ -0,0 +1,208 @@
+//! Baseline of known lint violations, to adopt rules in large codebases incrementally.
+//!
+//! A baseline records the violations of each file, so later runs only report violations which
+//! are not in it. Violations are identified by their rule and a fingerprint of the message and
+//! the reported source text, so they stay known when unrelated code above them moves.
+
+use std::{collections::BTreeMap, fs, path::Path};
+
+use oxc_diagnostics::OxcDiagnostic;
+use serde::{Deserialize, Serialize};
+
+use crate::fixer::Message;
+
+/// Known violations, keyed by file path, rule and fingerprint, with the number of occurrences.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Baseline {
+    files: BTreeMap<String, BTreeMap<String, BTreeMap<String, usize>>>,
+}
+
+impl Baseline {
+    /// # Errors
+    ///
+    /// Returns `Err` if the file cannot be read or is not a valid baseline.
+    pub fn from_file(path: &Path) -> Result<Self, OxcDiagnostic> {
+        let text = fs::read_to_string(path).map_err(|e| {
+            OxcDiagnostic::error(format!("Failed to read baseline {path:?} with error {e:?}"))
+        })?;
+        serde_json::from_str(&text).map_err(|err| {
+            OxcDiagnostic::error(format!("Failed to parse baseline {path:?}: {err}"))
+        })
+    }
+
+    /// Write the baseline as pretty printed JSON, with sorted keys so it diffs cleanly.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the file cannot be written.
+    pub fn write_file(&self, path: &Path) -> Result<(), OxcDiagnostic> {
+        let mut text = serde_json::to_string_pretty(self).unwrap();
+        text.push('\n');
+        fs::write(path, text).map_err(|e| {
+            OxcDiagnostic::error(format!("Failed to write baseline {path:?} with error {e:?}"))
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Number of known violations.
+    pub fn len(&self) -> usize {
+        self.files.values().flat_map(BTreeMap::values).flat_map(BTreeMap::values).sum()
+    }
+
+    /// Merge the violations of `other`, replacing the violations of the files it has recorded,
+    /// including files which no longer have any.
+    pub fn extend(&mut self, other: Self) {
+        self.files.extend(other.files);
+        self.files.retain(|_, rules| !rules.is_empty());
+    }
+
+    /// Record the rule violations in `messages`. Syntax errors are not recorded, since they
+    /// cannot be suppressed.
+    pub(crate) fn record(&mut self, path: &str, source_text: &str, messages: &[Message]) {
+        let rules = self.files.entry(path.to_string()).or_default();
+        for message in messages {
+            let Some(rule) = rule_name(message) else { continue };
+            let fingerprint = fingerprint(message, source_text);
+            *rules.entry(rule).or_default().entry(fingerprint).or_default() += 1;
+        }
+    }
+
+    /// Remove the messages of known violations. Each recorded occurrence suppresses one message,
+    /// so adding another identical violation to a file is still reported.
+    pub(crate) fn filter<'a>(
+        &self,
+        path: &str,
+        source_text: &str,
+        messages: Vec<Message<'a>>,
+    ) -> Vec<Message<'a>> {
+        let Some(known) = self.files.get(path) else { return messages };
+        let mut known = known.clone();
+        messages
+            .into_iter()
+            .filter(|message| {
+                let Some(rule) = rule_name(message) else { return true };
+                let Some(count) = known
+                    .get_mut(&rule)
+                    .and_then(|fingerprints| {
+                        fingerprints.get_mut(&fingerprint(message, source_text))
+                    })
+                    .filter(|count| **count > 0)
+                else {
+                    return true;
+                };
+                *count -= 1;
+                false
+            })
+            .collect()
+    }
+}
+
+/// Key of a file in the baseline: `path` relative to `cwd`, with `/` separators so baselines are
+/// portable between platforms.
+pub(crate) fn baseline_path(cwd: &Path, path: &Path) -> String {
+    let path = path.strip_prefix(cwd).unwrap_or(path);
+    path.components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// The rule which reported the message, e.g. `eslint(no-debugger)`.
+fn rule_name(message: &Message) -> Option<String> {
+    message.error.code.is_some().then(|| message.error.code.to_string())
+}
+
+/// Hash of the message and the reported source text, with whitespace collapsed so the
+/// fingerprint does not change when the code is re-indented.
+fn fingerprint(message: &Message, source_text: &str) -> String {
+    let snippet = source_text.get(message.start as usize..message.end as usize).unwrap_or_default();
+    // 64-bit FNV-1a, which is stable across Rust versions, unlike the `std` hashers.
+    let mut hash = 0xcbf2_9ce4_8422_2325_u64;
+    let mut write = |bytes: &[u8]| {
+        for byte in bytes {
+            hash ^= u64::from(*byte);
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+    };
+    write(message.error.message.as_bytes());
+    for word in snippet.split_whitespace() {
+        write(b"\0");
+        write(word.as_bytes());
+    }
+    format!("{hash:016x}")
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::Path;
+
+    use oxc_diagnostics::OxcDiagnostic;
+    use oxc_span::Span;
+
+    use super::{baseline_path, Baseline};
+    use crate::fixer::Message;
+
+    fn message(rule: &'static str, start: u32, end: u32) -> Message<'static> {
+        let error = OxcDiagnostic::warn("Unexpected debugger statement")
+            .with_label(Span::new(start, end))
+            .with_error_code("eslint", rule);
+        Message::new(error, None)
+    }
+
+    #[test]
+    fn filter_known_violations() {
+        let source_text = "debugger;\ndebugger;";
+        let mut baseline = Baseline::default();
+        baseline.record("a.js", source_text, &[message("no-debugger", 0, 8)]);
+        assert_eq!(baseline.len(), 1);
+
+        // Both statements have the same fingerprint, only one occurrence is known.
+        let messages = vec![message("no-debugger", 0, 8), message("no-debugger", 10, 18)];
+        let messages = baseline.filter("a.js", source_text, messages);
+        assert_eq!(messages.len(), 1);
+
+        // Moved code keeps its fingerprint.
+        let source_text = "\n\n  debugger;";
+        let messages = baseline.filter("a.js", source_text, vec![message("no-debugger", 4, 12)]);
+        assert!(messages.is_empty());
+
+        // Other files and rules are still reported.
+        let messages = baseline.filter("b.js", source_text, vec![message("no-debugger", 4, 12)]);
+        assert_eq!(messages.len(), 1);
+        let messages = baseline.filter("a.js", source_text, vec![message("no-empty", 4, 12)]);
+        assert_eq!(messages.len(), 1);
+    }
+
+    #[test]
+    fn update() {
+        let mut baseline = Baseline::default();
+        baseline.record("a.js", "debugger;", &[message("no-debugger", 0, 8)]);
+        baseline.record("b.js", "debugger;", &[message("no-debugger", 0, 8)]);
+
+        // `a.js` was fixed, `c.js` was not linted.
+        let mut recorded = Baseline::default();
+        recorded.record("a.js", "", &[]);
+        recorded.record("b.js", "debugger;", &[message("no-debugger", 0, 8)]);
+        baseline.extend(recorded);
+        assert_eq!(baseline.files.keys().collect::<Vec<_>>(), ["b.js"]);
+        assert_eq!(baseline.len(), 1);
+    }
+
+    #[test]
+    fn serialize() {
+        let mut baseline = Baseline::default();
+        baseline.record("src/a.js", "debugger;", &[message("no-debugger", 0, 8)]);
+        let json = serde_json::to_string(&baseline).unwrap();
+        assert!(json.contains(r#""src/a.js":{"eslint(no-debugger)":{"#), "{json}");
+        assert_eq!(serde_json::from_str::<Baseline>(&json).unwrap(), baseline);
+    }
+
+    #[test]
+    fn portable_paths() {
+        let path = Path::new("/project").join("src").join("a.js");
+        assert_eq!(baseline_path(Path::new("/project"), &path), "src/a.js");
+    }
+}
@@ -0,0 +1,146 @@
+use oxc_span::Span;
+
+/// Span of the value at the JSON pointer `path` in `source_text`, e.g. `/rules/no-empty/1`.
+///
+/// `source_text` must be valid JSON, such as a configuration file with its comments replaced by
+/// whitespace, which keeps the offsets of the original file.
+pub fn find(source_text: &str, path: &str) -> Option<Span> {
+    let mut scanner = Scanner { bytes: source_text.as_bytes(), source_text, pos: 0 };
+    for token in path.split('/').skip(1) {
+        let token = token.replace("~1", "/").replace("~0", "~");
+        scanner.skip_whitespace();
+        match scanner.bytes.get(scanner.pos)? {
+            b'{' => scanner.find_member(&token)?,
+            b'[' => scanner.find_element(token.parse().ok()?)?,
+            _ => return None,
+        }
+    }
+    scanner.skip_whitespace();
+    let start = scanner.pos;
+    scanner.skip_value()?;
+    Some(Span::new(u32::try_from(start).ok()?, u32::try_from(scanner.pos).ok()?))
+}
+
+struct Scanner<'a> {
+    bytes: &'a [u8],
+    source_text: &'a str,
+    pos: usize,
+}
+
+impl Scanner<'_> {
+    fn skip_whitespace(&mut self) {
+        while self.bytes.get(self.pos).is_some_and(u8::is_ascii_whitespace) {
+            self.pos += 1;
+        }
+    }
+
+    /// Skips `byte` and the whitespace after it.
+    fn expect(&mut self, byte: u8) -> Option<()> {
+        self.skip_whitespace();
+        (self.bytes.get(self.pos) == Some(&byte)).then(|| {
+            self.pos += 1;
+            self.skip_whitespace();
+        })
+    }
+
+    /// Moves to the value of the member `key` of the object at the current position.
+    fn find_member(&mut self, key: &str) -> Option<()> {
+        self.expect(b'{')?;
+        loop {
+            let start = self.pos;
+            self.skip_string()?;
+            let name = serde_json::from_str::<String>(&self.source_text[start..self.pos]).ok()?;
+            self.expect(b':')?;
+            if name == key {
+                return Some(());
+            }
+            self.skip_value()?;
+            self.expect(b',')?;
+        }
+    }
+
+    /// Moves to the element `index` of the array at the current position.
+    fn find_element(&mut self, index: usize) -> Option<()> {
+        self.expect(b'[')?;
+        for _ in 0..index {
+            self.skip_value()?;
+            self.expect(b',')?;
+        }
+        (self.bytes.get(self.pos) != Some(&b']')).then_some(())
+    }
+
+    fn skip_string(&mut self) -> Option<()> {
+        if self.bytes.get(self.pos) != Some(&b'"') {
+            return None;
+        }
+        self.pos += 1;
+        loop {
+            match self.bytes.get(self.pos)? {
+                b'\\' => self.pos += 2,
+                b'"' => {
+                    self.pos += 1;
+                    return Some(());
+                }
+                _ => self.pos += 1,
+            }
+        }
+    }
+
+    fn skip_value(&mut self) -> Option<()> {
+        match self.bytes.get(self.pos)? {
+            b'"' => self.skip_string(),
+            open @ (b'{' | b'[') => {
+                let close = if *open == b'{' { b'}' } else { b']' };
+                self.expect(*open)?;
+                while self.bytes.get(self.pos)? != &close {
+                    if *open == b'{' {
+                        self.skip_string()?;
+                        self.expect(b':')?;
+                    }
+                    self.skip_value()?;
+                    self.skip_whitespace();
+                    if self.bytes.get(self.pos) == Some(&b',') {
+                        self.expect(b',')?;
+                    }
+                }
+                self.pos += 1;
+                Some(())
+            }
+            _ => {
+                let len = self.bytes[self.pos..]
+                    .iter()
+                    .position(|b| matches!(b, b',' | b'}' | b']') || b.is_ascii_whitespace())
+                    .unwrap_or(self.bytes.len() - self.pos);
+                self.pos += len;
+                Some(())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_span::Span;
+
+    use super::find;
+
+    #[test]
+    fn test_find() {
+        let source_text = r#"{
+  "rules": {
+    "a~b/c": "off",
+    "no-empty": ["error", { "allowEmptyCatch": "yes", "x": [1, { "y": null }] }],
+    "no-void": [2, {}]
+  }
+}"#;
+        let text = |path| find(source_text, path).map(|span: Span| span.source_text(source_text));
+        assert_eq!(text("/rules/a~0b~1c"), Some(r#""off""#));
+        assert_eq!(text("/rules/no-empty/0"), Some(r#""error""#));
+        assert_eq!(text("/rules/no-empty/1/allowEmptyCatch"), Some(r#""yes""#));
+        assert_eq!(text("/rules/no-empty/1/x/1/y"), Some("null"));
+        assert_eq!(text("/rules/no-void/1"), Some("{}"));
+        assert_eq!(text("/rules/no-void/2"), None);
+        assert_eq!(text("/rules/no-empty/1/unknown"), None);
+        assert_eq!(text("/rules/no-empty/0/0"), None);
+    }
+}
@@ -1,15 +1,17 @@
 mod env;
 mod globals;
+mod json_pointer;
 mod rules;
 mod settings;
 
-use std::path::Path;
+use std::{fmt::Write, path::Path};
 
-use oxc_diagnostics::OxcDiagnostic;
+use oxc_diagnostics::{Error, LabeledSpan, NamedSource, OxcDiagnostic};
 use rustc_hash::FxHashSet;
 use schemars::JsonSchema;
 use serde::Deserialize;
 
+use self::rules::parse_rule_key;
 pub use self::{
     env::OxlintEnv,
     globals::OxlintGlobals,
@@ -17,7 +19,9 @@ pub use self::{
     settings::{jsdoc::JSDocPluginSettings, OxlintSettings},
 };
 use crate::{
-    rules::RuleEnum, utils::is_jest_rule_adapted_to_vitest, AllowWarnDeny, RuleWithSeverity,
+    rules::{RuleEnum, RULES},
+    utils::is_jest_rule_adapted_to_vitest,
+    AllowWarnDeny, RuleWithSeverity,
 };
 
 /// Oxlint Configuration File
@@ -65,10 +69,12 @@ impl OxlintConfig {
     /// # Errors
     ///
     /// * Parse Failure
-    pub fn from_file(path: &Path) -> Result<Self, OxcDiagnostic> {
-        let mut string = std::fs::read_to_string(path).map_err(|e| {
+    /// * Invalid options of enabled rules, see [`crate::RuleConfig`]
+    pub fn from_file(path: &Path) -> Result<Self, Error> {
+        let source_text = std::fs::read_to_string(path).map_err(|e| {
             OxcDiagnostic::error(format!("Failed to parse config {path:?} with error {e:?}"))
         })?;
+        let mut string = source_text.clone();
 
         // jsonc support
         json_strip_comments::strip(&mut string).map_err(|err| {
@@ -94,6 +100,14 @@ impl OxlintConfig {
             OxcDiagnostic::error(format!("Failed to parse config with error {err:?}"))
         })?;
 
+        // Comments are replaced with whitespace, so offsets in `string` are offsets in the file.
+        let labels = invalid_rule_options(&json, &string);
+        if !labels.is_empty() {
+            return Err(OxcDiagnostic::error(format!("Invalid rule options in config {path:?}"))
+                .with_labels(labels)
+                .with_source_code(NamedSource::new(path.to_string_lossy(), source_text)));
+        }
+
         Ok(config)
     }
 
@@ -172,6 +186,49 @@ impl OxlintConfig {
     }
 }
 
+/// Labels the invalid options of the enabled rules in `json`, at their location in `source_text`,
+/// which `json` is parsed from.
+fn invalid_rule_options(json: &serde_json::Value, source_text: &str) -> Vec<LabeledSpan> {
+    let Some(rules) = json.get("rules").and_then(serde_json::Value::as_object) else {
+        return vec![];
+    };
+    let mut labels = vec![];
+    for (key, value) in rules {
+        let Some([severity, options @ ..]) = value.as_array().map(Vec::as_slice) else {
+            continue;
+        };
+        if options.is_empty()
+            || !AllowWarnDeny::try_from(severity).is_ok_and(AllowWarnDeny::is_warn_deny)
+        {
+            continue;
+        }
+        let (plugin_name, rule_name) = parse_rule_key(key);
+        let (rule_name, plugin_name) = transform_rule_and_plugin_name(&rule_name, &plugin_name);
+        let Some(rule) =
+            RULES.iter().find(|r| r.name() == rule_name && r.plugin_name() == plugin_name)
+        else {
+            continue;
+        };
+        let rule_path = format!("/rules/{}", key.replace('~', "~0").replace('/', "~1"));
+        for error in rule.configuration_errors(&serde_json::Value::Array(options.to_vec())) {
+            // Error paths start at the options, which follow the severity in the file.
+            let mut path = rule_path.clone();
+            let mut tokens = error.path().split('/').skip(1);
+            if let Some(index) = tokens.next().and_then(|index| index.parse::<usize>().ok()) {
+                write!(path, "/{}", index + 1).unwrap();
+                for token in tokens {
+                    write!(path, "/{token}").unwrap();
+                }
+            }
+            let span = json_pointer::find(source_text, &path)
+                .or_else(|| json_pointer::find(source_text, &rule_path))
+                .unwrap_or_default();
+            labels.push(span.label(format!("{key}: {}", error.message())));
+        }
+    }
+    labels
+}
+
 fn transform_rule_and_plugin_name<'a>(
     rule_name: &'a str,
     plugin_name: &'a str,
@@ -202,6 +259,27 @@ mod test {
         assert!(!config.rules.is_empty());
     }
 
+    #[test]
+    fn test_invalid_rule_options() {
+        let fixture_path =
+            env::current_dir().unwrap().join("fixtures/eslint_config_invalid_options.json");
+        let source_text = std::fs::read_to_string(&fixture_path).unwrap();
+        let err = OxlintConfig::from_file(&fixture_path).unwrap_err();
+        let mut labels = err
+            .labels()
+            .unwrap()
+            .map(|label| {
+                let value = &source_text[label.offset()..label.offset() + label.len()];
+                (value, label.label().unwrap().to_string())
+            })
+            .collect::<Vec<_>>();
+        labels.sort();
+        assert_eq!(labels.len(), 2, "{labels:?}");
+        assert_eq!(labels[0].0, "\"yes\"");
+        assert!(labels[0].1.starts_with("no-empty: invalid type"), "{}", labels[0].1);
+        assert_eq!(labels[1], ("1", "eslint/no-self-assign: unknown option `x`".to_string()));
+    }
+
     #[test]
     fn test_deserialize() {
         let config = OxlintConfig::deserialize(&serde_json::json!({
@@ -14,6 +14,7 @@ use crate::{
     disable_directives::{DisableDirectives, DisableDirectivesBuilder},
     fixer::{FixKind, Message, RuleFix, RuleFixer},
     javascript_globals::GLOBALS,
+    module_resolution::{ResolvedModule, ResolvedModules},
     AllowWarnDeny, FrameworkFlags, OxlintConfig, OxlintEnv, OxlintGlobals, OxlintSettings,
 };
 
@@ -38,6 +39,10 @@ pub struct LintContext<'a> {
 
     file_path: Rc<Path>,
 
+    /// Resolutions of the import specifiers of the file. Empty unless the import plugin is
+    /// enabled.
+    resolved_modules: Rc<ResolvedModules>,
+
     eslint_config: Arc<OxlintConfig>,
 
     // states
@@ -80,6 +85,7 @@ impl<'a> LintContext<'a> {
             disable_directives: Rc::new(disable_directives),
             fix: FixKind::None,
             file_path: file_path.into(),
+            resolved_modules: Rc::default(),
             eslint_config: Arc::new(OxlintConfig::default()),
             current_plugin_prefix: "eslint",
             current_rule_name: "",
@@ -101,6 +107,11 @@ impl<'a> LintContext<'a> {
         self
     }
 
+    pub fn with_resolved_modules(mut self, resolved_modules: Rc<ResolvedModules>) -> Self {
+        self.resolved_modules = resolved_modules;
+        self
+    }
+
     pub fn with_plugin_name(mut self, plugin: &'static str) -> Self {
         self.current_plugin_prefix = plugin_name_to_prefix(plugin);
         self
@@ -311,6 +322,7 @@ impl<'a> LintContext<'a> {
     {
         let fixer = RuleFixer::new(fix_kind, self);
         let rule_fix: RuleFix<'a> = fix(fixer).into();
+        let rule_fix = rule_fix.classify(fix_kind);
         #[cfg(debug_assertions)]
         {
             assert!(
@@ -365,6 +377,13 @@ impl<'a> LintContext<'a> {
         self.semantic().module_record()
     }
 
+    /// What an import specifier of the file resolved to, e.g. `./foo` or `lodash`.
+    ///
+    /// `None` if the specifier is not imported by the file, or the import plugin is disabled.
+    pub fn resolved_module(&self, specifier: &str) -> Option<&ResolvedModule> {
+        self.resolved_modules.get(specifier)
+    }
+
     /// JSDoc comments
     ///
     /// Shorthand for `ctx.semantic().jsdoc()`.
@@ -60,6 +60,13 @@ impl FixKind {
         self.contains(Self::Dangerous)
     }
 
+    /// Whether this is a fix which never changes the behavior of the code, and is applied
+    /// with `--fix`.
+    #[inline]
+    pub fn is_safe_fix(self) -> bool {
+        self == Self::SafeFix
+    }
+
     /// Check if a fix produced by a lint rule is allowed to be applied
     /// to the source code.
     ///
@@ -180,6 +187,20 @@ impl<'a> RuleFix<'a> {
         self
     }
 
+    /// Classify this fix as `kind`, which is how the rule reported it, e.g. as a suggestion with
+    /// [`LintContext::diagnostic_with_suggestion`]. It stays dangerous if it was marked with
+    /// [`RuleFix::dangerously`].
+    ///
+    /// Fixes converted from a [`Fix`] or [`CompositeFix`] are safe fixes until they are
+    /// classified, so this prevents suggestions from being applied with `--fix`.
+    ///
+    /// [`LintContext::diagnostic_with_suggestion`]: crate::LintContext::diagnostic_with_suggestion
+    #[inline]
+    pub(crate) fn classify(mut self, kind: FixKind) -> Self {
+        self.kind = kind | (self.kind & FixKind::Dangerous);
+        self
+    }
+
     #[inline]
     pub fn with_message<S: Into<Cow<'a, str>>>(mut self, message: S) -> Self {
         self.message = Some(message.into());
@@ -548,6 +569,18 @@ mod test {
         assert!(!FixKind::None.can_apply(FixKind::DangerousFix));
     }
 
+    #[test]
+    fn test_classify() {
+        let fix = RuleFix::from(Fix::delete(Span::empty(0)));
+        assert!(fix.kind().is_safe_fix());
+        let suggestion = fix.classify(FixKind::Suggestion);
+        assert_eq!(suggestion.kind(), FixKind::Suggestion);
+        assert!(!FixKind::SafeFix.can_apply(suggestion.kind()));
+
+        let dangerous = RuleFix::from(Fix::delete(Span::empty(0))).dangerously();
+        assert_eq!(dangerous.classify(FixKind::SafeFix).kind(), FixKind::DangerousFix);
+    }
+
     #[test]
     fn test_composite_push_on_none() {
         let f: CompositeFix = Fix::new("foo", Span::empty(4)).into();
@@ -3,7 +3,7 @@ mod fix;
 use std::borrow::Cow;
 
 use oxc_codegen::{CodeGenerator, CodegenOptions};
-use oxc_diagnostics::OxcDiagnostic;
+use oxc_diagnostics::{LabeledSpan, OxcDiagnostic};
 use oxc_span::{GetSpan, Span};
 
 use crate::LintContext;
@@ -165,7 +165,8 @@ impl<'c, 'a: 'c> RuleFixer<'c, 'a> {
 
     #[allow(clippy::unused_self)]
     pub fn codegen(self) -> CodeGenerator<'a> {
-        CodeGenerator::new().with_options(CodegenOptions { single_quote: true })
+        CodeGenerator::new()
+            .with_options(CodegenOptions { single_quote: true, ..CodegenOptions::default() })
     }
 
     #[allow(clippy::unused_self)]
@@ -241,6 +242,36 @@ impl<'a> Message<'a> {
     pub fn end(&self) -> u32 {
         self.end
     }
+
+    /// Moves the spans of the diagnostic and the fix by `offset`, for code which is a part of a
+    /// larger file, such as the `<script>` block of a Vue component.
+    #[must_use]
+    pub fn with_offset(mut self, offset: u32) -> Self {
+        if offset == 0 {
+            return self;
+        }
+        if let Some(labels) = &self.error.labels {
+            let labels = labels
+                .iter()
+                .map(|label| {
+                    let text = label.label().map(ToString::to_string);
+                    let span = (label.offset() + offset as usize, label.len());
+                    if label.primary() {
+                        LabeledSpan::new_primary_with_span(text, span)
+                    } else {
+                        LabeledSpan::new_with_span(text, span)
+                    }
+                })
+                .collect::<Vec<_>>();
+            self.error = self.error.with_labels(labels);
+        }
+        self.start += offset;
+        self.end += offset;
+        if let Some(fix) = &mut self.fix {
+            fix.span = Span::new(fix.span.start + offset, fix.span.end + offset);
+        }
+        self
+    }
 }
 
 impl<'a> GetSpan for Message<'a> {
@@ -310,7 +341,7 @@ mod test {
     use std::borrow::Cow;
 
     use oxc_diagnostics::OxcDiagnostic;
-    use oxc_span::Span;
+    use oxc_span::{GetSpan, Span};
 
     use super::{CompositeFix, Fix, FixResult, Fixer, Message};
 
@@ -386,6 +417,15 @@ mod test {
         Message::new(error, fix)
     }
 
+    #[test]
+    fn with_offset() {
+        let message = create_message(no_fix(Span::new(4, 10)), Some(REPLACE_ID)).with_offset(8);
+        assert_eq!(message.span(), Span::new(12, 18));
+        let labels = message.error.labels.as_ref().unwrap();
+        assert_eq!((labels[0].offset(), labels[0].len()), (12, 6));
+        assert_eq!(message.fix.unwrap().span, Span::new(12, 18));
+    }
+
     #[test]
     fn insert_at_the_end() {
         let result = get_fix_result(vec![create_message(insert_at_end(), Some(INSERT_AT_END))]);
@@ -4,6 +4,7 @@
 mod tester;
 
 mod ast_util;
+mod baseline;
 mod config;
 mod context;
 mod disable_directives;
@@ -11,11 +12,13 @@ mod fixer;
 mod frameworks;
 mod globals;
 mod javascript_globals;
+mod module_resolution;
 mod options;
 mod rule;
 mod rules;
 mod service;
 mod utils;
+mod walk;
 
 pub mod partial_loader;
 pub mod table;
@@ -26,13 +29,16 @@ use oxc_diagnostics::Error;
 use oxc_semantic::{AstNode, Semantic};
 
 pub use crate::{
+    baseline::Baseline,
     config::OxlintConfig,
     context::LintContext,
     fixer::FixKind,
     frameworks::FrameworkFlags,
+    module_resolution::{ResolvedModule, ResolvedModules},
     options::{AllowWarnDeny, LintOptions},
-    rule::{RuleCategory, RuleMeta, RuleWithSeverity},
+    rule::{RuleCategory, RuleConfig, RuleConfigError, RuleMeta, RuleWithSeverity},
     service::{LintService, LintServiceOptions},
+    walk::{Symlinks, Walk, WalkOptions},
 };
 use crate::{
     config::{OxlintEnv, OxlintGlobals, OxlintSettings},
@@ -113,7 +119,18 @@ impl Linter {
 
     // pub fn run<'a>(&self, ctx: LintContext<'a>) -> Vec<Message<'a>> {
     pub fn run<'a>(&self, path: &Path, semantic: Rc<Semantic<'a>>) -> Vec<Message<'a>> {
-        let ctx = self.create_ctx(path, semantic);
+        self.run_with_resolved_modules(path, semantic, Rc::default())
+    }
+
+    /// Same as [`Linter::run`], with the resolutions of the file's import specifiers available to
+    /// rules through [`LintContext::resolved_module`].
+    pub fn run_with_resolved_modules<'a>(
+        &self,
+        path: &Path,
+        semantic: Rc<Semantic<'a>>,
+        resolved_modules: Rc<ResolvedModules>,
+    ) -> Vec<Message<'a>> {
+        let ctx = self.create_ctx(path, semantic).with_resolved_modules(resolved_modules);
         let semantic = Rc::clone(ctx.semantic());
 
         let rules = self
@@ -0,0 +1,126 @@
+use std::path::{Component, Path, PathBuf};
+
+use oxc_resolver::{
+    Resolution, ResolveError, ResolveOptions, Resolver, TsconfigOptions, TsconfigReferences,
+};
+use oxc_span::{CompactStr, VALID_EXTENSIONS};
+use rustc_hash::FxHashMap;
+
+/// Resolutions of the import specifiers of a file, keyed by specifier.
+pub type ResolvedModules = FxHashMap<CompactStr, ResolvedModule>;
+
+/// What an import specifier resolved to.
+///
+/// Resolution applies tsconfig `paths` aliases, package.json `exports` maps and extension
+/// resolution, so rules can compare and check specifiers without resolving them on their own.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ResolvedModule {
+    /// Absolute path of the resolved file.
+    File(PathBuf),
+    /// Node.js builtin module, e.g. `fs` or `node:fs`.
+    Builtin,
+    /// Missing file, or a package which is not installed.
+    Unresolved,
+}
+
+impl ResolvedModule {
+    pub fn path(&self) -> Option<&Path> {
+        match self {
+            Self::File(path) => Some(path),
+            Self::Builtin | Self::Unresolved => None,
+        }
+    }
+
+    pub fn is_unresolved(&self) -> bool {
+        matches!(self, Self::Unresolved)
+    }
+
+    /// Whether the file belongs to an installed package, i.e. it is inside `node_modules`.
+    pub fn is_external(&self) -> bool {
+        self.path().is_some_and(|path| {
+            path.components()
+                .any(|component| component == Component::Normal("node_modules".as_ref()))
+        })
+    }
+}
+
+impl From<&Result<Resolution, ResolveError>> for ResolvedModule {
+    fn from(resolution: &Result<Resolution, ResolveError>) -> Self {
+        match resolution {
+            Ok(resolution) => Self::File(resolution.path().to_path_buf()),
+            Err(ResolveError::Builtin(_)) => Self::Builtin,
+            Err(_) => Self::Unresolved,
+        }
+    }
+}
+
+/// Resolver shared by all files of a lint run.
+///
+/// `tsconfig` is only used if the file exists.
+pub fn create_resolver(tsconfig: Option<PathBuf>) -> Resolver {
+    let tsconfig = tsconfig.and_then(|path| {
+        if path.is_file() {
+            Some(TsconfigOptions { config_file: path, references: TsconfigReferences::Auto })
+        } else {
+            None
+        }
+    });
+
+    Resolver::new(ResolveOptions {
+        extensions: VALID_EXTENSIONS.iter().map(|ext| format!(".{ext}")).collect(),
+        // TypeScript allows importing `./foo.js` for `./foo.ts`.
+        extension_alias: vec![
+            (".js".into(), vec![".ts".into(), ".tsx".into(), ".js".into()]),
+            (".mjs".into(), vec![".mts".into(), ".mjs".into()]),
+            (".cjs".into(), vec![".cts".into(), ".cjs".into()]),
+        ],
+        condition_names: vec!["node".into(), "import".into(), "module".into(), "require".into()],
+        builtin_modules: true,
+        tsconfig,
+        ..ResolveOptions::default()
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use std::{path::Path, rc::Rc};
+
+    use oxc_allocator::Allocator;
+    use oxc_parser::Parser;
+    use oxc_semantic::SemanticBuilder;
+    use oxc_span::SourceType;
+
+    use super::{ResolvedModule, ResolvedModules};
+    use crate::{rules::RULES, AllowWarnDeny, LintOptions, Linter, RuleWithSeverity};
+
+    /// `import/no-duplicates` groups imports by the file their specifiers resolve to.
+    #[test]
+    fn rules_read_resolved_modules() {
+        let source_text = "import { x } from './foo'; import { y } from './foo.js'";
+        let source_type = SourceType::default().with_module(true);
+        let path = Path::new("/project/index.js");
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source_text, source_type).parse();
+        let program = allocator.alloc(ret.program);
+        let semantic = SemanticBuilder::new(source_text, source_type)
+            .with_trivias(ret.trivias)
+            .build_module_record(path.to_path_buf(), program)
+            .build(program)
+            .semantic;
+        let semantic = Rc::new(semantic);
+
+        let rule = RULES.iter().find(|rule| rule.name() == "no-duplicates").unwrap();
+        let linter = Linter::from_options(LintOptions::default().with_import_plugin(true))
+            .unwrap()
+            .with_rules(vec![RuleWithSeverity::new(rule.clone(), AllowWarnDeny::Warn)]);
+
+        // Without resolutions the specifiers are compared as written.
+        assert!(linter.run(path, Rc::clone(&semantic)).is_empty());
+
+        let foo = ResolvedModule::File("/project/foo.js".into());
+        let resolved_modules: ResolvedModules =
+            [("./foo".into(), foo.clone()), ("./foo.js".into(), foo)].into_iter().collect();
+        let messages = linter.run_with_resolved_modules(path, semantic, Rc::new(resolved_modules));
+        assert_eq!(messages.len(), 1);
+    }
+}
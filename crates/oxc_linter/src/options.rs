@@ -92,6 +92,34 @@ impl LintOptions {
         self
     }
 
+    /// Also apply suggestions, which may change the behavior of the code, like
+    /// `--fix-suggestions`. Only safe fixes are applied with [`FixKind::SafeFix`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use oxc_linter::{LintOptions, FixKind};
+    ///
+    /// let options = LintOptions::default().with_fix(FixKind::SafeFix).with_fix_suggestions(true);
+    /// assert!(options.fix.can_apply(FixKind::Suggestion));
+    /// ```
+    #[must_use]
+    pub fn with_fix_suggestions(mut self, yes: bool) -> Self {
+        self.fix.set(FixKind::Suggestion, yes);
+        self
+    }
+
+    /// Also apply dangerous fixes and suggestions, which may break the code, like
+    /// `--fix-dangerously`.
+    #[must_use]
+    pub fn with_fix_dangerously(mut self, yes: bool) -> Self {
+        if yes && self.fix.is_none() {
+            self.fix = FixKind::Fix;
+        }
+        self.fix.set(FixKind::Dangerous, yes);
+        self
+    }
+
     #[must_use]
     pub fn with_react_plugin(mut self, yes: bool) -> Self {
         self.react_plugin = yes;
@@ -15,6 +15,13 @@ pub trait Rule: Sized + Default + fmt::Debug {
         Self::default()
     }
 
+    /// Invalid options in the eslint json configuration, which [`Rule::from_configuration`]
+    /// ignores. Implemented with [`RuleConfig::parse_configuration`] by rules whose options derive
+    /// [`RuleConfig`].
+    fn configuration_errors(_value: &serde_json::Value) -> Vec<RuleConfigError> {
+        vec![]
+    }
+
     /// Visit each AST Node
     fn run<'a>(&self, _node: &AstNode<'a>, _ctx: &LintContext<'a>) {}
 
@@ -36,6 +43,65 @@ pub trait Rule: Sized + Default + fmt::Debug {
     }
 }
 
+/// Rule options which can be parsed from the eslint json configuration.
+///
+/// Usually implemented with `#[derive(RuleConfig)]` from `oxc_macros`, which also derives
+/// [`Default`]. Fields are read from the first element of the configuration array, using the
+/// camelCased field name as the key:
+///
+/// ```ignore
+/// #[derive(Debug, Clone, RuleConfig)]
+/// pub struct NoSelfAssign {
+///     #[rule_config(default = true)]
+///     props: bool,
+/// }
+/// ```
+pub trait RuleConfig: Sized {
+    /// Parse the options, falling back to the default value of any option which is missing or
+    /// invalid. Invalid and unknown options are returned as errors.
+    fn parse_configuration(value: &serde_json::Value) -> (Self, Vec<RuleConfigError>);
+}
+
+/// An invalid rule option, found while parsing a [`RuleConfig`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleConfigError {
+    /// JSON pointer to the invalid value, e.g. `/0/allowEmptyCatch`.
+    path: String,
+    message: String,
+}
+
+impl RuleConfigError {
+    pub fn new(path: impl Into<String>, message: impl Into<String>) -> Self {
+        Self { path: path.into(), message: message.into() }
+    }
+
+    /// An error for `key` in the options object.
+    pub fn option(key: &str, message: impl Into<String>) -> Self {
+        let key = key.replace('~', "~0").replace('/', "~1");
+        Self::new(format!("/0/{key}"), message)
+    }
+
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl fmt::Display for RuleConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "{}", self.message)
+        } else {
+            write!(f, "{}: {}", self.path, self.message)
+        }
+    }
+}
+
+impl std::error::Error for RuleConfigError {}
+
 pub trait RuleMeta {
     const NAME: &'static str;
 
@@ -223,6 +289,10 @@ impl RuleWithSeverity {
 
 #[cfg(test)]
 mod test {
+    use oxc_macros::RuleConfig;
+    use serde_json::json;
+
+    use super::{RuleConfig, RuleConfigError};
     use crate::rules::RULES;
 
     #[test]
@@ -232,4 +302,34 @@ mod test {
             assert!(rule.documentation().is_some_and(|s| !s.is_empty()), "{}", rule.name());
         }
     }
+
+    #[derive(Debug, Clone, RuleConfig)]
+    struct Options {
+        #[rule_config(default = true)]
+        props: bool,
+        allow_empty_catch: bool,
+        #[rule_config(rename = "a/b")]
+        renamed: Option<u32>,
+    }
+
+    #[test]
+    fn rule_config() {
+        let (options, errors) = Options::parse_configuration(&json!(null));
+        assert!(options.props && !options.allow_empty_catch && options.renamed.is_none());
+        assert!(errors.is_empty());
+
+        let (options, errors) = Options::parse_configuration(&json!([
+            { "props": false, "allowEmptyCatch": "yes", "a/b": 1, "unknown": true }
+        ]));
+        assert!(!options.props && !options.allow_empty_catch);
+        assert_eq!(options.renamed, Some(1));
+        assert_eq!(
+            errors.iter().map(RuleConfigError::path).collect::<Vec<_>>(),
+            ["/0/allowEmptyCatch", "/0/unknown"]
+        );
+
+        let (_, errors) = Options::parse_configuration(&json!([true]));
+        assert_eq!(errors, [RuleConfigError::new("/0", "expected an object")]);
+        assert_eq!(RuleConfigError::option("a/b~c", "").path(), "/0/a~1b~0c");
+    }
 }
@@ -13,10 +13,14 @@ use oxc_cfg::{
     EdgeType, ErrorEdgeKind, InstructionKind, ReturnInstructionKind,
 };
 use oxc_diagnostics::OxcDiagnostic;
-use oxc_macros::declare_oxc_lint;
+use oxc_macros::{declare_oxc_lint, RuleConfig};
 use oxc_span::Span;
 
-use crate::{context::LintContext, rule::Rule, AstNode};
+use crate::{
+    context::LintContext,
+    rule::{Rule, RuleConfig, RuleConfigError},
+    AstNode,
+};
 
 fn getter_return_diagnostic(span0: Span) -> OxcDiagnostic {
     OxcDiagnostic::warn("Expected to always return a value in getter.")
@@ -24,7 +28,7 @@ fn getter_return_diagnostic(span0: Span) -> OxcDiagnostic {
         .with_label(span0)
 }
 
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Clone, RuleConfig)]
 pub struct GetterReturn {
     pub allow_implicit: bool,
 }
@@ -73,13 +77,11 @@ impl Rule for GetterReturn {
     }
 
     fn from_configuration(value: serde_json::Value) -> Self {
-        let allow_implicit = value
-            .get(0)
-            .and_then(|config| config.get("allowImplicit"))
-            .and_then(serde_json::Value::as_bool)
-            .unwrap_or(false);
+        Self::parse_configuration(&value).0
+    }
 
-        Self { allow_implicit }
+    fn configuration_errors(value: &serde_json::Value) -> Vec<RuleConfigError> {
+        Self::parse_configuration(value).1
     }
 }
 
@@ -1,9 +1,13 @@
 use oxc_ast::AstKind;
 use oxc_diagnostics::OxcDiagnostic;
-use oxc_macros::declare_oxc_lint;
+use oxc_macros::{declare_oxc_lint, RuleConfig};
 use oxc_span::Span;
 
-use crate::{context::LintContext, rule::Rule, AstNode};
+use crate::{
+    context::LintContext,
+    rule::{Rule, RuleConfig, RuleConfigError},
+    AstNode,
+};
 
 fn no_empty_diagnostic(x0: &str, span1: Span) -> OxcDiagnostic {
     OxcDiagnostic::warn("Disallow empty block statements")
@@ -11,7 +15,7 @@ fn no_empty_diagnostic(x0: &str, span1: Span) -> OxcDiagnostic {
         .with_label(span1.label(format!("Empty {x0} statement")))
 }
 
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Clone, RuleConfig)]
 pub struct NoEmpty {
     allow_empty_catch: bool,
 }
@@ -36,13 +40,11 @@ declare_oxc_lint!(
 
 impl Rule for NoEmpty {
     fn from_configuration(value: serde_json::Value) -> Self {
-        let obj = value.get(0);
-        Self {
-            allow_empty_catch: obj
-                .and_then(|v| v.get("allowEmptyCatch"))
-                .and_then(serde_json::Value::as_bool)
-                .unwrap_or_default(),
-        }
+        Self::parse_configuration(&value).0
+    }
+
+    fn configuration_errors(value: &serde_json::Value) -> Vec<RuleConfigError> {
+        Self::parse_configuration(value).1
     }
 
     fn run<'a>(&self, node: &AstNode<'a>, ctx: &LintContext<'a>) {
@@ -1,17 +1,20 @@
 // Ported from https://github.com/eslint/eslint/tree/main/lib/rules/no-eval.js
 use oxc_ast::{ast::Expression, AstKind};
 use oxc_diagnostics::OxcDiagnostic;
-use oxc_macros::declare_oxc_lint;
+use oxc_macros::{declare_oxc_lint, RuleConfig};
 use oxc_semantic::AstNode;
 use oxc_span::Span;
 
-use crate::{context::LintContext, rule::Rule};
+use crate::{
+    context::LintContext,
+    rule::{Rule, RuleConfig, RuleConfigError},
+};
 
 fn no_eval_diagnostic(span0: Span) -> OxcDiagnostic {
     OxcDiagnostic::warn("eval can be harmful.").with_label(span0)
 }
 
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Clone, RuleConfig)]
 pub struct NoEval {
     /// Whether to allow references to the `eval` function as long as they are
     /// not called. For example, the following code is valid if this property is
@@ -49,11 +52,11 @@ declare_oxc_lint!(
 
 impl Rule for NoEval {
     fn from_configuration(value: serde_json::Value) -> Self {
-        let allow_indirect = value.get(0).map_or(false, |config| {
-            config.get("allowIndirect").and_then(serde_json::Value::as_bool).unwrap_or(false)
-        });
+        Self::parse_configuration(&value).0
+    }
 
-        Self { allow_indirect }
+    fn configuration_errors(value: &serde_json::Value) -> Vec<RuleConfigError> {
+        Self::parse_configuration(value).1
     }
 
     fn run<'a>(&self, node: &AstNode<'a>, ctx: &LintContext<'a>) {
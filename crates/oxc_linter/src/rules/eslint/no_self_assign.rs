@@ -7,28 +7,27 @@ use oxc_ast::{
     AstKind,
 };
 use oxc_diagnostics::OxcDiagnostic;
-use oxc_macros::declare_oxc_lint;
+use oxc_macros::{declare_oxc_lint, RuleConfig};
 use oxc_span::{GetSpan, Span};
 use oxc_syntax::operator::AssignmentOperator;
 
-use crate::{context::LintContext, rule::Rule, AstNode};
+use crate::{
+    context::LintContext,
+    rule::{Rule, RuleConfig, RuleConfigError},
+    AstNode,
+};
 
 fn no_self_assign_diagnostic(span0: Span) -> OxcDiagnostic {
     OxcDiagnostic::warn("this expression is assigned to itself").with_label(span0)
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, RuleConfig)]
 pub struct NoSelfAssign {
     /// if this is true, no-self-assign rule warns self-assignments of properties. Default is true.
+    #[rule_config(default = true)]
     props: bool,
 }
 
-impl Default for NoSelfAssign {
-    fn default() -> Self {
-        Self { props: true }
-    }
-}
-
 declare_oxc_lint!(
     /// ### What it does
     ///
@@ -49,13 +48,11 @@ declare_oxc_lint!(
 
 impl Rule for NoSelfAssign {
     fn from_configuration(value: serde_json::Value) -> Self {
-        Self {
-            props: value
-                .get(0)
-                .and_then(|v| v.get("props"))
-                .and_then(serde_json::Value::as_bool)
-                .unwrap_or(true),
-        }
+        Self::parse_configuration(&value).0
+    }
+
+    fn configuration_errors(value: &serde_json::Value) -> Vec<RuleConfigError> {
+        Self::parse_configuration(value).1
     }
 
     fn run<'a>(&self, node: &AstNode<'a>, ctx: &LintContext<'a>) {
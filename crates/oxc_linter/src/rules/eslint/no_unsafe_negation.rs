@@ -3,11 +3,16 @@ use oxc_ast::{
     AstKind,
 };
 use oxc_diagnostics::OxcDiagnostic;
-use oxc_macros::declare_oxc_lint;
+use oxc_macros::{declare_oxc_lint, RuleConfig};
 use oxc_span::{GetSpan, Span};
 use oxc_syntax::operator::{BinaryOperator, UnaryOperator};
 
-use crate::{context::LintContext, fixer::RuleFixer, rule::Rule, AstNode};
+use crate::{
+    context::LintContext,
+    fixer::RuleFixer,
+    rule::{Rule, RuleConfig, RuleConfigError},
+    AstNode,
+};
 
 fn no_unsafe_negation_diagnostic(x0: &str, span1: Span) -> OxcDiagnostic {
     OxcDiagnostic::warn(format!("Unexpected logical not in the left hand side of '{x0}' operator"))
@@ -15,7 +20,7 @@ fn no_unsafe_negation_diagnostic(x0: &str, span1: Span) -> OxcDiagnostic {
         .with_label(span1)
 }
 
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Clone, RuleConfig)]
 pub struct NoUnsafeNegation {
     /// true: disallow negation of the left-hand side of ordering relational operators
     /// false: allow negation of the left-hand side of ordering relational operators (<, >, <=, >=)
@@ -45,12 +50,11 @@ declare_oxc_lint!(
 
 impl Rule for NoUnsafeNegation {
     fn from_configuration(value: serde_json::Value) -> Self {
-        let enforce_for_ordering_relations = value
-            .get(0)
-            .and_then(|config| config.get("enforceForOrderingRelations"))
-            .and_then(serde_json::Value::as_bool)
-            .unwrap_or_default();
-        Self { enforce_for_ordering_relations }
+        Self::parse_configuration(&value).0
+    }
+
+    fn configuration_errors(value: &serde_json::Value) -> Vec<RuleConfigError> {
+        Self::parse_configuration(value).1
     }
 
     fn run<'a>(&self, node: &AstNode<'a>, ctx: &LintContext<'a>) {
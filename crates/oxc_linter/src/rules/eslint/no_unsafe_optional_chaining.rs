@@ -3,11 +3,15 @@ use oxc_ast::{
     AstKind,
 };
 use oxc_diagnostics::OxcDiagnostic;
-use oxc_macros::declare_oxc_lint;
+use oxc_macros::{declare_oxc_lint, RuleConfig};
 use oxc_span::Span;
 use oxc_syntax::operator::LogicalOperator;
 
-use crate::{context::LintContext, rule::Rule, AstNode};
+use crate::{
+    context::LintContext,
+    rule::{Rule, RuleConfig, RuleConfigError},
+    AstNode,
+};
 
 fn no_unsafe_optional_chaining_diagnostic(span0: Span) -> OxcDiagnostic {
     OxcDiagnostic::warn("Unsafe usage of optional chaining")
@@ -21,7 +25,7 @@ fn no_unsafe_arithmetic_diagnostic(span0: Span) -> OxcDiagnostic {
         .with_label(span0)
 }
 
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Clone, RuleConfig)]
 pub struct NoUnsafeOptionalChaining {
     /// Disallow arithmetic operations on optional chaining expressions (Default false).
     /// If this is true, this rule warns arithmetic operations on optional chaining expressions, which possibly result in NaN.
@@ -54,13 +58,11 @@ declare_oxc_lint!(
 
 impl Rule for NoUnsafeOptionalChaining {
     fn from_configuration(value: serde_json::Value) -> Self {
-        Self {
-            disallow_arithmetic_operators: value
-                .get(0)
-                .and_then(|v| v.get("disallowArithmeticOperators"))
-                .and_then(serde_json::Value::as_bool)
-                .unwrap_or_default(),
-        }
+        Self::parse_configuration(&value).0
+    }
+
+    fn configuration_errors(value: &serde_json::Value) -> Vec<RuleConfigError> {
+        Self::parse_configuration(value).1
     }
 
     fn run<'a>(&self, node: &AstNode<'a>, ctx: &LintContext<'a>) {
@@ -1,10 +1,14 @@
 use oxc_ast::AstKind;
 use oxc_diagnostics::OxcDiagnostic;
-use oxc_macros::declare_oxc_lint;
+use oxc_macros::{declare_oxc_lint, RuleConfig};
 use oxc_span::Span;
 use oxc_syntax::operator::UnaryOperator;
 
-use crate::{context::LintContext, rule::Rule, AstNode};
+use crate::{
+    context::LintContext,
+    rule::{Rule, RuleConfig, RuleConfigError},
+    AstNode,
+};
 
 fn no_void_diagnostic(span0: Span) -> OxcDiagnostic {
     OxcDiagnostic::warn("Disallow `void` operators")
@@ -12,7 +16,7 @@ fn no_void_diagnostic(span0: Span) -> OxcDiagnostic {
         .with_label(span0)
 }
 
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Clone, RuleConfig)]
 pub struct NoVoid {
     pub allow_as_statement: bool,
 }
@@ -40,13 +44,11 @@ declare_oxc_lint!(
 
 impl Rule for NoVoid {
     fn from_configuration(value: serde_json::Value) -> Self {
-        let allow_as_statement = value
-            .get(0)
-            .and_then(|config| config.get("allowAsStatement"))
-            .and_then(serde_json::Value::as_bool)
-            .unwrap_or(false);
+        Self::parse_configuration(&value).0
+    }
 
-        Self { allow_as_statement }
+    fn configuration_errors(value: &serde_json::Value) -> Vec<RuleConfigError> {
+        Self::parse_configuration(value).1
     }
 
     fn run<'a>(&self, node: &AstNode<'a>, ctx: &LintContext<'a>) {
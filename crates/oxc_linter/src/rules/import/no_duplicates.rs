@@ -3,7 +3,7 @@ use oxc_diagnostics::{LabeledSpan, OxcDiagnostic};
 use oxc_macros::declare_oxc_lint;
 use oxc_syntax::module_record::{ImportImportName, RequestedModule};
 
-use crate::{context::LintContext, rule::Rule};
+use crate::{context::LintContext, rule::Rule, ResolvedModule};
 
 /// <https://github.com/import-js/eslint-plugin-import/blob/main/docs/rules/no-duplicates.md>
 #[derive(Debug, Default, Clone)]
@@ -36,10 +36,10 @@ impl Rule for NoDuplicates {
             .requested_modules
             .iter()
             .map(|(source, requested_modules)| {
-                let resolved_absolute_path = module_record.loaded_modules.get(source).map_or_else(
-                    || source.to_string(),
-                    |module| module.resolved_absolute_path.to_string_lossy().to_string(),
-                );
+                let resolved_absolute_path = ctx
+                    .resolved_module(source)
+                    .and_then(ResolvedModule::path)
+                    .map_or_else(|| source.to_string(), |path| path.to_string_lossy().to_string());
                 (resolved_absolute_path, requested_modules)
             })
             .chunk_by(|r| r.0.clone());
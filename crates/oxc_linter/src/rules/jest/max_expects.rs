@@ -1,13 +1,13 @@
 use oxc_ast::{ast::Expression, AstKind};
 use oxc_diagnostics::OxcDiagnostic;
 use oxc_index::Idx;
-use oxc_macros::declare_oxc_lint;
+use oxc_macros::{declare_oxc_lint, RuleConfig};
 use oxc_span::Span;
 use rustc_hash::FxHashMap;
 
 use crate::{
     context::LintContext,
-    rule::Rule,
+    rule::{Rule, RuleConfig, RuleConfigError},
     utils::{collect_possible_jest_call_node, PossibleJestNode},
 };
 
@@ -17,17 +17,12 @@ fn exceeded_max_assertion(x0: usize, x1: usize, span2: Span) -> OxcDiagnostic {
         .with_label(span2)
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, RuleConfig)]
 pub struct MaxExpects {
+    #[rule_config(default = 5)]
     pub max: usize,
 }
 
-impl Default for MaxExpects {
-    fn default() -> Self {
-        Self { max: 5 }
-    }
-}
-
 declare_oxc_lint!(
     /// ### What it does
     /// As more assertions are made, there is a possible tendency for the test to be
@@ -66,14 +61,11 @@ declare_oxc_lint!(
 
 impl Rule for MaxExpects {
     fn from_configuration(value: serde_json::Value) -> Self {
-        let max = value
-            .get(0)
-            .and_then(|config| config.get("max"))
-            .and_then(serde_json::Value::as_number)
-            .and_then(serde_json::Number::as_u64)
-            .map_or(5, |v| usize::try_from(v).unwrap_or(5));
+        Self::parse_configuration(&value).0
+    }
 
-        Self { max }
+    fn configuration_errors(value: &serde_json::Value) -> Vec<RuleConfigError> {
+        Self::parse_configuration(value).1
     }
 
     fn run_once(&self, ctx: &LintContext) {
@@ -1,12 +1,12 @@
 use oxc_ast::AstKind;
 use oxc_diagnostics::OxcDiagnostic;
-use oxc_macros::declare_oxc_lint;
+use oxc_macros::{declare_oxc_lint, RuleConfig};
 use oxc_semantic::ScopeId;
 use oxc_span::Span;
 
 use crate::{
     context::LintContext,
-    rule::Rule,
+    rule::{Rule, RuleConfig, RuleConfigError},
     utils::{
         collect_possible_jest_call_node, is_type_of_jest_fn_call, JestFnKind, JestGeneralFnKind,
         PossibleJestNode,
@@ -19,17 +19,12 @@ fn exceeded_max_depth(current: usize, max: usize, span0: Span) -> OxcDiagnostic
         .with_label(span0)
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, RuleConfig)]
 pub struct MaxNestedDescribe {
+    #[rule_config(default = 5)]
     pub max: usize,
 }
 
-impl Default for MaxNestedDescribe {
-    fn default() -> Self {
-        Self { max: 5 }
-    }
-}
-
 declare_oxc_lint!(
     /// ### What it does
     ///
@@ -117,14 +112,11 @@ declare_oxc_lint!(
 
 impl Rule for MaxNestedDescribe {
     fn from_configuration(value: serde_json::Value) -> Self {
-        let max = value
-            .get(0)
-            .and_then(|config| config.get("max"))
-            .and_then(serde_json::Value::as_number)
-            .and_then(serde_json::Number::as_u64)
-            .map_or(5, |v| usize::try_from(v).unwrap_or(5));
+        Self::parse_configuration(&value).0
+    }
 
-        Self { max }
+    fn configuration_errors(value: &serde_json::Value) -> Vec<RuleConfigError> {
+        Self::parse_configuration(value).1
     }
 
     fn run_once(&self, ctx: &LintContext) {
@@ -1,10 +1,14 @@
 use oxc_ast::AstKind;
 use oxc_diagnostics::OxcDiagnostic;
-use oxc_macros::declare_oxc_lint;
+use oxc_macros::{declare_oxc_lint, RuleConfig};
 use oxc_span::Span;
 use serde_json::Value;
 
-use crate::{context::LintContext, rule::Rule, AstNode};
+use crate::{
+    context::LintContext,
+    rule::{Rule, RuleConfig, RuleConfigError},
+    AstNode,
+};
 
 fn no_explicit_any_diagnostic(span0: Span) -> OxcDiagnostic {
     OxcDiagnostic::warn("Unexpected any. Specify a different type.")
@@ -12,7 +16,7 @@ fn no_explicit_any_diagnostic(span0: Span) -> OxcDiagnostic {
         .with_label(span0)
 }
 
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Clone, RuleConfig)]
 pub struct NoExplicitAny {
     /// Whether to enable auto-fixing in which the `any` type is converted to the `unknown` type.
     ///
@@ -105,13 +109,11 @@ impl Rule for NoExplicitAny {
     }
 
     fn from_configuration(value: Value) -> Self {
-        let Some(cfg) = value.get(0) else {
-            return Self::default();
-        };
-        let fix_to_unknown = cfg.get("fixToUnknown").and_then(Value::as_bool).unwrap_or(false);
-        let ignore_rest_args = cfg.get("ignoreRestArgs").and_then(Value::as_bool).unwrap_or(false);
+        Self::parse_configuration(&value).0
+    }
 
-        Self { fix_to_unknown, ignore_rest_args }
+    fn configuration_errors(value: &serde_json::Value) -> Vec<RuleConfigError> {
+        Self::parse_configuration(value).1
     }
 
     fn should_run(&self, ctx: &LintContext) -> bool {
@@ -133,13 +135,58 @@ mod tests {
     use serde_json::json;
 
     use super::*;
-    use crate::tester::Tester;
+    use crate::tester::{Case, Tester};
 
     #[test]
     fn test_simple() {
-        let pass = vec!["let x: number = 1"];
-        let fail = vec!["let x: any = 1"];
-        Tester::new(NoExplicitAny::NAME, pass, fail).test();
+        let cases = vec![
+            Case::valid("let x: number = 1"),
+            Case::invalid("let x: any = 1").errors([(
+                "Unexpected any. Specify a different type.",
+                7,
+                10,
+            )]),
+            Case::invalid("let x: any = 1")
+                .config(json!([{ "fixToUnknown": true }]))
+                .errors([(7, 10)])
+                .output("let x: unknown = 1"),
+            Case::valid("function foo(...args: any[]) {}")
+                .config(json!([{ "ignoreRestArgs": true }])),
+            // Only TypeScript is checked.
+            Case::valid("let x = /** @type {any} */ (1)").path("no_explicit_any.js"),
+        ];
+        Tester::from_cases(NoExplicitAny::NAME, cases).test();
+    }
+
+    #[test]
+    fn test_eslint() {
+        let cases = [
+            json!("const number: number = 1;"),
+            json!({
+                "code": "const number: any = 1;",
+                "errors": [
+                    {
+                        "messageId": "unexpectedAny",
+                        "line": 1,
+                        "column": 15,
+                        "endLine": 1,
+                        "endColumn": 18,
+                    },
+                ],
+            }),
+            json!({
+                "code": "function foo(...args: any[]) {}",
+                "options": [{ "ignoreRestArgs": true }],
+            }),
+            json!({
+                "code": "function greet(): Array<Array<any>> {}",
+                "options": [{ "fixToUnknown": true }],
+                "errors": [{ "line": 1, "column": 31 }],
+                "output": "function greet(): Array<Array<unknown>> {}",
+            }),
+        ];
+        Tester::from_cases(NoExplicitAny::NAME, cases.iter().map(Case::from_eslint).collect())
+            .test();
     }
 
     #[test]
@@ -5,7 +5,7 @@ use oxc_ast::{
     AstKind,
 };
 use oxc_diagnostics::OxcDiagnostic;
-use oxc_macros::declare_oxc_lint;
+use oxc_macros::{declare_oxc_lint, RuleConfig};
 use oxc_span::{GetSpan, Span};
 use oxc_syntax::operator::BinaryOperator;
 
@@ -13,7 +13,7 @@ use crate::{
     ast_util::is_method_call,
     context::LintContext,
     fixer::{RuleFix, RuleFixer},
-    rule::Rule,
+    rule::{Rule, RuleConfig, RuleConfigError},
     AstNode,
 };
 
@@ -29,7 +29,7 @@ fn remove_null_diagnostic(span0: Span) -> OxcDiagnostic {
         .with_label(span0)
 }
 
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Clone, RuleConfig)]
 pub struct NoNull {
     check_strict_equality: Option<bool>,
 }
@@ -56,7 +56,7 @@ declare_oxc_lint!(
     /// ```
     NoNull,
     style,
-    fix
+    suggestion
 );
 
 fn match_null_arg(call_expr: &CallExpression, index: usize, span: Span) -> bool {
@@ -87,7 +87,7 @@ fn diagnose_binary_expression(
 
     // `if (foo != null) {}`
     if matches!(binary_expr.operator, BinaryOperator::Equality | BinaryOperator::Inequality) {
-        ctx.diagnostic_with_fix(replace_null_diagnostic(null_literal.span), |fixer| {
+        ctx.diagnostic_with_suggestion(replace_null_diagnostic(null_literal.span), |fixer| {
             fix_null(fixer, null_literal)
         });
 
@@ -95,7 +95,7 @@ fn diagnose_binary_expression(
     }
 
     // checkStrictEquality=true && `if (foo !== null) {}`
-    ctx.diagnostic_with_fix(replace_null_diagnostic(null_literal.span), |fixer| {
+    ctx.diagnostic_with_suggestion(replace_null_diagnostic(null_literal.span), |fixer| {
         fix_null(fixer, null_literal)
     });
 }
@@ -110,7 +110,7 @@ fn diagnose_variable_declarator(
     if matches!(&variable_declarator.init, Some(Expression::NullLiteral(expr)) if expr.span == null_literal.span)
         && matches!(parent_kind, Some(AstKind::VariableDeclaration(var_declaration)) if !var_declaration.kind.is_const() )
     {
-        ctx.diagnostic_with_fix(remove_null_diagnostic(null_literal.span), |fixer| {
+        ctx.diagnostic_with_suggestion(remove_null_diagnostic(null_literal.span), |fixer| {
             fixer.delete_range(Span::new(variable_declarator.id.span().end, null_literal.span.end))
         });
 
@@ -118,7 +118,7 @@ fn diagnose_variable_declarator(
     }
 
     // `const foo = null`
-    ctx.diagnostic_with_fix(replace_null_diagnostic(null_literal.span), |fixer| {
+    ctx.diagnostic_with_suggestion(replace_null_diagnostic(null_literal.span), |fixer| {
         fix_null(fixer, null_literal)
     });
 }
@@ -165,12 +165,11 @@ fn match_call_expression_pass_case(null_literal: &NullLiteral, call_expr: &CallE
 
 impl Rule for NoNull {
     fn from_configuration(value: serde_json::Value) -> Self {
-        Self {
-            check_strict_equality: value
-                .get(0)
-                .and_then(|v| v.get("checkStrictEquality"))
-                .and_then(serde_json::Value::as_bool),
-        }
+        Self::parse_configuration(&value).0
+    }
+
+    fn configuration_errors(value: &serde_json::Value) -> Vec<RuleConfigError> {
+        Self::parse_configuration(value).1
     }
 
     fn run<'a>(&self, node: &AstNode<'a>, ctx: &LintContext<'a>) {
@@ -207,15 +206,16 @@ impl Rule for NoNull {
 
             // `function foo() { return null; }`,
             if matches!(parent_node.kind(), AstKind::ReturnStatement(_)) {
-                ctx.diagnostic_with_fix(remove_null_diagnostic(null_literal.span), |fixer| {
-                    fixer.delete_range(null_literal.span)
-                });
+                ctx.diagnostic_with_suggestion(
+                    remove_null_diagnostic(null_literal.span),
+                    |fixer| fixer.delete_range(null_literal.span),
+                );
 
                 return;
             }
         }
 
-        ctx.diagnostic_with_fix(replace_null_diagnostic(null_literal.span), |fixer| {
+        ctx.diagnostic_with_suggestion(replace_null_diagnostic(null_literal.span), |fixer| {
             fix_null(fixer, null_literal)
         });
     }
@@ -38,7 +38,7 @@ declare_oxc_lint!(
     /// ```
     PreferCodePoint,
     pedantic,
-    fix
+    suggestion
 );
 
 impl Rule for PreferCodePoint {
@@ -61,7 +61,7 @@ impl Rule for PreferCodePoint {
             _ => return,
         };
 
-        ctx.diagnostic_with_fix(
+        ctx.diagnostic_with_suggestion(
             prefer_code_point_diagnostic(span, replacement, current),
             |fixer| fixer.replace(span, replacement),
         );
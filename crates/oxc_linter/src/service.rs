@@ -1,7 +1,7 @@
 use std::{
     collections::HashMap,
     ffi::OsStr,
-    fs,
+    fs, mem,
     path::{Path, PathBuf},
     rc::Rc,
     sync::{Arc, Condvar, Mutex},
@@ -18,6 +18,8 @@ use rayon::{iter::ParallelBridge, prelude::ParallelIterator};
 use rustc_hash::FxHashSet;
 
 use crate::{
+    baseline::{baseline_path, Baseline},
+    module_resolution::{create_resolver, ResolvedModule, ResolvedModules},
     partial_loader::{JavaScriptSource, PartialLoader, LINT_PARTIAL_LOADER_EXT},
     Fixer, Linter, Message,
 };
@@ -31,6 +33,13 @@ pub struct LintServiceOptions {
 
     /// TypeScript `tsconfig.json` path for reading path alias and project references
     pub tsconfig: Option<PathBuf>,
+
+    /// Violations which are not reported, see [`Baseline`].
+    pub baseline: Option<Baseline>,
+
+    /// Record the rule violations of all linted files instead of reporting them.
+    /// Retrieve them with [`LintService::take_recorded_baseline`] after [`LintService::run`].
+    pub record_baseline: bool,
 }
 
 #[derive(Clone)]
@@ -58,6 +67,18 @@ impl LintService {
         self.runtime.module_map.len() - self.runtime.paths.len()
     }
 
+    /// Rule violations recorded by [`LintService::run`], when
+    /// [`LintServiceOptions::record_baseline`] is enabled.
+    ///
+    /// # Panics
+    pub fn take_recorded_baseline(&self) -> Baseline {
+        self.runtime
+            .recorded_baseline
+            .as_ref()
+            .map(|recorded| mem::take(&mut *recorded.lock().unwrap()))
+            .unwrap_or_default()
+    }
+
     /// # Panics
     pub fn run(&self, tx_error: &DiagnosticSender) {
         self.runtime
@@ -130,12 +151,14 @@ pub struct Runtime {
     resolver: Option<Resolver>,
     module_map: ModuleMap,
     cache_state: CacheState,
+    baseline: Option<Baseline>,
+    recorded_baseline: Option<Mutex<Baseline>>,
 }
 
 impl Runtime {
     fn new(linter: Linter, options: LintServiceOptions) -> Self {
         let resolver = linter.options().import_plugin.then(|| {
-            Self::get_resolver(options.tsconfig.or_else(|| Some(options.cwd.join("tsconfig.json"))))
+            create_resolver(options.tsconfig.or_else(|| Some(options.cwd.join("tsconfig.json"))))
         });
         Self {
             cwd: options.cwd,
@@ -144,27 +167,11 @@ impl Runtime {
             resolver,
             module_map: ModuleMap::default(),
             cache_state: CacheState::default(),
+            baseline: options.baseline,
+            recorded_baseline: options.record_baseline.then(Mutex::default),
         }
     }
 
-    fn get_resolver(tsconfig: Option<PathBuf>) -> Resolver {
-        use oxc_resolver::{ResolveOptions, TsconfigOptions, TsconfigReferences};
-        let tsconfig = tsconfig.and_then(|path| {
-            if path.is_file() {
-                Some(TsconfigOptions { config_file: path, references: TsconfigReferences::Auto })
-            } else {
-                None
-            }
-        });
-
-        Resolver::new(ResolveOptions {
-            extensions: VALID_EXTENSIONS.iter().map(|ext| format!(".{ext}")).collect(),
-            condition_names: vec!["module".into(), "require".into()],
-            tsconfig,
-            ..ResolveOptions::default()
-        })
-    }
-
     fn get_source_type_and_text(
         path: &Path,
         ext: &str,
@@ -212,7 +219,6 @@ impl Runtime {
         };
 
         let sources = PartialLoader::parse(ext, &source_text);
-        let is_processed_by_partial_loader = sources.is_some();
         let sources =
             sources.unwrap_or_else(|| vec![JavaScriptSource::new(&source_text, source_type, 0)]);
 
@@ -221,25 +227,52 @@ impl Runtime {
             return;
         }
 
-        for JavaScriptSource { source_text, source_type, .. } in sources {
+        // The blocks are fixed one by one and put back in place of the original ones,
+        // and the messages are moved to their position in the file.
+        let mut fixed_code = String::new();
+        let mut last_end = 0;
+        let mut errors = vec![];
+        for JavaScriptSource { source_text: block_text, source_type, start } in sources {
             let allocator = Allocator::default();
             let mut messages =
-                self.process_source(path, &allocator, source_text, source_type, true, tx_error);
+                self.process_source(path, &allocator, block_text, source_type, true, tx_error);
 
-            // TODO: Span is wrong, ban this feature for file process by `PartialLoader`.
-            if !is_processed_by_partial_loader && self.linter.options().fix.is_some() {
-                let fix_result = Fixer::new(source_text, messages).fix();
-                fs::write(path, fix_result.fixed_code.as_bytes()).unwrap();
+            if self.linter.options().fix.is_some() {
+                let fix_result = Fixer::new(block_text, messages).fix();
+                fixed_code.push_str(&source_text[last_end..start]);
+                fixed_code.push_str(&fix_result.fixed_code);
+                last_end = start + block_text.len();
                 messages = fix_result.messages;
             }
 
-            if !messages.is_empty() {
-                self.ignore_path(path);
-                let errors = messages.into_iter().map(|m| m.error).collect();
-                let path = path.strip_prefix(&self.cwd).unwrap_or(path);
-                let diagnostics = DiagnosticService::wrap_diagnostics(path, source_text, errors);
-                tx_error.send(Some(diagnostics)).unwrap();
-            }
+            #[allow(clippy::cast_possible_truncation)]
+            let messages = messages
+                .into_iter()
+                .map(|message| message.with_offset(start as u32))
+                .collect::<Vec<_>>();
+            let messages = if let Some(recorded) = &self.recorded_baseline {
+                let key = baseline_path(&self.cwd, path);
+                let mut recorded = recorded.lock().unwrap();
+                recorded.record(&key, &source_text, &messages);
+                recorded.filter(&key, &source_text, messages)
+            } else if let Some(baseline) = &self.baseline {
+                baseline.filter(&baseline_path(&self.cwd, path), &source_text, messages)
+            } else {
+                messages
+            };
+            errors.extend(messages.into_iter().map(|m| m.error));
+        }
+
+        if self.linter.options().fix.is_some() {
+            fixed_code.push_str(&source_text[last_end..]);
+            fs::write(path, fixed_code.as_bytes()).unwrap();
+        }
+
+        if !errors.is_empty() {
+            self.ignore_path(path);
+            let path = path.strip_prefix(&self.cwd).unwrap_or(path);
+            let diagnostics = DiagnosticService::wrap_diagnostics(path, &source_text, errors);
+            tx_error.send(Some(diagnostics)).unwrap();
         }
     }
 
@@ -274,6 +307,7 @@ impl Runtime {
             .with_check_syntax_error(check_syntax_errors)
             .build_module_record(path.to_path_buf(), program);
         let module_record = semantic_builder.module_record();
+        let mut resolved_modules = ResolvedModules::default();
 
         if self.linter.options().import_plugin {
             self.module_map.insert(
@@ -284,30 +318,29 @@ impl Runtime {
 
             // Retrieve all dependency modules from this module.
             let dir = path.parent().unwrap();
-            module_record
+            resolved_modules = module_record
                 .requested_modules
                 .keys()
                 .par_bridge()
                 .map_with(self.resolver.as_ref().unwrap(), |resolver, specifier| {
-                    resolver.resolve(dir, specifier).ok().map(|r| (specifier, r))
+                    (specifier, resolver.resolve(dir, specifier))
+                })
+                .map_with(tx_error, |tx_error, (specifier, resolution)| {
+                    if let Ok(resolution) = &resolution {
+                        let path = resolution.path();
+                        self.process_path(path, tx_error);
+                        if let Some(ModuleState::Resolved(target_module_record)) =
+                            self.module_map.get(path).as_deref()
+                        {
+                            // Append target_module to loaded_modules
+                            module_record
+                                .loaded_modules
+                                .insert(specifier.clone(), Arc::clone(target_module_record));
+                        }
+                    }
+                    (specifier.clone(), ResolvedModule::from(&resolution))
                 })
-                .flatten()
-                .for_each_with(tx_error, |tx_error, (specifier, resolution)| {
-                    let path = resolution.path();
-                    self.process_path(path, tx_error);
-                    let Some(target_module_record_ref) = self.module_map.get(path) else {
-                        return;
-                    };
-                    let ModuleState::Resolved(target_module_record) =
-                        target_module_record_ref.value()
-                    else {
-                        return;
-                    };
-                    // Append target_module to loaded_modules
-                    module_record
-                        .loaded_modules
-                        .insert(specifier.clone(), Arc::clone(target_module_record));
-                });
+                .collect::<ResolvedModules>();
 
             // The thread is blocked here until all dependent modules are resolved.
 
@@ -353,7 +386,11 @@ impl Runtime {
             return semantic_ret.errors.into_iter().map(|err| Message::new(err, None)).collect();
         };
 
-        self.linter.run(path, Rc::new(semantic_ret.semantic))
+        self.linter.run_with_resolved_modules(
+            path,
+            Rc::new(semantic_ret.semantic),
+            Rc::new(resolved_modules),
+        )
     }
 
     fn init_cache_state(&self, path: &Path) -> bool {
@@ -1,16 +1,18 @@
 use std::{
     env,
+    fmt::{self, Write},
     path::{Path, PathBuf},
 };
 
 use oxc_allocator::Allocator;
 use oxc_diagnostics::{DiagnosticService, GraphicalReportHandler, GraphicalTheme, NamedSource};
+use oxc_span::Span;
 use serde::Deserialize;
-use serde_json::Value;
+use serde_json::{json, Value};
 
 use crate::{
     fixer::FixKind, rules::RULES, AllowWarnDeny, Fixer, LintOptions, LintService,
-    LintServiceOptions, Linter, OxlintConfig, RuleEnum, RuleWithSeverity,
+    LintServiceOptions, Linter, Message, OxlintConfig, RuleEnum, RuleWithSeverity,
 };
 
 #[derive(Eq, PartialEq)]
@@ -86,12 +88,204 @@ impl<S: Into<String>> From<(S, S)> for ExpectFix {
     }
 }
 
+/// A diagnostic expected by a [`Case`], with its message, its span, or both.
+#[derive(Debug, Clone, Default)]
+pub struct ExpectedError {
+    message: Option<String>,
+    start: Option<u32>,
+    end: Option<u32>,
+}
+
+impl ExpectedError {
+    fn matches(&self, message: &Message) -> bool {
+        self.message.as_ref().map_or(true, |expected| *expected == message.error.message)
+            && self.start.map_or(true, |start| start == message.start)
+            && self.end.map_or(true, |end| end == message.end)
+    }
+
+    fn found(message: &Message) -> Self {
+        Self {
+            message: Some(message.error.message.to_string()),
+            start: Some(message.start),
+            end: Some(message.end),
+        }
+    }
+}
+
+impl fmt::Display for ExpectedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let position = |offset: Option<u32>| offset.map_or("?".to_string(), |o| o.to_string());
+        write!(f, "{}..{}", position(self.start), position(self.end))?;
+        if let Some(message) = &self.message {
+            write!(f, " {message}")?;
+        }
+        Ok(())
+    }
+}
+
+impl From<&str> for ExpectedError {
+    fn from(message: &str) -> Self {
+        Self { message: Some(message.to_string()), ..Self::default() }
+    }
+}
+
+impl From<Span> for ExpectedError {
+    fn from(span: Span) -> Self {
+        Self { message: None, start: Some(span.start), end: Some(span.end) }
+    }
+}
+
+impl From<(u32, u32)> for ExpectedError {
+    fn from((start, end): (u32, u32)) -> Self {
+        Span::new(start, end).into()
+    }
+}
+
+impl From<(&str, u32, u32)> for ExpectedError {
+    fn from((message, start, end): (&str, u32, u32)) -> Self {
+        Self { message: Some(message.to_string()), ..Span::new(start, end).into() }
+    }
+}
+
+/// A test case with all of its expectations, for [`Tester::from_cases`].
+///
+/// ```ignore
+/// Case::invalid("if (foo) debugger").errors([(9, 17)]).output("if (foo) {}");
+/// Case::valid("let x: any").config(json!([{ "ignore": true }])).path("foo.d.ts");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Case {
+    test_case: TestCase,
+    /// `None` for valid code.
+    errors: Option<Vec<ExpectedError>>,
+    /// The code after all fixes have been applied.
+    output: Option<String>,
+}
+
+impl Case {
+    /// Code without diagnostics.
+    pub fn valid<S: Into<String>>(source: S) -> Self {
+        Self { test_case: TestCase::from(source.into()), ..Self::default() }
+    }
+
+    /// Code with at least one diagnostic, see [`Case::errors`].
+    pub fn invalid<S: Into<String>>(source: S) -> Self {
+        Self { errors: Some(vec![]), ..Self::valid(source) }
+    }
+
+    /// A case of ESLint's `RuleTester`, `{ "code": "...", "options": [...], "errors": [...] }`,
+    /// or only the code of a valid case, to port test suites. `output`, `filename`, `settings`
+    /// and `languageOptions.globals` are kept, errors are compared by their `message`, `line`,
+    /// `column`, `endLine` and `endColumn`, and `messageId`s are ignored.
+    ///
+    /// # Panics
+    ///
+    /// The case is neither a string nor an object with a `code`.
+    pub fn from_eslint(case: &Value) -> Self {
+        if let Some(source) = case.as_str() {
+            return Self::valid(source);
+        }
+        let source = case["code"].as_str().expect("ESLint test case without `code`");
+        let mut eslint_config = json!({});
+        if let Some(settings) = case.get("settings") {
+            eslint_config["settings"] = settings.clone();
+        }
+        if let Some(globals) = case.pointer("/languageOptions/globals") {
+            eslint_config["globals"] = globals.clone();
+        }
+        let mut test = Self::valid(source);
+        test.test_case.rule_config = case.get("options").cloned();
+        test.test_case.eslint_config = eslint_config
+            .as_object()
+            .is_some_and(|config| !config.is_empty())
+            .then_some(eslint_config);
+        test.test_case.path = case["filename"].as_str().map(PathBuf::from);
+        // `null` means that nothing is fixed.
+        test.output = match case.get("output") {
+            Some(Value::String(output)) => Some(output.clone()),
+            Some(Value::Null) => Some(source.to_string()),
+            _ => None,
+        };
+        test.errors = match &case["errors"] {
+            Value::Null => None,
+            Value::Number(count) => {
+                let count = count.as_u64().unwrap_or_default();
+                Some((0..count).map(|_| ExpectedError::default()).collect())
+            }
+            Value::Array(errors) => {
+                Some(errors.iter().map(|error| eslint_error(source, error)).collect())
+            }
+            errors => panic!("ESLint test case with invalid `errors`: {errors}"),
+        };
+        test
+    }
+
+    /// Options of the rule, like ESLint's `options`, e.g. `json!([{ "allow": ["foo"] }])`.
+    pub fn config(mut self, rule_config: Value) -> Self {
+        self.test_case.rule_config = Some(rule_config);
+        self
+    }
+
+    /// The `.oxlintrc` configuration, for `settings`, `env` and `globals`.
+    pub fn eslint_config(mut self, eslint_config: Value) -> Self {
+        self.test_case.eslint_config = Some(eslint_config);
+        self
+    }
+
+    /// The path of the file, whose extension decides how it is parsed. By default the name of the
+    /// rule with a `.tsx` extension.
+    pub fn path<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.test_case.path = Some(path.into());
+        self
+    }
+
+    /// The expected diagnostics, in the order of their spans. Each is given by its message,
+    /// `"..."`, its span, `(start, end)`, or both, `("...", start, end)`.
+    pub fn errors<E: Into<ExpectedError>, I: IntoIterator<Item = E>>(mut self, errors: I) -> Self {
+        self.errors = Some(errors.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// The code after all fixes have been applied, which is the original code if nothing is
+    /// fixed.
+    pub fn output<S: Into<String>>(mut self, output: S) -> Self {
+        self.output = Some(output.into());
+        self
+    }
+}
+
+/// An error of an ESLint test case, whose lines and columns are 1-based and count UTF-16 units.
+/// Without an `endLine` and `endColumn`, only the start is compared.
+fn eslint_error(source: &str, error: &Value) -> ExpectedError {
+    let offset = |line: &str, column: &str| {
+        let line = usize::try_from(error.get(line)?.as_u64()?).ok()?;
+        let column = usize::try_from(error.get(column)?.as_u64()?).ok()?;
+        let line_start =
+            source.split_inclusive('\n').take(line.saturating_sub(1)).map(str::len).sum::<usize>();
+        let mut units = 0;
+        let offset = source[line_start..]
+            .char_indices()
+            .find(|(_, c)| {
+                units += c.len_utf16();
+                units >= column
+            })
+            .map_or(source.len(), |(offset, _)| line_start + offset);
+        u32::try_from(offset).ok()
+    };
+    ExpectedError {
+        message: error.get("message").and_then(Value::as_str).map(ToString::to_string),
+        start: offset("line", "column"),
+        end: offset("endLine", "endColumn"),
+    }
+}
+
 pub struct Tester {
     rule_name: &'static str,
     rule_path: PathBuf,
     expect_pass: Vec<TestCase>,
     expect_fail: Vec<TestCase>,
     expect_fix: Vec<ExpectFix>,
+    cases: Vec<Case>,
     snapshot: String,
     /// Suffix added to end of snapshot name.
     ///
@@ -123,6 +317,7 @@ impl Tester {
             expect_pass,
             expect_fail,
             expect_fix: vec![],
+            cases: vec![],
             snapshot: String::new(),
             snapshot_suffix: None,
             current_working_directory,
@@ -135,6 +330,22 @@ impl Tester {
         }
     }
 
+    /// Test `cases`, each with all of its expectations, instead of lists of passing, failing and
+    /// fixed code.
+    ///
+    /// ```ignore
+    /// let cases = vec![
+    ///     Case::valid("var test = { debugger: 1 };"),
+    ///     Case::invalid("if (foo) debugger").errors([(9, 17)]).output("if (foo) {}"),
+    /// ];
+    /// Tester::from_cases(NoDebugger::NAME, cases).test_and_snapshot();
+    /// ```
+    pub fn from_cases(rule_name: &'static str, cases: Vec<Case>) -> Self {
+        let mut tester = Self::new::<TestCase>(rule_name, vec![], vec![]);
+        tester.cases = cases;
+        tester
+    }
+
     /// Change the path
     pub fn change_rule_path(mut self, path: &str) -> Self {
         self.rule_path = self.current_working_directory.join(path);
@@ -213,6 +424,7 @@ impl Tester {
         self.test_pass();
         self.test_fail();
         self.test_fix();
+        self.test_cases();
     }
 
     pub fn test_and_snapshot(&mut self) {
@@ -266,6 +478,61 @@ impl Tester {
         }
     }
 
+    fn test_cases(&mut self) {
+        for Case { test_case, errors, output } in self.cases.clone() {
+            let TestCase { source, rule_config, eslint_config, path } = test_case;
+            let allocator = Allocator::default();
+            let mut messages = self.lint(
+                &allocator,
+                &source,
+                rule_config.clone(),
+                &eslint_config,
+                path.clone(),
+                false,
+            );
+            messages.sort_by_key(|message| (message.start, message.end));
+            let is_valid = errors.is_none();
+            let expected = errors.unwrap_or_default();
+            let is_expected = if expected.is_empty() {
+                // Valid code has no diagnostics, and invalid code without expected errors has any.
+                messages.is_empty() == is_valid
+            } else {
+                messages.len() == expected.len()
+                    && expected.iter().zip(&messages).all(|(error, message)| error.matches(message))
+            };
+            if !is_expected {
+                let mut failure =
+                    format!("{}: unexpected diagnostics for\n{source}\n", self.rule_name);
+                if expected.is_empty() {
+                    let kind = if is_valid { "no" } else { "some" };
+                    writeln!(failure, "expected {kind} diagnostics").unwrap();
+                } else {
+                    writeln!(failure, "expected:").unwrap();
+                    for error in &expected {
+                        writeln!(failure, "  {error}").unwrap();
+                    }
+                }
+                writeln!(failure, "found:").unwrap();
+                for message in &messages {
+                    writeln!(failure, "  {}", ExpectedError::found(message)).unwrap();
+                }
+                panic!("{failure}");
+            }
+            self.render(&source, messages);
+
+            let Some(output) = output else { continue };
+            let fixed = match self.run(&source, rule_config, &eslint_config, path, true) {
+                TestResult::Fixed(fixed) => fixed,
+                _ => source.clone(),
+            };
+            assert_eq!(
+                output, fixed,
+                "{}: expected\n{source}\nto be fixed into\n{output}",
+                self.rule_name
+            );
+        }
+    }
+
     fn run(
         &mut self,
         source_text: &str,
@@ -275,9 +542,38 @@ impl Tester {
         is_fix: bool,
     ) -> TestResult {
         let allocator = Allocator::default();
-        let rule = self.find_rule().read_json(rule_config.unwrap_or_default());
+        let result = self.lint(&allocator, source_text, rule_config, eslint_config, path, is_fix);
+
+        if result.is_empty() {
+            return TestResult::Passed;
+        }
+
+        if is_fix {
+            let fix_result = Fixer::new(source_text, result).fix();
+            return TestResult::Fixed(fix_result.fixed_code.to_string());
+        }
+
+        self.render(source_text, result);
+        TestResult::Failed
+    }
+
+    fn lint<'a>(
+        &self,
+        allocator: &'a Allocator,
+        source_text: &'a str,
+        rule_config: Option<Value>,
+        eslint_config: &Option<Value>,
+        path: Option<PathBuf>,
+        is_fix: bool,
+    ) -> Vec<Message<'a>> {
+        let rule_config = rule_config.unwrap_or_default();
+        let errors = self.find_rule().configuration_errors(&rule_config);
+        assert!(errors.is_empty(), "Invalid configuration for {}: {errors:?}", self.rule_name);
+        let rule = self.find_rule().read_json(rule_config);
         let options = LintOptions::default()
-            .with_fix(is_fix.then_some(FixKind::DangerousFix).unwrap_or_default())
+            .with_fix(is_fix.then_some(FixKind::SafeFix).unwrap_or_default())
+            .with_fix_suggestions(is_fix)
+            .with_fix_dangerously(is_fix)
             .with_import_plugin(self.import_plugin)
             .with_jest_plugin(self.jest_plugin)
             .with_vitest_plugin(self.vitest_plugin)
@@ -302,21 +598,21 @@ impl Tester {
 
         let cwd = self.current_working_directory.clone();
         let paths = vec![path_to_lint.into_boxed_path()];
-        let options = LintServiceOptions { cwd, paths, tsconfig: None };
+        let options = LintServiceOptions {
+            cwd,
+            paths,
+            tsconfig: None,
+            baseline: None,
+            record_baseline: false,
+        };
         let lint_service = LintService::from_linter(linter, options);
         let diagnostic_service = DiagnosticService::default();
         let tx_error = diagnostic_service.sender();
-        let result = lint_service.run_source(&allocator, source_text, false, tx_error);
-
-        if result.is_empty() {
-            return TestResult::Passed;
-        }
-
-        if is_fix {
-            let fix_result = Fixer::new(source_text, result).fix();
-            return TestResult::Fixed(fix_result.fixed_code.to_string());
-        }
+        lint_service.run_source(allocator, source_text, false, tx_error)
+    }
 
+    /// Adds the diagnostics to the snapshot.
+    fn render(&mut self, source_text: &str, result: Vec<Message>) {
         let diagnostic_path = if self.import_plugin {
             self.rule_path.strip_prefix(&self.current_working_directory).unwrap()
         } else {
@@ -332,7 +628,6 @@ impl Tester {
             ));
             handler.render_report(&mut self.snapshot, diagnostic.as_ref()).unwrap();
         }
-        TestResult::Failed
     }
 
     fn find_rule(&self) -> &RuleEnum {
@@ -0,0 +1,328 @@
+//! Parallel discovery of the files to lint, for the CLI and for embedders such as editor servers
+//! and build plugins.
+
+use std::{
+    ffi::OsString,
+    path::{Path, PathBuf},
+    sync::mpsc,
+};
+
+use ignore::{
+    gitignore::{Gitignore, GitignoreBuilder},
+    overrides::OverrideBuilder,
+    DirEntry,
+};
+use oxc_span::VALID_EXTENSIONS;
+
+use crate::partial_loader::LINT_PARTIAL_LOADER_EXT;
+
+/// What [`Walk`] does with symbolic links.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Symlinks {
+    /// Symbolic links to directories are not followed. Symbolic links to files are linted.
+    #[default]
+    Skip,
+    /// Symbolic links are followed, which is slow.
+    Follow,
+}
+
+/// Options of [`Walk`].
+#[derive(Debug, Clone)]
+pub struct WalkOptions {
+    /// Ignore files which are read in every directory, with the syntax of `.gitignore`.
+    ///
+    /// Default `.eslintignore` and `.oxlintignore`
+    pub ignore_files: Vec<OsString>,
+
+    /// Patterns of files to ignore in addition to the ignore files, with the syntax of
+    /// `.gitignore`.
+    ///
+    /// Default `[]`
+    pub ignore_patterns: Vec<String>,
+
+    /// Respect `.gitignore` files of git repositories.
+    ///
+    /// Default `true`
+    pub gitignore: bool,
+
+    /// Disable `ignore_files` and `ignore_patterns`.
+    ///
+    /// Default `false`
+    pub no_ignore: bool,
+
+    /// Default `Symlinks::Skip`
+    pub symlinks: Symlinks,
+
+    /// Extensions of the files to find. Minified files, `foo.min.js`, are never found.
+    ///
+    /// Default the extensions the linter parses, including those of the partial loaders, such
+    /// as `vue`.
+    pub extensions: Vec<&'static str>,
+}
+
+impl Default for WalkOptions {
+    fn default() -> Self {
+        Self {
+            ignore_files: vec![".eslintignore".into(), ".oxlintignore".into()],
+            ignore_patterns: vec![],
+            gitignore: true,
+            no_ignore: false,
+            symlinks: Symlinks::Skip,
+            extensions: VALID_EXTENSIONS.iter().chain(LINT_PARTIAL_LOADER_EXT).copied().collect(),
+        }
+    }
+}
+
+impl WalkOptions {
+    /// Removes the paths of files matched by the ignore files in the current directory or by
+    /// `ignore_patterns`.
+    ///
+    /// [`Walk`] does not filter the paths it starts from, but most users lint automatically and
+    /// pass the changed files explicitly, and expect them to be ignored like the files which are
+    /// found. Directories are kept.
+    pub fn retain_unignored(&self, paths: &mut Vec<PathBuf>) {
+        if self.no_ignore {
+            return;
+        }
+        let mut builder = GitignoreBuilder::new("");
+        for ignore_file in &self.ignore_files {
+            // Ignore files which do not exist are fine.
+            let _ = builder.add(ignore_file);
+        }
+        for pattern in &self.ignore_patterns {
+            let _ = builder.add_line(None, pattern);
+        }
+        let ignore = builder.build().unwrap_or_else(|_| Gitignore::empty());
+        paths.retain(|path| path.is_dir() || !ignore.matched(path, false).is_ignore());
+    }
+}
+
+/// Finds the files to lint in parallel, in directories and their subdirectories.
+pub struct Walk {
+    inner: ignore::WalkParallel,
+    symlinks: Symlinks,
+    extensions: Vec<&'static str>,
+}
+
+struct WalkBuilder {
+    sender: mpsc::Sender<Vec<Box<Path>>>,
+    symlinks: Symlinks,
+    extensions: Vec<&'static str>,
+}
+
+impl<'s> ignore::ParallelVisitorBuilder<'s> for WalkBuilder {
+    fn build(&mut self) -> Box<dyn ignore::ParallelVisitor + 's> {
+        Box::new(WalkCollector {
+            paths: vec![],
+            sender: self.sender.clone(),
+            symlinks: self.symlinks,
+            extensions: self.extensions.clone(),
+        })
+    }
+}
+
+struct WalkCollector {
+    paths: Vec<Box<Path>>,
+    sender: mpsc::Sender<Vec<Box<Path>>>,
+    symlinks: Symlinks,
+    extensions: Vec<&'static str>,
+}
+
+impl Drop for WalkCollector {
+    fn drop(&mut self) {
+        let paths = std::mem::take(&mut self.paths);
+        self.sender.send(paths).unwrap();
+    }
+}
+
+impl ignore::ParallelVisitor for WalkCollector {
+    fn visit(&mut self, entry: Result<ignore::DirEntry, ignore::Error>) -> ignore::WalkState {
+        match entry {
+            Ok(entry) => {
+                if self.symlinks == Symlinks::Skip
+                    && entry.path_is_symlink()
+                    && entry.path().is_dir()
+                {
+                    return ignore::WalkState::Skip;
+                }
+                if Walk::is_wanted_entry(&entry, &self.extensions) {
+                    self.paths.push(entry.path().to_path_buf().into_boxed_path());
+                }
+                ignore::WalkState::Continue
+            }
+            Err(_err) => ignore::WalkState::Skip,
+        }
+    }
+}
+
+impl Walk {
+    /// Will not canonicalize paths.
+    ///
+    /// # Panics
+    ///
+    /// * `paths` is empty
+    /// * an ignore pattern is invalid
+    pub fn new(paths: &[PathBuf], options: &WalkOptions) -> Self {
+        let (first, rest) = paths.split_first().expect("At least one path must be provided");
+        let mut inner = ignore::WalkBuilder::new(first);
+        for path in rest {
+            inner.add(path);
+        }
+
+        inner.git_ignore(options.gitignore).git_exclude(options.gitignore);
+        if !options.no_ignore {
+            for ignore_file in &options.ignore_files {
+                inner.add_custom_ignore_filename(ignore_file);
+            }
+
+            if !options.ignore_patterns.is_empty() {
+                let mut override_builder = OverrideBuilder::new(Path::new("/"));
+                for pattern in &options.ignore_patterns {
+                    // Meaning of ignore pattern is reversed
+                    // <https://docs.rs/ignore/latest/ignore/overrides/struct.OverrideBuilder.html#method.add>
+                    let pattern = format!("!{pattern}");
+                    override_builder.add(&pattern).unwrap();
+                }
+                let overrides = override_builder.build().unwrap();
+                inner.overrides(overrides);
+            }
+        }
+        // Following symlinks is off by default because:
+        // * following symlinks is a really slow syscall
+        // * it is super rare to have symlinked source code
+        let inner = inner
+            .ignore(false)
+            .git_global(false)
+            .follow_links(options.symlinks == Symlinks::Follow)
+            .build_parallel();
+        Self { inner, symlinks: options.symlinks, extensions: options.extensions.clone() }
+    }
+
+    /// The paths of the files found, in no particular order.
+    pub fn paths(self) -> Vec<Box<Path>> {
+        let (sender, receiver) = mpsc::channel::<Vec<Box<Path>>>();
+        let mut builder =
+            WalkBuilder { sender, symlinks: self.symlinks, extensions: self.extensions };
+        self.inner.visit(&mut builder);
+        drop(builder);
+        receiver.into_iter().flatten().collect()
+    }
+
+    fn is_wanted_entry(dir_entry: &DirEntry, extensions: &[&'static str]) -> bool {
+        let Some(file_type) = dir_entry.file_type() else { return false };
+        if file_type.is_dir() {
+            return false;
+        }
+        let Some(file_name) = dir_entry.path().file_name() else { return false };
+        if [".min.", "-min.", "_min."].iter().any(|e| file_name.to_string_lossy().contains(e)) {
+            return false;
+        }
+        let Some(extension) = dir_entry.path().extension() else { return false };
+        let extension = extension.to_string_lossy();
+        extensions.contains(&extension.as_ref())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{env, fs, path::Path};
+
+    use super::{Symlinks, Walk, WalkOptions};
+
+    fn walk(dir: &Path, options: &WalkOptions) -> Vec<String> {
+        let mut paths = Walk::new(&[dir.to_path_buf()], options)
+            .paths()
+            .into_iter()
+            .map(|path| {
+                let path = path.strip_prefix(dir).unwrap();
+                path.components()
+                    .map(|component| component.as_os_str().to_string_lossy())
+                    .collect::<Vec<_>>()
+                    .join("/")
+            })
+            .collect::<Vec<_>>();
+        paths.sort();
+        paths
+    }
+
+    /// `a.js`, `b.js`, `nested/c.js` and an `.oxlintignore` which ignores `b.js`.
+    fn fixture() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("a.js"), "").unwrap();
+        fs::write(dir.path().join("b.js"), "").unwrap();
+        fs::create_dir(dir.path().join("nested")).unwrap();
+        fs::write(dir.path().join("nested/c.js"), "").unwrap();
+        fs::write(dir.path().join(".oxlintignore"), "b.js\n").unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_walk_with_extensions() {
+        let fixture = env::current_dir().unwrap().join("fixtures/walk_dir");
+        let options = WalkOptions { extensions: vec!["js", "vue"], ..WalkOptions::default() };
+        assert_eq!(walk(&fixture, &options), ["bar.vue", "foo.js"]);
+    }
+
+    #[test]
+    fn test_walk_ignore_files() {
+        let dir = fixture();
+        assert_eq!(walk(dir.path(), &WalkOptions::default()), ["a.js", "nested/c.js"]);
+        let options = WalkOptions { ignore_files: vec![], ..WalkOptions::default() };
+        assert_eq!(walk(dir.path(), &options), ["a.js", "b.js", "nested/c.js"]);
+        let options = WalkOptions { no_ignore: true, ..WalkOptions::default() };
+        assert_eq!(walk(dir.path(), &options), ["a.js", "b.js", "nested/c.js"]);
+    }
+
+    #[test]
+    fn test_walk_ignore_patterns() {
+        let dir = fixture();
+        let options =
+            WalkOptions { ignore_patterns: vec!["nested".into()], ..WalkOptions::default() };
+        assert_eq!(walk(dir.path(), &options), ["a.js"]);
+        let options = WalkOptions {
+            ignore_patterns: vec!["nested".into()],
+            no_ignore: true,
+            ..WalkOptions::default()
+        };
+        assert_eq!(walk(dir.path(), &options), ["a.js", "b.js", "nested/c.js"]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_walk_symlinks() {
+        let dir = fixture();
+        std::os::unix::fs::symlink(dir.path().join("a.js"), dir.path().join("link.js")).unwrap();
+        std::os::unix::fs::symlink(dir.path().join("nested"), dir.path().join("linked")).unwrap();
+        assert_eq!(walk(dir.path(), &WalkOptions::default()), ["a.js", "link.js", "nested/c.js"]);
+        let options = WalkOptions { symlinks: Symlinks::Follow, ..WalkOptions::default() };
+        assert_eq!(walk(dir.path(), &options), ["a.js", "link.js", "linked/c.js", "nested/c.js"]);
+    }
+
+    #[test]
+    fn test_retain_unignored() {
+        let dir = fixture();
+        let mut paths =
+            vec![dir.path().join("a.js"), dir.path().join("b.js"), dir.path().join("nested")];
+        let options = WalkOptions {
+            ignore_files: vec![dir.path().join(".oxlintignore").into()],
+            ..WalkOptions::default()
+        };
+        options.retain_unignored(&mut paths);
+        assert_eq!(paths, [dir.path().join("a.js"), dir.path().join("nested")]);
+
+        let mut paths = vec![dir.path().join("a.js"), dir.path().join("nested/c.js")];
+        let options =
+            WalkOptions { ignore_patterns: vec!["c.js".into()], ..WalkOptions::default() };
+        options.retain_unignored(&mut paths);
+        assert_eq!(paths, [dir.path().join("a.js")]);
+
+        let mut paths = vec![dir.path().join("b.js")];
+        let options = WalkOptions {
+            ignore_patterns: vec!["b.js".into()],
+            no_ignore: true,
+            ..WalkOptions::default()
+        };
+        options.retain_unignored(&mut paths);
+        assert_eq!(paths, [dir.path().join("b.js")]);
+    }
+}
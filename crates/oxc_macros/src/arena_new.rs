@@ -0,0 +1,117 @@
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::{Data, DeriveInput, Error, Fields, Lifetime, Result};
+
+struct ArenaField<'a> {
+    ident: &'a syn::Ident,
+    ty: &'a syn::Type,
+    /// Filled with `Default::default()` instead of an argument, `#[arena_new(default)]`.
+    default: bool,
+}
+
+fn parse_fields(input: &DeriveInput) -> Result<Vec<ArenaField<'_>>> {
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new_spanned(input, "`ArenaNew` can only be derived for structs"));
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(Error::new_spanned(
+            &data.fields,
+            "`ArenaNew` can only be derived for structs with named fields",
+        ));
+    };
+
+    fields
+        .named
+        .iter()
+        .map(|field| {
+            let mut default = false;
+            for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("arena_new")) {
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("default") {
+                        default = true;
+                        Ok(())
+                    } else {
+                        Err(meta.error("expected `default`"))
+                    }
+                })?;
+            }
+            Ok(ArenaField { ident: field.ident.as_ref().unwrap(), ty: &field.ty, default })
+        })
+        .collect()
+}
+
+/// The lifetime of the arena, the first lifetime parameter of the struct.
+fn arena_lifetime(input: &DeriveInput) -> Result<&Lifetime> {
+    input.generics.lifetimes().next().map(|param| &param.lifetime).ok_or_else(|| {
+        Error::new_spanned(&input.ident, "`ArenaNew` requires a lifetime parameter for the arena")
+    })
+}
+
+pub fn derive_arena_new(input: &DeriveInput) -> TokenStream {
+    let (fields, lifetime) =
+        match parse_fields(input).and_then(|fields| Ok((fields, arena_lifetime(input)?))) {
+            Ok(result) => result,
+            Err(err) => return err.to_compile_error(),
+        };
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let arguments = fields.iter().filter(|field| !field.default).collect::<Vec<_>>();
+    let params = (0..arguments.len()).map(|i| format_ident!("T{i}")).collect::<Vec<_>>();
+    // Hygienic, so that they do not clash with arguments named after the fields.
+    let alloc = Ident::new("alloc", Span::mixed_site());
+    let value = Ident::new("value", Span::mixed_site());
+    let argument_idents = arguments.iter().map(|field| field.ident).collect::<Vec<_>>();
+    let argument_types = arguments.iter().map(|field| field.ty).collect::<Vec<_>>();
+    let values = fields.iter().map(|field| {
+        let ident = field.ident;
+        if field.default {
+            quote! { #ident: Default::default() }
+        } else {
+            quote! { #ident: ::oxc_allocator::IntoIn::into_in(#ident, #alloc) }
+        }
+    });
+    let values = quote! { #(#values),* };
+
+    // `FromIn` from a tuple of the arguments, with their types as more parameters of the impl.
+    let generic_params = input.generics.params.iter();
+    let predicates = where_clause.into_iter().flat_map(|where_clause| &where_clause.predicates);
+    let tuple_values = fields.iter().map(|field| {
+        let ident = field.ident;
+        if let Some(index) = arguments.iter().position(|argument| argument.ident == ident) {
+            let index = syn::Index::from(index);
+            quote! { #ident: ::oxc_allocator::IntoIn::into_in(#value.#index, #alloc) }
+        } else {
+            quote! { #ident: Default::default() }
+        }
+    });
+
+    quote! {
+        impl #impl_generics #name #ty_generics #where_clause {
+            /// Allocates a new node in the arena, converting each argument with `IntoIn`.
+            #[inline]
+            #[allow(clippy::too_many_arguments)]
+            pub fn new_in<#(#params),*>(
+                #alloc: &#lifetime ::oxc_allocator::Allocator,
+                #(#argument_idents: #params),*
+            ) -> ::oxc_allocator::Box<#lifetime, Self>
+            where
+                #(#params: ::oxc_allocator::IntoIn<#lifetime, #argument_types>),*
+            {
+                ::oxc_allocator::Box::new_in(Self { #values }, #alloc)
+            }
+        }
+
+        impl<#(#generic_params,)* #(#params),*> ::oxc_allocator::FromIn<#lifetime, (#(#params,)*)>
+            for #name #ty_generics
+        where
+            #(#predicates,)*
+            #(#params: ::oxc_allocator::IntoIn<#lifetime, #argument_types>),*
+        {
+            #[inline]
+            fn from_in(#value: (#(#params,)*), #alloc: &#lifetime ::oxc_allocator::Allocator) -> Self {
+                Self { #(#tuple_values),* }
+            }
+        }
+    }
+}
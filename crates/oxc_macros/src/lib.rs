@@ -1,8 +1,10 @@
 use proc_macro::TokenStream;
-use syn::parse_macro_input;
+use syn::{parse_macro_input, DeriveInput};
 
+mod arena_new;
 mod declare_all_lint_rules;
 mod declare_oxc_lint;
+mod rule_config;
 
 /// Macro used to declare an oxc lint rule
 ///
@@ -56,3 +58,75 @@ pub fn declare_all_lint_rules(input: TokenStream) -> TokenStream {
     let metadata = parse_macro_input!(input as declare_all_lint_rules::AllLintRulesMeta);
     declare_all_lint_rules::declare_all_lint_rules(metadata)
 }
+
+/// Derives `Default` and `RuleConfig` for the options of a lint rule.
+///
+/// Options are read from the first element of the rule's ESLint configuration,
+/// e.g. `["error", { "allowEmptyCatch": true }]`, with each field keyed by its camelCased name.
+/// Missing and invalid options keep their default value, and invalid or unknown options are
+/// returned as `RuleConfigError`s with the JSON pointer of the offending value, which the linter
+/// reports at that value in the configuration file.
+///
+/// # Attributes
+///
+/// * `#[rule_config(default = <expr>)]`: default value of the field, `Default::default()` if omitted.
+/// * `#[rule_config(rename = "name")]`: key of the field in the options object.
+///
+/// # Example
+///
+/// ```ignore
+/// #[derive(Debug, Clone, RuleConfig)]
+/// pub struct NoSelfAssign {
+///     #[rule_config(default = true)]
+///     props: bool,
+/// }
+///
+/// impl Rule for NoSelfAssign {
+///     fn from_configuration(value: serde_json::Value) -> Self {
+///         Self::parse_configuration(&value).0
+///     }
+///
+///     fn configuration_errors(value: &serde_json::Value) -> Vec<RuleConfigError> {
+///         Self::parse_configuration(value).1
+///     }
+/// }
+/// ```
+#[proc_macro_derive(RuleConfig, attributes(rule_config))]
+pub fn derive_rule_config(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    rule_config::derive_rule_config(&input).into()
+}
+
+/// Derives constructors of an arena allocated struct, which convert each field with `IntoIn`.
+///
+/// * `T::new_in(alloc, fields...) -> Box<'a, T>` allocates the struct in the arena.
+/// * `FromIn<'a, (fields...)>` builds the struct from a tuple of its fields, so
+///   `(span, name).into_in(alloc)` works wherever a `T` is expected.
+///
+/// Fields are passed in declaration order. The first lifetime parameter of the struct is the
+/// lifetime of the arena, and the crate using the derive must depend on `oxc_allocator`.
+///
+/// # Attributes
+///
+/// * `#[arena_new(default)]`: the field is not passed, and set to `Default::default()`, e.g. for
+///   the `Cell`s which semantic analysis fills in later.
+///
+/// # Example
+///
+/// ```ignore
+/// #[derive(Debug, ArenaNew)]
+/// pub struct IdentifierReference<'a> {
+///     pub span: Span,
+///     pub name: Atom<'a>,
+///     #[arena_new(default)]
+///     pub reference_id: Cell<Option<ReferenceId>>,
+/// }
+///
+/// let ident = IdentifierReference::new_in(alloc, SPAN, "foo");
+/// let ident: IdentifierReference = (SPAN, "foo").into_in(alloc);
+/// ```
+#[proc_macro_derive(ArenaNew, attributes(arena_new))]
+pub fn derive_arena_new(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    arena_new::derive_arena_new(&input).into()
+}
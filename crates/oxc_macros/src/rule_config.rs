@@ -0,0 +1,119 @@
+use convert_case::{Case, Casing};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Error, Expr, Fields, LitStr, Result};
+
+struct ConfigField<'a> {
+    ident: &'a syn::Ident,
+    ty: &'a syn::Type,
+    /// Key in the options object, the camelCased field name unless renamed.
+    key: String,
+    default: Option<Expr>,
+}
+
+fn parse_fields(input: &DeriveInput) -> Result<Vec<ConfigField<'_>>> {
+    let Data::Struct(data) = &input.data else {
+        return Err(Error::new_spanned(input, "`RuleConfig` can only be derived for structs"));
+    };
+    let fields = match &data.fields {
+        Fields::Named(fields) => &fields.named,
+        Fields::Unit => return Ok(vec![]),
+        Fields::Unnamed(_) => {
+            return Err(Error::new_spanned(
+                &data.fields,
+                "`RuleConfig` can only be derived for structs with named fields",
+            ))
+        }
+    };
+
+    fields
+        .iter()
+        .map(|field| {
+            let ident = field.ident.as_ref().unwrap();
+            let mut key = ident.to_string().to_case(Case::Camel);
+            let mut default = None;
+            for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("rule_config")) {
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("rename") {
+                        key = meta.value()?.parse::<LitStr>()?.value();
+                        Ok(())
+                    } else if meta.path.is_ident("default") {
+                        default = Some(meta.value()?.parse::<Expr>()?);
+                        Ok(())
+                    } else {
+                        Err(meta.error("expected `rename` or `default`"))
+                    }
+                })?;
+            }
+            Ok(ConfigField { ident, ty: &field.ty, key, default })
+        })
+        .collect()
+}
+
+pub fn derive_rule_config(input: &DeriveInput) -> TokenStream {
+    let fields = match parse_fields(input) {
+        Ok(fields) => fields,
+        Err(err) => return err.to_compile_error(),
+    };
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let idents = fields.iter().map(|field| &field.ident).collect::<Vec<_>>();
+    let defaults = fields.iter().map(|field| {
+        field
+            .default
+            .as_ref()
+            .map_or_else(|| quote! { Default::default() }, |default| quote! { #default })
+    });
+    let keys = fields.iter().map(|field| &field.key).collect::<Vec<_>>();
+    let types = fields.iter().map(|field| &field.ty);
+
+    quote! {
+        impl #impl_generics Default for #name #ty_generics #where_clause {
+            fn default() -> Self {
+                Self { #(#idents: #defaults),* }
+            }
+        }
+
+        impl #impl_generics crate::rule::RuleConfig for #name #ty_generics #where_clause {
+            fn parse_configuration(
+                value: &serde_json::Value,
+            ) -> (Self, Vec<crate::rule::RuleConfigError>) {
+                let mut config = Self::default();
+                let mut errors = vec![];
+                let options = match value {
+                    serde_json::Value::Null => return (config, errors),
+                    serde_json::Value::Array(array) => match array.first() {
+                        Some(options) => options,
+                        None => return (config, errors),
+                    },
+                    _ => {
+                        errors.push(crate::rule::RuleConfigError::new("", "expected an array"));
+                        return (config, errors);
+                    }
+                };
+                let Some(options) = options.as_object() else {
+                    errors.push(crate::rule::RuleConfigError::new("/0", "expected an object"));
+                    return (config, errors);
+                };
+                for (key, value) in options {
+                    match key.as_str() {
+                        #(
+                            #keys => match serde_json::from_value::<#types>(value.clone()) {
+                                Ok(value) => config.#idents = value,
+                                Err(err) => errors.push(
+                                    crate::rule::RuleConfigError::option(key, err.to_string()),
+                                ),
+                            },
+                        )*
+                        _ => errors.push(crate::rule::RuleConfigError::option(
+                            key,
+                            format!("unknown option `{key}`"),
+                        )),
+                    }
+                }
+                (config, errors)
+            }
+        }
+    }
+}
@@ -0,0 +1,53 @@
+use std::cell::Cell;
+
+use oxc_allocator::{Allocator, Box, IntoIn, Vec};
+use oxc_macros::ArenaNew;
+use oxc_span::{Atom, Span, SPAN};
+
+#[derive(Debug, ArenaNew)]
+struct IdentifierReference<'a> {
+    span: Span,
+    name: Atom<'a>,
+    #[arena_new(default)]
+    reference_id: Cell<Option<u32>>,
+}
+
+#[derive(Debug, ArenaNew)]
+struct Sequence<'a, T>
+where
+    T: Copy,
+{
+    span: Span,
+    items: Vec<'a, T>,
+    last: Option<Box<'a, T>>,
+}
+
+#[test]
+fn new_in() {
+    let allocator = Allocator::default();
+    let ident = IdentifierReference::new_in(&allocator, Span::new(0, 3), "foo");
+    assert_eq!(ident.span, Span::new(0, 3));
+    assert_eq!(ident.name, "foo");
+    assert_eq!(ident.reference_id.get(), None);
+
+    // `Option<T>` is converted to `Option<Box<'a, T>>`.
+    let items = Vec::from_iter_in([1, 2], &allocator);
+    let sequence = Sequence::new_in(&allocator, SPAN, items, Some(2));
+    assert_eq!(sequence.span, SPAN);
+    assert_eq!(sequence.items.as_slice(), [1, 2]);
+    assert_eq!(sequence.last.as_deref(), Some(&2));
+}
+
+#[test]
+fn from_tuple() {
+    let allocator = Allocator::default();
+    let name = String::from("bar");
+    let ident: IdentifierReference = (SPAN, name).into_in(&allocator);
+    assert_eq!(ident.name, "bar");
+    assert_eq!(ident.reference_id.get(), None);
+
+    let sequence: Sequence<char> =
+        (SPAN, Vec::new_in(&allocator), None::<char>).into_in(&allocator);
+    assert!(sequence.items.is_empty());
+    assert!(sequence.last.is_none());
+}
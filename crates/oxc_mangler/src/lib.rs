@@ -3,12 +3,17 @@ use oxc_ast::ast::Program;
 use oxc_index::{index_vec, Idx, IndexVec};
 use oxc_semantic::{ReferenceId, SemanticBuilder, SymbolId, SymbolTable};
 use oxc_span::CompactStr;
+use oxc_syntax::keyword::{is_global_object, is_reserved_keyword};
+use rustc_hash::{FxHashMap, FxHashSet};
 
 type Slot = usize;
 
 #[derive(Debug)]
 pub struct Mangler {
     symbol_table: SymbolTable,
+    /// Unresolved references renamed by [`ManglerBuilder::cached_names`].
+    unresolved_names: FxHashMap<ReferenceId, CompactStr>,
+    top_level_names: Vec<(CompactStr, CompactStr)>,
 }
 
 impl Mangler {
@@ -17,8 +22,16 @@ impl Mangler {
     }
 
     pub fn get_reference_name(&self, reference_id: ReferenceId) -> Option<&str> {
-        let symbol_id = self.symbol_table.get_reference(reference_id).symbol_id()?;
-        Some(self.symbol_table.get_name(symbol_id))
+        match self.symbol_table.get_reference(reference_id).symbol_id() {
+            Some(symbol_id) => Some(self.symbol_table.get_name(symbol_id)),
+            None => self.unresolved_names.get(&reference_id).map(CompactStr::as_str),
+        }
+    }
+
+    /// The original and mangled names of the renamed top-level bindings, when the top-level
+    /// bindings of a script are renamed, see [`ManglerBuilder::cached_names`].
+    pub fn top_level_names(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.top_level_names.iter().map(|(name, mangled)| (name.as_str(), mangled.as_str()))
     }
 }
 
@@ -66,6 +79,10 @@ impl Mangler {
 #[derive(Debug, Default)]
 pub struct ManglerBuilder {
     debug: bool,
+    top_level: bool,
+    cached_names: FxHashMap<CompactStr, CompactStr>,
+    reserved: FxHashSet<CompactStr>,
+    globals: FxHashSet<CompactStr>,
 }
 
 impl ManglerBuilder {
@@ -75,6 +92,52 @@ impl ManglerBuilder {
         self
     }
 
+    /// Also rename the top-level bindings of scripts.
+    ///
+    /// Top-level bindings of scripts are globals which other scripts can access, so they are kept
+    /// by default. Top-level bindings of modules are always renamed, except for exports.
+    #[must_use]
+    pub fn top_level(mut self, yes: bool) -> Self {
+        self.top_level = yes;
+        self
+    }
+
+    /// Mangled names of top-level bindings from previous runs, by original name, so that scripts
+    /// which share globals, such as the chunks of a build, agree on their names.
+    ///
+    /// Only used when the top-level bindings of a script are renamed, see
+    /// [`ManglerBuilder::top_level`]. Top-level bindings with a cached name get that name, and
+    /// unresolved references with a cached name, which refer to the globals of other scripts,
+    /// are renamed as well. No other binding is given a cached name.
+    /// [`Mangler::top_level_names`] has the names to add to the cache afterwards.
+    #[must_use]
+    pub fn cached_names<I: IntoIterator<Item = (CompactStr, CompactStr)>>(
+        mut self,
+        names: I,
+    ) -> Self {
+        self.cached_names = names.into_iter().collect();
+        self
+    }
+
+    /// Names which are neither mangled nor used as mangled names, for bindings which code
+    /// outside of the program refers to by name, like terser's `mangle.reserved`.
+    #[must_use]
+    pub fn reserved<I: IntoIterator<Item = CompactStr>>(mut self, names: I) -> Self {
+        self.reserved = names.into_iter().collect();
+        self
+    }
+
+    /// Globals of the environment the output runs in, which are not used as mangled names.
+    ///
+    /// Mangled names never shadow the globals the program refers to. Other globals may still be
+    /// looked up by name, by code in `with` statements, direct `eval` or other scripts sharing
+    /// the global scope, or through another frame's realm, so they are avoided as well.
+    #[must_use]
+    pub fn globals<I: IntoIterator<Item = CompactStr>>(mut self, names: I) -> Self {
+        self.globals = names.into_iter().collect();
+        self
+    }
+
     #[must_use]
     pub fn build<'a>(self, program: &'a Program<'a>) -> Mangler {
         let semantic = SemanticBuilder::new("", program.source_type).build(program).semantic;
@@ -83,6 +146,45 @@ impl ManglerBuilder {
         // A slot is the occurrence index of a binding identifier inside a scope.
         let (mut symbol_table, scope_tree) = semantic.into_symbol_table_and_scope_tree();
 
+        let root_scope_id = scope_tree.root_scope_id();
+        let keep_top_level = !self.top_level && !program.source_type.is_module();
+        let rename_globals = self.top_level && !program.source_type.is_module();
+
+        // Top-level bindings and unresolved references which get their name from the cache.
+        let mut cached_symbols = FxHashSet::default();
+        let mut unresolved_names = FxHashMap::default();
+        if rename_globals {
+            for (name, symbol_id) in scope_tree.get_bindings(root_scope_id) {
+                let Some(cached_name) = self.cached_names.get(name) else { continue };
+                // A cached name can not be used if it is a global of this script.
+                if Self::is_renamable(&symbol_table, *symbol_id)
+                    && !scope_tree.root_unresolved_references().contains_key(cached_name)
+                {
+                    cached_symbols.insert(*symbol_id);
+                }
+            }
+            for (name, references) in scope_tree.root_unresolved_references() {
+                let Some(cached_name) = self.cached_names.get(name) else { continue };
+                for (reference_id, _) in references {
+                    unresolved_names.insert(*reference_id, cached_name.clone());
+                }
+            }
+        }
+
+        // Kept top-level names, cached names, reserved names and globals must not be used as
+        // mangled names, or they would be shadowed.
+        let cached_names = if rename_globals {
+            self.cached_names.values().map(CompactStr::as_str).collect()
+        } else {
+            FxHashSet::default()
+        };
+        let is_reserved_name = |name: &str| {
+            (keep_top_level && scope_tree.get_binding(root_scope_id, name).is_some())
+                || cached_names.contains(name)
+                || self.reserved.contains(name)
+                || self.globals.contains(name)
+        };
+
         // Total number of slots for all scopes
         let mut total_number_of_slots: Slot = 0;
 
@@ -118,8 +220,17 @@ impl ManglerBuilder {
             }
         }
 
-        let frequencies =
-            Self::tally_slot_frequencies(&symbol_table, total_number_of_slots, &slots);
+        let frequencies = Self::tally_slot_frequencies(
+            &symbol_table,
+            total_number_of_slots,
+            &slots,
+            |symbol_id| {
+                !Self::is_renamable(&symbol_table, symbol_id)
+                    || (keep_top_level && symbol_table.get_scope_id(symbol_id) == root_scope_id)
+                    || cached_symbols.contains(&symbol_id)
+                    || self.reserved.contains(symbol_table.get_name(symbol_id))
+            },
+        );
 
         let mut names = Vec::with_capacity(total_number_of_slots);
 
@@ -129,9 +240,10 @@ impl ManglerBuilder {
             names.push(loop {
                 let name = generate_name(count);
                 count += 1;
-                // Do not mangle keywords and unresolved references
-                if !is_keyword(&name)
+                // Do not mangle reserved words and unresolved references, or reserved names
+                if !is_reserved_word(&name)
                     && !scope_tree.root_unresolved_references().contains_key(name.as_str())
+                    && !is_reserved_name(&name)
                 {
                     break name;
                 }
@@ -182,19 +294,37 @@ impl ManglerBuilder {
             }
         }
 
-        Mangler { symbol_table }
+        let mut top_level_names = vec![];
+        if rename_globals {
+            for (name, symbol_id) in scope_tree.get_bindings(root_scope_id) {
+                if cached_symbols.contains(symbol_id) {
+                    symbol_table.set_name(*symbol_id, self.cached_names[name].clone());
+                }
+                if Self::is_renamable(&symbol_table, *symbol_id) {
+                    top_level_names
+                        .push((name.clone(), CompactStr::from(symbol_table.get_name(*symbol_id))));
+                }
+            }
+        }
+
+        Mangler { symbol_table, unresolved_names, top_level_names }
+    }
+
+    fn is_renamable(symbol_table: &SymbolTable, symbol_id: SymbolId) -> bool {
+        let symbol_flag = symbol_table.get_flag(symbol_id);
+        // omit renaming `export { x }`
+        symbol_flag.is_variable() && !symbol_flag.is_export()
     }
 
     fn tally_slot_frequencies(
         symbol_table: &SymbolTable,
         total_number_of_slots: usize,
         slots: &IndexVec<SymbolId, Slot>,
+        is_kept: impl Fn(SymbolId) -> bool,
     ) -> Vec<SlotFrequency> {
         let mut frequencies = vec![SlotFrequency::default(); total_number_of_slots];
         for (symbol_id, slot) in slots.iter_enumerated() {
-            let symbol_flag = symbol_table.get_flag(symbol_id);
-            // omit renaming `export { x }`
-            if !symbol_flag.is_variable() || symbol_flag.is_export() {
+            if is_kept(symbol_id) {
                 continue;
             }
             let index = *slot;
@@ -215,12 +345,24 @@ struct SlotFrequency {
     pub symbol_ids: Vec<SymbolId>,
 }
 
+/// Names which are never generated, whatever the mode of the code they end up in: all reserved
+/// words, including `await`, `yield`, `let` and `static` which are only reserved in modules,
+/// strict code or generators, `arguments` and `eval` which can not be bound in strict code, the
+/// value properties of the global object, `undefined` and `NaN`, and contextual keywords.
 #[rustfmt::skip]
-fn is_keyword(s: &str) -> bool {
-    matches!(s, "as" | "do" | "if" | "in" | "is" | "of" | "any" | "for" | "get"
-            | "let" | "new" | "out" | "set" | "try" | "var" | "case" | "else"
-            | "enum" | "from" | "meta" | "null" | "this" | "true" | "type"
-            | "void" | "with")
+pub fn is_reserved_word(s: &str) -> bool {
+    is_reserved_keyword(s)
+        || is_global_object(s)
+        || matches!(s, "arguments" | "eval" | "as" | "is" | "of" | "any" | "get" | "out"
+            | "set" | "from" | "meta" | "type" | "async" | "target")
+}
+
+/// Short names in the order the mangler assigns them, skipping reserved words, see
+/// [`is_reserved_word`].
+///
+/// Property mangling in `oxc_minifier` takes its names from here as well.
+pub fn mangled_names() -> impl Iterator<Item = CompactStr> {
+    (0..).map(base54).filter(|name| !is_reserved_word(name))
 }
 
 const BASE54_CHARS: &[u8; 64] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ$_0123456789";
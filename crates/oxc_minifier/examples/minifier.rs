@@ -1,4 +1,4 @@
-#![allow(clippy::print_stdout)]
+#![allow(clippy::print_stdout, clippy::print_stderr)]
 use std::path::Path;
 
 use oxc_allocator::Allocator;
@@ -11,6 +11,9 @@ use pico_args::Arguments;
 // Instruction:
 // create a `test.js`,
 // run `cargo run -p oxc_minifier --example minifier` or `just example minifier`
+// add `--memory` to print arena usage
+// add `--validate` to re-parse the output and check its exports
+// add `--config options.json` to read `MinifierOptions` from a JSON file
 
 fn main() -> std::io::Result<()> {
     let mut args = Arguments::from_env();
@@ -19,32 +22,68 @@ fn main() -> std::io::Result<()> {
     let mangle = args.contains("--mangle");
     let whitespace = args.contains("--whitespace");
     let twice = args.contains("--twice");
+    let memory = args.contains("--memory");
+    let validate = args.contains("--validate");
+    let config: Option<String> = args.opt_value_from_str("--config").unwrap();
+
+    let mut options = match config {
+        Some(config) => {
+            let json = std::fs::read_to_string(&config)?;
+            match MinifierOptions::from_json_str(&json) {
+                Ok(options) => options,
+                Err(error) => {
+                    eprintln!("{:?}", error.with_source_code(json));
+                    return Ok(());
+                }
+            }
+        }
+        None => MinifierOptions { mangle: false, ..MinifierOptions::default() },
+    };
+    options.mangle |= mangle;
+    options.validate_output |= validate;
+    options.validate_exports |= validate;
 
     let path = Path::new(&name);
     let source_text = std::fs::read_to_string(path)?;
     let source_type = SourceType::from_path(path).unwrap();
 
-    let printed = minify(&source_text, source_type, mangle, whitespace);
+    let printed = minify(&source_text, source_type, options.clone(), whitespace, memory);
     println!("{printed}");
 
     if twice {
-        let printed = minify(&printed, source_type, mangle, whitespace);
+        let printed = minify(&printed, source_type, options, whitespace, memory);
         println!("{printed}");
     }
 
     Ok(())
 }
 
-fn minify(source_text: &str, source_type: SourceType, mangle: bool, whitespace: bool) -> String {
+fn minify(
+    source_text: &str,
+    source_type: SourceType,
+    options: MinifierOptions,
+    whitespace: bool,
+    memory: bool,
+) -> String {
     let allocator = Allocator::default();
     let ret = Parser::new(&allocator, source_text, source_type).parse();
     let program = allocator.alloc(ret.program);
-    let options = MinifierOptions { mangle, ..MinifierOptions::default() };
-    let ret = Minifier::new(options).build(&allocator, program);
-    if whitespace {
-        CodeGenerator::new().with_mangler(ret.mangler).build(program)
+    let parsed_bytes = allocator.allocated_bytes();
+    let mut ret = Minifier::new(options).build(&allocator, program);
+    if memory {
+        // Arena growth while minifying, which includes nodes that became garbage.
+        let minified_bytes = allocator.allocated_bytes() - parsed_bytes;
+        eprintln!("arena: {parsed_bytes} bytes after parsing, +{minified_bytes} bytes minifying");
+    }
+    let printed = if whitespace {
+        CodeGenerator::new().with_mangler(ret.mangler.take()).build(program)
     } else {
-        WhitespaceRemover::new().with_mangler(ret.mangler).build(program)
+        WhitespaceRemover::new().with_mangler(ret.mangler.take()).build(program)
+    }
+    .source_text;
+    if let Err(error) = ret.validate_output(&printed) {
+        let error = error.with_source_code(printed.clone());
+        eprintln!("{error:?}");
     }
-    .source_text
+    printed
 }
@@ -0,0 +1,489 @@
+use oxc_allocator::Vec;
+use oxc_ast::{
+    ast::*,
+    syntax_directed_operations::BoundNames,
+    visit::{walk, walk_mut},
+    AstBuilder, Visit, VisitMut,
+};
+use oxc_span::{Atom, Span, SPAN};
+use oxc_syntax::{
+    number::NumberBase,
+    operator::{AssignmentOperator, BinaryOperator, UnaryOperator, UpdateOperator},
+    scope::ScopeFlags,
+};
+use rustc_hash::{FxHashMap, FxHashSet};
+
+/// Lower iteration of arrays to indexed `for` loops, which saves the calls of the callback or
+/// the iterator, `a.forEach((x) => f(x))` →
+/// `for (let _i = 0; _i < a.length; _i++) { let x = a[_i]; f(x) }`.
+///
+/// Covers `forEach` and `map` calls whose result is unused, and `for...of` loops. The array must
+/// be bound by a `const` declaration of an array literal without holes, and must not escape: it
+/// is only used for `.length`, reading elements and the iteration itself, so it keeps its length
+/// and elements. Callbacks must be arrow functions with up to two identifier parameters, without
+/// `var` or function declarations, which would be hoisted out of the loop, and whose `return`
+/// statements have no value and are not in a loop, so they become `continue`.
+///
+/// Enabled by `compress.profile: Profile::Speed`
+pub struct ArrayLoops<'a> {
+    ast: AstBuilder<'a>,
+    /// `const` bindings of arrays which do not escape.
+    arrays: FxHashSet<Atom<'a>>,
+    /// Name of the loop indices, which is not used anywhere else in the program.
+    index: Atom<'a>,
+}
+
+impl<'a> VisitMut<'a> for ArrayLoops<'a> {
+    fn visit_statement(&mut self, stmt: &mut Statement<'a>) {
+        walk_mut::walk_statement(self, stmt);
+        if self.is_lowered_callback(stmt) {
+            let callback = self.ast.move_statement(stmt);
+            *stmt = self.lower_callback(callback);
+        } else if self.is_lowered_for_of(stmt) {
+            let for_of = self.ast.move_statement(stmt);
+            *stmt = self.lower_for_of(for_of);
+        }
+    }
+}
+
+impl<'a> ArrayLoops<'a> {
+    pub fn new(ast: AstBuilder<'a>) -> Self {
+        Self { ast, arrays: FxHashSet::default(), index: Atom::from("_i") }
+    }
+
+    pub fn build(&mut self, program: &mut Program<'a>) {
+        let mut usage = ArrayUsage::default();
+        if !program.source_type.is_module() {
+            // Top level declarations of a script are visible to other scripts.
+            for stmt in &program.body {
+                if let Some(decl) = stmt.as_declaration() {
+                    decl.bound_names(&mut |ident| {
+                        usage.references.insert(ident.name.clone());
+                    });
+                }
+            }
+        }
+        usage.visit_program(program);
+        if usage.has_dynamic_scope {
+            return;
+        }
+        self.arrays = usage
+            .declarations
+            .into_iter()
+            .filter(|name| usage.bindings.get(name) == Some(&1) && !usage.references.contains(name))
+            .collect();
+        if self.arrays.is_empty() {
+            return;
+        }
+        self.index = (1..=usage.names.len() + 1)
+            .map(|i| if i == 1 { Atom::from("_i") } else { self.ast.atom(&format!("_i{i}")) })
+            .find(|name| !usage.names.contains(name))
+            .unwrap();
+        self.visit_program(program);
+    }
+
+    /// `array.forEach(callback)` or `array.map(callback)` as a statement, with a callback which
+    /// can be inlined in a loop.
+    fn is_lowered_callback(&self, stmt: &Statement<'a>) -> bool {
+        let Statement::ExpressionStatement(expr_stmt) = stmt else { return false };
+        let Expression::CallExpression(call) = &expr_stmt.expression else { return false };
+        let Some((array, callback)) = iteration(call) else { return false };
+        self.arrays.contains(&array.name)
+            && !callback.r#async
+            && callback.body.directives.is_empty()
+            && callback.params.items.iter().all(|param| param.pattern.kind.is_binding_identifier())
+            && CallbackBody::is_inlinable(&callback.body)
+    }
+
+    /// `array.forEach((x, i) => { ... })` →
+    /// `for (let _i = 0; _i < array.length; _i++) { let x = array[_i], i = _i; ... }`
+    fn lower_callback(&self, stmt: Statement<'a>) -> Statement<'a> {
+        let Statement::ExpressionStatement(expr_stmt) = stmt else { unreachable!() };
+        let expr_stmt = expr_stmt.unbox();
+        let Expression::CallExpression(call) = expr_stmt.expression else { unreachable!() };
+        let call = call.unbox();
+        let Some((array, _)) = iteration(&call) else { unreachable!() };
+        let array = array.name.clone();
+        let Some(Argument::ArrowFunctionExpression(callback)) = call.arguments.into_iter().next()
+        else {
+            unreachable!()
+        };
+        let callback = callback.unbox();
+        let mut body = callback.body.unbox().statements;
+        ReturnToContinue { ast: self.ast }.visit_statements(&mut body);
+
+        let mut params = callback.params.unbox().items.into_iter();
+        let element = params.next().map(|param| (param.pattern, self.element(&array)));
+        let index = params.next().map(|param| (param.pattern, self.index_reference()));
+        let kind = VariableDeclarationKind::Let;
+        let declarations = self.ast.vec_from_iter(
+            element
+                .into_iter()
+                .chain(index)
+                .map(|(id, init)| self.ast.variable_declarator(SPAN, kind, id, Some(init), false)),
+        );
+        if !declarations.is_empty() {
+            let decl = self.ast.declaration_variable(SPAN, kind, declarations, false);
+            body.insert(0, self.ast.statement_declaration(decl));
+        }
+        self.indexed_loop(expr_stmt.span, array, body)
+    }
+
+    /// `for (const x of array)` of an array which does not escape, whose body does not declare
+    /// the names bound by the loop.
+    fn is_lowered_for_of(&self, stmt: &Statement<'a>) -> bool {
+        let Statement::ForOfStatement(for_of) = stmt else { return false };
+        let Expression::Identifier(array) = &for_of.right else { return false };
+        if for_of.r#await || !self.arrays.contains(&array.name) {
+            return false;
+        }
+        let mut names = std::vec![];
+        match &for_of.left {
+            ForStatementLeft::VariableDeclaration(decl) if decl.declarations.len() == 1 => {
+                decl.bound_names(&mut |ident| names.push(ident.name.clone()));
+            }
+            ForStatementLeft::AssignmentTargetIdentifier(_) => {}
+            _ => return false,
+        }
+        // The declarations of the body would be in the same scope as the loop bindings.
+        let Statement::BlockStatement(block) = &for_of.body else { return true };
+        let mut is_shadowed = false;
+        for stmt in &block.body {
+            if let Some(decl) = stmt.as_declaration() {
+                decl.bound_names(&mut |ident| is_shadowed |= names.contains(&ident.name));
+            }
+        }
+        !is_shadowed
+    }
+
+    /// `for (const x of array) { ... }` →
+    /// `for (let _i = 0; _i < array.length; _i++) { const x = array[_i]; ... }`
+    fn lower_for_of(&self, stmt: Statement<'a>) -> Statement<'a> {
+        let Statement::ForOfStatement(for_of) = stmt else { unreachable!() };
+        let for_of = for_of.unbox();
+        let Expression::Identifier(array) = &for_of.right else { unreachable!() };
+        let array = array.name.clone();
+        let element = self.element(&array);
+        let first = match for_of.left {
+            ForStatementLeft::VariableDeclaration(mut decl) => {
+                decl.declarations.first_mut().unwrap().init = Some(element);
+                Statement::VariableDeclaration(decl)
+            }
+            ForStatementLeft::AssignmentTargetIdentifier(ident) => self.ast.statement_expression(
+                SPAN,
+                self.ast.expression_assignment(
+                    SPAN,
+                    AssignmentOperator::Assign,
+                    AssignmentTarget::AssignmentTargetIdentifier(ident),
+                    element,
+                ),
+            ),
+            _ => unreachable!(),
+        };
+        let mut body = self.ast.vec1(first);
+        match for_of.body {
+            Statement::BlockStatement(block) => body.extend(block.unbox().body),
+            stmt => body.push(stmt),
+        }
+        self.indexed_loop(for_of.span, array, body)
+    }
+
+    /// `for (let _i = 0; _i < array.length; _i++) { body }`
+    fn indexed_loop(
+        &self,
+        span: Span,
+        array: Atom<'a>,
+        body: Vec<'a, Statement<'a>>,
+    ) -> Statement<'a> {
+        let kind = VariableDeclarationKind::Let;
+        let id = self.ast.binding_pattern::<Option<TSTypeAnnotation>>(
+            self.ast.binding_pattern_kind_binding_identifier(SPAN, self.index.clone()),
+            None,
+            false,
+        );
+        let zero = self.ast.expression_numeric_literal(SPAN, 0.0, "0", NumberBase::Decimal);
+        let init = self.ast.for_statement_init_variable_declaration(
+            SPAN,
+            kind,
+            self.ast.vec1(self.ast.variable_declarator(SPAN, kind, id, Some(zero), false)),
+            false,
+        );
+        let length = self.ast.expression_member(self.ast.member_expression_static(
+            SPAN,
+            self.ast.expression_identifier_reference(SPAN, array),
+            self.ast.identifier_name(SPAN, "length"),
+            false,
+        ));
+        let test = self.ast.expression_binary(
+            SPAN,
+            self.index_reference(),
+            BinaryOperator::LessThan,
+            length,
+        );
+        let update = self.ast.expression_update(
+            SPAN,
+            UpdateOperator::Increment,
+            false,
+            self.ast.simple_assignment_target_identifier_reference(SPAN, self.index.clone()),
+        );
+        self.ast.statement_for(
+            span,
+            Some(init),
+            Some(test),
+            Some(update),
+            self.ast.statement_block(SPAN, body),
+        )
+    }
+
+    /// `array[_i]`
+    fn element(&self, array: &Atom<'a>) -> Expression<'a> {
+        self.ast.expression_member(self.ast.member_expression_computed(
+            SPAN,
+            self.ast.expression_identifier_reference(SPAN, array.clone()),
+            self.index_reference(),
+            false,
+        ))
+    }
+
+    fn index_reference(&self) -> Expression<'a> {
+        self.ast.expression_identifier_reference(SPAN, self.index.clone())
+    }
+}
+
+/// `array.forEach(callback)` or `array.map(callback)`, with an arrow function callback which
+/// only receives the element and the index, so the array does not escape through the callback.
+fn iteration<'s, 'a>(
+    call: &'s CallExpression<'a>,
+) -> Option<(&'s IdentifierReference<'a>, &'s ArrowFunctionExpression<'a>)> {
+    let Expression::StaticMemberExpression(member) = &call.callee else { return None };
+    let Expression::Identifier(array) = &member.object else { return None };
+    let [Argument::ArrowFunctionExpression(callback)] = call.arguments.as_slice() else {
+        return None;
+    };
+    (!call.optional
+        && !member.optional
+        && matches!(member.property.name.as_str(), "forEach" | "map")
+        && callback.params.rest.is_none()
+        && callback.params.items.len() <= 2)
+        .then_some((&**array, &**callback))
+}
+
+/// Whether the body of a callback can be inlined in a loop.
+struct CallbackBody {
+    is_inlinable: bool,
+    /// Number of loops around the current node.
+    loops: usize,
+}
+
+impl CallbackBody {
+    fn is_inlinable(body: &FunctionBody) -> bool {
+        let mut callback_body = Self { is_inlinable: true, loops: 0 };
+        callback_body.visit_function_body(body);
+        callback_body.is_inlinable
+    }
+}
+
+impl<'a> Visit<'a> for CallbackBody {
+    fn visit_return_statement(&mut self, stmt: &ReturnStatement<'a>) {
+        // `continue` would continue the inner loop.
+        if stmt.argument.is_some() || self.loops > 0 {
+            self.is_inlinable = false;
+        }
+    }
+
+    fn visit_variable_declaration(&mut self, decl: &VariableDeclaration<'a>) {
+        if decl.kind.is_var() {
+            self.is_inlinable = false;
+        }
+        walk::walk_variable_declaration(self, decl);
+    }
+
+    fn visit_function(&mut self, func: &Function<'a>, _flags: ScopeFlags) {
+        if func.is_declaration() {
+            self.is_inlinable = false;
+        }
+    }
+
+    fn visit_arrow_function_expression(&mut self, _arrow: &ArrowFunctionExpression<'a>) {}
+
+    fn visit_for_statement(&mut self, stmt: &ForStatement<'a>) {
+        self.loops += 1;
+        walk::walk_for_statement(self, stmt);
+        self.loops -= 1;
+    }
+
+    fn visit_for_in_statement(&mut self, stmt: &ForInStatement<'a>) {
+        self.loops += 1;
+        walk::walk_for_in_statement(self, stmt);
+        self.loops -= 1;
+    }
+
+    fn visit_for_of_statement(&mut self, stmt: &ForOfStatement<'a>) {
+        self.loops += 1;
+        walk::walk_for_of_statement(self, stmt);
+        self.loops -= 1;
+    }
+
+    fn visit_while_statement(&mut self, stmt: &WhileStatement<'a>) {
+        self.loops += 1;
+        walk::walk_while_statement(self, stmt);
+        self.loops -= 1;
+    }
+
+    fn visit_do_while_statement(&mut self, stmt: &DoWhileStatement<'a>) {
+        self.loops += 1;
+        walk::walk_do_while_statement(self, stmt);
+        self.loops -= 1;
+    }
+}
+
+/// Rewrites the `return;` statements of an inlined callback to `continue;`.
+struct ReturnToContinue<'a> {
+    ast: AstBuilder<'a>,
+}
+
+impl<'a> VisitMut<'a> for ReturnToContinue<'a> {
+    fn visit_statement(&mut self, stmt: &mut Statement<'a>) {
+        if let Statement::ReturnStatement(ret) = stmt {
+            *stmt = self.ast.statement_continue(ret.span, None);
+            return;
+        }
+        walk_mut::walk_statement(self, stmt);
+    }
+
+    fn visit_function(&mut self, _func: &mut Function<'a>, _flags: ScopeFlags) {}
+
+    fn visit_arrow_function_expression(&mut self, _arrow: &mut ArrowFunctionExpression<'a>) {}
+}
+
+/// Bindings of arrays and how they are used.
+#[derive(Default)]
+struct ArrayUsage<'a> {
+    /// All names of the program.
+    names: FxHashSet<Atom<'a>>,
+    bindings: FxHashMap<Atom<'a>, usize>,
+    /// Names referenced other than for `.length`, reading elements and iteration.
+    references: FxHashSet<Atom<'a>>,
+    /// `const` declarations initialized with an array literal without holes.
+    declarations: std::vec::Vec<Atom<'a>>,
+    /// `eval` or `with`, which may reference any binding.
+    has_dynamic_scope: bool,
+}
+
+impl<'a> Visit<'a> for ArrayUsage<'a> {
+    fn visit_variable_declarator(&mut self, declarator: &VariableDeclarator<'a>) {
+        if let (
+            VariableDeclarationKind::Const,
+            Some(id),
+            Some(Expression::ArrayExpression(array)),
+        ) = (declarator.kind, declarator.id.get_binding_identifier(), &declarator.init)
+        {
+            if !array.elements.iter().any(ArrayExpressionElement::is_elision) {
+                self.declarations.push(id.name.clone());
+            }
+        }
+        walk::walk_variable_declarator(self, declarator);
+    }
+
+    fn visit_expression(&mut self, expr: &Expression<'a>) {
+        match expr {
+            // `array.length`
+            Expression::StaticMemberExpression(member)
+                if member.property.name == "length"
+                    && matches!(member.object, Expression::Identifier(_)) =>
+            {
+                self.read(&member.object);
+            }
+            // `array[i]`
+            Expression::ComputedMemberExpression(member)
+                if matches!(member.object, Expression::Identifier(_)) =>
+            {
+                self.read(&member.object);
+                self.visit_expression(&member.expression);
+            }
+            _ => walk::walk_expression(self, expr),
+        }
+    }
+
+    fn visit_call_expression(&mut self, call: &CallExpression<'a>) {
+        if let Some((array, _)) = iteration(call) {
+            self.names.insert(array.name.clone());
+            self.visit_arguments(&call.arguments);
+            return;
+        }
+        // Methods are called with the object as `this`, `array[0]()`.
+        if let Some(member) = call.callee.as_member_expression() {
+            self.visit_member_expression(member);
+            self.visit_arguments(&call.arguments);
+            return;
+        }
+        walk::walk_call_expression(self, call);
+    }
+
+    fn visit_tagged_template_expression(&mut self, expr: &TaggedTemplateExpression<'a>) {
+        if let Some(member) = expr.tag.as_member_expression() {
+            self.visit_member_expression(member);
+            self.visit_template_literal(&expr.quasi);
+            return;
+        }
+        walk::walk_tagged_template_expression(self, expr);
+    }
+
+    fn visit_unary_expression(&mut self, expr: &UnaryExpression<'a>) {
+        // `delete array[0]` removes an element.
+        match expr.argument.as_member_expression() {
+            Some(member) if expr.operator == UnaryOperator::Delete => {
+                self.visit_member_expression(member);
+            }
+            _ => walk::walk_unary_expression(self, expr),
+        }
+    }
+
+    fn visit_for_of_statement(&mut self, stmt: &ForOfStatement<'a>) {
+        if stmt.r#await || !matches!(stmt.right, Expression::Identifier(_)) {
+            walk::walk_for_of_statement(self, stmt);
+            return;
+        }
+        self.read(&stmt.right);
+        self.visit_for_statement_left(&stmt.left);
+        self.visit_statement(&stmt.body);
+    }
+
+    fn visit_export_named_declaration(&mut self, decl: &ExportNamedDeclaration<'a>) {
+        // Importers of exported bindings may modify them.
+        decl.bound_names(&mut |ident| {
+            self.references.insert(ident.name.clone());
+        });
+        for specifier in &decl.specifiers {
+            self.references.insert(specifier.local.name());
+        }
+        walk::walk_export_named_declaration(self, decl);
+    }
+
+    fn visit_with_statement(&mut self, stmt: &WithStatement<'a>) {
+        self.has_dynamic_scope = true;
+        walk::walk_with_statement(self, stmt);
+    }
+
+    fn visit_identifier_reference(&mut self, ident: &IdentifierReference<'a>) {
+        if ident.name == "eval" {
+            self.has_dynamic_scope = true;
+        }
+        self.names.insert(ident.name.clone());
+        self.references.insert(ident.name.clone());
+    }
+
+    fn visit_binding_identifier(&mut self, ident: &BindingIdentifier<'a>) {
+        self.names.insert(ident.name.clone());
+        *self.bindings.entry(ident.name.clone()).or_default() += 1;
+    }
+}
+
+impl<'a> ArrayUsage<'a> {
+    /// A use of an array which does not escape.
+    fn read(&mut self, array: &Expression<'a>) {
+        if let Expression::Identifier(ident) = array {
+            self.names.insert(ident.name.clone());
+        }
+    }
+}
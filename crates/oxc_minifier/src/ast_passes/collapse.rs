@@ -1,15 +1,22 @@
+use std::collections::hash_map::Entry;
+
 use oxc_allocator::Vec;
 use oxc_ast::{ast::*, visit::walk_mut, AstBuilder, VisitMut};
+use oxc_span::{Atom, SPAN};
+use rustc_hash::FxHashMap;
 
-use crate::CompressOptions;
+use crate::{chunk_boundary::is_chunk_boundary, CompressOptions};
 
-/// Collapse variable declarations (TODO: and assignments).
+/// Collapse variable declarations (TODO: and assignments), imports and exports.
 ///
 /// `var a; var b = 1; var c = 2` => `var a, b = 1; c = 2`
+/// `export { a }; export { b }` => `export { a, b }`
+/// `var i = 0; for (;;) a()` => `for (var i = 0;;) a()`
 /// TODO: `a = null; b = null;` => `a = b = null`
 pub struct Collapse<'a> {
     ast: AstBuilder<'a>,
     options: CompressOptions,
+    changed: bool,
 }
 
 impl<'a> VisitMut<'a> for Collapse<'a> {
@@ -17,20 +24,96 @@ impl<'a> VisitMut<'a> for Collapse<'a> {
         if self.options.join_vars {
             self.join_vars(stmts);
         }
+        if self.options.loops {
+            self.join_for_init(stmts);
+        }
 
         walk_mut::walk_statements(self, stmts);
     }
+
+    fn visit_expression(&mut self, expr: &mut Expression<'a>) {
+        if self.options.keep_chunk_boundaries && is_chunk_boundary(expr) {
+            return;
+        }
+        walk_mut::walk_expression(self, expr);
+    }
 }
 
 impl<'a> Collapse<'a> {
     pub fn new(ast: AstBuilder<'a>, options: CompressOptions) -> Self {
-        Self { ast, options }
+        Self { ast, options, changed: false }
+    }
+
+    /// Whether [`Collapse::build`] joined any statements.
+    pub fn changed(&self) -> bool {
+        self.changed
     }
 
     pub fn build(&mut self, program: &mut Program<'a>) {
+        if self.options.join_imports_exports {
+            self.join_imports_exports(&mut program.body);
+        }
         self.visit_program(program);
     }
 
+    /// Merge imports of the same module, and local exports or re-exports of the same module,
+    /// into the first of them.
+    ///
+    /// Imports and exports are hoisted, so only the order in which modules are first requested
+    /// is observable, which merging into the first statement keeps.
+    fn join_imports_exports(&mut self, stmts: &mut Vec<'a, Statement<'a>>) {
+        let mut imports = FxHashMap::<Atom<'a>, usize>::default();
+        // Keyed by the module of re-exports, or `None` for local exports.
+        let mut exports = FxHashMap::<Option<Atom<'a>>, usize>::default();
+        let len = stmts.len();
+        let mut new_stmts = self.ast.vec_with_capacity(len);
+        for stmt in stmts.drain(..) {
+            match stmt {
+                Statement::ImportDeclaration(mut import_decl)
+                    if is_joinable_import(&import_decl) =>
+                {
+                    match imports.entry(import_decl.source.value.clone()) {
+                        Entry::Occupied(entry) => {
+                            if let Some(Statement::ImportDeclaration(target)) =
+                                new_stmts.get_mut(*entry.get())
+                            {
+                                if join_import(target, &mut import_decl) {
+                                    continue;
+                                }
+                            }
+                        }
+                        Entry::Vacant(entry) => {
+                            entry.insert(new_stmts.len());
+                        }
+                    }
+                    new_stmts.push(Statement::ImportDeclaration(import_decl));
+                }
+                Statement::ExportNamedDeclaration(mut export_decl)
+                    if is_joinable_export(&export_decl) =>
+                {
+                    let source = export_decl.source.as_ref().map(|source| source.value.clone());
+                    match exports.entry(source) {
+                        Entry::Occupied(entry) => {
+                            if let Some(Statement::ExportNamedDeclaration(target)) =
+                                new_stmts.get_mut(*entry.get())
+                            {
+                                target.specifiers.append(&mut export_decl.specifiers);
+                                continue;
+                            }
+                        }
+                        Entry::Vacant(entry) => {
+                            entry.insert(new_stmts.len());
+                        }
+                    }
+                    new_stmts.push(Statement::ExportNamedDeclaration(export_decl));
+                }
+                stmt => new_stmts.push(stmt),
+            }
+        }
+        self.changed |= new_stmts.len() < len;
+        *stmts = new_stmts;
+    }
+
     /// Join consecutive var statements
     fn join_vars(&mut self, stmts: &mut Vec<'a, Statement<'a>>) {
         // Collect all the consecutive ranges that contain joinable vars.
@@ -79,5 +162,134 @@ impl<'a> Collapse<'a> {
             }
         }
         *stmts = new_stmts;
+        self.changed = true;
+    }
+
+    /// Move a `var` declaration or an expression statement into the header of the `for` loop
+    /// which follows it, `a = 0; for (;;)` => `for (a = 0;;)`, or join it with the initializer
+    /// of the loop, `a(); for (b();;)` => `for (a(), b();;)`.
+    ///
+    /// The initializer runs once, right after the statement it was, so only the semicolon is
+    /// saved, but the header can then take sequences which are folded into it. `let` and `const`
+    /// would be scoped to the loop, and labeled loops are left alone.
+    fn join_for_init(&mut self, stmts: &mut Vec<'a, Statement<'a>>) {
+        let mut i = 1;
+        while i < stmts.len() {
+            let (prev, rest) = stmts.split_at_mut(i);
+            let (Some(prev), Some(Statement::ForStatement(for_stmt))) =
+                (prev.last_mut(), rest.first_mut())
+            else {
+                i += 1;
+                continue;
+            };
+            let joinable = match (&*prev, &for_stmt.init) {
+                (Statement::ExpressionStatement(expr_stmt), None) => {
+                    !self.is_kept_boundary(&expr_stmt.expression)
+                }
+                (Statement::ExpressionStatement(expr_stmt), Some(init)) => {
+                    init.is_expression() && !self.is_kept_boundary(&expr_stmt.expression)
+                }
+                (Statement::VariableDeclaration(decl), None) => {
+                    decl.kind == VariableDeclarationKind::Var
+                }
+                (
+                    Statement::VariableDeclaration(decl),
+                    Some(ForStatementInit::VariableDeclaration(init)),
+                ) => decl.kind == VariableDeclarationKind::Var && init.kind == decl.kind,
+                _ => false,
+            };
+            if !joinable {
+                i += 1;
+                continue;
+            }
+            let init = for_stmt.init.take();
+            let new_init = match (stmts.remove(i - 1), init) {
+                (Statement::ExpressionStatement(expr_stmt), None) => {
+                    ForStatementInit::from(expr_stmt.unbox().expression)
+                }
+                (Statement::ExpressionStatement(expr_stmt), Some(init)) => {
+                    let Ok(init) = Expression::try_from(init) else { unreachable!() };
+                    let expressions = self.ast.vec_from_iter([expr_stmt.unbox().expression, init]);
+                    ForStatementInit::from(self.ast.expression_sequence(SPAN, expressions))
+                }
+                (Statement::VariableDeclaration(decl), None) => {
+                    ForStatementInit::VariableDeclaration(decl)
+                }
+                (
+                    Statement::VariableDeclaration(mut decl),
+                    Some(ForStatementInit::VariableDeclaration(mut init)),
+                ) => {
+                    decl.declarations.append(&mut init.declarations);
+                    ForStatementInit::VariableDeclaration(decl)
+                }
+                _ => unreachable!(),
+            };
+            let Some(Statement::ForStatement(for_stmt)) = stmts.get_mut(i - 1) else {
+                unreachable!()
+            };
+            for_stmt.init = Some(new_init);
+            self.changed = true;
+        }
+    }
+
+    fn is_kept_boundary(&self, expr: &Expression<'a>) -> bool {
+        self.options.keep_chunk_boundaries && is_chunk_boundary(expr)
+    }
+}
+
+/// Value imports without import attributes, which could change how the module is loaded.
+fn is_joinable_import(import_decl: &ImportDeclaration) -> bool {
+    import_decl.import_kind.is_value() && import_decl.with_clause.is_none()
+}
+
+/// `export { a }` or `export { a } from "m"`, without import attributes.
+fn is_joinable_export(export_decl: &ExportNamedDeclaration) -> bool {
+    export_decl.declaration.is_none()
+        && export_decl.export_kind.is_value()
+        && export_decl.with_clause.is_none()
+}
+
+/// Moves the specifiers of `import_decl` into `target`, which imports the same module.
+/// Returns `false` if they can not be written in one import, e.g. a namespace import and named
+/// imports.
+fn join_import<'a>(
+    target: &mut ImportDeclaration<'a>,
+    import_decl: &mut ImportDeclaration<'a>,
+) -> bool {
+    let Some(specifiers) =
+        import_decl.specifiers.as_mut().filter(|specifiers| !specifiers.is_empty())
+    else {
+        // `import "m"` only loads the module, which `target` does already.
+        return true;
+    };
+    let Some(target_specifiers) =
+        target.specifiers.as_mut().filter(|specifiers| !specifiers.is_empty())
+    else {
+        target.specifiers = import_decl.specifiers.take();
+        return true;
+    };
+    let count = |kind: fn(&ImportDeclarationSpecifier) -> bool| {
+        target_specifiers
+            .iter()
+            .chain(specifiers.iter())
+            .filter(|specifier| kind(specifier))
+            .count()
+    };
+    let defaults = count(|specifier| {
+        matches!(specifier, ImportDeclarationSpecifier::ImportDefaultSpecifier(_))
+    });
+    let namespaces = count(|specifier| {
+        matches!(specifier, ImportDeclarationSpecifier::ImportNamespaceSpecifier(_))
+    });
+    let named =
+        count(|specifier| matches!(specifier, ImportDeclarationSpecifier::ImportSpecifier(_)));
+    if defaults > 1 || namespaces > 1 || (namespaces > 0 && named > 0) {
+        return false;
     }
+    target_specifiers.append(specifiers);
+    // The default import has to be written first, `import a, { b } from "m"`.
+    target_specifiers.sort_by_key(|specifier| {
+        !matches!(specifier, ImportDeclarationSpecifier::ImportDefaultSpecifier(_))
+    });
+    true
 }
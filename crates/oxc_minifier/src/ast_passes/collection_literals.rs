@@ -0,0 +1,412 @@
+use oxc_allocator::Vec;
+use oxc_ast::{
+    ast::*,
+    visit::{walk, walk_mut},
+    AstBuilder, Visit, VisitMut,
+};
+use oxc_span::{Atom, SPAN};
+use oxc_syntax::{
+    number::NumberBase,
+    operator::{BinaryOperator, LogicalOperator},
+};
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::EmitSizeEstimator;
+
+/// Rewrite lookups in `Set` and `Map` collections built from literals,
+/// `new Set(["a", "b"]).has(x)` → `x === "a" || x === "b"` and
+/// `new Map([["a", 1]]).get(x)` → `{ a: 1 }[x]`.
+///
+/// Collections bound by a `const` declaration are rewritten when the binding is only used for
+/// lookups, so the collection never escapes. The `Set` declaration is then removed and its
+/// elements are compared at every lookup, if that is shorter.
+///
+/// Enabled by `compress.unsafe_collections`
+pub struct CollectionLiterals<'a> {
+    ast: AstBuilder<'a>,
+    /// Elements of the `Set`s whose bindings are replaced by comparisons.
+    sets: FxHashMap<Atom<'a>, std::vec::Vec<SetElement<'a>>>,
+    /// Names of the `Map`s which are replaced by objects.
+    maps: FxHashSet<Atom<'a>>,
+    has_global_set: bool,
+    has_global_map: bool,
+}
+
+impl<'a> VisitMut<'a> for CollectionLiterals<'a> {
+    fn visit_statements(&mut self, stmts: &mut Vec<'a, Statement<'a>>) {
+        walk_mut::walk_statements(self, stmts);
+        stmts.retain(|stmt| {
+            !matches!(stmt, Statement::VariableDeclaration(decl) if decl.declarations.is_empty())
+        });
+    }
+
+    fn visit_variable_declaration(&mut self, decl: &mut VariableDeclaration<'a>) {
+        decl.declarations.retain(|declarator| {
+            declarator
+                .id
+                .get_binding_identifier()
+                .map_or(true, |id| !self.sets.contains_key(&id.name))
+        });
+        for declarator in decl.declarations.iter_mut() {
+            let is_map = declarator
+                .id
+                .get_binding_identifier()
+                .is_some_and(|id| self.maps.contains(&id.name));
+            if let (true, Some(init)) = (is_map, &mut declarator.init) {
+                let Expression::NewExpression(new_expr) = self.ast.move_expression(init) else {
+                    unreachable!()
+                };
+                *init = self.map_to_object(new_expr.unbox());
+            }
+        }
+        walk_mut::walk_variable_declaration(self, decl);
+    }
+
+    fn visit_expression(&mut self, expr: &mut Expression<'a>) {
+        walk_mut::walk_expression(self, expr);
+        self.try_rewrite_lookup(expr);
+    }
+}
+
+impl<'a> CollectionLiterals<'a> {
+    pub fn new(ast: AstBuilder<'a>) -> Self {
+        Self {
+            ast,
+            sets: FxHashMap::default(),
+            maps: FxHashSet::default(),
+            has_global_set: false,
+            has_global_map: false,
+        }
+    }
+
+    pub fn build(&mut self, program: &mut Program<'a>) {
+        let mut usage = CollectionUsage::default();
+        usage.visit_program(program);
+        self.has_global_set = !usage.bindings.contains_key("Set");
+        self.has_global_map = !usage.bindings.contains_key("Map");
+        for (name, collection) in &usage.declarations {
+            let Some(lookups) = usage.lookups.get(name) else { continue };
+            if usage.has_eval
+                || usage.bindings.get(name) != Some(&1)
+                || usage.references.contains(name)
+            {
+                continue;
+            }
+            match collection {
+                Collection::Set(elements) if self.has_global_set => {
+                    let is_shorter = lookups.iter().all(|lookup| lookup.method == "has")
+                        && elements_size(elements).is_some_and(|(count, size)| {
+                            let mut before = name.len() + 12 + size + count;
+                            let mut after = 0;
+                            for lookup in lookups {
+                                let Some(argument) = repeatable_size(lookup.argument, count) else {
+                                    return false;
+                                };
+                                before += name.len() + 6 + argument;
+                                after += comparisons_size(argument, count, size);
+                            }
+                            after <= before
+                        });
+                    if is_shorter {
+                        self.sets.insert(name.clone(), elements.clone());
+                    }
+                }
+                Collection::Map
+                    if self.has_global_map
+                        && lookups.iter().all(|lookup| lookup.method == "get") =>
+                {
+                    self.maps.insert(name.clone());
+                }
+                _ => {}
+            }
+        }
+        self.visit_program(program);
+    }
+
+    fn try_rewrite_lookup(&mut self, expr: &mut Expression<'a>) {
+        let Expression::CallExpression(call) = expr else { return };
+        let Some((object, method)) = lookup(call) else { return };
+        let argument_size = match &call.arguments[0] {
+            Argument::Identifier(ident) => Some(ident.name.len()),
+            _ => None,
+        };
+        let rewrite = match object {
+            Expression::Identifier(ident) if method == "has" => {
+                self.sets.get(&ident.name).map(|elements| Rewrite::Set(elements.clone()))
+            }
+            Expression::Identifier(ident) if method == "get" && self.maps.contains(&ident.name) => {
+                Some(Rewrite::Map)
+            }
+            Expression::NewExpression(new_expr) if method == "has" && self.has_global_set => {
+                set_elements(new_expr)
+                    .filter(|elements| {
+                        elements_size(elements).is_some_and(|(count, size)| {
+                            repeatable_size(argument_size, count).is_some_and(|argument| {
+                                comparisons_size(argument, count, size)
+                                    <= 16 + size + count + argument
+                            })
+                        })
+                    })
+                    .map(Rewrite::Set)
+            }
+            Expression::NewExpression(new_expr)
+                if method == "get" && self.has_global_map && is_literal_map(new_expr) =>
+            {
+                Some(Rewrite::NewMap)
+            }
+            _ => None,
+        };
+        let Some(rewrite) = rewrite else { return };
+        let Some(argument) = call.arguments.first_mut() else { unreachable!() };
+        let argument = self.ast.move_expression(argument.to_expression_mut());
+        let Expression::StaticMemberExpression(member) = &mut call.callee else { unreachable!() };
+        let object = self.ast.move_expression(&mut member.object);
+        *expr = match rewrite {
+            Rewrite::Set(elements) => self.comparisons(argument, &elements),
+            Rewrite::Map => self.ast.expression_member(
+                self.ast.member_expression_computed(SPAN, object, argument, false),
+            ),
+            Rewrite::NewMap => {
+                let Expression::NewExpression(new_expr) = object else { unreachable!() };
+                let object = self.map_to_object(new_expr.unbox());
+                self.ast.expression_member(
+                    self.ast.member_expression_computed(SPAN, object, argument, false),
+                )
+            }
+        };
+    }
+
+    /// `x === "a" || x === "b"`
+    fn comparisons(&self, argument: Expression<'a>, elements: &[SetElement<'a>]) -> Expression<'a> {
+        let mut argument = Some(argument);
+        let mut result: Option<Expression<'a>> = None;
+        for (index, element) in elements.iter().enumerate() {
+            let left = if index + 1 == elements.len() {
+                argument.take().unwrap()
+            } else {
+                let Some(Expression::Identifier(ident)) = &argument else { unreachable!() };
+                self.ast.expression_identifier_reference(SPAN, ident.name.clone())
+            };
+            let right = match element {
+                SetElement::String(value) => {
+                    self.ast.expression_string_literal(SPAN, value.clone())
+                }
+                SetElement::Number(value, raw) => {
+                    self.ast.expression_numeric_literal(SPAN, *value, *raw, NumberBase::Decimal)
+                }
+                SetElement::Boolean(value) => self.ast.expression_boolean_literal(SPAN, *value),
+                SetElement::Null => self.ast.expression_null_literal(SPAN),
+            };
+            let comparison =
+                self.ast.expression_binary(SPAN, left, BinaryOperator::StrictEquality, right);
+            result = Some(match result {
+                Some(left) => {
+                    self.ast.expression_logical(SPAN, left, LogicalOperator::Or, comparison)
+                }
+                None => comparison,
+            });
+        }
+        result.unwrap()
+    }
+
+    /// `new Map([["a", 1]])` → `{ a: 1 }`, for a map checked with [`is_literal_map`].
+    fn map_to_object(&self, new_expr: NewExpression<'a>) -> Expression<'a> {
+        let Some(Argument::ArrayExpression(entries)) = new_expr.arguments.into_iter().next() else {
+            unreachable!()
+        };
+        let properties = entries.unbox().elements.into_iter().map(|entry| {
+            let ArrayExpressionElement::ArrayExpression(entry) = entry else { unreachable!() };
+            let mut entry = entry.unbox().elements.into_iter();
+            let (Some(ArrayExpressionElement::StringLiteral(key)), Some(value)) =
+                (entry.next(), entry.next())
+            else {
+                unreachable!()
+            };
+            let key = PropertyKey::StringLiteral(key);
+            let mut value = value;
+            let value = self.ast.move_expression(value.to_expression_mut());
+            self.ast.object_property_kind_object_property(
+                SPAN,
+                PropertyKind::Init,
+                key,
+                value,
+                None,
+                false,
+                false,
+                false,
+            )
+        });
+        self.ast.expression_object(SPAN, self.ast.vec_from_iter(properties), None)
+    }
+}
+
+enum Rewrite<'a> {
+    Set(std::vec::Vec<SetElement<'a>>),
+    /// Lookup in a `Map` binding which is replaced by an object.
+    Map,
+    NewMap,
+}
+
+/// A literal element of a `Set`, compared with `===`. `Set.prototype.has` uses `SameValueZero`,
+/// which only differs for `NaN`, and `NaN` is not a literal.
+#[derive(Clone)]
+enum SetElement<'a> {
+    String(Atom<'a>),
+    Number(f64, &'a str),
+    Boolean(bool),
+    Null,
+}
+
+enum Collection<'a> {
+    Set(std::vec::Vec<SetElement<'a>>),
+    Map,
+}
+
+/// `object.has(argument)` or `object.get(argument)`
+fn lookup<'s, 'a>(call: &'s CallExpression<'a>) -> Option<(&'s Expression<'a>, &'s str)> {
+    let Expression::StaticMemberExpression(member) = &call.callee else { return None };
+    let method = member.property.name.as_str();
+    (!call.optional
+        && !member.optional
+        && matches!(method, "has" | "get")
+        && call.arguments.len() == 1
+        && !call.arguments[0].is_spread())
+    .then_some((&member.object, method))
+}
+
+/// Elements of `new Set([...])` with only literal elements.
+fn set_elements<'a>(new_expr: &NewExpression<'a>) -> Option<std::vec::Vec<SetElement<'a>>> {
+    if !new_expr.callee.is_specific_id("Set") {
+        return None;
+    }
+    let [Argument::ArrayExpression(array)] = new_expr.arguments.as_slice() else { return None };
+    array
+        .elements
+        .iter()
+        .map(|element| match element {
+            ArrayExpressionElement::StringLiteral(lit) => {
+                Some(SetElement::String(lit.value.clone()))
+            }
+            ArrayExpressionElement::NumericLiteral(lit) => {
+                Some(SetElement::Number(lit.value, lit.raw))
+            }
+            ArrayExpressionElement::BooleanLiteral(lit) => Some(SetElement::Boolean(lit.value)),
+            ArrayExpressionElement::NullLiteral(_) => Some(SetElement::Null),
+            _ => None,
+        })
+        .collect()
+}
+
+/// `new Map([["a", value], ...])` with string literal keys, other than `__proto__` which sets
+/// the prototype in an object literal.
+fn is_literal_map(new_expr: &NewExpression) -> bool {
+    if !new_expr.callee.is_specific_id("Map") {
+        return false;
+    }
+    let [Argument::ArrayExpression(entries)] = new_expr.arguments.as_slice() else { return false };
+    entries.elements.iter().all(|entry| {
+        let ArrayExpressionElement::ArrayExpression(entry) = entry else { return false };
+        match entry.elements.as_slice() {
+            [ArrayExpressionElement::StringLiteral(key), value] => {
+                key.value != "__proto__" && value.is_expression()
+            }
+            _ => false,
+        }
+    })
+}
+
+/// Number of elements and the size of their printed literals, for non-empty sets.
+fn elements_size(elements: &[SetElement]) -> Option<(usize, usize)> {
+    let size = elements
+        .iter()
+        .map(|element| match element {
+            SetElement::String(value) => EmitSizeEstimator::string(value),
+            SetElement::Number(value, _) => EmitSizeEstimator::number(*value, NumberBase::Decimal),
+            SetElement::Boolean(_) => 2,
+            SetElement::Null => 4,
+        })
+        .sum();
+    (!elements.is_empty()).then_some((elements.len(), size))
+}
+
+/// Size of the lookup argument, if it can be evaluated once per element. Only identifiers are
+/// repeated, other arguments are only allowed when there is a single comparison.
+fn repeatable_size(argument: Option<usize>, count: usize) -> Option<usize> {
+    argument.or((count == 1).then_some(0))
+}
+
+/// Size of `x===a||x===b` for `count` elements of total size `size`.
+fn comparisons_size(argument: usize, count: usize, size: usize) -> usize {
+    count * (argument + 3) + size + 2 * (count - 1)
+}
+
+/// Bindings of collections and how they are used.
+#[derive(Default)]
+struct CollectionUsage<'a> {
+    bindings: FxHashMap<Atom<'a>, usize>,
+    /// Names referenced other than as the object of a lookup.
+    references: FxHashSet<Atom<'a>>,
+    lookups: FxHashMap<Atom<'a>, std::vec::Vec<Lookup<'a>>>,
+    /// `const` declarations initialized with a literal `Set` or `Map`.
+    declarations: FxHashMap<Atom<'a>, Collection<'a>>,
+    has_eval: bool,
+}
+
+struct Lookup<'a> {
+    method: &'a str,
+    /// Size of the argument, if it is an identifier.
+    argument: Option<usize>,
+}
+
+impl<'a> Visit<'a> for CollectionUsage<'a> {
+    fn visit_statements(&mut self, stmts: &Vec<'a, Statement<'a>>) {
+        for stmt in stmts {
+            let Statement::VariableDeclaration(decl) = stmt else { continue };
+            if decl.kind != VariableDeclarationKind::Const {
+                continue;
+            }
+            for declarator in &decl.declarations {
+                let (Some(id), Some(Expression::NewExpression(new_expr))) =
+                    (declarator.id.get_binding_identifier(), &declarator.init)
+                else {
+                    continue;
+                };
+                let collection = if let Some(elements) = set_elements(new_expr) {
+                    Collection::Set(elements)
+                } else if is_literal_map(new_expr) {
+                    Collection::Map
+                } else {
+                    continue;
+                };
+                self.declarations.insert(id.name.clone(), collection);
+            }
+        }
+        walk::walk_statements(self, stmts);
+    }
+
+    fn visit_call_expression(&mut self, call: &CallExpression<'a>) {
+        let Some((Expression::Identifier(ident), _)) = lookup(call) else {
+            walk::walk_call_expression(self, call);
+            return;
+        };
+        let Expression::StaticMemberExpression(member) = &call.callee else { unreachable!() };
+        let argument = match &call.arguments[0] {
+            Argument::Identifier(ident) => Some(ident.name.len()),
+            _ => None,
+        };
+        let lookup = Lookup { method: member.property.name.as_str(), argument };
+        self.lookups.entry(ident.name.clone()).or_default().push(lookup);
+        self.visit_arguments(&call.arguments);
+    }
+
+    fn visit_identifier_reference(&mut self, ident: &IdentifierReference<'a>) {
+        if ident.name == "eval" {
+            self.has_eval = true;
+        }
+        self.references.insert(ident.name.clone());
+    }
+
+    fn visit_binding_identifier(&mut self, ident: &BindingIdentifier<'a>) {
+        *self.bindings.entry(ident.name.clone()).or_default() += 1;
+    }
+}
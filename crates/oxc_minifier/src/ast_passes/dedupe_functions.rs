@@ -0,0 +1,389 @@
+use std::mem;
+
+use oxc_allocator::Vec;
+use oxc_ast::{
+    ast::*,
+    syntax_directed_operations::BoundNames,
+    visit::{walk, walk_mut},
+    AstBuilder, Visit, VisitMut,
+};
+use oxc_codegen::{Context, GenExpr, WhitespaceRemover};
+use oxc_span::{Atom, SPAN};
+use oxc_syntax::{precedence::Precedence, scope::ScopeFlags};
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::{chunk_boundary::is_chunk_boundary, CompressOptions};
+
+/// Hoist function expressions which are repeated in a module into a single shared copy,
+/// `a.map((x) => x.id); b.map((x) => x.id)` →
+/// `const _fn = (x) => x.id; a.map(_fn); b.map(_fn)`.
+///
+/// Functions are identical when they print the same. A function is hoisted when it is anonymous
+/// and every name it references from outside resolves to a top level declaration or a global,
+/// so it sees the same bindings at the top level. Arrow functions which use `this`, `arguments`,
+/// `super` or `new.target` of the enclosing function, and functions which use private names of
+/// an enclosing class, are never hoisted. `import.meta` is the same in the whole module, and the
+/// shared copies are declared before the first statement which is not an import, so they are
+/// evaluated before any top level `await`.
+/// Functions are only hoisted when that is shorter, and functions nested in a hoisted function
+/// are hoisted separately.
+///
+/// Enabled by `compress.dedupe_functions`
+pub struct DedupeFunctions<'a> {
+    ast: AstBuilder<'a>,
+    options: CompressOptions,
+    scopes: Scopes<'a>,
+    /// Number of hoistable occurrences of each function, by its printed code.
+    counts: FxHashMap<String, usize>,
+    /// Names of the hoisted functions, by their printed code.
+    hoisted: FxHashMap<String, Atom<'a>>,
+    declarators: Vec<'a, VariableDeclarator<'a>>,
+    /// All names of the program, which the names of hoisted functions must not shadow.
+    names: FxHashSet<Atom<'a>>,
+}
+
+impl<'a> VisitMut<'a> for DedupeFunctions<'a> {
+    fn visit_expression(&mut self, expr: &mut Expression<'a>) {
+        if self.options.keep_chunk_boundaries && is_chunk_boundary(expr) {
+            return;
+        }
+        if let Some(name) = self.scopes.hoistable_key(expr).and_then(|key| self.hoist(key, expr)) {
+            *expr = self.ast.expression_identifier_reference(SPAN, name);
+        } else {
+            walk_mut::walk_expression(self, expr);
+        }
+    }
+
+    fn visit_function(&mut self, func: &mut Function<'a>, flags: ScopeFlags) {
+        self.scopes.functions.push(binding_names(|names| names.visit_function(func, flags)));
+        walk_mut::walk_function(self, func, flags);
+        self.scopes.functions.pop();
+    }
+
+    fn visit_arrow_function_expression(&mut self, arrow: &mut ArrowFunctionExpression<'a>) {
+        self.scopes
+            .functions
+            .push(binding_names(|names| names.visit_arrow_function_expression(arrow)));
+        walk_mut::walk_arrow_function_expression(self, arrow);
+        self.scopes.functions.pop();
+    }
+}
+
+impl<'a> DedupeFunctions<'a> {
+    pub fn new(ast: AstBuilder<'a>, options: CompressOptions) -> Self {
+        Self {
+            ast,
+            options,
+            scopes: Scopes::default(),
+            counts: FxHashMap::default(),
+            hoisted: FxHashMap::default(),
+            declarators: ast.vec(),
+            names: FxHashSet::default(),
+        }
+    }
+
+    pub fn build(&mut self, program: &mut Program<'a>) {
+        // Top level declarations of a script are shared with other scripts.
+        if !program.source_type.is_module() {
+            return;
+        }
+        let mut names = ProgramNames::default();
+        names.visit_program(program);
+        // Direct `eval` can reference any binding in scope.
+        if names.has_eval {
+            return;
+        }
+        let mut top_level = FxHashMap::default();
+        for stmt in &program.body {
+            top_level_bound_names(stmt, &mut |ident| {
+                *top_level.entry(ident.name.clone()).or_insert(0) += 1;
+            });
+        }
+        self.scopes.blocks = names
+            .declarations
+            .into_iter()
+            .filter(|(name, count)| top_level.get(name).map_or(true, |top| top < count))
+            .map(|(name, _)| name)
+            .collect();
+        self.names = names.names;
+
+        let mut counter = FunctionCounter {
+            scopes: &mut self.scopes,
+            keep_chunk_boundaries: self.options.keep_chunk_boundaries,
+            counts: FxHashMap::default(),
+        };
+        counter.visit_program(program);
+        self.counts = counter.counts;
+
+        self.visit_program(program);
+        if self.declarators.is_empty() {
+            return;
+        }
+        let declarators = mem::replace(&mut self.declarators, self.ast.vec());
+        let decl =
+            self.ast.declaration_variable(SPAN, VariableDeclarationKind::Const, declarators, false);
+        let index = program
+            .body
+            .iter()
+            .position(|stmt| !matches!(stmt, Statement::ImportDeclaration(_)))
+            .unwrap_or(program.body.len());
+        program.body.insert(index, self.ast.statement_declaration(decl));
+    }
+
+    /// Name of the shared copy of the function `func` printed as `key`, which is hoisted at its
+    /// first occurrence. `None` when the function is not repeated or hoisting it is not shorter.
+    fn hoist(&mut self, key: String, func: &mut Expression<'a>) -> Option<Atom<'a>> {
+        if let Some(name) = self.hoisted.get(&key) {
+            return Some(name.clone());
+        }
+        let count = self.counts.get(&key).copied().filter(|count| *count > 1)?;
+        let name = self.unique_name();
+        // Every occurrence is replaced by the name, and `name=func,` is added to the declaration.
+        if (count - 1) * key.len() <= (count + 1) * name.len() + 2 {
+            return None;
+        }
+        self.names.insert(name.clone());
+        walk_mut::walk_expression(self, func);
+        let id = self.ast.binding_pattern(
+            self.ast.binding_pattern_kind_binding_identifier(SPAN, name.clone()),
+            None::<TSTypeAnnotation>,
+            false,
+        );
+        let init = self.ast.move_expression(func);
+        self.declarators.push(self.ast.variable_declarator(
+            SPAN,
+            VariableDeclarationKind::Const,
+            id,
+            Some(init),
+            false,
+        ));
+        self.hoisted.insert(key, name.clone());
+        Some(name)
+    }
+
+    fn unique_name(&self) -> Atom<'a> {
+        (1..=self.names.len() + 1)
+            .map(|i| if i == 1 { Atom::from("_fn") } else { self.ast.atom(&format!("_fn{i}")) })
+            .find(|name| !self.names.contains(name))
+            .unwrap()
+    }
+}
+
+/// Names declared in the scopes enclosing the visited node, other than the top level.
+#[derive(Default)]
+struct Scopes<'a> {
+    /// Names declared in each enclosing function, in any of its scopes.
+    functions: std::vec::Vec<FxHashSet<Atom<'a>>>,
+    /// Names declared outside of functions but not at the top level, such as `{ let a }`.
+    blocks: FxHashSet<Atom<'a>>,
+}
+
+impl<'a> Scopes<'a> {
+    fn is_declared(&self, name: &str) -> bool {
+        self.blocks.contains(name) || self.functions.iter().any(|names| names.contains(name))
+    }
+
+    /// The printed code of `expr` if it is a function which can be hoisted to the top level.
+    fn hoistable_key(&self, expr: &Expression<'a>) -> Option<String> {
+        let usage = match expr {
+            Expression::FunctionExpression(func) if func.id.is_none() => {
+                let mut usage = FunctionUsage::new(&func.params, func.body.as_ref()?);
+                usage.own.insert(Atom::from("arguments"));
+                usage.visit_function(func, ScopeFlags::Function);
+                usage
+            }
+            Expression::ArrowFunctionExpression(arrow) => {
+                let mut usage = FunctionUsage::new(&arrow.params, &arrow.body);
+                usage.visit_arrow_function_expression(arrow);
+                usage
+            }
+            _ => return None,
+        };
+        let is_hoistable = !usage.uses_enclosing_context
+            && usage
+                .references
+                .iter()
+                .all(|name| usage.own.contains(name) || !self.is_declared(name));
+        is_hoistable.then(|| print(expr))
+    }
+}
+
+/// Counts the hoistable functions. Later occurrences of a function are not visited, because
+/// they are either replaced as a whole or kept as they are.
+struct FunctionCounter<'s, 'a> {
+    scopes: &'s mut Scopes<'a>,
+    keep_chunk_boundaries: bool,
+    counts: FxHashMap<String, usize>,
+}
+
+impl<'a> Visit<'a> for FunctionCounter<'_, 'a> {
+    fn visit_expression(&mut self, expr: &Expression<'a>) {
+        if self.keep_chunk_boundaries && is_chunk_boundary(expr) {
+            return;
+        }
+        if let Some(key) = self.scopes.hoistable_key(expr) {
+            let count = self.counts.entry(key).or_insert(0);
+            *count += 1;
+            if *count > 1 {
+                return;
+            }
+        }
+        walk::walk_expression(self, expr);
+    }
+
+    fn visit_function(&mut self, func: &Function<'a>, flags: ScopeFlags) {
+        self.scopes.functions.push(binding_names(|names| names.visit_function(func, flags)));
+        walk::walk_function(self, func, flags);
+        self.scopes.functions.pop();
+    }
+
+    fn visit_arrow_function_expression(&mut self, arrow: &ArrowFunctionExpression<'a>) {
+        self.scopes
+            .functions
+            .push(binding_names(|names| names.visit_arrow_function_expression(arrow)));
+        walk::walk_arrow_function_expression(self, arrow);
+        self.scopes.functions.pop();
+    }
+}
+
+/// Names referenced by a function, and whether it depends on the function enclosing it.
+#[derive(Default)]
+struct FunctionUsage<'a> {
+    references: FxHashSet<Atom<'a>>,
+    /// Names declared by the parameters and the top level statements of the function, which
+    /// its references resolve to wherever it is.
+    own: FxHashSet<Atom<'a>>,
+    /// Uses `this`, `arguments`, `super` or `new.target` of the enclosing function, or private
+    /// names of an enclosing class.
+    uses_enclosing_context: bool,
+    function_depth: usize,
+}
+
+impl<'a> FunctionUsage<'a> {
+    fn new(params: &FormalParameters<'a>, body: &FunctionBody<'a>) -> Self {
+        let mut own = FxHashSet::default();
+        let mut declare = |ident: &BindingIdentifier<'a>| {
+            own.insert(ident.name.clone());
+        };
+        params.bound_names(&mut declare);
+        for decl in body.statements.iter().filter_map(Statement::as_declaration) {
+            decl.bound_names(&mut declare);
+        }
+        Self { own, ..Self::default() }
+    }
+}
+
+impl<'a> Visit<'a> for FunctionUsage<'a> {
+    fn visit_identifier_reference(&mut self, ident: &IdentifierReference<'a>) {
+        if ident.name == "arguments" && self.function_depth == 0 {
+            self.uses_enclosing_context = true;
+        }
+        self.references.insert(ident.name.clone());
+    }
+
+    // Component names, `<Foo />`, are references.
+    fn visit_jsx_identifier(&mut self, ident: &JSXIdentifier<'a>) {
+        self.references.insert(ident.name.clone());
+    }
+
+    fn visit_this_expression(&mut self, _: &ThisExpression) {
+        self.uses_enclosing_context |= self.function_depth == 0;
+    }
+
+    fn visit_super(&mut self, _: &Super) {
+        self.uses_enclosing_context |= self.function_depth == 0;
+    }
+
+    fn visit_meta_property(&mut self, meta: &MetaProperty<'a>) {
+        self.uses_enclosing_context |= self.function_depth == 0 && meta.meta.name == "new";
+    }
+
+    fn visit_private_identifier(&mut self, _: &PrivateIdentifier<'a>) {
+        self.uses_enclosing_context = true;
+    }
+
+    fn visit_function(&mut self, func: &Function<'a>, flags: ScopeFlags) {
+        self.function_depth += 1;
+        walk::walk_function(self, func, flags);
+        self.function_depth -= 1;
+    }
+}
+
+/// All names of the program, and the declarations outside of functions.
+#[derive(Default)]
+struct ProgramNames<'a> {
+    names: FxHashSet<Atom<'a>>,
+    /// Number of declarations of each name outside of functions.
+    declarations: FxHashMap<Atom<'a>, usize>,
+    function_depth: usize,
+    has_eval: bool,
+}
+
+impl<'a> Visit<'a> for ProgramNames<'a> {
+    fn visit_identifier_reference(&mut self, ident: &IdentifierReference<'a>) {
+        self.has_eval |= ident.name == "eval";
+        self.names.insert(ident.name.clone());
+    }
+
+    fn visit_binding_identifier(&mut self, ident: &BindingIdentifier<'a>) {
+        if self.function_depth == 0 {
+            *self.declarations.entry(ident.name.clone()).or_insert(0) += 1;
+        }
+        self.names.insert(ident.name.clone());
+    }
+
+    fn visit_function(&mut self, func: &Function<'a>, flags: ScopeFlags) {
+        // The name of a function declaration is declared in the enclosing scope.
+        if let Some(id) = &func.id {
+            self.visit_binding_identifier(id);
+        }
+        self.function_depth += 1;
+        walk::walk_function(self, func, flags);
+        self.function_depth -= 1;
+    }
+
+    fn visit_arrow_function_expression(&mut self, arrow: &ArrowFunctionExpression<'a>) {
+        self.function_depth += 1;
+        walk::walk_arrow_function_expression(self, arrow);
+        self.function_depth -= 1;
+    }
+}
+
+/// All names declared in a function, in any of its scopes.
+#[derive(Default)]
+struct BindingNames<'a>(FxHashSet<Atom<'a>>);
+
+impl<'a> Visit<'a> for BindingNames<'a> {
+    fn visit_binding_identifier(&mut self, ident: &BindingIdentifier<'a>) {
+        self.0.insert(ident.name.clone());
+    }
+}
+
+fn binding_names<'a>(visit: impl FnOnce(&mut BindingNames<'a>)) -> FxHashSet<Atom<'a>> {
+    let mut names = BindingNames::default();
+    visit(&mut names);
+    names.0
+}
+
+fn top_level_bound_names<'a, F: FnMut(&BindingIdentifier<'a>)>(stmt: &Statement<'a>, f: &mut F) {
+    match stmt {
+        Statement::ExportDefaultDeclaration(decl) => match &decl.declaration {
+            ExportDefaultDeclarationKind::FunctionDeclaration(func) => func.bound_names(f),
+            ExportDefaultDeclarationKind::ClassDeclaration(class) => class.bound_names(f),
+            _ => {}
+        },
+        _ => {
+            if let Some(decl) = stmt.as_declaration() {
+                decl.bound_names(f);
+            } else if let Some(decl) = stmt.as_module_declaration() {
+                decl.bound_names(f);
+            }
+        }
+    }
+}
+
+fn print(expr: &Expression) -> String {
+    let mut codegen = WhitespaceRemover::new();
+    expr.gen_expr(&mut codegen, Precedence::Lowest, Context::empty());
+    codegen.into_source_text()
+}
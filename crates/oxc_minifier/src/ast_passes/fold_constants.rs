@@ -2,11 +2,11 @@
 //!
 //! <https://github.com/google/closure-compiler/blob/master/src/com/google/javascript/jscomp/PeepholeFoldConstants.java>
 
-use std::{cmp::Ordering, mem};
+use std::{borrow::Cow, cmp::Ordering};
 
 use num_bigint::BigInt;
 
-use oxc_ast::{ast::*, visit::walk_mut, AstBuilder, Visit, VisitMut};
+use oxc_ast::{ast::*, visit::walk_mut, AstBuilder, VisitMut};
 use oxc_span::{GetSpan, Span, SPAN};
 use oxc_syntax::{
     number::NumberBase,
@@ -15,36 +15,76 @@ use oxc_syntax::{
 
 use crate::{
     ast_util::{
-        get_boolean_value, get_number_value, get_side_free_bigint_value,
+        evaluate_number_method, get_boolean_value, get_number_value, get_side_free_bigint_value,
         get_side_free_number_value, get_side_free_string_value, get_string_value, is_exact_int64,
         MayHaveSideEffects, NumberValue,
     },
+    chunk_boundary::is_chunk_boundary,
     keep_var::KeepVar,
+    numeric::is_printable_as_literal,
+    recycler::Recycler,
+    static_bindings::StaticBindings,
     tri::Tri,
     ty::Ty,
+    Env, PureGetters,
 };
 
 pub struct FoldConstants<'a> {
     ast: AstBuilder<'a>,
+    recycler: Recycler<'a>,
     evaluate: bool,
+    /// See [`CompressOptions::keep_chunk_boundaries`](crate::CompressOptions::keep_chunk_boundaries).
+    keep_chunk_boundaries: bool,
+    /// See [`CompressOptions::pure_getters`](crate::CompressOptions::pure_getters).
+    pure_getters: PureGetters,
+    /// See [`CompressOptions::env`](crate::CompressOptions::env).
+    env: Env,
+    /// Collected in [`FoldConstants::build`].
+    bindings: StaticBindings<'a>,
+    /// Number of enclosing `with` bodies, where `undefined`, `NaN` and `Infinity` may resolve to
+    /// properties of the object. Nothing is folded inside them.
+    with_depth: usize,
 }
 
 impl<'a> VisitMut<'a> for FoldConstants<'a> {
     fn visit_statement(&mut self, stmt: &mut Statement<'a>) {
         walk_mut::walk_statement(self, stmt);
-        self.fold_condition(stmt);
+        if self.with_depth == 0 {
+            self.fold_condition(stmt);
+        }
     }
 
     fn visit_expression(&mut self, expr: &mut Expression<'a>) {
+        if self.keep_chunk_boundaries && is_chunk_boundary(expr) {
+            return;
+        }
         walk_mut::walk_expression(self, expr);
-        self.fold_expression(expr);
-        self.fold_conditional_expression(expr);
+        if self.with_depth == 0 {
+            self.fold_expression(expr);
+            self.fold_conditional_expression(expr);
+        }
+    }
+
+    fn visit_with_statement(&mut self, stmt: &mut WithStatement<'a>) {
+        self.visit_expression(&mut stmt.object);
+        self.with_depth += 1;
+        self.visit_statement(&mut stmt.body);
+        self.with_depth -= 1;
     }
 }
 
 impl<'a> FoldConstants<'a> {
     pub fn new(ast: AstBuilder<'a>) -> Self {
-        Self { ast, evaluate: false }
+        Self {
+            ast,
+            recycler: Recycler::default(),
+            evaluate: false,
+            keep_chunk_boundaries: false,
+            pure_getters: PureGetters::Never,
+            env: Env::Neutral,
+            bindings: StaticBindings::default(),
+            with_depth: 0,
+        }
     }
 
     pub fn with_evaluate(mut self, yes: bool) -> Self {
@@ -52,7 +92,32 @@ impl<'a> FoldConstants<'a> {
         self
     }
 
+    pub fn with_chunk_boundaries(mut self, keep: bool) -> Self {
+        self.keep_chunk_boundaries = keep;
+        self
+    }
+
+    pub fn with_pure_getters(mut self, pure_getters: PureGetters) -> Self {
+        self.pure_getters = pure_getters;
+        self
+    }
+
+    pub fn with_env(mut self, env: Env) -> Self {
+        self.env = env;
+        self
+    }
+
+    pub fn with_recycler(mut self, recycler: Recycler<'a>) -> Self {
+        self.recycler = recycler;
+        self
+    }
+
+    pub fn into_recycler(self) -> Recycler<'a> {
+        self.recycler
+    }
+
     pub fn build(&mut self, program: &mut Program<'a>) {
+        self.bindings = StaticBindings::new(program, self.pure_getters, self.env);
         self.visit_program(program);
     }
 
@@ -69,42 +134,58 @@ impl<'a> FoldConstants<'a> {
             self.fold_if_statement(alternate);
             if matches!(alternate, Statement::EmptyStatement(_)) {
                 if_stmt.alternate = None;
+                self.recycler.mark_changed();
             }
         }
 
-        match self.fold_expression_and_get_boolean_value(&mut if_stmt.test) {
-            Some(true) => {
-                *stmt = self.ast.move_statement(&mut if_stmt.consequent);
-            }
-            Some(false) => {
-                *stmt = if let Some(alternate) = &mut if_stmt.alternate {
-                    self.ast.move_statement(alternate)
-                } else {
-                    // Keep hoisted `vars` from the consequent block.
-                    let mut keep_var = KeepVar::new(self.ast);
-                    keep_var.visit_statement(&if_stmt.consequent);
-                    keep_var
-                        .get_variable_declaration_statement()
-                        .unwrap_or_else(|| self.ast.statement_empty(SPAN))
-                };
-            }
-            None => {}
+        let Some(test) = self.fold_expression_and_get_boolean_value(&mut if_stmt.test) else {
+            return;
+        };
+        let if_stmt = self.take_if_statement(stmt);
+        let (taken, removed) = if test {
+            (Some(if_stmt.consequent), if_stmt.alternate)
+        } else {
+            (if_stmt.alternate, Some(if_stmt.consequent))
+        };
+        // Keep hoisted `vars` from the removed branch.
+        let new_stmt = KeepVar::keep_taken_branch(self.ast, taken, removed.as_ref())
+            .unwrap_or_else(|| self.recycler.empty_statement(self.ast, SPAN));
+        if let Some(removed) = removed {
+            self.recycler.recycle_statement(removed);
         }
+        self.recycler.replace_statement(stmt, new_stmt);
+    }
+
+    /// Moves the `if` statement out, leaving a recycled placeholder which is returned to the
+    /// recycler once `stmt` is replaced.
+    fn take_if_statement(&mut self, stmt: &mut Statement<'a>) -> IfStatement<'a> {
+        let Statement::IfStatement(if_stmt) = self.recycler.take_statement(self.ast, stmt) else {
+            unreachable!()
+        };
+        if_stmt.unbox()
     }
 
     fn fold_conditional_expression(&mut self, expr: &mut Expression<'a>) {
         let Expression::ConditionalExpression(conditional_expr) = expr else {
             return;
         };
-        match self.fold_expression_and_get_boolean_value(&mut conditional_expr.test) {
-            Some(true) => {
-                *expr = self.ast.move_expression(&mut conditional_expr.consequent);
-            }
-            Some(false) => {
-                *expr = self.ast.move_expression(&mut conditional_expr.alternate);
-            }
-            _ => {}
-        }
+        let Some(test) = self.fold_expression_and_get_boolean_value(&mut conditional_expr.test)
+        else {
+            return;
+        };
+        let Expression::ConditionalExpression(conditional_expr) =
+            self.recycler.take_expression(self.ast, expr)
+        else {
+            unreachable!()
+        };
+        let conditional_expr = conditional_expr.unbox();
+        let (taken, discarded) = if test {
+            (conditional_expr.consequent, conditional_expr.alternate)
+        } else {
+            (conditional_expr.alternate, conditional_expr.consequent)
+        };
+        self.recycler.recycle_expression(discarded);
+        self.recycler.replace_expression(expr, taken);
     }
 
     pub fn fold_expression<'b>(&mut self, expr: &'b mut Expression<'a>) {
@@ -117,12 +198,17 @@ impl<'a> FoldConstants<'a> {
                 | BinaryOperator::LessThan
                 | BinaryOperator::LessEqualThan
                 | BinaryOperator::GreaterThan
-                | BinaryOperator::GreaterEqualThan => self.try_fold_comparison(
-                    binary_expr.span,
-                    binary_expr.operator,
-                    &binary_expr.left,
-                    &binary_expr.right,
-                ),
+                | BinaryOperator::GreaterEqualThan => {
+                    self.try_fold_typeof_comparison(binary_expr).or_else(|| {
+                        self.try_fold_comparison(
+                            binary_expr.span,
+                            binary_expr.operator,
+                            &binary_expr.left,
+                            &binary_expr.right,
+                        )
+                    })
+                }
+                BinaryOperator::Instanceof => self.try_fold_instanceof(binary_expr),
                 BinaryOperator::ShiftLeft
                 | BinaryOperator::ShiftRight
                 | BinaryOperator::ShiftRightZeroFill => self.try_fold_shift(
@@ -137,18 +223,33 @@ impl<'a> FoldConstants<'a> {
                 // don't match (even though the produced code is valid). Additionally, We'll likely
                 // want to add `evaluate` checks for all constant folding, not just additions, but
                 // we're adding this here until a decision is made.
-                BinaryOperator::Addition if self.evaluate => {
-                    self.try_fold_addition(binary_expr.span, &binary_expr.left, &binary_expr.right)
-                }
+                BinaryOperator::Addition if self.evaluate => self
+                    .try_fold_addition(binary_expr.span, &binary_expr.left, &binary_expr.right)
+                    .or_else(|| self.try_fold_empty_string_concat(binary_expr))
+                    .or_else(|| self.try_fold_string_concat_association(binary_expr)),
                 _ => None,
             },
+            Expression::CallExpression(call_expr) => {
+                self.try_fold_array_is_array(call_expr).or_else(|| {
+                    if self.evaluate {
+                        self.try_fold_number_method(call_expr)
+                    } else {
+                        None
+                    }
+                })
+            }
             Expression::LogicalExpression(logic_expr) => {
                 self.try_fold_logical_expression(logic_expr)
             }
+            Expression::UnaryExpression(unary_expr)
+                if unary_expr.operator == UnaryOperator::Typeof =>
+            {
+                self.try_fold_typeof(unary_expr)
+            }
             _ => None,
         };
         if let Some(folded_expr) = folded_expr {
-            *expr = folded_expr;
+            self.recycler.replace_expression(expr, folded_expr);
         }
     }
 
@@ -159,7 +260,9 @@ impl<'a> FoldConstants<'a> {
         right: &'b Expression<'a>,
     ) -> Option<Expression<'a>> {
         // skip any potentially dangerous compressions
-        if left.may_have_side_effects() || right.may_have_side_effects() {
+        if left.may_have_side_effects(self.pure_getters)
+            || right.may_have_side_effects(self.pure_getters)
+        {
             return None;
         }
 
@@ -176,25 +279,225 @@ impl<'a> FoldConstants<'a> {
                 let right_string = get_string_value(right)?;
                 // let value = left_string.to_owned().
                 let value = left_string + right_string;
-                Some(self.ast.expression_string_literal(span, value))
+                let value = self.ast.atom(&value);
+                Some(self.recycler.string_literal(self.ast, span, value))
             },
 
+            // `1n + 1` throws, and bigint addition is not folded
+            (Ty::BigInt, _) | (_, Ty::BigInt) => None,
+
             // number addition
             (Ty::Number, _) | (_, Ty::Number)
                 // when added, booleans get treated as numbers where `true` is 1 and `false` is 0
                 | (Ty::Boolean, Ty::Boolean) => {
                 let left_number = get_number_value(left)?;
                 let right_number = get_number_value(right)?;
-                let Ok(value) = TryInto::<f64>::try_into(left_number + right_number) else { return None };
+                let value = (left_number + right_number).to_f64();
+                if !is_printable_as_literal(value) {
+                    return None;
+                }
                 // Float if value has a fractional part, otherwise Decimal
                 let number_base = if is_exact_int64(value) { NumberBase::Decimal } else { NumberBase::Float };
                 // todo: add raw &str
-                Some(self.ast.expression_numeric_literal(span, value, "", number_base))
+                Some(self.recycler.numeric_literal(self.ast, span, value, "", number_base))
             },
             _ => None
         }
     }
 
+    /// `String(x) + ""` and `"" + String(x)` -> `String(x)`, for any operand known to be a string.
+    fn try_fold_empty_string_concat(
+        &mut self,
+        binary_expr: &mut BinaryExpression<'a>,
+    ) -> Option<Expression<'a>> {
+        let is_empty_string = |expr: &Expression| matches!(expr, Expression::StringLiteral(lit) if lit.value.is_empty());
+        let operand = if is_empty_string(&binary_expr.right)
+            && self.bindings.ty(&binary_expr.left) == Ty::Str
+        {
+            &mut binary_expr.left
+        } else if is_empty_string(&binary_expr.left)
+            && self.bindings.ty(&binary_expr.right) == Ty::Str
+        {
+            &mut binary_expr.right
+        } else {
+            return None;
+        };
+        Some(self.recycler.take_expression(self.ast, operand))
+    }
+
+    /// `x + "a" + "b"` -> `x + "ab"` and `"a" + ("b" + x)` -> `"ab" + x`.
+    ///
+    /// The middle operand is a string, so both additions are concatenations and can be
+    /// regrouped. The constant operands are literals, which convert to the same string either way.
+    fn try_fold_string_concat_association(
+        &mut self,
+        binary_expr: &mut BinaryExpression<'a>,
+    ) -> Option<Expression<'a>> {
+        fn literal_string<'b>(expr: &'b Expression) -> Option<Cow<'b, str>> {
+            match expr {
+                Expression::StringLiteral(_)
+                | Expression::TemplateLiteral(_)
+                | Expression::NumericLiteral(_)
+                | Expression::BooleanLiteral(_)
+                | Expression::NullLiteral(_) => get_string_value(expr),
+                _ => None,
+            }
+        }
+        fn addition_with_string<'b, 'a>(
+            expr: &'b mut Expression<'a>,
+            string_on_left: bool,
+        ) -> Option<&'b mut BinaryExpression<'a>> {
+            let Expression::BinaryExpression(binary_expr) = expr else { return None };
+            let string = if string_on_left { &binary_expr.left } else { &binary_expr.right };
+            (binary_expr.operator == BinaryOperator::Addition && Ty::from(string) == Ty::Str)
+                .then_some(&mut **binary_expr)
+        }
+
+        let span = binary_expr.span;
+        if let Some(inner) = addition_with_string(&mut binary_expr.left, false) {
+            let value = literal_string(&inner.right)? + literal_string(&binary_expr.right)?;
+            let left = self.recycler.take_expression(self.ast, &mut inner.left);
+            let right = self.ast.expression_string_literal(SPAN, value);
+            Some(self.ast.expression_binary(span, left, BinaryOperator::Addition, right))
+        } else if let Some(inner) = addition_with_string(&mut binary_expr.right, true) {
+            let value = literal_string(&binary_expr.left)? + literal_string(&inner.left)?;
+            let left = self.ast.expression_string_literal(SPAN, value);
+            let right = self.recycler.take_expression(self.ast, &mut inner.right);
+            Some(self.ast.expression_binary(span, left, BinaryOperator::Addition, right))
+        } else {
+            None
+        }
+    }
+
+    /// `typeof 1` -> `"number"`, `typeof {}` -> `"object"`, for operands of a known type
+    /// which have no side effects.
+    ///
+    /// Identifiers are only folded here when the string is not longer, `typeof undefined` ->
+    /// `"undefined"`. Comparisons fold the others and properties of globals, see
+    /// [`FoldConstants::try_fold_typeof_comparison`].
+    fn try_fold_typeof(&self, unary_expr: &UnaryExpression<'a>) -> Option<Expression<'a>> {
+        let value = match &unary_expr.argument {
+            Expression::Identifier(ident) => self
+                .bindings
+                .type_of(ident)
+                .filter(|value| value.len() + 2 <= "typeof ".len() + ident.name.len())?,
+            argument => {
+                let value = type_of(argument)?;
+                // Creating a function has no side effects, whatever its body does.
+                if value != "function" && argument.may_have_side_effects(self.pure_getters) {
+                    return None;
+                }
+                value
+            }
+        };
+        Some(self.ast.expression_string_literal(unary_expr.span, value))
+    }
+
+    /// `typeof x === "undefined"` and `typeof f === "function"` for identifiers and properties of
+    /// globals whose type is known everywhere in the program, see
+    /// [`StaticBindings::type_of_expression`].
+    fn try_fold_typeof_comparison(
+        &self,
+        binary_expr: &BinaryExpression<'a>,
+    ) -> Option<Expression<'a>> {
+        let is_equality = match binary_expr.operator {
+            BinaryOperator::Equality | BinaryOperator::StrictEquality => true,
+            BinaryOperator::Inequality | BinaryOperator::StrictInequality => false,
+            _ => return None,
+        };
+        let (typeof_expr, other) = match (&binary_expr.left, &binary_expr.right) {
+            (Expression::UnaryExpression(unary_expr), other)
+            | (other, Expression::UnaryExpression(unary_expr))
+                if unary_expr.operator == UnaryOperator::Typeof =>
+            {
+                (unary_expr, other)
+            }
+            _ => return None,
+        };
+        let Expression::StringLiteral(value) = other else { return None };
+        let is_equal = value.value == self.bindings.type_of_expression(&typeof_expr.argument)?;
+        Some(self.ast.expression_boolean_literal(binary_expr.span, is_equal == is_equality))
+    }
+
+    /// `new C() instanceof C`, when constructing `C` has no side effects and returns an
+    /// instance of `C`.
+    fn try_fold_instanceof(&self, binary_expr: &BinaryExpression<'a>) -> Option<Expression<'a>> {
+        let Expression::NewExpression(new_expr) = binary_expr.left.without_parenthesized() else {
+            return None;
+        };
+        let (Expression::Identifier(callee), Expression::Identifier(constructor)) =
+            (&new_expr.callee, &binary_expr.right)
+        else {
+            return None;
+        };
+        (callee.name == constructor.name
+            && self.bindings.is_pure_construction(callee, &new_expr.arguments))
+        .then(|| self.ast.expression_boolean_literal(binary_expr.span, true))
+    }
+
+    /// `Array.isArray([])` and `Array.isArray({})`, for arguments which are known to be an array
+    /// or not and have no side effects.
+    fn try_fold_array_is_array(&self, call_expr: &CallExpression<'a>) -> Option<Expression<'a>> {
+        let Expression::StaticMemberExpression(member_expr) = &call_expr.callee else {
+            return None;
+        };
+        if call_expr.optional
+            || member_expr.optional
+            || member_expr.property.name != "isArray"
+            || !member_expr.object.is_specific_id("Array")
+            || !self.bindings.is_global("Array")
+        {
+            return None;
+        }
+        let [argument] = call_expr.arguments.as_slice() else { return None };
+        let argument = argument.as_expression()?;
+        let is_array = match argument {
+            Expression::ArrayExpression(_)
+                if !argument.may_have_side_effects(self.pure_getters) =>
+            {
+                true
+            }
+            Expression::ObjectExpression(_)
+                if !argument.may_have_side_effects(self.pure_getters) =>
+            {
+                false
+            }
+            Expression::NewExpression(new_expr) => match &new_expr.callee {
+                Expression::Identifier(ident)
+                    if self.bindings.is_pure_construction(ident, &new_expr.arguments) =>
+                {
+                    ident.name == "Array"
+                }
+                _ => return None,
+            },
+            _ => return None,
+        };
+        Some(self.ast.expression_boolean_literal(call_expr.span, is_array))
+    }
+
+    /// `(1.5).toFixed(2)` -> `"1.50"`, `(255).toString(16)` -> `"ff"`
+    fn try_fold_number_method(&self, call_expr: &CallExpression<'a>) -> Option<Expression<'a>> {
+        let Expression::StaticMemberExpression(member_expr) = &call_expr.callee else {
+            return None;
+        };
+        if Ty::from(&member_expr.object) != Ty::Number {
+            return None;
+        }
+        let value = get_side_free_number_value(&member_expr.object, self.pure_getters)?.to_f64();
+        let argument = match call_expr.arguments.as_slice() {
+            [] => None,
+            [argument] => {
+                match get_side_free_number_value(argument.as_expression()?, self.pure_getters)? {
+                    NumberValue::Number(num) => Some(num),
+                    _ => return None,
+                }
+            }
+            _ => return None,
+        };
+        let value = evaluate_number_method(value, &member_expr.property.name, argument)?;
+        Some(self.ast.expression_string_literal(call_expr.span, value))
+    }
+
     fn try_fold_comparison<'b>(
         &mut self,
         span: Span,
@@ -207,7 +510,7 @@ impl<'a> FoldConstants<'a> {
             Tri::False => false,
             Tri::Unknown => return None,
         };
-        Some(self.ast.expression_boolean_literal(span, value))
+        Some(self.recycler.boolean_literal(self.ast, span, value))
     }
 
     fn evaluate_comparison<'b>(
@@ -216,28 +519,28 @@ impl<'a> FoldConstants<'a> {
         left: &'b Expression<'a>,
         right: &'b Expression<'a>,
     ) -> Tri {
-        if left.may_have_side_effects() || right.may_have_side_effects() {
+        if left.may_have_side_effects(self.pure_getters)
+            || right.may_have_side_effects(self.pure_getters)
+        {
             return Tri::Unknown;
         }
 
         match op {
             BinaryOperator::Equality => self.try_abstract_equality_comparison(left, right),
             BinaryOperator::Inequality => self.try_abstract_equality_comparison(left, right).not(),
-            BinaryOperator::StrictEquality => Self::try_strict_equality_comparison(left, right),
+            BinaryOperator::StrictEquality => self.try_strict_equality_comparison(left, right),
             BinaryOperator::StrictInequality => {
-                Self::try_strict_equality_comparison(left, right).not()
-            }
-            BinaryOperator::LessThan => {
-                Self::try_abstract_relational_comparison(left, right, false)
+                self.try_strict_equality_comparison(left, right).not()
             }
+            BinaryOperator::LessThan => self.try_abstract_relational_comparison(left, right, false),
             BinaryOperator::GreaterThan => {
-                Self::try_abstract_relational_comparison(right, left, false)
+                self.try_abstract_relational_comparison(right, left, false)
             }
             BinaryOperator::LessEqualThan => {
-                Self::try_abstract_relational_comparison(right, left, true).not()
+                self.try_abstract_relational_comparison(right, left, true).not()
             }
             BinaryOperator::GreaterEqualThan => {
-                Self::try_abstract_relational_comparison(left, right, true).not()
+                self.try_abstract_relational_comparison(left, right, true).not()
             }
             _ => Tri::Unknown,
         }
@@ -253,23 +556,20 @@ impl<'a> FoldConstants<'a> {
         let right = Ty::from(right_expr);
         if left != Ty::Undetermined && right != Ty::Undetermined {
             if left == right {
-                return Self::try_strict_equality_comparison(left_expr, right_expr);
+                return self.try_strict_equality_comparison(left_expr, right_expr);
             }
             if matches!((left, right), (Ty::Null, Ty::Void) | (Ty::Void, Ty::Null)) {
                 return Tri::True;
             }
+            if matches!(left, Ty::Null | Ty::Void) || matches!(right, Ty::Null | Ty::Void) {
+                return Tri::False;
+            }
 
             if matches!((left, right), (Ty::Number, Ty::Str)) || matches!(right, Ty::Boolean) {
-                let right_number = get_side_free_number_value(right_expr);
-
-                if let Some(NumberValue::Number(num)) = right_number {
-                    let number_literal_expr = self.ast.expression_numeric_literal(
-                        right_expr.span(),
-                        num,
-                        num.to_string(),
-                        if num.fract() == 0.0 { NumberBase::Decimal } else { NumberBase::Float },
-                    );
+                let right_number = get_side_free_number_value(right_expr, self.pure_getters);
 
+                if let Some(num) = right_number {
+                    let number_literal_expr = self.number_value_literal(right_expr.span(), num);
                     return self.try_abstract_equality_comparison(left_expr, &number_literal_expr);
                 }
 
@@ -277,16 +577,10 @@ impl<'a> FoldConstants<'a> {
             }
 
             if matches!((left, right), (Ty::Str, Ty::Number)) || matches!(left, Ty::Boolean) {
-                let left_number = get_side_free_number_value(left_expr);
-
-                if let Some(NumberValue::Number(num)) = left_number {
-                    let number_literal_expr = self.ast.expression_numeric_literal(
-                        left_expr.span(),
-                        num,
-                        num.to_string(),
-                        if num.fract() == 0.0 { NumberBase::Decimal } else { NumberBase::Float },
-                    );
+                let left_number = get_side_free_number_value(left_expr, self.pure_getters);
 
+                if let Some(num) = left_number {
+                    let number_literal_expr = self.number_value_literal(left_expr.span(), num);
                     return self.try_abstract_equality_comparison(&number_literal_expr, right_expr);
                 }
 
@@ -294,12 +588,14 @@ impl<'a> FoldConstants<'a> {
             }
 
             if matches!(left, Ty::BigInt) || matches!(right, Ty::BigInt) {
-                let left_bigint = get_side_free_bigint_value(left_expr);
-                let right_bigint = get_side_free_bigint_value(right_expr);
+                let left_bigint = get_side_free_bigint_value(left_expr, self.pure_getters);
+                let right_bigint = get_side_free_bigint_value(right_expr, self.pure_getters);
 
                 if let (Some(l_big), Some(r_big)) = (left_bigint, right_bigint) {
                     return Tri::for_boolean(l_big.eq(&r_big));
                 }
+                // Strings which are not bigints, and numbers which are not safe integers.
+                return Tri::Unknown;
             }
 
             if matches!(left, Ty::Str | Ty::Number) && matches!(right, Ty::Object) {
@@ -315,8 +611,16 @@ impl<'a> FoldConstants<'a> {
         Tri::Unknown
     }
 
+    /// A literal which only stands for `value` while comparing, it may be infinite or `NaN`.
+    fn number_value_literal(&self, span: Span, value: NumberValue) -> Expression<'a> {
+        let value = value.to_f64();
+        let base = if is_exact_int64(value) { NumberBase::Decimal } else { NumberBase::Float };
+        self.ast.expression_numeric_literal(span, value, "", base)
+    }
+
     /// <https://tc39.es/ecma262/#sec-abstract-relational-comparison>
     fn try_abstract_relational_comparison<'b>(
+        &self,
         left_expr: &'b Expression<'a>,
         right_expr: &'b Expression<'a>,
         will_negative: bool,
@@ -326,8 +630,8 @@ impl<'a> FoldConstants<'a> {
 
         // First, check for a string comparison.
         if left == Ty::Str && right == Ty::Str {
-            let left_string = get_side_free_string_value(left_expr);
-            let right_string = get_side_free_string_value(right_expr);
+            let left_string = get_side_free_string_value(left_expr, self.pure_getters);
+            let right_string = get_side_free_string_value(right_expr, self.pure_getters);
             if let (Some(left_string), Some(right_string)) = (left_string, right_string) {
                 // In JS, browsers parse \v differently. So do not compare strings if one contains \v.
                 if left_string.contains('\u{000B}') || right_string.contains('\u{000B}') {
@@ -354,11 +658,11 @@ impl<'a> FoldConstants<'a> {
             }
         }
 
-        let left_bigint = get_side_free_bigint_value(left_expr);
-        let right_bigint = get_side_free_bigint_value(right_expr);
+        let left_bigint = get_side_free_bigint_value(left_expr, self.pure_getters);
+        let right_bigint = get_side_free_bigint_value(right_expr, self.pure_getters);
 
-        let left_num = get_side_free_number_value(left_expr);
-        let right_num = get_side_free_number_value(right_expr);
+        let left_num = get_side_free_number_value(left_expr, self.pure_getters);
+        let right_num = get_side_free_number_value(right_expr, self.pure_getters);
 
         match (left_bigint, right_bigint, left_num, right_num) {
             // Next, try to evaluate based on the value of the node. Try comparing as BigInts first.
@@ -370,8 +674,7 @@ impl<'a> FoldConstants<'a> {
                 (NumberValue::NaN, _) | (_, NumberValue::NaN) => {
                     return Tri::for_boolean(will_negative);
                 }
-                (NumberValue::Number(l), NumberValue::Number(r)) => return Tri::for_boolean(l < r),
-                _ => {}
+                (l, r) => return Tri::for_boolean(l.to_f64() < r.to_f64()),
             },
             // Finally, try comparisons between BigInt and Number.
             (Some(l_big), _, _, Some(r_num)) => {
@@ -426,6 +729,7 @@ impl<'a> FoldConstants<'a> {
 
     /// <https://tc39.es/ecma262/#sec-strict-equality-comparison>
     fn try_strict_equality_comparison<'b>(
+        &self,
         left_expr: &'b Expression<'a>,
         right_expr: &'b Expression<'a>,
     ) -> Tri {
@@ -438,8 +742,8 @@ impl<'a> FoldConstants<'a> {
             }
             return match left {
                 Ty::Number => {
-                    let left_number = get_side_free_number_value(left_expr);
-                    let right_number = get_side_free_number_value(right_expr);
+                    let left_number = get_side_free_number_value(left_expr, self.pure_getters);
+                    let right_number = get_side_free_number_value(right_expr, self.pure_getters);
 
                     if let (Some(l_num), Some(r_num)) = (left_number, right_number) {
                         if l_num.is_nan() || r_num.is_nan() {
@@ -452,8 +756,8 @@ impl<'a> FoldConstants<'a> {
                     Tri::Unknown
                 }
                 Ty::Str => {
-                    let left_string = get_side_free_string_value(left_expr);
-                    let right_string = get_side_free_string_value(right_expr);
+                    let left_string = get_side_free_string_value(left_expr, self.pure_getters);
+                    let right_string = get_side_free_string_value(right_expr, self.pure_getters);
                     if let (Some(left_string), Some(right_string)) = (left_string, right_string) {
                         // In JS, browsers parse \v differently. So do not compare strings if one contains \v.
                         if left_string.contains('\u{000B}') || right_string.contains('\u{000B}') {
@@ -482,6 +786,14 @@ impl<'a> FoldConstants<'a> {
 
                     Tri::Unknown
                 }
+                Ty::BigInt => {
+                    let left_bigint = get_side_free_bigint_value(left_expr, self.pure_getters);
+                    let right_bigint = get_side_free_bigint_value(right_expr, self.pure_getters);
+                    match (left_bigint, right_bigint) {
+                        (Some(l_big), Some(r_big)) => Tri::for_boolean(l_big == r_big),
+                        _ => Tri::Unknown,
+                    }
+                }
                 Ty::Void | Ty::Null => Tri::True,
                 _ => Tri::Unknown,
             };
@@ -505,8 +817,8 @@ impl<'a> FoldConstants<'a> {
         left: &'b Expression<'a>,
         right: &'b Expression<'a>,
     ) -> Option<Expression<'a>> {
-        let left_num = get_side_free_number_value(left);
-        let right_num = get_side_free_number_value(right);
+        let left_num = get_side_free_number_value(left, self.pure_getters);
+        let right_num = get_side_free_number_value(right, self.pure_getters);
 
         if let (Some(NumberValue::Number(left_val)), Some(NumberValue::Number(right_val))) =
             (left_num, right_num)
@@ -537,10 +849,12 @@ impl<'a> FoldConstants<'a> {
                 _ => unreachable!("Unknown binary operator {:?}", op),
             };
 
-            return Some(self.ast.expression_numeric_literal(
+            let raw = self.ast.str(&result_val.to_string());
+            return Some(self.recycler.numeric_literal(
+                self.ast,
                 span,
                 result_val,
-                result_val.to_string(),
+                raw,
                 NumberBase::Decimal,
             ));
         }
@@ -566,7 +880,7 @@ impl<'a> FoldConstants<'a> {
                 || (!boolean_value && op == LogicalOperator::And)
             {
                 return Some(self.move_out_expression(&mut logical_expr.left));
-            } else if !logical_expr.left.may_have_side_effects() {
+            } else if !logical_expr.left.may_have_side_effects(self.pure_getters) {
                 // (FALSE || x) => x
                 // (TRUE && x) => x
                 return Some(self.move_out_expression(&mut logical_expr.right));
@@ -586,7 +900,7 @@ impl<'a> FoldConstants<'a> {
                 let left_child_right_boolean = get_boolean_value(&left_child.right);
                 let left_child_op = left_child.operator;
                 if let Some(right_boolean) = left_child_right_boolean {
-                    if !left_child.right.may_have_side_effects() {
+                    if !left_child.right.may_have_side_effects(self.pure_getters) {
                         // a || false || b => a || b
                         // a && true && b => a && b
                         if !right_boolean && left_child_op == LogicalOperator::Or
@@ -660,8 +974,7 @@ impl<'a> FoldConstants<'a> {
     }
 
     fn move_out_expression(&mut self, expr: &mut Expression<'a>) -> Expression<'a> {
-        let null_expr = self.ast.expression_null_literal(expr.span());
-        mem::replace(expr, null_expr)
+        self.recycler.take_expression(self.ast, expr)
     }
 
     /// ported from [closure compiler](https://github.com/google/closure-compiler/blob/master/src/com/google/javascript/jscomp/PeepholeMinimizeConditions.java#L401-L435)
@@ -686,3 +999,33 @@ impl<'a> FoldConstants<'a> {
         }
     }
 }
+
+/// The value of `typeof expr`, if the type of `expr` is known from its syntax.
+fn type_of(expr: &Expression) -> Option<&'static str> {
+    let value = match expr.without_parenthesized() {
+        Expression::NumericLiteral(_) => "number",
+        Expression::StringLiteral(_) | Expression::TemplateLiteral(_) => "string",
+        Expression::BooleanLiteral(_) => "boolean",
+        Expression::BigIntLiteral(_) => "bigint",
+        Expression::NullLiteral(_)
+        | Expression::RegExpLiteral(_)
+        | Expression::ObjectExpression(_)
+        | Expression::ArrayExpression(_) => "object",
+        // Not classes, whose static blocks and initializers run when they are created.
+        Expression::FunctionExpression(_) | Expression::ArrowFunctionExpression(_) => "function",
+        Expression::UnaryExpression(unary_expr) => match unary_expr.operator {
+            UnaryOperator::Void => "undefined",
+            UnaryOperator::LogicalNot => "boolean",
+            UnaryOperator::Typeof => "string",
+            // `-x` is a bigint when `x` is one.
+            UnaryOperator::UnaryNegation => match &unary_expr.argument {
+                Expression::NumericLiteral(_) => "number",
+                Expression::BigIntLiteral(_) => "bigint",
+                _ => return None,
+            },
+            _ => return None,
+        },
+        _ => return None,
+    };
+    Some(value)
+}
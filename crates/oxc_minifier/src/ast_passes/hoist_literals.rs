@@ -0,0 +1,316 @@
+use oxc_ast::{ast::*, visit::walk_mut, AstBuilder, VisitMut};
+use oxc_span::{Atom, SPAN};
+use oxc_syntax::operator::UnaryOperator;
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::{
+    static_bindings::{global_path, StaticBindings},
+    Env, PureGetters,
+};
+
+/// Replace string literals and chains of properties of globals which are repeated often enough
+/// with variables declared at the top level, when that is shorter once the variables are mangled,
+/// `f("mousedown"); g("mousedown"); h("mousedown")` →
+/// `var _str = "mousedown"; f(_str); g(_str); h(_str)`.
+///
+/// Strings which name modules, `require("x")` and `import("x")`, and property keys are kept.
+/// Chains are only hoisted when their path is known to the [`Env`], and never when they are
+/// called, which would change `this`, or assigned or deleted anywhere in the program. Nothing
+/// inside `with` bodies is replaced, where the variables may resolve to properties of the object.
+///
+/// Enabled by `compress.hoist_strings` and `compress.hoist_props`
+pub struct HoistLiterals<'a> {
+    ast: AstBuilder<'a>,
+    strings: bool,
+    props: bool,
+    pure_getters: PureGetters,
+    env: Env,
+}
+
+/// Mangled names are assumed to be this long when deciding whether a variable is shorter.
+const MANGLED_NAME_LENGTH: usize = 2;
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Literal<'a> {
+    Str(Atom<'a>),
+    /// The path of a chain, `Math.PI`.
+    Chain(String),
+}
+
+impl<'a> Literal<'a> {
+    /// Length of the literal when it is printed, without the escapes of strings.
+    fn len(&self) -> usize {
+        match self {
+            Self::Str(value) => value.len() + 2,
+            Self::Chain(path) => path.len(),
+        }
+    }
+}
+
+impl<'a> HoistLiterals<'a> {
+    pub fn new(ast: AstBuilder<'a>, strings: bool, props: bool) -> Self {
+        Self { ast, strings, props, pure_getters: PureGetters::Never, env: Env::Neutral }
+    }
+
+    pub fn with_pure_getters(mut self, pure_getters: PureGetters) -> Self {
+        self.pure_getters = pure_getters;
+        self
+    }
+
+    pub fn with_env(mut self, env: Env) -> Self {
+        self.env = env;
+        self
+    }
+
+    pub fn build(&mut self, program: &mut Program<'a>) {
+        let bindings = StaticBindings::new(program, self.pure_getters, self.env);
+        let mut counter = LiteralFinder {
+            ast: self.ast,
+            strings: self.strings,
+            props: self.props,
+            bindings: &bindings,
+            env: self.env,
+            literals: FxHashMap::default(),
+            order: vec![],
+            assigned: FxHashSet::default(),
+            names: FxHashSet::default(),
+            has_eval: false,
+            names_only: 0,
+            aliases: None,
+        };
+        counter.visit_program(program);
+        if counter.has_eval {
+            return;
+        }
+
+        let mut names = counter.names;
+        let mut aliases = FxHashMap::default();
+        let mut declarators = self.ast.vec();
+        for literal in counter.order {
+            let count = counter.literals[&literal];
+            let len = literal.len();
+            if let Literal::Chain(path) = &literal {
+                let is_assigned = counter.assigned.iter().any(|assigned| {
+                    path == assigned
+                        || path
+                            .strip_prefix(assigned.as_str())
+                            .is_some_and(|rest| rest.starts_with('.'))
+                });
+                if is_assigned {
+                    continue;
+                }
+            }
+            let saved = count * len.saturating_sub(MANGLED_NAME_LENGTH);
+            if saved <= "var =;".len() + MANGLED_NAME_LENGTH + len {
+                continue;
+            }
+            let base = match &literal {
+                Literal::Str(_) => "_str".to_string(),
+                Literal::Chain(path) => format!("_{}", path.replace('.', "_")),
+            };
+            let name = (0..=names.len())
+                .map(|i| if i == 0 { base.clone() } else { format!("{base}{i}") })
+                .find(|name| !names.contains(name))
+                .unwrap();
+            names.insert(name.clone());
+            let name = self.ast.atom(&name);
+            let init = match &literal {
+                Literal::Str(value) => self.ast.expression_string_literal(SPAN, value.clone()),
+                Literal::Chain(path) => self.chain(path),
+            };
+            declarators.push(self.ast.variable_declarator(
+                SPAN,
+                VariableDeclarationKind::Var,
+                self.ast.binding_pattern(
+                    self.ast.binding_pattern_kind_binding_identifier(SPAN, name.clone()),
+                    None::<TSTypeAnnotation>,
+                    false,
+                ),
+                Some(init),
+                false,
+            ));
+            aliases.insert(literal, name);
+        }
+        if declarators.is_empty() {
+            return;
+        }
+
+        let mut replacer = LiteralFinder {
+            literals: FxHashMap::default(),
+            order: vec![],
+            assigned: FxHashSet::default(),
+            names: FxHashSet::default(),
+            aliases: Some(aliases),
+            ..counter
+        };
+        replacer.visit_program(program);
+        let decl =
+            self.ast.declaration_variable(SPAN, VariableDeclarationKind::Var, declarators, false);
+        program.body.insert(0, Statement::from(decl));
+    }
+
+    fn chain(&self, path: &str) -> Expression<'a> {
+        let mut names = path.split('.');
+        let root = self.ast.atom(names.next().unwrap());
+        let mut expr = self.ast.expression_identifier_reference(SPAN, root);
+        for name in names {
+            let property = self.ast.identifier_name(SPAN, self.ast.atom(name));
+            expr = self.ast.member_expression_static(SPAN, expr, property, false).into();
+        }
+        expr
+    }
+}
+
+/// Counts the literals which can be hoisted, or replaces them with `aliases`.
+struct LiteralFinder<'a, 'b> {
+    ast: AstBuilder<'a>,
+    strings: bool,
+    props: bool,
+    bindings: &'b StaticBindings<'a>,
+    env: Env,
+    literals: FxHashMap<Literal<'a>, usize>,
+    /// The literals in the order they are first found, for stable names.
+    order: Vec<Literal<'a>>,
+    /// Paths of chains which are assigned or deleted.
+    assigned: FxHashSet<String>,
+    /// Names bound or referenced anywhere in the program, which the variables must not use.
+    names: FxHashSet<String>,
+    has_eval: bool,
+    /// Depth of `with` bodies and counted chains, where names are collected but nothing is
+    /// counted or replaced.
+    names_only: usize,
+    aliases: Option<FxHashMap<Literal<'a>, Atom<'a>>>,
+}
+
+impl<'a, 'b> LiteralFinder<'a, 'b> {
+    fn literal(&self, expr: &Expression<'a>) -> Option<Literal<'a>> {
+        if self.names_only > 0 {
+            return None;
+        }
+        match expr {
+            Expression::StringLiteral(lit) if self.strings => Some(Literal::Str(lit.value.clone())),
+            Expression::StaticMemberExpression(_) if self.props => {
+                let (root, path) = global_path(expr)?;
+                (self.bindings.is_global(root) && self.env.exists(root) && self.env.exists(&path))
+                    .then_some(Literal::Chain(path))
+            }
+            _ => None,
+        }
+    }
+
+    /// Visits `expr` only to collect the names in it.
+    fn collect_names(&mut self, expr: &mut Expression<'a>) {
+        self.names_only += 1;
+        self.visit_expression(expr);
+        self.names_only -= 1;
+    }
+
+    /// Visits the parts of a callee, a tag or a `delete` argument, which is not replaced itself.
+    fn visit_member_parts(&mut self, expr: &mut Expression<'a>) {
+        match expr {
+            Expression::StaticMemberExpression(member_expr) => {
+                self.visit_expression(&mut member_expr.object);
+            }
+            expr => self.visit_expression(expr),
+        }
+    }
+}
+
+impl<'a, 'b> VisitMut<'a> for LiteralFinder<'a, 'b> {
+    fn visit_expression(&mut self, expr: &mut Expression<'a>) {
+        let Some(literal) = self.literal(expr) else {
+            walk_mut::walk_expression(self, expr);
+            return;
+        };
+        if let Some(aliases) = &self.aliases {
+            if let Some(name) = aliases.get(&literal) {
+                *expr = self.ast.expression_identifier_reference(SPAN, name.clone());
+            } else if let Expression::StaticMemberExpression(member_expr) = expr {
+                self.visit_expression(&mut member_expr.object);
+            }
+            return;
+        }
+        let count = self.literals.entry(literal.clone()).or_default();
+        if *count == 0 {
+            self.order.push(literal);
+        }
+        *count += 1;
+        // Chains inside a hoisted chain would not be replaced, so they are not counted.
+        if let Expression::StaticMemberExpression(member_expr) = expr {
+            self.collect_names(&mut member_expr.object);
+        }
+    }
+
+    fn visit_call_expression(&mut self, expr: &mut CallExpression<'a>) {
+        if matches!(&expr.callee, Expression::Identifier(ident) if ident.name == "require") {
+            for argument in expr.arguments.iter_mut() {
+                if !matches!(argument, Argument::StringLiteral(_)) {
+                    self.visit_argument(argument);
+                }
+            }
+        } else {
+            self.visit_arguments(&mut expr.arguments);
+        }
+        self.visit_member_parts(&mut expr.callee);
+    }
+
+    fn visit_tagged_template_expression(&mut self, expr: &mut TaggedTemplateExpression<'a>) {
+        self.visit_member_parts(&mut expr.tag);
+        self.visit_template_literal(&mut expr.quasi);
+    }
+
+    fn visit_import_expression(&mut self, expr: &mut ImportExpression<'a>) {
+        if !matches!(expr.source, Expression::StringLiteral(_)) {
+            self.visit_expression(&mut expr.source);
+        }
+        self.visit_expressions(&mut expr.arguments);
+    }
+
+    fn visit_unary_expression(&mut self, expr: &mut UnaryExpression<'a>) {
+        if expr.operator != UnaryOperator::Delete {
+            walk_mut::walk_unary_expression(self, expr);
+            return;
+        }
+        if let Some(path) = expr.argument.as_member_expression().and_then(member_path) {
+            self.assigned.insert(path);
+        }
+        self.visit_member_parts(&mut expr.argument);
+    }
+
+    fn visit_simple_assignment_target(&mut self, target: &mut SimpleAssignmentTarget<'a>) {
+        if let Some(path) = target.as_member_expression().and_then(member_path) {
+            self.assigned.insert(path);
+        }
+        walk_mut::walk_simple_assignment_target(self, target);
+    }
+
+    fn visit_property_key(&mut self, key: &mut PropertyKey<'a>) {
+        if !matches!(key, PropertyKey::StringLiteral(_)) {
+            walk_mut::walk_property_key(self, key);
+        }
+    }
+
+    fn visit_with_statement(&mut self, stmt: &mut WithStatement<'a>) {
+        self.visit_expression(&mut stmt.object);
+        self.names_only += 1;
+        self.visit_statement(&mut stmt.body);
+        self.names_only -= 1;
+    }
+
+    fn visit_identifier_reference(&mut self, ident: &mut IdentifierReference<'a>) {
+        if ident.name == "eval" {
+            self.has_eval = true;
+        }
+        self.names.insert(ident.name.to_string());
+    }
+
+    fn visit_binding_identifier(&mut self, ident: &mut BindingIdentifier<'a>) {
+        self.names.insert(ident.name.to_string());
+    }
+}
+
+/// The path of a member expression whose object is a chain, `Math.PI` for `Math["PI"]`.
+fn member_path(member_expr: &MemberExpression) -> Option<String> {
+    let (_, path) = global_path(member_expr.object())?;
+    Some(format!("{path}.{}", member_expr.static_property_name()?))
+}
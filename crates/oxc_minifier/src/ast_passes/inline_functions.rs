@@ -0,0 +1,492 @@
+use oxc_allocator::Vec;
+use oxc_ast::{ast::*, visit::walk_mut, AstBuilder, AstKind, VisitMut};
+use oxc_semantic::{Reference, ReferenceId, Semantic, SemanticBuilder, SymbolId};
+use oxc_span::GetSpan;
+use oxc_syntax::operator::UnaryOperator;
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use super::inline_variables::is_constant;
+use crate::Inline;
+
+/// Largest number of nodes in the returned expression of a function which is inlined.
+const MAX_NODES: usize = 8;
+
+/// Inline calls of functions whose body is a single `return` of a small expression,
+/// `function f(a) { return a + 1 } g(f(2))` → `g(2 + 1)`.
+///
+/// The returned expression may only contain literals, identifiers, operators, member access and
+/// calls, so it has no scopes of its own and does not use `this` or `arguments`. A call is
+/// inlined when every binding the expression references resolves to the same binding at the
+/// call site, and its arguments are literals or bindings which are never reassigned, so they
+/// have no side effects and the same value wherever the parameters are used. Missing arguments
+/// become `void 0`.
+///
+/// Functions are declared at the top of a function or module, or bound by a `const` declaration
+/// of an arrow function or function expression, and are never reassigned. Their declarations are
+/// removed when all their references were inlined, no inlined body still calls them and they are
+/// not exported. Calls in the body of a `with` statement are kept.
+///
+/// Enabled by `compress.inline`
+pub struct InlineFunctions<'a> {
+    ast: AstBuilder<'a>,
+    level: Inline,
+    functions: std::vec::Vec<Function<'a>>,
+    /// Inlined calls, by the reference of their callee, and the index of the called function.
+    calls: FxHashMap<ReferenceId, usize>,
+    /// Functions whose declarations are removed.
+    removed: FxHashSet<SymbolId>,
+}
+
+/// A function which can be inlined.
+struct Function<'a> {
+    symbol_id: SymbolId,
+    /// A copy of the returned expression.
+    body: Expression<'a>,
+    /// References to parameters in `body`, and the index of the parameter.
+    parameters: FxHashMap<ReferenceId, usize>,
+}
+
+impl<'a> VisitMut<'a> for InlineFunctions<'a> {
+    fn visit_statements(&mut self, stmts: &mut Vec<'a, Statement<'a>>) {
+        walk_mut::walk_statements(self, stmts);
+        if self.removed.is_empty() {
+            return;
+        }
+        for stmt in stmts.iter_mut() {
+            if let Statement::VariableDeclaration(decl) = stmt {
+                decl.declarations.retain(|declarator| !self.is_removed(&declarator.id));
+            }
+        }
+        stmts.retain(|stmt| match stmt {
+            Statement::FunctionDeclaration(func) => !func
+                .id
+                .as_ref()
+                .and_then(|id| id.symbol_id.get())
+                .is_some_and(|id| self.removed.contains(&id)),
+            Statement::VariableDeclaration(decl) => !decl.declarations.is_empty(),
+            _ => true,
+        });
+    }
+
+    fn visit_expression(&mut self, expr: &mut Expression<'a>) {
+        if !self.try_inline(expr) {
+            walk_mut::walk_expression(self, expr);
+        }
+    }
+}
+
+impl<'a> InlineFunctions<'a> {
+    pub fn new(ast: AstBuilder<'a>, level: Inline) -> Self {
+        Self {
+            ast,
+            level,
+            functions: vec![],
+            calls: FxHashMap::default(),
+            removed: FxHashSet::default(),
+        }
+    }
+
+    pub fn build(&mut self, program: &mut Program<'a>) {
+        if self.level == Inline::Never {
+            return;
+        }
+        {
+            let semantic = SemanticBuilder::new("", program.source_type).build(program).semantic;
+            if semantic.scopes().root_unresolved_references().contains_key("eval") {
+                return;
+            }
+            for symbol_id in semantic.symbols().iter() {
+                self.add_function(&semantic, symbol_id);
+            }
+        }
+        if !self.calls.is_empty() {
+            self.visit_program(program);
+        }
+    }
+
+    fn is_removed(&self, id: &BindingPattern) -> bool {
+        let BindingPatternKind::BindingIdentifier(id) = &id.kind else { return false };
+        id.symbol_id.get().is_some_and(|id| self.removed.contains(&id))
+    }
+
+    fn add_function(&mut self, semantic: &Semantic<'a>, symbol_id: SymbolId) {
+        let symbols = semantic.symbols();
+        let nodes = semantic.nodes();
+        if !symbols.get_redeclarations(symbol_id).is_empty()
+            || symbols.get_resolved_references(symbol_id).any(Reference::is_write)
+        {
+            return;
+        }
+        let is_script_top_level = !semantic.source_type().is_module()
+            && symbols.get_scope_id(symbol_id) == semantic.scopes().root_scope_id();
+        if is_script_top_level {
+            return;
+        }
+        let declaration = symbols.get_declaration(symbol_id);
+        let parent = nodes.parent_kind(declaration);
+        let (span, params, body, is_removable) = match nodes.kind(declaration) {
+            AstKind::Function(func) if func.is_declaration() => {
+                if !matches!(
+                    parent,
+                    Some(
+                        AstKind::Program(_)
+                            | AstKind::FunctionBody(_)
+                            | AstKind::ExportNamedDeclaration(_)
+                            | AstKind::ExportDefaultDeclaration(_)
+                    )
+                ) || func.r#async
+                    || func.generator
+                {
+                    return;
+                }
+                let Some(body) = &func.body else { return };
+                let is_removable =
+                    matches!(parent, Some(AstKind::Program(_) | AstKind::FunctionBody(_)));
+                (func.span, &func.params, returned_expression(body, false), is_removable)
+            }
+            AstKind::VariableDeclarator(declarator)
+                if declarator.kind == VariableDeclarationKind::Const =>
+            {
+                let (params, body) = match &declarator.init {
+                    Some(Expression::ArrowFunctionExpression(arrow)) if !arrow.r#async => {
+                        (&arrow.params, returned_expression(&arrow.body, arrow.expression))
+                    }
+                    Some(Expression::FunctionExpression(func))
+                        if !func.r#async && !func.generator =>
+                    {
+                        let Some(body) = &func.body else { return };
+                        (&func.params, returned_expression(body, false))
+                    }
+                    _ => return,
+                };
+                let is_exported = nodes
+                    .parent_id(declaration)
+                    .and_then(|id| nodes.parent_kind(id))
+                    .is_some_and(|kind| matches!(kind, AstKind::ExportNamedDeclaration(_)));
+                (declarator.span, params, body, !is_exported)
+            }
+            _ => return,
+        };
+        if !params.items.is_empty() && self.level < Inline::Arguments {
+            return;
+        }
+        if params.rest.is_some() {
+            return;
+        }
+        let Some(expr) = body else { return };
+        if expression_size(expr).map_or(true, |size| size > MAX_NODES) {
+            return;
+        }
+
+        // Parameters, and the references in `expr` to them or to bindings outside of the function.
+        let mut parameter_ids = vec![];
+        for param in &params.items {
+            let BindingPatternKind::BindingIdentifier(id) = &param.pattern.kind else { return };
+            let Some(id) = id.symbol_id.get() else { return };
+            parameter_ids.push(id);
+        }
+        let mut parameters = FxHashMap::default();
+        for (index, id) in parameter_ids.iter().enumerate() {
+            for &reference_id in symbols.get_resolved_reference_ids(*id) {
+                parameters.insert(reference_id, index);
+            }
+        }
+        let mut free = vec![];
+        let mut is_valid = true;
+        each_identifier(expr, &mut |ident| {
+            let symbol_id =
+                ident.reference_id.get().and_then(|id| symbols.get_reference(id).symbol_id());
+            match symbol_id {
+                Some(id) if parameter_ids.contains(&id) => {}
+                // Bindings of the function itself, such as the name of a function expression.
+                Some(id) if span.contains_inclusive(symbols.get_span(id)) => is_valid = false,
+                _ => free.push((ident.name.as_str(), symbol_id)),
+            }
+        });
+        if !is_valid {
+            return;
+        }
+
+        // `f()()` calls the result without `this`, unlike `a.b()`, and `delete a.b` deletes.
+        let is_member = matches!(
+            expr.without_parenthesized(),
+            Expression::StaticMemberExpression(_) | Expression::ComputedMemberExpression(_)
+        );
+        let is_hoisted = matches!(nodes.kind(declaration), AstKind::Function(_));
+        let mut calls = vec![];
+        let reference_ids = symbols.get_resolved_reference_ids(symbol_id);
+        for &reference_id in reference_ids {
+            let reference = symbols.get_reference(reference_id);
+            let node = nodes.get_node(reference.node_id());
+            let ident_span = node.kind().span();
+            let Some(call_id) = nodes.parent_id(node.id()) else { continue };
+            let AstKind::CallExpression(call) = nodes.kind(call_id) else { continue };
+            if call.callee.span() != ident_span
+                || call.optional
+                || span.contains_inclusive(call.span)
+                // `const` bindings throw before their declaration.
+                || (!is_hoisted && call.span.start < span.end)
+            {
+                continue;
+            }
+            match nodes.parent_kind(call_id) {
+                Some(AstKind::CallExpression(outer))
+                    if outer.callee.span() == call.span && is_member =>
+                {
+                    continue
+                }
+                Some(AstKind::TaggedTemplateExpression(_)) if is_member => continue,
+                // `typeof x` does not throw when `x` is not declared.
+                Some(AstKind::UnaryExpression(unary))
+                    if matches!(unary.operator, UnaryOperator::Delete | UnaryOperator::Typeof) =>
+                {
+                    continue
+                }
+                _ => {}
+            }
+            // Names in the body of a `with` statement may resolve to properties of its object.
+            if nodes
+                .ancestors(call_id)
+                .any(|id| matches!(nodes.kind(id), AstKind::WithStatement(_)))
+            {
+                continue;
+            }
+            let scope_id = node.scope_id();
+            if free.iter().any(|&(name, id)| semantic.scopes().find_binding(scope_id, name) != id) {
+                continue;
+            }
+            let arguments_are_values = call.arguments.iter().all(|argument| {
+                argument.as_expression().is_some_and(|argument| match argument {
+                    Expression::Identifier(ident) => ident
+                        .reference_id
+                        .get()
+                        .and_then(|id| symbols.get_reference(id).symbol_id())
+                        .is_some_and(|id| {
+                            symbols
+                                .get_resolved_references(id)
+                                .all(|reference| !reference.is_write())
+                        }),
+                    _ => is_constant(argument),
+                })
+            });
+            if arguments_are_values {
+                calls.push(reference_id);
+            }
+        }
+        if calls.is_empty() {
+            return;
+        }
+        if is_removable && calls.len() == reference_ids.len() {
+            self.removed.insert(symbol_id);
+        }
+        let index = self.functions.len();
+        self.calls.extend(calls.into_iter().map(|reference_id| (reference_id, index)));
+        let body = copy_expression(self.ast, expr, &mut |_| None);
+        self.functions.push(Function { symbol_id, body, parameters });
+    }
+
+    fn try_inline(&mut self, expr: &mut Expression<'a>) -> bool {
+        let Expression::CallExpression(call) = expr else { return false };
+        let Expression::Identifier(callee) = &call.callee else { return false };
+        let Some(&index) = callee.reference_id.get().and_then(|id| self.calls.get(&id)) else {
+            return false;
+        };
+        let function = &self.functions[index];
+        let ast = self.ast;
+        let arguments =
+            call.arguments.iter().filter_map(Argument::as_expression).collect::<std::vec::Vec<_>>();
+        let inlined = copy_expression(ast, &function.body, &mut |ident| {
+            let index = *ident.reference_id.get().and_then(|id| function.parameters.get(&id))?;
+            Some(match arguments.get(index) {
+                Some(argument) => copy_expression(ast, argument, &mut |_| None),
+                None => ast.void_0(),
+            })
+        });
+        // Calls in the inlined copy are not inlined again, since the names in the body of
+        // their callee may resolve differently here, so their callees are kept.
+        let mut kept = vec![];
+        each_identifier(&inlined, &mut |ident| {
+            if let Some(&index) = ident.reference_id.get().and_then(|id| self.calls.get(&id)) {
+                kept.push(self.functions[index].symbol_id);
+            }
+        });
+        for symbol_id in kept {
+            self.removed.remove(&symbol_id);
+        }
+        *expr = inlined;
+        true
+    }
+}
+
+/// The argument of the only statement of `body`, if it is a `return` statement, or the
+/// expression of an arrow function without braces.
+fn returned_expression<'s, 'a>(
+    body: &'s FunctionBody<'a>,
+    is_expression: bool,
+) -> Option<&'s Expression<'a>> {
+    if !body.directives.is_empty() {
+        return None;
+    }
+    match body.statements.as_slice() {
+        [Statement::ReturnStatement(stmt)] => stmt.argument.as_ref(),
+        [Statement::ExpressionStatement(stmt)] if is_expression => Some(&stmt.expression),
+        _ => None,
+    }
+}
+
+/// The number of nodes of `expr`, if it only contains the expressions which are inlined.
+fn expression_size(expr: &Expression) -> Option<usize> {
+    let size = match expr {
+        Expression::BooleanLiteral(_)
+        | Expression::NullLiteral(_)
+        | Expression::NumericLiteral(_)
+        | Expression::BigIntLiteral(_)
+        | Expression::StringLiteral(_) => 1,
+        Expression::Identifier(ident) if ident.name != "arguments" => 1,
+        Expression::ParenthesizedExpression(expr) => expression_size(&expr.expression)?,
+        Expression::UnaryExpression(expr) if expr.operator != UnaryOperator::Delete => {
+            1 + expression_size(&expr.argument)?
+        }
+        Expression::BinaryExpression(expr) => {
+            1 + expression_size(&expr.left)? + expression_size(&expr.right)?
+        }
+        Expression::LogicalExpression(expr) => {
+            1 + expression_size(&expr.left)? + expression_size(&expr.right)?
+        }
+        Expression::ConditionalExpression(expr) => {
+            1 + expression_size(&expr.test)?
+                + expression_size(&expr.consequent)?
+                + expression_size(&expr.alternate)?
+        }
+        Expression::StaticMemberExpression(expr) if !expr.optional => {
+            1 + expression_size(&expr.object)?
+        }
+        Expression::ComputedMemberExpression(expr) if !expr.optional => {
+            1 + expression_size(&expr.object)? + expression_size(&expr.expression)?
+        }
+        Expression::CallExpression(call) if !call.optional => {
+            let mut size = 1 + expression_size(&call.callee)?;
+            for argument in &call.arguments {
+                size += expression_size(argument.as_expression()?)?;
+            }
+            size
+        }
+        _ => return None,
+    };
+    Some(size)
+}
+
+/// Calls `f` with the identifiers of `expr`, which only contains the expressions counted by
+/// [`expression_size`].
+fn each_identifier<'a, F: FnMut(&IdentifierReference<'a>)>(expr: &Expression<'a>, f: &mut F) {
+    match expr {
+        Expression::Identifier(ident) => f(ident),
+        Expression::ParenthesizedExpression(expr) => each_identifier(&expr.expression, f),
+        Expression::UnaryExpression(expr) => each_identifier(&expr.argument, f),
+        Expression::BinaryExpression(expr) => {
+            each_identifier(&expr.left, f);
+            each_identifier(&expr.right, f);
+        }
+        Expression::LogicalExpression(expr) => {
+            each_identifier(&expr.left, f);
+            each_identifier(&expr.right, f);
+        }
+        Expression::ConditionalExpression(expr) => {
+            each_identifier(&expr.test, f);
+            each_identifier(&expr.consequent, f);
+            each_identifier(&expr.alternate, f);
+        }
+        Expression::StaticMemberExpression(expr) => each_identifier(&expr.object, f),
+        Expression::ComputedMemberExpression(expr) => {
+            each_identifier(&expr.object, f);
+            each_identifier(&expr.expression, f);
+        }
+        Expression::CallExpression(call) => {
+            each_identifier(&call.callee, f);
+            for argument in call.arguments.iter().filter_map(Argument::as_expression) {
+                each_identifier(argument, f);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Copies `expr`, which only contains the expressions counted by [`expression_size`], replacing
+/// the identifiers for which `substitute` returns an expression.
+fn copy_expression<'a, F>(
+    ast: AstBuilder<'a>,
+    expr: &Expression<'a>,
+    substitute: &mut F,
+) -> Expression<'a>
+where
+    F: FnMut(&IdentifierReference<'a>) -> Option<Expression<'a>>,
+{
+    match expr {
+        Expression::BooleanLiteral(lit) => Expression::BooleanLiteral(ast.alloc((**lit).clone())),
+        Expression::NullLiteral(lit) => Expression::NullLiteral(ast.alloc((**lit).clone())),
+        Expression::NumericLiteral(lit) => Expression::NumericLiteral(ast.alloc((**lit).clone())),
+        Expression::BigIntLiteral(lit) => Expression::BigIntLiteral(ast.alloc((**lit).clone())),
+        Expression::StringLiteral(lit) => Expression::StringLiteral(ast.alloc((**lit).clone())),
+        Expression::Identifier(ident) => substitute(ident)
+            .unwrap_or_else(|| Expression::Identifier(ast.alloc((**ident).clone()))),
+        Expression::ParenthesizedExpression(expr) => ast.expression_parenthesized(
+            expr.span,
+            copy_expression(ast, &expr.expression, substitute),
+        ),
+        Expression::UnaryExpression(expr) => ast.expression_unary(
+            expr.span,
+            expr.operator,
+            copy_expression(ast, &expr.argument, substitute),
+        ),
+        Expression::BinaryExpression(expr) => {
+            let left = copy_expression(ast, &expr.left, substitute);
+            let right = copy_expression(ast, &expr.right, substitute);
+            ast.expression_binary(expr.span, left, expr.operator, right)
+        }
+        Expression::LogicalExpression(expr) => {
+            let left = copy_expression(ast, &expr.left, substitute);
+            let right = copy_expression(ast, &expr.right, substitute);
+            ast.expression_logical(expr.span, left, expr.operator, right)
+        }
+        Expression::ConditionalExpression(expr) => {
+            let test = copy_expression(ast, &expr.test, substitute);
+            let consequent = copy_expression(ast, &expr.consequent, substitute);
+            let alternate = copy_expression(ast, &expr.alternate, substitute);
+            ast.expression_conditional(expr.span, test, consequent, alternate)
+        }
+        Expression::StaticMemberExpression(expr) => {
+            let object = copy_expression(ast, &expr.object, substitute);
+            ast.expression_member(ast.member_expression_static(
+                expr.span,
+                object,
+                expr.property.clone(),
+                false,
+            ))
+        }
+        Expression::ComputedMemberExpression(expr) => {
+            let object = copy_expression(ast, &expr.object, substitute);
+            let property = copy_expression(ast, &expr.expression, substitute);
+            ast.expression_member(
+                ast.member_expression_computed(expr.span, object, property, false),
+            )
+        }
+        Expression::CallExpression(call) => {
+            let callee = copy_expression(ast, &call.callee, substitute);
+            let arguments = ast.vec_from_iter(
+                call.arguments
+                    .iter()
+                    .filter_map(Argument::as_expression)
+                    .map(|argument| Argument::from(copy_expression(ast, argument, substitute)))
+                    .collect::<std::vec::Vec<_>>(),
+            );
+            ast.expression_call(
+                call.span,
+                arguments,
+                callee,
+                Option::<TSTypeParameterInstantiation>::None,
+                false,
+            )
+        }
+        _ => unreachable!(),
+    }
+}
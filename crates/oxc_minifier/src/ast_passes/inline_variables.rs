@@ -0,0 +1,378 @@
+use oxc_allocator::Vec;
+use oxc_ast::{ast::*, visit::walk_mut, VisitMut};
+use oxc_semantic::{ReferenceId, SemanticBuilder, SymbolId};
+use oxc_syntax::{
+    operator::{AssignmentOperator, UnaryOperator},
+    scope::ScopeFlags,
+};
+use rustc_hash::FxHashMap;
+
+use crate::{ast_util::MayHaveSideEffects, PureGetters};
+
+/// Inline `const` and `let` bindings which are read exactly once and never written,
+/// `const a = f(); return a` → `return f()`.
+///
+/// Primitive literals are inlined into any later statement of the same block, including nested
+/// functions, `const a = 1; g(() => a)` → `g(() => 1)`. Other initializers are only inlined into
+/// the next statement, when the use is evaluated before anything with side effects, so moving
+/// the initializer does not change the order of side effects: `const a = f(); g(a)` stays, since
+/// `g` is read before `f` is called. Initializers with side effects are only inlined when nothing
+/// but literals is evaluated before the use.
+///
+/// Uses before the declaration, which throw in the temporal dead zone, are never inlined, nor
+/// uses in function declarations, which may be called before it, nor uses in the body of `with`
+/// statements, which may resolve to a property of its object. Top level bindings of scripts are
+/// visible to other scripts, and nothing is inlined when the program calls `eval`.
+///
+/// Enabled by `compress.inline_variables`
+pub struct InlineVariables {
+    pure_getters: PureGetters,
+    /// Bindings which are read exactly once, and their reference.
+    candidates: FxHashMap<SymbolId, ReferenceId>,
+    is_module: bool,
+}
+
+impl<'a> VisitMut<'a> for InlineVariables {
+    fn visit_program(&mut self, program: &mut Program<'a>) {
+        if self.is_module {
+            self.visit_statements(&mut program.body);
+        } else {
+            walk_mut::walk_statements(self, &mut program.body);
+        }
+    }
+
+    fn visit_statements(&mut self, stmts: &mut Vec<'a, Statement<'a>>) {
+        walk_mut::walk_statements(self, stmts);
+        self.inline_in_statements(stmts);
+    }
+}
+
+impl InlineVariables {
+    pub fn new(pure_getters: PureGetters) -> Self {
+        Self { pure_getters, candidates: FxHashMap::default(), is_module: false }
+    }
+
+    pub fn build(&mut self, program: &mut Program<'_>) {
+        {
+            let semantic = SemanticBuilder::new("", program.source_type).build(program).semantic;
+            if semantic.scopes().root_unresolved_references().contains_key("eval") {
+                return;
+            }
+            let symbols = semantic.symbols();
+            for symbol_id in symbols.iter() {
+                if !symbols.get_redeclarations(symbol_id).is_empty() {
+                    continue;
+                }
+                let [reference_id] = symbols.get_resolved_reference_ids(symbol_id).as_slice()
+                else {
+                    continue;
+                };
+                let reference = symbols.get_reference(*reference_id);
+                if reference.is_read() && !reference.is_write() {
+                    self.candidates.insert(symbol_id, *reference_id);
+                }
+            }
+        }
+        self.is_module = program.source_type.is_module();
+        self.visit_program(program);
+    }
+
+    fn inline_in_statements<'a>(&mut self, stmts: &mut Vec<'a, Statement<'a>>) {
+        let mut i = 0;
+        while i < stmts.len() {
+            let (head, tail) = stmts.split_at_mut(i + 1);
+            let Statement::VariableDeclaration(decl) = &mut head[i] else {
+                i += 1;
+                continue;
+            };
+            if !self.inline_last_declarator(decl, tail) {
+                i += 1;
+            } else if decl.declarations.is_empty() {
+                stmts.remove(i);
+            }
+        }
+    }
+
+    /// Inlines the last declarator of `decl` into `stmts`, the statements after it, and removes
+    /// it. Earlier declarators are evaluated before it, so they can not move past it.
+    fn inline_last_declarator<'a>(
+        &mut self,
+        decl: &mut VariableDeclaration<'a>,
+        stmts: &mut [Statement<'a>],
+    ) -> bool {
+        if !matches!(decl.kind, VariableDeclarationKind::Const | VariableDeclarationKind::Let) {
+            return false;
+        }
+        let Some(declarator) = decl.declarations.last_mut() else { return false };
+        let BindingPatternKind::BindingIdentifier(id) = &declarator.id.kind else { return false };
+        let Some(&reference_id) = id.symbol_id.get().and_then(|id| self.candidates.get(&id)) else {
+            return false;
+        };
+        let Some(init) = &declarator.init else { return false };
+
+        let is_constant = is_constant(init);
+        let has_side_effects = init.may_have_side_effects(self.pure_getters);
+        let mut value = declarator.init.take();
+        if is_constant {
+            let mut replacer = Replacer { reference_id, value: &mut value };
+            for stmt in stmts.iter_mut() {
+                replacer.visit_statement(stmt);
+            }
+        } else if let Some(stmt) = stmts.first_mut() {
+            let mut replacer = OrderedReplacer {
+                reference_id,
+                value: &mut value,
+                pure_getters: self.pure_getters,
+                has_side_effects,
+            };
+            replacer.statement(stmt);
+        }
+        if value.is_some() {
+            decl.declarations.last_mut().unwrap().init = value;
+            return false;
+        }
+        decl.declarations.pop();
+        true
+    }
+}
+
+/// Primitive literals, which can be evaluated anywhere: they do not depend on bindings, and are
+/// not objects with an identity.
+pub(super) fn is_constant(expr: &Expression) -> bool {
+    match expr {
+        Expression::BooleanLiteral(_)
+        | Expression::NullLiteral(_)
+        | Expression::NumericLiteral(_)
+        | Expression::BigIntLiteral(_)
+        | Expression::StringLiteral(_) => true,
+        Expression::TemplateLiteral(template) => template.is_no_substitution_template(),
+        Expression::UnaryExpression(unary_expr) => {
+            unary_expr.operator != UnaryOperator::Delete && is_constant(&unary_expr.argument)
+        }
+        _ => false,
+    }
+}
+
+/// Replaces the reference `reference_id` with `value`, except in function declarations and in
+/// the body of `with` statements, where it may resolve to a property of the object.
+struct Replacer<'v, 'a> {
+    reference_id: ReferenceId,
+    value: &'v mut Option<Expression<'a>>,
+}
+
+impl<'v, 'a> Replacer<'v, 'a> {
+    fn replace(&mut self, expr: &mut Expression<'a>) -> bool {
+        let Expression::Identifier(ident) = expr else { return false };
+        if ident.reference_id.get() != Some(self.reference_id) {
+            return false;
+        }
+        let Some(value) = self.value.take() else { return false };
+        *expr = value;
+        true
+    }
+}
+
+impl<'v, 'a> VisitMut<'a> for Replacer<'v, 'a> {
+    fn visit_expression(&mut self, expr: &mut Expression<'a>) {
+        if self.value.is_some() && !self.replace(expr) {
+            walk_mut::walk_expression(self, expr);
+        }
+    }
+
+    fn visit_object_property(&mut self, prop: &mut ObjectProperty<'a>) {
+        walk_mut::walk_object_property(self, prop);
+        if prop.shorthand && !matches!(prop.value, Expression::Identifier(_)) {
+            prop.shorthand = false;
+        }
+    }
+
+    fn visit_function(&mut self, func: &mut Function<'a>, flags: ScopeFlags) {
+        if !func.is_declaration() {
+            walk_mut::walk_function(self, func, flags);
+        }
+    }
+
+    fn visit_with_statement(&mut self, stmt: &mut WithStatement<'a>) {
+        self.visit_expression(&mut stmt.object);
+    }
+}
+
+enum Substitution {
+    Replaced,
+    /// The reference is not in the expression, which can be evaluated before the value.
+    NotFound,
+    /// The value can not be moved to the reference, or after the expression.
+    Blocked,
+}
+
+/// Replaces the reference `reference_id` with `value` when it is evaluated before anything the
+/// value may depend on or affect, following the order of evaluation.
+struct OrderedReplacer<'v, 'a> {
+    reference_id: ReferenceId,
+    value: &'v mut Option<Expression<'a>>,
+    pure_getters: PureGetters,
+    /// Whether evaluating the value has side effects, so it can not move past reads either.
+    has_side_effects: bool,
+}
+
+impl<'v, 'a> OrderedReplacer<'v, 'a> {
+    fn statement(&mut self, stmt: &mut Statement<'a>) {
+        match stmt {
+            Statement::ExpressionStatement(stmt) => {
+                self.expression(&mut stmt.expression);
+            }
+            Statement::ReturnStatement(stmt) => {
+                if let Some(argument) = &mut stmt.argument {
+                    self.expression(argument);
+                }
+            }
+            Statement::ThrowStatement(stmt) => {
+                self.expression(&mut stmt.argument);
+            }
+            Statement::IfStatement(stmt) => {
+                self.expression(&mut stmt.test);
+            }
+            Statement::SwitchStatement(stmt) => {
+                self.expression(&mut stmt.discriminant);
+            }
+            Statement::VariableDeclaration(decl) => {
+                for declarator in decl.declarations.iter_mut() {
+                    if !declarator.id.kind.is_binding_identifier() {
+                        return;
+                    }
+                    if let Some(init) = &mut declarator.init {
+                        if !self.sequence(init) {
+                            return;
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Visits `expr`, which is evaluated before the expressions after it, and returns whether
+    /// they may be visited as well.
+    fn sequence(&mut self, expr: &mut Expression<'a>) -> bool {
+        match self.expression(expr) {
+            Substitution::NotFound => self.can_precede(expr),
+            Substitution::Replaced | Substitution::Blocked => false,
+        }
+    }
+
+    /// Whether `expr` can be evaluated before the value instead of after it.
+    fn can_precede(&self, expr: &Expression<'a>) -> bool {
+        if self.has_side_effects {
+            is_constant(expr)
+        } else {
+            !expr.may_have_side_effects(self.pure_getters)
+        }
+    }
+
+    fn expression(&mut self, expr: &mut Expression<'a>) -> Substitution {
+        if let Expression::Identifier(ident) = expr {
+            if ident.reference_id.get() == Some(self.reference_id) {
+                *expr = self.value.take().unwrap();
+                return Substitution::Replaced;
+            }
+            return Substitution::NotFound;
+        }
+        let operands: std::vec::Vec<&mut Expression<'a>> = match expr {
+            Expression::ParenthesizedExpression(expr) => vec![&mut expr.expression],
+            Expression::BinaryExpression(expr) => {
+                let BinaryExpression { left, right, .. } = &mut **expr;
+                vec![left, right]
+            }
+            // Only the operands which are always evaluated.
+            Expression::LogicalExpression(expr) => vec![&mut expr.left],
+            Expression::ConditionalExpression(expr) => vec![&mut expr.test],
+            Expression::UnaryExpression(expr) if expr.operator != UnaryOperator::Delete => {
+                vec![&mut expr.argument]
+            }
+            Expression::AwaitExpression(expr) => vec![&mut expr.argument],
+            Expression::StaticMemberExpression(expr) => vec![&mut expr.object],
+            Expression::ComputedMemberExpression(expr) => {
+                let ComputedMemberExpression { object, expression, .. } = &mut **expr;
+                vec![object, expression]
+            }
+            Expression::SequenceExpression(expr) => expr.expressions.iter_mut().collect(),
+            Expression::TemplateLiteral(template) => template.expressions.iter_mut().collect(),
+            Expression::CallExpression(call) => {
+                let CallExpression { callee, arguments, optional, .. } = &mut **call;
+                if *optional {
+                    vec![callee]
+                } else {
+                    let mut operands = vec![callee];
+                    for argument in arguments.iter_mut() {
+                        let Some(argument) = argument.as_expression_mut() else { break };
+                        operands.push(argument);
+                    }
+                    operands
+                }
+            }
+            Expression::NewExpression(new) => {
+                let NewExpression { callee, arguments, .. } = &mut **new;
+                let mut operands = vec![callee];
+                for argument in arguments.iter_mut() {
+                    let Some(argument) = argument.as_expression_mut() else { break };
+                    operands.push(argument);
+                }
+                operands
+            }
+            Expression::ArrayExpression(array) => {
+                let mut operands = vec![];
+                for element in array.elements.iter_mut() {
+                    let Some(element) = element.as_expression_mut() else { break };
+                    operands.push(element);
+                }
+                operands
+            }
+            Expression::ObjectExpression(object) => {
+                return self.object_properties(&mut object.properties);
+            }
+            Expression::AssignmentExpression(assignment) => {
+                // A simple target is resolved before the right side, but not evaluated.
+                if assignment.operator != AssignmentOperator::Assign
+                    || !matches!(assignment.left, AssignmentTarget::AssignmentTargetIdentifier(_))
+                {
+                    return Substitution::Blocked;
+                }
+                vec![&mut assignment.right]
+            }
+            _ => return Substitution::NotFound,
+        };
+        let count = operands.len();
+        for (i, operand) in operands.into_iter().enumerate() {
+            match self.expression(operand) {
+                Substitution::NotFound if i + 1 == count => {}
+                Substitution::NotFound if self.can_precede(operand) => {}
+                Substitution::NotFound | Substitution::Blocked => return Substitution::Blocked,
+                Substitution::Replaced => return Substitution::Replaced,
+            }
+        }
+        Substitution::NotFound
+    }
+
+    fn object_properties(
+        &mut self,
+        properties: &mut Vec<'a, ObjectPropertyKind<'a>>,
+    ) -> Substitution {
+        for property in properties.iter_mut() {
+            let ObjectPropertyKind::ObjectProperty(prop) = property else {
+                return Substitution::Blocked;
+            };
+            if prop.computed {
+                return Substitution::Blocked;
+            }
+            match self.expression(&mut prop.value) {
+                Substitution::Replaced => {
+                    prop.shorthand = false;
+                    return Substitution::Replaced;
+                }
+                Substitution::NotFound if self.can_precede(&prop.value) => {}
+                Substitution::NotFound | Substitution::Blocked => return Substitution::Blocked,
+            }
+        }
+        Substitution::NotFound
+    }
+}
@@ -1,15 +1,36 @@
 #![allow(clippy::wildcard_imports)]
 
+mod array_loops;
 mod collapse;
+mod collection_literals;
+mod dedupe_functions;
 mod fold_constants;
+mod hoist_literals;
+mod inline_functions;
+mod inline_variables;
 mod remove_dead_code;
 mod remove_syntax;
+mod remove_unused;
+mod reorder_declarations;
 mod replace_global_defines;
 mod substitute_alternate_syntax;
+mod substitute_globals;
 
+pub use array_loops::ArrayLoops;
 pub use collapse::Collapse;
+pub use collection_literals::CollectionLiterals;
+pub use dedupe_functions::DedupeFunctions;
 pub use fold_constants::FoldConstants;
+pub use hoist_literals::HoistLiterals;
+pub use inline_functions::InlineFunctions;
+pub use inline_variables::InlineVariables;
 pub use remove_dead_code::RemoveDeadCode;
 pub use remove_syntax::RemoveSyntax;
-pub use replace_global_defines::{ReplaceGlobalDefines, ReplaceGlobalDefinesConfig};
+pub(crate) use remove_unused::DeclarationEffects;
+pub use remove_unused::RemoveUnused;
+pub use reorder_declarations::ReorderDeclarations;
+pub use replace_global_defines::{
+    ReplaceGlobalDefines, ReplaceGlobalDefinesConfig, ReplaceGlobalDefinesReturn,
+};
 pub use substitute_alternate_syntax::SubstituteAlternateSyntax;
+pub use substitute_globals::SubstituteGlobals;
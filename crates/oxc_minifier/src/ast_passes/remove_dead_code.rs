@@ -1,7 +1,26 @@
+use std::cell::Cell;
+
 use oxc_allocator::Vec;
-use oxc_ast::{ast::*, visit::walk_mut, AstBuilder, Visit, VisitMut};
+use oxc_ast::{
+    ast::*,
+    visit::{walk, walk_mut},
+    AstBuilder, Visit, VisitMut,
+};
+use oxc_span::{Atom, GetSpan, Span, SPAN};
+use oxc_syntax::{
+    operator::{BinaryOperator, LogicalOperator, UnaryOperator},
+    scope::{ScopeFlags, ScopeId},
+};
+use rustc_hash::{FxHashMap, FxHashSet};
 
-use crate::keep_var::KeepVar;
+use crate::{
+    ast_util::{get_boolean_value, MayHaveSideEffects},
+    chunk_boundary::is_chunk_boundary,
+    commonjs,
+    keep_var::KeepVar,
+    recycler::Recycler,
+    CompressOptions,
+};
 
 /// Remove Dead Code from the AST.
 ///
@@ -10,61 +29,1182 @@ use crate::keep_var::KeepVar;
 /// See `KeepVar` at the end of this file for `var` hoisting logic.
 pub struct RemoveDeadCode<'a> {
     ast: AstBuilder<'a>,
+    recycler: Recycler<'a>,
+    options: CompressOptions,
+    /// Binding and reference counts, collected when `compress.unused_generators` or
+    /// `compress.commonjs` is enabled.
+    name_usage: Option<NameUsage<'a>>,
+    scope_flags: std::vec::Vec<ScopeFlags>,
+    /// Number of enclosing `with` bodies.
+    with_depth: usize,
+    /// Spans of the callees of the enclosing calls and tagged templates, and of the arguments of
+    /// `delete`, where a member expression would behave differently than any other value.
+    member_positions: std::vec::Vec<Span>,
+    /// No binding named `undefined` is declared in the program, and nothing calls `eval`.
+    is_undefined_global: bool,
+    is_module: bool,
 }
 
 impl<'a> VisitMut<'a> for RemoveDeadCode<'a> {
+    fn enter_scope(&mut self, flags: ScopeFlags, _scope_id: &Cell<Option<ScopeId>>) {
+        self.scope_flags.push(flags);
+    }
+
+    fn leave_scope(&mut self) {
+        self.scope_flags.pop();
+    }
+
     fn visit_statements(&mut self, stmts: &mut Vec<'a, Statement<'a>>) {
-        stmts.retain(|stmt| !matches!(stmt, Statement::EmptyStatement(_)));
+        // Folded before the statements are visited, so the empty statements they leave are
+        // removed and `if (true) return` ends the list.
+        for stmt in stmts.iter_mut() {
+            self.fold_if_statement(stmt);
+        }
+        self.recycler
+            .retain_statements(stmts, |stmt| !matches!(stmt, Statement::EmptyStatement(_)));
         self.dead_code_elimintation(stmts);
+        self.remove_unused_generators(stmts);
+        if self.options.iife {
+            self.unwrap_iife_statements(stmts);
+        }
         walk_mut::walk_statements(self, stmts);
+        if self.options.labels {
+            self.flatten_blocks(stmts);
+        }
+    }
+
+    fn visit_statement(&mut self, stmt: &mut Statement<'a>) {
+        self.fold_if_statement(stmt);
+        walk_mut::walk_statement(self, stmt);
+        if self.options.labels {
+            self.compress_labeled_statement(stmt);
+        }
     }
 
     fn visit_expression(&mut self, expr: &mut Expression<'a>) {
+        if self.options.keep_chunk_boundaries && is_chunk_boundary(expr) {
+            return;
+        }
+        let member_position = match expr {
+            Expression::CallExpression(call) => Some(call.callee.without_parenthesized().span()),
+            Expression::TaggedTemplateExpression(expr) => {
+                Some(expr.tag.without_parenthesized().span())
+            }
+            Expression::UnaryExpression(expr) if expr.operator == UnaryOperator::Delete => {
+                Some(expr.argument.without_parenthesized().span())
+            }
+            _ => None,
+        };
+        if let Some(span) = member_position {
+            self.member_positions.push(span);
+        }
         walk_mut::walk_expression(self, expr);
+        if member_position.is_some() {
+            self.member_positions.pop();
+        }
+        if self.options.iife {
+            self.unwrap_iife(expr);
+        }
+        self.fold_conditional_expression(expr);
+        self.fold_logical_expression(expr);
+    }
+
+    fn visit_function_body(&mut self, body: &mut FunctionBody<'a>) {
+        walk_mut::walk_function_body(self, body);
+        self.remove_tail_returns(&mut body.statements);
+    }
+
+    fn visit_class(&mut self, class: &mut Class<'a>) {
+        walk_mut::walk_class(self, class);
+        self.remove_dead_class_elements(&mut class.body, class.super_class.is_some());
+    }
+
+    fn visit_with_statement(&mut self, stmt: &mut WithStatement<'a>) {
+        self.visit_expression(&mut stmt.object);
+        self.with_depth += 1;
+        self.visit_statement(&mut stmt.body);
+        self.with_depth -= 1;
     }
 }
 
 impl<'a> RemoveDeadCode<'a> {
-    pub fn new(ast: AstBuilder<'a>) -> Self {
-        Self { ast }
+    pub fn new(ast: AstBuilder<'a>, options: CompressOptions) -> Self {
+        Self {
+            ast,
+            recycler: Recycler::default(),
+            options,
+            name_usage: None,
+            scope_flags: std::vec![],
+            with_depth: 0,
+            member_positions: std::vec![],
+            is_undefined_global: false,
+            is_module: false,
+        }
+    }
+
+    #[must_use]
+    pub fn with_recycler(mut self, recycler: Recycler<'a>) -> Self {
+        self.recycler = recycler;
+        self
+    }
+
+    pub fn into_recycler(self) -> Recycler<'a> {
+        self.recycler
     }
 
     pub fn build(&mut self, program: &mut Program<'a>) {
+        self.is_module = program.source_type.is_module();
+        let mut undefined_bindings = UndefinedBindings::default();
+        undefined_bindings.visit_program(program);
+        self.is_undefined_global = !undefined_bindings.may_shadow;
+        let is_commonjs = self.options.commonjs && !self.is_module;
+        if self.options.unused_generators || self.options.unawaited_async || is_commonjs {
+            let mut name_usage = NameUsage::default();
+            name_usage.visit_program(program);
+            self.name_usage = Some(name_usage);
+        }
+        if is_commonjs {
+            self.remove_commonjs_dead_code(&mut program.body);
+        }
         self.visit_program(program);
     }
 
-    /// Removes dead code thats comes after `return` statements after inlining `if` statements
+    /// Removes the statements after a statement which always ends the list, which is `return`,
+    /// `throw`, `break` or `continue`, a block containing one, or an `if` statement whose branches
+    /// both end.
+    ///
+    /// Function declarations are hoisted and kept, and `var` declarations are kept without their
+    /// initializers: `return f(); var a = 1; function f() {}` => `return f(); function f() {} var a`
     fn dead_code_elimintation(&mut self, stmts: &mut Vec<'a, Statement<'a>>) {
-        // Remove code after `return` and `throw` statements
-        let mut index = None;
-        'outer: for (i, stmt) in stmts.iter().enumerate() {
-            if matches!(stmt, Statement::ReturnStatement(_) | Statement::ThrowStatement(_)) {
-                index.replace(i);
-                break;
-            }
-            // Double check block statements folded by if statements above
-            if let Statement::BlockStatement(block_stmt) = stmt {
-                for stmt in &block_stmt.body {
-                    if matches!(stmt, Statement::ReturnStatement(_) | Statement::ThrowStatement(_))
-                    {
-                        index.replace(i);
-                        break 'outer;
+        let Some(index) = stmts.iter().position(Self::is_terminating) else { return };
+        if stmts.len() == index + 1 {
+            return;
+        }
+        let mut keep_var = KeepVar::new(self.ast);
+        let mut functions = std::vec![];
+        for stmt in stmts.drain(index + 1..) {
+            match stmt {
+                Statement::FunctionDeclaration(_) => functions.push(stmt),
+                stmt => {
+                    keep_var.visit_statement(&stmt);
+                    self.recycler.recycle_statement(stmt);
+                }
+            }
+        }
+        self.recycler.mark_changed();
+        stmts.extend(functions);
+        if let Some(stmt) = keep_var.get_variable_declaration_statement() {
+            stmts.push(stmt);
+        }
+    }
+
+    fn is_terminating(stmt: &Statement<'a>) -> bool {
+        match stmt {
+            Statement::ReturnStatement(_)
+            | Statement::ThrowStatement(_)
+            | Statement::BreakStatement(_)
+            | Statement::ContinueStatement(_) => true,
+            Statement::BlockStatement(block) => block.body.iter().any(Self::is_terminating),
+            Statement::IfStatement(if_stmt) => {
+                if_stmt.alternate.as_ref().is_some_and(|alternate| {
+                    Self::is_terminating(&if_stmt.consequent) && Self::is_terminating(alternate)
+                })
+            }
+            _ => false,
+        }
+    }
+
+    /// Removes `return` and `return void 0` at the end of a function body, including at the end
+    /// of blocks and `if` branches there, `function f() { if (a) { b(); return } }` =>
+    /// `function f() { if (a) b() }`.
+    fn remove_tail_returns(&mut self, stmts: &mut Vec<'a, Statement<'a>>) {
+        while stmts.last().is_some_and(|stmt| self.is_empty_return(stmt)) {
+            if let Some(stmt) = stmts.pop() {
+                self.recycler.recycle_statement(stmt);
+            }
+            self.recycler.mark_changed();
+        }
+        if let Some(last) = stmts.last_mut() {
+            self.remove_tail_return(last);
+        }
+    }
+
+    fn remove_tail_return(&mut self, stmt: &mut Statement<'a>) {
+        match stmt {
+            Statement::ReturnStatement(_) if self.is_empty_return(stmt) => {
+                let stmt = self.recycler.take_statement(self.ast, stmt);
+                self.recycler.recycle_statement(stmt);
+            }
+            Statement::BlockStatement(block) => self.remove_tail_returns(&mut block.body),
+            Statement::IfStatement(if_stmt) => {
+                let consequent_emptied = self.remove_branch_tail_return(&mut if_stmt.consequent);
+                if let Some(alternate) = &mut if_stmt.alternate {
+                    if self.remove_branch_tail_return(alternate) {
+                        if let Some(alternate) = if_stmt.alternate.take() {
+                            self.recycler.recycle_statement(alternate);
+                        }
                     }
                 }
+                // `if (a) { return }` => `a`
+                if if_stmt.alternate.is_none() && consequent_emptied {
+                    let Statement::IfStatement(if_stmt) =
+                        self.recycler.take_statement(self.ast, stmt)
+                    else {
+                        unreachable!()
+                    };
+                    let if_stmt = if_stmt.unbox();
+                    self.recycler.recycle_statement(if_stmt.consequent);
+                    *stmt = self.ast.statement_expression(if_stmt.span, if_stmt.test);
+                }
             }
+            _ => {}
         }
+    }
 
-        let Some(index) = index else { return };
+    /// Removes the tail return of an `if` branch, and returns whether the branch is `;` or
+    /// became `{}` without it. Blocks which were already empty are left to other passes.
+    fn remove_branch_tail_return(&mut self, branch: &mut Statement<'a>) -> bool {
+        let is_empty_block = |stmt: &Statement<'a>| match stmt {
+            Statement::BlockStatement(block) => block.body.is_empty(),
+            _ => false,
+        };
+        let was_empty_block = is_empty_block(branch);
+        self.remove_tail_return(branch);
+        matches!(branch, Statement::EmptyStatement(_))
+            || (!was_empty_block && is_empty_block(branch))
+    }
 
-        let mut keep_var = KeepVar::new(self.ast);
+    /// `return`, `return void 0` and `return undefined`, when `undefined` is the global.
+    fn is_empty_return(&self, stmt: &Statement<'a>) -> bool {
+        let Statement::ReturnStatement(return_stmt) = stmt else { return false };
+        return_stmt.argument.as_ref().map_or(true, |argument| {
+            argument.is_void_0()
+                || (argument.is_undefined() && self.is_undefined_global && self.with_depth == 0)
+        })
+    }
 
-        for stmt in stmts.iter().skip(index) {
-            keep_var.visit_statement(stmt);
+    /// Removes the branch of an `if` statement which is not taken, keeping its `var` declarations.
+    ///
+    /// `if ("production" !== "production") { var a = 1; f() } else g()` => `{ g(); var a }`
+    fn fold_if_statement(&mut self, stmt: &mut Statement<'a>) {
+        if self.with_depth > 0 {
+            return;
         }
+        // The taken branch may be another `if` statement.
+        while let Statement::IfStatement(if_stmt) = stmt {
+            let Some(test) = self.condition_value(&if_stmt.test) else { return };
+            let Statement::IfStatement(if_stmt) = self.recycler.take_statement(self.ast, stmt)
+            else {
+                unreachable!()
+            };
+            let if_stmt = if_stmt.unbox();
+            let (taken, removed) = if test {
+                (Some(if_stmt.consequent), if_stmt.alternate)
+            } else {
+                (if_stmt.alternate, Some(if_stmt.consequent))
+            };
+            let new_stmt = KeepVar::keep_taken_branch(self.ast, taken, removed.as_ref())
+                .unwrap_or_else(|| self.recycler.empty_statement(self.ast, SPAN));
+            if let Some(removed) = removed {
+                self.recycler.recycle_statement(removed);
+            }
+            self.recycler.replace_statement(stmt, new_stmt);
+        }
+    }
 
-        stmts.drain(index + 1..);
-        if let Some(stmt) = keep_var.get_variable_declaration_statement() {
-            stmts.push(stmt);
+    /// `true ? a : b` => `a`
+    fn fold_conditional_expression(&mut self, expr: &mut Expression<'a>) {
+        if self.with_depth > 0 {
+            return;
+        }
+        let Expression::ConditionalExpression(conditional_expr) = expr else { return };
+        let Some(test) = self.condition_value(&conditional_expr.test) else { return };
+        let Expression::ConditionalExpression(conditional_expr) =
+            self.recycler.take_expression(self.ast, expr)
+        else {
+            unreachable!()
+        };
+        let conditional_expr = conditional_expr.unbox();
+        let (taken, removed) = if test {
+            (conditional_expr.consequent, conditional_expr.alternate)
+        } else {
+            (conditional_expr.alternate, conditional_expr.consequent)
+        };
+        self.recycler.recycle_expression(removed);
+        self.recycler.replace_expression(expr, taken);
+    }
+
+    /// `true && a` => `a`, `"a" === "b" && a` => `"a" === "b"`
+    fn fold_logical_expression(&mut self, expr: &mut Expression<'a>) {
+        if self.with_depth > 0 {
+            return;
+        }
+        let Expression::LogicalExpression(logical_expr) = expr else { return };
+        let Some(left) = self.condition_value(&logical_expr.left) else { return };
+        let short_circuits = match logical_expr.operator {
+            LogicalOperator::And => !left,
+            LogicalOperator::Or => left,
+            LogicalOperator::Coalesce => return,
+        };
+        let Expression::LogicalExpression(logical_expr) =
+            self.recycler.take_expression(self.ast, expr)
+        else {
+            unreachable!()
+        };
+        let logical_expr = logical_expr.unbox();
+        let (taken, removed) = if short_circuits {
+            (logical_expr.left, logical_expr.right)
+        } else {
+            (logical_expr.right, logical_expr.left)
+        };
+        self.recycler.recycle_expression(removed);
+        self.recycler.replace_expression(expr, taken);
+    }
+
+    /// The boolean value of a condition without side effects, if it is known. This includes
+    /// comparisons of literals, which `process.env.NODE_ENV === "production"` becomes with
+    /// [`crate::ReplaceGlobalDefines`].
+    #[allow(clippy::float_cmp)]
+    fn condition_value(&self, expr: &Expression<'a>) -> Option<bool> {
+        match expr {
+            Expression::BinaryExpression(binary_expr) => {
+                let is_equal = match (&binary_expr.left, &binary_expr.right) {
+                    (Expression::StringLiteral(left), Expression::StringLiteral(right)) => {
+                        left.value == right.value
+                    }
+                    (Expression::NumericLiteral(left), Expression::NumericLiteral(right)) => {
+                        left.value == right.value
+                    }
+                    (Expression::BooleanLiteral(left), Expression::BooleanLiteral(right)) => {
+                        left.value == right.value
+                    }
+                    (Expression::NullLiteral(_), Expression::NullLiteral(_)) => true,
+                    _ => return None,
+                };
+                // Literals of the same type are loosely equal if they are strictly equal.
+                match binary_expr.operator {
+                    BinaryOperator::Equality | BinaryOperator::StrictEquality => Some(is_equal),
+                    BinaryOperator::Inequality | BinaryOperator::StrictInequality => {
+                        Some(!is_equal)
+                    }
+                    _ => None,
+                }
+            }
+            Expression::UnaryExpression(unary_expr)
+                if unary_expr.operator == UnaryOperator::LogicalNot =>
+            {
+                self.condition_value(&unary_expr.argument).map(|value| !value)
+            }
+            Expression::LogicalExpression(logical_expr) => {
+                let left = self.condition_value(&logical_expr.left)?;
+                match logical_expr.operator {
+                    LogicalOperator::And if left => self.condition_value(&logical_expr.right),
+                    LogicalOperator::Or if !left => self.condition_value(&logical_expr.right),
+                    LogicalOperator::And | LogicalOperator::Or => Some(left),
+                    LogicalOperator::Coalesce => None,
+                }
+            }
+            Expression::ParenthesizedExpression(paren_expr) => {
+                self.condition_value(&paren_expr.expression)
+            }
+            _ if expr.may_have_side_effects(self.options.pure_getters) => None,
+            _ => get_boolean_value(expr),
+        }
+    }
+
+    /// Removes empty static blocks and `undefined` field initializers, and with
+    /// `compress.classes` static blocks without side effects and constructors which are the same
+    /// as the default one.
+    ///
+    /// Static blocks and field initializers are evaluated in order, so removing a no-op does not
+    /// change the evaluation order of the remaining elements.
+    ///
+    /// `class C { static {} x = void 0 }` => `class C { x }`
+    /// `class C extends B { constructor(...a) { super(...a) } }` => `class C extends B {}`
+    fn remove_dead_class_elements(&mut self, body: &mut ClassBody<'a>, is_derived: bool) {
+        let len = body.body.len();
+        body.body.retain(|element| match element {
+            ClassElement::StaticBlock(block) => {
+                !(block.body.is_empty()
+                    || (self.options.classes
+                        && block.body.iter().all(|stmt| self.is_static_block_no_op(stmt))))
+            }
+            ClassElement::MethodDefinition(method) => {
+                !(self.options.classes && Self::is_default_constructor(method, is_derived))
+            }
+            _ => true,
+        });
+        if body.body.len() < len {
+            self.recycler.mark_changed();
+        }
+        for element in body.body.iter_mut() {
+            let value = match element {
+                ClassElement::PropertyDefinition(prop) => &mut prop.value,
+                ClassElement::AccessorProperty(prop) => &mut prop.value,
+                _ => continue,
+            };
+            if value.as_ref().is_some_and(|value| {
+                value.is_void_0()
+                    || (value.is_undefined() && self.is_undefined_global && self.with_depth == 0)
+            }) {
+                *value = None;
+                self.recycler.mark_changed();
+            }
+        }
+    }
+
+    /// Whether `stmt` has no side effects in a static block. Declarations are local to the block,
+    /// so only their initializers are evaluated.
+    fn is_static_block_no_op(&self, stmt: &Statement<'a>) -> bool {
+        let pure_getters = self.options.pure_getters;
+        match stmt {
+            Statement::EmptyStatement(_) | Statement::FunctionDeclaration(_) => true,
+            Statement::ExpressionStatement(stmt) => {
+                !stmt.expression.may_have_side_effects(pure_getters)
+            }
+            Statement::VariableDeclaration(decl) => decl.declarations.iter().all(|declarator| {
+                declarator.id.kind.is_binding_identifier()
+                    && declarator
+                        .init
+                        .as_ref()
+                        .map_or(true, |init| !init.may_have_side_effects(pure_getters))
+            }),
+            _ => false,
+        }
+    }
+
+    /// Whether `method` is the same as the constructor of a class without one, `constructor() {}`,
+    /// or `constructor(...args) { super(...args) }` if the class is derived. The default
+    /// constructor of a derived class does not iterate its arguments, but a spread only observes
+    /// a changed `Array.prototype[Symbol.iterator]`.
+    fn is_default_constructor(method: &MethodDefinition<'a>, is_derived: bool) -> bool {
+        let func = &method.value;
+        let Some(body) = &func.body else { return false };
+        if method.kind != MethodDefinitionKind::Constructor
+            || !method.decorators.is_empty()
+            || !body.directives.is_empty()
+            || !func.params.items.is_empty()
+        {
+            return false;
+        }
+        if !is_derived {
+            return func.params.rest.is_none() && body.statements.is_empty();
+        }
+        // `arguments` or the rest parameter.
+        let name = match &func.params.rest {
+            None => "arguments",
+            Some(rest) => match &rest.argument.kind {
+                BindingPatternKind::BindingIdentifier(ident) => ident.name.as_str(),
+                _ => return false,
+            },
+        };
+        let [Statement::ExpressionStatement(stmt)] = body.statements.as_slice() else {
+            return false;
+        };
+        let Expression::CallExpression(call) = &stmt.expression else { return false };
+        let [Argument::SpreadElement(spread)] = call.arguments.as_slice() else { return false };
+        matches!(&call.callee, Expression::Super(_))
+            && matches!(&spread.argument, Expression::Identifier(ident) if ident.name == name)
+    }
+
+    /// Removes discarded calls to sibling generator and async function declarations, and the
+    /// declarations themselves once they are no longer referenced.
+    ///
+    /// `function* g() {} g();` => ``
+    ///
+    /// Enabled by `compress.unused_generators` and `compress.unawaited_async`
+    fn remove_unused_generators(&mut self, stmts: &mut Vec<'a, Statement<'a>>) {
+        // Names inside `with` may resolve to properties of the object.
+        if self.with_depth > 0 || self.name_usage.is_none() {
+            return;
+        }
+        let generators = stmts
+            .iter()
+            .filter_map(|stmt| match stmt {
+                Statement::FunctionDeclaration(func) if self.is_removable_callee(func) => {
+                    func.id.as_ref().map(|id| id.name.clone())
+                }
+                _ => None,
+            })
+            .collect::<std::vec::Vec<_>>();
+        let Some(name_usage) = &mut self.name_usage else { return };
+
+        // Names bound exactly once in the program cannot be shadowed, so every reference with
+        // the same name resolves to this declaration, unless the name is assigned another value.
+        let generators = generators
+            .into_iter()
+            .filter(|name| name_usage.is_static_binding(name) && !name_usage.writes.contains(name))
+            .collect::<std::vec::Vec<_>>();
+        if generators.is_empty() {
+            return;
+        }
+
+        let len = stmts.len();
+        stmts.retain(|stmt| {
+            let Statement::ExpressionStatement(expr_stmt) = stmt else { return true };
+            let Expression::CallExpression(call_expr) = &expr_stmt.expression else { return true };
+            let Expression::Identifier(ident) = &call_expr.callee else { return true };
+            if call_expr.optional
+                || !generators.contains(&ident.name)
+                || call_expr.arguments.iter().any(|arg| {
+                    arg.as_expression()
+                        .map_or(true, |expr| expr.may_have_side_effects(self.options.pure_getters))
+                })
+            {
+                return true;
+            }
+            if let Some(count) = name_usage.references.get_mut(&ident.name) {
+                *count -= 1;
+            }
+            false
+        });
+        if stmts.len() < len {
+            self.recycler.mark_changed();
+        }
+
+        // Function declarations at the top level of a script create properties on the global
+        // object, which may be observed by other scripts.
+        // Class static blocks are a var scope of their own.
+        let is_script_top_level = !self.is_module
+            && !self
+                .scope_flags
+                .iter()
+                .any(|flags| flags.intersects(ScopeFlags::Function | ScopeFlags::ClassStaticBlock));
+        if is_script_top_level {
+            return;
+        }
+        self.recycler.retain_statements(stmts, |stmt| {
+            let Statement::FunctionDeclaration(func) = stmt else { return true };
+            let Some(id) = &func.id else { return true };
+            !generators.contains(&id.name)
+                || name_usage.references.get(&id.name).is_some_and(|count| *count > 0)
+        });
+    }
+
+    /// Removes duplicate `__esModule` markers and `require()` calls of JSON files whose result is
+    /// unused, at the top level of a CommonJS module.
+    ///
+    /// `exports.__esModule = true; exports.__esModule = true; const data = require("./a.json");`
+    /// => `exports.__esModule = true;`
+    ///
+    /// Enabled by `compress.commonjs`
+    fn remove_commonjs_dead_code(&mut self, stmts: &mut Vec<'a, Statement<'a>>) {
+        let mut has_es_module_marker = false;
+        self.recycler.retain_statements(stmts, |stmt| {
+            if commonjs::is_es_module_marker(stmt) {
+                return !std::mem::replace(&mut has_es_module_marker, true);
+            }
+            let Statement::ExpressionStatement(expr_stmt) = stmt else { return true };
+            !commonjs::is_json_require(&expr_stmt.expression)
+        });
+
+        let Some(name_usage) = &self.name_usage else { return };
+        // The top level of a CommonJS module is a function scope,
+        // so its bindings are not visible to other modules.
+        let is_unused = |decl: &VariableDeclarator<'a>| {
+            decl.init.as_ref().is_some_and(commonjs::is_json_require)
+                && decl.id.get_binding_identifier().is_some_and(|id| {
+                    name_usage.is_static_binding(&id.name)
+                        && !name_usage.references.contains_key(&id.name)
+                })
+        };
+        for stmt in stmts.iter_mut() {
+            if let Statement::VariableDeclaration(decl) = stmt {
+                let len = decl.declarations.len();
+                decl.declarations.retain(|decl| !is_unused(decl));
+                if decl.declarations.len() < len {
+                    self.recycler.mark_changed();
+                }
+            }
+        }
+        self.recycler.retain_statements(stmts, |stmt| {
+            !matches!(stmt, Statement::VariableDeclaration(decl) if decl.declarations.is_empty())
+        });
+    }
+
+    /// Removes labels which are not referenced, after rewriting `break` statements to the label of
+    /// a block into conditionals where possible.
+    ///
+    /// `a: { b: { if (x) break a; y() } }` => `{ if (!x) y() }`
+    ///
+    /// Enabled by `compress.labels`
+    fn compress_labeled_statement(&mut self, stmt: &mut Statement<'a>) {
+        self.merge_nested_labels(stmt);
+        let Statement::LabeledStatement(labeled) = stmt else { return };
+        let labeled = &mut **labeled;
+        if let Statement::BlockStatement(block) = &mut labeled.body {
+            self.remove_breaks(&mut block.body, &labeled.label.name);
+        }
+        // `a: { for (;;) x }` is the same as `a: for (;;) x`.
+        let body = match &mut labeled.body {
+            Statement::BlockStatement(block) if block.body.len() == 1 => {
+                block.body.first_mut().unwrap()
+            }
+            body => body,
+        };
+        if body.is_iteration_statement() || matches!(body, Statement::SwitchStatement(_)) {
+            let mut unqualifier = LabelUnqualifier {
+                label: labeled.label.name.clone(),
+                depth: 0,
+                loops: 0,
+                changed: false,
+            };
+            // The body itself is the target of unlabeled statements.
+            walk_mut::walk_statement(&mut unqualifier, body);
+            if unqualifier.changed {
+                self.recycler.mark_changed();
+            }
+        }
+        if LabelReferences::find(&labeled.body, &labeled.label.name) {
+            return;
+        }
+        let Statement::LabeledStatement(labeled) = self.recycler.take_statement(self.ast, stmt)
+        else {
+            unreachable!()
+        };
+        self.recycler.replace_statement(stmt, labeled.unbox().body);
+    }
+
+    /// Statements with more than one label, `a: b: x` or `a: { b: x }`, are given the innermost
+    /// label because `break a` and `break b` both go to the end of `x`.
+    fn merge_nested_labels(&mut self, stmt: &mut Statement<'a>) {
+        loop {
+            let Statement::LabeledStatement(outer) = stmt else { return };
+            let outer = &mut **outer;
+            let inner_stmt = match &mut outer.body {
+                inner_stmt @ Statement::LabeledStatement(_) => inner_stmt,
+                Statement::BlockStatement(block)
+                    if block.body.len() == 1
+                        && matches!(block.body[0], Statement::LabeledStatement(_)) =>
+                {
+                    block.body.first_mut().unwrap()
+                }
+                _ => return,
+            };
+            let Statement::LabeledStatement(inner) = inner_stmt else { unreachable!() };
+            let mut renamer =
+                LabelRenamer { from: outer.label.name.clone(), to: inner.label.name.clone() };
+            renamer.visit_statement(&mut inner.body);
+            let inner_stmt = self.recycler.take_statement(self.ast, inner_stmt);
+            self.recycler.replace_statement(stmt, inner_stmt);
+        }
+    }
+
+    /// Removes trailing `break label` statements and rewrites `if (x) break label; rest` to
+    /// `if (!x) { rest }` in the body of a labeled block.
+    ///
+    /// Moving `rest` into a nested block changes the scope of its `let`, `const`, `class` and
+    /// function declarations, which are visible to closures earlier in the block, so these bail.
+    fn remove_breaks(&mut self, stmts: &mut Vec<'a, Statement<'a>>, label: &Atom<'a>) {
+        while stmts.last().is_some_and(|stmt| Self::is_break_to(stmt, label)) {
+            let stmt = stmts.pop().unwrap();
+            self.recycler.recycle_statement(stmt);
+            self.recycler.mark_changed();
+        }
+        let Some(index) = stmts.iter().position(|stmt| Self::is_conditional_break_to(stmt, label))
+        else {
+            return;
+        };
+        if stmts.iter().skip(index + 1).any(Self::is_scoped_declaration) {
+            return;
+        }
+        self.recycler.mark_changed();
+        let mut rest = self.ast.vec_from_iter(stmts.drain(index + 1..));
+        self.remove_breaks(&mut rest, label);
+
+        let Some(Statement::IfStatement(if_stmt)) = stmts.pop() else { unreachable!() };
+        let if_stmt = if_stmt.unbox();
+        self.recycler.recycle_statement(if_stmt.consequent);
+        if rest.is_empty() {
+            if if_stmt.test.may_have_side_effects(self.options.pure_getters) {
+                stmts.push(self.ast.statement_expression(if_stmt.span, if_stmt.test));
+            }
+            return;
+        }
+        let test = match if_stmt.test {
+            Expression::UnaryExpression(unary_expr)
+                if unary_expr.operator == UnaryOperator::LogicalNot =>
+            {
+                unary_expr.unbox().argument
+            }
+            test => self.ast.expression_unary(SPAN, UnaryOperator::LogicalNot, test),
+        };
+        let consequent = if rest.len() == 1 && !rest[0].is_declaration() {
+            rest.pop().unwrap()
+        } else {
+            self.ast.statement_block(SPAN, rest)
+        };
+        stmts.push(self.ast.statement_if(if_stmt.span, test, consequent, None));
+    }
+
+    /// Merges blocks into the enclosing statement list, `a; { b; c }` => `a; b; c`.
+    ///
+    /// Enabled by `compress.labels`
+    fn flatten_blocks(&mut self, stmts: &mut Vec<'a, Statement<'a>>) {
+        if !stmts.iter().any(Self::is_flattenable_block) {
+            return;
+        }
+        let mut flattened = self.ast.vec_with_capacity(stmts.len());
+        for stmt in stmts.drain(..) {
+            if Self::is_flattenable_block(&stmt) {
+                let Statement::BlockStatement(block) = stmt else { unreachable!() };
+                flattened.extend(block.unbox().body);
+            } else {
+                flattened.push(stmt);
+            }
         }
+        *stmts = flattened;
+        self.recycler.mark_changed();
+    }
+
+    fn is_flattenable_block(stmt: &Statement<'a>) -> bool {
+        let Statement::BlockStatement(block) = stmt else { return false };
+        // A leading string would become a directive at the start of a function body.
+        !block.body.iter().any(Self::is_scoped_declaration)
+            && !matches!(
+                block.body.first(),
+                Some(Statement::ExpressionStatement(expr_stmt))
+                    if matches!(expr_stmt.expression, Expression::StringLiteral(_))
+            )
+    }
+
+    fn is_scoped_declaration(stmt: &Statement<'a>) -> bool {
+        stmt.is_declaration()
+            && !matches!(stmt, Statement::VariableDeclaration(decl) if decl.kind.is_var())
+    }
+
+    /// `break label` or `{ break label }`
+    fn is_break_to(stmt: &Statement<'a>, label: &Atom<'a>) -> bool {
+        match stmt {
+            Statement::BreakStatement(break_stmt) => {
+                break_stmt.label.as_ref().is_some_and(|l| l.name == *label)
+            }
+            Statement::BlockStatement(block) => {
+                matches!(block.body.as_slice(), [stmt] if Self::is_break_to(stmt, label))
+            }
+            _ => false,
+        }
+    }
+
+    /// `if (x) break label`
+    fn is_conditional_break_to(stmt: &Statement<'a>, label: &Atom<'a>) -> bool {
+        matches!(
+            stmt,
+            Statement::IfStatement(if_stmt)
+                if if_stmt.alternate.is_none() && Self::is_break_to(&if_stmt.consequent, label)
+        )
+    }
+
+    /// `(() => x)()` → `x`, `(function () { return x })()` → `x`, `(function () {})()` → `void 0`
+    ///
+    /// Member expressions are kept in the callee of a call or tagged template, where
+    /// `(() => a.b)()()` calls without `this`, and in `delete`, which would delete the property.
+    ///
+    /// Enabled by `compress.iife`
+    fn unwrap_iife(&mut self, expr: &mut Expression<'a>) {
+        let Expression::CallExpression(call) = expr else { return };
+        let is_member_position = self.member_positions.last() == Some(&call.span);
+        let Some((body, is_expression)) = Self::iife_body(call) else { return };
+        let value = match body.statements.as_mut_slice() {
+            [] => None,
+            [Statement::ExpressionStatement(stmt)] if is_expression => {
+                if is_member_position && is_member(&stmt.expression) {
+                    return;
+                }
+                Some(self.ast.move_expression(&mut stmt.expression))
+            }
+            [Statement::ReturnStatement(stmt)] => {
+                if is_member_position && stmt.argument.as_ref().is_some_and(is_member) {
+                    return;
+                }
+                stmt.argument.take()
+            }
+            _ => return,
+        };
+        *expr = value.unwrap_or_else(|| self.ast.void_0());
+        self.recycler.mark_changed();
+    }
+
+    /// `(function () { a(); b() })()` → `{ a(); b() }`, also with `!` or `void` in front, which
+    /// only discard the result. Bodies which return, or declare `var`s or, in scripts, functions,
+    /// which would be visible outside of the block, are kept.
+    ///
+    /// Enabled by `compress.iife`
+    fn unwrap_iife_statements(&mut self, stmts: &mut Vec<'a, Statement<'a>>) {
+        for stmt in stmts.iter_mut() {
+            let Statement::ExpressionStatement(expr_stmt) = stmt else { continue };
+            let expr = match &mut expr_stmt.expression {
+                Expression::UnaryExpression(unary)
+                    if matches!(
+                        unary.operator,
+                        UnaryOperator::Void | UnaryOperator::LogicalNot
+                    ) =>
+                {
+                    &mut unary.argument
+                }
+                expr => expr,
+            };
+            let Expression::CallExpression(call) = expr else { continue };
+            let Some((body, false)) = Self::iife_body(call) else { continue };
+            let mut uses = IifeUses::default();
+            uses.visit_function_body(body);
+            if uses.returns || uses.declares_var || (uses.declares_function && !self.is_module) {
+                continue;
+            }
+            let body = self.ast.move_statement_vec(&mut body.statements);
+            *stmt = self.ast.statement_block(SPAN, body);
+            self.recycler.mark_changed();
+        }
+    }
+
+    /// The body of an immediately invoked function or arrow function without a name, parameters
+    /// or arguments, if it can run in place of the call, and whether it is the expression of an
+    /// arrow function. Functions which use their own `this`, `arguments` or `new.target`, and
+    /// bodies with directives or direct `eval` calls are not.
+    fn iife_body<'b>(call: &'b mut CallExpression<'a>) -> Option<(&'b mut FunctionBody<'a>, bool)> {
+        if call.optional || !call.arguments.is_empty() {
+            return None;
+        }
+        let (body, is_function, is_expression) = match &mut call.callee {
+            Expression::FunctionExpression(func)
+                if func.id.is_none()
+                    && !func.r#async
+                    && !func.generator
+                    && func.params.items.is_empty()
+                    && func.params.rest.is_none() =>
+            {
+                (func.body.as_deref_mut()?, true, false)
+            }
+            Expression::ArrowFunctionExpression(arrow)
+                if !arrow.r#async
+                    && arrow.params.items.is_empty()
+                    && arrow.params.rest.is_none() =>
+            {
+                let is_expression = arrow.expression;
+                (&mut *arrow.body, false, is_expression)
+            }
+            _ => return None,
+        };
+        let mut uses = IifeUses::default();
+        uses.visit_function_body(body);
+        let uses_scope = uses.uses_eval || (is_function && uses.uses_function_scope);
+        (body.directives.is_empty() && !uses_scope).then_some((body, is_expression))
+    }
+
+    /// Calling a generator only evaluates its parameters, which have no side effects when they
+    /// are plain identifiers.
+    fn is_removable_callee(&self, func: &Function<'a>) -> bool {
+        let is_removable = if func.generator {
+            self.options.unused_generators
+        } else if func.r#async {
+            self.options.unawaited_async
+                && func.body.as_ref().is_some_and(|body| self.is_pure_body(&body.statements))
+        } else {
+            false
+        };
+        is_removable
+            && !func.declare
+            && func.params.iter_bindings().all(|pattern| pattern.kind.is_binding_identifier())
+    }
+
+    /// Whether running the statements of an async function body has no side effects besides
+    /// settling the promise it returns.
+    fn is_pure_body(&self, stmts: &[Statement<'a>]) -> bool {
+        let is_pure = |expr: &Expression<'a>| {
+            let expr = match expr {
+                Expression::AwaitExpression(await_expr) => &await_expr.argument,
+                expr => expr,
+            };
+            !expr.may_have_side_effects(self.options.pure_getters)
+        };
+        stmts.iter().all(|stmt| match stmt {
+            Statement::EmptyStatement(_) => true,
+            Statement::ExpressionStatement(expr_stmt) => is_pure(&expr_stmt.expression),
+            Statement::ReturnStatement(return_stmt) => {
+                return_stmt.argument.as_ref().map_or(true, is_pure)
+            }
+            _ => false,
+        })
+    }
+}
+
+/// Counts bindings and references by name.
+///
+/// References inside `with` bodies are counted like any other, which keeps the declarations they
+/// may refer to. Direct `eval` can read and write every binding of the functions around it, so
+/// those names are collected in `evaluated` instead of disabling the analysis for the program.
+#[derive(Default)]
+struct NameUsage<'a> {
+    bindings: FxHashMap<Atom<'a>, usize>,
+    references: FxHashMap<Atom<'a>, usize>,
+    /// Names which are assigned to or updated.
+    writes: FxHashSet<Atom<'a>>,
+    /// Names bound in a function which contains a direct `eval` call.
+    evaluated: FxHashSet<Atom<'a>>,
+    /// Functions being visited, innermost last.
+    frames: std::vec::Vec<NameUsageFrame<'a>>,
+}
+
+#[derive(Default)]
+struct NameUsageFrame<'a> {
+    /// Names bound in the function, not counting nested functions.
+    names: std::vec::Vec<Atom<'a>>,
+    /// The function or a nested function calls `eval` directly.
+    has_direct_eval: bool,
+}
+
+impl<'a> NameUsage<'a> {
+    /// Whether the name is bound once, and is not visible to a direct `eval`.
+    fn is_static_binding(&self, name: &Atom<'a>) -> bool {
+        self.bindings.get(name) == Some(&1) && !self.evaluated.contains(name)
+    }
+
+    fn leave_frame(&mut self) {
+        let frame = self.frames.pop().unwrap();
+        if frame.has_direct_eval {
+            self.evaluated.extend(frame.names);
+            if let Some(parent) = self.frames.last_mut() {
+                parent.has_direct_eval = true;
+            }
+        }
+    }
+}
+
+impl<'a> Visit<'a> for NameUsage<'a> {
+    fn visit_program(&mut self, program: &Program<'a>) {
+        self.frames.push(NameUsageFrame::default());
+        walk::walk_program(self, program);
+        self.leave_frame();
+    }
+
+    fn visit_function(&mut self, func: &Function<'a>, flags: ScopeFlags) {
+        // The name of a function declaration is bound in the enclosing function.
+        if func.is_declaration() {
+            if let (Some(id), Some(frame)) = (&func.id, self.frames.last_mut()) {
+                frame.names.push(id.name.clone());
+            }
+        }
+        self.frames.push(NameUsageFrame::default());
+        walk::walk_function(self, func, flags);
+        self.leave_frame();
+    }
+
+    fn visit_arrow_function_expression(&mut self, arrow: &ArrowFunctionExpression<'a>) {
+        self.frames.push(NameUsageFrame::default());
+        walk::walk_arrow_function_expression(self, arrow);
+        self.leave_frame();
+    }
+
+    fn visit_binding_identifier(&mut self, ident: &BindingIdentifier<'a>) {
+        *self.bindings.entry(ident.name.clone()).or_default() += 1;
+        if let Some(frame) = self.frames.last_mut() {
+            frame.names.push(ident.name.clone());
+        }
+    }
+
+    fn visit_identifier_reference(&mut self, ident: &IdentifierReference<'a>) {
+        *self.references.entry(ident.name.clone()).or_default() += 1;
+    }
+
+    fn visit_simple_assignment_target(&mut self, target: &SimpleAssignmentTarget<'a>) {
+        if let SimpleAssignmentTarget::AssignmentTargetIdentifier(ident) = target {
+            self.writes.insert(ident.name.clone());
+        }
+        walk::walk_simple_assignment_target(self, target);
+    }
+
+    fn visit_assignment_target_property_identifier(
+        &mut self,
+        property: &AssignmentTargetPropertyIdentifier<'a>,
+    ) {
+        self.writes.insert(property.binding.name.clone());
+        walk::walk_assignment_target_property_identifier(self, property);
+    }
+
+    fn visit_call_expression(&mut self, expr: &CallExpression<'a>) {
+        if expr.callee.is_specific_id("eval") {
+            if let Some(frame) = self.frames.last_mut() {
+                frame.has_direct_eval = true;
+            }
+        }
+        walk::walk_call_expression(self, expr);
+    }
+}
+
+/// Whether the program may bind the name `undefined`, by declaring it or with a direct `eval`.
+#[derive(Default)]
+struct UndefinedBindings {
+    may_shadow: bool,
+}
+
+impl<'a> Visit<'a> for UndefinedBindings {
+    fn visit_binding_identifier(&mut self, ident: &BindingIdentifier<'a>) {
+        self.may_shadow |= ident.name == "undefined";
+    }
+
+    fn visit_identifier_reference(&mut self, ident: &IdentifierReference<'a>) {
+        self.may_shadow |= ident.name == "eval";
+    }
+}
+
+/// What the body of an immediately invoked function uses which would change meaning outside of
+/// it. Nested functions are skipped, they have their own scope.
+#[derive(Default)]
+struct IifeUses {
+    /// `this`, `arguments` or `new.target`, which arrow functions take from the scope around
+    /// them.
+    uses_function_scope: bool,
+    /// A direct `eval` call, which can declare variables, or `yield` and `await` used as
+    /// names, which are keywords in some of the places the body could move to.
+    uses_eval: bool,
+    returns: bool,
+    declares_var: bool,
+    declares_function: bool,
+}
+
+impl<'a> Visit<'a> for IifeUses {
+    fn visit_this_expression(&mut self, _expr: &ThisExpression) {
+        self.uses_function_scope = true;
+    }
+
+    fn visit_meta_property(&mut self, meta: &MetaProperty<'a>) {
+        // `import.meta` is the same everywhere in a module.
+        if meta.meta.name == "new" {
+            self.uses_function_scope = true;
+        }
+    }
+
+    fn visit_identifier_reference(&mut self, ident: &IdentifierReference<'a>) {
+        match ident.name.as_str() {
+            "arguments" => self.uses_function_scope = true,
+            "eval" | "yield" | "await" => self.uses_eval = true,
+            _ => {}
+        }
+    }
+
+    fn visit_binding_identifier(&mut self, ident: &BindingIdentifier<'a>) {
+        if matches!(ident.name.as_str(), "yield" | "await") {
+            self.uses_eval = true;
+        }
+    }
+
+    fn visit_return_statement(&mut self, stmt: &ReturnStatement<'a>) {
+        self.returns = true;
+        walk::walk_return_statement(self, stmt);
+    }
+
+    fn visit_variable_declaration(&mut self, decl: &VariableDeclaration<'a>) {
+        self.declares_var |= decl.kind.is_var();
+        walk::walk_variable_declaration(self, decl);
+    }
+
+    fn visit_function(&mut self, func: &Function<'a>, _flags: ScopeFlags) {
+        if func.is_declaration() {
+            self.declares_function = true;
+            if let Some(id) = &func.id {
+                self.visit_binding_identifier(id);
+            }
+        }
+    }
+}
+
+/// Finds `break` and `continue` statements to a label. Labels are not visible inside functions
+/// and classes, so these are skipped.
+struct LabelReferences<'b, 'a> {
+    label: &'b Atom<'a>,
+    found: bool,
+}
+
+impl<'b, 'a> LabelReferences<'b, 'a> {
+    fn find(stmt: &Statement<'a>, label: &'b Atom<'a>) -> bool {
+        let mut references = Self { label, found: false };
+        references.visit_statement(stmt);
+        references.found
+    }
+}
+
+impl<'b, 'a> Visit<'a> for LabelReferences<'b, 'a> {
+    fn visit_label_identifier(&mut self, ident: &LabelIdentifier<'a>) {
+        if ident.name == *self.label {
+            self.found = true;
+        }
+    }
+
+    fn visit_function(&mut self, _func: &Function<'a>, _flags: ScopeFlags) {}
+
+    fn visit_arrow_function_expression(&mut self, _arrow: &ArrowFunctionExpression<'a>) {}
+
+    fn visit_class(&mut self, _class: &Class<'a>) {}
+}
+
+/// Removes the label from `break` and `continue` statements in a labeled loop or `switch` when
+/// they would go to the same place without it, `a: for (;;) break a` => `a: for (;;) break`.
+struct LabelUnqualifier<'a> {
+    label: Atom<'a>,
+    /// Nested loops and `switch` statements, which are the target of a `break` without a label.
+    depth: usize,
+    /// Nested loops, which are the target of a `continue` without a label.
+    loops: usize,
+    /// Whether a label was removed.
+    changed: bool,
+}
+
+impl<'a> VisitMut<'a> for LabelUnqualifier<'a> {
+    fn visit_statement(&mut self, stmt: &mut Statement<'a>) {
+        let is_loop = stmt.is_iteration_statement();
+        let is_breakable = is_loop || matches!(stmt, Statement::SwitchStatement(_));
+        self.depth += usize::from(is_breakable);
+        self.loops += usize::from(is_loop);
+        walk_mut::walk_statement(self, stmt);
+        self.depth -= usize::from(is_breakable);
+        self.loops -= usize::from(is_loop);
+    }
+
+    fn visit_break_statement(&mut self, stmt: &mut BreakStatement<'a>) {
+        if self.depth == 0 && stmt.label.as_ref().is_some_and(|label| label.name == self.label) {
+            stmt.label = None;
+            self.changed = true;
+        }
+    }
+
+    fn visit_continue_statement(&mut self, stmt: &mut ContinueStatement<'a>) {
+        if self.loops == 0 && stmt.label.as_ref().is_some_and(|label| label.name == self.label) {
+            stmt.label = None;
+            self.changed = true;
+        }
+    }
+
+    fn visit_function(&mut self, _func: &mut Function<'a>, _flags: ScopeFlags) {}
+
+    fn visit_arrow_function_expression(&mut self, _arrow: &mut ArrowFunctionExpression<'a>) {}
+
+    fn visit_class(&mut self, _class: &mut Class<'a>) {}
+}
+
+/// Renames `break` and `continue` statements to a label.
+struct LabelRenamer<'a> {
+    from: Atom<'a>,
+    to: Atom<'a>,
+}
+
+impl<'a> VisitMut<'a> for LabelRenamer<'a> {
+    fn visit_label_identifier(&mut self, ident: &mut LabelIdentifier<'a>) {
+        if ident.name == self.from {
+            ident.name = self.to.clone();
+        }
+    }
+
+    fn visit_function(&mut self, _func: &mut Function<'a>, _flags: ScopeFlags) {}
+
+    fn visit_arrow_function_expression(&mut self, _arrow: &mut ArrowFunctionExpression<'a>) {}
+
+    fn visit_class(&mut self, _class: &mut Class<'a>) {}
+}
+
+/// Whether `expr` is a member expression, which passes its object as `this` when it is called.
+fn is_member(expr: &Expression) -> bool {
+    match expr.without_parenthesized() {
+        Expression::ChainExpression(chain) => chain.expression.is_member_expression(),
+        expr => expr.is_member_expression(),
     }
 }
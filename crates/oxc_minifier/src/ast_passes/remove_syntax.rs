@@ -1,20 +1,37 @@
 use oxc_allocator::Vec;
 use oxc_ast::{ast::*, visit::walk_mut, AstBuilder, VisitMut};
+use oxc_span::GetSpan;
 
-use crate::CompressOptions;
+use crate::{
+    ast_util::MayHaveSideEffects, chunk_boundary::is_chunk_boundary, recycler::Recycler,
+    static_bindings::StaticBindings, CompressOptions, ConsoleMethods, PureAnnotations,
+};
 
 /// Remove syntax from the AST.
 ///
 /// * Parenthesized Expression
 /// * `debugger`
 /// * `console.log`
+/// * Calls of `pure_funcs`, of the pure functions of `env` and calls annotated as pure
+///
+/// Calls of `pure_funcs` and of `env` functions are kept inside `with` bodies and functions which
+/// call `eval` directly, where their names may refer to something else.
 pub struct RemoveSyntax<'a> {
     ast: AstBuilder<'a>,
+    recycler: Recycler<'a>,
     options: CompressOptions,
+    pure_annotations: PureAnnotations,
+    /// Collected in [`RemoveSyntax::build`], to find calls of the pure functions of `env`.
+    bindings: StaticBindings<'a>,
 }
 
 impl<'a> VisitMut<'a> for RemoveSyntax<'a> {
     fn visit_statements(&mut self, stmts: &mut Vec<'a, Statement<'a>>) {
+        if self.has_pure_calls() {
+            for stmt in stmts.iter_mut() {
+                self.drop_pure_call_statement(stmt);
+            }
+        }
         stmts.retain(|stmt| {
             !(matches!(stmt, Statement::EmptyStatement(_))
                 || self.drop_debugger(stmt)
@@ -25,24 +42,55 @@ impl<'a> VisitMut<'a> for RemoveSyntax<'a> {
 
     fn visit_expression(&mut self, expr: &mut Expression<'a>) {
         self.strip_parenthesized_expression(expr);
+        if self.options.keep_chunk_boundaries && is_chunk_boundary(expr) {
+            return;
+        }
         self.compress_console(expr);
+        self.drop_pure_calls_in_sequence(expr);
         walk_mut::walk_expression(self, expr);
     }
 }
 
 impl<'a> RemoveSyntax<'a> {
     pub fn new(ast: AstBuilder<'a>, options: CompressOptions) -> Self {
-        Self { ast, options }
+        Self {
+            ast,
+            recycler: Recycler::default(),
+            options,
+            pure_annotations: PureAnnotations::default(),
+            bindings: StaticBindings::default(),
+        }
     }
 
     pub fn build(&mut self, program: &mut Program<'a>) {
+        self.bindings = StaticBindings::new(program, self.options.pure_getters, self.options.env);
         self.visit_program(program);
     }
 
-    fn strip_parenthesized_expression(&self, expr: &mut Expression<'a>) {
-        if let Expression::ParenthesizedExpression(paren_expr) = expr {
-            *expr = self.ast.move_expression(&mut paren_expr.expression);
-            self.strip_parenthesized_expression(expr);
+    #[must_use]
+    pub fn with_pure_annotations(mut self, pure_annotations: PureAnnotations) -> Self {
+        self.pure_annotations = pure_annotations;
+        self
+    }
+
+    #[must_use]
+    pub fn with_recycler(mut self, recycler: Recycler<'a>) -> Self {
+        self.recycler = recycler;
+        self
+    }
+
+    pub fn into_recycler(self) -> Recycler<'a> {
+        self.recycler
+    }
+
+    fn strip_parenthesized_expression(&mut self, expr: &mut Expression<'a>) {
+        while matches!(expr, Expression::ParenthesizedExpression(_)) {
+            let Expression::ParenthesizedExpression(paren_expr) =
+                self.recycler.take_expression(self.ast, expr)
+            else {
+                unreachable!()
+            };
+            self.recycler.replace_expression(expr, paren_expr.unbox().expression);
         }
     }
 
@@ -55,24 +103,155 @@ impl<'a> RemoveSyntax<'a> {
 
     /// Drop `console.*` expressions.
     ///
-    /// Enabled by `compress.drop_console
+    /// Enabled by `compress.drop_console`, except for the methods in `compress.keep_console`.
     fn drop_console(&mut self, stmt: &Statement<'a>) -> bool {
         self.options.drop_console
-            && matches!(stmt, Statement::ExpressionStatement(expr) if Self::is_console(&expr.expression))
+            && matches!(stmt, Statement::ExpressionStatement(expr) if self.is_dropped_console_call(&expr.expression))
     }
 
     fn compress_console(&mut self, expr: &mut Expression<'a>) {
-        if self.options.drop_console && Self::is_console(expr) {
+        if self.options.drop_console && self.is_dropped_console_call(expr) {
             *expr = self.ast.void_0();
         }
     }
 
-    fn is_console(expr: &Expression<'_>) -> bool {
-        // let Statement::ExpressionStatement(expr) = stmt else { return false };
+    fn is_dropped_console_call(&self, expr: &Expression<'_>) -> bool {
         let Expression::CallExpression(call_expr) = &expr else { return false };
         let Some(member_expr) = call_expr.callee.as_member_expression() else { return false };
         let obj = member_expr.object();
         let Some(ident) = obj.get_identifier_reference() else { return false };
-        ident.name == "console"
+        if ident.name != "console" {
+            return false;
+        }
+        let keep = self.options.keep_console;
+        if keep.is_empty() {
+            return true;
+        }
+        member_expr.static_property_name().is_some_and(|name| {
+            ConsoleMethods::from_method(name).map_or(true, |method| !keep.contains(method))
+        })
+    }
+
+    /// Drop calls of `pure_funcs`, of the pure functions of `env` and calls annotated as pure
+    /// whose result is unused, keeping the arguments with side effects.
+    ///
+    /// Enabled by `compress.pure_funcs`, `compress.env` and [`PureAnnotations`].
+    fn drop_pure_call_statement(&mut self, stmt: &mut Statement<'a>) {
+        let Statement::ExpressionStatement(expr_stmt) = stmt else { return };
+        self.strip_parenthesized_expression(&mut expr_stmt.expression);
+        if !self.is_pure_call(&expr_stmt.expression) {
+            return;
+        }
+        let mut effects = std::vec![];
+        self.side_effects(&mut expr_stmt.expression, &mut effects);
+        let span = expr_stmt.span;
+        if let Some(expr) = self.sequence(effects) {
+            expr_stmt.expression = expr;
+        } else {
+            *stmt = self.recycler.empty_statement(self.ast, span);
+        }
+    }
+
+    /// `(assert(x), a)` → `a`
+    fn drop_pure_calls_in_sequence(&mut self, expr: &mut Expression<'a>) {
+        if !self.has_pure_calls() {
+            return;
+        }
+        let Expression::SequenceExpression(sequence_expr) = expr else { return };
+        let Some((last, rest)) = sequence_expr.expressions.split_last_mut() else { return };
+        for expr in rest.iter_mut() {
+            self.strip_parenthesized_expression(expr);
+        }
+        if !rest.iter().any(|expr| self.is_pure_call(expr)) {
+            return;
+        }
+        let last = self.recycler.take_expression(self.ast, last);
+        let mut effects = std::vec![];
+        for expr in rest {
+            if self.is_pure_call(expr) {
+                self.side_effects(expr, &mut effects);
+            } else {
+                effects.push(self.recycler.take_expression(self.ast, expr));
+            }
+        }
+        effects.push(last);
+        let Some(new_expr) = self.sequence(effects) else { unreachable!() };
+        self.recycler.replace_expression(expr, new_expr);
+    }
+
+    fn has_pure_calls(&self) -> bool {
+        !self.options.pure_funcs.is_empty()
+            || !self.pure_annotations.is_empty()
+            || self.options.env.pure_functions().next().is_some()
+    }
+
+    /// A call of `pure_funcs` or of a pure function of `env`, or a call or `new` expression
+    /// annotated as pure.
+    fn is_pure_call(&self, expr: &Expression<'a>) -> bool {
+        match expr {
+            Expression::CallExpression(call_expr) => {
+                !call_expr.arguments.iter().any(Argument::is_spread)
+                    && (self.pure_annotations.contains(call_expr.span)
+                        || (!self.bindings.is_opaque(call_expr.span)
+                            && self
+                                .options
+                                .pure_funcs
+                                .iter()
+                                .any(|name| is_callee(&call_expr.callee, name)))
+                        || self.bindings.is_pure_call(expr))
+            }
+            Expression::NewExpression(new_expr) => {
+                !new_expr.arguments.iter().any(Argument::is_spread)
+                    && self.pure_annotations.contains(new_expr.span)
+            }
+            _ => false,
+        }
+    }
+
+    /// Moves the parts of `expr` with side effects into `effects`, for an expression whose
+    /// result is unused: the side effects of the arguments of pure calls, or `expr` itself.
+    fn side_effects(
+        &mut self,
+        expr: &mut Expression<'a>,
+        effects: &mut std::vec::Vec<Expression<'a>>,
+    ) {
+        self.strip_parenthesized_expression(expr);
+        if self.is_pure_call(expr) {
+            let arguments = match expr {
+                Expression::CallExpression(call_expr) => &mut call_expr.arguments,
+                Expression::NewExpression(new_expr) => &mut new_expr.arguments,
+                _ => unreachable!(),
+            };
+            for argument in arguments.iter_mut().filter_map(Argument::as_expression_mut) {
+                self.side_effects(argument, effects);
+            }
+        } else if expr.may_have_side_effects(self.options.pure_getters) {
+            effects.push(self.recycler.take_expression(self.ast, expr));
+        }
+    }
+
+    fn sequence(&self, mut exprs: std::vec::Vec<Expression<'a>>) -> Option<Expression<'a>> {
+        match exprs.len() {
+            0 => None,
+            1 => exprs.pop(),
+            _ => {
+                let span = exprs[0].span();
+                Some(self.ast.expression_sequence(span, self.ast.vec_from_iter(exprs)))
+            }
+        }
+    }
+}
+
+/// Whether `callee` is `name`, which is an identifier or a chain of static member expressions,
+/// `a.b.c`.
+fn is_callee(callee: &Expression<'_>, name: &str) -> bool {
+    match callee.without_parenthesized() {
+        Expression::Identifier(ident) => ident.name == name,
+        Expression::StaticMemberExpression(member_expr) => {
+            name.rsplit_once('.').is_some_and(|(object, property)| {
+                member_expr.property.name == property && is_callee(&member_expr.object, object)
+            })
+        }
+        _ => false,
     }
 }
@@ -0,0 +1,292 @@
+use oxc_ast::{ast::*, syntax_directed_operations::BoundNames, AstBuilder, AstKind};
+use oxc_semantic::{Semantic, SemanticBuilder, SymbolFlags};
+use oxc_span::{GetSpan, Span};
+
+use crate::{ast_util::MayHaveSideEffects, PureGetters};
+
+/// Remove top level functions, classes and variables of modules which are never referenced and
+/// not exported, `function unused() {}` → ``.
+///
+/// A reference only counts when it is outside of the declaration itself and of the other removed
+/// declarations, so recursive functions and declarations only used by unused ones are removed as
+/// well. Variables are kept when their initializer may have side effects, and classes when
+/// evaluating them may, for example with static blocks, decorators or a super class which is not
+/// a function or class declaration. Nothing is removed when the module calls `eval`.
+///
+/// Exports listed in `compress.unused_exports` are removed first, so their declarations are
+/// removed as well unless the module uses them.
+///
+/// Enabled by `compress.unused`
+pub struct RemoveUnused<'a> {
+    ast: AstBuilder<'a>,
+    pure_getters: PureGetters,
+    unused_exports: Vec<String>,
+}
+
+impl<'a> RemoveUnused<'a> {
+    pub fn new(ast: AstBuilder<'a>, pure_getters: PureGetters) -> Self {
+        Self { ast, pure_getters, unused_exports: vec![] }
+    }
+
+    /// See [`CompressOptions::unused_exports`](crate::CompressOptions::unused_exports).
+    #[must_use]
+    pub fn with_unused_exports(mut self, unused_exports: Vec<String>) -> Self {
+        self.unused_exports = unused_exports;
+        self
+    }
+
+    pub fn build(&self, program: &mut Program<'a>) {
+        if !program.source_type.is_module() {
+            return;
+        }
+        if !self.unused_exports.is_empty() {
+            self.remove_exports(program);
+        }
+        let removed = {
+            let semantic = SemanticBuilder::new("", program.source_type).build(program).semantic;
+            if semantic.scopes().root_unresolved_references().contains_key("eval") {
+                return;
+            }
+            let mut finder = UnusedDeclarations {
+                semantic: &semantic,
+                effects: DeclarationEffects::new(&semantic, self.pure_getters),
+                removed: vec![],
+            };
+            finder.find(program);
+            finder.removed
+        };
+        if removed.is_empty() {
+            return;
+        }
+        let is_removed = |span: Span| removed.contains(&span);
+        program.body.retain_mut(|stmt| match stmt {
+            Statement::FunctionDeclaration(func) => !is_removed(func.span),
+            Statement::ClassDeclaration(class) => !is_removed(class.span),
+            Statement::VariableDeclaration(decl) => {
+                decl.declarations.retain(|declarator| !is_removed(declarator.span));
+                !decl.declarations.is_empty()
+            }
+            _ => true,
+        });
+    }
+
+    /// Turns exported declarations whose names are all unused into plain declarations, and
+    /// removes unused names from export lists, `export const a = 1; export { b as c }` →
+    /// `const a = 1` if `a` and `c` are unused.
+    ///
+    /// Re-exports are kept, since [`SymbolLiveness`](crate::SymbolLiveness) records them as
+    /// roots.
+    fn remove_exports(&self, program: &mut Program<'a>) {
+        let is_unused = |name: &str| self.unused_exports.iter().any(|unused| unused == name);
+        for stmt in program.body.iter_mut() {
+            match stmt {
+                Statement::ExportNamedDeclaration(decl) => {
+                    if let Some(declaration) = &decl.declaration {
+                        let mut all_unused = !declaration.is_typescript_syntax();
+                        declaration.bound_names(&mut |ident| all_unused &= is_unused(&ident.name));
+                        if !all_unused {
+                            continue;
+                        }
+                        let Statement::ExportNamedDeclaration(decl) = self.ast.move_statement(stmt)
+                        else {
+                            unreachable!()
+                        };
+                        *stmt = Statement::from(decl.unbox().declaration.unwrap());
+                    } else if decl.source.is_none() {
+                        decl.specifiers.retain(|specifier| !is_unused(&specifier.exported.name()));
+                    }
+                }
+                Statement::ExportDefaultDeclaration(decl) if is_unused("default") => {
+                    match &decl.declaration {
+                        ExportDefaultDeclarationKind::FunctionDeclaration(func)
+                            if func.id.is_some() => {}
+                        ExportDefaultDeclarationKind::ClassDeclaration(class)
+                            if class.id.is_some() => {}
+                        ExportDefaultDeclarationKind::Identifier(_) => {
+                            *stmt = self.ast.statement_empty(decl.span);
+                            continue;
+                        }
+                        _ => continue,
+                    }
+                    let Statement::ExportDefaultDeclaration(decl) = self.ast.move_statement(stmt)
+                    else {
+                        unreachable!()
+                    };
+                    *stmt = match decl.unbox().declaration {
+                        ExportDefaultDeclarationKind::FunctionDeclaration(func) => {
+                            Statement::FunctionDeclaration(func)
+                        }
+                        ExportDefaultDeclarationKind::ClassDeclaration(class) => {
+                            Statement::ClassDeclaration(class)
+                        }
+                        _ => unreachable!(),
+                    };
+                }
+                _ => {}
+            }
+        }
+        program.body.retain(|stmt| match stmt {
+            Statement::ExportNamedDeclaration(decl) => {
+                decl.declaration.is_some() || decl.source.is_some() || !decl.specifiers.is_empty()
+            }
+            Statement::EmptyStatement(_) => false,
+            _ => true,
+        });
+    }
+}
+
+struct UnusedDeclarations<'s, 'a> {
+    semantic: &'s Semantic<'a>,
+    effects: DeclarationEffects<'s, 'a>,
+    /// Spans of the removed declarations, in which references do not count.
+    removed: Vec<Span>,
+}
+
+impl<'a> UnusedDeclarations<'_, 'a> {
+    /// Collects the unused declarations, until removing them leaves no more unused ones.
+    fn find(&mut self, program: &Program<'a>) {
+        loop {
+            let count = self.removed.len();
+            for stmt in &program.body {
+                match stmt {
+                    Statement::FunctionDeclaration(func) => {
+                        self.check(func.id.as_ref(), func.span);
+                    }
+                    Statement::ClassDeclaration(class)
+                        if !self.effects.class_may_have_side_effects(class) =>
+                    {
+                        self.check(class.id.as_ref(), class.span);
+                    }
+                    Statement::VariableDeclaration(decl) => {
+                        for declarator in &decl.declarations {
+                            let BindingPatternKind::BindingIdentifier(id) = &declarator.id.kind
+                            else {
+                                continue;
+                            };
+                            if !declarator
+                                .init
+                                .as_ref()
+                                .is_some_and(|init| self.effects.may_have_side_effects(init))
+                            {
+                                self.check(Some(id), declarator.span);
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            if self.removed.len() == count {
+                break;
+            }
+        }
+    }
+
+    /// Marks the declaration at `span` as removed if its binding is unused.
+    fn check(&mut self, id: Option<&BindingIdentifier<'a>>, span: Span) {
+        // Generated declarations share their span, so they can not be told apart.
+        if span.is_unspanned() || self.removed.contains(&span) {
+            return;
+        }
+        let Some(symbol_id) = id.and_then(|id| id.symbol_id.get()) else { return };
+        let symbols = self.semantic.symbols();
+        if symbols.get_flag(symbol_id).contains(SymbolFlags::Export) {
+            return;
+        }
+        let nodes = self.semantic.nodes();
+        let is_unused = symbols.get_resolved_references(symbol_id).all(|reference| {
+            let node = nodes.get_node(reference.node_id()).kind().span();
+            !node.is_unspanned()
+                && (contains(span, node)
+                    || self.removed.iter().any(|&removed| contains(removed, node)))
+        });
+        if is_unused {
+            self.removed.push(span);
+        }
+    }
+}
+
+/// Whether evaluating a top level declaration may have side effects, so it has to be kept even
+/// if its bindings are unused.
+pub(crate) struct DeclarationEffects<'s, 'a> {
+    semantic: &'s Semantic<'a>,
+    pure_getters: PureGetters,
+}
+
+impl<'s, 'a> DeclarationEffects<'s, 'a> {
+    pub(crate) fn new(semantic: &'s Semantic<'a>, pure_getters: PureGetters) -> Self {
+        Self { semantic, pure_getters }
+    }
+
+    /// Whether evaluating the initializer `expr` of a variable may have side effects.
+    pub(crate) fn may_have_side_effects(&self, expr: &Expression<'a>) -> bool {
+        match expr.without_parenthesized() {
+            Expression::FunctionExpression(_) | Expression::ArrowFunctionExpression(_) => false,
+            Expression::ClassExpression(class) => self.class_may_have_side_effects(class),
+            expr => expr.may_have_side_effects(self.pure_getters),
+        }
+    }
+
+    pub(crate) fn class_may_have_side_effects(&self, class: &Class<'a>) -> bool {
+        if !class.decorators.is_empty() {
+            return true;
+        }
+        if let Some(super_class) = &class.super_class {
+            if !self.is_constructor(super_class) {
+                return true;
+            }
+        }
+        let key_may_have_side_effects = |key: &PropertyKey<'a>, computed: bool| {
+            computed && key.as_expression().map_or(true, |key| self.may_have_side_effects(key))
+        };
+        class.body.body.iter().any(|element| match element {
+            ClassElement::StaticBlock(block) => !block.body.is_empty(),
+            ClassElement::MethodDefinition(method) => {
+                !method.decorators.is_empty()
+                    || key_may_have_side_effects(&method.key, method.computed)
+            }
+            ClassElement::PropertyDefinition(prop) => {
+                !prop.decorators.is_empty()
+                    || key_may_have_side_effects(&prop.key, prop.computed)
+                    || (prop.r#static
+                        && prop
+                            .value
+                            .as_ref()
+                            .is_some_and(|value| self.may_have_side_effects(value)))
+            }
+            ClassElement::AccessorProperty(prop) => {
+                !prop.decorators.is_empty()
+                    || key_may_have_side_effects(&prop.key, prop.computed)
+                    || (prop.r#static
+                        && prop
+                            .value
+                            .as_ref()
+                            .is_some_and(|value| self.may_have_side_effects(value)))
+            }
+            ClassElement::TSIndexSignature(_) => false,
+        })
+    }
+
+    /// Whether `expr` is a reference to a function or class declaration which is never
+    /// redeclared or reassigned, so extending it does not throw.
+    fn is_constructor(&self, expr: &Expression<'a>) -> bool {
+        let Expression::Identifier(ident) = expr.without_parenthesized() else { return false };
+        let symbols = self.semantic.symbols();
+        let Some(symbol_id) =
+            ident.reference_id.get().and_then(|id| symbols.get_reference(id).symbol_id())
+        else {
+            return false;
+        };
+        let is_declaration = match self.semantic.nodes().kind(symbols.get_declaration(symbol_id)) {
+            AstKind::Function(func) => func.is_declaration() && !func.generator && !func.r#async,
+            AstKind::Class(class) => class.is_declaration(),
+            _ => false,
+        };
+        is_declaration
+            && symbols.get_redeclarations(symbol_id).is_empty()
+            && symbols.get_resolved_references(symbol_id).all(|reference| !reference.is_write())
+    }
+}
+
+fn contains(outer: Span, inner: Span) -> bool {
+    outer.start <= inner.start && inner.end <= outer.end
+}
@@ -0,0 +1,264 @@
+use std::ops::Range;
+
+use oxc_ast::{ast::*, syntax_directed_operations::BoundNames, Visit};
+use oxc_semantic::{Semantic, SemanticBuilder, SymbolId, SymbolTable};
+use rustc_hash::FxHashMap;
+
+use crate::{ast_passes::DeclarationEffects, PureGetters};
+
+/// Reorder top level declarations without side effects so that similar declarations are next to
+/// each other, which gives gzip more repeated text to refer back to:
+/// `const a = {}; function f() {} const b = {}` → `const a = {}; const b = {}; function f() {}`.
+///
+/// Only consecutive declarations whose evaluation has no side effects are reordered: functions,
+/// classes and variables whose initializers have none, exported or not. Two declarations keep
+/// their order when either references a binding of the other anywhere in its code, including
+/// nested functions, so every initializer sees the same bindings and throws the same temporal
+/// dead zone errors, or when both declare the same binding. Other statements stay where they
+/// are, and nothing moves when the program calls `eval`.
+///
+/// Experimental, enabled by `compress.reorder_declarations`
+pub struct ReorderDeclarations {
+    pure_getters: PureGetters,
+}
+
+/// What a declaration looks like. Declarations of the same shape are grouped together.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Shape {
+    is_exported: bool,
+    kind: DeclarationShape,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum DeclarationShape {
+    Function,
+    Class,
+    /// The kind of the declaration and the shape of its first initializer.
+    Variable(VariableDeclarationKind, InitializerShape),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum InitializerShape {
+    None,
+    Function,
+    Arrow,
+    Class,
+    Object,
+    Array,
+    Literal,
+    Template,
+    Call,
+    Other,
+}
+
+impl ReorderDeclarations {
+    pub fn new(pure_getters: PureGetters) -> Self {
+        Self { pure_getters }
+    }
+
+    /// Returns whether any declaration moved.
+    pub fn build(&self, program: &mut Program<'_>) -> bool {
+        let order = {
+            let semantic = SemanticBuilder::new("", program.source_type).build(program).semantic;
+            if semantic.scopes().root_unresolved_references().contains_key("eval") {
+                return false;
+            }
+            Planner::new(&semantic, self.pure_getters).order(&program.body)
+        };
+        if order.iter().enumerate().all(|(index, &i)| index == i) {
+            return false;
+        }
+        let mut stmts = program.body.drain(..).map(Some).collect::<std::vec::Vec<_>>();
+        program.body.extend(order.into_iter().filter_map(|i| stmts[i].take()));
+        true
+    }
+}
+
+struct Planner<'s, 'a> {
+    symbols: &'s SymbolTable,
+    effects: DeclarationEffects<'s, 'a>,
+    /// Shapes of the statements, `None` for the statements which must not move.
+    shapes: std::vec::Vec<Option<Shape>>,
+    /// The statements declaring each top level binding of a declaration which may move, more than
+    /// one for redeclared `var`s and functions.
+    declarations: FxHashMap<SymbolId, std::vec::Vec<usize>>,
+}
+
+impl<'s, 'a> Planner<'s, 'a> {
+    fn new(semantic: &'s Semantic<'a>, pure_getters: PureGetters) -> Self {
+        Self {
+            symbols: semantic.symbols(),
+            effects: DeclarationEffects::new(semantic, pure_getters),
+            shapes: vec![],
+            declarations: FxHashMap::default(),
+        }
+    }
+
+    /// The new order of `stmts`, by index.
+    fn order(mut self, stmts: &[Statement<'a>]) -> std::vec::Vec<usize> {
+        self.shapes = stmts.iter().map(|stmt| self.shape(stmt)).collect();
+        for (index, stmt) in stmts.iter().enumerate() {
+            if self.shapes[index].is_some() {
+                if let Some(declaration) = declaration(stmt) {
+                    declaration.bound_names(&mut |ident| {
+                        if let Some(symbol_id) = ident.symbol_id.get() {
+                            self.declarations.entry(symbol_id).or_default().push(index);
+                        }
+                    });
+                }
+            }
+        }
+        let mut order = std::vec::Vec::with_capacity(stmts.len());
+        let mut start = 0;
+        while start < stmts.len() {
+            let end = self.shapes[start..]
+                .iter()
+                .position(Option::is_none)
+                .map_or(stmts.len(), |length| start + length);
+            if start == end {
+                order.push(start);
+                start += 1;
+            } else {
+                self.order_run(stmts, start..end, &mut order);
+                start = end;
+            }
+        }
+        order
+    }
+
+    /// Orders the declarations of `run`, a range of consecutive statements which may move, so
+    /// that declarations of the same shape follow each other as long as no dependency is in the
+    /// way. Otherwise the first declaration in the original order goes next.
+    fn order_run(
+        &self,
+        stmts: &[Statement<'a>],
+        run: Range<usize>,
+        order: &mut std::vec::Vec<usize>,
+    ) {
+        // The later declarations which have to stay after each declaration, and how many
+        // declarations have to stay before each one, indexed from the start of the run.
+        let mut successors = vec![vec![]; run.len()];
+        let mut predecessors = vec![0; run.len()];
+        for index in run.clone() {
+            let mut references = References { symbols: self.symbols, symbol_ids: vec![] };
+            references.visit_statement(&stmts[index]);
+            // Redeclarations of the same binding keep their order, so the last one still
+            // assigns the final value.
+            if let Some(declaration) = declaration(&stmts[index]) {
+                declaration.bound_names(&mut |ident| {
+                    references.symbol_ids.extend(ident.symbol_id.get());
+                });
+            }
+            for symbol_id in references.symbol_ids {
+                let Some(others) = self.declarations.get(&symbol_id) else { continue };
+                for &other in others {
+                    if other != index && run.contains(&other) {
+                        let (first, second) = (index.min(other), index.max(other));
+                        successors[first - run.start].push(second - run.start);
+                    }
+                }
+            }
+        }
+        for successors in &mut successors {
+            successors.sort_unstable();
+            successors.dedup();
+            for &successor in successors.iter() {
+                predecessors[successor] += 1;
+            }
+        }
+        let mut available =
+            (0..run.len()).filter(|&i| predecessors[i] == 0).collect::<std::vec::Vec<_>>();
+        let mut last = None;
+        while !available.is_empty() {
+            let position = last
+                .and_then(|shape| {
+                    available.iter().position(|&i| self.shapes[run.start + i] == Some(shape))
+                })
+                .unwrap_or(0);
+            let i = available.remove(position);
+            order.push(run.start + i);
+            last = self.shapes[run.start + i];
+            for &successor in &successors[i] {
+                predecessors[successor] -= 1;
+                if predecessors[successor] == 0 {
+                    let at = available.partition_point(|&other| other < successor);
+                    available.insert(at, successor);
+                }
+            }
+        }
+    }
+
+    /// The shape of `stmt` if it is a declaration without side effects.
+    fn shape(&self, stmt: &Statement<'a>) -> Option<Shape> {
+        let is_exported = match stmt {
+            Statement::ExportNamedDeclaration(decl) => decl.source.is_none(),
+            _ => false,
+        };
+        let kind = match declaration(stmt)? {
+            Declaration::FunctionDeclaration(func) => {
+                (!func.declare && func.body.is_some()).then_some(DeclarationShape::Function)?
+            }
+            Declaration::ClassDeclaration(class) => (!class.declare
+                && !self.effects.class_may_have_side_effects(class))
+            .then_some(DeclarationShape::Class)?,
+            Declaration::VariableDeclaration(decl) => {
+                let is_pure = !decl.declare
+                    && decl.declarations.iter().all(|declarator| {
+                        // Destructuring may call getters and iterators.
+                        declarator.id.kind.is_binding_identifier()
+                            && declarator
+                                .init
+                                .as_ref()
+                                .map_or(true, |init| !self.effects.may_have_side_effects(init))
+                    });
+                let init =
+                    decl.declarations.first().and_then(|declarator| declarator.init.as_ref());
+                is_pure.then(|| DeclarationShape::Variable(decl.kind, initializer_shape(init)))?
+            }
+            _ => return None,
+        };
+        Some(Shape { is_exported, kind })
+    }
+}
+
+fn declaration<'b, 'a>(stmt: &'b Statement<'a>) -> Option<&'b Declaration<'a>> {
+    match stmt {
+        Statement::ExportNamedDeclaration(decl) if decl.source.is_none() => {
+            decl.declaration.as_ref()
+        }
+        _ => stmt.as_declaration(),
+    }
+}
+
+fn initializer_shape(init: Option<&Expression>) -> InitializerShape {
+    match init.map(Expression::without_parenthesized) {
+        None => InitializerShape::None,
+        Some(Expression::FunctionExpression(_)) => InitializerShape::Function,
+        Some(Expression::ArrowFunctionExpression(_)) => InitializerShape::Arrow,
+        Some(Expression::ClassExpression(_)) => InitializerShape::Class,
+        Some(Expression::ObjectExpression(_)) => InitializerShape::Object,
+        Some(Expression::ArrayExpression(_)) => InitializerShape::Array,
+        Some(Expression::TemplateLiteral(_)) => InitializerShape::Template,
+        Some(Expression::CallExpression(_) | Expression::NewExpression(_)) => {
+            InitializerShape::Call
+        }
+        Some(expr) if expr.is_literal() => InitializerShape::Literal,
+        Some(_) => InitializerShape::Other,
+    }
+}
+
+/// The symbols referenced in a statement.
+struct References<'s> {
+    symbols: &'s SymbolTable,
+    symbol_ids: std::vec::Vec<SymbolId>,
+}
+
+impl<'a, 's> Visit<'a> for References<'s> {
+    fn visit_identifier_reference(&mut self, ident: &IdentifierReference<'a>) {
+        if let Some(symbol_id) =
+            ident.reference_id.get().and_then(|id| self.symbols.get_reference(id).symbol_id())
+        {
+            self.symbol_ids.push(symbol_id);
+        }
+    }
+}
@@ -4,9 +4,11 @@ use oxc_allocator::Allocator;
 use oxc_ast::{ast::*, visit::walk_mut, AstBuilder, VisitMut};
 use oxc_diagnostics::OxcDiagnostic;
 use oxc_parser::Parser;
-use oxc_span::SourceType;
+use oxc_span::{GetSpan, SourceType};
 use oxc_syntax::identifier::is_identifier_name;
 
+use crate::Env;
+
 /// Configuration for [ReplaceGlobalDefines].
 ///
 /// Due to the usage of an arena allocator, the constructor will parse once for grammatical errors,
@@ -33,57 +35,132 @@ impl ReplaceGlobalDefinesConfig {
     /// * key is not an identifier
     /// * value has a syntax error
     pub fn new<S: AsRef<str>>(defines: &[(S, S)]) -> Result<Self, Vec<OxcDiagnostic>> {
+        Self::from_iter(defines.iter().map(|(key, value)| (key, value)))
+    }
+
+    /// Same as [ReplaceGlobalDefinesConfig::new], but reports all invalid defines instead of
+    /// only the first one.
+    ///
+    /// # Errors
+    ///
+    /// * key is not an identifier
+    /// * value is not a single expression
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_iter<I, K, V>(defines: I) -> Result<Self, Vec<OxcDiagnostic>>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
         let allocator = Allocator::default();
         let mut identifier_defines = vec![];
         let mut dot_defines = vec![];
+        let mut errors = vec![];
         for (key, value) in defines {
             let key = key.as_ref();
-
             let value = value.as_ref();
-            Self::check_value(&allocator, value)?;
 
-            match Self::check_key(key)? {
-                IdentifierType::Identifier => {
+            if let Err(error) = Self::check_value(&allocator, key, value) {
+                errors.push(error);
+                continue;
+            }
+
+            match Self::check_key(key) {
+                Ok(IdentifierType::Identifier) => {
                     identifier_defines.push((key.to_string(), value.to_string()));
                 }
-                IdentifierType::DotDefines(parts) => {
+                Ok(IdentifierType::DotDefines(parts)) => {
                     dot_defines.push((parts, value.to_string()));
                 }
+                Err(error) => errors.push(error),
             }
         }
 
+        if !errors.is_empty() {
+            return Err(errors);
+        }
         Ok(Self(Arc::new(ReplaceGlobalDefinesConfigImpl { identifier_defines, dot_defines })))
     }
 
-    fn check_key(key: &str) -> Result<IdentifierType, Vec<OxcDiagnostic>> {
+    /// Reads defines from a JSON object, e.g. `{ "process.env.NODE_ENV": "'production'" }`.
+    ///
+    /// String values are the source text of the replacement. Other values are used as written,
+    /// so `{ "DEBUG": false }` is the same as `{ "DEBUG": "false" }`.
+    ///
+    /// # Errors
+    ///
+    /// * the JSON is malformed or not an object
+    /// * any of the errors of [ReplaceGlobalDefinesConfig::from_iter]
+    pub fn from_json(json: &str) -> Result<Self, Vec<OxcDiagnostic>> {
+        let value: serde_json::Value = serde_json::from_str(json).map_err(|err| {
+            vec![OxcDiagnostic::error(format!("Failed to parse defines as JSON: {err}."))]
+        })?;
+        let serde_json::Value::Object(defines) = value else {
+            return Err(vec![OxcDiagnostic::error("Defines must be a JSON object.").with_help(
+                r#"Map each global to its replacement, e.g. `{ "process.env.NODE_ENV": "'production'" }`."#,
+            )]);
+        };
+        Self::from_iter(defines.into_iter().map(|(key, value)| match value {
+            serde_json::Value::String(value) => (key, value),
+            value => (key, value.to_string()),
+        }))
+    }
+
+    fn check_key(key: &str) -> Result<IdentifierType, OxcDiagnostic> {
         let parts: Vec<&str> = key.split('.').collect();
 
         assert!(!parts.is_empty());
 
         if parts.len() == 1 {
             if !is_identifier_name(parts[0]) {
-                return Err(vec![OxcDiagnostic::error(format!("`{key}` is not an identifier."))]);
+                return Err(OxcDiagnostic::error(format!("`{key}` is not an identifier.")));
             }
             return Ok(IdentifierType::Identifier);
         }
 
         for part in &parts {
             if !is_identifier_name(part) {
-                return Err(vec![OxcDiagnostic::error(format!("`{key}` is not an identifier."))]);
+                return Err(OxcDiagnostic::error(format!("`{key}` is not an identifier.")));
             }
         }
 
         Ok(IdentifierType::DotDefines(parts.iter().map(ToString::to_string).collect()))
     }
 
-    fn check_value(allocator: &Allocator, source_text: &str) -> Result<(), Vec<OxcDiagnostic>> {
-        Parser::new(allocator, source_text, SourceType::default()).parse_expression()?;
-        Ok(())
+    fn check_value(
+        allocator: &Allocator,
+        key: &str,
+        source_text: &str,
+    ) -> Result<(), OxcDiagnostic> {
+        let error = || {
+            OxcDiagnostic::error(format!(
+                "The replacement for `{key}` is not a valid expression: `{source_text}`."
+            ))
+        };
+        match Parser::new(allocator, source_text, SourceType::default()).parse_expression() {
+            // The parser stops after the first expression, e.g. `a b` is parsed as `a`.
+            Ok(expr) => {
+                let end = expr.span().end as usize;
+                if end == source_text.trim_end().len() {
+                    Ok(())
+                } else {
+                    let rest = source_text[end..].trim();
+                    Err(error().with_help(format!("Unexpected `{rest}` after the expression.")))
+                }
+            }
+            Err(errors) => Err(errors
+                .into_iter()
+                .next()
+                .map_or_else(error, |e| error().with_help(e.message.clone()))),
+        }
     }
 }
 
 /// Replace Global Defines.
 ///
+/// Keys are identifiers, `DEBUG`, or member expressions of an identifier or of `import.meta`,
+/// `process.env.NODE_ENV` or `import.meta.env.MODE`.
+///
 /// References:
 ///
 /// * <https://esbuild.github.io/api/#define>
@@ -92,15 +169,79 @@ impl ReplaceGlobalDefinesConfig {
 pub struct ReplaceGlobalDefines<'a> {
     ast: AstBuilder<'a>,
     config: ReplaceGlobalDefinesConfig,
+    env: Env,
+    warnings: Vec<OxcDiagnostic>,
+}
+
+pub struct ReplaceGlobalDefinesReturn {
+    /// Local bindings which share a name with a define, and defines which replace a global of
+    /// the environment.
+    pub warnings: Vec<OxcDiagnostic>,
 }
 
 impl<'a> ReplaceGlobalDefines<'a> {
     pub fn new(allocator: &'a Allocator, config: ReplaceGlobalDefinesConfig) -> Self {
-        Self { ast: AstBuilder::new(allocator), config }
+        Self { ast: AstBuilder::new(allocator), config, env: Env::Neutral, warnings: vec![] }
+    }
+
+    /// The environment the output runs in, see
+    /// [`CompressOptions::env`](crate::CompressOptions::env). Defines of globals which exist in it
+    /// are reported since they contradict it, e.g. `window` defined as `undefined` with
+    /// `Env::Browser`.
+    #[must_use]
+    pub fn with_env(mut self, env: Env) -> Self {
+        self.env = env;
+        self
     }
 
-    pub fn build(&mut self, program: &mut Program<'a>) {
+    pub fn build(mut self, program: &mut Program<'a>) -> ReplaceGlobalDefinesReturn {
+        self.check_env_defines();
         self.visit_program(program);
+        ReplaceGlobalDefinesReturn { warnings: self.warnings }
+    }
+
+    fn check_env_defines(&mut self) {
+        let env = match self.env {
+            Env::Neutral => return,
+            Env::Browser => "browser",
+            Env::Node => "Node.js",
+        };
+        for (key, _) in &self.config.0.identifier_defines {
+            if self.env.exists(key) {
+                self.warnings.push(
+                    OxcDiagnostic::warn(format!(
+                        "The define `{key}` replaces a global of the {env} environment."
+                    ))
+                    .with_help("Compress for another environment, or define a property instead."),
+                );
+            }
+        }
+    }
+
+    /// Defines are replaced regardless of scope, so references to a local binding with the same
+    /// name as a define are replaced as well.
+    fn check_shadowed_define(&mut self, ident: &BindingIdentifier<'a>) {
+        let name = ident.name.as_str();
+        let config = &self.config.0;
+        let key = config
+            .identifier_defines
+            .iter()
+            .map(|(key, _)| key.as_str())
+            .find(|key| *key == name)
+            .or_else(|| {
+                config
+                    .dot_defines
+                    .iter()
+                    .map(|(parts, _)| parts[0].as_str())
+                    .find(|part| *part == name)
+            });
+        if let Some(key) = key {
+            self.warnings.push(
+                OxcDiagnostic::warn(format!("The local binding `{key}` shadows a global define."))
+                    .with_help("References to this binding are replaced by the define as well.")
+                    .with_label(ident.span),
+            );
+        }
     }
 
     // Construct a new expression because we don't have ast clone right now.
@@ -126,40 +267,12 @@ impl<'a> ReplaceGlobalDefines<'a> {
     }
 
     fn replace_dot_defines(&self, expr: &mut Expression<'a>) {
-        if let Expression::StaticMemberExpression(member) = expr {
-            'outer: for (parts, value) in &self.config.0.dot_defines {
-                assert!(parts.len() > 1);
-
-                let mut current_part_member_expression = Some(&*member);
-                let mut cur_part_name = &member.property.name;
-
-                for (i, part) in parts.iter().enumerate().rev() {
-                    if cur_part_name.as_str() != part {
-                        continue 'outer;
-                    }
-
-                    if i == 0 {
-                        break;
-                    }
-
-                    current_part_member_expression =
-                        if let Some(member) = current_part_member_expression {
-                            match &member.object.without_parenthesized() {
-                                Expression::StaticMemberExpression(member) => {
-                                    cur_part_name = &member.property.name;
-                                    Some(member)
-                                }
-                                Expression::Identifier(ident) => {
-                                    cur_part_name = &ident.name;
-                                    None
-                                }
-                                _ => None,
-                            }
-                        } else {
-                            continue 'outer;
-                        };
-                }
-
+        if !matches!(expr, Expression::StaticMemberExpression(_) | Expression::MetaProperty(_)) {
+            return;
+        }
+        for (parts, value) in &self.config.0.dot_defines {
+            assert!(parts.len() > 1);
+            if is_dot_define(expr, parts) {
                 let value = self.parse_value(value);
                 *expr = value;
                 break;
@@ -168,7 +281,28 @@ impl<'a> ReplaceGlobalDefines<'a> {
     }
 }
 
+/// Whether `expr` is the member expression `parts`, e.g. `process.env.NODE_ENV`. The object may
+/// be `import.meta`, so `import.meta.env.MODE` can be defined like in Vite.
+fn is_dot_define(expr: &Expression, parts: &[String]) -> bool {
+    match expr {
+        Expression::StaticMemberExpression(member) => {
+            let Some((property, object)) = parts.split_last() else { return false };
+            member.property.name == property
+                && is_dot_define(member.object.without_parenthesized(), object)
+        }
+        Expression::MetaProperty(meta) => {
+            matches!(parts, [meta_name, property] if meta.meta.name == meta_name && meta.property.name == property)
+        }
+        Expression::Identifier(ident) => matches!(parts, [name] if ident.name == name),
+        _ => false,
+    }
+}
+
 impl<'a> VisitMut<'a> for ReplaceGlobalDefines<'a> {
+    fn visit_binding_identifier(&mut self, ident: &mut BindingIdentifier<'a>) {
+        self.check_shadowed_define(ident);
+    }
+
     fn visit_expression(&mut self, expr: &mut Expression<'a>) {
         self.replace_identifier_defines(expr);
         self.replace_dot_defines(expr);
@@ -1,37 +1,84 @@
-use oxc_ast::{ast::*, visit::walk_mut, AstBuilder, VisitMut};
-use oxc_span::SPAN;
+use std::{borrow::Cow, mem};
+
+use oxc_allocator::{Box, Vec};
+use oxc_ast::{
+    ast::*,
+    visit::{walk, walk_mut},
+    AstBuilder, Visit, VisitMut,
+};
+use oxc_span::{Atom, GetSpan, Span, SPAN};
 use oxc_syntax::{
+    identifier::is_identifier_name,
+    keyword::is_es3_reserved_word,
     number::NumberBase,
-    operator::{BinaryOperator, UnaryOperator},
+    operator::{AssignmentOperator, BinaryOperator, LogicalOperator, UnaryOperator},
+    scope::ScopeFlags,
 };
+use rustc_hash::FxHashSet;
 
-use crate::CompressOptions;
+use crate::{
+    ast_util::{get_boolean_value, get_string_value, IsLiteralValue},
+    chunk_boundary::is_chunk_boundary,
+    commonjs,
+    emit_size::EmitSizeEstimator,
+    parameters::{self, ParameterUsage},
+    recycler::Recycler,
+    static_bindings::StaticBindings,
+    ty::Ty,
+    CompressOptions, ESTarget,
+};
 
 /// A peephole optimization that minimizes code by simplifying conditional
 /// expressions, replacing IFs with HOOKs, replacing object constructors
 /// with literals, and simplifying returns.
 pub struct SubstituteAlternateSyntax<'a> {
     ast: AstBuilder<'a>,
+    recycler: Recycler<'a>,
     options: CompressOptions,
+    /// `module.exports` can be replaced with `exports`, see [`commonjs::ModuleExportsUsage`].
+    is_module_exports_aliased: bool,
+    /// Number of enclosing `with` bodies, where any name may resolve to a property of the object.
+    with_depth: usize,
+    bindings: StaticBindings<'a>,
+    /// Function expressions which must not become arrow functions, see [`ConstructorFunctions`].
+    constructor_functions: FxHashSet<Span>,
 }
 
 impl<'a> VisitMut<'a> for SubstituteAlternateSyntax<'a> {
     fn visit_statement(&mut self, stmt: &mut Statement<'a>) {
         self.compress_block(stmt);
-        // self.compress_while(stmt);
         walk_mut::walk_statement(self, stmt);
+        if self.options.booleans {
+            self.compress_statement_test(stmt);
+        }
+        if self.options.loops {
+            self.compress_loop(stmt);
+        }
+        if self.options.conditionals {
+            self.compress_if_statement(stmt);
+        }
+        if self.options.classes {
+            self.compress_class_variable(stmt);
+        }
+    }
+
+    fn visit_statements(&mut self, stmts: &mut Vec<'a, Statement<'a>>) {
+        walk_mut::walk_statements(self, stmts);
+        if self.options.conditionals {
+            self.compress_if_returns(stmts);
+        }
     }
 
     fn visit_return_statement(&mut self, stmt: &mut ReturnStatement<'a>) {
         walk_mut::walk_return_statement(self, stmt);
         // We may fold `void 1` to `void 0`, so compress it after visiting
-        Self::compress_return_statement(stmt);
+        self.compress_return_statement(stmt);
     }
 
     fn visit_variable_declaration(&mut self, decl: &mut VariableDeclaration<'a>) {
         for declarator in decl.declarations.iter_mut() {
             self.visit_variable_declarator(declarator);
-            Self::compress_variable_declarator(declarator);
+            self.compress_variable_declarator(declarator);
         }
     }
 
@@ -40,78 +87,659 @@ impl<'a> VisitMut<'a> for SubstituteAlternateSyntax<'a> {
         if Self::is_object_define_property_exports(expr) {
             return;
         }
+        if self.options.keep_chunk_boundaries && is_chunk_boundary(expr) {
+            return;
+        }
         walk_mut::walk_expression(self, expr);
-        if !self.compress_undefined(expr) {
+        // Names inside `with` may resolve to properties of the object.
+        let is_global_scope = self.with_depth == 0;
+        if is_global_scope && self.is_module_exports_aliased {
+            self.compress_module_exports(expr);
+        }
+        if self.options.booleans {
+            if is_global_scope {
+                self.compress_boolean_call(expr);
+            }
+            self.compress_boolean_operands(expr);
+        }
+        if !(is_global_scope && self.compress_undefined(expr)) {
             self.compress_boolean(expr);
         }
+        if self.options.template_literals
+            && !(self.options.target >= ESTarget::ES2015
+                && self.compress_string_concatenation(expr))
+        {
+            self.compress_template_literal(expr);
+        }
+        if self.options.arrow_functions && self.options.target >= ESTarget::ES2015 {
+            self.compress_function_expression(expr);
+        }
+    }
+
+    fn visit_with_statement(&mut self, stmt: &mut WithStatement<'a>) {
+        self.visit_expression(&mut stmt.object);
+        self.with_depth += 1;
+        self.visit_statement(&mut stmt.body);
+        self.with_depth -= 1;
+    }
+
+    fn visit_member_expression(&mut self, expr: &mut MemberExpression<'a>) {
+        walk_mut::walk_member_expression(self, expr);
+        self.compress_property_access(expr);
     }
 
     fn visit_binary_expression(&mut self, expr: &mut BinaryExpression<'a>) {
         walk_mut::walk_binary_expression(self, expr);
         self.compress_typeof_undefined(expr);
     }
+
+    fn visit_function(&mut self, func: &mut Function<'a>, flags: ScopeFlags) {
+        walk_mut::walk_function(self, func, flags);
+        if !flags.intersects(ScopeFlags::GetAccessor | ScopeFlags::SetAccessor) {
+            self.compress_arguments(func);
+        }
+        if let Some(body) = &mut func.body {
+            let is_setter = flags.contains(ScopeFlags::SetAccessor);
+            self.compress_parameters(&mut func.params, body, false, is_setter);
+        }
+    }
+
+    fn visit_arrow_function_expression(&mut self, arrow: &mut ArrowFunctionExpression<'a>) {
+        walk_mut::walk_arrow_function_expression(self, arrow);
+        self.compress_parameters(&mut arrow.params, &mut arrow.body, arrow.expression, false);
+    }
+
+    fn visit_object_property(&mut self, prop: &mut ObjectProperty<'a>) {
+        if !prop.computed && !prop.shorthand {
+            self.compress_property_key(&mut prop.key);
+        }
+        // Accessors can not have a rest parameter.
+        if prop.kind != PropertyKind::Init {
+            if let Expression::FunctionExpression(func) = &mut prop.value {
+                self.visit_property_key(&mut prop.key);
+                walk_mut::walk_function(self, func, ScopeFlags::Function);
+                return;
+            }
+        }
+        // Methods are shorter than arrow functions.
+        if self.can_use_shorthand() {
+            if let Expression::FunctionExpression(func) = &mut prop.value {
+                self.visit_property_key(&mut prop.key);
+                self.visit_function(func, ScopeFlags::Function);
+                self.compress_shorthand_property(prop);
+                return;
+            }
+        }
+        walk_mut::walk_object_property(self, prop);
+        if self.can_use_shorthand() {
+            self.compress_shorthand_property(prop);
+        }
+    }
+
+    fn visit_argument(&mut self, arg: &mut Argument<'a>) {
+        walk_mut::walk_argument(self, arg);
+        if let Argument::SpreadElement(spread) = arg {
+            self.compress_iterated_array(&mut spread.argument, false);
+        }
+    }
+
+    fn visit_array_expression_element(&mut self, elem: &mut ArrayExpressionElement<'a>) {
+        walk_mut::walk_array_expression_element(self, elem);
+        if let ArrayExpressionElement::SpreadElement(spread) = elem {
+            self.compress_iterated_array(&mut spread.argument, false);
+        }
+    }
+
+    fn visit_for_of_statement(&mut self, stmt: &mut ForOfStatement<'a>) {
+        walk_mut::walk_for_of_statement(self, stmt);
+        self.compress_iterated_array(&mut stmt.right, false);
+    }
+
+    fn visit_expression_statement(&mut self, stmt: &mut ExpressionStatement<'a>) {
+        walk_mut::walk_expression_statement(self, stmt);
+        // The value of the assignment is the array itself, which must keep its length and
+        // elements when it is used.
+        if let Expression::AssignmentExpression(expr) = &mut stmt.expression {
+            if expr.operator == AssignmentOperator::Assign {
+                if let AssignmentTarget::ArrayAssignmentTarget(target) = &expr.left {
+                    let has_rest = target.rest.is_some();
+                    self.compress_iterated_array(&mut expr.right, !has_rest);
+                }
+            }
+        }
+    }
 }
 
 impl<'a> SubstituteAlternateSyntax<'a> {
     pub fn new(ast: AstBuilder<'a>, options: CompressOptions) -> Self {
-        Self { ast, options }
+        Self {
+            ast,
+            recycler: Recycler::default(),
+            options,
+            is_module_exports_aliased: false,
+            with_depth: 0,
+            bindings: StaticBindings::default(),
+            constructor_functions: FxHashSet::default(),
+        }
     }
 
     pub fn build(&mut self, program: &mut Program<'a>) {
+        self.is_module_exports_aliased = self.options.commonjs
+            && !program.source_type.is_module()
+            && commonjs::ModuleExportsUsage::is_aliased(program);
+        if self.options.booleans || self.options.classes {
+            self.bindings =
+                StaticBindings::new(program, self.options.pure_getters, self.options.env);
+        }
+        if self.options.arrow_functions {
+            self.constructor_functions = ConstructorFunctions::collect(program);
+        }
         self.visit_program(program);
     }
 
+    pub fn with_recycler(mut self, recycler: Recycler<'a>) -> Self {
+        self.recycler = recycler;
+        self
+    }
+
+    pub fn into_recycler(self) -> Recycler<'a> {
+        self.recycler
+    }
+
     /* Utilities */
 
     /// Transforms `undefined` => `void 0`
-    fn compress_undefined(&self, expr: &mut Expression<'a>) -> bool {
+    fn compress_undefined(&mut self, expr: &mut Expression<'a>) -> bool {
         let Expression::Identifier(ident) = expr else { return false };
         if ident.name == "undefined" {
             // if let Some(reference_id) = ident.reference_id.get() {
             // && self.semantic.symbols().is_global_reference(reference_id)
             *expr = self.ast.void_0();
+            self.recycler.mark_changed();
             return true;
             // }
         }
         false
     }
 
-    /// Test `Object.defineProperty(exports, ...)`
+    /// Test `Object.defineProperty(exports, ...)` and `Object.defineProperty(module.exports, ...)`
     fn is_object_define_property_exports(expr: &Expression<'a>) -> bool {
         let Expression::CallExpression(call_expr) = expr else { return false };
-        let Some(Argument::Identifier(ident)) = call_expr.arguments.first() else { return false };
-        if ident.name != "exports" {
+        let Some(exports) = call_expr.arguments.first().and_then(Argument::as_expression) else {
+            return false;
+        };
+        if !commonjs::is_exports(exports) {
             return false;
         }
         call_expr.callee.is_specific_member_access("Object", "defineProperty")
     }
 
+    /// Transforms `module.exports` => `exports`
+    ///
+    /// Enabled by `compress.commonjs`
+    fn compress_module_exports(&mut self, expr: &mut Expression<'a>) {
+        if expr.is_specific_member_access("module", "exports") {
+            let exports = self.ast.expression_identifier_reference(expr.span(), "exports");
+            self.recycler.replace_expression(expr, exports);
+        }
+    }
+
+    /// Whether `name` can be written after a `.` or as an unquoted key for the target.
+    fn is_property_name(&self, name: &str) -> bool {
+        is_identifier_name(name)
+            && !(self.options.target == ESTarget::ES3 && is_es3_reserved_word(name))
+    }
+
+    /// `foo["bar"]` => `foo.bar`, enabled by `compress.properties`
+    /// `foo.class` => `foo["class"]` for ES3
+    fn compress_property_access(&mut self, expr: &mut MemberExpression<'a>) {
+        match expr {
+            MemberExpression::ComputedMemberExpression(member_expr) if self.options.properties => {
+                let Expression::StringLiteral(lit) = &member_expr.expression else { return };
+                if !self.is_property_name(&lit.value) {
+                    return;
+                }
+                let property = self.ast.identifier_name(lit.span, lit.value.clone());
+                let object = self.recycler.take_expression(self.ast, &mut member_expr.object);
+                *expr = self.ast.member_expression_static(
+                    member_expr.span,
+                    object,
+                    property,
+                    member_expr.optional,
+                );
+            }
+            MemberExpression::StaticMemberExpression(member_expr) => {
+                if self.is_property_name(&member_expr.property.name) {
+                    return;
+                }
+                let property = self.ast.expression_string_literal(
+                    member_expr.property.span,
+                    &member_expr.property.name,
+                );
+                let object = self.recycler.take_expression(self.ast, &mut member_expr.object);
+                *expr = self.ast.member_expression_computed(
+                    member_expr.span,
+                    object,
+                    property,
+                    member_expr.optional,
+                );
+            }
+            _ => {}
+        }
+    }
+
+    /// `{ "bar": 1 }` => `{ bar: 1 }`, enabled by `compress.properties`
+    /// `{ class: 1 }` => `{ "class": 1 }` for ES3
+    fn compress_property_key(&mut self, key: &mut PropertyKey<'a>) {
+        match key {
+            PropertyKey::StringLiteral(lit) if self.options.properties => {
+                if self.is_property_name(&lit.value) {
+                    *key = self.ast.property_key_identifier_name(lit.span, lit.value.clone());
+                    self.recycler.mark_changed();
+                }
+            }
+            PropertyKey::StaticIdentifier(ident) => {
+                if !self.is_property_name(&ident.name) {
+                    *key = self.ast.property_key_expression(
+                        self.ast.expression_string_literal(ident.span, &ident.name),
+                    );
+                    self.recycler.mark_changed();
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn can_use_shorthand(&self) -> bool {
+        self.options.shorthand && self.options.target >= ESTarget::ES2015
+    }
+
+    /// `{ a: a }` => `{ a }`
+    /// `{ f: function () {} }` => `{ f() {} }`
+    /// Enabled by `compress.shorthand`
+    fn compress_shorthand_property(&mut self, prop: &mut ObjectProperty<'a>) {
+        if prop.kind != PropertyKind::Init || prop.method || prop.shorthand {
+            return;
+        }
+        // `__proto__: value` sets the prototype, while `__proto__` and `__proto__() {}` define
+        // an own property.
+        let key_name = match &prop.key {
+            PropertyKey::StaticIdentifier(key) if !prop.computed => Some(&key.name),
+            PropertyKey::StringLiteral(key) if !prop.computed => Some(&key.value),
+            _ => None,
+        };
+        if key_name.is_some_and(|name| name == "__proto__") {
+            return;
+        }
+        match &prop.value {
+            Expression::Identifier(ident) if key_name == Some(&ident.name) => {
+                prop.key = self.ast.property_key_identifier_name(ident.span, ident.name.clone());
+                prop.shorthand = true;
+                self.recycler.mark_changed();
+            }
+            Expression::FunctionExpression(_) => self.compress_method(prop),
+            _ => {}
+        }
+    }
+
+    /// `{ f: function () {} }` => `{ f() {} }`, for functions without a name which do not use
+    /// `this` or `new.target` and can therefore be assumed not to be constructors.
+    fn compress_method(&mut self, prop: &mut ObjectProperty<'a>) {
+        let Expression::FunctionExpression(func) = &prop.value else { return };
+        if func.id.is_some() || has_duplicate_parameters(&func.params) {
+            return;
+        }
+        let Some(body) = &func.body else { return };
+        let mut uses = ConstructorUses::default();
+        uses.visit_function_body(body);
+        uses.visit_formal_parameters(&func.params);
+        if !uses.uses_this {
+            prop.method = true;
+            self.recycler.mark_changed();
+        }
+    }
+
+    /// `function (a) { return a }` => `(a) => a`, for functions without a name which do not use
+    /// `this`, `arguments`, `new.target` or a direct `eval`, and which are not used with `new`,
+    /// `instanceof`, `extends` or `.prototype`, directly or through the name they are assigned to.
+    /// Enabled by `compress.arrow_functions`
+    fn compress_function_expression(&mut self, expr: &mut Expression<'a>) {
+        let Expression::FunctionExpression(func) = expr else { return };
+        if func.id.is_some() || func.generator || self.constructor_functions.contains(&func.span) {
+            return;
+        }
+        let Some(body) = &func.body else { return };
+        let mut uses = ConstructorUses::default();
+        uses.visit_function_body(body);
+        uses.visit_formal_parameters(&func.params);
+        if uses.uses_this || uses.uses_arguments {
+            return;
+        }
+        if has_duplicate_parameters(&func.params) {
+            return;
+        }
+        let Expression::FunctionExpression(func) = self.recycler.take_expression(self.ast, expr)
+        else {
+            unreachable!()
+        };
+        let func = func.unbox();
+        let Some(mut body) = func.body else { unreachable!() };
+        let is_expression = body.directives.is_empty()
+            && matches!(
+                body.statements.as_slice(),
+                [Statement::ReturnStatement(stmt)] if stmt.argument.is_some()
+            );
+        if is_expression {
+            let Some(Statement::ReturnStatement(stmt)) = body.statements.pop() else {
+                unreachable!()
+            };
+            let stmt = stmt.unbox();
+            let argument = stmt.argument.unwrap();
+            body.statements.push(self.ast.statement_expression(stmt.span, argument));
+        }
+        *expr = self.ast.expression_arrow_function(
+            func.span,
+            is_expression,
+            func.r#async,
+            func.type_parameters,
+            func.params,
+            func.return_type,
+            body,
+        );
+    }
+
+    /// `[a, void 0, b]` => `[a, , b]` for arrays which are only iterated, and
+    /// `[a, void 0]` => `[a]` if `drop_trailing`, when destructuring without a rest element.
+    /// Enabled by `compress.shorthand`
+    fn compress_iterated_array(&mut self, expr: &mut Expression<'a>, drop_trailing: bool) {
+        if !self.can_use_shorthand() {
+            return;
+        }
+        let Expression::ArrayExpression(array) = expr else { return };
+        for elem in array.elements.iter_mut() {
+            if elem.as_expression().is_some_and(Expression::is_void_0) {
+                *elem = self.ast.array_expression_element_elision(elem.span());
+                self.recycler.mark_changed();
+            }
+        }
+        if drop_trailing {
+            while array.elements.last().is_some_and(ArrayExpressionElement::is_elision) {
+                array.elements.pop();
+                self.recycler.mark_changed();
+            }
+        }
+    }
+
     /* Statements */
 
+    /// `const C = class {}` -> `class C {}`
+    ///
+    /// The class gets the same name either way. Unlike the constant, the binding of a class
+    /// declaration can be assigned, so `C` must never be assigned, where assigning the constant
+    /// throws.
+    fn compress_class_variable(&mut self, stmt: &mut Statement<'a>) {
+        match stmt {
+            Statement::VariableDeclaration(decl) => {
+                if let Some(class) = self.take_class_variable(decl) {
+                    *stmt = Statement::ClassDeclaration(class);
+                }
+            }
+            Statement::ExportNamedDeclaration(export) => {
+                let Some(Declaration::VariableDeclaration(decl)) = &mut export.declaration else {
+                    return;
+                };
+                if let Some(class) = self.take_class_variable(decl) {
+                    export.declaration = Some(Declaration::ClassDeclaration(class));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// The anonymous class of `const C = class {}`, named `C` and turned into a declaration.
+    fn take_class_variable(
+        &mut self,
+        decl: &mut VariableDeclaration<'a>,
+    ) -> Option<Box<'a, Class<'a>>> {
+        if decl.kind != VariableDeclarationKind::Const || decl.declare {
+            return None;
+        }
+        let [declarator] = decl.declarations.as_mut_slice() else { return None };
+        let BindingPatternKind::BindingIdentifier(ident) = &declarator.id.kind else {
+            return None;
+        };
+        let Some(Expression::ClassExpression(class)) = &declarator.init else { return None };
+        if class.id.is_some()
+            || !class.decorators.is_empty()
+            || declarator.id.type_annotation.is_some()
+            || self.bindings.is_assigned(&ident.name)
+        {
+            return None;
+        }
+        let Some(Expression::ClassExpression(mut class)) = declarator.init.take() else {
+            unreachable!()
+        };
+        class.r#type = ClassType::ClassDeclaration;
+        class.id = Some(ident.as_ref().clone());
+        self.recycler.mark_changed();
+        Some(class)
+    }
+
     /// Remove block from single line blocks
     /// `{ block } -> block`
-    #[allow(clippy::only_used_in_recursion)] // `&self` is only used in recursion
-    fn compress_block(&self, stmt: &mut Statement<'a>) {
+    fn compress_block(&mut self, stmt: &mut Statement<'a>) {
         if let Statement::BlockStatement(block) = stmt {
             // Avoid compressing `if (x) { var x = 1 }` to `if (x) var x = 1` due to different
             // semantics according to AnnexB, which lead to different semantics.
             if block.body.len() == 1 && !block.body[0].is_declaration() {
                 *stmt = block.body.remove(0);
+                self.recycler.mark_changed();
                 self.compress_block(stmt);
             }
         }
     }
 
-    // /// Transforms `while(expr)` to `for(;expr;)`
-    // fn compress_while(&mut self, stmt: &mut Statement<'a>) {
-    // let Statement::WhileStatement(while_stmt) = stmt else { return };
-    // if self.options.loops {
-    // let dummy_test = self.ast.expression_this(SPAN);
-    // let test = std::mem::replace(&mut while_stmt.test, dummy_test);
-    // let body = self.ast.move_statement(&mut while_stmt.body);
-    // *stmt = self.ast.statement_for(SPAN, None, Some(test), None, body);
-    // }
-    // }
+    /// `if (a) b(); else c()` → `a ? b() : c()`, `if (a) x = b; else x = c` → `x = a ? b : c`
+    /// and `if (a) return b; else return c` → `return a ? b : c`.
+    ///
+    /// Branches are compressed first, so nested `else if` chains become nested conditionals.
+    /// Enabled by `compress.conditionals`
+    fn compress_if_statement(&mut self, stmt: &mut Statement<'a>) {
+        let Statement::IfStatement(if_stmt) = stmt else { return };
+        let Some(alternate) = &if_stmt.alternate else { return };
+        if !Self::is_conditional_pair(&if_stmt.consequent, alternate) {
+            return;
+        }
+        let Statement::IfStatement(if_stmt) = self.recycler.take_statement(self.ast, stmt) else {
+            unreachable!()
+        };
+        let if_stmt = if_stmt.unbox();
+        let new_stmt = self.join_conditional_pair(
+            if_stmt.span,
+            if_stmt.test,
+            if_stmt.consequent,
+            if_stmt.alternate.unwrap(),
+        );
+        self.recycler.replace_statement(stmt, new_stmt);
+    }
+
+    /// `if (a) return b; return c` → `return a ? b : c`
+    ///
+    /// Enabled by `compress.conditionals`
+    fn compress_if_returns(&mut self, stmts: &mut Vec<'a, Statement<'a>>) {
+        // From the end, so `if (a) return b; if (c) return d; return e` is joined twice.
+        for i in (1..stmts.len()).rev() {
+            let Statement::IfStatement(if_stmt) = &stmts[i - 1] else { continue };
+            if if_stmt.alternate.is_some()
+                || !matches!(stmts[i], Statement::ReturnStatement(_))
+                || !Self::is_conditional_pair(&if_stmt.consequent, &stmts[i])
+            {
+                continue;
+            }
+            let alternate = stmts.remove(i);
+            let Some(stmt) = stmts.get_mut(i - 1) else { unreachable!() };
+            let Statement::IfStatement(if_stmt) = self.recycler.take_statement(self.ast, stmt)
+            else {
+                unreachable!()
+            };
+            let if_stmt = if_stmt.unbox();
+            let new_stmt = self.join_conditional_pair(
+                if_stmt.span,
+                if_stmt.test,
+                if_stmt.consequent,
+                alternate,
+            );
+            self.recycler.replace_statement(stmt, new_stmt);
+        }
+    }
+
+    /// Whether the branches `consequent` and `alternate` can be joined into a single statement
+    /// with a conditional expression: both are expression statements, or both return and at
+    /// least one has a value.
+    fn is_conditional_pair(consequent: &Statement<'a>, alternate: &Statement<'a>) -> bool {
+        match (consequent, alternate) {
+            (Statement::ExpressionStatement(_), Statement::ExpressionStatement(_)) => true,
+            (Statement::ReturnStatement(consequent), Statement::ReturnStatement(alternate)) => {
+                consequent.argument.is_some() || alternate.argument.is_some()
+            }
+            _ => false,
+        }
+    }
+
+    /// Joins branches checked with [`Self::is_conditional_pair`].
+    fn join_conditional_pair(
+        &mut self,
+        span: Span,
+        test: Expression<'a>,
+        consequent: Statement<'a>,
+        alternate: Statement<'a>,
+    ) -> Statement<'a> {
+        match (consequent, alternate) {
+            (
+                Statement::ExpressionStatement(consequent),
+                Statement::ExpressionStatement(alternate),
+            ) => {
+                let (consequent, alternate) =
+                    (consequent.unbox().expression, alternate.unbox().expression);
+                let expr = match (consequent, alternate) {
+                    (
+                        Expression::AssignmentExpression(consequent),
+                        Expression::AssignmentExpression(alternate),
+                    ) if Self::is_same_assignment(&consequent, &alternate) => {
+                        let (consequent, alternate) = (consequent.unbox(), alternate.unbox());
+                        let value = self.conditional(span, test, consequent.right, alternate.right);
+                        self.ast.expression_assignment(
+                            span,
+                            AssignmentOperator::Assign,
+                            consequent.left,
+                            value,
+                        )
+                    }
+                    (consequent, alternate) => self.conditional(span, test, consequent, alternate),
+                };
+                self.ast.statement_expression(span, expr)
+            }
+            (Statement::ReturnStatement(consequent), Statement::ReturnStatement(alternate)) => {
+                let consequent = consequent.unbox().argument.unwrap_or_else(|| self.ast.void_0());
+                let alternate = alternate.unbox().argument.unwrap_or_else(|| self.ast.void_0());
+                let expr = self.conditional(span, test, consequent, alternate);
+                self.ast.statement_return(span, Some(expr))
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    /// `x = a` and `x = b`, which assign to the same variable.
+    fn is_same_assignment(a: &AssignmentExpression<'a>, b: &AssignmentExpression<'a>) -> bool {
+        match (&a.left, &b.left) {
+            (
+                AssignmentTarget::AssignmentTargetIdentifier(a_id),
+                AssignmentTarget::AssignmentTargetIdentifier(b_id),
+            ) => {
+                a.operator == AssignmentOperator::Assign
+                    && b.operator == AssignmentOperator::Assign
+                    && a_id.name == b_id.name
+            }
+            _ => false,
+        }
+    }
+
+    /// `test ? consequent : alternate`, with the branches swapped instead of negating the test,
+    /// `!a ? b : c` → `a ? c : b`.
+    fn conditional(
+        &self,
+        span: Span,
+        test: Expression<'a>,
+        consequent: Expression<'a>,
+        alternate: Expression<'a>,
+    ) -> Expression<'a> {
+        match test {
+            Expression::UnaryExpression(unary_expr)
+                if unary_expr.operator == UnaryOperator::LogicalNot =>
+            {
+                let test = unary_expr.unbox().argument;
+                self.ast.expression_conditional(span, test, alternate, consequent)
+            }
+            test => self.ast.expression_conditional(span, test, consequent, alternate),
+        }
+    }
+
+    /// `while (a) b` → `for (;a;) b`, `while (!0) b` → `for (;;) b` and `do b; while (!0)` →
+    /// `for (;;) b`. The `for` form is as long, but [`Collapse`](super::Collapse) can move the
+    /// statement before the loop into its header, and always true tests can be dropped.
+    /// Enabled by `compress.loops`
+    fn compress_loop(&mut self, stmt: &mut Statement<'a>) {
+        match stmt {
+            Statement::WhileStatement(_) => {
+                let Statement::WhileStatement(while_stmt) =
+                    self.recycler.take_statement(self.ast, stmt)
+                else {
+                    unreachable!()
+                };
+                let while_stmt = while_stmt.unbox();
+                let test = if Self::is_always_true(&while_stmt.test) {
+                    self.recycler.recycle_expression(while_stmt.test);
+                    None
+                } else {
+                    Some(while_stmt.test)
+                };
+                *stmt = self.ast.statement_for(while_stmt.span, None, test, None, while_stmt.body);
+            }
+            // `continue` jumps to the test, which is always true, so the loops are equivalent.
+            Statement::DoWhileStatement(do_while_stmt)
+                if Self::is_always_true(&do_while_stmt.test) =>
+            {
+                let Statement::DoWhileStatement(do_while_stmt) =
+                    self.recycler.take_statement(self.ast, stmt)
+                else {
+                    unreachable!()
+                };
+                let do_while_stmt = do_while_stmt.unbox();
+                self.recycler.recycle_expression(do_while_stmt.test);
+                *stmt = self.ast.statement_for(
+                    do_while_stmt.span,
+                    None,
+                    None,
+                    None,
+                    do_while_stmt.body,
+                );
+            }
+            Statement::ForStatement(for_stmt)
+                if for_stmt.test.as_ref().is_some_and(Self::is_always_true) =>
+            {
+                if let Some(test) = for_stmt.test.take() {
+                    self.recycler.recycle_expression(test);
+                }
+                self.recycler.mark_changed();
+            }
+            _ => {}
+        }
+    }
+
+    /// `true`, `!0` and other literals which are always truthy.
+    fn is_always_true(expr: &Expression<'a>) -> bool {
+        expr.is_literal_value(false) && get_boolean_value(expr) == Some(true)
+    }
 
     /* Expressions */
 
@@ -127,14 +755,155 @@ impl<'a> SubstituteAlternateSyntax<'a> {
                 NumberBase::Decimal,
             );
             *expr = self.ast.expression_unary(SPAN, UnaryOperator::LogicalNot, num);
+            self.recycler.mark_changed();
             return true;
         }
         false
     }
 
+    /// Simplifies the test of a statement, which is only used as a boolean, `if (!!a)` → `if (a)`
+    /// Enabled by `compress.booleans`
+    fn compress_statement_test(&mut self, stmt: &mut Statement<'a>) {
+        let test = match stmt {
+            Statement::IfStatement(if_stmt) => &mut if_stmt.test,
+            Statement::WhileStatement(while_stmt) => &mut while_stmt.test,
+            Statement::DoWhileStatement(do_while_stmt) => &mut do_while_stmt.test,
+            Statement::ForStatement(for_stmt) => match &mut for_stmt.test {
+                Some(test) => test,
+                None => return,
+            },
+            _ => return,
+        };
+        self.compress_boolean_context(test);
+    }
+
+    /// Simplifies the operands of `!`, conditional tests and comparisons with booleans,
+    /// `!!a ? b : c` → `a ? b : c`, `a == true` → `a == 1`, `a < b === true` → `a < b`
+    /// Enabled by `compress.booleans`
+    fn compress_boolean_operands(&mut self, expr: &mut Expression<'a>) {
+        match expr {
+            Expression::ConditionalExpression(cond_expr) => {
+                self.compress_boolean_context(&mut cond_expr.test);
+            }
+            Expression::UnaryExpression(unary_expr)
+                if unary_expr.operator == UnaryOperator::LogicalNot =>
+            {
+                self.compress_boolean_context(&mut unary_expr.argument);
+            }
+            Expression::BinaryExpression(binary_expr) if binary_expr.operator.is_equality() => {
+                self.compress_boolean_comparison(expr);
+            }
+            _ => {}
+        }
+    }
+
+    /// Removes conversions to booleans from `expr`, whose value is only used as a boolean,
+    /// `!!a && !!b` → `a && b`
+    fn compress_boolean_context(&mut self, expr: &mut Expression<'a>) {
+        match expr {
+            Expression::UnaryExpression(unary_expr)
+                if unary_expr.operator == UnaryOperator::LogicalNot =>
+            {
+                let Expression::UnaryExpression(argument) = &mut unary_expr.argument else {
+                    return;
+                };
+                if argument.operator != UnaryOperator::LogicalNot {
+                    return;
+                }
+                *expr = self.ast.move_expression(&mut argument.argument);
+                self.recycler.mark_changed();
+                self.compress_boolean_context(expr);
+            }
+            Expression::LogicalExpression(logical_expr)
+                if logical_expr.operator != LogicalOperator::Coalesce =>
+            {
+                self.compress_boolean_context(&mut logical_expr.left);
+                self.compress_boolean_context(&mut logical_expr.right);
+            }
+            _ => {}
+        }
+    }
+
+    /// Transforms comparisons with `true` and `false`, which are already `!0` and `!1` when the
+    /// operands have been visited: `a == true` → `a == 1`, and when `a` is a boolean,
+    /// `a === true` → `a` and `a != true` → `!a`
+    fn compress_boolean_comparison(&mut self, expr: &mut Expression<'a>) {
+        let Expression::BinaryExpression(binary_expr) = expr else { return };
+        let BinaryExpression { left, operator, right, .. } = &mut **binary_expr;
+        let (value, boolean_is_left) =
+            match (Self::boolean_literal_value(left), Self::boolean_literal_value(right)) {
+                (_, Some(value)) => (value, false),
+                (Some(value), None) => (value, true),
+                (None, None) => return,
+            };
+        let (boolean, other) = if boolean_is_left { (left, right) } else { (right, left) };
+        if self.bindings.ty(other) == Ty::Boolean {
+            let is_equality =
+                matches!(operator, BinaryOperator::Equality | BinaryOperator::StrictEquality);
+            let other = self.ast.move_expression(other);
+            *expr = if is_equality == value {
+                other
+            } else {
+                self.ast.expression_unary(SPAN, UnaryOperator::LogicalNot, other)
+            };
+        } else if matches!(operator, BinaryOperator::Equality | BinaryOperator::Inequality) {
+            // `==` converts booleans to numbers.
+            *boolean = self.ast.expression_numeric_literal(
+                SPAN,
+                if value { 1.0 } else { 0.0 },
+                if value { "1" } else { "0" },
+                NumberBase::Decimal,
+            );
+        } else {
+            return;
+        }
+        self.recycler.mark_changed();
+    }
+
+    /// Value of `true`, `false`, `!0` and `!1`.
+    #[allow(clippy::float_cmp)]
+    fn boolean_literal_value(expr: &Expression<'a>) -> Option<bool> {
+        match expr {
+            Expression::BooleanLiteral(lit) => Some(lit.value),
+            Expression::UnaryExpression(unary_expr)
+                if unary_expr.operator == UnaryOperator::LogicalNot =>
+            {
+                match &unary_expr.argument {
+                    Expression::NumericLiteral(lit) if lit.value == 0.0 => Some(true),
+                    Expression::NumericLiteral(lit) if lit.value == 1.0 => Some(false),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Transforms `Boolean(a)` → `!!a` and `Boolean()` → `false`
+    /// Enabled by `compress.booleans`
+    fn compress_boolean_call(&mut self, expr: &mut Expression<'a>) {
+        let Expression::CallExpression(call_expr) = expr else { return };
+        if call_expr.optional
+            || call_expr.arguments.len() > 1
+            || !matches!(&call_expr.callee, Expression::Identifier(ident) if ident.name == "Boolean")
+            || !self.bindings.is_global("Boolean")
+        {
+            return;
+        }
+        *expr = match call_expr.arguments.first_mut() {
+            None => self.ast.expression_boolean_literal(call_expr.span, false),
+            Some(Argument::SpreadElement(_)) => return,
+            Some(argument) => {
+                let argument = self.ast.move_expression(argument.to_expression_mut());
+                let argument = self.ast.expression_unary(SPAN, UnaryOperator::LogicalNot, argument);
+                self.ast.expression_unary(call_expr.span, UnaryOperator::LogicalNot, argument)
+            }
+        };
+        self.recycler.mark_changed();
+    }
+
     /// Compress `typeof foo == "undefined"` into `typeof foo > "u"`
     /// Enabled by `compress.typeofs`
-    fn compress_typeof_undefined(&self, expr: &mut BinaryExpression<'a>) {
+    fn compress_typeof_undefined(&mut self, expr: &mut BinaryExpression<'a>) {
         if !self.options.typeofs {
             return;
         }
@@ -168,6 +937,7 @@ impl<'a> SubstituteAlternateSyntax<'a> {
             self.ast.expression_from_string_literal(right),
         );
         *expr = binary_expr;
+        self.recycler.mark_changed();
     }
 
     fn commutative_pair<A, F, G, RetF: 'a, RetG: 'a>(
@@ -191,22 +961,825 @@ impl<'a> SubstituteAlternateSyntax<'a> {
         None
     }
 
+    /// Transforms string concatenation into a template literal when it is shorter
+    /// `"a" + x + "b"` -> `` `a${x}b` ``
+    /// Enabled by `compress.template_literals`
+    fn compress_string_concatenation(&mut self, expr: &mut Expression<'a>) -> bool {
+        let operands = Self::string_concatenation_operands(expr);
+        if operands.len() < 2 {
+            return false;
+        }
+        let (mut concat_len, mut template_len) = (operands.len() - 1, 2);
+        for (i, operand) in operands.iter().enumerate() {
+            match operand {
+                Expression::StringLiteral(lit) => {
+                    concat_len += EmitSizeEstimator::string(&lit.value);
+                    template_len += EmitSizeEstimator::template_raw(&lit.value);
+                }
+                Expression::TemplateLiteral(template) if is_cooked(template) => {
+                    let raw_len =
+                        template.quasis.iter().map(|quasi| quasi.value.raw.len()).sum::<usize>();
+                    let substitutions_len = 3 * template.expressions.len();
+                    concat_len += 2 + raw_len + substitutions_len;
+                    template_len += raw_len + substitutions_len;
+                }
+                _ => {
+                    concat_len +=
+                        EmitSizeEstimator::addition_parens(operand, i == operands.len() - 1);
+                    template_len += 3;
+                }
+            }
+        }
+        if template_len >= concat_len {
+            return false;
+        }
+
+        let mut operands = self.take_string_concatenation_operands(expr);
+        operands.reverse();
+        let mut quasis = self.ast.vec();
+        let mut expressions = self.ast.vec();
+        let (mut cooked, mut raw) = (String::new(), String::new());
+        for operand in operands {
+            match operand {
+                Expression::StringLiteral(lit) => {
+                    cooked.push_str(&lit.value);
+                    push_template_raw(&mut raw, &lit.value);
+                }
+                Expression::TemplateLiteral(template) if is_cooked(&template) => {
+                    let template = template.unbox();
+                    let mut template_expressions = template.expressions.into_iter();
+                    for quasi in template.quasis {
+                        cooked.push_str(quasi.value.cooked.as_ref().unwrap());
+                        raw.push_str(&quasi.value.raw);
+                        if let Some(expr) = template_expressions.next() {
+                            quasis.push(self.template_element(&mut cooked, &mut raw, false));
+                            expressions.push(expr);
+                        }
+                    }
+                }
+                expr => {
+                    quasis.push(self.template_element(&mut cooked, &mut raw, false));
+                    expressions.push(expr);
+                }
+            }
+        }
+        quasis.push(self.template_element(&mut cooked, &mut raw, true));
+        let template = self.ast.expression_template_literal(SPAN, quasis, expressions);
+        self.recycler.replace_expression(expr, template);
+        true
+    }
+
+    /// Operands of a string concatenation chain, from right to left.
+    ///
+    /// Operands before the first string are added as numbers or strings depending on their
+    /// runtime values, so they are kept together as a single operand.
+    /// `1 + x + "a" + y` -> `[y, "a", 1 + x]`
+    fn string_concatenation_operands<'b>(
+        mut expr: &'b Expression<'a>,
+    ) -> std::vec::Vec<&'b Expression<'a>> {
+        let mut operands = std::vec![];
+        loop {
+            match expr {
+                Expression::BinaryExpression(binary_expr)
+                    if binary_expr.operator == BinaryOperator::Addition
+                        && Ty::from(expr) == Ty::Str =>
+                {
+                    operands.push(&binary_expr.right);
+                    expr = &binary_expr.left;
+                }
+                _ => {
+                    operands.push(expr);
+                    return operands;
+                }
+            }
+        }
+    }
+
+    /// Owned version of `string_concatenation_operands`.
+    fn take_string_concatenation_operands(
+        &mut self,
+        expr: &mut Expression<'a>,
+    ) -> std::vec::Vec<Expression<'a>> {
+        let mut operands = std::vec![];
+        let mut expr = self.recycler.take_expression(self.ast, expr);
+        loop {
+            if matches!(&expr, Expression::BinaryExpression(binary_expr) if binary_expr.operator == BinaryOperator::Addition)
+                && Ty::from(&expr) == Ty::Str
+            {
+                let Expression::BinaryExpression(binary_expr) = expr else { unreachable!() };
+                let binary_expr = binary_expr.unbox();
+                operands.push(binary_expr.right);
+                expr = binary_expr.left;
+            } else {
+                operands.push(expr);
+                return operands;
+            }
+        }
+    }
+
+    /// Transforms a template literal into string concatenation when it is shorter
+    /// `` `${x}px` `` -> `x + "px"`
+    /// Enabled by `compress.template_literals`
+    fn compress_template_literal(&mut self, expr: &mut Expression<'a>) {
+        let Expression::TemplateLiteral(template) = expr else { return };
+        if !is_cooked(template) {
+            return;
+        }
+        self.inline_template_constants(template);
+        if template.expressions.is_empty() {
+            self.compress_template_without_substitutions(expr);
+            return;
+        }
+        let template_len = 2
+            + template.quasis.iter().map(|quasi| quasi.value.raw.len()).sum::<usize>()
+            + 3 * template.expressions.len();
+        // `${a}${b}` is `"" + a + b`, otherwise `a + b` would be a numeric addition.
+        let needs_empty_string = template.quasis[0].value.cooked.as_ref().unwrap().is_empty()
+            && template.quasis[1].value.cooked.as_ref().unwrap().is_empty()
+            && Ty::from(&template.expressions[0]) != Ty::Str;
+        let mut concat_len = if needs_empty_string { 2 } else { 0 };
+        let mut operands = usize::from(needs_empty_string);
+        for (i, quasi) in template.quasis.iter().enumerate() {
+            let cooked = quasi.value.cooked.as_ref().unwrap();
+            if !cooked.is_empty() {
+                concat_len += EmitSizeEstimator::string(cooked);
+                operands += 1;
+            }
+            if let Some(expr) = template.expressions.get(i) {
+                concat_len += EmitSizeEstimator::addition_parens(expr, operands == 0);
+                operands += 1;
+            }
+        }
+        concat_len += operands - 1;
+        if concat_len >= template_len {
+            return;
+        }
+
+        let Expression::TemplateLiteral(template) = self.recycler.take_expression(self.ast, expr)
+        else {
+            unreachable!()
+        };
+        let template = template.unbox();
+        let mut result = needs_empty_string.then(|| self.ast.expression_string_literal(SPAN, ""));
+        let mut template_expressions = template.expressions.into_iter();
+        for quasi in template.quasis {
+            let cooked = quasi.value.cooked.unwrap();
+            let operands = [
+                (!cooked.is_empty()).then(|| self.ast.expression_string_literal(SPAN, cooked)),
+                template_expressions.next(),
+            ];
+            for operand in operands.into_iter().flatten() {
+                result = Some(match result {
+                    Some(left) => {
+                        self.ast.expression_binary(SPAN, left, BinaryOperator::Addition, operand)
+                    }
+                    None => operand,
+                });
+            }
+        }
+        self.recycler.replace_expression(expr, result.unwrap());
+    }
+
+    /// Moves constant substitutions into the text around them
+    /// `` `a${1}b${x}` `` -> `` `a1b${x}` ``
+    fn inline_template_constants(&mut self, template: &mut TemplateLiteral<'a>) {
+        for i in (0..template.expressions.len()).rev() {
+            let Some(value) = template_constant(&template.expressions[i]) else { continue };
+            let next = template.quasis.remove(i + 1);
+            let quasi = template.quasis.get_mut(i).unwrap();
+            let cooked = format!(
+                "{}{value}{}",
+                quasi.value.cooked.as_ref().unwrap(),
+                next.value.cooked.as_ref().unwrap()
+            );
+            let mut raw = String::new();
+            push_template_raw(&mut raw, &cooked);
+            quasi.value = TemplateElementValue {
+                raw: self.ast.atom(&raw),
+                cooked: Some(self.ast.atom(&cooked)),
+            };
+            quasi.tail = next.tail;
+            self.recycler.recycle_expression(template.expressions.remove(i));
+            self.recycler.mark_changed();
+        }
+    }
+
+    /// Transforms a template literal without substitutions into a string literal, unless the
+    /// string needs more escapes
+    /// `` `abc` `` -> `"abc"`
+    fn compress_template_without_substitutions(&mut self, expr: &mut Expression<'a>) {
+        let Expression::TemplateLiteral(template) = expr else { return };
+        let cooked = template.quasis[0].value.cooked.clone().unwrap();
+        if EmitSizeEstimator::string(&cooked) > 2 + EmitSizeEstimator::template_raw(&cooked) {
+            return;
+        }
+        let span = template.span;
+        let string = self.ast.expression_string_literal(span, cooked);
+        self.recycler.replace_expression(expr, string);
+    }
+
+    fn template_element(
+        &self,
+        cooked: &mut String,
+        raw: &mut String,
+        tail: bool,
+    ) -> TemplateElement<'a> {
+        let value =
+            TemplateElementValue { raw: self.ast.atom(raw), cooked: Some(self.ast.atom(cooked)) };
+        cooked.clear();
+        raw.clear();
+        self.ast.template_element(SPAN, tail, value)
+    }
+
+    /// Transforms `arguments` into a rest parameter
+    /// `function f() { return arguments[0] }` -> `function f(...args) { return args[0] }`
+    /// Enabled by `compress.rest_parameters`
+    fn compress_arguments(&mut self, func: &mut Function<'a>) {
+        if !self.options.rest_parameters || self.options.target < ESTarget::ES2015 {
+            return;
+        }
+        // `arguments` of a function with parameters is mapped to them in sloppy mode.
+        if !func.params.items.is_empty() || func.params.rest.is_some() {
+            return;
+        }
+        let Some(body) = &mut func.body else { return };
+        // "use strict" is a syntax error in functions with a rest parameter.
+        if body.has_use_strict_directive() {
+            return;
+        }
+        let mut usage = ArgumentsUsage::default();
+        usage.visit_function_body(body);
+        if !usage.can_use_rest_parameter() {
+            return;
+        }
+
+        let name = ["args", "rest", "a"]
+            .into_iter()
+            .map(Atom::from)
+            .find(|name| !usage.names.contains(name));
+        let name = name.unwrap_or_else(|| {
+            (1..=usage.names.len() + 1)
+                .map(|i| self.ast.atom(&format!("args{i}")))
+                .find(|name| !usage.names.contains(name))
+                .unwrap()
+        });
+        ArgumentsRenamer { name: name.clone(), function_depth: 0 }.visit_function_body(body);
+        let argument = self.ast.binding_pattern(
+            self.ast.binding_pattern_kind_binding_identifier(SPAN, name),
+            None::<TSTypeAnnotation>,
+            false,
+        );
+        func.params.rest = Some(self.ast.alloc(self.ast.binding_rest_element(SPAN, argument)));
+        self.recycler.mark_changed();
+    }
+
+    /// Simplifies default parameter values.
+    ///
+    /// * `function f(a = 1, b = void 0) {}` -> `function f(a = 1, b) {}`
+    /// * `function f(a = 1, b) { if (b === void 0) b = 2 }` -> `function f(a = 1, b = 2) {}`
+    /// * `function f({ a = 1, b = 2 }) { return a }` -> `function f({ a = 1, b }) { return a }`
+    /// * `function f(a, b, ...c) { return a }` -> `function f(a) { return a }`
+    ///
+    /// Defaults before the first remaining default and parameters change `f.length`,
+    /// so they are only added or removed without `compress.keep_fn_length`.
+    /// Enabled by `compress.parameters`
+    fn compress_parameters(
+        &mut self,
+        params: &mut FormalParameters<'a>,
+        body: &mut FunctionBody<'a>,
+        is_expression_body: bool,
+        is_setter: bool,
+    ) {
+        if !self.options.parameters || (params.items.is_empty() && params.rest.is_none()) {
+            return;
+        }
+        let usage = ParameterUsage::new(params, body);
+        if usage.has_eval() {
+            return;
+        }
+        self.remove_undefined_defaults(params, &usage);
+        // The expression body of an arrow function can not be removed.
+        if self.options.target >= ESTarget::ES2015 && !is_expression_body {
+            self.move_default_assignments(params, body, &usage);
+        }
+        for param in params.items.iter_mut() {
+            if let BindingPatternKind::AssignmentPattern(assign) = &mut param.pattern.kind {
+                self.remove_unused_destructured_defaults(&mut assign.left, &usage);
+            } else {
+                self.remove_unused_destructured_defaults(&mut param.pattern, &usage);
+            }
+        }
+        // Setters must have exactly one parameter.
+        if !is_setter {
+            self.remove_unused_trailing_parameters(params, &usage);
+        }
+    }
+
+    /// Whether adding or removing the default of the parameter at `index` keeps `f.length`,
+    /// which counts the parameters before the first one with a default.
+    fn keeps_function_length(&self, params: &FormalParameters<'a>, index: usize) -> bool {
+        !self.options.keep_fn_length || params.items.iter().take(index).any(parameters::has_default)
+    }
+
+    /// `function f(a = 1, b = void 0) {}` -> `function f(a = 1, b) {}`
+    fn remove_undefined_defaults(
+        &mut self,
+        params: &mut FormalParameters<'a>,
+        usage: &ParameterUsage<'a>,
+    ) {
+        let removable = (0..params.items.len())
+            .filter(|&index| {
+                matches!(&params.items[index].pattern.kind, BindingPatternKind::AssignmentPattern(assign) if assign.right.is_void_0())
+                    && self.keeps_function_length(params, index)
+            })
+            .collect::<std::vec::Vec<_>>();
+        // Without defaults, destructuring or a rest parameter, `arguments` is mapped to the
+        // parameters in sloppy mode.
+        let becomes_simple = params.rest.is_none()
+            && params.items.iter().enumerate().all(|(index, param)| {
+                param.pattern.kind.is_binding_identifier() || removable.contains(&index)
+            });
+        if becomes_simple && !usage.can_have_non_simple_parameters() {
+            return;
+        }
+        for (index, param) in params.items.iter_mut().enumerate() {
+            if !removable.contains(&index) {
+                continue;
+            }
+            let pattern = &mut param.pattern;
+            let dummy = self.ast.binding_pattern_kind_binding_identifier(SPAN, "");
+            let BindingPatternKind::AssignmentPattern(assign) =
+                mem::replace(&mut pattern.kind, dummy)
+            else {
+                unreachable!()
+            };
+            pattern.kind = assign.unbox().left.kind;
+            self.recycler.mark_changed();
+        }
+    }
+
+    /// `function f(a, b, ...c) { return a }` -> `function f(a) { return a }`
+    ///
+    /// Only parameters which are plain names are removed, since defaults and destructuring can
+    /// have side effects. With `arguments`, the parameters which are not used by name may still
+    /// be read or written, so nothing is removed.
+    fn remove_unused_trailing_parameters(
+        &mut self,
+        params: &mut FormalParameters<'a>,
+        usage: &ParameterUsage<'a>,
+    ) {
+        if self.options.keep_fn_length || usage.is_referenced("arguments") {
+            return;
+        }
+        let is_unused = |pattern: &BindingPattern<'a>| matches!(&pattern.kind, BindingPatternKind::BindingIdentifier(ident) if !usage.is_referenced(&ident.name));
+        if params.rest.as_ref().is_some_and(|rest| is_unused(&rest.argument)) {
+            params.rest = None;
+            self.recycler.mark_changed();
+        }
+        if params.rest.is_some() {
+            return;
+        }
+        while params.items.last().is_some_and(|param| {
+            is_unused(&param.pattern) && param.decorators.is_empty() && !param.has_modifier()
+        }) {
+            params.items.pop();
+            self.recycler.mark_changed();
+        }
+    }
+
+    /// `function f(a = 1, b) { if (b === void 0) b = 2 }` -> `function f(a = 1, b = 2) {}`
+    ///
+    /// Only literal values are moved, since other expressions may refer to names declared in the
+    /// body, which parameter defaults can not see.
+    fn move_default_assignments(
+        &mut self,
+        params: &mut FormalParameters<'a>,
+        body: &mut FunctionBody<'a>,
+        usage: &ParameterUsage<'a>,
+    ) {
+        while let Some((name, value)) =
+            body.statements.first().and_then(parameters::default_assignment)
+        {
+            let Some(index) = params.items.iter().position(|param| {
+                matches!(&param.pattern.kind, BindingPatternKind::BindingIdentifier(ident) if ident.name == name)
+            }) else {
+                break;
+            };
+            let is_simple = params.rest.is_none()
+                && params.items.iter().all(|param| param.pattern.kind.is_binding_identifier());
+            if !value.is_literal_value(false)
+                || usage.is_declared_in_body(name)
+                || !self.keeps_function_length(params, index)
+                || (is_simple
+                    && (body.has_use_strict_directive() || !usage.can_have_non_simple_parameters()))
+            {
+                break;
+            }
+            let Some(value) = parameters::take_default_value(body.statements.remove(0)) else {
+                unreachable!()
+            };
+            let Some(param) = params.items.get_mut(index) else { unreachable!() };
+            let pattern = &mut param.pattern;
+            let dummy = self.ast.binding_pattern_kind_binding_identifier(SPAN, "");
+            let left = self.ast.binding_pattern(
+                mem::replace(&mut pattern.kind, dummy),
+                None::<TSTypeAnnotation>,
+                false,
+            );
+            pattern.kind = self.ast.binding_pattern_kind_assignment_pattern(SPAN, left, value);
+            self.recycler.mark_changed();
+        }
+    }
+
+    /// `function f({ a = 1, b = 2 }) { return a }` -> `function f({ a = 1, b }) { return a }`
+    ///
+    /// The property is still read, only the default of the unused binding is dropped.
+    fn remove_unused_destructured_defaults(
+        &mut self,
+        pattern: &mut BindingPattern<'a>,
+        usage: &ParameterUsage<'a>,
+    ) {
+        match &mut pattern.kind {
+            BindingPatternKind::BindingIdentifier(_) => {}
+            BindingPatternKind::ObjectPattern(object_pattern) => {
+                for property in object_pattern.properties.iter_mut() {
+                    self.remove_unused_default(&mut property.value, usage);
+                }
+            }
+            BindingPatternKind::ArrayPattern(array_pattern) => {
+                for element in array_pattern.elements.iter_mut().flatten() {
+                    self.remove_unused_default(element, usage);
+                }
+            }
+            BindingPatternKind::AssignmentPattern(assign) => {
+                self.remove_unused_destructured_defaults(&mut assign.left, usage);
+            }
+        }
+    }
+
+    fn remove_unused_default(
+        &mut self,
+        pattern: &mut BindingPattern<'a>,
+        usage: &ParameterUsage<'a>,
+    ) {
+        let is_removable = match &pattern.kind {
+            BindingPatternKind::AssignmentPattern(assign) => {
+                matches!(&assign.left.kind, BindingPatternKind::BindingIdentifier(ident) if !usage.is_referenced(&ident.name))
+                    && assign.right.is_literal_value(false)
+            }
+            _ => false,
+        };
+        if !is_removable {
+            self.remove_unused_destructured_defaults(pattern, usage);
+            return;
+        }
+        let dummy = self.ast.binding_pattern_kind_binding_identifier(SPAN, "");
+        let BindingPatternKind::AssignmentPattern(assign) = mem::replace(&mut pattern.kind, dummy)
+        else {
+            unreachable!()
+        };
+        pattern.kind = assign.unbox().left.kind;
+        self.recycler.mark_changed();
+    }
+
     /// Removes redundant argument of `ReturnStatement`
     ///
     /// `return undefined` -> `return`
     /// `return void 0` -> `return`
-    fn compress_return_statement(stmt: &mut ReturnStatement<'a>) {
+    fn compress_return_statement(&mut self, stmt: &mut ReturnStatement<'a>) {
         if stmt.argument.as_ref().is_some_and(|expr| expr.is_undefined() || expr.is_void_0()) {
             stmt.argument = None;
+            self.recycler.mark_changed();
         }
     }
 
-    fn compress_variable_declarator(decl: &mut VariableDeclarator<'a>) {
+    fn compress_variable_declarator(&mut self, decl: &mut VariableDeclarator<'a>) {
+        if let (BindingPatternKind::ArrayPattern(pattern), Some(init)) =
+            (&decl.id.kind, &mut decl.init)
+        {
+            let has_rest = pattern.rest.is_some();
+            self.compress_iterated_array(init, !has_rest);
+        }
         if decl.kind.is_const() {
             return;
         }
         if decl.init.as_ref().is_some_and(|init| init.is_undefined() || init.is_void_0()) {
             decl.init = None;
+            self.recycler.mark_changed();
+        }
+    }
+}
+
+/// Template literals with invalid escapes are only allowed in tagged templates.
+fn is_cooked(template: &TemplateLiteral) -> bool {
+    template.quasis.iter().all(|quasi| quasi.value.cooked.is_some())
+}
+
+/// The string value of a substitution which can be moved into the text of a template literal.
+///
+/// Only literals, since `${x}` calls `x.toString()` on objects and other names may be shadowed.
+fn template_constant(expr: &Expression) -> Option<String> {
+    match expr {
+        Expression::StringLiteral(_)
+        | Expression::NumericLiteral(_)
+        | Expression::BooleanLiteral(_)
+        | Expression::NullLiteral(_) => {}
+        Expression::TemplateLiteral(template) if template.expressions.is_empty() => {}
+        Expression::UnaryExpression(unary_expr)
+            if matches!(
+                unary_expr.operator,
+                UnaryOperator::LogicalNot | UnaryOperator::UnaryNegation
+            ) && matches!(
+                unary_expr.argument,
+                Expression::NumericLiteral(_) | Expression::BooleanLiteral(_)
+            ) => {}
+        _ => return None,
+    }
+    get_string_value(expr).map(Cow::into_owned)
+}
+
+fn push_template_raw(raw: &mut String, cooked: &str) {
+    let mut chars = cooked.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            // `\r` is normalized to `\n` in template literals, so it has to be escaped.
+            '\r' => raw.push_str("\\r"),
+            '`' | '\\' => {
+                raw.push('\\');
+                raw.push(c);
+            }
+            '$' if chars.peek() == Some(&'{') => raw.push_str("\\$"),
+            _ => raw.push(c),
+        }
+    }
+}
+
+/// Arrow functions and methods can not have duplicate parameters, which only simple parameter
+/// lists of sloppy functions can.
+fn has_duplicate_parameters(params: &FormalParameters) -> bool {
+    let mut names = FxHashSet::default();
+    !params.iter_bindings().all(|pattern| {
+        pattern.kind.get_identifier().map_or(true, |name| names.insert(name.clone()))
+    })
+}
+
+/// Collects uses of `arguments` in a function body, not counting nested non-arrow functions.
+#[derive(Default)]
+struct ArgumentsUsage<'a> {
+    function_depth: usize,
+    /// All `arguments` references.
+    references: usize,
+    /// `arguments.length`, `arguments[i]`, `...arguments` and `f.apply(x, arguments)`.
+    replaceable_references: usize,
+    /// `arguments` is written, called as a method receiver, shadowed or observable through
+    /// `eval` or `with`.
+    has_unsafe_use: bool,
+    /// Every name bound or referenced, including in nested functions,
+    /// which a new rest parameter must not shadow.
+    names: FxHashSet<Atom<'a>>,
+}
+
+impl<'a> ArgumentsUsage<'a> {
+    fn can_use_rest_parameter(&self) -> bool {
+        !self.has_unsafe_use
+            && self.references > 0
+            && self.references == self.replaceable_references
+    }
+
+    fn is_arguments(&self, expr: &Expression) -> bool {
+        self.function_depth == 0
+            && matches!(expr, Expression::Identifier(ident) if ident.name == "arguments")
+    }
+
+    fn is_arguments_member(&self, expr: &Expression) -> bool {
+        match expr {
+            Expression::StaticMemberExpression(member_expr) => {
+                self.is_arguments(&member_expr.object)
+            }
+            Expression::ComputedMemberExpression(member_expr) => {
+                self.is_arguments(&member_expr.object)
+            }
+            _ => false,
+        }
+    }
+}
+
+impl<'a> Visit<'a> for ArgumentsUsage<'a> {
+    fn visit_function(&mut self, func: &Function<'a>, flags: ScopeFlags) {
+        self.function_depth += 1;
+        walk::walk_function(self, func, flags);
+        self.function_depth -= 1;
+    }
+
+    fn visit_identifier_reference(&mut self, ident: &IdentifierReference<'a>) {
+        if ident.name == "eval" {
+            self.has_unsafe_use = true;
+        }
+        if self.function_depth == 0 && ident.name == "arguments" {
+            self.references += 1;
+        }
+        self.names.insert(ident.name.clone());
+    }
+
+    fn visit_binding_identifier(&mut self, ident: &BindingIdentifier<'a>) {
+        if ident.name == "arguments" {
+            self.has_unsafe_use = true;
+        }
+        self.names.insert(ident.name.clone());
+    }
+
+    fn visit_with_statement(&mut self, stmt: &WithStatement<'a>) {
+        self.has_unsafe_use = true;
+        walk::walk_with_statement(self, stmt);
+    }
+
+    fn visit_static_member_expression(&mut self, expr: &StaticMemberExpression<'a>) {
+        if self.is_arguments(&expr.object) && expr.property.name == "length" {
+            self.replaceable_references += 1;
+        }
+        walk::walk_static_member_expression(self, expr);
+    }
+
+    fn visit_computed_member_expression(&mut self, expr: &ComputedMemberExpression<'a>) {
+        if self.is_arguments(&expr.object) {
+            self.replaceable_references += 1;
+        }
+        walk::walk_computed_member_expression(self, expr);
+    }
+
+    fn visit_spread_element(&mut self, elem: &SpreadElement<'a>) {
+        if self.is_arguments(&elem.argument) {
+            self.replaceable_references += 1;
+        }
+        walk::walk_spread_element(self, elem);
+    }
+
+    fn visit_call_expression(&mut self, expr: &CallExpression<'a>) {
+        // `arguments[0]()` calls with `arguments` as `this`.
+        if self.is_arguments_member(&expr.callee) {
+            self.has_unsafe_use = true;
+        }
+        if matches!(&expr.callee, Expression::StaticMemberExpression(member_expr) if member_expr.property.name == "apply")
+            && expr.arguments.len() == 2
+            && expr.arguments[1].as_expression().is_some_and(|arg| self.is_arguments(arg))
+        {
+            self.replaceable_references += 1;
+        }
+        walk::walk_call_expression(self, expr);
+    }
+
+    fn visit_tagged_template_expression(&mut self, expr: &TaggedTemplateExpression<'a>) {
+        if self.is_arguments_member(&expr.tag) {
+            self.has_unsafe_use = true;
+        }
+        walk::walk_tagged_template_expression(self, expr);
+    }
+
+    fn visit_simple_assignment_target(&mut self, target: &SimpleAssignmentTarget<'a>) {
+        if let Some(member_expr) = target.as_member_expression() {
+            if self.is_arguments(member_expr.object()) {
+                self.has_unsafe_use = true;
+            }
+        }
+        walk::walk_simple_assignment_target(self, target);
+    }
+
+    fn visit_unary_expression(&mut self, expr: &UnaryExpression<'a>) {
+        if expr.operator == UnaryOperator::Delete && self.is_arguments_member(&expr.argument) {
+            self.has_unsafe_use = true;
+        }
+        walk::walk_unary_expression(self, expr);
+    }
+}
+
+/// Whether a function uses `this` or `new.target`, not counting nested non-arrow functions.
+/// `this` in a nested class is counted, since it may be in a computed key.
+#[derive(Default)]
+struct ConstructorUses {
+    uses_this: bool,
+    /// `arguments`, or a direct `eval` call which could read `this` or `arguments`.
+    uses_arguments: bool,
+}
+
+impl<'a> Visit<'a> for ConstructorUses {
+    fn visit_function(&mut self, _func: &Function<'a>, _flags: ScopeFlags) {}
+
+    fn visit_this_expression(&mut self, _expr: &ThisExpression) {
+        self.uses_this = true;
+    }
+
+    fn visit_meta_property(&mut self, meta: &MetaProperty<'a>) {
+        if meta.meta.name == "new" {
+            self.uses_this = true;
+        }
+    }
+
+    fn visit_identifier_reference(&mut self, ident: &IdentifierReference<'a>) {
+        if matches!(ident.name.as_str(), "arguments" | "eval") {
+            self.uses_arguments = true;
+        }
+    }
+}
+
+/// Function expressions which may be constructors, because they are used with `new`,
+/// `instanceof`, `extends` or `.prototype`, directly or through a name they are assigned to.
+#[derive(Default)]
+struct ConstructorFunctions<'a> {
+    names: FxHashSet<Atom<'a>>,
+    functions: FxHashSet<Span>,
+    /// Function expressions assigned to a name, `var F = function () {}` and
+    /// `F = function () {}`.
+    assigned: std::vec::Vec<(Atom<'a>, Span)>,
+}
+
+impl<'a> ConstructorFunctions<'a> {
+    fn collect(program: &Program<'a>) -> FxHashSet<Span> {
+        let mut collector = Self::default();
+        collector.visit_program(program);
+        let Self { names, mut functions, assigned } = collector;
+        functions.extend(
+            assigned.into_iter().filter(|(name, _)| names.contains(name)).map(|(_, span)| span),
+        );
+        functions
+    }
+
+    fn add(&mut self, expr: &Expression<'a>) {
+        match expr.without_parenthesized() {
+            Expression::Identifier(ident) => {
+                self.names.insert(ident.name.clone());
+            }
+            Expression::FunctionExpression(func) => {
+                self.functions.insert(func.span);
+            }
+            _ => {}
+        }
+    }
+}
+
+impl<'a> Visit<'a> for ConstructorFunctions<'a> {
+    fn visit_new_expression(&mut self, expr: &NewExpression<'a>) {
+        self.add(&expr.callee);
+        walk::walk_new_expression(self, expr);
+    }
+
+    fn visit_binary_expression(&mut self, expr: &BinaryExpression<'a>) {
+        if expr.operator == BinaryOperator::Instanceof {
+            self.add(&expr.right);
+        }
+        walk::walk_binary_expression(self, expr);
+    }
+
+    fn visit_class(&mut self, class: &Class<'a>) {
+        if let Some(super_class) = &class.super_class {
+            self.add(super_class);
+        }
+        walk::walk_class(self, class);
+    }
+
+    fn visit_static_member_expression(&mut self, expr: &StaticMemberExpression<'a>) {
+        if expr.property.name == "prototype" {
+            self.add(&expr.object);
+        }
+        walk::walk_static_member_expression(self, expr);
+    }
+
+    fn visit_variable_declarator(&mut self, decl: &VariableDeclarator<'a>) {
+        if let (BindingPatternKind::BindingIdentifier(ident), Some(init)) =
+            (&decl.id.kind, &decl.init)
+        {
+            if let Expression::FunctionExpression(func) = init.without_parenthesized() {
+                self.assigned.push((ident.name.clone(), func.span));
+            }
+        }
+        walk::walk_variable_declarator(self, decl);
+    }
+
+    fn visit_assignment_expression(&mut self, expr: &AssignmentExpression<'a>) {
+        if let AssignmentTarget::AssignmentTargetIdentifier(ident) = &expr.left {
+            if let Expression::FunctionExpression(func) = expr.right.without_parenthesized() {
+                self.assigned.push((ident.name.clone(), func.span));
+            }
+        }
+        walk::walk_assignment_expression(self, expr);
+    }
+}
+
+/// Renames `arguments` in a function body, not counting nested non-arrow functions.
+struct ArgumentsRenamer<'a> {
+    name: Atom<'a>,
+    function_depth: usize,
+}
+
+impl<'a> VisitMut<'a> for ArgumentsRenamer<'a> {
+    fn visit_function(&mut self, func: &mut Function<'a>, flags: ScopeFlags) {
+        self.function_depth += 1;
+        walk_mut::walk_function(self, func, flags);
+        self.function_depth -= 1;
+    }
+
+    fn visit_identifier_reference(&mut self, ident: &mut IdentifierReference<'a>) {
+        if self.function_depth == 0 && ident.name == "arguments" {
+            ident.name = self.name.clone();
         }
     }
 }
@@ -0,0 +1,155 @@
+use oxc_ast::{ast::*, visit::walk_mut, AstBuilder, VisitMut};
+use oxc_semantic::{ReferenceId, SemanticBuilder};
+use oxc_span::{Atom, GetSpan, SPAN};
+use oxc_syntax::{number::NumberBase, operator::BinaryOperator};
+use rustc_hash::FxHashSet;
+
+/// Replace the global `undefined`, `NaN` and `Infinity` with expressions which can not be
+/// shadowed, `undefined` → `void 0`, `NaN` → `0 / 0` and `Infinity` → `1 / 0`. `NaN` and
+/// `Infinity` are only replaced with `compress.global_constants`.
+///
+/// Only references which resolve to the globals are replaced, and none inside `with` bodies,
+/// where they may be properties of the object. The globals are not writable, so they always have
+/// their initial value.
+///
+/// With an alias, `undefined` and `void 0` then become a variable which is never assigned,
+/// `f(void 0); g(void 0)` → `var _undefined; f(_undefined); g(_undefined)`, when that is shorter
+/// once the variable is mangled. The variable is declared at the top level, so it is only added
+/// to modules, and to CommonJS scripts whose top level is a function scope.
+///
+/// Enabled by `compress.global_constants` and `compress.undefined_alias`
+pub struct SubstituteGlobals<'a> {
+    ast: AstBuilder<'a>,
+    /// Replace `NaN` and `Infinity` as well as `undefined`.
+    constants: bool,
+    undefined_alias: bool,
+    /// References to the globals.
+    globals: FxHashSet<ReferenceId>,
+    /// Names bound or referenced anywhere in the program, which the alias must not use.
+    names: FxHashSet<String>,
+    has_eval: bool,
+}
+
+/// Mangled names are assumed to be this long when deciding whether an alias is shorter.
+const MANGLED_NAME_LENGTH: usize = 2;
+
+impl<'a> VisitMut<'a> for SubstituteGlobals<'a> {
+    fn visit_expression(&mut self, expr: &mut Expression<'a>) {
+        walk_mut::walk_expression(self, expr);
+        let Expression::Identifier(ident) = expr else { return };
+        if !ident.reference_id.get().is_some_and(|id| self.globals.contains(&id)) {
+            return;
+        }
+        let (numerator, raw) = match ident.name.as_str() {
+            "undefined" => {
+                *expr = self.ast.void_0();
+                return;
+            }
+            "NaN" => (0.0, "0"),
+            "Infinity" => (1.0, "1"),
+            _ => return,
+        };
+        let span = ident.span;
+        let left = self.ast.expression_numeric_literal(SPAN, numerator, raw, NumberBase::Decimal);
+        let right = self.ast.expression_numeric_literal(SPAN, 0.0, "0", NumberBase::Decimal);
+        *expr = self.ast.expression_binary(span, left, BinaryOperator::Division, right);
+    }
+
+    fn visit_with_statement(&mut self, stmt: &mut WithStatement<'a>) {
+        self.visit_expression(&mut stmt.object);
+    }
+}
+
+impl<'a> SubstituteGlobals<'a> {
+    pub fn new(ast: AstBuilder<'a>, constants: bool, undefined_alias: bool) -> Self {
+        Self {
+            ast,
+            constants,
+            undefined_alias,
+            globals: FxHashSet::default(),
+            names: FxHashSet::default(),
+            has_eval: false,
+        }
+    }
+
+    pub fn build(&mut self, program: &mut Program<'a>) {
+        {
+            let semantic = SemanticBuilder::new("", program.source_type).build(program).semantic;
+            let unresolved = semantic.scopes().root_unresolved_references();
+            self.has_eval = unresolved.contains_key("eval");
+            let names: &[&str] =
+                if self.constants { &["undefined", "NaN", "Infinity"] } else { &["undefined"] };
+            for &name in names {
+                let references = unresolved.get(name).into_iter().flatten();
+                self.globals.extend(references.map(|(reference_id, _)| *reference_id));
+            }
+            if self.undefined_alias {
+                self.names.extend(unresolved.keys().map(ToString::to_string));
+                self.names.extend(semantic.symbols().names.iter().map(ToString::to_string));
+            }
+        }
+        self.visit_program(program);
+        if self.undefined_alias && !self.has_eval {
+            self.alias_undefined(program);
+        }
+    }
+
+    /// Replaces `void 0` with a new variable if it is used often enough to be shorter.
+    fn alias_undefined(&mut self, program: &mut Program<'a>) {
+        let mut counter = VoidZero { alias: None, count: 0 };
+        counter.visit_program(program);
+        let saved = counter.count * ("void 0".len() - MANGLED_NAME_LENGTH);
+        if saved <= "var ;".len() + MANGLED_NAME_LENGTH {
+            return;
+        }
+        let name = (0..=self.names.len())
+            .map(|i| if i == 0 { "_undefined".to_string() } else { format!("_undefined{i}") })
+            .find(|name| !self.names.contains(name))
+            .unwrap();
+        let name = self.ast.atom(&name);
+        let mut replacer = VoidZero { alias: Some((self.ast, name.clone())), count: 0 };
+        replacer.visit_program(program);
+        let declarator = self.ast.variable_declarator(
+            SPAN,
+            VariableDeclarationKind::Var,
+            self.ast.binding_pattern(
+                self.ast.binding_pattern_kind_binding_identifier(SPAN, name),
+                None::<TSTypeAnnotation>,
+                false,
+            ),
+            None,
+            false,
+        );
+        let decl = self.ast.declaration_variable(
+            SPAN,
+            VariableDeclarationKind::Var,
+            self.ast.vec1(declarator),
+            false,
+        );
+        program.body.insert(0, Statement::from(decl));
+    }
+}
+
+/// Counts `void 0` outside of `with` bodies, or replaces them with `alias`.
+struct VoidZero<'a> {
+    alias: Option<(AstBuilder<'a>, Atom<'a>)>,
+    count: usize,
+}
+
+impl<'a> VisitMut<'a> for VoidZero<'a> {
+    fn visit_expression(&mut self, expr: &mut Expression<'a>) {
+        if !expr.is_void_0() {
+            walk_mut::walk_expression(self, expr);
+            return;
+        }
+        self.count += 1;
+        if let Some((ast, name)) = &self.alias {
+            let span = expr.span();
+            *expr = ast.expression_identifier_reference(span, name.clone());
+        }
+    }
+
+    fn visit_with_statement(&mut self, stmt: &mut WithStatement<'a>) {
+        self.visit_expression(&mut stmt.object);
+    }
+}
@@ -3,11 +3,22 @@ use std::borrow::Cow;
 use num_bigint::BigInt;
 use num_traits::{One, Zero};
 use oxc_ast::ast::{
-    match_expression, ArrayExpressionElement, BinaryExpression, Expression, NumericLiteral,
-    ObjectProperty, ObjectPropertyKind, PropertyKey, SpreadElement, UnaryExpression,
+    match_expression, match_member_expression, ArrayExpressionElement, BinaryExpression,
+    ChainElement, Expression, MemberExpression, NumericLiteral, ObjectProperty, ObjectPropertyKind,
+    PropertyKey, SpreadElement, UnaryExpression,
 };
 use oxc_semantic::ReferenceFlag;
-use oxc_syntax::operator::{AssignmentOperator, LogicalOperator, UnaryOperator};
+use oxc_syntax::{
+    number::ToJsString,
+    operator::{AssignmentOperator, LogicalOperator, UnaryOperator},
+};
+
+pub use crate::numeric::NumberValue;
+use crate::{
+    numeric::{bigint_literal_value, string_to_bigint, string_to_number},
+    ty::Ty,
+    PureGetters,
+};
 
 /// Code ported from [closure-compiler](https://github.com/google/closure-compiler/blob/f3ce5ed8b630428e311fe9aa2e20d36560d975e2/src/com/google/javascript/jscomp/NodeUtil.java#LL836C6-L836C6)
 /// Returns true if this is a literal value. We define a literal value as any node that evaluates
@@ -90,8 +101,8 @@ pub trait MayHaveSideEffects<'a, 'b>
 where
     Self: CheckForStateChange<'a, 'b>,
 {
-    fn may_have_side_effects(&self) -> bool {
-        self.check_for_state_change(false)
+    fn may_have_side_effects(&self, pure_getters: PureGetters) -> bool {
+        self.check_for_state_change(false, pure_getters)
     }
 }
 
@@ -99,12 +110,21 @@ where
 /// Returns true if some node in n's subtree changes application state. If
 /// `check_for_new_objects` is true, we assume that newly created mutable objects (like object
 /// literals) change state. Otherwise, we assume that they have no side effects.
+/// `pure_getters` decides whether property access may change state.
 pub trait CheckForStateChange<'a, 'b> {
-    fn check_for_state_change(&self, check_for_new_objects: bool) -> bool;
+    fn check_for_state_change(
+        &self,
+        check_for_new_objects: bool,
+        pure_getters: PureGetters,
+    ) -> bool;
 }
 
 impl<'a, 'b> CheckForStateChange<'a, 'b> for Expression<'a> {
-    fn check_for_state_change(&self, check_for_new_objects: bool) -> bool {
+    fn check_for_state_change(
+        &self,
+        check_for_new_objects: bool,
+        pure_getters: PureGetters,
+    ) -> bool {
         match self {
             Self::NumericLiteral(_)
             | Self::BooleanLiteral(_)
@@ -119,73 +139,124 @@ impl<'a, 'b> CheckForStateChange<'a, 'b> for Expression<'a> {
             Self::TemplateLiteral(template) => template
                 .expressions
                 .iter()
-                .any(|expr| expr.check_for_state_change(check_for_new_objects)),
+                .any(|expr| expr.check_for_state_change(check_for_new_objects, pure_getters)),
             Self::Identifier(ident) => ident.reference_flag == ReferenceFlag::Write,
             Self::UnaryExpression(unary_expr) => {
-                unary_expr.check_for_state_change(check_for_new_objects)
+                unary_expr.check_for_state_change(check_for_new_objects, pure_getters)
             }
             Self::ParenthesizedExpression(p) => {
-                p.expression.check_for_state_change(check_for_new_objects)
+                p.expression.check_for_state_change(check_for_new_objects, pure_getters)
             }
             Self::ConditionalExpression(p) => {
-                p.test.check_for_state_change(check_for_new_objects)
-                    || p.consequent.check_for_state_change(check_for_new_objects)
-                    || p.alternate.check_for_state_change(check_for_new_objects)
-            }
-            Self::SequenceExpression(s) => {
-                s.expressions.iter().any(|expr| expr.check_for_state_change(check_for_new_objects))
+                p.test.check_for_state_change(check_for_new_objects, pure_getters)
+                    || p.consequent.check_for_state_change(check_for_new_objects, pure_getters)
+                    || p.alternate.check_for_state_change(check_for_new_objects, pure_getters)
             }
+            Self::SequenceExpression(s) => s
+                .expressions
+                .iter()
+                .any(|expr| expr.check_for_state_change(check_for_new_objects, pure_getters)),
             Self::BinaryExpression(binary_expr) => {
-                binary_expr.check_for_state_change(check_for_new_objects)
+                binary_expr.check_for_state_change(check_for_new_objects, pure_getters)
             }
             Self::ObjectExpression(object_expr) => {
                 if check_for_new_objects {
                     return true;
                 }
 
-                object_expr
-                    .properties
-                    .iter()
-                    .any(|property| property.check_for_state_change(check_for_new_objects))
+                object_expr.properties.iter().any(|property| {
+                    property.check_for_state_change(check_for_new_objects, pure_getters)
+                })
             }
             Self::ArrayExpression(array_expr) => {
                 if check_for_new_objects {
                     return true;
                 }
-                array_expr
-                    .elements
-                    .iter()
-                    .any(|element| element.check_for_state_change(check_for_new_objects))
+                array_expr.elements.iter().any(|element| {
+                    element.check_for_state_change(check_for_new_objects, pure_getters)
+                })
             }
+            match_member_expression!(Self) => self
+                .to_member_expression()
+                .check_for_state_change(check_for_new_objects, pure_getters),
+            Self::ChainExpression(chain_expr) => match &chain_expr.expression {
+                ChainElement::CallExpression(_) => true,
+                match_member_expression!(ChainElement) => chain_expr
+                    .expression
+                    .to_member_expression()
+                    .check_for_state_change(check_for_new_objects, pure_getters),
+            },
             _ => true,
         }
     }
 }
 
 impl<'a, 'b> CheckForStateChange<'a, 'b> for UnaryExpression<'a> {
-    fn check_for_state_change(&self, check_for_new_objects: bool) -> bool {
+    fn check_for_state_change(
+        &self,
+        check_for_new_objects: bool,
+        pure_getters: PureGetters,
+    ) -> bool {
         if is_simple_unary_operator(self.operator) {
-            return self.argument.check_for_state_change(check_for_new_objects);
+            return self.argument.check_for_state_change(check_for_new_objects, pure_getters);
         }
         true
     }
 }
 
 impl<'a, 'b> CheckForStateChange<'a, 'b> for BinaryExpression<'a> {
-    fn check_for_state_change(&self, check_for_new_objects: bool) -> bool {
-        let left = self.left.check_for_state_change(check_for_new_objects);
-        let right = self.right.check_for_state_change(check_for_new_objects);
+    fn check_for_state_change(
+        &self,
+        check_for_new_objects: bool,
+        pure_getters: PureGetters,
+    ) -> bool {
+        let left = self.left.check_for_state_change(check_for_new_objects, pure_getters);
+        let right = self.right.check_for_state_change(check_for_new_objects, pure_getters);
 
         left || right
     }
 }
 
+impl<'a, 'b> CheckForStateChange<'a, 'b> for MemberExpression<'a> {
+    fn check_for_state_change(
+        &self,
+        check_for_new_objects: bool,
+        pure_getters: PureGetters,
+    ) -> bool {
+        let may_throw = match pure_getters {
+            PureGetters::Never => return true,
+            // `a?.b` does not throw when `a` is nullish.
+            PureGetters::Safe => {
+                !self.optional()
+                    && matches!(Ty::from(self.object()), Ty::Null | Ty::Void | Ty::Undetermined)
+            }
+            PureGetters::Always => false,
+        };
+        may_throw
+            || self.object().check_for_state_change(check_for_new_objects, pure_getters)
+            || match self {
+                Self::ComputedMemberExpression(member_expr) => member_expr
+                    .expression
+                    .check_for_state_change(check_for_new_objects, pure_getters),
+                Self::StaticMemberExpression(_) => false,
+                // Throws when the object does not have the private field.
+                Self::PrivateFieldExpression(_) => true,
+            }
+    }
+}
+
 impl<'a, 'b> CheckForStateChange<'a, 'b> for ArrayExpressionElement<'a> {
-    fn check_for_state_change(&self, check_for_new_objects: bool) -> bool {
+    fn check_for_state_change(
+        &self,
+        check_for_new_objects: bool,
+        pure_getters: PureGetters,
+    ) -> bool {
         match self {
-            Self::SpreadElement(element) => element.check_for_state_change(check_for_new_objects),
+            Self::SpreadElement(element) => {
+                element.check_for_state_change(check_for_new_objects, pure_getters)
+            }
             match_expression!(Self) => {
-                self.to_expression().check_for_state_change(check_for_new_objects)
+                self.to_expression().check_for_state_change(check_for_new_objects, pure_getters)
             }
             Self::Elision(_) => false,
         }
@@ -193,38 +264,61 @@ impl<'a, 'b> CheckForStateChange<'a, 'b> for ArrayExpressionElement<'a> {
 }
 
 impl<'a, 'b> CheckForStateChange<'a, 'b> for ObjectPropertyKind<'a> {
-    fn check_for_state_change(&self, check_for_new_objects: bool) -> bool {
+    fn check_for_state_change(
+        &self,
+        check_for_new_objects: bool,
+        pure_getters: PureGetters,
+    ) -> bool {
         match self {
-            Self::ObjectProperty(method) => method.check_for_state_change(check_for_new_objects),
+            Self::ObjectProperty(method) => {
+                method.check_for_state_change(check_for_new_objects, pure_getters)
+            }
+            // Object spread only calls getters, and ignores `null` and `undefined`.
             Self::SpreadProperty(spread_element) => {
-                spread_element.check_for_state_change(check_for_new_objects)
+                pure_getters == PureGetters::Never
+                    || spread_element
+                        .argument
+                        .check_for_state_change(check_for_new_objects, pure_getters)
             }
         }
     }
 }
 
 impl<'a, 'b> CheckForStateChange<'a, 'b> for SpreadElement<'a> {
-    fn check_for_state_change(&self, _check_for_new_objects: bool) -> bool {
-        // Object-rest and object-spread may trigger a getter.
-        // TODO: Closure Compiler assumes that getters may side-free when set `assumeGettersArePure`.
+    fn check_for_state_change(
+        &self,
+        _check_for_new_objects: bool,
+        _pure_getters: PureGetters,
+    ) -> bool {
+        // Array spread calls the iterator of the argument. Object spread only triggers getters,
+        // and is handled by `ObjectPropertyKind` with `pure_getters`, like Closure Compiler's
+        // `assumeGettersArePure`.
         // https://github.com/google/closure-compiler/blob/a4c880032fba961f7a6c06ef99daa3641810bfdd/src/com/google/javascript/jscomp/AstAnalyzer.java#L282
         true
     }
 }
 
 impl<'a, 'b> CheckForStateChange<'a, 'b> for ObjectProperty<'a> {
-    fn check_for_state_change(&self, check_for_new_objects: bool) -> bool {
-        self.key.check_for_state_change(check_for_new_objects)
-            || self.value.check_for_state_change(check_for_new_objects)
+    fn check_for_state_change(
+        &self,
+        check_for_new_objects: bool,
+        pure_getters: PureGetters,
+    ) -> bool {
+        self.key.check_for_state_change(check_for_new_objects, pure_getters)
+            || self.value.check_for_state_change(check_for_new_objects, pure_getters)
     }
 }
 
 impl<'a, 'b> CheckForStateChange<'a, 'b> for PropertyKey<'a> {
-    fn check_for_state_change(&self, check_for_new_objects: bool) -> bool {
+    fn check_for_state_change(
+        &self,
+        check_for_new_objects: bool,
+        pure_getters: PureGetters,
+    ) -> bool {
         match self {
             Self::StaticIdentifier(_) | Self::PrivateIdentifier(_) => false,
             match_expression!(Self) => {
-                self.to_expression().check_for_state_change(check_for_new_objects)
+                self.to_expression().check_for_state_change(check_for_new_objects, pure_getters)
             }
         }
     }
@@ -238,113 +332,21 @@ fn is_simple_unary_operator(operator: UnaryOperator) -> bool {
     operator != UnaryOperator::Delete
 }
 
-#[derive(PartialEq)]
-pub enum NumberValue {
-    Number(f64),
-    PositiveInfinity,
-    NegativeInfinity,
-    NaN,
-}
-
-impl NumberValue {
-    #[must_use]
-    pub fn not(&self) -> Self {
-        match self {
-            Self::Number(num) => Self::Number(-num),
-            Self::PositiveInfinity => Self::NegativeInfinity,
-            Self::NegativeInfinity => Self::PositiveInfinity,
-            Self::NaN => Self::NaN,
-        }
-    }
-
-    pub fn is_nan(&self) -> bool {
-        matches!(self, Self::NaN)
-    }
-}
-
-impl std::ops::Add<Self> for NumberValue {
-    type Output = Self;
-
-    fn add(self, other: Self) -> Self {
-        match self {
-            Self::Number(num) => match other {
-                Self::Number(other_num) => Self::Number(num + other_num),
-                Self::PositiveInfinity => Self::PositiveInfinity,
-                Self::NegativeInfinity => Self::NegativeInfinity,
-                Self::NaN => Self::NaN,
-            },
-            Self::NaN => Self::NaN,
-            Self::PositiveInfinity => match other {
-                Self::NaN | Self::NegativeInfinity => Self::NaN,
-                _ => Self::PositiveInfinity,
-            },
-            Self::NegativeInfinity => match other {
-                Self::NaN | Self::PositiveInfinity => Self::NaN,
-                _ => Self::NegativeInfinity,
-            },
-        }
-    }
-}
-
-impl TryFrom<NumberValue> for f64 {
-    type Error = ();
-
-    fn try_from(value: NumberValue) -> Result<Self, Self::Error> {
-        match value {
-            NumberValue::Number(num) => Ok(num),
-            NumberValue::PositiveInfinity => Ok(Self::INFINITY),
-            NumberValue::NegativeInfinity => Ok(Self::NEG_INFINITY),
-            NumberValue::NaN => Err(()),
-        }
-    }
-}
-
 pub fn is_exact_int64(num: f64) -> bool {
     num.fract() == 0.0
 }
 
-/// port from [closure compiler](https://github.com/google/closure-compiler/blob/master/src/com/google/javascript/jscomp/NodeUtil.java#L540)
-pub fn get_string_bigint_value(raw_string: &str) -> Option<BigInt> {
-    if raw_string.contains('\u{000b}') {
-        // vertical tab is not always whitespace
-        return None;
-    }
-
-    let s = raw_string.trim();
-
-    if s.is_empty() {
-        return Some(BigInt::zero());
-    }
-
-    if s.len() > 2 && s.starts_with('0') {
-        let radix: u32 = match s.chars().nth(1) {
-            Some('x' | 'X') => 16,
-            Some('o' | 'O') => 8,
-            Some('b' | 'B') => 2,
-            _ => 0,
-        };
-
-        if radix == 0 {
-            return None;
-        }
-
-        return BigInt::parse_bytes(s[2..].as_bytes(), radix);
-    }
-
-    return BigInt::parse_bytes(s.as_bytes(), 10);
-}
-
 /// port from [closure compiler](https://github.com/google/closure-compiler/blob/a4c880032fba961f7a6c06ef99daa3641810bfdd/src/com/google/javascript/jscomp/NodeUtil.java#L348)
 /// Gets the value of a node as a Number, or None if it cannot be converted.
 /// This method does not consider whether `expr` may have side effects.
 pub fn get_number_value(expr: &Expression) -> Option<NumberValue> {
     match expr {
-        Expression::NumericLiteral(number_literal) => {
-            Some(NumberValue::Number(number_literal.value))
-        }
+        Expression::NumericLiteral(number_literal) => Some(NumberValue::from(number_literal.value)),
         Expression::UnaryExpression(unary_expr) => match unary_expr.operator {
             UnaryOperator::UnaryPlus => get_number_value(&unary_expr.argument),
-            UnaryOperator::UnaryNegation => get_number_value(&unary_expr.argument).map(|v| v.not()),
+            UnaryOperator::UnaryNegation => {
+                get_number_value(&unary_expr.argument).map(NumberValue::not)
+            }
             UnaryOperator::BitwiseNot => get_number_value(&unary_expr.argument).map(|value| {
                 match value {
                     NumberValue::Number(num) => {
@@ -375,11 +377,8 @@ pub fn get_number_value(expr: &Expression) -> Option<NumberValue> {
             "NaN" | "undefined" => Some(NumberValue::NaN),
             _ => None,
         },
-        // TODO: will be implemented in next PR, just for test pass now.
-        Expression::StringLiteral(string_literal) => string_literal
-            .value
-            .parse::<f64>()
-            .map_or(Some(NumberValue::NaN), |num| Some(NumberValue::Number(num))),
+        Expression::StringLiteral(string_literal) => string_to_number(&string_literal.value),
+        Expression::TemplateLiteral(_) => get_string_value(expr).and_then(|s| string_to_number(&s)),
         _ => None,
     }
 }
@@ -395,10 +394,7 @@ pub fn get_bigint_value(expr: &Expression) -> Option<BigInt> {
                 None
             }
         }
-        Expression::BigIntLiteral(_bigint_literal) => {
-            // TODO: evaluate the bigint value
-            None
-        }
+        Expression::BigIntLiteral(bigint_literal) => bigint_literal_value(&bigint_literal.raw),
         Expression::BooleanLiteral(bool_literal) => {
             if bool_literal.value {
                 Some(BigInt::one())
@@ -417,12 +413,15 @@ pub fn get_bigint_value(expr: &Expression) -> Option<BigInt> {
             UnaryOperator::BitwiseNot => {
                 get_bigint_value(&unary_expr.argument).map(std::ops::Not::not)
             }
-            UnaryOperator::UnaryPlus => get_bigint_value(&unary_expr.argument),
+            // `+1n` throws.
+            UnaryOperator::UnaryPlus if !matches!(Ty::from(&unary_expr.argument), Ty::BigInt) => {
+                get_bigint_value(&unary_expr.argument)
+            }
             _ => None,
         },
-        Expression::StringLiteral(string_literal) => get_string_bigint_value(&string_literal.value),
+        Expression::StringLiteral(string_literal) => string_to_bigint(&string_literal.value),
         Expression::TemplateLiteral(_) => {
-            get_string_value(expr).and_then(|value| get_string_bigint_value(&value))
+            get_string_value(expr).and_then(|value| string_to_bigint(&value))
         }
         _ => None,
     }
@@ -430,13 +429,16 @@ pub fn get_bigint_value(expr: &Expression) -> Option<BigInt> {
 
 /// port from [closure compiler](https://github.com/google/closure-compiler/blob/a4c880032fba961f7a6c06ef99daa3641810bfdd/src/com/google/javascript/jscomp/AbstractPeepholeOptimization.java#L104-L114)
 /// Returns the number value of the node if it has one and it cannot have side effects.
-pub fn get_side_free_number_value(expr: &Expression) -> Option<NumberValue> {
+pub fn get_side_free_number_value(
+    expr: &Expression,
+    pure_getters: PureGetters,
+) -> Option<NumberValue> {
     let value = get_number_value(expr);
     // Calculating the number value, if any, is likely to be faster than calculating side effects,
     // and there are only a very few cases where we can compute a number value, but there could
     // also be side effects. e.g. `void doSomething()` has value NaN, regardless of the behavior
     // of `doSomething()`
-    if value.is_some() && expr.may_have_side_effects() {
+    if value.is_some() && expr.may_have_side_effects(pure_getters) {
         None
     } else {
         value
@@ -444,13 +446,13 @@ pub fn get_side_free_number_value(expr: &Expression) -> Option<NumberValue> {
 }
 
 /// port from [closure compiler](https://github.com/google/closure-compiler/blob/master/src/com/google/javascript/jscomp/AbstractPeepholeOptimization.java#L121)
-pub fn get_side_free_bigint_value(expr: &Expression) -> Option<BigInt> {
+pub fn get_side_free_bigint_value(expr: &Expression, pure_getters: PureGetters) -> Option<BigInt> {
     let value = get_bigint_value(expr);
     // Calculating the bigint value, if any, is likely to be faster than calculating side effects,
     // and there are only a very few cases where we can compute a bigint value, but there could
     // also be side effects. e.g. `void doSomething()` has value NaN, regardless of the behavior
     // of `doSomething()`
-    if value.is_some() && expr.may_have_side_effects() {
+    if value.is_some() && expr.may_have_side_effects(pure_getters) {
         None
     } else {
         value
@@ -472,8 +474,12 @@ pub fn get_boolean_value(expr: &Expression) -> Option<bool> {
         | Expression::ObjectExpression(_) => Some(true),
         Expression::NullLiteral(_) => Some(false),
         Expression::BooleanLiteral(boolean_literal) => Some(boolean_literal.value),
-        Expression::NumericLiteral(number_literal) => Some(number_literal.value != 0.0),
-        Expression::BigIntLiteral(big_int_literal) => Some(!big_int_literal.is_zero()),
+        Expression::NumericLiteral(number_literal) => {
+            Some(NumberValue::from(number_literal.value).to_boolean())
+        }
+        Expression::BigIntLiteral(big_int_literal) => {
+            bigint_literal_value(&big_int_literal.raw).map(|value| !value.is_zero())
+        }
         Expression::StringLiteral(string_literal) => Some(!string_literal.value.is_empty()),
         Expression::TemplateLiteral(template_literal) => {
             // only for ``
@@ -547,7 +553,7 @@ pub fn get_boolean_value(expr: &Expression) -> Option<bool> {
                 // +1 -> true
                 // +0 -> false
                 // -0 -> false
-                get_number_value(expr).map(|value| value != NumberValue::Number(0_f64))
+                get_number_value(expr).map(NumberValue::to_boolean)
             } else if unary_expr.operator == UnaryOperator::LogicalNot {
                 // !true -> false
                 get_boolean_value(&unary_expr.argument).map(|boolean| !boolean)
@@ -587,10 +593,10 @@ pub fn get_string_value<'a>(expr: &'a Expression) -> Option<Cow<'a, str>> {
             }
         }
         Expression::NumericLiteral(number_literal) => {
-            Some(Cow::Owned(number_literal.value.to_string()))
+            Some(Cow::Owned(number_literal.value.to_js_string()))
         }
         Expression::BigIntLiteral(big_int_literal) => {
-            Some(Cow::Owned(big_int_literal.raw.to_string()))
+            bigint_literal_value(&big_int_literal.raw).map(|value| Cow::Owned(value.to_string()))
         }
         Expression::NullLiteral(_) => Some(Cow::Borrowed("null")),
         Expression::BooleanLiteral(bool_literal) => {
@@ -603,6 +609,11 @@ pub fn get_string_value<'a>(expr: &'a Expression) -> Option<Cow<'a, str>> {
         Expression::UnaryExpression(unary_expr) => {
             match unary_expr.operator {
                 UnaryOperator::Void => Some(Cow::Borrowed("undefined")),
+                UnaryOperator::UnaryNegation
+                | UnaryOperator::UnaryPlus
+                | UnaryOperator::BitwiseNot => {
+                    get_number_value(expr).map(|value| Cow::Owned(value.to_js_string()))
+                }
                 UnaryOperator::LogicalNot => {
                     get_boolean_value(&unary_expr.argument).map(|boolean| {
                         // need reversed.
@@ -629,14 +640,82 @@ pub fn get_string_value<'a>(expr: &'a Expression) -> Option<Cow<'a, str>> {
 /// Gets the value of a node as a String, or `None` if it cannot be converted.
 /// This method effectively emulates the `String()` JavaScript cast function when
 /// possible and the node has no side effects. Otherwise, it returns `None`.
-pub fn get_side_free_string_value<'a>(expr: &'a Expression) -> Option<Cow<'a, str>> {
+pub fn get_side_free_string_value<'a>(
+    expr: &'a Expression,
+    pure_getters: PureGetters,
+) -> Option<Cow<'a, str>> {
     let value = get_string_value(expr);
     // Calculating the string value, if any, is likely to be faster than calculating side effects,
     // and there are only a very few cases where we can compute a string value, but there could
     // also be side effects. e.g. `void doSomething()` has value 'undefined', regardless of the
     // behavior of `doSomething()`
-    if value.is_some() && !expr.may_have_side_effects() {
+    if value.is_some() && !expr.may_have_side_effects(pure_getters) {
         return value;
     }
     None
 }
+
+/// Evaluates `value.method(argument)` for the methods of `Number.prototype` which are safe to call
+/// at compile time: they have no side effects and return the same string in every locale.
+/// `toLocaleString` is deliberately missing.
+///
+/// Returns `None` if the method throws, or its result is implementation-defined.
+pub fn evaluate_number_method(value: f64, method: &str, argument: Option<f64>) -> Option<String> {
+    match method {
+        "toFixed" => number_to_fixed(value, argument.unwrap_or(0.0)),
+        "toString" => number_to_string(value, argument.unwrap_or(10.0)),
+        _ => None,
+    }
+}
+
+/// <https://tc39.es/ecma262/#sec-number.prototype.tofixed>
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn number_to_fixed(value: f64, digits: f64) -> Option<String> {
+    if digits.fract() != 0.0 || !(0.0..=100.0).contains(&digits) {
+        return None;
+    }
+    if !value.is_finite() || value.abs() >= 1e21 {
+        return Some(value.to_js_string());
+    }
+    let digits = digits as usize;
+    // `toFixed` rounds ties up, while Rust rounds them to even. Bail out on an exact tie, which
+    // needs every digit of the value: at most 1074 after the decimal point.
+    let exact = format!("{:.1074}", value.abs());
+    let (_, fraction) = exact.split_once('.')?;
+    let rest = &fraction[digits..];
+    if rest.starts_with('5') && rest[1..].bytes().all(|b| b == b'0') {
+        return None;
+    }
+    // `(-0).toFixed()` is `"0"`.
+    let value = if value == 0.0 { 0.0 } else { value };
+    Some(format!("{value:.digits$}"))
+}
+
+/// <https://tc39.es/ecma262/#sec-number.prototype.tostring>
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss, clippy::float_cmp)]
+fn number_to_string(value: f64, radix: f64) -> Option<String> {
+    if radix.fract() != 0.0 || !(2.0..=36.0).contains(&radix) {
+        return None;
+    }
+    if radix == 10.0 || !value.is_finite() {
+        return Some(value.to_js_string());
+    }
+    // Fractions in other radixes are implementation-approximated.
+    if value.fract() != 0.0 || value.abs() > 9_007_199_254_740_991.0 {
+        return None;
+    }
+    let radix = radix as u32;
+    let mut n = value.abs() as u64;
+    let mut digits = vec![];
+    loop {
+        digits.push(char::from_digit((n % u64::from(radix)) as u32, radix)?);
+        n /= u64::from(radix);
+        if n == 0 {
+            break;
+        }
+    }
+    if value < 0.0 {
+        digits.push('-');
+    }
+    Some(digits.into_iter().rev().collect())
+}
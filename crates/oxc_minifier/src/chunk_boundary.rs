@@ -0,0 +1,66 @@
+#[allow(clippy::wildcard_imports)]
+use oxc_ast::ast::*;
+
+/// Whether `expr` marks a lazy chunk boundary, which bundlers split into a separate chunk:
+///
+/// * a function which only returns a dynamic import, `() => import("./page")`,
+///   optionally followed by promise handlers, `() => import("./page").then((m) => m.Page)`
+/// * a call to a lazy component wrapper with a function argument, `lazy(() => ...)`,
+///   `React.lazy(() => ...)`, `defineAsyncComponent(() => ...)` or `loadable(() => ...)`
+///
+/// Bundlers recognize these by their shape, so compressions must not rewrite them.
+pub fn is_chunk_boundary(expr: &Expression) -> bool {
+    match expr {
+        Expression::ArrowFunctionExpression(arrow) => {
+            returns_dynamic_import(&arrow.body, arrow.expression)
+        }
+        Expression::FunctionExpression(func) => {
+            func.body.as_ref().is_some_and(|body| returns_dynamic_import(body, false))
+        }
+        Expression::CallExpression(call_expr) => {
+            is_lazy_wrapper(&call_expr.callee)
+                && matches!(
+                    call_expr.arguments.first(),
+                    Some(Argument::ArrowFunctionExpression(_) | Argument::FunctionExpression(_))
+                )
+        }
+        _ => false,
+    }
+}
+
+/// `lazy`, `React.lazy`, `defineAsyncComponent` or `loadable`
+fn is_lazy_wrapper(callee: &Expression) -> bool {
+    match callee {
+        Expression::Identifier(ident) => {
+            matches!(ident.name.as_str(), "lazy" | "defineAsyncComponent" | "loadable")
+        }
+        Expression::StaticMemberExpression(member_expr) => member_expr.property.name == "lazy",
+        _ => false,
+    }
+}
+
+/// `{ return import("./page") }`, or the expression body `import("./page")` of an arrow function.
+fn returns_dynamic_import(body: &FunctionBody, is_expression: bool) -> bool {
+    match body.statements.as_slice() {
+        [Statement::ExpressionStatement(stmt)] if is_expression => {
+            is_dynamic_import(&stmt.expression)
+        }
+        [Statement::ReturnStatement(stmt)] => stmt.argument.as_ref().is_some_and(is_dynamic_import),
+        _ => false,
+    }
+}
+
+/// `import("./page")`, `import("./page").then(...)`, `.catch(...)` or `.finally(...)`
+fn is_dynamic_import(expr: &Expression) -> bool {
+    match expr.without_parenthesized() {
+        Expression::ImportExpression(_) => true,
+        Expression::CallExpression(call_expr) => {
+            let Expression::StaticMemberExpression(member_expr) = &call_expr.callee else {
+                return false;
+            };
+            matches!(member_expr.property.name.as_str(), "then" | "catch" | "finally")
+                && is_dynamic_import(&member_expr.object)
+        }
+        _ => false,
+    }
+}
@@ -0,0 +1,153 @@
+#[allow(clippy::wildcard_imports)]
+use oxc_ast::{ast::*, visit::walk, Visit};
+use oxc_syntax::operator::{AssignmentOperator, UnaryOperator};
+
+/// `Object.defineProperty(exports, "__esModule", { value: true })` or
+/// `exports.__esModule = true`, emitted by transpilers to mark a CommonJS module as converted
+/// from an ES module.
+pub fn is_es_module_marker(stmt: &Statement) -> bool {
+    let Statement::ExpressionStatement(expr_stmt) = stmt else { return false };
+    match &expr_stmt.expression {
+        Expression::CallExpression(call_expr) => {
+            call_expr.callee.is_specific_member_access("Object", "defineProperty")
+                && call_expr.arguments.len() == 3
+                && call_expr.arguments[0].as_expression().is_some_and(is_exports)
+                && matches!(&call_expr.arguments[1], Argument::StringLiteral(lit) if lit.value == "__esModule")
+                && is_true_value_descriptor(&call_expr.arguments[2])
+        }
+        Expression::AssignmentExpression(assign_expr) => {
+            assign_expr.operator == AssignmentOperator::Assign
+                && assign_expr.left.as_member_expression().is_some_and(|member_expr| {
+                    member_expr.static_property_name() == Some("__esModule")
+                        && is_exports(member_expr.object())
+                })
+                && is_true(&assign_expr.right)
+        }
+        _ => false,
+    }
+}
+
+/// `{ value: true }`
+fn is_true_value_descriptor(arg: &Argument) -> bool {
+    let Argument::ObjectExpression(obj_expr) = arg else { return false };
+    let [ObjectPropertyKind::ObjectProperty(prop)] = obj_expr.properties.as_slice() else {
+        return false;
+    };
+    prop.key.is_specific_static_name("value") && is_true(&prop.value)
+}
+
+/// `true` or `!0`
+fn is_true(expr: &Expression) -> bool {
+    match expr {
+        Expression::BooleanLiteral(lit) => lit.value,
+        Expression::UnaryExpression(unary_expr) => {
+            unary_expr.operator == UnaryOperator::LogicalNot
+                && matches!(&unary_expr.argument, Expression::NumericLiteral(lit) if lit.value == 0.0)
+        }
+        _ => false,
+    }
+}
+
+/// `exports` or `module.exports`
+pub fn is_exports(expr: &Expression) -> bool {
+    expr.is_specific_id("exports") || expr.is_specific_member_access("module", "exports")
+}
+
+/// `require("./data.json")`
+///
+/// Requiring a JSON file only parses it, and the result is cached,
+/// so the call can be removed when the result is unused.
+pub fn is_json_require(expr: &Expression) -> bool {
+    let Expression::CallExpression(call_expr) = expr else { return false };
+    call_expr.common_js_require().is_some_and(|lit| lit.value.ends_with(".json"))
+}
+
+/// Checks whether `module.exports` and `exports` refer to the same object everywhere.
+///
+/// This is the case unless either is reassigned, `module` or `exports` is shadowed, or `module`
+/// escapes to code which may reassign `module.exports`. Direct `eval` can do either.
+/// Inside `with` bodies the names may resolve to properties of the object instead,
+/// which the caller has to check.
+#[derive(Default)]
+pub struct ModuleExportsUsage {
+    /// References of `module`.
+    module_references: usize,
+    /// `module.foo`, which does not let `module` escape.
+    module_member_references: usize,
+    has_unsafe_use: bool,
+}
+
+impl ModuleExportsUsage {
+    pub fn is_aliased(program: &Program) -> bool {
+        let mut usage = Self::default();
+        usage.visit_program(program);
+        !usage.has_unsafe_use && usage.module_references == usage.module_member_references
+    }
+
+    fn check_assignment_target(&mut self, target: &SimpleAssignmentTarget) {
+        let is_unsafe = match target {
+            SimpleAssignmentTarget::AssignmentTargetIdentifier(ident) => {
+                matches!(ident.name.as_str(), "module" | "exports")
+            }
+            _ => target.as_member_expression().is_some_and(|member_expr| {
+                member_expr.is_specific_member_access("module", "exports")
+            }),
+        };
+        if is_unsafe {
+            self.has_unsafe_use = true;
+        }
+    }
+}
+
+impl<'a> Visit<'a> for ModuleExportsUsage {
+    fn visit_identifier_reference(&mut self, ident: &IdentifierReference<'a>) {
+        if ident.name == "module" {
+            self.module_references += 1;
+        }
+    }
+
+    fn visit_binding_identifier(&mut self, ident: &BindingIdentifier<'a>) {
+        if matches!(ident.name.as_str(), "module" | "exports") {
+            self.has_unsafe_use = true;
+        }
+    }
+
+    fn visit_call_expression(&mut self, expr: &CallExpression<'a>) {
+        // Direct `eval` can reassign `exports` or `module.exports`.
+        if expr.callee.is_specific_id("eval") {
+            self.has_unsafe_use = true;
+        }
+        walk::walk_call_expression(self, expr);
+    }
+
+    fn visit_static_member_expression(&mut self, expr: &StaticMemberExpression<'a>) {
+        if expr.object.is_specific_id("module") {
+            self.module_member_references += 1;
+        }
+        walk::walk_static_member_expression(self, expr);
+    }
+
+    fn visit_simple_assignment_target(&mut self, target: &SimpleAssignmentTarget<'a>) {
+        self.check_assignment_target(target);
+        walk::walk_simple_assignment_target(self, target);
+    }
+
+    fn visit_assignment_target_property_identifier(
+        &mut self,
+        ident: &AssignmentTargetPropertyIdentifier<'a>,
+    ) {
+        if matches!(ident.binding.name.as_str(), "module" | "exports") {
+            self.has_unsafe_use = true;
+        }
+        walk::walk_assignment_target_property_identifier(self, ident);
+    }
+
+    fn visit_unary_expression(&mut self, expr: &UnaryExpression<'a>) {
+        if expr.operator == UnaryOperator::Delete
+            && expr.argument.is_specific_member_access("module", "exports")
+        {
+            self.has_unsafe_use = true;
+        }
+        walk::walk_unary_expression(self, expr);
+    }
+}
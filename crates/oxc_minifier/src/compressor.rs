@@ -1,63 +1,426 @@
+use std::{
+    io::Write,
+    mem,
+    time::{Duration, Instant},
+};
+
+use flate2::{write::GzEncoder, Compression};
 use oxc_allocator::Allocator;
 #[allow(clippy::wildcard_imports)]
 use oxc_ast::{ast::*, AstBuilder};
+use oxc_codegen::{CodeGenerator, WhitespaceRemover};
 
 use crate::{
     ast_passes::{
-        Collapse, FoldConstants, RemoveDeadCode, RemoveSyntax, SubstituteAlternateSyntax,
+        ArrayLoops, Collapse, CollectionLiterals, DedupeFunctions, FoldConstants, HoistLiterals,
+        InlineFunctions, InlineVariables, RemoveDeadCode, RemoveSyntax, RemoveUnused,
+        ReorderDeclarations, SubstituteAlternateSyntax, SubstituteGlobals,
     },
-    CompressOptions,
+    property_names::PropertyNames,
+    pure_annotations::PureAnnotations,
+    recycler::Recycler,
+    symbol_liveness::SymbolLiveness,
+    Budget, CompressOptions, Inline, Profile,
 };
 
+/// Upper bound of the extra rounds run with a [`Budget`], in case passes keep undoing each
+/// other's changes without shrinking the program.
+const MAX_ROUNDS: usize = 10;
+
+/// The passes which are run more than once with a [`Budget`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Pass {
+    FoldConstants,
+    RemoveDeadCode,
+    SubstituteAlternateSyntax,
+    Collapse,
+}
+
+impl Pass {
+    const ALL: [Self; 4] = [
+        Self::FoldConstants,
+        Self::RemoveDeadCode,
+        Self::SubstituteAlternateSyntax,
+        Self::Collapse,
+    ];
+
+    /// The passes whose changes may give this pass more to do, e.g. removing dead code leaves
+    /// conditions which fold. A pass is not its own dependency: running it again right away does
+    /// not change the program.
+    fn dependencies(self) -> &'static [Self] {
+        match self {
+            Self::FoldConstants => &[Self::RemoveDeadCode, Self::SubstituteAlternateSyntax],
+            Self::RemoveDeadCode => &[Self::FoldConstants, Self::SubstituteAlternateSyntax],
+            Self::SubstituteAlternateSyntax => &[Self::FoldConstants, Self::RemoveDeadCode],
+            Self::Collapse => {
+                &[Self::FoldConstants, Self::RemoveDeadCode, Self::SubstituteAlternateSyntax]
+            }
+        }
+    }
+
+    fn is_enabled(self, options: &CompressOptions) -> bool {
+        match self {
+            Self::FoldConstants => options.fold_constants,
+            Self::RemoveDeadCode => options.remove_dead_code,
+            Self::SubstituteAlternateSyntax => options.substitute_alternate_syntax,
+            Self::Collapse => options.collapse,
+        }
+    }
+}
+
+/// Measurements of the compressed program, to evaluate experimental passes, when enabled with
+/// [`Compressor::with_stats`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct MinifierStats {
+    /// Gzipped size of the program printed without whitespace, before and after
+    /// [`CompressOptions::reorder_declarations`], when it is enabled.
+    /// The program is printed and gzipped twice to measure them.
+    pub gzip_size_before_reorder: Option<usize>,
+    /// See [`Self::gzip_size_before_reorder`].
+    pub gzip_size_after_reorder: Option<usize>,
+}
+
+pub struct CompressorReturn {
+    /// Property name frequencies of the compressed program, see [`PropertyNames`].
+    pub property_names: PropertyNames,
+    /// Names of the passes which ran, in order, such as `"fold_constants"`. Passes run more than
+    /// once with a [`Budget`] are listed every time.
+    pub passes: Vec<&'static str>,
+    /// The program printed after each of the [`Self::passes`], when enabled with
+    /// [`Compressor::with_pass_outputs`], so changes in the output can be attributed to a pass.
+    pub pass_outputs: Vec<String>,
+    /// Top level symbols of the compressed module and their dependencies, when enabled with
+    /// [`Compressor::with_symbol_liveness`].
+    pub symbol_liveness: Option<SymbolLiveness>,
+    /// Sizes measured by experimental passes when enabled with [`Compressor::with_stats`], see
+    /// [`MinifierStats`].
+    pub stats: MinifierStats,
+}
+
 pub struct Compressor<'a> {
     ast: AstBuilder<'a>,
     options: CompressOptions,
+    /// Shared by all passes, see [`Recycler`].
+    recycler: Recycler<'a>,
+    pure_annotations: PureAnnotations,
+    passes: Vec<&'static str>,
+    /// Printed programs after each pass, if enabled.
+    pass_outputs: Option<Vec<String>>,
+    symbol_liveness: bool,
+    /// Measure [`MinifierStats`], set by [`Compressor::with_stats`].
+    measure_stats: bool,
+    stats: MinifierStats,
+    /// Passes whose dependencies changed the program since they last ran, indexed by [`Pass`].
+    stale: [bool; Pass::ALL.len()],
 }
 
 impl<'a> Compressor<'a> {
     pub fn new(allocator: &'a Allocator, options: CompressOptions) -> Self {
         let ast = AstBuilder::new(allocator);
-        Self { ast, options }
+        Self {
+            ast,
+            options,
+            recycler: Recycler::default(),
+            pure_annotations: PureAnnotations::default(),
+            passes: vec![],
+            pass_outputs: None,
+            symbol_liveness: false,
+            measure_stats: false,
+            stats: MinifierStats::default(),
+            stale: [false; Pass::ALL.len()],
+        }
     }
 
-    pub fn build(self, program: &mut Program<'a>) {
-        // TODO: inline variables
+    /// Remove unused calls annotated with `/* @__PURE__ */`, which are found in the comments of
+    /// the program with [`PureAnnotations::new`]. Requires `remove_syntax`.
+    #[must_use]
+    pub fn with_pure_annotations(mut self, pure_annotations: PureAnnotations) -> Self {
+        self.pure_annotations = pure_annotations;
+        self
+    }
+
+    /// Print the program after every pass, see [`CompressorReturn::pass_outputs`]. This is slow,
+    /// and meant for tests and debugging passes which undo or enable each other's changes.
+    #[must_use]
+    pub fn with_pass_outputs(mut self, yes: bool) -> Self {
+        self.pass_outputs = yes.then(Vec::new);
+        self
+    }
+
+    /// Collect the top level symbols which survive compression, see
+    /// [`CompressorReturn::symbol_liveness`].
+    #[must_use]
+    pub fn with_symbol_liveness(mut self, yes: bool) -> Self {
+        self.symbol_liveness = yes;
+        self
+    }
+
+    /// Measure the sizes of [`CompressorReturn::stats`].
+    #[must_use]
+    pub fn with_stats(mut self, yes: bool) -> Self {
+        self.measure_stats = yes;
+        self
+    }
+
+    pub fn build(mut self, program: &mut Program<'a>) -> CompressorReturn {
+        let start = matches!(self.options.budget, Some(Budget::Time(_))).then(Instant::now);
         self.remove_syntax(program);
+        self.inline_variables(program);
+        self.inline_functions(program);
         self.fold_constants(program);
         self.remove_dead_code(program);
+        self.collection_literals(program);
+        self.array_loops(program);
         // TODO: StatementFusion
         // TODO: PeepholeMinimizeConditions
         self.substitute_alternate_syntax(program);
         self.collapse(program);
+        self.remove_unused(program);
+        self.reorder_declarations(program);
+        match self.options.budget {
+            Some(budget) => self.spend_budget(program, budget, start),
+            None => self.dedupe_functions(program),
+        }
+        self.substitute_globals(program);
+        self.hoist_literals(program);
+        CompressorReturn {
+            property_names: PropertyNames::new(program),
+            passes: self.passes,
+            pass_outputs: self.pass_outputs.unwrap_or_default(),
+            symbol_liveness: self
+                .symbol_liveness
+                .then(|| SymbolLiveness::new(program, self.options.pure_getters)),
+            stats: self.stats,
+        }
+    }
+
+    /// Runs the expensive passes while `budget` allows: more rounds of the passes which fold,
+    /// remove and substitute code, until a round no longer shrinks the program, and then
+    /// `dedupe_functions` if it is enabled.
+    ///
+    /// A round only runs the passes which are stale, see [`Pass::dependencies`], and there are no
+    /// more rounds once none are.
+    ///
+    /// With a [`Budget::Time`], every step is expected to take as long as the passes before it,
+    /// and only runs when it would still end within the budget.
+    fn spend_budget(&mut self, program: &mut Program<'a>, budget: Budget, start: Option<Instant>) {
+        let estimate = start.map_or(Duration::ZERO, |start| start.elapsed());
+        let mut size = printed_size(program);
+        let allows = |size: usize| match budget {
+            Budget::Time(limit) => {
+                start.map_or(true, |start| start.elapsed().saturating_add(estimate) <= limit)
+            }
+            Budget::Size(limit) => size > limit,
+        };
+        for _ in 0..MAX_ROUNDS {
+            if !self.stale.contains(&true) {
+                break;
+            }
+            if !allows(size) {
+                return;
+            }
+            if self.is_stale(Pass::FoldConstants) {
+                self.fold_constants(program);
+            }
+            if self.is_stale(Pass::RemoveDeadCode) {
+                self.remove_dead_code(program);
+            }
+            if self.is_stale(Pass::SubstituteAlternateSyntax) {
+                self.substitute_alternate_syntax(program);
+            }
+            if self.is_stale(Pass::Collapse) {
+                self.collapse(program);
+            }
+            let previous_size = mem::replace(&mut size, printed_size(program));
+            if size >= previous_size {
+                break;
+            }
+        }
+        if allows(size) {
+            self.dedupe_functions(program);
+        }
     }
 
-    fn remove_syntax(&self, program: &mut Program<'a>) {
+    fn finish_pass(&mut self, name: &'static str, program: &Program<'a>) {
+        self.passes.push(name);
+        if let Some(pass_outputs) = &mut self.pass_outputs {
+            pass_outputs.push(CodeGenerator::new().build(program).source_text);
+        }
+    }
+
+    fn is_stale(&self, pass: Pass) -> bool {
+        self.stale[pass as usize]
+    }
+
+    /// Records that `pass` ran, and marks the passes which depend on it as stale if it changed
+    /// the program.
+    fn ran(&mut self, pass: Pass, changed: bool) {
+        self.stale[pass as usize] = false;
+        if changed {
+            for dependent in Pass::ALL {
+                if dependent.dependencies().contains(&pass) && dependent.is_enabled(&self.options) {
+                    self.stale[dependent as usize] = true;
+                }
+            }
+        }
+    }
+
+    fn remove_syntax(&mut self, program: &mut Program<'a>) {
         if self.options.remove_syntax {
-            RemoveSyntax::new(self.ast, self.options).build(program);
+            let mut pass = RemoveSyntax::new(self.ast, self.options.clone())
+                .with_pure_annotations(mem::take(&mut self.pure_annotations))
+                .with_recycler(mem::take(&mut self.recycler));
+            pass.build(program);
+            self.recycler = pass.into_recycler();
+            self.finish_pass("remove_syntax", program);
+        }
+    }
+
+    fn inline_variables(&mut self, program: &mut Program<'a>) {
+        if self.options.inline_variables {
+            InlineVariables::new(self.options.pure_getters).build(program);
+            self.finish_pass("inline_variables", program);
+        }
+    }
+
+    fn inline_functions(&mut self, program: &mut Program<'a>) {
+        if self.options.inline != Inline::Never {
+            InlineFunctions::new(self.ast, self.options.inline).build(program);
+            self.finish_pass("inline_functions", program);
         }
     }
 
-    fn fold_constants(&self, program: &mut Program<'a>) {
+    fn fold_constants(&mut self, program: &mut Program<'a>) {
         if self.options.fold_constants {
-            FoldConstants::new(self.ast).with_evaluate(self.options.evaluate).build(program);
+            let changes = self.recycler.changes();
+            let mut pass = FoldConstants::new(self.ast)
+                .with_evaluate(self.options.evaluate)
+                .with_chunk_boundaries(self.options.keep_chunk_boundaries)
+                .with_pure_getters(self.options.pure_getters)
+                .with_env(self.options.env)
+                .with_recycler(mem::take(&mut self.recycler));
+            pass.build(program);
+            self.recycler = pass.into_recycler();
+            self.finish_pass("fold_constants", program);
+            self.ran(Pass::FoldConstants, self.recycler.changes() != changes);
         }
     }
 
-    fn substitute_alternate_syntax(&self, program: &mut Program<'a>) {
+    fn substitute_alternate_syntax(&mut self, program: &mut Program<'a>) {
         if self.options.substitute_alternate_syntax {
-            SubstituteAlternateSyntax::new(self.ast, self.options).build(program);
+            let changes = self.recycler.changes();
+            let mut pass = SubstituteAlternateSyntax::new(self.ast, self.options.clone())
+                .with_recycler(mem::take(&mut self.recycler));
+            pass.build(program);
+            self.recycler = pass.into_recycler();
+            self.finish_pass("substitute_alternate_syntax", program);
+            self.ran(Pass::SubstituteAlternateSyntax, self.recycler.changes() != changes);
         }
     }
 
-    fn remove_dead_code(&self, program: &mut Program<'a>) {
+    fn remove_dead_code(&mut self, program: &mut Program<'a>) {
         if self.options.remove_dead_code {
-            RemoveDeadCode::new(self.ast).build(program);
+            let changes = self.recycler.changes();
+            let mut pass = RemoveDeadCode::new(self.ast, self.options.clone())
+                .with_recycler(mem::take(&mut self.recycler));
+            pass.build(program);
+            self.recycler = pass.into_recycler();
+            self.finish_pass("remove_dead_code", program);
+            self.ran(Pass::RemoveDeadCode, self.recycler.changes() != changes);
         }
     }
 
-    fn collapse(&self, program: &mut Program<'a>) {
+    fn collection_literals(&mut self, program: &mut Program<'a>) {
+        if self.options.unsafe_collections {
+            CollectionLiterals::new(self.ast).build(program);
+            self.finish_pass("unsafe_collections", program);
+            // The passes before it may fold or remove more with the new literals.
+            for pass in [Pass::FoldConstants, Pass::RemoveDeadCode] {
+                self.stale[pass as usize] = pass.is_enabled(&self.options);
+            }
+        }
+    }
+
+    fn array_loops(&mut self, program: &mut Program<'a>) {
+        if self.options.profile == Profile::Speed {
+            ArrayLoops::new(self.ast).build(program);
+            self.finish_pass("array_loops", program);
+        }
+    }
+
+    fn collapse(&mut self, program: &mut Program<'a>) {
         if self.options.collapse {
-            Collapse::new(self.ast, self.options).build(program);
+            let mut pass = Collapse::new(self.ast, self.options.clone());
+            pass.build(program);
+            self.finish_pass("collapse", program);
+            self.ran(Pass::Collapse, pass.changed());
         }
     }
+
+    fn remove_unused(&mut self, program: &mut Program<'a>) {
+        if self.options.unused {
+            RemoveUnused::new(self.ast, self.options.pure_getters)
+                .with_unused_exports(self.options.unused_exports.clone())
+                .build(program);
+            self.finish_pass("unused", program);
+        }
+    }
+
+    fn reorder_declarations(&mut self, program: &mut Program<'a>) {
+        if self.options.reorder_declarations {
+            let before = self.measure_stats.then(|| gzip_size(program));
+            let changed = ReorderDeclarations::new(self.options.pure_getters).build(program);
+            self.finish_pass("reorder_declarations", program);
+            if let Some(before) = before {
+                self.stats.gzip_size_before_reorder = Some(before);
+                self.stats.gzip_size_after_reorder =
+                    Some(if changed { gzip_size(program) } else { before });
+            }
+        }
+    }
+
+    /// Runs last, so that `void 0` added by other passes can be aliased.
+    fn substitute_globals(&mut self, program: &mut Program<'a>) {
+        let undefined_alias = self.options.undefined_alias
+            && (program.source_type.is_module() || self.options.commonjs);
+        if self.options.global_constants || undefined_alias {
+            SubstituteGlobals::new(self.ast, self.options.global_constants, undefined_alias)
+                .build(program);
+            self.finish_pass("substitute_globals", program);
+        }
+    }
+
+    /// Runs last, like `substitute_globals`, so that literals added by other passes are counted.
+    fn hoist_literals(&mut self, program: &mut Program<'a>) {
+        let top_level_is_local = program.source_type.is_module() || self.options.commonjs;
+        if (self.options.hoist_strings || self.options.hoist_props) && top_level_is_local {
+            HoistLiterals::new(self.ast, self.options.hoist_strings, self.options.hoist_props)
+                .with_pure_getters(self.options.pure_getters)
+                .with_env(self.options.env)
+                .build(program);
+            self.finish_pass("hoist_literals", program);
+        }
+    }
+
+    fn dedupe_functions(&mut self, program: &mut Program<'a>) {
+        if self.options.dedupe_functions {
+            DedupeFunctions::new(self.ast, self.options.clone()).build(program);
+            self.finish_pass("dedupe_functions", program);
+        }
+    }
+}
+
+/// Size of the code generated from `program`, without whitespace.
+fn printed_size(program: &Program) -> usize {
+    WhitespaceRemover::new().build(program).source_text.len()
+}
+
+fn gzip_size(program: &Program) -> usize {
+    let source_text = WhitespaceRemover::new().build(program).source_text;
+    let mut encoder = GzEncoder::new(vec![], Compression::default());
+    // Writing to a `Vec` does not fail.
+    encoder
+        .write_all(source_text.as_bytes())
+        .and_then(|()| encoder.finish())
+        .map_or(0, |gz| gz.len())
 }
@@ -0,0 +1,168 @@
+#[allow(clippy::wildcard_imports)]
+use oxc_ast::ast::*;
+use oxc_codegen::{Context, Gen, GenExpr, WhitespaceRemover};
+use oxc_span::{GetSpan, Span, SPAN};
+use oxc_syntax::{
+    identifier::{LS, PS},
+    number::NumberBase,
+    precedence::{GetPrecedence, Precedence},
+};
+use rustc_hash::FxHashMap;
+
+/// Size in bytes of code as printed by [`WhitespaceRemover`], for rewrites which pick the shorter
+/// of two forms, `"a" + x` or `` `a${x}` ``.
+///
+/// Identifiers, literals and template literals are measured without printing them. Other nodes
+/// are printed, and their size is cached by their kind and span, so an estimator must only
+/// measure nodes which no longer change, as in passes which rewrite the children of a node before
+/// the node itself. Generated nodes without a span are not cached.
+#[derive(Default)]
+pub struct EmitSizeEstimator {
+    cache: FxHashMap<(NodeKind, Span), usize>,
+}
+
+/// An expression statement and its expression share a span, but not their size.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+enum NodeKind {
+    Expression,
+    Statement,
+}
+
+impl EmitSizeEstimator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Size of `expr` printed on its own, without parentheses around it.
+    pub fn expression(&mut self, expr: &Expression) -> usize {
+        match expr {
+            Expression::Identifier(ident) => ident.name.len(),
+            Expression::StringLiteral(lit) => Self::string(&lit.value),
+            Expression::NumericLiteral(lit) => Self::number(lit.value, lit.base),
+            Expression::BooleanLiteral(lit) => lit.as_str().len(),
+            Expression::NullLiteral(_) | Expression::ThisExpression(_) => 4,
+            Expression::TemplateLiteral(template) => {
+                2 + template.quasis.iter().map(|quasi| quasi.value.raw.len()).sum::<usize>()
+                    + template
+                        .expressions
+                        .iter()
+                        .map(|expr| 3 + self.expression(expr))
+                        .sum::<usize>()
+            }
+            _ => self.cached(NodeKind::Expression, expr.span(), |codegen| {
+                expr.gen_expr(codegen, Precedence::Lowest, Context::empty());
+            }),
+        }
+    }
+
+    /// Size of `stmt`, including its semicolon unless it is the last statement of a block.
+    pub fn statement(&mut self, stmt: &Statement) -> usize {
+        self.cached(NodeKind::Statement, stmt.span(), |codegen| stmt.gen(codegen, Context::empty()))
+    }
+
+    fn cached(
+        &mut self,
+        kind: NodeKind,
+        span: Span,
+        print: impl FnOnce(&mut WhitespaceRemover),
+    ) -> usize {
+        if let Some(size) = self.cache.get(&(kind, span)) {
+            return *size;
+        }
+        let mut codegen = WhitespaceRemover::new();
+        print(&mut codegen);
+        let size = codegen.into_source_text().len();
+        if !span.is_unspanned() {
+            self.cache.insert((kind, span), size);
+        }
+        size
+    }
+
+    /// Size of a string literal with the value `value`, including its quotes and escapes.
+    /// The literal is quoted with the quote which occurs less often in `value`.
+    pub fn string(value: &str) -> usize {
+        let mut chars = value.chars().peekable();
+        let mut size = 2;
+        let (mut single, mut double) = (0, 0);
+        while let Some(c) = chars.next() {
+            size += match c {
+                '\0' if chars.peek().is_some_and(char::is_ascii_digit) => 4,
+                '\x07' | '\x1B' | '\u{a0}' => 4,
+                '\0' | '\u{8}' | '\u{b}' | '\u{c}' | '\n' | '\r' | '\\' => 2,
+                '$' if chars.peek() == Some(&'{') => 2,
+                '\'' => {
+                    single += 1;
+                    1
+                }
+                '"' => {
+                    double += 1;
+                    1
+                }
+                LS | PS => 6,
+                c => c.len_utf8(),
+            };
+        }
+        size + usize::min(single, double)
+    }
+
+    /// Size of the raw text of a template literal quasi with the value `cooked`.
+    pub fn template_raw(cooked: &str) -> usize {
+        let mut chars = cooked.chars().peekable();
+        let mut size = 0;
+        while let Some(c) = chars.next() {
+            size += match c {
+                // `\r` is normalized to `\n` in template literals, so it has to be escaped.
+                '\r' | '`' | '\\' => 2,
+                '$' if chars.peek() == Some(&'{') => 2,
+                c => c.len_utf8(),
+            };
+        }
+        size
+    }
+
+    /// Size of a numeric literal with the value `value`.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn number(value: f64, base: NumberBase) -> usize {
+        let sign = usize::from(value.is_sign_negative());
+        let abs_value = value.abs();
+        if abs_value == f64::INFINITY {
+            return sign + "Infinity".len();
+        }
+        // Integers less than 1000 are never shorter with an exponent.
+        if abs_value < 1000.0 && abs_value.fract() == 0.0 {
+            let value = abs_value as u64;
+            return sign
+                + if value < 10 {
+                    1
+                } else if value < 100 {
+                    2
+                } else {
+                    3
+                };
+        }
+        let mut codegen = WhitespaceRemover::new();
+        NumericLiteral { span: SPAN, value, raw: "", base }.gen(&mut codegen, Context::empty());
+        codegen.into_source_text().len()
+    }
+
+    /// Size of the parentheses around `expr` as an operand of `+`, on the left if `is_left`.
+    pub fn addition_parens(expr: &Expression, is_left: bool) -> usize {
+        let precedence = match expr {
+            Expression::BinaryExpression(binary_expr) => binary_expr.operator.precedence(),
+            Expression::LogicalExpression(logical_expr) => logical_expr.operator.precedence(),
+            Expression::ConditionalExpression(_)
+            | Expression::AssignmentExpression(_)
+            | Expression::ArrowFunctionExpression(_)
+            | Expression::YieldExpression(_)
+            | Expression::SequenceExpression(_) => return 2,
+            _ => return 0,
+        };
+        let needs_parens =
+            if is_left { precedence < Precedence::Add } else { precedence <= Precedence::Add };
+        if needs_parens {
+            2
+        } else {
+            0
+        }
+    }
+}
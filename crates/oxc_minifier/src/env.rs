@@ -0,0 +1,197 @@
+use serde::{Deserialize, Serialize};
+
+/// The environment the output runs in, see [`CompressOptions::env`](crate::CompressOptions::env).
+///
+/// Each environment comes with a knowledge pack, a table of the globals and properties of globals
+/// which exist in it and of the ones which do not. Properties are listed only if reading them has
+/// no side effects and does not throw, so globals like `localStorage`, whose getter throws in
+/// sandboxed frames, are left out. A listed function may still have side effects when it is
+/// called, `document.createElement` exists but creating a custom element runs its constructor,
+/// so calls are only dropped for the functions marked as pure.
+///
+/// Nothing is assumed about a name which the program declares or assigns anywhere.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Env {
+    /// Any ECMAScript environment. Only the globals of ES5 are known to exist, since newer ones
+    /// such as `Promise` are commonly checked for to load polyfills.
+    #[default]
+    Neutral,
+    /// The main thread of a browser. Workers have neither `window` nor `document`.
+    Browser,
+    /// Node.js
+    Node,
+}
+
+/// A global, or a property of a global.
+#[derive(Debug)]
+pub struct Global {
+    /// The name of the global, `document`, or the path of the property, `document.createElement`.
+    pub path: &'static str,
+    /// The value of `typeof`, `"undefined"` for a global which does not exist.
+    pub type_of: &'static str,
+    /// Whether calls have no side effects other than those of their arguments, so they can be
+    /// dropped when their result is unused.
+    pub pure_call: bool,
+}
+
+const fn global(path: &'static str, type_of: &'static str) -> Global {
+    Global { path, type_of, pure_call: false }
+}
+
+const fn pure_function(path: &'static str) -> Global {
+    Global { path, type_of: "function", pure_call: true }
+}
+
+const fn absent(path: &'static str) -> Global {
+    Global { path, type_of: "undefined", pure_call: false }
+}
+
+const ECMASCRIPT: &[Global] = &[
+    global("undefined", "undefined"),
+    global("NaN", "number"),
+    global("Infinity", "number"),
+    global("Math", "object"),
+    global("JSON", "object"),
+    global("Object", "function"),
+    global("Function", "function"),
+    global("Array", "function"),
+    global("String", "function"),
+    global("Number", "function"),
+    global("Boolean", "function"),
+    global("Date", "function"),
+    global("RegExp", "function"),
+    global("Error", "function"),
+    global("EvalError", "function"),
+    global("RangeError", "function"),
+    global("ReferenceError", "function"),
+    global("SyntaxError", "function"),
+    global("TypeError", "function"),
+    global("URIError", "function"),
+    global("parseInt", "function"),
+    global("parseFloat", "function"),
+    global("isNaN", "function"),
+    global("isFinite", "function"),
+    global("encodeURI", "function"),
+    global("encodeURIComponent", "function"),
+    global("decodeURI", "function"),
+    global("decodeURIComponent", "function"),
+    global("Math.PI", "number"),
+    global("Math.E", "number"),
+    global("JSON.parse", "function"),
+    global("JSON.stringify", "function"),
+    pure_function("Array.isArray"),
+    pure_function("Date.now"),
+];
+
+const BROWSER: &[Global] = &[
+    global("window", "object"),
+    global("self", "object"),
+    global("document", "object"),
+    global("navigator", "object"),
+    global("location", "object"),
+    global("history", "object"),
+    global("console", "object"),
+    global("performance", "object"),
+    global("fetch", "function"),
+    global("setTimeout", "function"),
+    global("clearTimeout", "function"),
+    global("setInterval", "function"),
+    global("clearInterval", "function"),
+    global("requestAnimationFrame", "function"),
+    global("cancelAnimationFrame", "function"),
+    global("queueMicrotask", "function"),
+    global("addEventListener", "function"),
+    global("removeEventListener", "function"),
+    global("HTMLElement", "function"),
+    global("Element", "function"),
+    global("Node", "function"),
+    global("Event", "function"),
+    global("EventTarget", "function"),
+    global("CustomEvent", "function"),
+    global("URL", "function"),
+    global("URLSearchParams", "function"),
+    global("XMLHttpRequest", "function"),
+    global("document.createElement", "function"),
+    global("document.createTextNode", "function"),
+    global("document.getElementById", "function"),
+    global("document.querySelector", "function"),
+    global("document.querySelectorAll", "function"),
+    global("document.addEventListener", "function"),
+    pure_function("performance.now"),
+    absent("process"),
+    absent("global"),
+    absent("Buffer"),
+    absent("setImmediate"),
+];
+
+const NODE: &[Global] = &[
+    global("process", "object"),
+    global("global", "object"),
+    global("console", "object"),
+    global("performance", "object"),
+    global("Buffer", "function"),
+    global("setTimeout", "function"),
+    global("clearTimeout", "function"),
+    global("setInterval", "function"),
+    global("clearInterval", "function"),
+    global("setImmediate", "function"),
+    global("clearImmediate", "function"),
+    global("queueMicrotask", "function"),
+    global("URL", "function"),
+    global("URLSearchParams", "function"),
+    global("process.env", "object"),
+    global("process.argv", "object"),
+    global("process.versions", "object"),
+    global("process.platform", "string"),
+    global("process.version", "string"),
+    global("process.nextTick", "function"),
+    global("process.cwd", "function"),
+    global("process.exit", "function"),
+    pure_function("Buffer.isBuffer"),
+    pure_function("performance.now"),
+    absent("window"),
+    absent("self"),
+    absent("document"),
+    absent("location"),
+];
+
+impl Env {
+    /// The global or property of a global at `path`, e.g. `"document.createElement"`, if it is
+    /// known in this environment.
+    pub fn global(self, path: &str) -> Option<&'static Global> {
+        self.globals().find(|global| global.path == path)
+    }
+
+    /// The value of `typeof` for the global or property of a global at `path`.
+    pub fn type_of(self, path: &str) -> Option<&'static str> {
+        self.global(path).map(|global| global.type_of)
+    }
+
+    /// Whether the global or property of a global at `path` is known to exist.
+    pub fn exists(self, path: &str) -> bool {
+        self.type_of(path).is_some_and(|type_of| type_of != "undefined")
+    }
+
+    /// The names of the globals known to exist in this environment, `document` but not
+    /// `document.createElement`.
+    pub fn global_names(self) -> impl Iterator<Item = &'static str> {
+        self.globals()
+            .filter(|global| !global.path.contains('.') && global.type_of != "undefined")
+            .map(|global| global.path)
+    }
+
+    /// The pure functions of this environment, see [`Global::pure_call`].
+    pub fn pure_functions(self) -> impl Iterator<Item = &'static str> {
+        self.globals().filter(|global| global.pure_call).map(|global| global.path)
+    }
+
+    fn globals(self) -> impl Iterator<Item = &'static Global> {
+        let globals = match self {
+            Self::Neutral => &[],
+            Self::Browser => BROWSER,
+            Self::Node => NODE,
+        };
+        ECMASCRIPT.iter().chain(globals)
+    }
+}
@@ -31,6 +31,27 @@ impl<'a> KeepVar<'a> {
         Self { ast, vars: std::vec![] }
     }
 
+    /// What is left of a branch when the `taken` side is known to run and the `removed` side is
+    /// not: `taken`, and the `var` declarations of `removed`, which are hoisted out of it.
+    ///
+    /// `if (true) a(); else { var b = 1 }` => `{ a(); var b }`
+    pub fn keep_taken_branch(
+        ast: AstBuilder<'a>,
+        taken: Option<Statement<'a>>,
+        removed: Option<&Statement<'a>>,
+    ) -> Option<Statement<'a>> {
+        let mut keep_var = Self::new(ast);
+        if let Some(removed) = removed {
+            keep_var.visit_statement(removed);
+        }
+        match (taken, keep_var.get_variable_declaration_statement()) {
+            (Some(taken), Some(var_decl)) => {
+                Some(ast.statement_block(SPAN, ast.vec_from_iter([taken, var_decl])))
+            }
+            (taken, var_decl) => taken.or(var_decl),
+        }
+    }
+
     pub fn get_variable_declaration_statement(self) -> Option<Statement<'a>> {
         if self.vars.is_empty() {
             return None;
@@ -2,50 +2,247 @@
 
 mod ast_passes;
 mod ast_util;
+mod chunk_boundary;
+mod commonjs;
 mod compressor;
+mod emit_size;
+mod env;
 mod keep_var;
+mod mangle_props;
+mod name_cache;
+mod numeric;
 mod options;
+mod parameters;
+mod property_names;
+mod pure_annotations;
+mod recycler;
+mod static_bindings;
+mod symbol_liveness;
 mod tri;
 mod ty;
+mod validate;
 
 use oxc_allocator::Allocator;
-use oxc_ast::ast::Program;
+use oxc_ast::{ast::Program, AstBuilder};
+use oxc_diagnostics::OxcDiagnostic;
 use oxc_mangler::{Mangler, ManglerBuilder};
+use oxc_span::{CompactStr, Span};
+use serde::{Deserialize, Serialize};
+
+use crate::mangle_props::PropertyMangler;
 
 pub use crate::{
-    ast_passes::{RemoveDeadCode, RemoveSyntax, ReplaceGlobalDefines, ReplaceGlobalDefinesConfig},
-    compressor::Compressor,
-    options::CompressOptions,
+    ast_passes::{
+        RemoveDeadCode, RemoveSyntax, ReplaceGlobalDefines, ReplaceGlobalDefinesConfig,
+        ReplaceGlobalDefinesReturn,
+    },
+    compressor::{Compressor, CompressorReturn, MinifierStats},
+    emit_size::EmitSizeEstimator,
+    env::Env,
+    mangle_props::ManglePropsOptions,
+    name_cache::NameCache,
+    options::{Budget, CompressOptions, ConsoleMethods, ESTarget, Inline, Profile, PureGetters},
+    property_names::PropertyNames,
+    pure_annotations::PureAnnotations,
+    recycler::Recycler,
+    symbol_liveness::{LiveSymbol, SymbolLiveness},
+    validate::OutputValidator,
 };
 
-#[derive(Debug, Clone, Copy)]
+/// Serialized with the field names, and deserialized with defaults for missing fields, see
+/// [`MinifierOptions::from_json_str`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct MinifierOptions {
     pub mangle: bool,
+    /// Also mangle the top-level names of scripts, which are globals other scripts can access.
+    /// Only used together with `mangle`; top-level names of modules are always mangled.
+    ///
+    /// Default `false`
+    #[serde(alias = "toplevel")]
+    pub mangle_toplevel: bool,
+    /// Mangled top-level names of previous runs, so scripts sharing globals agree on their
+    /// names, see [`NameCache`]. Only used together with `mangle_toplevel`.
+    ///
+    /// Default `None`
+    pub name_cache: Option<NameCache>,
+    /// Names which are neither mangled nor used as mangled names, for globals which code
+    /// outside of the program expects, e.g. `["$", "jQuery"]`. Only used together with
+    /// `mangle`. The globals of [`CompressOptions::env`] are never used as mangled names either.
+    ///
+    /// Default `[]`
+    #[serde(alias = "reserved")]
+    pub mangle_reserved: Vec<String>,
+    /// Also deserialized from a boolean, `false` for [`CompressOptions::all_false`].
+    #[serde(deserialize_with = "options::deserialize_compress")]
     pub compress: CompressOptions,
+    /// Rename properties whose names match a regex, see [`ManglePropsOptions`].
+    /// Unlike `mangle`, this is unsafe: it assumes the properties are only accessed by name in
+    /// the program.
+    ///
+    /// Default `None`
+    pub mangle_props: Option<ManglePropsOptions>,
+    /// Re-parse the emitted code and report syntax errors, see
+    /// [`MinifierReturn::validate_output`].
+    ///
+    /// Default `false`
+    pub validate_output: bool,
+    /// Also check that the emitted code exports the same names as the input.
+    /// Only used together with `validate_output`.
+    ///
+    /// Default `false`
+    pub validate_exports: bool,
+    /// Collect the top level symbols which survive compression, see [`SymbolLiveness`].
+    ///
+    /// Default `false`
+    pub symbol_liveness: bool,
+    /// Measure the sizes of [`MinifierReturn::stats`], which takes extra time.
+    ///
+    /// Default `false`
+    pub stats: bool,
 }
 
 impl Default for MinifierOptions {
     fn default() -> Self {
-        Self { mangle: true, compress: CompressOptions::default() }
+        Self {
+            mangle: true,
+            mangle_toplevel: false,
+            name_cache: None,
+            mangle_reserved: vec![],
+            compress: CompressOptions::default(),
+            mangle_props: None,
+            validate_output: false,
+            validate_exports: false,
+            symbol_liveness: false,
+            stats: false,
+        }
+    }
+}
+
+impl MinifierOptions {
+    /// Options from a JSON config, e.g.
+    /// `{ "mangle": false, "compress": { "drop_console": true, "ecma": 2015 } }`, for command line
+    /// tools and bindings which read options from a file or another language. Missing options
+    /// have their default values.
+    ///
+    /// # Errors
+    ///
+    /// The JSON is malformed, or has an unknown option or an invalid value. The error is labeled
+    /// with its location in `json`.
+    pub fn from_json_str(json: &str) -> Result<Self, OxcDiagnostic> {
+        serde_json::from_str(json).map_err(|err| {
+            let message = err.to_string();
+            let position = format!(" at line {} column {}", err.line(), err.column());
+            let message = message.strip_suffix(&position).unwrap_or(&message);
+            let offset = json_offset(json, err.line(), err.column());
+            OxcDiagnostic::error(format!("Invalid minifier options: {message}."))
+                .with_label(Span::new(offset, offset))
+        })
     }
 }
 
+/// The byte offset after the error at a 1-based line and column reported by `serde_json`, whose
+/// column is the last byte read.
+fn json_offset(json: &str, line: usize, column: usize) -> u32 {
+    let line_start =
+        json.split_inclusive('\n').take(line.saturating_sub(1)).map(str::len).sum::<usize>();
+    let offset = (line_start + column).min(json.len());
+    u32::try_from(offset).unwrap_or(u32::MAX)
+}
+
 pub struct MinifierReturn {
     pub mangler: Option<Mangler>,
+    /// Property name frequencies of the compressed program, see [`PropertyNames`].
+    pub property_names: PropertyNames,
+    /// Set when [`MinifierOptions::validate_output`] is enabled.
+    pub validator: Option<OutputValidator>,
+    /// Names of the compressor passes which ran, see [`CompressorReturn::passes`].
+    pub passes: Vec<&'static str>,
+    /// [`MinifierOptions::name_cache`] with the top-level names of this program added.
+    pub name_cache: Option<NameCache>,
+    /// Set when [`MinifierOptions::symbol_liveness`] is enabled.
+    pub symbol_liveness: Option<SymbolLiveness>,
+    /// Set when [`MinifierOptions::stats`] is enabled, see [`CompressorReturn::stats`].
+    pub stats: MinifierStats,
+}
+
+impl MinifierReturn {
+    /// Checks `output`, the code generated from the minified program, when
+    /// [`MinifierOptions::validate_output`] is enabled.
+    ///
+    /// # Errors
+    ///
+    /// See [`OutputValidator::validate`].
+    pub fn validate_output(&self, output: &str) -> Result<(), OxcDiagnostic> {
+        self.validator.as_ref().map_or(Ok(()), |validator| validator.validate(output))
+    }
 }
 
 pub struct Minifier {
     options: MinifierOptions,
+    pure_annotations: PureAnnotations,
 }
 
 impl Minifier {
     pub fn new(options: MinifierOptions) -> Self {
-        Self { options }
+        Self { options, pure_annotations: PureAnnotations::default() }
+    }
+
+    /// Remove unused calls annotated as pure, see [`Compressor::with_pure_annotations`].
+    #[must_use]
+    pub fn with_pure_annotations(mut self, pure_annotations: PureAnnotations) -> Self {
+        self.pure_annotations = pure_annotations;
+        self
     }
 
     pub fn build<'a>(self, allocator: &'a Allocator, program: &mut Program<'a>) -> MinifierReturn {
-        Compressor::new(allocator, self.options.compress).build(program);
-        let mangler = self.options.mangle.then(|| ManglerBuilder::default().build(program));
-        MinifierReturn { mangler }
+        let validator = self
+            .options
+            .validate_output
+            .then(|| OutputValidator::new(program, self.options.validate_exports));
+        let mut compress = self.options.compress;
+        let env = compress.env;
+        // The variables are only shorter than what they replace once they are mangled.
+        let mangles_top_level = self.options.mangle
+            && (program.source_type.is_module() || self.options.mangle_toplevel);
+        compress.undefined_alias &= mangles_top_level;
+        compress.hoist_strings &= mangles_top_level;
+        compress.hoist_props &= mangles_top_level;
+        let CompressorReturn { mut property_names, passes, symbol_liveness, stats, .. } =
+            Compressor::new(allocator, compress)
+                .with_pure_annotations(self.pure_annotations)
+                .with_symbol_liveness(self.options.symbol_liveness)
+                .with_stats(self.options.stats)
+                .build(program);
+        if let Some(options) = &self.options.mangle_props {
+            let ast = AstBuilder::new(allocator);
+            PropertyMangler::new(ast, options, &property_names, program).build(program);
+            property_names = PropertyNames::new(program);
+        }
+        let mut name_cache = self.options.name_cache;
+        let mangler = self.options.mangle.then(|| {
+            let cached_names = name_cache.iter().flat_map(NameCache::names);
+            let mangler = ManglerBuilder::default()
+                .top_level(self.options.mangle_toplevel)
+                .cached_names(cached_names)
+                .reserved(
+                    self.options.mangle_reserved.iter().map(String::as_str).map(CompactStr::from),
+                )
+                .globals(env.global_names().map(CompactStr::from))
+                .build(program);
+            if let Some(name_cache) = &mut name_cache {
+                name_cache.extend(&mangler);
+            }
+            mangler
+        });
+        MinifierReturn {
+            mangler,
+            property_names,
+            validator,
+            passes,
+            name_cache,
+            symbol_liveness,
+            stats,
+        }
     }
 }
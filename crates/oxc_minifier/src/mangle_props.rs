@@ -0,0 +1,296 @@
+use std::fmt;
+
+#[allow(clippy::wildcard_imports)]
+use oxc_ast::{
+    ast::*,
+    visit::{walk, walk_mut},
+    AstBuilder, Visit, VisitMut,
+};
+use oxc_span::{Atom, CompactStr, SPAN};
+use regex::Regex;
+use rustc_hash::{FxHashMap, FxHashSet};
+use serde::{
+    de::{self, value::MapAccessDeserializer, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::property_names::PropertyNames;
+
+/// Options of [`crate::MinifierOptions::mangle_props`].
+#[derive(Debug, Clone)]
+pub struct ManglePropsOptions {
+    /// Only properties whose names match are renamed, e.g. `^_` for names with a leading
+    /// underscore.
+    pub regex: Regex,
+
+    /// Do not rename names which are quoted anywhere, `a["_b"]` or `{ "_b": 1 }`, so properties
+    /// can be kept by quoting them.
+    ///
+    /// Default `false`
+    pub keep_quoted: bool,
+}
+
+impl ManglePropsOptions {
+    pub fn new(regex: Regex) -> Self {
+        Self { regex, keep_quoted: false }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ManglePropsRepr {
+    regex: String,
+    #[serde(default)]
+    keep_quoted: bool,
+}
+
+/// Serialized as `{ "regex": "^_", "keep_quoted": false }`, and also deserialized from only the
+/// regex, like esbuild's `mangleProps`.
+impl Serialize for ManglePropsOptions {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        ManglePropsRepr { regex: self.regex.as_str().to_string(), keep_quoted: self.keep_quoted }
+            .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for ManglePropsOptions {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ManglePropsVisitor;
+
+        impl<'de> Visitor<'de> for ManglePropsVisitor {
+            type Value = ManglePropsOptions;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a regex or mangle props options")
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<ManglePropsOptions, E> {
+                let regex = Regex::new(value).map_err(E::custom)?;
+                Ok(ManglePropsOptions::new(regex))
+            }
+
+            fn visit_map<A: de::MapAccess<'de>>(
+                self,
+                map: A,
+            ) -> Result<ManglePropsOptions, A::Error> {
+                let repr = ManglePropsRepr::deserialize(MapAccessDeserializer::new(map))?;
+                let regex = Regex::new(&repr.regex).map_err(de::Error::custom)?;
+                Ok(ManglePropsOptions { regex, keep_quoted: repr.keep_quoted })
+            }
+        }
+
+        deserializer.deserialize_any(ManglePropsVisitor)
+    }
+}
+
+/// Properties with special meaning, and builtin properties of objects, functions and arrays,
+/// which are never renamed or used as renamed names.
+const RESERVED_PROPERTIES: [&str; 56] = [
+    "__proto__",
+    "constructor",
+    "prototype",
+    // `Object.prototype`
+    "__defineGetter__",
+    "__defineSetter__",
+    "__lookupGetter__",
+    "__lookupSetter__",
+    "hasOwnProperty",
+    "isPrototypeOf",
+    "propertyIsEnumerable",
+    "toLocaleString",
+    "toString",
+    "valueOf",
+    // `Function.prototype`
+    "apply",
+    "arguments",
+    "bind",
+    "call",
+    "caller",
+    "length",
+    "name",
+    // `Array.prototype`
+    "at",
+    "concat",
+    "copyWithin",
+    "entries",
+    "every",
+    "fill",
+    "filter",
+    "find",
+    "findIndex",
+    "findLast",
+    "findLastIndex",
+    "flat",
+    "flatMap",
+    "forEach",
+    "includes",
+    "indexOf",
+    "join",
+    "keys",
+    "lastIndexOf",
+    "map",
+    "pop",
+    "push",
+    "reduce",
+    "reduceRight",
+    "reverse",
+    "shift",
+    "slice",
+    "some",
+    "sort",
+    "splice",
+    "toReversed",
+    "toSorted",
+    "toSpliced",
+    "unshift",
+    "values",
+    "with",
+];
+
+/// Renames the properties selected by [`ManglePropsOptions`], in member expressions,
+/// `a._b` and `a["_b"]`, and in property keys of objects, classes and destructuring patterns.
+///
+/// All occurrences of a name are renamed together, so the program stays consistent as long as
+/// the properties are not accessed in other ways: with dynamic keys, `"_b" in a`, as JSX
+/// attributes, or by code outside of the program. Names are renamed in [`PropertyNames`] order,
+/// so the most frequent ones get the shortest names, and never to the name of a property which
+/// is kept or of a [builtin property](RESERVED_PROPERTIES).
+pub struct PropertyMangler<'a> {
+    ast: AstBuilder<'a>,
+    renames: FxHashMap<CompactStr, Atom<'a>>,
+}
+
+impl<'a> PropertyMangler<'a> {
+    pub fn new(
+        ast: AstBuilder<'a>,
+        options: &ManglePropsOptions,
+        property_names: &PropertyNames,
+        program: &Program<'a>,
+    ) -> Self {
+        let mut usage = PropertyUsage::default();
+        usage.visit_program(program);
+        let mut mangled_names = oxc_mangler::mangled_names();
+        let mut renames = FxHashMap::default();
+        for (name, _) in property_names.sorted_by_frequency() {
+            if !options.regex.is_match(name)
+                || RESERVED_PROPERTIES.contains(&name)
+                || usage.unrenamable.contains(name)
+                || (options.keep_quoted && usage.quoted.contains(name))
+            {
+                continue;
+            }
+            let Some(mangled) = mangled_names.by_ref().find(|mangled| {
+                property_names.frequency(mangled) == 0
+                    && !RESERVED_PROPERTIES.contains(&mangled.as_str())
+            }) else {
+                break;
+            };
+            renames.insert(CompactStr::from(name), ast.atom(&mangled));
+        }
+        Self { ast, renames }
+    }
+
+    pub fn build(&mut self, program: &mut Program<'a>) {
+        if !self.renames.is_empty() {
+            self.visit_program(program);
+        }
+    }
+
+    fn rename(&self, name: &mut Atom<'a>) {
+        if let Some(mangled) = self.renames.get(name.as_str()) {
+            *name = mangled.clone();
+        }
+    }
+}
+
+impl<'a> VisitMut<'a> for PropertyMangler<'a> {
+    fn visit_member_expression(&mut self, expr: &mut MemberExpression<'a>) {
+        match expr {
+            MemberExpression::StaticMemberExpression(expr) => self.rename(&mut expr.property.name),
+            MemberExpression::ComputedMemberExpression(expr) => {
+                if let Expression::StringLiteral(lit) = &mut expr.expression {
+                    self.rename(&mut lit.value);
+                }
+            }
+            MemberExpression::PrivateFieldExpression(_) => {}
+        }
+        walk_mut::walk_member_expression(self, expr);
+    }
+
+    fn visit_property_key(&mut self, key: &mut PropertyKey<'a>) {
+        match key {
+            PropertyKey::StaticIdentifier(ident) => self.rename(&mut ident.name),
+            PropertyKey::StringLiteral(lit) => self.rename(&mut lit.value),
+            _ => {}
+        }
+        walk_mut::walk_property_key(self, key);
+    }
+
+    /// `({ _b } = a)` → `({ c: _b } = a)`
+    fn visit_assignment_target_property(&mut self, property: &mut AssignmentTargetProperty<'a>) {
+        if let AssignmentTargetProperty::AssignmentTargetPropertyIdentifier(ident) = property {
+            if let Some(mangled) = self.renames.get(ident.binding.name.as_str()) {
+                let name = self.ast.property_key_identifier_name(SPAN, mangled.clone());
+                let binding = self.ast.assignment_target_simple(
+                    self.ast
+                        .simple_assignment_target_from_identifier_reference(ident.binding.clone()),
+                );
+                let binding = match ident.init.take() {
+                    Some(init) => {
+                        self.ast.assignment_target_maybe_default_assignment_target_with_default(
+                            SPAN, binding, init,
+                        )
+                    }
+                    None => self.ast.assignment_target_maybe_default_assignment_target(binding),
+                };
+                *property =
+                    self.ast.assignment_target_property_assignment_target_property_property(
+                        ident.span, name, binding,
+                    );
+            }
+        }
+        walk_mut::walk_assignment_target_property(self, property);
+    }
+}
+
+/// Names which are quoted, and names which are used in ways that are not renamed, such as
+/// template literal keys `` a[`_b`] ``.
+#[derive(Default)]
+struct PropertyUsage {
+    quoted: FxHashSet<CompactStr>,
+    unrenamable: FxHashSet<CompactStr>,
+}
+
+impl<'a> Visit<'a> for PropertyUsage {
+    fn visit_member_expression(&mut self, expr: &MemberExpression<'a>) {
+        if let MemberExpression::ComputedMemberExpression(computed) = expr {
+            match &computed.expression {
+                Expression::StringLiteral(lit) => {
+                    self.quoted.insert(lit.value.to_compact_str());
+                }
+                Expression::TemplateLiteral(_) => {
+                    if let Some(name) = computed.static_property_name() {
+                        self.unrenamable.insert(name.to_compact_str());
+                    }
+                }
+                _ => {}
+            }
+        }
+        walk::walk_member_expression(self, expr);
+    }
+
+    fn visit_property_key(&mut self, key: &PropertyKey<'a>) {
+        match key {
+            PropertyKey::StaticIdentifier(_) => {}
+            PropertyKey::StringLiteral(lit) => {
+                self.quoted.insert(lit.value.to_compact_str());
+            }
+            _ => {
+                if let Some(name) = key.static_name() {
+                    self.unrenamable.insert(CompactStr::from(name.as_ref()));
+                }
+            }
+        }
+        walk::walk_property_key(self, key);
+    }
+}
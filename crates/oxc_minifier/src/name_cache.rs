@@ -0,0 +1,105 @@
+use std::collections::BTreeMap;
+
+use oxc_diagnostics::OxcDiagnostic;
+use oxc_mangler::{is_reserved_word, Mangler};
+use oxc_span::CompactStr;
+use oxc_syntax::identifier::is_identifier_name;
+use serde::{de, Deserialize, Deserializer, Serialize};
+
+/// Mangled names of top-level bindings, by original name, kept between runs of the minifier.
+///
+/// Scripts share their top-level bindings as globals, so when they are mangled with
+/// [`crate::MinifierOptions::mangle_toplevel`], scripts which use each other's globals, such as
+/// the chunks of a code-split build, must agree on the names. Pass the cache of the previous run
+/// in [`crate::MinifierOptions::name_cache`], and keep [`crate::MinifierReturn::name_cache`] for
+/// the next one.
+///
+/// Serialized as `{ "vars": { "original": "mangled" } }`, with sorted names so it diffs cleanly.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize)]
+pub struct NameCache {
+    vars: BTreeMap<String, String>,
+}
+
+impl NameCache {
+    /// # Errors
+    ///
+    /// * the JSON is malformed or not in the format written by [`NameCache::to_json`]
+    /// * a mangled name is not an identifier, or is a reserved word which the mangler never
+    ///   generates, see [`oxc_mangler::is_reserved_word`]
+    pub fn from_json(json: &str) -> Result<Self, OxcDiagnostic> {
+        let value: serde_json::Value = serde_json::from_str(json).map_err(|err| {
+            OxcDiagnostic::error(format!("Failed to parse name cache as JSON: {err}."))
+        })?;
+        Self::from_value(value)
+    }
+
+    fn from_value(value: serde_json::Value) -> Result<Self, OxcDiagnostic> {
+        let invalid = || {
+            OxcDiagnostic::error("Invalid name cache.")
+                .with_help(r#"A name cache looks like `{ "vars": { "original": "a" } }`."#)
+        };
+        let serde_json::Value::Object(mut cache) = value else { return Err(invalid()) };
+        let vars = match cache.remove("vars") {
+            Some(serde_json::Value::Object(vars)) => vars,
+            None => return Ok(Self::default()),
+            Some(_) => return Err(invalid()),
+        };
+        let mut names = Self::default();
+        for (name, mangled) in vars {
+            let serde_json::Value::String(mangled) = mangled else { return Err(invalid()) };
+            if !is_identifier_name(&mangled) {
+                return Err(OxcDiagnostic::error(format!(
+                    "The cached name for `{name}` is not an identifier: `{mangled}`."
+                )));
+            }
+            if is_reserved_word(&mangled) {
+                return Err(OxcDiagnostic::error(format!(
+                    "The cached name for `{name}` is a reserved word: `{mangled}`."
+                )));
+            }
+            names.vars.insert(name, mangled);
+        }
+        Ok(names)
+    }
+
+    pub fn to_json(&self) -> String {
+        let vars = self
+            .vars
+            .iter()
+            .map(|(name, mangled)| (name.clone(), mangled.clone().into()))
+            .collect::<serde_json::Map<_, _>>();
+        let cache =
+            serde_json::Value::Object([("vars".to_string(), vars.into())].into_iter().collect());
+        format!("{cache:#}")
+    }
+
+    /// The cached mangled name of the top-level binding `name`.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.vars.get(name).map(String::as_str)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.vars.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.vars.len()
+    }
+
+    pub(crate) fn names(&self) -> impl Iterator<Item = (CompactStr, CompactStr)> + '_ {
+        self.vars.iter().map(|(name, mangled)| (CompactStr::new(name), CompactStr::new(mangled)))
+    }
+
+    /// Add the top-level names of a mangled script.
+    pub(crate) fn extend(&mut self, mangler: &Mangler) {
+        self.vars
+            .extend(mangler.top_level_names().map(|(name, mangled)| (name.into(), mangled.into())));
+    }
+}
+
+impl<'de> Deserialize<'de> for NameCache {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = serde_json::Value::deserialize(deserializer)?;
+        Self::from_value(value).map_err(|err| de::Error::custom(err.to_string()))
+    }
+}
@@ -0,0 +1,230 @@
+//! Numeric semantics shared by the folding passes.
+//!
+//! Folding is only correct if it follows the conversions of the specification exactly, and the
+//! edge cases are easy to get wrong with Rust's own conversions: `"".parse::<f64>()` fails while
+//! `Number("")` is `0`, `"inf".parse::<f64>()` is infinite while `Number("inf")` is `NaN`, and
+//! `-0.0 == 0.0` hides the sign of zero. The helpers here are the only place which converts
+//! strings and bigint literals to numeric values.
+
+use num_bigint::BigInt;
+use num_traits::{Num, Zero};
+use oxc_syntax::{
+    identifier::{is_line_terminator, FF, NBSP, TAB, VT, ZWNBSP},
+    number::ToJsString,
+};
+
+/// The result of `ToNumber`. Infinite and `NaN` values are never stored in `Number`, use
+/// [`NumberValue::from`] to build a value from a computed `f64`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum NumberValue {
+    Number(f64),
+    PositiveInfinity,
+    NegativeInfinity,
+    NaN,
+}
+
+impl From<f64> for NumberValue {
+    fn from(value: f64) -> Self {
+        if value.is_nan() {
+            Self::NaN
+        } else if value == f64::INFINITY {
+            Self::PositiveInfinity
+        } else if value == f64::NEG_INFINITY {
+            Self::NegativeInfinity
+        } else {
+            Self::Number(value)
+        }
+    }
+}
+
+impl NumberValue {
+    #[must_use]
+    pub fn not(self) -> Self {
+        match self {
+            Self::Number(num) => Self::Number(-num),
+            Self::PositiveInfinity => Self::NegativeInfinity,
+            Self::NegativeInfinity => Self::PositiveInfinity,
+            Self::NaN => Self::NaN,
+        }
+    }
+
+    pub fn is_nan(self) -> bool {
+        matches!(self, Self::NaN)
+    }
+
+    pub fn to_f64(self) -> f64 {
+        match self {
+            Self::Number(num) => num,
+            Self::PositiveInfinity => f64::INFINITY,
+            Self::NegativeInfinity => f64::NEG_INFINITY,
+            Self::NaN => f64::NAN,
+        }
+    }
+
+    /// <https://tc39.es/ecma262/#sec-toboolean>, `NaN`, `0` and `-0` are falsy.
+    pub fn to_boolean(self) -> bool {
+        match self {
+            Self::Number(num) => num != 0.0,
+            Self::PositiveInfinity | Self::NegativeInfinity => true,
+            Self::NaN => false,
+        }
+    }
+
+    /// <https://tc39.es/ecma262/#sec-numeric-types-number-tostring>
+    pub fn to_js_string(self) -> String {
+        match self {
+            Self::Number(num) => num.to_js_string(),
+            Self::PositiveInfinity => "Infinity".to_string(),
+            Self::NegativeInfinity => "-Infinity".to_string(),
+            Self::NaN => "NaN".to_string(),
+        }
+    }
+}
+
+impl std::ops::Add<Self> for NumberValue {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::from(self.to_f64() + other.to_f64())
+    }
+}
+
+impl TryFrom<NumberValue> for f64 {
+    type Error = ();
+
+    fn try_from(value: NumberValue) -> Result<Self, Self::Error> {
+        match value {
+            NumberValue::NaN => Err(()),
+            value => Ok(value.to_f64()),
+        }
+    }
+}
+
+/// Whether a computed number can replace an expression as a numeric literal. `Infinity` and `NaN`
+/// are globals which may be shadowed, so only finite values are printed as literals. `-0` is
+/// printed as `-0`, which keeps its sign.
+pub fn is_printable_as_literal(value: f64) -> bool {
+    value.is_finite()
+}
+
+/// `StrWhiteSpaceChar`, the characters which `Number()` and `BigInt()` trim.
+///
+/// <https://tc39.es/ecma262/#prod-StrWhiteSpaceChar>
+fn is_str_whitespace(c: char) -> bool {
+    matches!(
+        c,
+        TAB | VT | FF | ' ' | NBSP | ZWNBSP | '\u{1680}' | '\u{2000}'
+            ..='\u{200a}' | '\u{202f}' | '\u{205f}' | '\u{3000}'
+    ) || is_line_terminator(c)
+}
+
+/// Trims `StrWhiteSpaceChar`, or returns `None` for strings containing a vertical tab, which old
+/// engines do not treat as whitespace.
+fn trim_str_whitespace(s: &str) -> Option<&str> {
+    if s.contains(VT) {
+        return None;
+    }
+    Some(s.trim_matches(is_str_whitespace))
+}
+
+/// Splits a `NonDecimalIntegerLiteral` into its digits and radix.
+fn split_radix(s: &str) -> Option<(&str, u32)> {
+    let radix = match s.get(..2)? {
+        "0x" | "0X" => 16,
+        "0o" | "0O" => 8,
+        "0b" | "0B" => 2,
+        _ => return None,
+    };
+    Some((&s[2..], radix))
+}
+
+fn split_sign(s: &str) -> (&str, bool) {
+    match s.as_bytes().first() {
+        Some(b'+') => (&s[1..], false),
+        Some(b'-') => (&s[1..], true),
+        _ => (s, false),
+    }
+}
+
+fn is_digits(s: &str, radix: u32) -> bool {
+    !s.is_empty() && s.chars().all(|c| c.is_digit(radix))
+}
+
+/// `StrUnsignedDecimalLiteral` without `Infinity`. Unlike numeric literals, there are no
+/// separators.
+fn is_unsigned_decimal(s: &str) -> bool {
+    let (mantissa, exponent) = match s.split_once(['e', 'E']) {
+        Some((mantissa, exponent)) => (mantissa, Some(exponent)),
+        None => (s, None),
+    };
+    let (integer, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    if integer.is_empty() && fraction.is_empty() {
+        return false;
+    }
+    let is_optional_digits = |s: &str| s.is_empty() || is_digits(s, 10);
+    is_optional_digits(integer)
+        && is_optional_digits(fraction)
+        && exponent.map_or(true, |exponent| {
+            is_digits(exponent.strip_prefix(['+', '-']).unwrap_or(exponent), 10)
+        })
+}
+
+/// `ToNumber` applied to a string, or `None` if the result is not known at compile time.
+///
+/// <https://tc39.es/ecma262/#sec-stringtonumber>
+#[allow(clippy::cast_precision_loss)]
+pub fn string_to_number(s: &str) -> Option<NumberValue> {
+    let s = trim_str_whitespace(s)?;
+    if s.is_empty() {
+        return Some(NumberValue::Number(0.0));
+    }
+    if let Some((digits, radix)) = split_radix(s) {
+        if !is_digits(digits, radix) {
+            return Some(NumberValue::NaN);
+        }
+        // Larger values would need the rounding of the specification.
+        let value = u64::from_str_radix(digits, radix).ok().filter(|&value| value < 1 << 53)?;
+        return Some(NumberValue::Number(value as f64));
+    }
+    let (unsigned, negative) = split_sign(s);
+    let value = if unsigned == "Infinity" {
+        NumberValue::PositiveInfinity
+    } else if is_unsigned_decimal(unsigned) {
+        NumberValue::from(unsigned.parse::<f64>().ok()?)
+    } else {
+        return Some(NumberValue::NaN);
+    };
+    Some(if negative { value.not() } else { value })
+}
+
+/// `StringToBigInt`, or `None` if the string is not a valid bigint.
+///
+/// <https://tc39.es/ecma262/#sec-stringtobigint>
+pub fn string_to_bigint(s: &str) -> Option<BigInt> {
+    let s = trim_str_whitespace(s)?;
+    if s.is_empty() {
+        return Some(BigInt::zero());
+    }
+    if let Some((digits, radix)) = split_radix(s) {
+        return if is_digits(digits, radix) {
+            BigInt::from_str_radix(digits, radix).ok()
+        } else {
+            None
+        };
+    }
+    let (unsigned, negative) = split_sign(s);
+    if !is_digits(unsigned, 10) {
+        return None;
+    }
+    let value = BigInt::from_str_radix(unsigned, 10).ok()?;
+    Some(if negative { -value } else { value })
+}
+
+/// The value of a bigint literal from its raw text, such as `0x1_0n`.
+pub fn bigint_literal_value(raw: &str) -> Option<BigInt> {
+    let raw = raw.strip_suffix('n')?.replace('_', "");
+    match split_radix(&raw) {
+        Some((digits, radix)) => BigInt::from_str_radix(digits, radix).ok(),
+        None => BigInt::from_str_radix(&raw, 10).ok(),
+    }
+}
@@ -1,12 +1,346 @@
-#[derive(Debug, Clone, Copy)]
+use std::{fmt, time::Duration};
+
+use bitflags::bitflags;
+use serde::{
+    de::{self, value::MapAccessDeserializer, Visitor},
+    ser::SerializeSeq,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::Env;
+
+/// ECMAScript version of the output, which decides the syntax compressions may introduce.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ESTarget {
+    ES3,
+    ES5,
+    ES2015,
+    ES2016,
+    ES2017,
+    ES2018,
+    ES2019,
+    ES2020,
+    ES2021,
+    ES2022,
+    ES2023,
+    ES2024,
+    #[default]
+    ESNext,
+}
+
+const ES_TARGETS: [(&str, u64, ESTarget); 13] = [
+    ("es3", 3, ESTarget::ES3),
+    ("es5", 5, ESTarget::ES5),
+    ("es2015", 2015, ESTarget::ES2015),
+    ("es2016", 2016, ESTarget::ES2016),
+    ("es2017", 2017, ESTarget::ES2017),
+    ("es2018", 2018, ESTarget::ES2018),
+    ("es2019", 2019, ESTarget::ES2019),
+    ("es2020", 2020, ESTarget::ES2020),
+    ("es2021", 2021, ESTarget::ES2021),
+    ("es2022", 2022, ESTarget::ES2022),
+    ("es2023", 2023, ESTarget::ES2023),
+    ("es2024", 2024, ESTarget::ES2024),
+    ("esnext", u64::MAX, ESTarget::ESNext),
+];
+
+/// Serialized as `"es2015"`, and deserialized from names in any case or from years, like
+/// terser's `ecma: 2015`.
+impl Serialize for ESTarget {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let (name, _, _) = ES_TARGETS.iter().find(|(_, _, target)| target == self).unwrap();
+        serializer.serialize_str(name)
+    }
+}
+
+impl<'de> Deserialize<'de> for ESTarget {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct TargetVisitor;
+
+        impl<'de> Visitor<'de> for TargetVisitor {
+            type Value = ESTarget;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("an ECMAScript version such as \"es2015\", \"esnext\" or 2015")
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<ESTarget, E> {
+                ES_TARGETS
+                    .iter()
+                    .find(|(name, _, _)| name.eq_ignore_ascii_case(value))
+                    .map(|(_, _, target)| *target)
+                    .ok_or_else(|| E::invalid_value(de::Unexpected::Str(value), &self))
+            }
+
+            fn visit_u64<E: de::Error>(self, value: u64) -> Result<ESTarget, E> {
+                ES_TARGETS
+                    .iter()
+                    .find(|(_, year, _)| *year == value)
+                    .map(|(_, _, target)| *target)
+                    .ok_or_else(|| E::invalid_value(de::Unexpected::Unsigned(value), &self))
+            }
+        }
+
+        deserializer.deserialize_any(TargetVisitor)
+    }
+}
+
+/// How much the side effect analysis may assume about property access, see
+/// [`CompressOptions::pure_getters`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum PureGetters {
+    /// `pure_getters: false`: any property access may call a getter with side effects.
+    #[default]
+    Never,
+    /// `pure_getters: "safe"`: getters have no side effects, but property access may still throw
+    /// because the object is `null` or `undefined`. Access is only free of side effects when the
+    /// object is known to be a primitive or an object, or with optional chaining, `a?.b`.
+    Safe,
+    /// `pure_getters: true`: property access has no side effects and never throws.
+    Always,
+}
+
+/// Serialized like terser's option, `false`, `"safe"` or `true`. terser's `"strict"` is
+/// deserialized as `Safe`.
+impl Serialize for PureGetters {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Self::Never => serializer.serialize_bool(false),
+            Self::Safe => serializer.serialize_str("safe"),
+            Self::Always => serializer.serialize_bool(true),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for PureGetters {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct PureGettersVisitor;
+
+        impl<'de> Visitor<'de> for PureGettersVisitor {
+            type Value = PureGetters;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a boolean or \"safe\"")
+            }
+
+            fn visit_bool<E: de::Error>(self, value: bool) -> Result<PureGetters, E> {
+                Ok(if value { PureGetters::Always } else { PureGetters::Never })
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<PureGetters, E> {
+                match value {
+                    "safe" | "strict" => Ok(PureGetters::Safe),
+                    _ => Err(E::invalid_value(de::Unexpected::Str(value), &self)),
+                }
+            }
+        }
+
+        deserializer.deserialize_any(PureGettersVisitor)
+    }
+}
+
+/// Which functions are inlined into their call sites, see [`CompressOptions::inline`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Inline {
+    /// `inline: 0`: calls are kept.
+    #[default]
+    Never,
+    /// `inline: 1`: functions without parameters.
+    Simple,
+    /// `inline: 2` and `inline: 3`: functions with parameters as well, when the arguments are
+    /// literals or bindings which are never reassigned.
+    Arguments,
+}
+
+/// Serialized as the levels of terser's option, `0`, `1` or `2`. `3` and `true` are deserialized
+/// as `Arguments`, and `false` as `Never`.
+impl Serialize for Inline {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(*self as u8)
+    }
+}
+
+impl<'de> Deserialize<'de> for Inline {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct InlineVisitor;
+
+        impl<'de> Visitor<'de> for InlineVisitor {
+            type Value = Inline;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a boolean or a level from 0 to 3")
+            }
+
+            fn visit_bool<E: de::Error>(self, value: bool) -> Result<Inline, E> {
+                Ok(if value { Inline::Arguments } else { Inline::Never })
+            }
+
+            fn visit_u64<E: de::Error>(self, value: u64) -> Result<Inline, E> {
+                match value {
+                    0 => Ok(Inline::Never),
+                    1 => Ok(Inline::Simple),
+                    2 | 3 => Ok(Inline::Arguments),
+                    _ => Err(E::invalid_value(de::Unexpected::Unsigned(value), &self)),
+                }
+            }
+        }
+
+        deserializer.deserialize_any(InlineVisitor)
+    }
+}
+
+/// Limit of the effort spent on expensive passes, see [`CompressOptions::budget`].
+///
+/// Serialized as `{ "time_ms": 100 }` or `{ "size": 10000 }`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "BudgetRepr", into = "BudgetRepr")]
+pub enum Budget {
+    /// Run expensive passes while the compressor has taken less than this long in total.
+    /// `Duration::ZERO` only runs the enabled passes once, `Duration::MAX` runs expensive passes
+    /// until they no longer shrink the program.
+    /// Uses `std::time::Instant`, which is not available on `wasm32-unknown-unknown`.
+    Time(Duration),
+    /// Run expensive passes while the output, without whitespace, is larger than this many bytes.
+    Size(usize),
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+enum BudgetRepr {
+    TimeMs(u64),
+    Size(usize),
+}
+
+impl From<BudgetRepr> for Budget {
+    fn from(budget: BudgetRepr) -> Self {
+        match budget {
+            BudgetRepr::TimeMs(ms) => Self::Time(Duration::from_millis(ms)),
+            BudgetRepr::Size(size) => Self::Size(size),
+        }
+    }
+}
+
+impl From<Budget> for BudgetRepr {
+    fn from(budget: Budget) -> Self {
+        match budget {
+            Budget::Time(time) => Self::TimeMs(u64::try_from(time.as_millis()).unwrap_or(u64::MAX)),
+            Budget::Size(size) => Self::Size(size),
+        }
+    }
+}
+
+/// What the compressor optimizes for, see [`CompressOptions::profile`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Profile {
+    /// The smallest output.
+    #[default]
+    Size,
+    /// The smallest output, except where a few more bytes make the code faster to run.
+    Speed,
+}
+
+bitflags! {
+    /// Methods of `console`, see [`CompressOptions::keep_console`].
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+    pub struct ConsoleMethods: u32 {
+        const Assert         = 1 << 0;
+        const Clear          = 1 << 1;
+        const Count          = 1 << 2;
+        const CountReset     = 1 << 3;
+        const Debug          = 1 << 4;
+        const Dir            = 1 << 5;
+        const Dirxml         = 1 << 6;
+        const Error          = 1 << 7;
+        const Group          = 1 << 8;
+        const GroupCollapsed = 1 << 9;
+        const GroupEnd       = 1 << 10;
+        const Info           = 1 << 11;
+        const Log            = 1 << 12;
+        const Table          = 1 << 13;
+        const Time           = 1 << 14;
+        const TimeEnd        = 1 << 15;
+        const TimeLog        = 1 << 16;
+        const Trace          = 1 << 17;
+        const Warn           = 1 << 18;
+    }
+}
+
+const CONSOLE_METHODS: [(&str, ConsoleMethods); 19] = [
+    ("assert", ConsoleMethods::Assert),
+    ("clear", ConsoleMethods::Clear),
+    ("count", ConsoleMethods::Count),
+    ("countReset", ConsoleMethods::CountReset),
+    ("debug", ConsoleMethods::Debug),
+    ("dir", ConsoleMethods::Dir),
+    ("dirxml", ConsoleMethods::Dirxml),
+    ("error", ConsoleMethods::Error),
+    ("group", ConsoleMethods::Group),
+    ("groupCollapsed", ConsoleMethods::GroupCollapsed),
+    ("groupEnd", ConsoleMethods::GroupEnd),
+    ("info", ConsoleMethods::Info),
+    ("log", ConsoleMethods::Log),
+    ("table", ConsoleMethods::Table),
+    ("time", ConsoleMethods::Time),
+    ("timeEnd", ConsoleMethods::TimeEnd),
+    ("timeLog", ConsoleMethods::TimeLog),
+    ("trace", ConsoleMethods::Trace),
+    ("warn", ConsoleMethods::Warn),
+];
+
+impl ConsoleMethods {
+    /// The method named `name`, e.g. `"error"` for `console.error`.
+    pub fn from_method(name: &str) -> Option<Self> {
+        CONSOLE_METHODS.iter().find(|(method, _)| *method == name).map(|(_, method)| *method)
+    }
+}
+
+/// Serialized as a list of method names, `["error", "warn"]`.
+impl Serialize for ConsoleMethods {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(None)?;
+        for (name, method) in CONSOLE_METHODS {
+            if self.contains(method) {
+                seq.serialize_element(name)?;
+            }
+        }
+        seq.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for ConsoleMethods {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let names = Vec::<String>::deserialize(deserializer)?;
+        names.iter().try_fold(Self::empty(), |methods, name| {
+            Self::from_method(name)
+                .map(|method| methods | method)
+                .ok_or_else(|| de::Error::custom(format!("unknown console method `{name}`")))
+        })
+    }
+}
+
+/// Serialized with the field names, and deserialized with defaults for missing fields. Some
+/// fields also accept the names of the terser or esbuild options they match, e.g. `ecma` for
+/// `target` or `dead_code` for `remove_dead_code`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
 pub struct CompressOptions {
+    /// Output syntax must be supported by this version.
+    ///
+    /// Default `ESNext`
+    #[serde(alias = "ecma")]
+    pub target: ESTarget,
+
     pub remove_syntax: bool,
     pub substitute_alternate_syntax: bool,
     pub fold_constants: bool,
+    #[serde(alias = "dead_code")]
     pub remove_dead_code: bool,
+    #[serde(alias = "collapse_vars")]
     pub collapse: bool,
 
-    /// Various optimizations for boolean context, for example `!!a ? b : c` → `a ? b : c`.
+    /// Various optimizations for boolean context, for example `!!a ? b : c` → `a ? b : c`,
+    /// `true` → `!0`, `a == true` → `a == 1` and `Boolean(a)` → `!!a`.
     ///
     /// Default `true`
     pub booleans: bool,
@@ -16,11 +350,29 @@ pub struct CompressOptions {
     /// Default `true`
     pub drop_debugger: bool,
 
-    /// Remove `console.*` statements.
+    /// Remove `console.*()` call statements, and replace calls in other expressions with
+    /// `void 0`, `x = console.log(a)` → `x = void 0`. The arguments are removed as well.
     ///
     /// Default `false`
     pub drop_console: bool,
 
+    /// Methods of `console` whose calls `drop_console` keeps, e.g.
+    /// `ConsoleMethods::Error | ConsoleMethods::Warn`. When any are kept, calls with a computed
+    /// method name, `console[method]()`, are kept as well.
+    ///
+    /// Default `ConsoleMethods::empty()`
+    pub keep_console: ConsoleMethods,
+
+    /// Names of functions without side effects, e.g. `"invariant"` or `"Debug.log"`. Calls whose
+    /// result is unused are removed, except for arguments with side effects,
+    /// `invariant(x, "message"); assert(check())` → `check()`.
+    ///
+    /// Calls are matched by name, whether or not the name is shadowed by a local binding.
+    ///
+    /// Default `vec![]`
+    #[serde(alias = "pure")]
+    pub pure_funcs: Vec<String>,
+
     /// Attempt to evaluate constant expressions
     ///
     /// Default `true`
@@ -31,6 +383,13 @@ pub struct CompressOptions {
     /// Default `true`
     pub join_vars: bool,
 
+    /// Join the branches of `if` statements into conditional expressions,
+    /// `if (a) b(); else c()` → `a ? b() : c()`, `if (a) x = b; else x = c` → `x = a ? b : c` and
+    /// `if (a) return b; return c` → `return a ? b : c`.
+    ///
+    /// Default `true`
+    pub conditionals: bool,
+
     /// Optimizations for do, while and for loops when we can statically determine the condition
     ///
     /// Default `true`
@@ -40,11 +399,334 @@ pub struct CompressOptions {
     ///
     /// Default `true`
     pub typeofs: bool,
+
+    /// Assume calling a generator or async generator function has no side effects until the
+    /// returned iterator is consumed.
+    ///
+    /// When enabled, discarded calls such as `g();` to a sibling `function* g(x) {}` are removed,
+    /// as well as generator declarations that are never referenced.
+    /// Only generators with plain identifier parameters qualify, because default values and
+    /// destructuring are evaluated at call time, and only names which are never assigned.
+    /// Async functions are not covered, their body runs synchronously until the first `await`,
+    /// see [`CompressOptions::unawaited_async`].
+    ///
+    /// Default `false`
+    pub unused_generators: bool,
+
+    /// Assume the promise returned by a discarded call of an async function is never observed,
+    /// including its rejection by an `unhandledrejection` handler, and that the values it awaits
+    /// or returns are not thenables whose `then` method has side effects.
+    ///
+    /// When enabled, discarded calls such as `f();` to a sibling `async function f(x) {}` are
+    /// removed when the body of `f` only has expressions, `await` and `return` statements without
+    /// side effects, as well as async function declarations that are never referenced. The
+    /// parameters must be plain identifiers, like for [`CompressOptions::unused_generators`].
+    ///
+    /// Default `false`
+    pub unawaited_async: bool,
+
+    /// Convert between string concatenation and template literals when the result is shorter,
+    /// for example `"a" + x + "b"` → `` `a${x}b` `` and `` `${x}px` `` → `x + "px"`.
+    /// Literal substitutions are moved into the text, `` `a${1}b` `` → `"a1b"`, and template
+    /// literals without substitutions become strings unless the string needs more escapes.
+    ///
+    /// Template literals convert substitutions with `ToString`, while `+` first calls
+    /// `ToPrimitive` without a hint, so this assumes objects converted to strings do not
+    /// distinguish the two, e.g. by having a `valueOf` that disagrees with `toString`.
+    /// Operands before the first string in a chain stay grouped, so `1 + x + "a"` becomes
+    /// `` `${1 + x}a` `` and not `` `1${x}a` ``.
+    /// Concatenations only become template literals when `target` is ES2015 or later.
+    ///
+    /// Default `false`
+    pub template_literals: bool,
+
+    /// Rewrite functions without parameters which only use `arguments` for `arguments.length`,
+    /// `arguments[i]`, `...arguments` and `f.apply(x, arguments)` to a rest parameter,
+    /// `function f() { return arguments[0] }` → `function f(...args) { return args[0] }`.
+    ///
+    /// Assumes computed accesses `arguments[key]` only use indices, since a rest parameter is an
+    /// array and has properties such as `map` that `arguments` does not.
+    /// Only applies when `target` is ES2015 or later.
+    ///
+    /// Default `false`
+    pub rest_parameters: bool,
+
+    /// Use the shorter literal syntax of ES2015. Properties become shorthand properties,
+    /// `{ a: a }` → `{ a }`, and functions without a name become methods,
+    /// `{ f: function () {} }` → `{ f() {} }`. In arrays which are only iterated, by spread,
+    /// `for-of` or destructuring, `void 0` elements become holes, `f(...[a, void 0, b])` →
+    /// `f(...[a, , b])`, and trailing ones are dropped when destructuring without a rest
+    /// element, `[a, b] = [1, void 0]` → `[a, b] = [1]`.
+    ///
+    /// Methods can not be called with `new` and have no `prototype`, so functions which use
+    /// `this` or `new.target` are kept, and this assumes the others are not used as
+    /// constructors. Holes read `Array.prototype`, which is assumed to have no indexed
+    /// properties. Only applies when `target` is ES2015 or later.
+    ///
+    /// Default `false`
+    pub shorthand: bool,
+
+    /// Turn function expressions without a name into arrow functions,
+    /// `f(function (a) { return a })` → `f((a) => a)`.
+    ///
+    /// Functions which use `this`, `arguments`, `new.target` or a direct `eval` are kept, as are
+    /// generators and functions used with `new`, `instanceof`, `extends` or `.prototype`,
+    /// directly or through a name they are assigned to. Arrow functions have no `prototype`, so
+    /// this assumes the others are not constructed elsewhere, such as by the callers of exported
+    /// functions. Object literal methods are preferred with `shorthand`.
+    /// Only applies when `target` is ES2015 or later.
+    ///
+    /// Default `false`
+    pub arrow_functions: bool,
+
+    /// Replace the global `NaN` and `Infinity` with `0 / 0` and `1 / 0`, which can not be
+    /// shadowed. References to local bindings of the same names and inside `with` bodies are
+    /// kept.
+    ///
+    /// Default `false`
+    pub global_constants: bool,
+
+    /// Replace `undefined` and `void 0` with a variable which is never assigned when they are
+    /// used often enough that this is shorter once the variable is mangled,
+    /// `f(void 0); g(void 0)` → `var _undefined; f(_undefined); g(_undefined)`.
+    ///
+    /// The variable is declared at the top level, so this only applies to modules and, with
+    /// `commonjs`, to scripts. [`crate::Minifier`] turns it off unless the top level is mangled.
+    /// Programs which call `eval` are not changed.
+    ///
+    /// Default `false`
+    pub undefined_alias: bool,
+
+    /// Replace string literals which are repeated often enough that this is shorter once the
+    /// variable is mangled with a variable declared at the top level,
+    /// `f("mousedown"); g("mousedown"); h("mousedown")` →
+    /// `var _str = "mousedown"; f(_str); g(_str); h(_str)`.
+    /// Module names, `require("x")` and `import("x")`, and property keys are kept.
+    ///
+    /// Like `undefined_alias`, this only applies to modules and, with `commonjs`, to scripts,
+    /// [`crate::Minifier`] turns it off unless the top level is mangled, and programs which call
+    /// `eval` are not changed. The variable is assigned before the rest of the module runs, so
+    /// functions called by other modules of an import cycle before that see `undefined`.
+    ///
+    /// Default `false`
+    pub hoist_strings: bool,
+
+    /// Replace chains of properties of globals of the `env` which are repeated often enough with
+    /// a variable like `hoist_strings` does, `f(JSON.stringify, JSON.stringify, JSON.stringify)`
+    /// → `var _JSON_stringify = JSON.stringify; f(_JSON_stringify, ...)`. Chains which are
+    /// called, assigned or deleted are kept.
+    ///
+    /// Default `false`
+    pub hoist_props: bool,
+
+    /// Remove unused labels and merge blocks into the enclosing statement list.
+    /// `break` statements to the label of a block are rewritten to conditionals first,
+    /// `a: { if (x) break a; y() }` → `if (!x) y()`.
+    ///
+    /// Default `true`
+    pub labels: bool,
+    /// Unwrap immediately invoked functions and arrow functions without parameters or
+    /// arguments. Those which only return a value become the value, `(() => 42)()` → `42`, and
+    /// those whose result is unused become a block, `(function () { let a = f(); g(a) })()` →
+    /// `{ let a = f(); g(a) }`.
+    ///
+    /// Functions which use their own `this`, `arguments` or `new.target`, call `eval`, or whose
+    /// body has directives are kept. Blocks are only made from bodies which do not return and
+    /// do not declare `var`s or, in scripts, functions. Requires `remove_dead_code`.
+    ///
+    /// Default `false`
+    pub iife: bool,
+
+    /// Assume scripts are CommonJS modules, whose top level is the scope of the module wrapper
+    /// function, and where `exports` is `module.exports` unless either is reassigned.
+    ///
+    /// Removes duplicate `__esModule` markers, `require()` calls of `.json` files whose result is
+    /// unused, and shortens `module.exports.foo` to `exports.foo`.
+    /// Unused exports are kept, and `define` replacements are not propagated through
+    /// `module.exports`. ES modules are not affected.
+    ///
+    /// Default `false`
+    pub commonjs: bool,
+
+    /// Rewrite property access and object literal keys with the dot notation and unquoted names,
+    /// `foo["bar"]` → `foo.bar` and `{ "bar": 1 }` → `{ bar: 1 }`.
+    ///
+    /// When `target` is ES3, reserved words are quoted instead whether or not this is enabled,
+    /// `foo.class` → `foo["class"]`, because ES3 does not allow them as property names.
+    ///
+    /// Default `true`
+    pub properties: bool,
+
+    /// Simplify default parameter values:
+    /// drop `= undefined` defaults, turn leading `if (a === undefined) a = 1` statements into
+    /// defaults when `target` is ES2015 or later, and drop unused defaults in destructured
+    /// parameters, `function f({ a = 1 }) {}` → `function f({ a }) {}`. Without
+    /// `keep_fn_length`, also drop unused trailing parameters of functions which do not use
+    /// `arguments`, `function f(a, b) { return a }` → `function f(a) { return a }`.
+    ///
+    /// Default `true`
+    pub parameters: bool,
+
+    /// Keep the `length` of functions and classes, which counts the parameters before the first
+    /// one with a default value, for code which reflects on it. Rewrites which change the number
+    /// of parameters are skipped, and defaults are only added or removed after that parameter.
+    ///
+    /// Default `true`
+    #[serde(alias = "keep_fargs")]
+    pub keep_fn_length: bool,
+
+    /// Compress classes: drop constructors which are the same as the default one,
+    /// `constructor(...args) { super(...args) }`, and static blocks without side effects, and
+    /// turn `const C = class {}` into `class C {}` when `C` is never assigned.
+    ///
+    /// Default `true`
+    pub classes: bool,
+
+    /// Merge `export { a }; export { b }` into `export { a, b }`, and imports and re-exports of
+    /// the same module into the first one, `import a from "m"; import { b } from "m"` →
+    /// `import a, { b } from "m"`. Imports without bindings, `import "m"`, are dropped when the
+    /// module is imported anyway.
+    ///
+    /// Default `true`
+    pub join_imports_exports: bool,
+
+    /// Leave lazy chunk boundaries as written, so bundlers which split code at them still
+    /// recognize them after minification: functions which only return a dynamic import,
+    /// `() => import("./page")`, and calls of lazy component wrappers such as `React.lazy(...)`
+    /// or `defineAsyncComponent(...)`.
+    ///
+    /// No pass inlines, merges or rewrites code inside them.
+    ///
+    /// Default `false`
+    pub keep_chunk_boundaries: bool,
+
+    /// Rewrite lookups in `Set` and `Map` collections built from literals, which is common in
+    /// generated code: `new Set(["a", "b"]).has(x)` → `x === "a" || x === "b"` when shorter, and
+    /// `new Map([["a", 1]]).get(x)` → `{ a: 1 }[x]`. `const` bindings of such collections are
+    /// rewritten when they are only used for `.has(x)` or `.get(x)` calls.
+    ///
+    /// Assumes `Set` and `Map` are the built-in globals, and that keys looked up in a `Map` are
+    /// strings which are not properties of `Object.prototype`, such as `"toString"`.
+    ///
+    /// Default `false`
+    pub unsafe_collections: bool,
+
+    /// Assume property getters have no side effects, so unused property access can be removed
+    /// and reordered, `void a.b, c` → `c`.
+    ///
+    /// Object spread, `{ ...a }`, is covered as well, since it only calls getters.
+    /// Access of private fields, `a.#b`, is never assumed to be free of side effects, because it
+    /// throws when `a` does not have the field.
+    ///
+    /// Default `PureGetters::Never`
+    pub pure_getters: PureGetters,
+
+    /// Hoist function expressions which are repeated in a module, as is common for helpers
+    /// after transpilation, into a single shared copy when that is shorter, `a.map((x) => x.id);
+    /// b.map((x) => x.id)` → `const _fn = (x) => x.id; a.map(_fn); b.map(_fn)`.
+    ///
+    /// Only functions which do not close over bindings of an enclosing function or block are
+    /// hoisted, so they behave the same at the top level. Assumes the identity and the `name` of
+    /// the functions are not observed, since all occurrences evaluate to the same function.
+    /// Only applies to modules.
+    ///
+    /// Default `false`
+    pub dedupe_functions: bool,
+
+    /// Remove top level functions, classes and variables of modules which are never referenced
+    /// and not exported, `function unused() {}` → ``. Declarations which are only referenced by
+    /// removed declarations are removed as well.
+    ///
+    /// Variables are only removed when their initializer has no side effects, and classes when
+    /// evaluating them has none. Nothing is removed when the module calls `eval`.
+    ///
+    /// Default `false`
+    pub unused: bool,
+
+    /// Experimental: reorder consecutive top level declarations without side effects so that
+    /// similar ones are next to each other, which compresses better with gzip,
+    /// `const a = {}; function f() {} const b = {}` →
+    /// `const a = {}; const b = {}; function f() {}`.
+    /// Declarations which reference each other keep their order.
+    /// [`CompressorReturn::stats`](crate::CompressorReturn::stats) reports the gzipped size
+    /// before and after when enabled with
+    /// [`Compressor::with_stats`](crate::Compressor::with_stats).
+    ///
+    /// Default `false`
+    pub reorder_declarations: bool,
+
+    /// Inline `const` and `let` bindings which are read exactly once and never written,
+    /// `const a = f(); return a` → `return f()`.
+    ///
+    /// Primitive literals are inlined anywhere later in the same block. Other initializers are
+    /// only inlined into the next statement, when that does not change the order of side
+    /// effects. Top level bindings of scripts are kept, and nothing is inlined when the program
+    /// calls `eval`.
+    ///
+    /// Default `false`
+    pub inline_variables: bool,
+
+    /// Inline calls of functions whose body is a single `return` of a small expression,
+    /// `function f(a) { return a + 1 } g(f(2))` → `g(2 + 1)`. Functions whose calls are all
+    /// inlined are removed.
+    ///
+    /// Only functions which do not use `this`, `arguments` or nested functions are inlined, and
+    /// only where the bindings they reference resolve the same. Like terser's `inline: 3`, except
+    /// that functions which declare variables are kept. Top level functions of scripts are kept,
+    /// since other scripts may replace them.
+    ///
+    /// Default `Inline::Never`
+    pub inline: Inline,
+
+    /// Names exported by the module which no other module imports, `"default"` for the default
+    /// export. With `unused`, these exports are removed, and their declarations as well unless
+    /// the module still uses them: `export function f() {}` → ``. Re-exports are kept.
+    ///
+    /// Meant for bundlers which tree shake across chunks: they minify every chunk, find the
+    /// exports no chunk uses from the [`SymbolLiveness`](crate::SymbolLiveness) of all chunks, and
+    /// minify again with them.
+    ///
+    /// Default `[]`
+    pub unused_exports: Vec<String>,
+
+    /// Adapt the effort to a budget: the enabled passes run once, and expensive passes run only
+    /// while the budget allows. These are further rounds of `fold_constants`, `remove_dead_code`,
+    /// `substitute_alternate_syntax` and `collapse`, until a round no longer shrinks the program,
+    /// and then `dedupe_functions` if it is enabled.
+    /// [`CompressorReturn::passes`](crate::CompressorReturn::passes) reports which passes ran.
+    ///
+    /// Without a budget, the enabled passes run once.
+    ///
+    /// Default `None`
+    pub budget: Option<Budget>,
+
+    /// Trade size for runtime performance with `Profile::Speed`, which lowers iteration of arrays
+    /// to indexed `for` loops: `forEach` and `map` calls whose result is unused, and `for...of`
+    /// loops, `a.forEach((x) => f(x))` →
+    /// `for (let _i = 0; _i < a.length; _i++) { let x = a[_i]; f(x) }`.
+    ///
+    /// Only applies to arrays bound by a `const` declaration of an array literal without holes,
+    /// which are only used for `.length`, reading elements and iteration, and to arrow function
+    /// callbacks which can be inlined in the loop. Assumes `Array.prototype` is not modified.
+    ///
+    /// Default `Profile::Size`
+    pub profile: Profile,
+
+    /// The environment the output runs in, whose globals are assumed to exist or not:
+    /// `typeof window === "undefined"` → `!1` in `Env::Browser` and `!0` in `Env::Node`, and
+    /// calls of its pure functions are dropped when their result is unused, `performance.now();`
+    /// → ``. Assumptions about the environment belong here rather than in `unsafe_*` options or
+    /// `pure_funcs`. See [`Env`] for what each environment knows.
+    ///
+    /// Default `Env::Neutral`
+    #[serde(alias = "platform")]
+    pub env: Env,
 }
 
 impl Default for CompressOptions {
     fn default() -> Self {
         Self {
+            target: ESTarget::ESNext,
             remove_syntax: true,
             substitute_alternate_syntax: true,
             fold_constants: true,
@@ -53,10 +735,43 @@ impl Default for CompressOptions {
             booleans: true,
             drop_debugger: true,
             drop_console: false,
+            keep_console: ConsoleMethods::empty(),
+            pure_funcs: vec![],
             evaluate: true,
             join_vars: true,
+            conditionals: true,
             loops: true,
             typeofs: true,
+            unused_generators: false,
+            unawaited_async: false,
+            template_literals: false,
+            rest_parameters: false,
+            shorthand: false,
+            arrow_functions: false,
+            global_constants: false,
+            undefined_alias: false,
+            hoist_strings: false,
+            hoist_props: false,
+            labels: true,
+            iife: false,
+            commonjs: false,
+            properties: true,
+            parameters: true,
+            keep_fn_length: true,
+            classes: true,
+            join_imports_exports: true,
+            keep_chunk_boundaries: false,
+            unsafe_collections: false,
+            pure_getters: PureGetters::Never,
+            dedupe_functions: false,
+            unused: false,
+            reorder_declarations: false,
+            unused_exports: vec![],
+            inline_variables: false,
+            inline: Inline::Never,
+            budget: None,
+            profile: Profile::Size,
+            env: Env::Neutral,
         }
     }
 }
@@ -77,6 +792,7 @@ impl CompressOptions {
 
     pub fn all_false() -> Self {
         Self {
+            target: ESTarget::ESNext,
             remove_syntax: false,
             substitute_alternate_syntax: false,
             fold_constants: false,
@@ -85,15 +801,49 @@ impl CompressOptions {
             booleans: false,
             drop_debugger: false,
             drop_console: false,
+            keep_console: ConsoleMethods::empty(),
+            pure_funcs: vec![],
             evaluate: false,
             join_vars: false,
+            conditionals: false,
             loops: false,
             typeofs: false,
+            unused_generators: false,
+            unawaited_async: false,
+            template_literals: false,
+            rest_parameters: false,
+            shorthand: false,
+            arrow_functions: false,
+            global_constants: false,
+            undefined_alias: false,
+            hoist_strings: false,
+            hoist_props: false,
+            labels: false,
+            iife: false,
+            commonjs: false,
+            properties: false,
+            parameters: false,
+            keep_fn_length: true,
+            classes: false,
+            join_imports_exports: false,
+            keep_chunk_boundaries: false,
+            unsafe_collections: false,
+            pure_getters: PureGetters::Never,
+            dedupe_functions: false,
+            unused: false,
+            reorder_declarations: false,
+            unused_exports: vec![],
+            inline_variables: false,
+            inline: Inline::Never,
+            budget: None,
+            profile: Profile::Size,
+            env: Env::Neutral,
         }
     }
 
     pub fn dead_code_elimintation() -> Self {
         Self {
+            target: ESTarget::ESNext,
             remove_syntax: true,
             fold_constants: true,
             remove_dead_code: true,
@@ -101,3 +851,29 @@ impl CompressOptions {
         }
     }
 }
+
+/// Deserializes [`crate::MinifierOptions::compress`] from options, or from a boolean like
+/// terser's `compress: false`, which disables every compression.
+pub(crate) fn deserialize_compress<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<CompressOptions, D::Error> {
+    struct CompressVisitor;
+
+    impl<'de> Visitor<'de> for CompressVisitor {
+        type Value = CompressOptions;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("a boolean or compress options")
+        }
+
+        fn visit_bool<E: de::Error>(self, value: bool) -> Result<CompressOptions, E> {
+            Ok(if value { CompressOptions::default() } else { CompressOptions::all_false() })
+        }
+
+        fn visit_map<A: de::MapAccess<'de>>(self, map: A) -> Result<CompressOptions, A::Error> {
+            CompressOptions::deserialize(MapAccessDeserializer::new(map))
+        }
+    }
+
+    deserializer.deserialize_any(CompressVisitor)
+}
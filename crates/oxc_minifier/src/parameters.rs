@@ -0,0 +1,150 @@
+#[allow(clippy::wildcard_imports)]
+use oxc_ast::{ast::*, Visit};
+use oxc_span::Atom;
+use oxc_syntax::operator::{AssignmentOperator, BinaryOperator, LogicalOperator};
+use rustc_hash::FxHashSet;
+
+/// Names used by a function, which decide whether the defaults of its parameters can be added or
+/// removed.
+///
+/// Names are collected from nested functions too, so shadowing only makes the result more
+/// conservative.
+#[derive(Default)]
+pub struct ParameterUsage<'a> {
+    /// Names read or written anywhere in the parameters or the body.
+    references: FxHashSet<Atom<'a>>,
+    /// Names declared in the body.
+    declarations: FxHashSet<Atom<'a>>,
+    /// Names bound directly by the parameters, in order.
+    parameters: Vec<Atom<'a>>,
+    in_body: bool,
+    /// Direct `eval` can read and declare any name.
+    has_eval: bool,
+}
+
+impl<'a> ParameterUsage<'a> {
+    pub fn new(params: &FormalParameters<'a>, body: &FunctionBody<'a>) -> Self {
+        let mut usage = Self::default();
+        usage.visit_formal_parameters(params);
+        usage.in_body = true;
+        usage.visit_function_body(body);
+        usage
+    }
+
+    pub fn has_eval(&self) -> bool {
+        self.has_eval
+    }
+
+    pub fn is_referenced(&self, name: &str) -> bool {
+        self.has_eval || self.references.contains(name)
+    }
+
+    pub fn is_declared_in_body(&self, name: &str) -> bool {
+        self.has_eval || self.declarations.contains(name)
+    }
+
+    /// Whether the parameters can become non-simple: duplicate names are not allowed then, and
+    /// `arguments` is no longer mapped to the parameters in sloppy mode.
+    pub fn can_have_non_simple_parameters(&self) -> bool {
+        let unique = self.parameters.iter().collect::<FxHashSet<_>>();
+        !self.is_referenced("arguments") && unique.len() == self.parameters.len()
+    }
+}
+
+impl<'a> Visit<'a> for ParameterUsage<'a> {
+    fn visit_identifier_reference(&mut self, ident: &IdentifierReference<'a>) {
+        if ident.name == "eval" {
+            self.has_eval = true;
+        }
+        self.references.insert(ident.name.clone());
+    }
+
+    fn visit_binding_identifier(&mut self, ident: &BindingIdentifier<'a>) {
+        if self.in_body {
+            self.declarations.insert(ident.name.clone());
+        }
+    }
+
+    fn visit_formal_parameter(&mut self, param: &FormalParameter<'a>) {
+        if !self.in_body {
+            if let Some(ident) = param.pattern.get_binding_identifier() {
+                self.parameters.push(ident.name.clone());
+            }
+        }
+        self.visit_binding_pattern(&param.pattern);
+    }
+}
+
+/// Whether the parameter is written with a default value, which ends the parameters counted by
+/// `Function.prototype.length`.
+pub fn has_default(param: &FormalParameter) -> bool {
+    matches!(param.pattern.kind, BindingPatternKind::AssignmentPattern(_))
+}
+
+/// `if (a === void 0) a = value` or `a === void 0 && (a = value)`, which is the default value of
+/// a parameter `a`. Returns `a` and `value`.
+pub fn default_assignment<'s, 'a>(
+    stmt: &'s Statement<'a>,
+) -> Option<(&'s Atom<'a>, &'s Expression<'a>)> {
+    let (test, assignment) = match stmt {
+        Statement::IfStatement(if_stmt) if if_stmt.alternate.is_none() => {
+            let consequent = match &if_stmt.consequent {
+                Statement::BlockStatement(block) => match block.body.as_slice() {
+                    [stmt] => stmt,
+                    _ => return None,
+                },
+                stmt => stmt,
+            };
+            let Statement::ExpressionStatement(expr_stmt) = consequent else { return None };
+            (&if_stmt.test, &expr_stmt.expression)
+        }
+        Statement::ExpressionStatement(expr_stmt) => {
+            let Expression::LogicalExpression(logical_expr) = &expr_stmt.expression else {
+                return None;
+            };
+            if logical_expr.operator != LogicalOperator::And {
+                return None;
+            }
+            (&logical_expr.left, logical_expr.right.without_parenthesized())
+        }
+        _ => return None,
+    };
+    let Expression::AssignmentExpression(assign_expr) = assignment else { return None };
+    let AssignmentTarget::AssignmentTargetIdentifier(target) = &assign_expr.left else {
+        return None;
+    };
+    if assign_expr.operator != AssignmentOperator::Assign || !is_undefined_check(test, &target.name)
+    {
+        return None;
+    }
+    Some((&target.name, &assign_expr.right))
+}
+
+/// The assigned value of a [`default_assignment`].
+pub fn take_default_value(stmt: Statement<'_>) -> Option<Expression<'_>> {
+    let assignment = match stmt {
+        Statement::IfStatement(if_stmt) => match if_stmt.unbox().consequent {
+            Statement::BlockStatement(block) => block.unbox().body.into_iter().next()?,
+            stmt => stmt,
+        },
+        stmt => stmt,
+    };
+    let Statement::ExpressionStatement(expr_stmt) = assignment else { return None };
+    let mut expr = expr_stmt.unbox().expression;
+    if let Expression::LogicalExpression(logical_expr) = expr {
+        expr = logical_expr.unbox().right;
+    }
+    while let Expression::ParenthesizedExpression(paren_expr) = expr {
+        expr = paren_expr.unbox().expression;
+    }
+    let Expression::AssignmentExpression(assign_expr) = expr else { return None };
+    Some(assign_expr.unbox().right)
+}
+
+/// `a === void 0` or `void 0 === a`
+fn is_undefined_check(expr: &Expression, name: &str) -> bool {
+    let Expression::BinaryExpression(binary_expr) = expr else { return false };
+    binary_expr.operator == BinaryOperator::StrictEquality
+        && ((binary_expr.left.is_specific_id(name) && binary_expr.right.is_void_0())
+            || (binary_expr.left.is_void_0() && binary_expr.right.is_specific_id(name)))
+}
@@ -0,0 +1,82 @@
+#[allow(clippy::wildcard_imports)]
+use oxc_ast::{ast::*, visit::walk, Visit};
+use oxc_span::CompactStr;
+use rustc_hash::FxHashMap;
+
+/// How often each property name occurs in the program.
+///
+/// Counted once after compression, so later steps which rank property names, e.g. property
+/// mangling or string deduplication, can share it instead of each walking the AST again.
+///
+/// Counts member expressions and property keys with a static name (`a.b`, `a["b"]`, `{ b: 1 }`)
+/// in objects, classes and destructuring patterns, including shorthand assignment targets.
+#[derive(Debug, Default, Clone)]
+pub struct PropertyNames {
+    frequencies: FxHashMap<CompactStr, usize>,
+}
+
+impl PropertyNames {
+    pub fn new(program: &Program) -> Self {
+        let mut names = Self::default();
+        names.visit_program(program);
+        names
+    }
+
+    /// Number of occurrences of `name`.
+    pub fn frequency(&self, name: &str) -> usize {
+        self.frequencies.get(name).copied().unwrap_or_default()
+    }
+
+    /// Number of distinct names.
+    pub fn len(&self) -> usize {
+        self.frequencies.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frequencies.is_empty()
+    }
+
+    /// Names and their frequencies, most frequent first. Ties are ordered by name, so the order
+    /// does not depend on hashing.
+    pub fn sorted_by_frequency(&self) -> Vec<(&str, usize)> {
+        let mut names = self
+            .frequencies
+            .iter()
+            .map(|(name, &frequency)| (name.as_str(), frequency))
+            .collect::<Vec<_>>();
+        names.sort_unstable_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+        names
+    }
+
+    fn add(&mut self, name: &str) {
+        if let Some(frequency) = self.frequencies.get_mut(name) {
+            *frequency += 1;
+        } else {
+            self.frequencies.insert(CompactStr::from(name), 1);
+        }
+    }
+}
+
+impl<'a> Visit<'a> for PropertyNames {
+    fn visit_member_expression(&mut self, expr: &MemberExpression<'a>) {
+        if let Some(name) = expr.static_property_name() {
+            self.add(name);
+        }
+        walk::walk_member_expression(self, expr);
+    }
+
+    fn visit_property_key(&mut self, key: &PropertyKey<'a>) {
+        if let Some(name) = key.static_name() {
+            self.add(&name);
+        }
+        walk::walk_property_key(self, key);
+    }
+
+    fn visit_assignment_target_property_identifier(
+        &mut self,
+        ident: &AssignmentTargetPropertyIdentifier<'a>,
+    ) {
+        self.add(&ident.binding.name);
+        walk::walk_assignment_target_property_identifier(self, ident);
+    }
+}
@@ -0,0 +1,82 @@
+use oxc_ast::{
+    ast::{CallExpression, NewExpression, Program},
+    visit::walk,
+    CommentKind, Trivias, Visit,
+};
+use oxc_span::Span;
+use rustc_hash::FxHashSet;
+
+/// Call and `new` expressions annotated with `/* @__PURE__ */` or `/* #__PURE__ */`, which have
+/// no side effects other than those of their arguments. The compressor removes them when their
+/// result is unused, `/* @__PURE__ */ f(a, g())` → `g()`.
+///
+/// An annotation applies to the call or `new` expression right after it, separated only by
+/// whitespace and parentheses, `/* @__PURE__ */ (f())`. In a chain of calls, it applies to the
+/// outermost one, `/* @__PURE__ */ a().b()`.
+#[derive(Debug, Default, Clone)]
+pub struct PureAnnotations {
+    spans: FxHashSet<Span>,
+}
+
+impl PureAnnotations {
+    /// Finds the annotations of `program`, which was parsed from `source_text` with `trivias`.
+    pub fn new(program: &Program, source_text: &str, trivias: &Trivias) -> Self {
+        let mut finder = PureAnnotationFinder {
+            source_text,
+            trivias,
+            spans: FxHashSet::default(),
+            used_comments: FxHashSet::default(),
+        };
+        finder.visit_program(program);
+        Self { spans: finder.spans }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.spans.is_empty()
+    }
+
+    /// Whether the call or `new` expression at `span` is annotated as pure.
+    pub fn contains(&self, span: Span) -> bool {
+        self.spans.contains(&span)
+    }
+}
+
+struct PureAnnotationFinder<'t> {
+    source_text: &'t str,
+    trivias: &'t Trivias,
+    spans: FxHashSet<Span>,
+    /// Starts of the comments which already annotate an expression.
+    used_comments: FxHashSet<u32>,
+}
+
+impl PureAnnotationFinder<'_> {
+    fn annotate(&mut self, span: Span) {
+        let Some(comment) = self.trivias.comments_range(..span.start).next_back() else { return };
+        let end = match comment.kind {
+            CommentKind::SingleLine => comment.span.end,
+            CommentKind::MultiLine => comment.span.end + 2,
+        };
+        let Some(between) = self.source_text.get(end as usize..span.start as usize) else {
+            return;
+        };
+        let text = comment.span.source_text(self.source_text);
+        if between.chars().all(|c| c.is_ascii_whitespace() || c == '(')
+            && (text.contains("@__PURE__") || text.contains("#__PURE__"))
+            && self.used_comments.insert(comment.span.start)
+        {
+            self.spans.insert(span);
+        }
+    }
+}
+
+impl<'a> Visit<'a> for PureAnnotationFinder<'_> {
+    fn visit_call_expression(&mut self, expr: &CallExpression<'a>) {
+        self.annotate(expr.span);
+        walk::walk_call_expression(self, expr);
+    }
+
+    fn visit_new_expression(&mut self, expr: &NewExpression<'a>) {
+        self.annotate(expr.span);
+        walk::walk_new_expression(self, expr);
+    }
+}
@@ -0,0 +1,214 @@
+use std::mem;
+
+use oxc_allocator::{Box, Vec};
+#[allow(clippy::wildcard_imports)]
+use oxc_ast::{ast::*, AstBuilder};
+use oxc_span::{Atom, GetSpan, Span};
+use oxc_syntax::number::NumberBase;
+
+/// Freelists of placeholder and literal nodes.
+///
+/// Moving a node out of the AST leaves a placeholder behind, a `null` literal for expressions and
+/// an empty statement for statements. The arena never frees memory, so every move used to allocate
+/// a new placeholder, which became garbage as soon as the parent was replaced or the empty
+/// statement was removed. The recycler reuses these placeholders instead.
+///
+/// Folding constants also replaces operators on literals with new literals, `1 + 2` → `3` and
+/// `"a" === "b"` → `false`. The literal operands of replaced operators are kept, and reused by
+/// the literals which later folds create.
+///
+/// The compressor threads a single recycler through all passes, so placeholders created by one
+/// pass (e.g. empty statements left by folding `if (false)`) are reused by the next.
+///
+/// Since every pass has it, it also counts the changes made to the AST, which tells the compressor
+/// whether a pass changed the program.
+#[derive(Default)]
+pub struct Recycler<'a> {
+    null_literals: std::vec::Vec<Box<'a, NullLiteral>>,
+    empty_statements: std::vec::Vec<Box<'a, EmptyStatement>>,
+    boolean_literals: std::vec::Vec<Box<'a, BooleanLiteral>>,
+    numeric_literals: std::vec::Vec<Box<'a, NumericLiteral<'a>>>,
+    string_literals: std::vec::Vec<Box<'a, StringLiteral<'a>>>,
+    changes: usize,
+}
+
+impl<'a> Recycler<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of changes made so far, by moving nodes out of the AST or reported with
+    /// [`Recycler::mark_changed`].
+    pub fn changes(&self) -> usize {
+        self.changes
+    }
+
+    /// Records a change which is not made with the recycler, such as setting a field in place.
+    pub fn mark_changed(&mut self) {
+        self.changes += 1;
+    }
+
+    /// A `null` literal, reusing a recycled one if available.
+    pub fn null_literal(&mut self, ast: AstBuilder<'a>, span: Span) -> Expression<'a> {
+        match self.null_literals.pop() {
+            Some(mut lit) => {
+                lit.span = span;
+                Expression::NullLiteral(lit)
+            }
+            None => ast.expression_null_literal(span),
+        }
+    }
+
+    /// An empty statement, reusing a recycled one if available.
+    pub fn empty_statement(&mut self, ast: AstBuilder<'a>, span: Span) -> Statement<'a> {
+        match self.empty_statements.pop() {
+            Some(mut stmt) => {
+                stmt.span = span;
+                Statement::EmptyStatement(stmt)
+            }
+            None => ast.statement_empty(span),
+        }
+    }
+
+    /// A boolean literal, reusing a recycled one if available.
+    pub fn boolean_literal(
+        &mut self,
+        ast: AstBuilder<'a>,
+        span: Span,
+        value: bool,
+    ) -> Expression<'a> {
+        match self.boolean_literals.pop() {
+            Some(mut lit) => {
+                lit.span = span;
+                lit.value = value;
+                Expression::BooleanLiteral(lit)
+            }
+            None => ast.expression_boolean_literal(span, value),
+        }
+    }
+
+    /// A numeric literal, reusing a recycled one if available.
+    pub fn numeric_literal(
+        &mut self,
+        ast: AstBuilder<'a>,
+        span: Span,
+        value: f64,
+        raw: &'a str,
+        base: NumberBase,
+    ) -> Expression<'a> {
+        match self.numeric_literals.pop() {
+            Some(mut lit) => {
+                lit.span = span;
+                lit.value = value;
+                lit.raw = raw;
+                lit.base = base;
+                Expression::NumericLiteral(lit)
+            }
+            None => ast.expression_numeric_literal(span, value, raw, base),
+        }
+    }
+
+    /// A string literal, reusing a recycled one if available.
+    pub fn string_literal(
+        &mut self,
+        ast: AstBuilder<'a>,
+        span: Span,
+        value: Atom<'a>,
+    ) -> Expression<'a> {
+        match self.string_literals.pop() {
+            Some(mut lit) => {
+                lit.span = span;
+                lit.value = value;
+                Expression::StringLiteral(lit)
+            }
+            None => ast.expression_string_literal(span, value),
+        }
+    }
+
+    /// Moves the expression out by replacing it with a recycled `null` literal.
+    pub fn take_expression(
+        &mut self,
+        ast: AstBuilder<'a>,
+        expr: &mut Expression<'a>,
+    ) -> Expression<'a> {
+        self.changes += 1;
+        let null_expr = self.null_literal(ast, expr.span());
+        mem::replace(expr, null_expr)
+    }
+
+    /// Moves the statement out by replacing it with a recycled empty statement.
+    pub fn take_statement(
+        &mut self,
+        ast: AstBuilder<'a>,
+        stmt: &mut Statement<'a>,
+    ) -> Statement<'a> {
+        self.changes += 1;
+        let empty_stmt = self.empty_statement(ast, stmt.span());
+        mem::replace(stmt, empty_stmt)
+    }
+
+    /// Replaces the expression, recycling the old one.
+    pub fn replace_expression(&mut self, expr: &mut Expression<'a>, new_expr: Expression<'a>) {
+        self.changes += 1;
+        let old_expr = mem::replace(expr, new_expr);
+        self.recycle_expression(old_expr);
+    }
+
+    /// Replaces the statement, recycling the old one.
+    pub fn replace_statement(&mut self, stmt: &mut Statement<'a>, new_stmt: Statement<'a>) {
+        self.changes += 1;
+        let old_stmt = mem::replace(stmt, new_stmt);
+        self.recycle_statement(old_stmt);
+    }
+
+    /// Keeps the node for reuse if it is a placeholder or literal kind. The operands of unary,
+    /// binary and logical expressions are recycled as well, other children are not.
+    pub fn recycle_expression(&mut self, expr: Expression<'a>) {
+        let mut stack = vec![expr];
+        while let Some(expr) = stack.pop() {
+            match expr {
+                Expression::NullLiteral(lit) => self.null_literals.push(lit),
+                Expression::BooleanLiteral(lit) => self.boolean_literals.push(lit),
+                Expression::NumericLiteral(lit) => self.numeric_literals.push(lit),
+                Expression::StringLiteral(lit) => self.string_literals.push(lit),
+                Expression::UnaryExpression(unary_expr) => stack.push(unary_expr.unbox().argument),
+                Expression::BinaryExpression(binary_expr) => {
+                    let binary_expr = binary_expr.unbox();
+                    stack.extend([binary_expr.left, binary_expr.right]);
+                }
+                Expression::LogicalExpression(logical_expr) => {
+                    let logical_expr = logical_expr.unbox();
+                    stack.extend([logical_expr.left, logical_expr.right]);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Keeps the node for reuse if it is a placeholder kind. Children are not recycled.
+    pub fn recycle_statement(&mut self, stmt: Statement<'a>) {
+        if let Statement::EmptyStatement(stmt) = stmt {
+            self.empty_statements.push(stmt);
+        }
+    }
+
+    /// Same as [`Vec::retain`], but recycles the removed statements.
+    pub fn retain_statements<F>(&mut self, stmts: &mut Vec<'a, Statement<'a>>, mut keep: F)
+    where
+        F: FnMut(&Statement<'a>) -> bool,
+    {
+        let mut kept = 0;
+        for i in 0..stmts.len() {
+            if keep(&stmts[i]) {
+                stmts.swap(kept, i);
+                kept += 1;
+            }
+        }
+        if kept < stmts.len() {
+            self.changes += 1;
+        }
+        for stmt in stmts.drain(kept..) {
+            self.recycle_statement(stmt);
+        }
+    }
+}
@@ -0,0 +1,327 @@
+#[allow(clippy::wildcard_imports)]
+use oxc_ast::{ast::*, visit::walk, Visit};
+use oxc_span::{Atom, GetSpan, Span};
+use oxc_syntax::scope::ScopeFlags;
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::{ast_util::MayHaveSideEffects, ty::Ty, Env, PureGetters};
+
+/// Global constructors which return a new instance of themselves when called without arguments.
+const PURE_GLOBAL_CONSTRUCTORS: [&str; 17] = [
+    "Array",
+    "Object",
+    "Map",
+    "Set",
+    "WeakMap",
+    "WeakSet",
+    "Date",
+    "RegExp",
+    "Error",
+    "AggregateError",
+    "EvalError",
+    "RangeError",
+    "ReferenceError",
+    "SyntaxError",
+    "TypeError",
+    "URIError",
+    "ArrayBuffer",
+];
+
+/// Bindings whose values are known everywhere in the program: top level function and class
+/// declarations of a module, top level `var` declarations of a module which are never assigned,
+/// and the globals of the [`Env`].
+///
+/// A declaration qualifies when its name is declared only once in the whole program and never
+/// assigned, so no reference can see a different value. A global qualifies when its name is
+/// never declared or assigned, like [`Ty`](crate::ty::Ty) assumes for `undefined`. Top level
+/// bindings of a script can be reassigned by other scripts, and direct `eval` can declare or
+/// assign any name, so nothing is known about declarations then.
+///
+/// Classes are in their temporal dead zone until their declaration is evaluated, so only
+/// references after the declaration know a class.
+///
+/// Globals are not known inside `with` bodies, where they may resolve to properties of the
+/// object, nor in functions which call `eval` directly, which may declare a `var` of the same
+/// name.
+#[derive(Default)]
+pub struct StaticBindings<'a> {
+    /// Top level function and class declarations of a module.
+    functions: FxHashSet<Atom<'a>>,
+    /// The end of the top level class declarations of a module.
+    class_ends: FxHashMap<Atom<'a>, u32>,
+    /// Top level `var` declarations of a module without an initializer, which stay `undefined`.
+    undefined_vars: FxHashSet<Atom<'a>>,
+    /// Top level classes for which `new C()` has no side effects and returns an instance of `C`.
+    pure_classes: FxHashSet<Atom<'a>>,
+    /// Number of declarations of each name, in any scope.
+    declarations: FxHashMap<Atom<'a>, usize>,
+    assigned: FxHashSet<Atom<'a>>,
+    has_eval: bool,
+    /// `with` bodies and functions which call `eval` directly, or the whole program if a direct
+    /// `eval` is not in a function.
+    opaque_spans: Vec<Span>,
+    /// Functions being visited, innermost last.
+    function_spans: Vec<Span>,
+    pure_getters: PureGetters,
+    env: Env,
+}
+
+impl<'a> StaticBindings<'a> {
+    pub fn new(program: &Program<'a>, pure_getters: PureGetters, env: Env) -> Self {
+        let mut bindings = Self { pure_getters, env, ..Self::default() };
+        bindings.visit_program(program);
+        if program.source_type.is_module() && !bindings.has_eval {
+            for stmt in &program.body {
+                bindings.add_declaration(stmt);
+            }
+        }
+        bindings
+    }
+
+    /// The value of `typeof ident`, if `ident` refers to a top level function or class, a top
+    /// level `var` which is never assigned, or a global of the environment.
+    pub fn type_of(&self, ident: &IdentifierReference<'a>) -> Option<&'static str> {
+        let name = ident.name.as_str();
+        if self.functions.contains(name) {
+            return self.is_initialized(name, ident.span).then_some("function");
+        }
+        if self.undefined_vars.contains(name) {
+            return Some("undefined");
+        }
+        if self.is_global(name) {
+            return self.env.type_of(name);
+        }
+        None
+    }
+
+    /// The value of `typeof expr` for an identifier, see [`StaticBindings::type_of`], or a
+    /// property of a global of the environment, `document.createElement`.
+    pub fn type_of_expression(&self, expr: &Expression<'a>) -> Option<&'static str> {
+        match expr {
+            Expression::Identifier(ident) => self.type_of(ident),
+            Expression::StaticMemberExpression(_) => {
+                let (root, path) = global_path(expr)?;
+                (self.is_global(root) && self.env.exists(root))
+                    .then(|| self.env.type_of(&path))
+                    .flatten()
+            }
+            _ => None,
+        }
+    }
+
+    /// Whether `expr` is a call of a pure function of the environment, see
+    /// [`Env::pure_functions`].
+    pub fn is_pure_call(&self, expr: &Expression<'a>) -> bool {
+        let Expression::CallExpression(call_expr) = expr else { return false };
+        if call_expr.optional || call_expr.arguments.iter().any(Argument::is_spread) {
+            return false;
+        }
+        let Some((root, path)) = global_path(&call_expr.callee) else { return false };
+        self.is_global(root)
+            && !self.is_opaque(call_expr.span)
+            && self.env.pure_functions().any(|function| function == path)
+    }
+
+    /// Whether `span` is inside a `with` body or a function which calls `eval` directly, where
+    /// names may not refer to the bindings they appear to.
+    pub fn is_opaque(&self, span: Span) -> bool {
+        self.opaque_spans.iter().any(|opaque| opaque.start <= span.start && span.end <= opaque.end)
+    }
+
+    /// The type of `expr` like [`Ty::from`], which also knows that calls of the global `String`,
+    /// `Number` and `Boolean` return a string, a number and a boolean.
+    pub fn ty(&self, expr: &Expression<'a>) -> Ty {
+        if let Expression::CallExpression(call_expr) = expr {
+            if let Expression::Identifier(ident) = &call_expr.callee {
+                let ty = match ident.name.as_str() {
+                    "String" => Ty::Str,
+                    "Number" => Ty::Number,
+                    "Boolean" => Ty::Boolean,
+                    _ => Ty::Undetermined,
+                };
+                if self.is_global(&ident.name) && !self.is_opaque(call_expr.span) {
+                    return ty;
+                }
+            }
+        }
+        Ty::from(expr)
+    }
+
+    /// Whether `name` refers to a global which is never declared or assigned in the program.
+    pub fn is_global(&self, name: &str) -> bool {
+        !self.declarations.contains_key(name) && !self.assigned.contains(name)
+    }
+
+    /// Whether `name` may be assigned anywhere in the program.
+    pub fn is_assigned(&self, name: &str) -> bool {
+        self.has_eval || self.assigned.contains(name)
+    }
+
+    /// Whether `new callee(...arguments)` has no side effects and returns an instance of
+    /// `callee`.
+    pub fn is_pure_construction(
+        &self,
+        callee: &IdentifierReference<'a>,
+        arguments: &[Argument<'a>],
+    ) -> bool {
+        let name = callee.name.as_str();
+        if self.pure_classes.contains(name) {
+            return self.is_initialized(name, callee.span)
+                && arguments.iter().all(|argument| {
+                    argument
+                        .as_expression()
+                        .is_some_and(|expr| !expr.may_have_side_effects(self.pure_getters))
+                });
+        }
+        arguments.is_empty() && PURE_GLOBAL_CONSTRUCTORS.contains(&name) && self.is_global(name)
+    }
+
+    /// Whether a reference at `span` comes after the declaration of `name`, if it is a class.
+    fn is_initialized(&self, name: &str, span: Span) -> bool {
+        self.class_ends.get(name).map_or(true, |&end| span.start >= end)
+    }
+
+    fn add_declaration(&mut self, stmt: &Statement<'a>) {
+        let var_decl = match stmt {
+            Statement::ExportNamedDeclaration(decl) => match &decl.declaration {
+                Some(Declaration::VariableDeclaration(var_decl)) => Some(var_decl),
+                _ => None,
+            },
+            Statement::VariableDeclaration(var_decl) => Some(var_decl),
+            _ => None,
+        };
+        if let Some(var_decl) = var_decl.filter(|decl| decl.kind.is_var() && !decl.declare) {
+            for declarator in &var_decl.declarations {
+                if let BindingPatternKind::BindingIdentifier(ident) = &declarator.id.kind {
+                    if declarator.init.is_none() && self.is_constant(&ident.name) {
+                        self.undefined_vars.insert(ident.name.clone());
+                    }
+                }
+            }
+            return;
+        }
+        let declaration = match stmt {
+            Statement::ExportNamedDeclaration(decl) => match &decl.declaration {
+                Some(Declaration::FunctionDeclaration(func)) => Some((func.id.as_ref(), None)),
+                Some(Declaration::ClassDeclaration(class)) => {
+                    Some((class.id.as_ref(), Some(class)))
+                }
+                _ => None,
+            },
+            Statement::ExportDefaultDeclaration(decl) => match &decl.declaration {
+                ExportDefaultDeclarationKind::FunctionDeclaration(func) => {
+                    Some((func.id.as_ref(), None))
+                }
+                ExportDefaultDeclarationKind::ClassDeclaration(class) => {
+                    Some((class.id.as_ref(), Some(class)))
+                }
+                _ => None,
+            },
+            Statement::FunctionDeclaration(func) => Some((func.id.as_ref(), None)),
+            Statement::ClassDeclaration(class) => Some((class.id.as_ref(), Some(class))),
+            _ => None,
+        };
+        let Some((Some(ident), class)) = declaration else { return };
+        if !self.is_constant(&ident.name) {
+            return;
+        }
+        self.functions.insert(ident.name.clone());
+        if let Some(class) = class {
+            self.class_ends.insert(ident.name.clone(), class.span.end);
+            if is_pure_class(class, self.pure_getters) {
+                self.pure_classes.insert(ident.name.clone());
+            }
+        }
+    }
+
+    /// Whether `name` is declared only once and never assigned.
+    fn is_constant(&self, name: &str) -> bool {
+        self.declarations.get(name) == Some(&1) && !self.assigned.contains(name)
+    }
+}
+
+/// The name of the global at the root of a chain of static member expressions, `document`, and
+/// the path of the chain, `document.createElement`.
+pub fn global_path<'b>(expr: &'b Expression) -> Option<(&'b str, String)> {
+    match expr {
+        Expression::Identifier(ident) => Some((ident.name.as_str(), ident.name.to_string())),
+        Expression::StaticMemberExpression(member_expr) if !member_expr.optional => {
+            let (root, path) = global_path(&member_expr.object)?;
+            Some((root, format!("{path}.{}", member_expr.property.name)))
+        }
+        _ => None,
+    }
+}
+
+/// Whether `new C()` only creates an instance: nothing runs during construction and no
+/// `static [Symbol.hasInstance]` can change the result of `instanceof`.
+fn is_pure_class(class: &Class, pure_getters: PureGetters) -> bool {
+    class.super_class.is_none()
+        && class.decorators.is_empty()
+        && class.body.body.iter().all(|element| match element {
+            ClassElement::MethodDefinition(method) if method.kind.is_constructor() => {
+                method.value.body.as_ref().is_some_and(|body| body.statements.is_empty())
+            }
+            ClassElement::PropertyDefinition(property) if !property.r#static => property
+                .value
+                .as_ref()
+                .map_or(true, |value| !value.may_have_side_effects(pure_getters)),
+            ClassElement::AccessorProperty(property) if !property.r#static => property
+                .value
+                .as_ref()
+                .map_or(true, |value| !value.may_have_side_effects(pure_getters)),
+            element => !(element.r#static() && element.computed()),
+        })
+}
+
+impl<'a> Visit<'a> for StaticBindings<'a> {
+    fn visit_identifier_reference(&mut self, ident: &IdentifierReference<'a>) {
+        if ident.name == "eval" {
+            self.has_eval = true;
+        }
+    }
+
+    fn visit_call_expression(&mut self, call_expr: &CallExpression<'a>) {
+        if call_expr.callee.is_specific_id("eval") {
+            let span = self.function_spans.last().copied().unwrap_or(Span::new(0, u32::MAX));
+            self.opaque_spans.push(span);
+        }
+        walk::walk_call_expression(self, call_expr);
+    }
+
+    fn visit_function(&mut self, func: &Function<'a>, flags: ScopeFlags) {
+        self.function_spans.push(func.span);
+        walk::walk_function(self, func, flags);
+        self.function_spans.pop();
+    }
+
+    fn visit_arrow_function_expression(&mut self, arrow: &ArrowFunctionExpression<'a>) {
+        self.function_spans.push(arrow.span);
+        walk::walk_arrow_function_expression(self, arrow);
+        self.function_spans.pop();
+    }
+
+    fn visit_with_statement(&mut self, stmt: &WithStatement<'a>) {
+        self.opaque_spans.push(stmt.body.span());
+        walk::walk_with_statement(self, stmt);
+    }
+
+    fn visit_binding_identifier(&mut self, ident: &BindingIdentifier<'a>) {
+        *self.declarations.entry(ident.name.clone()).or_default() += 1;
+    }
+
+    fn visit_simple_assignment_target(&mut self, target: &SimpleAssignmentTarget<'a>) {
+        if let SimpleAssignmentTarget::AssignmentTargetIdentifier(ident) = target {
+            self.assigned.insert(ident.name.clone());
+        }
+        walk::walk_simple_assignment_target(self, target);
+    }
+
+    fn visit_assignment_target_property_identifier(
+        &mut self,
+        property: &AssignmentTargetPropertyIdentifier<'a>,
+    ) {
+        self.assigned.insert(property.binding.name.clone());
+        walk::walk_assignment_target_property_identifier(self, property);
+    }
+}
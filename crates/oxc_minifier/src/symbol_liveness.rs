@@ -0,0 +1,314 @@
+use std::path::PathBuf;
+
+#[allow(clippy::wildcard_imports)]
+use oxc_ast::{ast::*, syntax_directed_operations::BoundNames};
+use oxc_semantic::{Semantic, SemanticBuilder, SymbolId};
+use oxc_span::{CompactStr, GetSpan, Span};
+use oxc_syntax::module_record::{ExportExportName, ExportImportName, ImportImportName};
+use rustc_hash::FxHashMap;
+
+use crate::{ast_passes::DeclarationEffects, PureGetters};
+
+/// The top level symbols of a module which survived compression, and which symbols each of them
+/// uses, for bundlers which tree shake across chunks.
+///
+/// A bundler minifies every chunk, marks the symbols which are live from the exports other chunks
+/// import, and minifies again with the exports no chunk imports as
+/// [`CompressOptions::unused_exports`](crate::CompressOptions::unused_exports). Imported symbols
+/// record the module and name they come from, so the live imports of a chunk tell which exports
+/// of other chunks are used.
+///
+/// A symbol is a root when code outside of the top level declarations references it, or its
+/// declaration may have side effects, so it is live whatever the module exports. Every symbol is
+/// a root when the module calls `eval`. Names are the names before mangling.
+///
+/// Re-exports, `export { a as b } from "m"` and `export * from "m"`, have no binding in the
+/// module. They are recorded after the top level symbols as roots named after their exported
+/// name, or `"*"`, which import from the module, so the exports they forward stay live.
+#[derive(Debug, Default, Clone)]
+pub struct SymbolLiveness {
+    symbols: Vec<LiveSymbol>,
+}
+
+/// A top level symbol of [`SymbolLiveness`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LiveSymbol {
+    pub name: CompactStr,
+    /// Names this symbol is exported as, `"default"` for the default export.
+    pub exports: Vec<CompactStr>,
+    /// The module specifier and imported name of an imported symbol, `"default"` for default
+    /// imports and `"*"` for namespace imports.
+    pub import: Option<(CompactStr, CompactStr)>,
+    /// See [`SymbolLiveness`].
+    pub is_root: bool,
+    /// Indices of the symbols which the declaration of this symbol references.
+    pub dependencies: Vec<usize>,
+}
+
+impl SymbolLiveness {
+    pub fn new(program: &Program, pure_getters: PureGetters) -> Self {
+        let semantic = SemanticBuilder::new("", program.source_type)
+            .build_module_record(PathBuf::new(), program)
+            .build(program)
+            .semantic;
+        Builder::new(&semantic, pure_getters).build(program)
+    }
+
+    /// Top level symbols, in the order of their declarations.
+    pub fn symbols(&self) -> &[LiveSymbol] {
+        &self.symbols
+    }
+
+    /// Index of the symbol named `name`.
+    pub fn find(&self, name: &str) -> Option<usize> {
+        self.symbols.iter().position(|symbol| symbol.name == name)
+    }
+
+    /// Which symbols are live, by index, when other modules import the exports for which
+    /// `is_used_export` returns `true`. Roots, used exports and the dependencies of live symbols
+    /// are live.
+    pub fn live<F: Fn(&str) -> bool>(&self, is_used_export: F) -> Vec<bool> {
+        let mut live = vec![false; self.symbols.len()];
+        let mut stack = self
+            .symbols
+            .iter()
+            .enumerate()
+            .filter(|(_, symbol)| {
+                symbol.is_root || symbol.exports.iter().any(|name| is_used_export(name))
+            })
+            .map(|(index, _)| index)
+            .collect::<Vec<_>>();
+        while let Some(index) = stack.pop() {
+            if !std::mem::replace(&mut live[index], true) {
+                stack.extend(self.symbols[index].dependencies.iter().filter(|&&i| !live[i]));
+            }
+        }
+        live
+    }
+
+    /// Names exported by the module, in the order of the symbols.
+    pub fn exports(&self) -> impl Iterator<Item = &str> {
+        self.symbols.iter().flat_map(|symbol| symbol.exports.iter().map(CompactStr::as_str))
+    }
+
+    /// The imports of live symbols, as pairs of module specifier and imported name, see
+    /// [`SymbolLiveness::live`].
+    pub fn live_imports<F: Fn(&str) -> bool>(&self, is_used_export: F) -> Vec<(&str, &str)> {
+        self.symbols
+            .iter()
+            .zip(self.live(is_used_export))
+            .filter(|(_, live)| *live)
+            .filter_map(|(symbol, _)| symbol.import.as_ref())
+            .map(|(source, name)| (source.as_str(), name.as_str()))
+            .collect()
+    }
+}
+
+struct Builder<'s, 'a> {
+    semantic: &'s Semantic<'a>,
+    effects: DeclarationEffects<'s, 'a>,
+    indices: FxHashMap<SymbolId, usize>,
+    symbols: Vec<LiveSymbol>,
+    /// Top level declarations, with the indices of the symbols they declare.
+    declarations: Vec<(Span, Vec<usize>)>,
+    /// `export { a }` and `export default a`, whose references do not make symbols roots.
+    exports: Vec<Span>,
+    /// Re-exports, which follow the top level symbols.
+    re_exports: Vec<LiveSymbol>,
+}
+
+impl<'s, 'a> Builder<'s, 'a> {
+    fn new(semantic: &'s Semantic<'a>, pure_getters: PureGetters) -> Self {
+        Self {
+            semantic,
+            effects: DeclarationEffects::new(semantic, pure_getters),
+            indices: FxHashMap::default(),
+            symbols: vec![],
+            declarations: vec![],
+            exports: vec![],
+            re_exports: vec![],
+        }
+    }
+
+    fn build(mut self, program: &Program<'a>) -> SymbolLiveness {
+        let scopes = self.semantic.scopes();
+        let symbols = self.semantic.symbols();
+        let mut symbol_ids =
+            scopes.get_bindings(scopes.root_scope_id()).values().copied().collect::<Vec<_>>();
+        symbol_ids.sort_unstable_by_key(|&symbol_id| symbols.get_span(symbol_id).start);
+        for symbol_id in symbol_ids {
+            self.indices.insert(symbol_id, self.symbols.len());
+            self.symbols.push(LiveSymbol {
+                name: symbols.get_name(symbol_id).into(),
+                exports: vec![],
+                import: None,
+                is_root: false,
+                dependencies: vec![],
+            });
+        }
+        self.add_module_record();
+        for stmt in &program.body {
+            self.add_statement(stmt);
+        }
+        let has_eval = scopes.root_unresolved_references().contains_key("eval");
+        let indices = self.indices.iter().map(|(&id, &index)| (id, index)).collect::<Vec<_>>();
+        for (symbol_id, index) in indices {
+            if has_eval {
+                self.symbols[index].is_root = true;
+            }
+            for reference in symbols.get_resolved_references(symbol_id) {
+                let span = self.semantic.nodes().get_node(reference.node_id()).kind().span();
+                self.add_reference(index, span);
+            }
+        }
+        for symbol in &mut self.symbols {
+            symbol.dependencies.sort_unstable();
+            symbol.dependencies.dedup();
+        }
+        self.symbols.append(&mut self.re_exports);
+        SymbolLiveness { symbols: self.symbols }
+    }
+
+    fn add_module_record(&mut self) {
+        let module_record = self.semantic.module_record();
+        for entry in &module_record.local_export_entries {
+            let exported = match &entry.export_name {
+                ExportExportName::Name(name) => name.name().clone(),
+                ExportExportName::Default(_) => CompactStr::from("default"),
+                ExportExportName::Null => continue,
+            };
+            if let Some(index) = entry.local_name.name().and_then(|name| self.find(name)) {
+                self.symbols[index].exports.push(exported);
+            }
+        }
+        for entry in &module_record.import_entries {
+            let Some(index) = self.find(entry.local_name.name()) else { continue };
+            let imported = match &entry.import_name {
+                ImportImportName::Name(name) => name.name().clone(),
+                ImportImportName::NamespaceObject => CompactStr::from("*"),
+                ImportImportName::Default(_) => CompactStr::from("default"),
+            };
+            self.symbols[index].import = Some((entry.module_request.name().clone(), imported));
+        }
+        let re_exports =
+            module_record.indirect_export_entries.iter().chain(&module_record.star_export_entries);
+        for entry in re_exports {
+            let Some(module_request) = &entry.module_request else { continue };
+            let imported = match &entry.import_name {
+                ExportImportName::Name(name) => name.name().clone(),
+                ExportImportName::All | ExportImportName::AllButDefault => CompactStr::from("*"),
+                ExportImportName::Null => continue,
+            };
+            let name = match &entry.export_name {
+                ExportExportName::Name(name) => name.name().clone(),
+                ExportExportName::Default(_) => CompactStr::from("default"),
+                ExportExportName::Null => CompactStr::from("*"),
+            };
+            self.re_exports.push(LiveSymbol {
+                name,
+                exports: vec![],
+                import: Some((module_request.name().clone(), imported)),
+                is_root: true,
+                dependencies: vec![],
+            });
+        }
+    }
+
+    fn find(&self, name: &str) -> Option<usize> {
+        let scopes = self.semantic.scopes();
+        scopes
+            .get_binding(scopes.root_scope_id(), name)
+            .and_then(|id| self.indices.get(&id).copied())
+    }
+
+    fn add_statement(&mut self, stmt: &Statement<'a>) {
+        match stmt {
+            Statement::ExportNamedDeclaration(decl) => match &decl.declaration {
+                Some(declaration) => self.add_declarations(declaration),
+                None if decl.source.is_none() => self.exports.push(decl.span),
+                None => {}
+            },
+            Statement::ExportDefaultDeclaration(decl) => match &decl.declaration {
+                ExportDefaultDeclarationKind::FunctionDeclaration(func) => {
+                    self.add_declaration(func.span, &**func, false);
+                }
+                ExportDefaultDeclarationKind::ClassDeclaration(class) => {
+                    let is_root = self.effects.class_may_have_side_effects(class);
+                    self.add_declaration(class.span, &**class, is_root);
+                }
+                ExportDefaultDeclarationKind::Identifier(_) => self.exports.push(decl.span),
+                _ => {}
+            },
+            _ => {
+                if let Some(declaration) = stmt.as_declaration() {
+                    self.add_declarations(declaration);
+                }
+            }
+        }
+    }
+
+    fn add_declarations(&mut self, declaration: &Declaration<'a>) {
+        match declaration {
+            Declaration::FunctionDeclaration(func) => {
+                self.add_declaration(func.span, &**func, false);
+            }
+            Declaration::ClassDeclaration(class) => {
+                let is_root = self.effects.class_may_have_side_effects(class);
+                self.add_declaration(class.span, &**class, is_root);
+            }
+            Declaration::VariableDeclaration(decl) => {
+                for declarator in &decl.declarations {
+                    // Destructuring may call getters and iterators.
+                    let is_root = !declarator.id.kind.is_binding_identifier()
+                        || declarator
+                            .init
+                            .as_ref()
+                            .is_some_and(|init| self.effects.may_have_side_effects(init));
+                    self.add_declaration(declarator.span, &declarator.id, is_root);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn add_declaration<T: BoundNames<'a>>(&mut self, span: Span, declaration: &T, is_root: bool) {
+        let mut indices = vec![];
+        declaration.bound_names(&mut |ident| {
+            if let Some(index) = ident.symbol_id.get().and_then(|id| self.indices.get(&id)) {
+                indices.push(*index);
+            }
+        });
+        for &index in &indices {
+            self.symbols[index].is_root |= is_root;
+        }
+        self.declarations.push((span, indices));
+    }
+
+    /// Records a reference at `span` to the symbol at `index`, as a dependency of the
+    /// declarations around it, or as a root if there are none.
+    fn add_reference(&mut self, index: usize, span: Span) {
+        // Generated references can not be told apart from the code around them.
+        if span.is_unspanned() {
+            self.symbols[index].is_root = true;
+            return;
+        }
+        let contains = |outer: &Span| outer.start <= span.start && span.end <= outer.end;
+        if self.exports.iter().any(contains) {
+            return;
+        }
+        let mut is_declared = false;
+        for (declaration, indices) in &self.declarations {
+            if contains(declaration) {
+                is_declared = true;
+                for &dependent in indices {
+                    if dependent != index {
+                        self.symbols[dependent].dependencies.push(index);
+                    }
+                }
+            }
+        }
+        if !is_declared {
+            self.symbols[index].is_root = true;
+        }
+    }
+}
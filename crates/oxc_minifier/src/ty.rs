@@ -25,7 +25,7 @@ impl<'a> From<&Expression<'a>> for Ty {
             Expression::BooleanLiteral(_) => Self::Boolean,
             Expression::NullLiteral(_) => Self::Null,
             Expression::NumericLiteral(_) => Self::Number,
-            Expression::StringLiteral(_) => Self::Str,
+            Expression::StringLiteral(_) | Expression::TemplateLiteral(_) => Self::Str,
             Expression::ObjectExpression(_)
             | Expression::ArrayExpression(_)
             | Expression::RegExpLiteral(_)
@@ -67,6 +67,20 @@ impl<'a> From<&Expression<'a>> for Ty {
 
                     Self::Undetermined
                 }
+                op if op.is_equality() || op.is_compare() || op.is_relational() => Self::Boolean,
+                _ => Self::Undetermined,
+            },
+            // Calls of the global `String`, `Number` and `Boolean` are typed by
+            // `StaticBindings::ty`, which knows whether the names are shadowed.
+            Expression::CallExpression(call_expr) => match &call_expr.callee {
+                Expression::StaticMemberExpression(member_expr)
+                    if matches!(
+                        member_expr.property.name.as_str(),
+                        "toExponential" | "toFixed" | "toLocaleString" | "toPrecision" | "toString"
+                    ) && Self::from(&member_expr.object) == Self::Number =>
+                {
+                    Self::Str
+                }
                 _ => Self::Undetermined,
             },
             _ => Self::Undetermined,
@@ -0,0 +1,81 @@
+use oxc_allocator::Allocator;
+#[allow(clippy::wildcard_imports)]
+use oxc_ast::{ast::*, syntax_directed_operations::BoundNames};
+use oxc_diagnostics::OxcDiagnostic;
+use oxc_parser::Parser;
+use oxc_span::{CompactStr, SourceType};
+
+/// Checks the emitted code of a minified program, see [`crate::MinifierOptions::validate_output`].
+///
+/// Created before the program is minified, so the exports of the input can be compared with
+/// the exports of the output.
+pub struct OutputValidator {
+    source_type: SourceType,
+    /// Sorted exported names of the input, if exports are compared.
+    exports: Option<Vec<CompactStr>>,
+}
+
+impl OutputValidator {
+    pub fn new(program: &Program, validate_exports: bool) -> Self {
+        Self {
+            source_type: program.source_type,
+            exports: validate_exports.then(|| export_names(program)),
+        }
+    }
+
+    /// Re-parses `output`, which is the code generated from the minified program.
+    ///
+    /// # Errors
+    ///
+    /// * `output` has a syntax error. The labels of the diagnostic point into `output`.
+    /// * `output` exports different names than the input, when exports are compared.
+    pub fn validate(&self, output: &str) -> Result<(), OxcDiagnostic> {
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, output, self.source_type).parse();
+        if let Some(error) = ret.errors.into_iter().next() {
+            return Err(OxcDiagnostic::error(format!(
+                "Minified output is not valid: {}",
+                error.message
+            ))
+            .with_labels(error.labels.clone().unwrap_or_default())
+            .with_help("This is a bug in the minifier, please report it."));
+        }
+        let Some(expected) = &self.exports else { return Ok(()) };
+        let actual = export_names(&ret.program);
+        if &actual == expected {
+            return Ok(());
+        }
+        let missing = expected.iter().filter(|name| !actual.contains(name)).collect::<Vec<_>>();
+        let added = actual.iter().filter(|name| !expected.contains(name)).collect::<Vec<_>>();
+        Err(OxcDiagnostic::error("Minified output exports different names than the input.")
+            .with_help(format!("Missing exports: {missing:?}, added exports: {added:?}")))
+    }
+}
+
+/// Exported names of a module, sorted. `export * from "a"` is recorded as `* from "a"`, since
+/// the names it exports are only known after resolving `"a"`.
+fn export_names(program: &Program) -> Vec<CompactStr> {
+    let mut names = vec![];
+    for stmt in &program.body {
+        match stmt {
+            Statement::ExportNamedDeclaration(decl) => {
+                if let Some(declaration) = &decl.declaration {
+                    declaration.bound_names(&mut |ident| names.push(ident.name.to_compact_str()));
+                }
+                names.extend(
+                    decl.specifiers
+                        .iter()
+                        .map(|specifier| specifier.exported.name().into_compact_str()),
+                );
+            }
+            Statement::ExportDefaultDeclaration(_) => names.push(CompactStr::new("default")),
+            Statement::ExportAllDeclaration(decl) => match &decl.exported {
+                Some(exported) => names.push(exported.name().into_compact_str()),
+                None => names.push(CompactStr::from(format!("* from {:?}", decl.source.value))),
+            },
+            _ => {}
+        }
+    }
+    names.sort_unstable_by(|a, b| a.as_str().cmp(b.as_str()));
+    names
+}
@@ -6,10 +6,10 @@ use crate::test;
 fn fold_return_result() {
     test("function f(){return !1;}", "function f(){return !1}");
     test("function f(){return null;}", "function f(){return null}");
-    test("function f(){return void 0;}", "function f(){return}");
+    test("function f(){return void 0;}", "function f(){}");
     test("function f(){return void foo();}", "function f(){return void foo()}");
-    test("function f(){return undefined;}", "function f(){return}");
-    test("function f(){if(a()){return undefined;}}", "function f(){if(a())return}");
+    test("function f(){return undefined;}", "function f(){}");
+    test("function f(){if(a()){return undefined;}}", "function f(){a()}");
 }
 
 #[test]
@@ -7,11 +7,14 @@ use oxc_parser::Parser;
 use oxc_span::SourceType;
 
 fn mangle(source_text: &str) -> String {
+    mangle_with(source_text, SourceType::default().with_module(true), false)
+}
+
+fn mangle_with(source_text: &str, source_type: SourceType, top_level: bool) -> String {
     let allocator = Allocator::default();
-    let source_type = SourceType::default().with_module(true);
     let ret = Parser::new(&allocator, source_text, source_type).parse();
     let program = ret.program;
-    let mangler = ManglerBuilder::default().build(&program);
+    let mangler = ManglerBuilder::default().top_level(top_level).build(&program);
     CodeGenerator::new().with_mangler(Some(mangler)).build(&program).source_text
 }
 
@@ -33,3 +36,93 @@ fn mangler() {
         insta::assert_snapshot!("mangler", snapshot);
     });
 }
+
+#[test]
+fn top_level() {
+    let script = SourceType::default();
+    let module = SourceType::default().with_module(true);
+    let cases = [
+        // Globals of scripts are kept, inner names do not shadow them.
+        ("var foo = 1; function bar(a) { return a + foo }", script, false),
+        ("var b; function foo(a, c) { return a + b + c }", script, false),
+        ("(function () { var foo = 1; return foo })()", script, false),
+        ("var foo = 1; function bar(a) { return a + foo }", script, true),
+        ("let foo = 1; function bar(a) { return a + foo }", module, false),
+    ];
+
+    let snapshot =
+        cases.into_iter().fold(String::new(), |mut w, (case, source_type, top_level)| {
+            let kind = if source_type.is_module() { "module" } else { "script" };
+            write!(
+                w,
+                "{case} ({kind}, top_level: {top_level})\n{}\n",
+                mangle_with(case, source_type, top_level)
+            )
+            .unwrap();
+            w
+        });
+
+    insta::with_settings!({ prepend_module_to_snapshot => false, omit_expression => true }, {
+        insta::assert_snapshot!("top_level", snapshot);
+    });
+}
+
+#[test]
+fn mangled_names_skip_reserved_words() {
+    let words = ["do", "if", "in", "of", "as", "is", "for", "let", "new", "try", "var", "NaN"];
+    // All names of up to three characters.
+    for name in oxc_mangler::mangled_names().take_while(|name| name.len() <= 3) {
+        assert!(!words.contains(&name.as_str()), "{name}");
+    }
+    for word in [
+        "case",
+        "else",
+        "enum",
+        "eval",
+        "null",
+        "this",
+        "true",
+        "void",
+        "with",
+        "await",
+        "yield",
+        "static",
+        "arguments",
+        "instanceof",
+        "undefined",
+        "Infinity",
+        "globalThis",
+        "implements",
+    ] {
+        assert!(oxc_mangler::is_reserved_word(word), "{word}");
+    }
+}
+
+#[test]
+fn reserved_and_globals() {
+    let allocator = Allocator::default();
+    // Enough symbols to reach `NaN`, the last of the avoided names.
+    let count = 150_000;
+    let names = (0..count).map(|i| format!("v{i}")).collect::<Vec<_>>().join(",");
+    let source_text =
+        format!("function f($, ...rest) {{ var {names}; return [$, rest, {names}] }}");
+    let source_type = SourceType::default().with_module(true);
+    let ret = Parser::new(&allocator, &source_text, source_type).parse();
+    let program = ret.program;
+    let mangler = ManglerBuilder::default()
+        .reserved(["$".into(), "b".into(), "ab".into()])
+        .globals(["c".into(), "URL".into(), "Map".into()])
+        .build(&program);
+    let output = CodeGenerator::new().with_mangler(Some(mangler)).build(&program).source_text;
+    let (params, body) = output.split_once(") {").unwrap();
+    // Reserved names are kept.
+    assert!(params.ends_with("($, ...a"), "{params}");
+    let declared = body.split_once(';').unwrap().0.trim().strip_prefix("var ").unwrap();
+    let declared = declared.split(", ").collect::<Vec<_>>();
+    assert_eq!(declared.len(), count);
+    for name in ["b", "ab", "c", "URL", "Map", "do", "if", "in", "for", "let", "new", "NaN"] {
+        assert!(!declared.contains(&name), "{name}");
+    }
+    let ret = Parser::new(&allocator, &output, source_type).parse();
+    assert!(ret.errors.is_empty());
+}
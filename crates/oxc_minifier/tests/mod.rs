@@ -4,6 +4,8 @@ mod oxc;
 // mod tdewolff;
 // mod terser;
 
+use std::fmt::Write;
+
 use oxc_allocator::Allocator;
 use oxc_codegen::{CodeGenerator, CodegenOptions};
 use oxc_minifier::{CompressOptions, Compressor};
@@ -41,11 +43,37 @@ pub(crate) fn run(
         Compressor::new(&allocator, options).build(program);
     }
     CodeGenerator::new()
-        .with_options(CodegenOptions { single_quote: true })
+        .with_options(CodegenOptions { single_quote: true, ..CodegenOptions::default() })
         .build(program)
         .source_text
 }
 
+/// `source_text` printed after each compressor pass, so a change of the output can be attributed
+/// to the pass which made it. Passes which leave the program unchanged are listed without it.
+pub(crate) fn run_passes(
+    source_text: &str,
+    source_type: SourceType,
+    options: CompressOptions,
+) -> String {
+    let allocator = Allocator::default();
+    let ret = Parser::new(&allocator, source_text, source_type).parse();
+    let program = allocator.alloc(ret.program);
+    let mut previous = CodeGenerator::new().build(program).source_text;
+    let ret = Compressor::new(&allocator, options).with_pass_outputs(true).build(program);
+    let mut snapshot = format!("==================== SOURCE ====================\n{previous}\n");
+    for (pass, output) in ret.passes.iter().zip(ret.pass_outputs) {
+        if output == previous {
+            writeln!(snapshot, "==================== {pass} (unchanged) ====================\n")
+                .unwrap();
+        } else {
+            writeln!(snapshot, "==================== {pass} ====================\n{output}")
+                .unwrap();
+            previous = output;
+        }
+    }
+    snapshot
+}
+
 pub(crate) fn test_snapshot<S>(name: &str, sources: S)
 where
     S: IntoIterator<Item = &'static str>,
@@ -55,7 +83,7 @@ where
     let snapshot: String = sources
         .into_iter()
         .map(|source| {
-            let minified = run(source, source_type, Some(options));
+            let minified = run(source, source_type, Some(options.clone()));
             format!(
                 "==================================== SOURCE ====================================
 {source}
@@ -0,0 +1,94 @@
+use oxc_minifier::Profile;
+
+use crate::{test_with_options, CompressOptions};
+
+fn test(source_text: &str, expected: &str) {
+    let options = CompressOptions { profile: Profile::Speed, ..CompressOptions::default() };
+    test_with_options(source_text, expected, options);
+}
+
+fn test_same(source_text: &str) {
+    test(source_text, source_text);
+}
+
+#[test]
+fn callbacks() {
+    test(
+        "function f() { const a = [1, 2]; a.forEach((x) => g(x)) }",
+        "function f() { const a = [1, 2]; for (let _i = 0; _i < a.length; _i++) { let x = a[_i]; g(x) } }",
+    );
+    test(
+        "function f() { const a = [1, 2]; a.map((x, i) => { if (x) return; g(x, i) }) }",
+        "function f() { const a = [1, 2]; for (let _i = 0; _i < a.length; _i++) { let x = a[_i], i = _i; if (x) continue; g(x, i) } }",
+    );
+    test(
+        "function f() { const a = [...b]; a.forEach(() => g()) }",
+        "function f() { const a = [...b]; for (let _i = 0; _i < a.length; _i++) g() }",
+    );
+    // The index does not shadow other names.
+    test(
+        "function f(_i) { const a = [1]; a.forEach((x) => g(x, _i)) }",
+        "function f(_i) { const a = [1]; for (let _i2 = 0; _i2 < a.length; _i2++) { let x = a[_i2]; g(x, _i) } }",
+    );
+    // The result of `map` is used, or the callback may receive the array.
+    test_same("function f() { const a = [1]; return a.map((x) => x) }");
+    test_same("function f() { const a = [1]; a.forEach(g) }");
+    test_same("function f() { const a = [1]; a.forEach((x, i, array) => g(array)) }");
+    test_same("function f() { const a = [1]; a.forEach((...args) => g(args)) }");
+    test_same("function f() { const a = [1]; a.forEach((x) => g(x), thisArg) }");
+    // The callback can not be inlined.
+    test_same("function f() { const a = [1]; a.forEach(async (x) => await g(x)) }");
+    test_same("function f() { const a = [1]; a.forEach(({ x }) => g(x)) }");
+    test_same("function f() { const a = [1]; a.forEach((x) => { return g(x) }) }");
+    test_same("function f() { const a = [1]; a.forEach((x) => { for (;;) return }) }");
+    test_same("function f() { const a = [1]; a.forEach((x) => { var y = x; g(y) }) }");
+    test_same("function f() { const a = [1]; a.forEach((x) => { function g() {} g(x) }) }");
+}
+
+#[test]
+fn for_of() {
+    test(
+        "function f() { const a = [1, 2]; for (const x of a) { if (x) break; g(x) } }",
+        "function f() { const a = [1, 2]; for (let _i = 0; _i < a.length; _i++) { const x = a[_i]; if (x) break; g(x) } }",
+    );
+    test(
+        "function f() { const a = [[1, 2]]; for (const [x, y] of a) g(x, y) }",
+        "function f() { const a = [[1, 2]]; for (let _i = 0; _i < a.length; _i++) { const [x, y] = a[_i]; g(x, y) } }",
+    );
+    test(
+        "function f() { let x; const a = [1]; for (x of a) g(x) }",
+        "function f() { let x; const a = [1]; for (let _i = 0; _i < a.length; _i++) { x = a[_i]; g(x) } }",
+    );
+    test_same("async function f() { const a = [1]; for await (const x of a) g(x) }");
+    test_same("function f() { const a = [1]; for (const x of a) { let x = 1; g(x) } }");
+    test_same("function f() { const a = [1]; for (x.y of a) g(x) }");
+}
+
+#[test]
+fn escaping_arrays() {
+    // Modified, passed on or not an array literal without holes.
+    test_same("function f() { const a = [1]; a.push(2); for (const x of a) g(x) }");
+    test_same("function f() { const a = [1]; a[0] = 2; for (const x of a) g(x) }");
+    test_same("function f() { const a = [1]; delete a[0]; for (const x of a) g(x) }");
+    test_same("function f() { const a = [1]; a[0](); for (const x of a) g(x) }");
+    test_same("function f() { const a = [1]; g(a); for (const x of a) g(x) }");
+    test_same("function f() { const a = [1, , 2]; for (const x of a) g(x) }");
+    test_same("function f() { let a = [1]; for (const x of a) g(x) }");
+    test_same("function f() { const a = b; for (const x of a) g(x) }");
+    // Shadowed, or visible to other code.
+    test_same("function f() { const a = [1]; for (const x of a) g(x) } function h(a) {}");
+    test_same("const a = [1]; for (const x of a) g(x)");
+    test_same("function f() { const a = [1]; for (const x of a) g(x); eval('') }");
+    // Reads are fine.
+    test(
+        "function f() { const a = [1]; g(a.length, a[0]); for (const x of a) g(x) }",
+        "function f() { const a = [1]; g(a.length, a[0]); for (let _i = 0; _i < a.length; _i++) { const x = a[_i]; g(x) } }",
+    );
+}
+
+#[test]
+fn size_profile() {
+    let options = CompressOptions::default();
+    let source_text = "function f() { const a = [1]; a.forEach((x) => g(x)) }";
+    test_with_options(source_text, source_text, options);
+}
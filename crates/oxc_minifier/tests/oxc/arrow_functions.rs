@@ -0,0 +1,76 @@
+use oxc_minifier::ESTarget;
+
+use crate::{test_with_options, CompressOptions};
+
+fn test(source_text: &str, expected: &str) {
+    let options = CompressOptions { arrow_functions: true, ..CompressOptions::all_true() };
+    test_with_options(source_text, expected, options);
+}
+
+fn test_same(source_text: &str) {
+    test(source_text, source_text);
+}
+
+#[test]
+fn arrow_functions() {
+    test("f(function () { g() })", "f(() => { g() })");
+    test("f(function (a, b) { return a + b })", "f((a, b) => a + b)");
+    test("f(function () { return { a: 1 } })", "f(() => ({ a: 1 }))");
+    test("f(async function (a) { await a })", "f(async (a) => { await a })");
+    test("x = function (a) { return function () { return a } }", "x = (a) => () => a");
+    // The arrow function has the same name.
+    test("var f = function () { return 1 }", "var f = () => 1");
+    // `this` of nested functions is their own.
+    test(
+        "f(function () { return function () { return this } })",
+        "f(() => function () { return this })",
+    );
+    test_with_options(
+        "f(function () { g() })",
+        "f(function () { g() })",
+        CompressOptions { arrow_functions: false, ..CompressOptions::all_true() },
+    );
+    test_with_options(
+        "f(function () { g() })",
+        "f(function () { g() })",
+        CompressOptions {
+            arrow_functions: true,
+            target: ESTarget::ES5,
+            ..CompressOptions::all_true()
+        },
+    );
+}
+
+#[test]
+fn function_scope() {
+    test_same("f(function () { return this })");
+    test_same("f(function () { return () => this })");
+    test_same("f(function () { return arguments })");
+    test_same("f(function () { return new.target })");
+    test_same("f(function () { return eval('this') })");
+    test_same("f(function* () {})");
+    test_same("f(function g() { return g })");
+    test_same("f(function (a, a) { return a })");
+}
+
+#[test]
+fn constructors() {
+    test_same("new (function () {})()");
+    test_same("x = function () {}.prototype");
+    test_same("var F = function () {}; new F()");
+    test_same("var F = function () {}; F.prototype.a = 1");
+    test_same("F = function () {}; x = a instanceof F");
+    test_same("var F = function () {}; class G extends F {}");
+}
+
+#[test]
+fn methods() {
+    let options =
+        CompressOptions { arrow_functions: true, shorthand: true, ..CompressOptions::all_true() };
+    test_with_options("x = { f: function () { g() } }", "x = { f() { g() } }", options.clone());
+    test_with_options(
+        "x = { f: function () { g() } }",
+        "x = { f: () => { g() } }",
+        CompressOptions { shorthand: false, ..options },
+    );
+}
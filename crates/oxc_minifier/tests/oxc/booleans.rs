@@ -1,4 +1,6 @@
-use crate::test_same;
+use oxc_minifier::CompressOptions;
+
+use crate::{test, test_same, test_with_options};
 
 #[test]
 fn cjs() {
@@ -13,3 +15,50 @@ fn cjs() {
         });",
     );
 }
+
+#[test]
+fn literals() {
+    test("x = true", "x = !0");
+    test("x = false", "x = !1");
+    test_with_options(
+        "x = true",
+        "x = true",
+        CompressOptions { booleans: false, ..CompressOptions::all_true() },
+    );
+}
+
+#[test]
+fn boolean_context() {
+    test("if (!!a) b()", "if (a) b()");
+    test("while (!!a) b()", "for (;a;) b()");
+    test("x = !!a ? b : c", "x = a ? b : c");
+    test("x = !!!a", "x = !a");
+    test("if (!!a && !!b) c()", "if (a && b) c()");
+    test_same("x = !!a");
+    test_same("x = !!a && b");
+    test_same("x = !!a ?? b");
+}
+
+#[test]
+fn comparisons() {
+    test("x = a == true", "x = a == 1");
+    test("x = a != false", "x = a != 0");
+    test("x = a === true", "x = a === !0");
+    test("x = !a === true", "x = !a");
+    test("x = !a == false", "x = !!a");
+    test("x = a < b !== true", "x = !(a < b)");
+    test("x = true === a instanceof b", "x = a instanceof b");
+    // A local `Boolean` may return anything.
+    test("var Boolean = f; x = Boolean(a) === true", "var Boolean = f; x = Boolean(a) === !0");
+}
+
+#[test]
+fn boolean_calls() {
+    test("x = Boolean(a)", "x = !!a");
+    test("x = Boolean()", "x = !1");
+    test("if (Boolean(a)) b()", "if (a) b()");
+    test_same("x = Boolean(a, b)");
+    test_same("x = Boolean(...a)");
+    test_same("var Boolean; x = Boolean(a)");
+    test_same("with (a) x = Boolean(b)");
+}
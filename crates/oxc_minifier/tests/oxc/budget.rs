@@ -0,0 +1,76 @@
+use std::time::Duration;
+
+use oxc_allocator::Allocator;
+use oxc_codegen::{CodeGenerator, CodegenOptions};
+use oxc_minifier::{Budget, CompressOptions, Compressor};
+use oxc_parser::Parser;
+use oxc_span::SourceType;
+
+const SOURCE_TEXT: &str = "a.map((item) => item.id); b.map((item) => item.id)";
+
+fn compress(budget: Option<Budget>) -> (String, Vec<&'static str>) {
+    compress_source(SOURCE_TEXT, budget)
+}
+
+fn compress_source(source_text: &str, budget: Option<Budget>) -> (String, Vec<&'static str>) {
+    let allocator = Allocator::default();
+    let source_type = SourceType::default().with_module(true);
+    let ret = Parser::new(&allocator, source_text, source_type).parse();
+    let program = allocator.alloc(ret.program);
+    let options = CompressOptions { dedupe_functions: true, budget, ..CompressOptions::default() };
+    let passes = Compressor::new(&allocator, options).build(program).passes;
+    let code = CodeGenerator::new()
+        .with_options(CodegenOptions { single_quote: true, ..CodegenOptions::default() })
+        .build(program)
+        .source_text;
+    (code, passes)
+}
+
+const FIRST_ROUND: [&str; 5] = [
+    "remove_syntax",
+    "fold_constants",
+    "remove_dead_code",
+    "substitute_alternate_syntax",
+    "collapse",
+];
+
+#[test]
+fn no_budget() {
+    let (code, passes) = compress(None);
+    assert!(code.starts_with("const _fn"), "{code}");
+    assert_eq!(passes, [&FIRST_ROUND[..], &["dedupe_functions"]].concat());
+}
+
+#[test]
+fn time_budget() {
+    let (code, passes) = compress(Some(Budget::Time(Duration::ZERO)));
+    assert!(!code.contains("_fn"), "{code}");
+    assert_eq!(passes, FIRST_ROUND);
+
+    let (code, passes) = compress(Some(Budget::Time(Duration::MAX)));
+    assert!(code.starts_with("const _fn"), "{code}");
+    // The first round does not change the program, so there is nothing to run again.
+    assert_eq!(passes, [&FIRST_ROUND[..], &["dedupe_functions"]].concat());
+}
+
+#[test]
+fn size_budget() {
+    let (_, passes) = compress(Some(Budget::Size(SOURCE_TEXT.len())));
+    assert_eq!(passes, FIRST_ROUND);
+
+    let (code, passes) = compress(Some(Budget::Size(0)));
+    assert!(code.starts_with("const _fn"), "{code}");
+    assert_eq!(passes, [&FIRST_ROUND[..], &["dedupe_functions"]].concat());
+}
+
+#[test]
+fn stale_passes() {
+    // Only the passes before `substitute_alternate_syntax` may have more to do once `undefined`
+    // is substituted, and they find nothing.
+    let (code, passes) = compress_source("var a; var b; x(undefined)", Some(Budget::Size(0)));
+    assert_eq!(code, "var a, b;\nx(void 0);\n");
+    assert_eq!(
+        passes,
+        [&FIRST_ROUND[..], &["fold_constants", "remove_dead_code", "dedupe_functions"]].concat()
+    );
+}
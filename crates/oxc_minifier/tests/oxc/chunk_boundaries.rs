@@ -0,0 +1,46 @@
+use oxc_minifier::CompressOptions;
+
+use crate::{test, test_with_options};
+
+fn test_keep(source_text: &str, expected: &str) {
+    let options = CompressOptions { keep_chunk_boundaries: true, ..CompressOptions::default() };
+    test_with_options(source_text, expected, options);
+}
+
+fn test_keep_same(source_text: &str) {
+    test_keep(source_text, source_text);
+}
+
+#[test]
+fn dynamic_import() {
+    test_keep_same("const load = () => import('./page').then((m) => m['default'])");
+    test_keep_same("const load = function () { return import('./page' + '.js') }");
+    test_keep_same("routes.push({ load: () => import('./page').catch(() => void 0) })");
+    test(
+        "const load = () => import('./page').then((m) => m['default'])",
+        "const load = () => import('./page').then((m) => m.default)",
+    );
+    // Code around the boundary is still compressed.
+    test_keep("if (true) { x = () => import('./' + 'page') }", "x = () => import('./' + 'page')");
+    // Functions doing more than importing are not boundaries.
+    test_keep(
+        "const load = () => { foo(); return import('./' + 'page') }",
+        "const load = () => { foo(); return import('./page') }",
+    );
+}
+
+#[test]
+fn lazy_wrappers() {
+    test_keep_same("const Page = lazy(() => import('./page'))");
+    test_keep_same("const Page = React.lazy(() => { return import('./pages/' + 'home') })");
+    test_keep_same(
+        "const Page = defineAsyncComponent(() => import('./page').then((m) => m['Page']))",
+    );
+    test(
+        "const Page = React.lazy(() => { return import('./pages/' + 'home') })",
+        "const Page = React.lazy(() => { return import('./pages/home') })",
+    );
+    // Only calls with a function argument are boundaries.
+    test_keep("lazy['x'](() => foo['bar'])", "lazy.x(() => foo.bar)");
+    test_keep("const Page = lazy(load['page'])", "const Page = lazy(load.page)");
+}
@@ -0,0 +1,58 @@
+use oxc_minifier::CompressOptions;
+use oxc_span::SourceType;
+
+use crate::{test, test_same, test_with_options};
+
+fn test_module(source_text: &str, expected: &str) {
+    let source_type = SourceType::default().with_module(true);
+    let result = crate::run(source_text, source_type, Some(CompressOptions::all_true()));
+    let expected = crate::run(expected, source_type, None);
+    assert_eq!(result, expected, "for source {source_text}");
+}
+
+#[test]
+fn default_constructors() {
+    test("x = class { constructor() {} m() {} }", "x = class { m() {} }");
+    test(
+        "x = class extends B { constructor(...args) { super(...args) } }",
+        "x = class extends B {}",
+    );
+    test("x = class extends B { constructor() { super(...arguments) } }", "x = class extends B {}");
+    // `C.length` would change.
+    test_same("x = class { constructor(a) {} }");
+    test_same("x = class { constructor() { 'use strict' } }");
+    // Not the arguments of the constructor.
+    test_same("x = class extends B { constructor() { super() } }");
+    test_same("x = class extends B { constructor(...a) { super(...b) } }");
+    test_same("x = class extends B { constructor(a, ...b) { super(...b) } }");
+    test_same("x = class extends B { constructor(...a) { super(...a, 1) } }");
+    // Throws without calling `super`.
+    test_same("x = class extends B { constructor() {} }");
+}
+
+#[test]
+fn static_blocks() {
+    test("x = class { static { 1; let a = 2; var b = 3; function f() {} } }", "x = class {}");
+    test_same("x = class { static { f() } }");
+    test_same("x = class { static { this.a = 1 } }");
+    test_same("x = class { static { let { a } = b } }");
+}
+
+#[test]
+fn class_variables() {
+    test("const C = class { m() {} }; x = new C()", "class C { m() {} } x = new C()");
+    test_module("export const C = class extends B {}", "export class C extends B {}");
+    // The class has another name.
+    test_same("const C = class D {}; x = new C()");
+    // Assigning a class declaration does not throw.
+    test_same("const C = class {}; C = 1");
+    test_same("let C = class {}; x = new C()");
+    test_same("const C = class {}; eval('C = 1')");
+
+    let options = CompressOptions { classes: false, ..CompressOptions::all_true() };
+    test_with_options(
+        "const C = class { constructor() {} static { 1 } }",
+        "const C = class { constructor() {} static { 1 } }",
+        options,
+    );
+}
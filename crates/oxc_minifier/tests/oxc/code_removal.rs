@@ -1,3 +1,5 @@
+use oxc_minifier::ConsoleMethods;
+
 use crate::{test, test_with_options, CompressOptions};
 
 #[test]
@@ -12,25 +14,231 @@ fn undefined_assignment() {
 
 #[test]
 fn undefined_return() {
-    test("function f(){return undefined;}", "function f(){return}");
-    test("function f(){return void 0;}", "function f(){return}");
+    test("function f(){return undefined;}", "function f(){}");
+    test("function f(){return void 0;}", "function f(){}");
     test("function f(){return void foo();}", "function f(){return void foo()}");
-    test("function f(){if(a()){return undefined;}}", "function f(){if(a())return}");
+    test("function f(){if(a()){return undefined;}}", "function f(){a()}");
 }
 
 #[test]
 fn console_removal() {
     let options = CompressOptions { drop_console: true, ..CompressOptions::default() };
-    test_with_options("console.log('hi')", "", options);
-    test_with_options("let x = console.error('oops')", "let x", options);
+    test_with_options("console.log('hi')", "", options.clone());
+    test_with_options("let x = console.error('oops')", "let x", options.clone());
     test_with_options(
         "function f() { return console.warn('problem') }",
-        "function f(){return}",
-        options,
+        "function f(){}",
+        options.clone(),
+    );
+    test_with_options("x = console.log(a), y", "x = void 0, y", options.clone());
+    test_with_options("console['log']('hi')", "", options.clone());
+    test_with_options("console.log.bind(console)", "console.log.bind(console)", options);
+
+    // Methods in `keep_console` are kept.
+    let options = CompressOptions {
+        drop_console: true,
+        keep_console: ConsoleMethods::Error | ConsoleMethods::Warn,
+        ..CompressOptions::default()
+    };
+    test_with_options(
+        "console.log('hi'); console.error('oops')",
+        "console.error('oops')",
+        options.clone(),
     );
+    test_with_options(
+        "console.warn('problem'); console.foo()",
+        "console.warn('problem')",
+        options.clone(),
+    );
+    test_with_options("console[method]('hi')", "console[method]('hi')", options);
+    assert_eq!(ConsoleMethods::from_method("error"), Some(ConsoleMethods::Error));
+    assert_eq!(ConsoleMethods::from_method("countReset"), Some(ConsoleMethods::CountReset));
+    assert_eq!(ConsoleMethods::from_method("foo"), None);
 
     // console isn't removed when drop_console is `false`. This is also the
     // default value.
     let options = CompressOptions::default();
     test_with_options("console.log('hi')", "console.log('hi')", options);
 }
+
+#[test]
+fn pure_funcs_removal() {
+    let options = CompressOptions {
+        pure_funcs: vec!["assert".to_string(), "Debug.log".to_string()],
+        ..CompressOptions::default()
+    };
+    test_with_options("assert(x, 'message')", "", options.clone());
+    test_with_options("Debug.log('a'); log('b')", "log('b')", options.clone());
+    test_with_options(
+        "function f() { assert(x); return 1 }",
+        "function f(){return 1}",
+        options.clone(),
+    );
+    // Arguments with side effects are kept.
+    test_with_options("assert(check(), 'message')", "check()", options.clone());
+    test_with_options("assert(a(), b())", "a(),b()", options.clone());
+    test_with_options("x = (assert(a), Debug.log(b()), y)", "x=(b(),y)", options.clone());
+    // Calls whose result is used, spread arguments and other names are kept.
+    test_with_options("x = assert(a)", "x=assert(a)", options.clone());
+    test_with_options("assert(...a)", "assert(...a)", options.clone());
+    test_with_options(
+        "Debug.warn(a); log.assert(a)",
+        "Debug.warn(a); log.assert(a)",
+        options.clone(),
+    );
+    // Names inside `with` and functions which call `eval` may refer to something else.
+    test_with_options("with (o) { assert(x) }", "with(o)assert(x)", options.clone());
+    test_with_options(
+        "function f() { assert(x); eval(s) }",
+        "function f(){assert(x);eval(s)}",
+        options.clone(),
+    );
+    test_with_options(
+        "function f() { assert(x) } eval(s)",
+        "function f(){assert(x)}eval(s)",
+        options.clone(),
+    );
+    test_with_options("with (o) { x() } assert(x)", "with(o)x()", options.clone());
+    test_with_options(
+        "function f() { assert(x) } function g() { eval(s) }",
+        "function f(){}function g(){eval(s)}",
+        options,
+    );
+}
+
+#[test]
+fn unused_generators() {
+    let options = CompressOptions { unused_generators: true, ..CompressOptions::default() };
+    test_with_options("function f() { function* g() {} g() }", "function f(){}", options.clone());
+    test_with_options(
+        "function f() { async function* g(a) { a() } g(1) }",
+        "function f(){}",
+        options.clone(),
+    );
+    test_with_options(
+        "function f() { function* g() {} g(); return g }",
+        "function f(){function*g(){}return g}",
+        options.clone(),
+    );
+    // Top level declarations of a script are observable through the global object.
+    test_with_options("function* g() {} g()", "function*g(){}", options.clone());
+    test_with_options("class C { static { function* g() {} g() } }", "class C{}", options.clone());
+    // Parameter defaults and destructuring run when the generator is called.
+    test_with_options(
+        "function f() { function* g(a = x()) {} g() }",
+        "function f(){function*g(a=x()){}g()}",
+        options.clone(),
+    );
+    test_with_options(
+        "function f() { function* g() {} g(x()) }",
+        "function f(){function*g(){}g(x())}",
+        options.clone(),
+    );
+    // Shadowed names, `eval` and `with` are left alone.
+    test_with_options(
+        "function f() { function* g() {} function h(g) { g() } g() }",
+        "function f(){function*g(){}function h(g){g()}g()}",
+        options.clone(),
+    );
+    test_with_options(
+        "function f() { function* g() {} g(); eval('') }",
+        "function f(){function*g(){}g();eval('')}",
+        options.clone(),
+    );
+    // Direct `eval` only affects the functions around it.
+    test_with_options(
+        "function f() { function* g() {} g(); function h() { eval('') } }",
+        "function f(){function*g(){}g();function h(){eval('')}}",
+        options.clone(),
+    );
+    test_with_options(
+        "function f() { function* g() {} g() } function h() { eval('') }",
+        "function f(){} function h(){eval('')}",
+        options.clone(),
+    );
+    test_with_options(
+        "function f() { function* g() {} g(); (0, eval)('') }",
+        "function f(){(0,eval)('')}",
+        options.clone(),
+    );
+    test_with_options(
+        "function f() { function* g() {} with (o) { g() } }",
+        "function f(){function*g(){}with(o)g()}",
+        options.clone(),
+    );
+    test_with_options(
+        "function f() { function* g() {} g(); with (o) { h() } }",
+        "function f(){with(o)h()}",
+        options.clone(),
+    );
+    // Names which are assigned may refer to another function.
+    test_with_options(
+        "function f() { function* g() {} g = h; g() }",
+        "function f(){function*g(){}g=h;g()}",
+        options.clone(),
+    );
+    test_with_options(
+        "function f() { function* g() {} ({ g } = o); g() }",
+        "function f(){function*g(){}({g}=o);g()}",
+        options.clone(),
+    );
+    // Regular and async functions run their body when called.
+    test_with_options(
+        "function f() { async function g() {} g() }",
+        "function f(){async function g(){}g()}",
+        options,
+    );
+
+    let options = CompressOptions::default();
+    test_with_options(
+        "function f() { function* g() {} g() }",
+        "function f(){function*g(){}g()}",
+        options,
+    );
+}
+
+#[test]
+fn unawaited_async() {
+    let options = CompressOptions { unawaited_async: true, ..CompressOptions::default() };
+    test_with_options(
+        "function f() { async function g() {} g() }",
+        "function f(){}",
+        options.clone(),
+    );
+    test_with_options(
+        "function f() { async function g(a) { await a; return 1 } g(x) }",
+        "function f(){}",
+        options.clone(),
+    );
+    // Side effects before or after an `await` are kept.
+    test_with_options(
+        "function f() { async function g() { h() } g() }",
+        "function f(){async function g(){h()}g()}",
+        options.clone(),
+    );
+    test_with_options(
+        "function f() { async function g() { await h() } g() }",
+        "function f(){async function g(){await h()}g()}",
+        options.clone(),
+    );
+    test_with_options(
+        "function f() { async function g() {} g = h; g() }",
+        "function f(){async function g(){}g=h;g()}",
+        options.clone(),
+    );
+    // Generators are covered by `unused_generators`.
+    test_with_options(
+        "function f() { function* g() {} g() }",
+        "function f(){function*g(){}g()}",
+        options,
+    );
+}
+
+#[test]
+fn with_statement() {
+    // Names inside `with` may resolve to properties of the object.
+    test("with (o) { x = undefined }", "with(o)x=undefined");
+    test("with (o) { x = 1 + NaN }", "with(o)x=1+NaN");
+    test("with (undefined) {} x = undefined", "with(void 0){}x=void 0");
+    test("with (o) { x = 1 } if (true) y()", "with(o)x=1;y()");
+}
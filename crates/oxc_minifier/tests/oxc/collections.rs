@@ -0,0 +1,47 @@
+use crate::{test_with_options, CompressOptions};
+
+fn test(source_text: &str, expected: &str) {
+    let options = CompressOptions { unsafe_collections: true, ..CompressOptions::default() };
+    test_with_options(source_text, expected, options);
+}
+
+fn test_same(source_text: &str) {
+    test(source_text, source_text);
+}
+
+#[test]
+fn set_literals() {
+    test("f(new Set(['a', 'b']).has(x))", "f(x === 'a' || x === 'b')");
+    test("f(new Set([1, null, true]).has(x))", "f(x === 1 || x === null || x === !0)");
+    test("f(new Set(['a']).has(g()))", "f(g() === 'a')");
+    test(
+        "const s = new Set(['a', 'b']); function f(x) { return s.has(x) }",
+        "function f(x) { return x === 'a' || x === 'b' }",
+    );
+    test("const a = 1, s = new Set(['a']); f(s.has(x))", "const a = 1; f(x === 'a')");
+    // Longer than the lookup.
+    test_same("f(new Set(['a', 'b', 'c', 'd', 'e']).has(foo.bar))");
+    test_same("const s = new Set(['a', 'b', 'c']); f(s.has(x), s.has(y), s.has(z), s.has(w))");
+    // The argument would be evaluated more than once.
+    test_same("f(new Set(['a', 'b']).has(g()))");
+    // The collection escapes or is not the global `Set`.
+    test_same("const s = new Set(['a']); f(s.has(x), s)");
+    test_same("const s = new Set(['a']); s.add(x)");
+    test_same("let s = new Set(['a']); f(s.has(x))");
+    test_same("function g(Set) { return new Set(['a']).has(x) }");
+    test_same("f(new Set([a]).has(x))");
+    test_same("f(new Set([]).has(x))");
+}
+
+#[test]
+fn map_literals() {
+    test("f(new Map([['a', 1], ['b', g()]]).get(x))", "f({ a: 1, b: g() }[x])");
+    test(
+        "const m = new Map([['a', 1], ['b-c', 2]]); f(m.get(x), m.get('a'))",
+        "const m = { a: 1, 'b-c': 2 }; f(m[x], m.a)",
+    );
+    test_same("f(new Map([['__proto__', 1]]).get(x))");
+    test_same("f(new Map([[1, 1]]).get(x))");
+    test_same("const m = new Map([['a', 1]]); f(m.get(x), m.size)");
+    test_same("const m = new Map([['a', 1]]); f(m.has(x))");
+}
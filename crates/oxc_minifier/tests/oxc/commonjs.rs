@@ -0,0 +1,71 @@
+use oxc_minifier::CompressOptions;
+
+use crate::test_with_options;
+
+fn test(source_text: &str, expected: &str) {
+    let options = CompressOptions { commonjs: true, ..CompressOptions::all_true() };
+    test_with_options(source_text, expected, options);
+}
+
+fn test_same(source_text: &str) {
+    test(source_text, source_text);
+}
+
+#[test]
+fn es_module_marker() {
+    test(
+        "Object.defineProperty(exports, '__esModule', { value: true }); exports.__esModule = true; foo()",
+        "Object.defineProperty(exports, '__esModule', { value: true }); foo()",
+    );
+    test(
+        "exports.__esModule = true; Object.defineProperty(module.exports, '__esModule', { value: true })",
+        "exports.__esModule = !0",
+    );
+    // The marker must stay recognizable by `cjs-module-lexer`.
+    test_same("Object.defineProperty(module.exports, '__esModule', { value: true })");
+    // Only top level markers are merged.
+    test_same("exports.__esModule = !0; if (foo) exports.__esModule = !0");
+    test_same("exports.__esModule = !0; exports.__esModule = !1");
+}
+
+#[test]
+fn json_require() {
+    test("require('./data.json'); foo()", "foo()");
+    test("const data = require('./data.json'), other = require('./other.json'); foo()", "foo()");
+    test("var data = require('./data.json'), x = 1; foo(x)", "var x = 1; foo(x)");
+    test_same("const data = require('./data.json'); foo(data)");
+    test_same("require('./data.js')");
+    test_same("require(path)");
+    // Referenced or shadowed bindings are kept.
+    test_same("var data = require('./data.json'); function f(data) { return data }");
+    test_same("var data = require('./data.json'); eval('data')");
+}
+
+#[test]
+fn module_exports_alias() {
+    test("module.exports.foo = 1", "exports.foo = 1");
+    test(
+        "module.exports.foo = function () { return module.exports.bar }",
+        "exports.foo = function () { return exports.bar }",
+    );
+    test("foo(module.exports); module.hot.accept()", "foo(exports); module.hot.accept()");
+    // `exports` is no longer `module.exports` after either is reassigned.
+    test_same("module.exports = {}; module.exports.foo = 1");
+    test_same("exports = {}; module.exports.foo = 1");
+    test_same("({ exports } = x); module.exports.foo = 1");
+    // `module` or `exports` may be shadowed or escape.
+    test_same("function f(exports) { module.exports.foo = exports }");
+    test_same("var module = {}; module.exports.foo = 1");
+    test_same("foo(module); module.exports.foo = 1");
+}
+
+#[test]
+fn disabled() {
+    let options = CompressOptions::all_true();
+    test_with_options(
+        "module.exports.foo = require('./data.json')",
+        "module.exports.foo = require('./data.json')",
+        options.clone(),
+    );
+    test_with_options("require('./data.json')", "require('./data.json')", options);
+}
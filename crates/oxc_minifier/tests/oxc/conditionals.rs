@@ -0,0 +1,55 @@
+use oxc_minifier::CompressOptions;
+
+use crate::{test, test_same, test_with_options};
+
+#[test]
+fn if_else_expressions() {
+    test("if (a) b(); else c();", "a ? b() : c()");
+    test("if (a) { b() } else { c() }", "a ? b() : c()");
+    test("if (!a) b(); else c();", "a ? c() : b()");
+    test("if (a) b(), c(); else d();", "a ? (b(), c()) : d()");
+    // Nested
+    test("if (a) b(); else if (c) d(); else e();", "a ? b() : c ? d() : e()");
+    test("if (a) { if (b) c(); else d() } else e();", "a ? b ? c() : d() : e()");
+    test_same("if (a) b();");
+    test_same("if (a) b(); else { c(); d() }");
+    test_same("if (a) b(); else return;");
+}
+
+#[test]
+fn if_else_assignments() {
+    test("if (a) x = b; else x = c;", "x = a ? b : c");
+    test("if (a) x = b; else if (c) x = d; else x = e;", "x = a ? b : c ? d : e");
+    test("if (a) x = b; else y = c;", "a ? x = b : y = c");
+    test("if (a) x += b; else x = c;", "a ? x += b : x = c");
+    test("if (a) x.y = b; else x.y = c;", "a ? x.y = b : x.y = c");
+}
+
+#[test]
+fn if_else_returns() {
+    test("function f() { if (a) return b; else return c; }", "function f() { return a ? b : c }");
+    test("function f() { if (a) return b; return c; }", "function f() { return a ? b : c }");
+    test(
+        "function f() { if (a) return b; if (c) return d; return e; }",
+        "function f() { return a ? b : c ? d : e }",
+    );
+    test("function f() { if (a) return; return c; }", "function f() { return a ? void 0 : c }");
+    test(
+        "function f() { x(); if (a) return b; return c; }",
+        "function f() { x(); return a ? b : c }",
+    );
+    test("function f() { if (a) return; return; }", "function f() { a }");
+    test_same("function f() { if (a) return b; c(); return d; }");
+    test_same("function f() { if (a) { b(); return c } return d; }");
+}
+
+#[test]
+fn conditionals_disabled() {
+    let options = CompressOptions { conditionals: false, ..CompressOptions::all_true() };
+    test_with_options("if (a) b(); else c();", "if (a) b(); else c();", options.clone());
+    test_with_options(
+        "function f() { if (a) return b; return c; }",
+        "function f() { if (a) return b; return c; }",
+        options,
+    );
+}
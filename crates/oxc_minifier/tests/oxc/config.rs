@@ -0,0 +1,125 @@
+use std::time::Duration;
+
+use oxc_minifier::{
+    Budget, ConsoleMethods, ESTarget, Env, Inline, MinifierOptions, Profile, PureGetters,
+};
+
+fn to_value(options: &MinifierOptions) -> serde_json::Value {
+    serde_json::to_value(options).unwrap()
+}
+
+/// The error message and the offset of its label.
+fn error(json: &str) -> (String, usize) {
+    let err = MinifierOptions::from_json_str(json).unwrap_err();
+    let offset = err.labels.as_ref().unwrap()[0].offset();
+    (err.message.to_string(), offset)
+}
+
+#[test]
+fn defaults() {
+    let options = MinifierOptions::from_json_str("{}").unwrap();
+    assert_eq!(to_value(&options), to_value(&MinifierOptions::default()));
+    let options = MinifierOptions::from_json_str(r#"{ "compress": {} }"#).unwrap();
+    assert_eq!(to_value(&options), to_value(&MinifierOptions::default()));
+}
+
+#[test]
+fn round_trip() {
+    let json = r#"{
+        "mangle": false,
+        "mangle_toplevel": true,
+        "name_cache": { "vars": { "counter": "a" } },
+        "mangle_props": { "regex": "^_", "keep_quoted": true },
+        "compress": {
+            "target": "es2015",
+            "drop_console": true,
+            "keep_console": ["error", "warn"],
+            "pure_getters": "safe",
+            "inline": 1,
+            "budget": { "time_ms": 100 },
+            "profile": "speed",
+            "env": "browser"
+        }
+    }"#;
+    let options = MinifierOptions::from_json_str(json).unwrap();
+    assert!(!options.mangle);
+    assert!(options.mangle_toplevel);
+    assert_eq!(options.name_cache.as_ref().unwrap().get("counter"), Some("a"));
+    let mangle_props = options.mangle_props.as_ref().unwrap();
+    assert_eq!(mangle_props.regex.as_str(), "^_");
+    assert!(mangle_props.keep_quoted);
+    let compress = &options.compress;
+    assert_eq!(compress.target, ESTarget::ES2015);
+    assert!(compress.drop_console);
+    assert_eq!(compress.keep_console, ConsoleMethods::Error | ConsoleMethods::Warn);
+    assert_eq!(compress.pure_getters, PureGetters::Safe);
+    assert_eq!(compress.inline, Inline::Simple);
+    assert_eq!(compress.budget, Some(Budget::Time(Duration::from_millis(100))));
+    assert_eq!(compress.profile, Profile::Speed);
+    assert_eq!(compress.env, Env::Browser);
+
+    let serialized = serde_json::to_string(&options).unwrap();
+    let deserialized = MinifierOptions::from_json_str(&serialized).unwrap();
+    assert_eq!(to_value(&deserialized), to_value(&options));
+}
+
+#[test]
+fn aliases() {
+    let json = r#"{
+        "toplevel": true,
+        "reserved": ["$", "jQuery"],
+        "mangle_props": "^_",
+        "compress": {
+            "ecma": 2020,
+            "dead_code": false,
+            "collapse_vars": false,
+            "pure": ["invariant"],
+            "inline": 3,
+            "pure_getters": "strict",
+            "platform": "node",
+            "keep_fargs": false
+        }
+    }"#;
+    let options = MinifierOptions::from_json_str(json).unwrap();
+    assert!(options.mangle_toplevel);
+    assert_eq!(options.mangle_reserved, vec!["$".to_string(), "jQuery".to_string()]);
+    assert!(!options.mangle_props.unwrap().keep_quoted);
+    let compress = options.compress;
+    assert_eq!(compress.target, ESTarget::ES2020);
+    assert!(!compress.remove_dead_code);
+    assert!(!compress.collapse);
+    assert_eq!(compress.pure_funcs, vec!["invariant".to_string()]);
+    assert_eq!(compress.inline, Inline::Arguments);
+    assert_eq!(compress.pure_getters, PureGetters::Safe);
+    assert_eq!(compress.env, Env::Node);
+    assert!(!compress.keep_fn_length);
+
+    let options = MinifierOptions::from_json_str(r#"{ "compress": false }"#).unwrap();
+    assert!(!options.compress.remove_dead_code && !options.compress.booleans);
+    let options = MinifierOptions::from_json_str(r#"{ "compress": { "target": "ESNext" } }"#);
+    assert_eq!(options.unwrap().compress.target, ESTarget::ESNext);
+    let options = MinifierOptions::from_json_str(r#"{ "compress": { "pure_getters": true } }"#);
+    assert_eq!(options.unwrap().compress.pure_getters, PureGetters::Always);
+}
+
+#[test]
+fn errors() {
+    let (message, offset) = error(r#"{ "mangel": true }"#);
+    assert!(message.starts_with("Invalid minifier options: unknown field `mangel`"), "{message}");
+    assert_eq!(offset, 10);
+
+    let json = "{\n  \"compress\": {\n    \"ecma\": 2014\n  }\n}";
+    let (message, offset) = error(json);
+    assert!(message.contains("invalid value: integer `2014`"), "{message}");
+    assert_eq!(&json[..offset], "{\n  \"compress\": {\n    \"ecma\": 2014");
+
+    let (message, _) = error(r#"{ "compress": { "keep_console": ["log", "print"] } }"#);
+    assert!(message.contains("unknown console method `print`"), "{message}");
+    let (message, _) = error(r#"{ "mangle_props": "(" }"#);
+    assert!(message.contains("regex parse error"), "{message}");
+    let (message, _) = error(r#"{ "name_cache": { "vars": { "a": "1" } } }"#);
+    assert!(message.contains("not an identifier"), "{message}");
+    let (message, offset) = error(r#"{ "mangle": "#);
+    assert!(message.contains("EOF"), "{message}");
+    assert_eq!(offset, 12);
+}
@@ -0,0 +1,94 @@
+use oxc_minifier::CompressOptions;
+use oxc_span::SourceType;
+
+use crate::run;
+
+fn test(source_text: &str, expected: &str) {
+    let source_type = SourceType::default().with_module(true);
+    let options = CompressOptions { dedupe_functions: true, ..CompressOptions::all_false() };
+    let result = run(source_text, source_type, Some(options));
+    let expected = run(expected, source_type, None);
+    assert_eq!(
+        result, expected,
+        "\nfor source {source_text:?}\nexpect {expected:?}\ngot    {result:?}"
+    );
+}
+
+fn test_same(source_text: &str) {
+    test(source_text, source_text);
+}
+
+#[test]
+fn hoist_repeated_functions() {
+    test(
+        "a.map((item) => item.id); b.map((item) => item.id)",
+        "const _fn = (item) => item.id; a.map(_fn); b.map(_fn)",
+    );
+    test(
+        "import x from 'x'; a(function () { return this.value }); b(function () { return this.value })",
+        "import x from 'x'; const _fn = function () { return this.value }; a(_fn); b(_fn)",
+    );
+    // References to top level declarations and globals resolve to the same bindings.
+    test(
+        "let n = 1; function f() { a((x) => x + n + Math.max(x)) } a((x) => x + n + Math.max(x))",
+        "const _fn = (x) => x + n + Math.max(x); let n = 1; function f() { a(_fn) } a(_fn)",
+    );
+    // Declarations of the function itself.
+    test(
+        "f(function (a) { var b = a; return b }); g(function (a) { var b = a; return b })",
+        "const _fn = function (a) { var b = a; return b }; f(_fn); g(_fn)",
+    );
+    // `import.meta` is the same everywhere in the module.
+    test(
+        "a(() => import.meta.resolve(x)); b(() => import.meta.resolve(x))",
+        "const _fn = () => import.meta.resolve(x); a(_fn); b(_fn)",
+    );
+    // Declaring the shared copy before a top level `await` evaluates no code.
+    test(
+        "await x; a(async (item) => await item.id); b(async (item) => await item.id)",
+        "const _fn = async (item) => await item.id; await x; a(_fn); b(_fn)",
+    );
+    // Names are not shadowed.
+    test(
+        "let _fn; a((item) => item.id); b((item) => item.id)",
+        "const _fn2 = (item) => item.id; let _fn; a(_fn2); b(_fn2)",
+    );
+}
+
+#[test]
+fn hoist_nested_functions() {
+    test(
+        "a((x) => x.map((item) => item.id)); b((x) => x.map((item) => item.id)); c((item) => item.id)",
+        "const _fn2 = (item) => item.id, _fn = (x) => x.map(_fn2); a(_fn); b(_fn); c(_fn2)",
+    );
+}
+
+#[test]
+fn keep_functions() {
+    // Not shorter.
+    test_same("a(() => 1); b(() => 1)");
+    test_same("a((item) => item.id)");
+    // Closes over bindings of an enclosing scope.
+    test_same("function f(n) { a((item) => item.id + n); b((item) => item.id + n) }");
+    test_same("{ let n; a((item) => item.id + n); b((item) => item.id + n) }");
+    test_same("try {} catch (n) { a((item) => item.id + n); b((item) => item.id + n) }");
+    test_same("{ function n() {} a((item) => item.id + n); b((item) => item.id + n) }");
+    test_same("function f() { a((item) => <Item {...item} />); b((item) => <Item {...item} />); function Item() {} }");
+    // Uses the context of the enclosing function or class.
+    test_same("function f() { a((item) => item[this.key]); b((item) => item[this.key]) }");
+    test_same("function f() { a((item) => item[arguments[0]]); b((item) => item[arguments[0]]) }");
+    test_same("class C { #key; f() { a(function (item) { return item.#key }); b(function (item) { return item.#key }) } }");
+    // Named function expressions.
+    test_same("a(function f(item) { return item.id }); b(function f(item) { return item.id })");
+    // Direct `eval` can reference any binding.
+    test_same("eval(s); a((item) => item.id); b((item) => item.id)");
+}
+
+#[test]
+fn scripts() {
+    let source_type = SourceType::default();
+    let source_text = "a.map((item) => item.id); b.map((item) => item.id)";
+    let options = CompressOptions { dedupe_functions: true, ..CompressOptions::all_false() };
+    let result = run(source_text, source_type, Some(options));
+    assert_eq!(result, run(source_text, source_type, None));
+}
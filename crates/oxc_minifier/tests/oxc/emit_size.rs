@@ -0,0 +1,118 @@
+use oxc_allocator::Allocator;
+use oxc_ast::ast::Statement;
+use oxc_codegen::{Context, Gen, GenExpr, WhitespaceRemover};
+use oxc_minifier::EmitSizeEstimator;
+use oxc_parser::Parser;
+use oxc_span::SourceType;
+use oxc_syntax::precedence::Precedence;
+
+/// The estimated size of the expression `source_text` is the size it is printed with.
+fn test(source_text: &str) {
+    let allocator = Allocator::default();
+    let expr =
+        Parser::new(&allocator, source_text, SourceType::default()).parse_expression().unwrap();
+    let mut codegen = WhitespaceRemover::new();
+    expr.gen_expr(&mut codegen, Precedence::Lowest, Context::empty());
+    let printed = codegen.into_source_text();
+    let size = EmitSizeEstimator::new().expression(&expr);
+    assert_eq!(size, printed.len(), "for source {source_text:?}, printed as {printed:?}");
+}
+
+#[test]
+fn literals() {
+    test("foo");
+    test("this");
+    test("null");
+    test("true");
+    test("false");
+    test("0");
+    test("7");
+    test("42");
+    test("999");
+    test("1000");
+    test("0.5");
+    test("0.001");
+    test("123456789");
+    test("1e21");
+    test("0xFFFFFFFFFFFF");
+    test("Infinity");
+}
+
+#[test]
+fn strings() {
+    test("'abc'");
+    test("'a\"b'");
+    test("\"a'b\"");
+    test("'a\"b\\'c\"'");
+    test("'a`b'");
+    test("'a\\\\b'");
+    test("'a\\nb\\r\\t'");
+    test("'\\0'");
+    test("'\\x001'");
+    test("'\\b\\v\\f\\x07\\x1B'");
+    test("'\\u00a0'");
+    test("'\\u2028\\u2029'");
+    test("'${a}$'");
+    test("'日本語'");
+}
+
+#[test]
+fn templates() {
+    test("`abc`");
+    test("`a${b}c`");
+    test("`a${b + c}c${'d'}`");
+    test("`\\r\\``");
+    for cooked in ["abc", "a`b", "a\\b", "a\rb", "${a}", "$a", "日本語"] {
+        let template = format!(
+            "`{}`",
+            cooked
+                .replace('\\', "\\\\")
+                .replace('`', "\\`")
+                .replace('\r', "\\r")
+                .replace("${", "\\${")
+        );
+        assert_eq!(EmitSizeEstimator::template_raw(cooked) + 2, template.len(), "for {cooked:?}");
+    }
+}
+
+#[test]
+fn other_expressions() {
+    test("a.b");
+    test("a(b, c)");
+    test("(a, b)");
+    test("a + b * c");
+    test("(a + b) * c");
+    test("function () { return 1 }");
+    test("x => ({ a: 1 })");
+}
+
+#[test]
+fn addition_parens() {
+    let parens = |source_text: &str, is_left| {
+        let allocator = Allocator::default();
+        let expr =
+            Parser::new(&allocator, source_text, SourceType::default()).parse_expression().unwrap();
+        EmitSizeEstimator::addition_parens(&expr, is_left)
+    };
+    assert_eq!(parens("a", false), 0);
+    assert_eq!(parens("a * b", false), 0);
+    assert_eq!(parens("a + b", true), 0);
+    assert_eq!(parens("a + b", false), 2);
+    assert_eq!(parens("a || b", true), 2);
+    assert_eq!(parens("a ? b : c", true), 2);
+}
+
+#[test]
+fn statement_and_its_expression() {
+    let allocator = Allocator::default();
+    let source_text = "a + b";
+    let program = Parser::new(&allocator, source_text, SourceType::default()).parse().program;
+    let Statement::ExpressionStatement(stmt) = &program.body[0] else { unreachable!() };
+    let mut codegen = WhitespaceRemover::new();
+    program.body[0].gen(&mut codegen, Context::empty());
+    let printed = codegen.into_source_text();
+    // Both nodes have the span of `a + b`.
+    let mut estimator = EmitSizeEstimator::new();
+    assert_eq!(estimator.statement(&program.body[0]), printed.len());
+    assert_eq!(estimator.expression(&stmt.expression), source_text.len() - 2);
+}
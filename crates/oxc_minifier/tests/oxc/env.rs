@@ -0,0 +1,82 @@
+use oxc_minifier::{CompressOptions, Env};
+use oxc_span::SourceType;
+
+use crate::run;
+
+fn test(env: Env, source_text: &str, expected: &str) {
+    test_with_source_type(env, SourceType::default().with_module(true), source_text, expected);
+}
+
+fn test_with_source_type(env: Env, source_type: SourceType, source_text: &str, expected: &str) {
+    let options = CompressOptions { env, ..CompressOptions::all_true() };
+    let result = run(source_text, source_type, Some(options));
+    let expected = run(expected, source_type, None);
+    assert_eq!(
+        result, expected,
+        "\nfor source {source_text:?}\nexpect {expected:?}\ngot    {result:?}"
+    );
+}
+
+fn test_same(env: Env, source_text: &str) {
+    test(env, source_text, source_text);
+}
+
+#[test]
+fn environment_detection() {
+    let source = "if (typeof window === 'undefined') server(); else client()";
+    test(Env::Browser, source, "client()");
+    test(Env::Node, source, "server()");
+    test(Env::Neutral, source, "typeof window > 'u' ? server() : client()");
+
+    let source = "x = typeof process !== 'undefined' && process.versions.node";
+    test(Env::Browser, source, "x = !1");
+    test(Env::Node, source, "x = process.versions.node");
+
+    test(Env::Browser, "x = typeof document.createElement == 'function'", "x = !0");
+    test(Env::Node, "x = typeof process.env == 'object'", "x = !0");
+    // Properties of globals which do not exist throw.
+    test_same(Env::Node, "x = typeof document.createElement == 'function'");
+    // Newer globals may be polyfilled.
+    test_same(Env::Neutral, "x = typeof Promise == 'function'");
+    test(Env::Neutral, "x = typeof JSON == 'object'", "x = !0");
+}
+
+#[test]
+fn declared_names() {
+    test_same(Env::Browser, "let window; x = typeof window == 'object'");
+    test_same(Env::Browser, "function f(document) { x = typeof document == 'object' }");
+    test_same(Env::Node, "process = {}; x = typeof process == 'object'");
+}
+
+#[test]
+fn pure_calls() {
+    test(Env::Browser, "performance.now(); Date.now(); Array.isArray(f())", "f()");
+    test(Env::Node, "Buffer.isBuffer(x); performance.now()", "");
+    // Exists, but creating a custom element runs its constructor.
+    test_same(Env::Browser, "document.createElement('my-element')");
+    test_same(Env::Neutral, "performance.now()");
+    test_same(Env::Browser, "let performance = p; performance.now()");
+}
+
+#[test]
+fn pure_calls_in_with_and_eval() {
+    let script = SourceType::default();
+    test_with_source_type(
+        Env::Browser,
+        script,
+        "performance.now(); with (o) { x() }",
+        "with(o)x()",
+    );
+    // The names may resolve to a property of the object or a `var` declared by `eval`.
+    let source = "with (o) performance.now()";
+    test_with_source_type(Env::Browser, script, source, source);
+    let source = "function f() { performance.now(); eval(s) }";
+    test_with_source_type(Env::Browser, script, source, source);
+    // Direct `eval` only affects the functions around it.
+    test_with_source_type(
+        Env::Browser,
+        script,
+        "function f() { performance.now() } function g() { eval(s) }",
+        "function f() {} function g() { eval(s) }",
+    );
+}
@@ -0,0 +1,7 @@
+function pick(a, b) {
+  if (a === true) {
+    return "a" + b + "c";
+  } else {
+    return Boolean(b);
+  }
+}
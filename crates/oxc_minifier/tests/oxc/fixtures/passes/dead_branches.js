@@ -0,0 +1,9 @@
+const DEBUG = false;
+if (DEBUG && 1 + 1 === 2) {
+  console.log("debug");
+} else {
+  run(undefined);
+}
+while (true) {
+  if (!!ready) break;
+}
@@ -1,6 +1,6 @@
 use oxc_minifier::CompressOptions;
 
-use crate::{test, test_snapshot, test_with_options};
+use crate::{test, test_same, test_snapshot, test_with_options};
 
 #[test]
 fn addition_folding() {
@@ -10,10 +10,85 @@ fn addition_folding() {
     test("x+''", "x+''");
 }
 
+#[test]
+fn string_coercion_folding() {
+    test("String(x) + ''", "String(x)");
+    test("'' + String(x)", "String(x)");
+    test("'' + typeof x", "typeof x");
+    test("x + 'a' + ''", "x+'a'");
+    test("'' + -1", "'-1'");
+    test("'' + -0", "'0'");
+    test("'' + 1e21", "'1e+21'");
+    test("'' + 1e-7", "'1e-7'");
+    test("x + ''", "x+''");
+    test("Number(x) + ''", "Number(x)+''");
+    // A local `String` may return anything.
+    test_same("function String() { return 1 } x = String(x) + ''");
+    test_same("var String = f; x = '' + String(x)");
+}
+
+#[test]
+fn string_concat_folding() {
+    test("'a' + 'b' + x", "'ab' + x");
+    test("x + 'a' + 'b'", "x + 'ab'");
+    test("x + 'a' + 1 + true", "x + 'a1true'");
+    test("'a' + ('b' + x)", "'ab' + x");
+    test("1 + ('b' + x)", "'1b' + x");
+    test("x + 1 + 'a'", "x + 1 + 'a'");
+    test("'a' + (x + 'b')", "'a' + (x + 'b')");
+    test("x + 'a' + y", "x + 'a' + y");
+}
+
+#[test]
+fn number_method_folding() {
+    test("(1.5).toFixed(2)", "'1.50'");
+    test("(-1.005).toFixed(2)", "'-1.00'");
+    test("(-0).toFixed(1)", "'0.0'");
+    test("(1e21).toFixed(2)", "'1e+21'");
+    test("NaN.toFixed()", "'NaN'");
+    test("(255).toString(16)", "'ff'");
+    test("(-255).toString(2)", "'-11111111'");
+    test("(0.5).toString()", "'0.5'");
+    test("(1e21).toString(10)", "'1e+21'");
+    test("(1).toFixed(2) + ''", "'1.00'");
+    // Exact ties are rounded up by `toFixed`.
+    test("(2.5).toFixed(0)", "(2.5).toFixed(0)");
+    test("(0.125).toFixed(2)", "(0.125).toFixed(2)");
+    // Throws a RangeError.
+    test("(1).toFixed(101)", "(1).toFixed(101)");
+    test("(1).toString(1)", "(1).toString(1)");
+    // Implementation-approximated.
+    test("(0.5).toString(2)", "(0.5).toString(2)");
+    // Locale dependent.
+    test("(1000).toLocaleString()", "(1000).toLocaleString()");
+    test("(1).toFixed(x)", "(1).toFixed(x)");
+    test("x.toFixed(2)", "x.toFixed(2)");
+}
+
 #[test]
 fn typeof_folding() {
     test("typeof x === 'undefined'", "typeof x>'u'");
     test("'undefined' === typeof x", "typeof x>'u'");
+
+    test("x = typeof 5", "x = 'number'");
+    test("x = typeof -5n", "x = 'bigint'");
+    test("x = typeof `a`", "x = 'string'");
+    test("x = typeof null", "x = 'object'");
+    test("x = typeof [1, {}]", "x = 'object'");
+    test("x = typeof /a/", "x = 'object'");
+    test("x = typeof function () {}", "x = 'function'");
+    test("x = typeof (() => {})", "x = 'function'");
+    test("x = typeof void 0", "x = 'undefined'");
+    test("x = typeof !y", "x = 'boolean'");
+    test("x = typeof undefined", "x = 'undefined'");
+    test("if (typeof 5 === 'number') a(); else b()", "a()");
+    test("if (typeof undefined !== 'undefined') a()", "");
+    test("x = typeof NaN == 'number'", "x = !0");
+
+    // Side effects and operands of unknown type.
+    test_same("x = typeof [f()]");
+    test_same("x = typeof -y");
+    test_same("x = typeof y");
 }
 
 #[test]
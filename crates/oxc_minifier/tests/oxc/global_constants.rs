@@ -0,0 +1,91 @@
+use oxc_allocator::Allocator;
+use oxc_codegen::WhitespaceRemover;
+use oxc_minifier::{CompressOptions, Minifier, MinifierOptions};
+use oxc_parser::Parser;
+use oxc_span::SourceType;
+
+use crate::test_with_options;
+
+fn test(source_text: &str, expected: &str) {
+    let options = CompressOptions { global_constants: true, ..CompressOptions::all_true() };
+    test_with_options(source_text, expected, options);
+}
+
+fn test_same(source_text: &str) {
+    test(source_text, source_text);
+}
+
+fn test_alias(source_text: &str, expected: &str, source_type: SourceType) {
+    let options = CompressOptions { undefined_alias: true, ..CompressOptions::all_true() };
+    let result = crate::run(source_text, source_type, Some(options));
+    let expected = crate::run(expected, source_type, None);
+    assert_eq!(result, expected, "for source {source_text}");
+}
+
+#[test]
+fn constants() {
+    test("x = NaN; y = Infinity", "x = 0 / 0; y = 1 / 0");
+    test("x = -Infinity", "x = -(1 / 0)");
+    test("f(undefined, NaN)", "f(void 0, 0 / 0)");
+    test("function f() { return [NaN, Infinity] }", "function f() { return [0 / 0, 1 / 0] }");
+    test_same("function f(NaN) { return NaN }");
+    test_same("function f() { var Infinity = 1; return Infinity }");
+    test_same("with (o) x = NaN");
+    test_same("x = o.NaN");
+}
+
+#[test]
+fn undefined_alias() {
+    let module = SourceType::default().with_module(true);
+    test_alias(
+        "f(undefined); g(void 0); h(void 0)",
+        "var _undefined; f(_undefined); g(_undefined); h(_undefined)",
+        module,
+    );
+    test_alias(
+        "export function f(a) { return a === void 0 ? g(void 0) : void 0 }",
+        "var _undefined; export function f(a) { return a === _undefined ? g(_undefined) : _undefined }",
+        module,
+    );
+    // The name is not used anywhere else.
+    test_alias(
+        "f(void 0, _undefined); g(void 0)",
+        "var _undefined1; f(_undefined1, _undefined); g(_undefined1)",
+        module,
+    );
+    test_alias("f(void 0)", "f(void 0)", module);
+    test_alias(
+        "f(void 0); with (o) g(void 0, void 0)",
+        "f(void 0); with (o) g(void 0, void 0)",
+        module,
+    );
+    test_alias("f(void 0); g(void 0); eval(s)", "f(void 0); g(void 0); eval(s)", module);
+    // The top level of scripts is global, unless they are CommonJS modules.
+    let script = SourceType::default();
+    test_alias("f(void 0); g(void 0)", "f(void 0); g(void 0)", script);
+    let options =
+        CompressOptions { undefined_alias: true, commonjs: true, ..CompressOptions::all_true() };
+    let result = crate::run("f(void 0); g(void 0)", script, Some(options));
+    assert_eq!(result, "var _undefined;\nf(_undefined);\ng(_undefined);\n");
+}
+
+#[test]
+fn undefined_alias_is_mangled() {
+    let minify = |mangle: bool| {
+        let allocator = Allocator::default();
+        let source_type = SourceType::default().with_module(true);
+        let source_text = "f(void 0); g(void 0); h(void 0)";
+        let ret = Parser::new(&allocator, source_text, source_type).parse();
+        let program = allocator.alloc(ret.program);
+        let options = MinifierOptions {
+            mangle,
+            compress: CompressOptions { undefined_alias: true, ..CompressOptions::all_false() },
+            ..MinifierOptions::default()
+        };
+        let ret = Minifier::new(options).build(&allocator, program);
+        WhitespaceRemover::new().with_mangler(ret.mangler).build(program).source_text
+    };
+    assert_eq!(minify(true), "var a;f(a);g(a);h(a);");
+    // Without mangling the alias would be longer.
+    assert_eq!(minify(false), "f(void 0);g(void 0);h(void 0);");
+}
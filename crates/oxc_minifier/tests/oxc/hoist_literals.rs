@@ -0,0 +1,104 @@
+use oxc_allocator::Allocator;
+use oxc_codegen::WhitespaceRemover;
+use oxc_minifier::{CompressOptions, Env, Minifier, MinifierOptions};
+use oxc_parser::Parser;
+use oxc_span::SourceType;
+
+fn test_with_options(source_text: &str, expected: &str, options: CompressOptions) {
+    let source_type = SourceType::default().with_module(true);
+    let result = crate::run(source_text, source_type, Some(options));
+    let expected = crate::run(expected, source_type, None);
+    assert_eq!(result, expected, "for source {source_text}");
+}
+
+fn test(source_text: &str, expected: &str) {
+    let options =
+        CompressOptions { hoist_strings: true, hoist_props: true, ..CompressOptions::all_true() };
+    test_with_options(source_text, expected, options);
+}
+
+fn test_same(source_text: &str) {
+    test(source_text, source_text);
+}
+
+#[test]
+fn strings() {
+    test(
+        "f('mousedown'); g('mousedown'); h('mousedown')",
+        "var _str = 'mousedown'; f(_str); g(_str); h(_str)",
+    );
+    test(
+        "export function f(a) { return a === 'mousedown' ? g('mousedown', 'keydown') : ['mousedown', 'keydown', 'keydown'] }",
+        "var _str = 'mousedown', _str1 = 'keydown'; export function f(a) { return a === _str ? g(_str, _str1) : [_str, _str1, _str1] }",
+    );
+    // The name is not used anywhere else.
+    test(
+        "f('mousedown', _str); g('mousedown'); h('mousedown')",
+        "var _str1 = 'mousedown'; f(_str1, _str); g(_str1); h(_str1)",
+    );
+    // Not shorter.
+    test_same("f('click'); g('click'); h('click')");
+    test_same("f('mousedown'); g('mousedown')");
+}
+
+#[test]
+fn kept_strings() {
+    test_same("x = { 'mouse-down': 1, 'mouse-down': 2, 'mouse-down': 3 }");
+    test_same("require('mousedown'); require('mousedown'); require('mousedown')");
+    test_same("import('mousedown'); import('mousedown'); import('mousedown')");
+    test_same("f('mousedown'); with (o) g('mousedown', 'mousedown')");
+    test_same("f('mousedown'); g('mousedown'); h('mousedown'); eval(s)");
+    // Not in scripts, where the top level is global, unless they are CommonJS modules.
+    let options = CompressOptions { hoist_strings: true, ..CompressOptions::all_true() };
+    let source_text = "f('mousedown'); g('mousedown'); h('mousedown')";
+    let result = crate::run(source_text, SourceType::default(), Some(options.clone()));
+    assert_eq!(result, "f('mousedown');\ng('mousedown');\nh('mousedown');\n");
+    let options = CompressOptions { commonjs: true, ..options };
+    let result = crate::run(source_text, SourceType::default(), Some(options));
+    assert_eq!(result, "var _str = 'mousedown';\nf(_str);\ng(_str);\nh(_str);\n");
+}
+
+#[test]
+fn props() {
+    test(
+        "f(JSON.stringify, JSON.stringify); g(JSON.stringify)",
+        "var _JSON_stringify = JSON.stringify; f(_JSON_stringify, _JSON_stringify); g(_JSON_stringify)",
+    );
+    // Calls would change `this`.
+    test_same("JSON.stringify(a); JSON.stringify(b); JSON.stringify(c)");
+    test_same("f(JSON.stringify, JSON.stringify, JSON.stringify); JSON.stringify = g");
+    test_same("f(JSON.stringify, JSON.stringify, JSON.stringify); delete JSON.stringify");
+    test_same("f(JSON.stringify, JSON.stringify, JSON.stringify); JSON = g");
+    test_same("function f(JSON) { g(JSON.stringify, JSON.stringify, JSON.stringify) }");
+    // Only chains known to the environment.
+    test_same("f(JSON.stringified, JSON.stringified, JSON.stringified)");
+    test_same("f(document.createElement, document.createElement, document.createElement)");
+    let options =
+        CompressOptions { hoist_props: true, env: Env::Browser, ..CompressOptions::all_true() };
+    test_with_options(
+        "f(document.createElement, document.createElement, document.createElement)",
+        "var _document_createElement = document.createElement; f(_document_createElement, _document_createElement, _document_createElement)",
+        options,
+    );
+}
+
+#[test]
+fn hoisted_literals_are_mangled() {
+    let minify = |mangle: bool| {
+        let allocator = Allocator::default();
+        let source_type = SourceType::default().with_module(true);
+        let source_text = "f('mousedown'); g('mousedown'); h('mousedown')";
+        let ret = Parser::new(&allocator, source_text, source_type).parse();
+        let program = allocator.alloc(ret.program);
+        let options = MinifierOptions {
+            mangle,
+            compress: CompressOptions { hoist_strings: true, ..CompressOptions::all_false() },
+            ..MinifierOptions::default()
+        };
+        let ret = Minifier::new(options).build(&allocator, program);
+        WhitespaceRemover::new().with_mangler(ret.mangler).build(program).source_text
+    };
+    assert_eq!(minify(true), "var a=\"mousedown\";f(a);g(a);h(a);");
+    // Without mangling the variable would be longer.
+    assert_eq!(minify(false), "f(\"mousedown\");g(\"mousedown\");h(\"mousedown\");");
+}
@@ -0,0 +1,84 @@
+use oxc_minifier::CompressOptions;
+use oxc_span::SourceType;
+
+use crate::run;
+
+fn test_with_source_type(source_text: &str, expected: &str, source_type: SourceType) {
+    let options = CompressOptions {
+        remove_syntax: true,
+        remove_dead_code: true,
+        labels: true,
+        iife: true,
+        ..CompressOptions::all_false()
+    };
+    let result = run(source_text, source_type, Some(options));
+    let expected = run(expected, source_type, None);
+    assert_eq!(
+        result, expected,
+        "\nfor source {source_text:?}\nexpect {expected:?}\ngot    {result:?}"
+    );
+}
+
+fn test(source_text: &str, expected: &str) {
+    test_with_source_type(source_text, expected, SourceType::default());
+}
+
+fn test_same(source_text: &str) {
+    test(source_text, source_text);
+}
+
+#[test]
+fn unwrap_values() {
+    test("x = (function () { return 42 })()", "x = 42");
+    test("x = (() => 42)()", "x = 42");
+    test("x = (() => { return a + b })()", "x = a + b");
+    test("x = (function () {})()", "x = void 0");
+    test("x = (function () { return })()", "x = void 0");
+    test("x = (() => { a })()", "x = (() => { a })()");
+    test("x = (() => (() => 1)())()", "x = 1");
+    // The `this` of an arrow function is the one around it.
+    test("x = (() => this.a)()", "x = this.a");
+    // Calling or deleting a member expression uses its object.
+    test("x = (() => a.b)()", "x = a.b");
+    test("x = (() => a.b)()(c)", "x = (() => a.b)()(c)");
+    test("x = (() => { return a[b] })()``", "x = (() => { return a[b] })()``");
+    test("x = delete (() => a.b)()", "x = delete (() => a.b)()");
+    test("x = (() => a?.b)()()", "x = (() => a?.b)()()");
+    test("x = (() => a)()()", "x = a()");
+}
+
+#[test]
+fn unwrap_statements() {
+    test("(function () { a(); b() })()", "a(); b()");
+    test("!function () { a() }()", "a()");
+    test("void (() => { a() })()", "a()");
+    test("(() => { let a = f(); g(a) })()", "{ let a = f(); g(a) }");
+    test("(() => {})()", "");
+    test("(() => f())()", "f()");
+    test("(function () { function f() {} f() })()", "(function () { function f() {} f() })()");
+    test_with_source_type(
+        "(function () { function f() {} f() })()",
+        "{ function f() {} f() }",
+        SourceType::default().with_module(true),
+    );
+    test("(function () { a(function () { return 1 }) })()", "a(function () { return 1 })");
+}
+
+#[test]
+fn keep_iifes() {
+    test_same("x = (function (a) { return a })(1)");
+    test_same("x = (() => 1)(f())");
+    test_same("x = (function f() { return f })()");
+    test_same("x = (async () => 1)()");
+    test_same("x = (function* () { return 1 })()");
+    test_same("x = (function () { return this })()");
+    test_same("x = (function () { return arguments })()");
+    test_same("x = (function () { return new.target })()");
+    test_same("x = (function () { 'use strict'; return a })()");
+    test_same("x = (() => eval('a'))()");
+    test_same("x = (() => { a(); return b })()");
+    test_same("x = f?.()");
+    test_same("(function () { if (a) return; b() })()");
+    test_same("(function () { var a = f(); g(a) })()");
+    test_same("(function () { yield(1) })()");
+}
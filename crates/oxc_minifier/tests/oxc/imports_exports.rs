@@ -0,0 +1,69 @@
+use oxc_minifier::CompressOptions;
+use oxc_span::SourceType;
+
+use crate::run;
+
+fn test(source_text: &str, expected: &str) {
+    let source_type = SourceType::default().with_module(true);
+    let result = run(source_text, source_type, Some(CompressOptions::all_true()));
+    let expected = run(expected, source_type, None);
+    assert_eq!(
+        result, expected,
+        "\nfor source {source_text:?}\nexpect {expected:?}\ngot    {result:?}"
+    );
+}
+
+fn test_same(source_text: &str) {
+    test(source_text, source_text);
+}
+
+#[test]
+fn join_exports() {
+    test("export { a }; export { b }", "export { a, b }");
+    test("export { a }; f(); export { b as c }", "export { a, b as c }; f()");
+    test("export { a } from 'm'; export { b } from 'm'", "export { a, b } from 'm'");
+    test(
+        "export { a } from 'm'; export { b }; export { c } from 'n'; export { d } from 'm'",
+        "export { a, d } from 'm'; export { b }; export { c } from 'n'",
+    );
+
+    test_same("export { a } from 'm'; export { b } from 'n'");
+    test_same("export * from 'm'; export * from 'n'");
+    test_same("export const a = 1; export { b }");
+    test_same("export { a } from 'm' with { type: 'json' }; export { b } from 'm'");
+}
+
+#[test]
+fn join_imports() {
+    test(
+        "import { a } from 'm'; import { b } from 'm'; f(a, b)",
+        "import { a, b } from 'm'; f(a, b)",
+    );
+    test("import { a } from 'm'; import b from 'm'; f(a, b)", "import b, { a } from 'm'; f(a, b)");
+    test(
+        "import * as a from 'm'; import b from 'm'; f(a, b)",
+        "import b, * as a from 'm'; f(a, b)",
+    );
+    test(
+        "import a from 'm'; import 'n'; import { b } from 'm'; f(a, b)",
+        "import a, { b } from 'm'; import 'n'; f(a, b)",
+    );
+    // Imports without bindings only load the module.
+    test("import 'm'; import 'm'", "import 'm'");
+    test("import 'm'; import { a } from 'm'; f(a)", "import { a } from 'm'; f(a)");
+    test("import { a } from 'm'; import {} from 'm'; f(a)", "import { a } from 'm'; f(a)");
+
+    test_same("import * as a from 'm'; import { b } from 'm'; f(a, b)");
+    test_same("import a from 'm'; import b from 'm'; f(a, b)");
+    test_same("import a from 'm'; import b from 'm' with { type: 'json' }; f(a, b)");
+    test_same("import a from 'm'; import b from 'n'; f(a, b)");
+}
+
+#[test]
+fn disabled() {
+    let source_type = SourceType::default().with_module(true);
+    let source_text = "import { a } from 'm'; import { b } from 'm'; export { a }; export { b }";
+    let options = CompressOptions { join_imports_exports: false, ..CompressOptions::all_true() };
+    let result = run(source_text, source_type, Some(options));
+    assert_eq!(result, run(source_text, source_type, None));
+}
@@ -0,0 +1,119 @@
+use oxc_minifier::{CompressOptions, Inline};
+use oxc_span::SourceType;
+
+use crate::run;
+
+fn test_with_options(source_text: &str, expected: &str, inline: Inline, source_type: SourceType) {
+    let options = CompressOptions { inline, ..CompressOptions::all_false() };
+    let result = run(source_text, source_type, Some(options));
+    let expected = run(expected, source_type, None);
+    assert_eq!(
+        result, expected,
+        "\nfor source {source_text:?}\nexpect {expected:?}\ngot    {result:?}"
+    );
+}
+
+/// In a function body, since top level functions of scripts are kept.
+fn test(source_text: &str, expected: &str) {
+    test_with_options(
+        &format!("function g() {{ {source_text} }}"),
+        &format!("function g() {{ {expected} }}"),
+        Inline::Arguments,
+        SourceType::default(),
+    );
+}
+
+fn test_same(source_text: &str) {
+    test(source_text, source_text);
+}
+
+#[test]
+fn inline_calls() {
+    test("function f() { return 1 } return f()", "return 1");
+    test("function f(a) { return a + 1 } return h(f(2))", "return h(2 + 1)");
+    test("const x = h(); const f = (a, b) => a * b; return f(x, 2)", "const x = h(); return x * 2");
+    test("const f = function (a) { return a.b }; return f('x')", "return 'x'.b");
+    test(
+        "function f(a, b) { return [a, b] } return f(1)",
+        "function f(a, b) { return [a, b] } return f(1)",
+    );
+    test("function f(a, b) { return b === a } return f(1)", "return void 0 === 1");
+    test("function f(a) { return a + a } return f('x')", "return 'x' + 'x'");
+    test("function f() { return x.y(1) } h(f()); return f()", "h(x.y(1)); return x.y(1)");
+    // The declaration is kept while other references remain.
+    test("function f() { return 1 } h(f); return f()", "function f() { return 1 } h(f); return 1");
+    // Calls in inlined bodies keep their callee.
+    test(
+        "function a() { return 1 } function b() { return a() } return b()",
+        "function a() { return 1 } return a()",
+    );
+}
+
+#[test]
+fn keep_calls() {
+    // Arguments with side effects, or which may change.
+    test_same("function f(a) { return a } return f(h())");
+    test_same("let x = 1; x++; function f(a) { return a } return f(x)");
+    test_same("function f(a) { return a } return f(x)");
+    test_same("function f(...a) { return a } return f(1)");
+    test_same("function f({ a }) { return a } return f(x)");
+    // `this`, `arguments` and nested functions.
+    test_same("function f() { return this.a } return f()");
+    test_same("function f() { return arguments[0] } return f(1)");
+    test_same("function f() { return () => 1 } return f()");
+    test_same("function f() { x(); return 1 } return f()");
+    test_same("async function f() { return 1 } return f()");
+    // Bindings which resolve differently at the call site.
+    test_same("const x = 1; function f() { return x } { const x = 2; h(f()) }");
+    // Reassigned functions.
+    test_same("function f() { return 1 } f = h; return f()");
+    // The callee of a call or `delete` would see a member expression.
+    test_same("function f() { return x.y } return f()()");
+    test_same("function f() { return x.y } return delete f()");
+    // Calls before the declaration of a `const` throw.
+    test_same("h(f()); const f = () => 1");
+    test_same("function f() { return f() } return f()");
+    // Names in the body of `with` may resolve to properties.
+    test_same("function f() { return x } with (o) h(f())");
+}
+
+#[test]
+fn levels() {
+    let script = SourceType::default();
+    test_with_options(
+        "function g() { function f() { return 1 } function h(a) { return a } return f() + h(1) }",
+        "function g() { function h(a) { return a } return 1 + h(1) }",
+        Inline::Simple,
+        script,
+    );
+    test_with_options(
+        "function g() { function f() { return 1 } return f() }",
+        "function g() { function f() { return 1 } return f() }",
+        Inline::Never,
+        script,
+    );
+}
+
+#[test]
+fn top_level() {
+    let module = SourceType::default().with_module(true);
+    test_with_options("function f() { return 1 } g(f())", "g(1)", Inline::Arguments, module);
+    test_with_options(
+        "export function f() { return 1 } g(f())",
+        "export function f() { return 1 } g(1)",
+        Inline::Arguments,
+        module,
+    );
+    test_with_options(
+        "function f() { return 1 } g(f())",
+        "function f() { return 1 } g(f())",
+        Inline::Arguments,
+        SourceType::default(),
+    );
+    test_with_options(
+        "function f() { return 1 } g(f()); eval('')",
+        "function f() { return 1 } g(f()); eval('')",
+        Inline::Arguments,
+        module,
+    );
+}
@@ -0,0 +1,90 @@
+use oxc_minifier::CompressOptions;
+use oxc_span::SourceType;
+
+use crate::run;
+
+fn test_with_source_type(source_text: &str, expected: &str, source_type: SourceType) {
+    let options = CompressOptions { inline_variables: true, ..CompressOptions::all_false() };
+    let result = run(source_text, source_type, Some(options));
+    let expected = run(expected, source_type, None);
+    assert_eq!(
+        result, expected,
+        "\nfor source {source_text:?}\nexpect {expected:?}\ngot    {result:?}"
+    );
+}
+
+/// In a function body, since top level bindings of scripts are kept.
+fn test(source_text: &str, expected: &str) {
+    test_with_source_type(
+        &format!("function f() {{ {source_text} }}"),
+        &format!("function f() {{ {expected} }}"),
+        SourceType::default(),
+    );
+}
+
+fn test_same(source_text: &str) {
+    test(source_text, source_text);
+}
+
+#[test]
+fn next_statement() {
+    test("const a = g(); return a", "return g()");
+    test("let a = g(); return a.b", "return g().b");
+    test("const a = g(); h = a", "h = g()");
+    test("const a = g(); if (a) h()", "if (g()) h()");
+    test("const a = g(); throw a", "throw g()");
+    test("const a = x + y; return h(a)", "return h(x + y)");
+    // Getters may have side effects.
+    test_same("const a = b.c; return h(a)");
+    test("const a = x + y; return [1, a]", "return [1, x + y]");
+    test("const a = x; return { a }", "return { a: x }");
+    test("const a = await g(); return a", "return await g()");
+    test("const a = x, b = g(); return b", "const a = x; return g()");
+    test("const a = x; const b = a", "const b = x");
+}
+
+#[test]
+fn order_of_side_effects() {
+    test_same("const a = g(); return h(a)");
+    test_same("const a = g(); return [x, a]");
+    test_same("const a = x; g(); return a");
+    test_same("const a = x; return [g(), a]");
+    test_same("const a = g(); return x && a");
+    test_same("const a = g(); return x ? a : 1");
+    test_same("const a = g(); return () => a");
+    test_same("const a = g(); b.c = a");
+    test("const a = g(); return [1, a]", "return [1, g()]");
+    test_same("const a = x; return x && a");
+}
+
+#[test]
+fn constants() {
+    test("const a = 1; g(); h(() => a)", "g(); h(() => 1)");
+    test("let a = 'x'; for (;;) g(a)", "for (;;) g('x')");
+    test("const a = -1; if (x) { g(a) }", "if (x) { g(-1) }");
+    test("const a = 1; return { a }", "return { a: 1 }");
+    test_same("const a = 1; function g() { return a }");
+    test_same("const a = /x/; g(); h(a)");
+    test_same("const a = {}; g(); h(a)");
+}
+
+#[test]
+fn keep_bindings() {
+    test_same("const a = g(); return a + a");
+    test_same("let a = g(); a = 1; return a");
+    test_same("var a = g(); return a");
+    test_same("const { a } = g(); return a");
+    test_same("const a = g(); eval('a')");
+    test_same("g(); const a = 1");
+    // `a` may be a property of `o`.
+    test_same("const a = 1; with (o) g(a)");
+    test_same("const a = g(); with (o) h(a)");
+}
+
+#[test]
+fn top_level() {
+    let module = SourceType::default().with_module(true);
+    test_with_source_type("const a = 1; g(a)", "g(1)", module);
+    test_with_source_type("const a = 1; export { a }", "const a = 1; export { a }", module);
+    test_with_source_type("const a = 1; g(a)", "const a = 1; g(a)", SourceType::default());
+}
@@ -0,0 +1,59 @@
+use crate::{test_with_options, CompressOptions};
+
+fn test(source_text: &str, expected: &str) {
+    let options = CompressOptions::all_true();
+    test_with_options(source_text, expected, options);
+}
+
+fn test_same(source_text: &str) {
+    test(source_text, source_text);
+}
+
+#[test]
+fn unused_labels() {
+    test("a: { x(); y() }", "x(); y()");
+    test("a: for (;;) { x() }", "for (;;) x()");
+    test("a: for (;;) { if (x) break a; y() }", "for (;;) { if (x) break; y() }");
+    test_same("a: for (;;) for (;;) { if (x) continue a; y() }");
+    // Labels are not visible in functions.
+    test("a: { x(function () { b: for (;;) break b }) }", "x(function () { for (;;) break })");
+}
+
+#[test]
+fn break_to_block() {
+    test("a: { x(); if (y) break a; z() }", "x(); if (!y) z()");
+    test("a: { x(); if (!y) break a; z(); w() }", "x(); if (y) { z(); w() }");
+    test("a: { if (x) break a; if (y) break a; z() }", "if (!x) if (!y) z()");
+    test("a: { x(); if (y()) break a }", "x(); y()");
+    test("a: { x(); if (y) break a }", "x()");
+    test("a: { x(); break a }", "x()");
+    test("a: { if (x) { break a } y() }", "if (!x) y()");
+}
+
+#[test]
+fn nested_labels() {
+    test("a: { b: { if (x) break a; if (y) break b; z() } }", "if (!x) if (!y) z()");
+    test("a: b: for (;;) { if (x) continue a; y() }", "for (;;) { if (x) continue; y() }");
+    test(
+        "a: { b: for (;;) { if (x) break a; if (y) continue b; z() } }",
+        "for (;;) { if (x) break; if (y) continue; z() }",
+    );
+}
+
+#[test]
+fn keep_labels() {
+    // `break` inside a nested statement.
+    test_same("a: { for (;;) if (x) break a; y() }");
+    test_same("a: { if (x) { y(); break a } z() }");
+    // Block scoped declarations can be captured by closures before the `break`.
+    test_same("a: { f = () => y; if (x) break a; let y = 1; g() }");
+    test("a: { if (x) break a; var y = 1; g() }", "if (!x) { var y = 1; g() }");
+}
+
+#[test]
+fn flatten_blocks() {
+    test("x(); { y(); z() } w()", "x(); y(); z(); w()");
+    test("function f() { { x(); y() } }", "function f() { x(); y() }");
+    test_same("x(); { let y = 1; z(y) }");
+    test_same("function f() { { 'use strict'; x() } }");
+}
@@ -0,0 +1,35 @@
+use oxc_minifier::CompressOptions;
+
+use crate::{test, test_same, test_with_options};
+
+#[test]
+fn while_to_for() {
+    test("while (a) b()", "for (;a;) b()");
+    test("while (true) b()", "for (;;) b()");
+    test("while (1) b()", "for (;;) b()");
+    test("for (;true;) b()", "for (;;) b()");
+    test("do b(); while (true)", "for (;;) b()");
+    test_same("do b(); while (a)");
+    test("a: while (b) for (;;) continue a", "a: for (;b;) for (;;) continue a");
+    test_with_options(
+        "while (a) b()",
+        "while (a) b()",
+        CompressOptions { loops: false, ..CompressOptions::all_true() },
+    );
+}
+
+#[test]
+fn join_for_init() {
+    test("a = 0; while (b) c()", "for (a = 0; b;) c()");
+    test("var i = 0; while (i < 10) i++", "for (var i = 0; i < 10;) i++");
+    test("var a = 1; var b = 2; for (;;) c()", "for (var a = 1, b = 2;;) c()");
+    test("var a = 1; for (var i = 0;;) c()", "for (var a = 1, i = 0;;) c()");
+    test("a(); for (b(); c;) d()", "for (a(), b(); c;) d()");
+    // The operator `in` is parenthesized in the header.
+    test("a = b in c; for (;;) d()", "for (a = (b in c);;) d()");
+    // `let` and `const` would only be visible in the loop.
+    test_same("let a = 0; for (;;) b(a)");
+    test_same("const a = 0; for (;;) b(a)");
+    test_same("var a = 0; for (let i = 0;;) b(a, i)");
+    test_same("a(); b: for (;;) for (;;) break b");
+}
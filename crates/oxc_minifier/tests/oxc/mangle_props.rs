@@ -0,0 +1,100 @@
+use oxc_allocator::Allocator;
+use oxc_codegen::{CodeGenerator, CodegenOptions};
+use oxc_minifier::{CompressOptions, ManglePropsOptions, Minifier, MinifierOptions};
+use oxc_parser::Parser;
+use oxc_span::SourceType;
+use regex::Regex;
+
+fn print(source_text: &str, mangle_props: Option<ManglePropsOptions>) -> String {
+    let allocator = Allocator::default();
+    let source_type = SourceType::default().with_module(true);
+    let ret = Parser::new(&allocator, source_text, source_type).parse();
+    let program = allocator.alloc(ret.program);
+    let options = MinifierOptions {
+        mangle: false,
+        compress: CompressOptions::all_false(),
+        mangle_props,
+        ..MinifierOptions::default()
+    };
+    Minifier::new(options).build(&allocator, program);
+    CodeGenerator::new()
+        .with_options(CodegenOptions { single_quote: true, ..CodegenOptions::default() })
+        .build(program)
+        .source_text
+}
+
+fn test_options(source_text: &str, expected: &str, options: ManglePropsOptions) {
+    let result = print(source_text, Some(options));
+    let expected = print(expected, None);
+    assert_eq!(
+        result, expected,
+        "\nfor source {source_text:?}\nexpect {expected:?}\ngot    {result:?}"
+    );
+}
+
+fn test(source_text: &str, expected: &str) {
+    test_options(source_text, expected, ManglePropsOptions::new(Regex::new("^_").unwrap()));
+}
+
+fn test_same(source_text: &str) {
+    test(source_text, source_text);
+}
+
+#[test]
+fn rename_matching_properties() {
+    test("x._a = 1; f(x._a, x.b)", "x.a = 1; f(x.a, x.b)");
+    test(
+        "x = { _a: 1, '_b': 2, _c() {}, get _d() {} }",
+        "x = { a: 1, 'b': 2, c() {}, get d() {} }",
+    );
+    test(
+        "class C { _a = 1; static _b() {} m() { return this._a } }",
+        "class C { a = 1; static b() {} m() { return this.a } }",
+    );
+    test("f(x['_a'], x?._a, x?.['_a'])", "f(x['a'], x?.a, x?.['a'])");
+    // Destructuring.
+    test("const { _a, _b: b = 1 } = x", "const { a: _a, b: b = 1 } = x");
+    test("({ _a, _b = 1 } = x)", "({ a: _a, b: _b = 1 } = x)");
+    test("x = { _a }", "x = { a: _a }");
+}
+
+#[test]
+fn frequent_names_first() {
+    test("f(x._a, x._b, x._b)", "f(x.b, x.a, x.a)");
+}
+
+#[test]
+fn keep_other_names() {
+    // New names do not clash with kept properties.
+    test("f(x._a, x.a, x.b)", "f(x.c, x.a, x.b)");
+    test_same("f(x.a_, x[_a], x.#b)");
+    // Template literal keys are not renamed, so neither are other uses of their names.
+    test_same("f(x._a, x[`_a`])");
+}
+
+#[test]
+fn keep_reserved_names() {
+    test(
+        "x = { __proto__: p, _a: 1 }; f(x.__proto__)",
+        "x = { __proto__: p, a: 1 }; f(x.__proto__)",
+    );
+    let options = ManglePropsOptions::new(Regex::new("^(_a|constructor|toString)$").unwrap());
+    test_options(
+        "f(x._a, x.constructor, x.toString)",
+        "f(x.a, x.constructor, x.toString)",
+        options,
+    );
+}
+
+#[test]
+fn keep_quoted() {
+    let options = ManglePropsOptions {
+        keep_quoted: true,
+        ..ManglePropsOptions::new(Regex::new("^_").unwrap())
+    };
+    test_options(
+        "f(x._a, x['_b'], x._b, { '_c': 1 }, x._c)",
+        "f(x.a, x['_b'], x._b, { '_c': 1 }, x._c)",
+        options,
+    );
+}
@@ -1,5 +1,43 @@
+mod array_loops;
+mod arrow_functions;
 mod booleans;
+mod budget;
+mod chunk_boundaries;
+mod classes;
 mod code_removal;
+mod collections;
+mod commonjs;
+mod conditionals;
+mod config;
+mod dedupe_functions;
+mod emit_size;
+mod env;
 mod folding;
+mod global_constants;
+mod hoist_literals;
+mod iife;
+mod imports_exports;
+mod inline_functions;
+mod inline_variables;
+mod labels;
+mod loops;
+mod mangle_props;
+mod module_context;
+mod name_cache;
+mod numeric_semantics;
+mod parameters;
+mod pass_outputs;
+mod properties;
+mod pure_annotations;
+mod pure_getters;
+mod recycler;
 mod remove_dead_code;
+mod reorder_declarations;
 mod replace_global_defines;
+mod rest_parameters;
+mod shorthand;
+mod static_types;
+mod symbol_liveness;
+mod template_literals;
+mod unused;
+mod validate_output;
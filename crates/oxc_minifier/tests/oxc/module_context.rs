@@ -0,0 +1,37 @@
+use oxc_minifier::CompressOptions;
+use oxc_span::SourceType;
+
+use crate::run;
+
+fn test(source_text: &str, expected: &str) {
+    let source_type = SourceType::default().with_module(true);
+    let result = run(source_text, source_type, Some(CompressOptions::all_true()));
+    let expected = run(expected, source_type, None);
+    assert_eq!(
+        result, expected,
+        "\nfor source {source_text:?}\nexpect {expected:?}\ngot    {result:?}"
+    );
+}
+
+fn test_same(source_text: &str) {
+    test(source_text, source_text);
+}
+
+/// Top level `await` suspends the module, so nothing is moved across it.
+#[test]
+fn top_level_await() {
+    test_same("a(); await b; c()");
+    test_same("x = await a || await b");
+    test("var a = await f(); var b = g()", "var a = await f(), b = g()");
+    // The condition is awaited even when its value is known.
+    test("if (await true) a()", "if (await !0) a()");
+    test("if (true) { await a } b()", "await a; b()");
+    test("if (false) { await a } b()", "b()");
+}
+
+#[test]
+fn import_meta() {
+    test_same("x = import.meta.url");
+    test_same("import.meta.hot && import.meta.hot.accept()");
+    test("if (true) { x = import.meta.url }", "x = import.meta.url");
+}
@@ -0,0 +1,76 @@
+use oxc_allocator::Allocator;
+use oxc_codegen::WhitespaceRemover;
+use oxc_minifier::{CompressOptions, Minifier, MinifierOptions, NameCache};
+use oxc_parser::Parser;
+use oxc_span::SourceType;
+
+fn minify(source_text: &str, name_cache: NameCache) -> (String, NameCache) {
+    let allocator = Allocator::default();
+    let ret = Parser::new(&allocator, source_text, SourceType::default()).parse();
+    let program = allocator.alloc(ret.program);
+    let options = MinifierOptions {
+        mangle_toplevel: true,
+        compress: CompressOptions::all_false(),
+        name_cache: Some(name_cache),
+        ..MinifierOptions::default()
+    };
+    let ret = Minifier::new(options).build(&allocator, program);
+    let printed = WhitespaceRemover::new().with_mangler(ret.mangler).build(program).source_text;
+    (printed, ret.name_cache.unwrap())
+}
+
+#[test]
+fn shared_globals() {
+    let (first, name_cache) = minify(
+        "var counter = 0; function increment(step) { counter += step; return counter }",
+        NameCache::default(),
+    );
+    assert_eq!(first, "var a=0;function b(c){a+=c;return a}");
+    assert_eq!(name_cache.get("counter"), Some("a"));
+    assert_eq!(name_cache.get("increment"), Some("b"));
+    assert_eq!(name_cache.get("step"), None);
+
+    // Globals of the first chunk keep their names, new ones do not take them.
+    let (second, name_cache) = minify(
+        "var total = increment(1); function reset(value) { counter = value } reset(total)",
+        name_cache,
+    );
+    assert_eq!(second, "var c=b(1);function d(e){a=e}d(c);");
+    assert_eq!(name_cache.len(), 4);
+
+    // Names are reused when the same chunk is built again, local names avoid all cached names.
+    let (again, _) = minify(
+        "var counter = 0; function increment(step) { counter += step; return counter }",
+        name_cache,
+    );
+    assert_eq!(again, "var a=0;function b(g){a+=g;return a}");
+}
+
+#[test]
+fn unused_without_mangle_toplevel() {
+    let allocator = Allocator::default();
+    let ret = Parser::new(&allocator, "var counter = 0; counter++", SourceType::default()).parse();
+    let program = allocator.alloc(ret.program);
+    let name_cache = NameCache::from_json(r#"{ "vars": { "counter": "a" } }"#).unwrap();
+    let options =
+        MinifierOptions { name_cache: Some(name_cache.clone()), ..MinifierOptions::default() };
+    let ret = Minifier::new(options).build(&allocator, program);
+    let printed = WhitespaceRemover::new().with_mangler(ret.mangler).build(program).source_text;
+    assert_eq!(printed, "var counter=0;counter++;");
+    assert_eq!(ret.name_cache, Some(name_cache));
+}
+
+#[test]
+fn json() {
+    let (_, name_cache) = minify("var foo = 1, bar = foo", NameCache::default());
+    let json = name_cache.to_json();
+    assert_eq!(json, "{\n  \"vars\": {\n    \"bar\": \"b\",\n    \"foo\": \"a\"\n  }\n}");
+    assert_eq!(NameCache::from_json(&json).unwrap(), name_cache);
+    assert!(NameCache::from_json("{}").unwrap().is_empty());
+
+    assert!(NameCache::from_json("[]").is_err());
+    assert!(NameCache::from_json(r#"{ "vars": { "foo": 1 } }"#).is_err());
+    assert!(NameCache::from_json(r#"{ "vars": { "foo": "a b" } }"#).is_err());
+    assert!(NameCache::from_json(r#"{ "vars": { "foo": "do" } }"#).is_err());
+    assert!(NameCache::from_json(r#"{ "vars": { "foo": "undefined" } }"#).is_err());
+}
@@ -0,0 +1,56 @@
+use crate::{test, test_same};
+
+#[test]
+fn negative_zero() {
+    test("x(-0 + -0)", "x(-0)");
+    test("x(0 + -0, -0 + 0)", "x(0, 0)");
+    test("x('' + -0, 'a' + -0)", "x('0', 'a0')");
+    test("x(-0 === 0, -0 == '0', -0 <= 0)", "x(!0, !0, !0)");
+    test("x(-0 < 0, 0 > -0)", "x(!1, !1)");
+    test("x(-'0' ? a : b, -0 ? a : b)", "x(b, b)");
+    test_same("x(1 / -0)");
+}
+
+#[test]
+fn not_a_number() {
+    test("x(+'abc' ? a : b, -'nan' ? a : b, NaN ? a : b)", "x(b, b, b)");
+    test("x(NaN == NaN, NaN === NaN, 'NaN' == NaN)", "x(!1, !1, !1)");
+    test("x(NaN < NaN, NaN >= 1, 'x' < 1, 'x' >= 1)", "x(!1, !1, !1, !1)");
+    test("x('nan' == 'nan')", "x(!0)");
+}
+
+#[test]
+fn infinity() {
+    // `Infinity` may be shadowed, so infinite results are not folded.
+    test_same("x(1e308 + 1e308, -1e308 - 1e308)");
+    test("x('Infinity' == Infinity, '-Infinity' == -Infinity)", "x(!0, !0)");
+    test("x('1e1000' == Infinity, +'Infinity' ? a : b)", "x(!0, a)");
+    test("x('inf' == Infinity, 'INFINITY' == Infinity, '+inf' == Infinity)", "x(!1, !1, !1)");
+}
+
+#[test]
+fn string_to_number() {
+    test("x('' == 0, ' \\n' == 0, '\\t12 ' == 12, -'' == 0)", "x(!0, !0, !0, !0)");
+    test("x('0x10' == 16, '0b11' == 3, '0o17' == 15)", "x(!0, !0, !0)");
+    test("x('1.' == 1, '.5' == 0.5, '1e1' == 10)", "x(!0, !0, !0)");
+    // Signs are not allowed before other bases, and there are no separators.
+    test("x('-0x10' == -16, '1_0' == 10, '1 0' == 10)", "x(!1, !1, !1)");
+    test("x(+'' ? a : b)", "x(b)");
+    // Vertical tabs are not whitespace in old engines.
+    test_same("x('\\v1' == 1)");
+}
+
+#[test]
+fn bigint() {
+    test("x(1n == 1, -1n == -1, 0x10n == 16, 1_0n == 10)", "x(!0, !0, !0, !0)");
+    test("x(1n == '1', 1n == ' 1 ', 1n == true)", "x(!0, !0, !0)");
+    test("x(1n === 1, 1n === 1n, 0x1n === 1n)", "x(!1, !0, !0)");
+    test("x(1n < 2, 2n > '1', 1n < Infinity, 1n < NaN)", "x(!0, !0, !0, !1)");
+    test("x(null == 0n)", "x(!1)");
+    test("x('' + 1n, '' + 0x10n, 'a' + 1_000n)", "x('1', '16', 'a1000')");
+    test("x(0n ? a : b, 0x0n ? a : b)", "x(b, b)");
+    // Mixing bigints and numbers throws.
+    test_same("x(1n + 1, +1n)");
+    // Numbers which are not safe integers may not be exact.
+    test_same("x(9007199254740993n == 9007199254740993)");
+}
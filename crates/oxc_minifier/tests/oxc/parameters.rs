@@ -0,0 +1,113 @@
+use oxc_minifier::{CompressOptions, ESTarget};
+
+use crate::{test, test_same, test_with_options};
+
+fn test_without_keep_fn_length(source_text: &str, expected: &str) {
+    let options = CompressOptions { keep_fn_length: false, ..CompressOptions::all_true() };
+    test_with_options(source_text, expected, options);
+}
+
+#[test]
+fn undefined_defaults() {
+    test("function f(a = 1, b = undefined) {}", "function f(a = 1, b) {}");
+    test("(a = 1, b = void 0, c = void 0) => {}", "(a = 1, b, c) => {}");
+    // `f.length` counts the parameters before the first default.
+    test_same("function f(a, b = void 0) {}");
+    test_same("x = class { constructor(a, b = void 0) {} }");
+    test_without_keep_fn_length(
+        "function f(a, b = void 0) { return [a, b] }",
+        "function f(a, b) { return [a, b] }",
+    );
+    // `arguments` would become mapped to the parameters.
+    test_without_keep_fn_length(
+        "function f(a = void 0) { a = 1; return arguments[0] }",
+        "function f(a = void 0) { a = 1; return arguments[0] }",
+    );
+    test_same("function f(a = 1, b = void 0) { eval('b') }");
+}
+
+#[test]
+fn constant_defaults() {
+    test("function f(a = 1 + 2, b = 'a' + 'b') {}", "function f(a = 3, b = 'ab') {}");
+    test("(a = 'a' + 1) => a", "(a = 'a1') => a");
+}
+
+#[test]
+fn default_assignments() {
+    test(
+        "function f(a = 1, b) { if (b === undefined) b = 2; return b }",
+        "function f(a = 1, b = 2) { return b }",
+    );
+    test(
+        "function f(a = 1, b, c) { if (void 0 === b) { b = [] } c === void 0 && (c = 'c'); return b + c }",
+        "function f(a = 1, b = [], c = 'c') { return b + c }",
+    );
+    test_without_keep_fn_length(
+        "function f(a) { if (a === void 0) a = 1; return a }",
+        "function f(a = 1) { return a }",
+    );
+    // Adding the first default changes `f.length`.
+    test_same("function f(a) { if (a === void 0) a = 1; return a }");
+    // Not equivalent to a default value.
+    test_same("function f(a = 1, b) { b = b || 2; return b }");
+    test_same("function f(a = 1, b) { if (b == null) b = 2; return b }");
+    // Defaults can not see names declared in the body.
+    test_same("function f(a = 1, b) { if (b === void 0) b = c; var c = 2; return b }");
+    test_same("function f(a = 1, b) { if (b === void 0) b = 2; var b; return b }");
+    // Only leading statements.
+    test_same("function f(a = 1, b) { g(); if (b === void 0) b = 2; return b }");
+    // Parameters with defaults make "use strict" a syntax error.
+    test_without_keep_fn_length(
+        "function f(a) { 'use strict'; if (a === void 0) a = 1; return a }",
+        "function f(a) { 'use strict'; if (a === void 0) a = 1; return a }",
+    );
+    let options = CompressOptions { target: ESTarget::ES5, ..CompressOptions::all_true() };
+    test_with_options(
+        "function f(a = 1, b) { if (b === void 0) b = 2; return b }",
+        "function f(a = 1, b) { if (b === void 0) b = 2; return b }",
+        options,
+    );
+}
+
+#[test]
+fn unused_destructured_defaults() {
+    test("function f({ a = 1, b = 2 }) { return a }", "function f({ a = 1, b }) { return a }");
+    test("([a = 1, [b = 2]] = []) => {}", "([a, [b]] = []) => {}");
+    test("function f({ a: { b = 1 } = {} }) {}", "function f({ a: { b } = {} }) {}");
+    // Parameter defaults change `f.length`.
+    test_same("function f(a = 1) {}");
+    test_same("function f({ a = g() }) {}");
+    test_same("function f({ a = 1, b = a }) { return b }");
+}
+
+#[test]
+fn unused_trailing_parameters() {
+    test_without_keep_fn_length("function f(a, b, c) { return a }", "function f(a) { return a }");
+    test_without_keep_fn_length("function f(a, b) {}", "function f() {}");
+    test_without_keep_fn_length("(a, b) => b", "(a, b) => b");
+    test_without_keep_fn_length("(a, ...b) => a", "(a) => a");
+    test_without_keep_fn_length(
+        "x = class { constructor(a, b) { this.a = a } m(c, d) {} }",
+        "x = class { constructor(a) { this.a = a } m() {} }",
+    );
+    // Used by a nested function.
+    test_without_keep_fn_length(
+        "function f(a, b) { return () => b }",
+        "function f(a, b) { return () => b }",
+    );
+    // Defaults and destructuring may have side effects.
+    test_without_keep_fn_length("function f(a, b = g()) {}", "function f(a, b = g()) {}");
+    test_without_keep_fn_length("function f(a, { b }) {}", "function f(a, { b }) {}");
+    test_without_keep_fn_length("function f(a, ...[b]) {}", "function f(a, ...[b]) {}");
+    // Setters must have exactly one parameter.
+    test_without_keep_fn_length("x = { set a(v) {} }", "x = { set a(v) {} }");
+    test_without_keep_fn_length("x = class { set a(v) {} }", "x = class { set a(v) {} }");
+    // Dynamic functions.
+    test_without_keep_fn_length(
+        "function f(a, b) { return arguments[1] }",
+        "function f(a, b) { return arguments[1] }",
+    );
+    test_without_keep_fn_length("function f(a, b) { eval(s) }", "function f(a, b) { eval(s) }");
+    // Removing parameters changes `f.length`.
+    test_same("function f(a, b) { return a }");
+}
@@ -0,0 +1,31 @@
+use std::fs;
+
+use oxc_allocator::Allocator;
+use oxc_minifier::{CompressOptions, Compressor};
+use oxc_parser::Parser;
+use oxc_span::SourceType;
+
+use crate::run_passes;
+
+#[test]
+fn fixtures() {
+    insta::glob!("fixtures/passes/*.js", |path| {
+        let source_text = fs::read_to_string(path).unwrap();
+        let source_type = SourceType::from_path(path).unwrap();
+        let snapshot = run_passes(&source_text, source_type, CompressOptions::all_true());
+        let name = path.file_stem().unwrap().to_str().unwrap();
+        insta::with_settings!({ prepend_module_to_snapshot => false, snapshot_suffix => "", omit_expression => true }, {
+            insta::assert_snapshot!(name, snapshot);
+        });
+    });
+}
+
+#[test]
+fn disabled_by_default() {
+    let allocator = Allocator::default();
+    let program = Parser::new(&allocator, "x = 1 + 1", SourceType::default()).parse().program;
+    let program = allocator.alloc(program);
+    let ret = Compressor::new(&allocator, CompressOptions::all_true()).build(program);
+    assert!(!ret.passes.is_empty());
+    assert!(ret.pass_outputs.is_empty());
+}
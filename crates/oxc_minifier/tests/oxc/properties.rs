@@ -0,0 +1,73 @@
+use oxc_allocator::Allocator;
+use oxc_minifier::{ESTarget, Minifier, MinifierOptions};
+use oxc_parser::Parser;
+use oxc_span::SourceType;
+
+use crate::{test_with_options, CompressOptions};
+
+fn test(source_text: &str, expected: &str) {
+    test_with_options(source_text, expected, CompressOptions::all_true());
+}
+
+fn test_same(source_text: &str) {
+    test(source_text, source_text);
+}
+
+fn test_es3(source_text: &str, expected: &str) {
+    let options = CompressOptions { target: ESTarget::ES3, ..CompressOptions::all_true() };
+    test_with_options(source_text, expected, options);
+}
+
+#[test]
+fn dot_notation() {
+    test("a['b']", "a.b");
+    test("a['b']['c'] = 1", "a.b.c = 1");
+    test("a?.['b']", "a?.b");
+    test("a['class']", "a.class");
+    test("x = { 'b': 1, 'class': 2 }", "x = { b: 1, class: 2 }");
+    test("x = { 'b'() {}, get 'c'() {} }", "x = { b() {}, get c() {} }");
+    test_same("a['b-c']");
+    test_same("a['1']");
+    test_same("a[b]");
+    test_same("x = { 'b-c': 1, ['d']: 2 }");
+
+    let options = CompressOptions { properties: false, ..CompressOptions::all_true() };
+    test_with_options("a['b']", "a['b']", options);
+}
+
+#[test]
+fn es3_reserved_words() {
+    test_es3("a.class", "a['class']");
+    test_es3("a.int = a.b", "a['int'] = a.b");
+    test_es3("a['class']", "a['class']");
+    test_es3("a['b']", "a.b");
+    test_es3("x = { class: 1, 'int': 2, b: 3 }", "x = { 'class': 1, 'int': 2, b: 3 }");
+    // Only words reserved in ES3 are quoted.
+    test_es3("a.let", "a.let");
+
+    let options =
+        CompressOptions { target: ESTarget::ES3, properties: false, ..CompressOptions::all_true() };
+    test_with_options("a.class; a['b']", "a['class']; a['b']", options);
+}
+
+#[test]
+fn property_name_frequencies() {
+    let source_text = "
+        a.b = a['b'] + a.c;
+        x = { b: 1, 'c': 2, [d]: 3, 'e-f': 4 };
+        ({ b } = x);
+        if (false) a.g;
+        class A { b() {} #h = 1; }
+    ";
+    let allocator = Allocator::default();
+    let ret = Parser::new(&allocator, source_text, SourceType::default()).parse();
+    let program = allocator.alloc(ret.program);
+    let ret = Minifier::new(MinifierOptions::default()).build(&allocator, program);
+    let names = ret.property_names;
+    assert_eq!(names.frequency("b"), 5);
+    assert_eq!(names.frequency("c"), 2);
+    assert_eq!(names.frequency("e-f"), 1);
+    // Removed as dead code.
+    assert_eq!(names.frequency("g"), 0);
+    assert_eq!(names.sorted_by_frequency(), [("b", 5), ("c", 2), ("e-f", 1)]);
+}
@@ -0,0 +1,51 @@
+use oxc_allocator::Allocator;
+use oxc_codegen::{CodeGenerator, CodegenOptions};
+use oxc_minifier::{CompressOptions, Compressor, PureAnnotations};
+use oxc_parser::Parser;
+use oxc_span::SourceType;
+
+fn test(source_text: &str, expected: &str) {
+    let allocator = Allocator::default();
+    let ret = Parser::new(&allocator, source_text, SourceType::default()).parse();
+    let program = allocator.alloc(ret.program);
+    let pure_annotations = PureAnnotations::new(program, source_text, &ret.trivias);
+    Compressor::new(&allocator, CompressOptions::default())
+        .with_pure_annotations(pure_annotations)
+        .build(program);
+    let minified = CodeGenerator::new()
+        .with_options(CodegenOptions { single_quote: true, ..CodegenOptions::default() })
+        .build(program)
+        .source_text;
+    let expected = crate::run(expected, SourceType::default(), None);
+    assert_eq!(minified, expected, "for source {source_text}");
+}
+
+fn test_same(source_text: &str) {
+    test(source_text, source_text);
+}
+
+#[test]
+fn unused_calls() {
+    test("/* @__PURE__ */ f()", "");
+    test("/* #__PURE__ */ new Foo(a, b)", "");
+    test("/*#__PURE__*/ a.b.c()", "");
+    test("// @__PURE__\nf()", "");
+    test("/* @__PURE__ */ (f())", "");
+    test("x = (/* @__PURE__ */ f(), y)", "x = y");
+    // Arguments with side effects are kept.
+    test("/* @__PURE__ */ f(a, g())", "g()");
+    test("/* @__PURE__ */ f(/* @__PURE__ */ g(h()))", "h()");
+    // Only the outermost call of a chain is annotated.
+    test("/* @__PURE__ */ a().b()", "");
+    test_same("a(/* @__PURE__ */ b()).c()");
+}
+
+#[test]
+fn used_or_unannotated_calls() {
+    test_same("x = /* @__PURE__ */ f()");
+    test_same("f()");
+    test_same("/* some comment */ f()");
+    test_same("/* @__PURE__ */ f(...a)");
+    // The annotation is not right before the call.
+    test_same("/* @__PURE__ */ a, f()");
+}
@@ -0,0 +1,52 @@
+use oxc_minifier::PureGetters;
+
+use crate::{test_with_options, CompressOptions};
+
+fn test(source_text: &str, expected: &str, pure_getters: PureGetters) {
+    let options = CompressOptions { pure_getters, ..CompressOptions::default() };
+    test_with_options(source_text, expected, options);
+}
+
+#[test]
+fn never() {
+    let test = |source_text, expected| test(source_text, expected, PureGetters::Never);
+    test("f(void a.b || c)", "f((void a.b, c))");
+    test("f(void a?.b || c)", "f((void a?.b, c))");
+    test("f(void 'abc'.length || c)", "f((void 'abc'.length, c))");
+    test("f(void { ...a } || c)", "f((void { ...a }, c))");
+}
+
+#[test]
+fn safe() {
+    let test = |source_text, expected| test(source_text, expected, PureGetters::Safe);
+    test("f(void 'abc'.length || c)", "f(c)");
+    test("f(void [].length || c)", "f(c)");
+    test("f(void a?.b || c)", "f(c)");
+    test("f(void a?.[b] || c)", "f(c)");
+    test("f(void { ...a } || c)", "f(c)");
+    // `a` or `a?.b` may be nullish.
+    test("f(void a.b || c)", "f((void a.b, c))");
+    test("f(void a?.b.c || c)", "f((void a?.b.c, c))");
+    test("f(void null.b || c)", "f((void null.b, c))");
+    // The key or the object have side effects.
+    test("f(void a?.[g()] || c)", "f((void a?.[g()], c))");
+    test("f(void g()?.b || c)", "f((void g()?.b, c))");
+    test("f(void a?.b() || c)", "f((void a?.b(), c))");
+}
+
+#[test]
+fn always() {
+    let test = |source_text, expected| test(source_text, expected, PureGetters::Always);
+    test("f(void a.b || c)", "f(c)");
+    test("f(void a.b.c || c)", "f(c)");
+    test("f(void a[b] || c)", "f(c)");
+    test("f(void a?.b.c || c)", "f(c)");
+    test("f(void a[g()] || c)", "f((void a[g()], c))");
+    test("f(void a.b() || c)", "f((void a.b(), c))");
+    test("f(void [...a] || c)", "f((void [...a], c))");
+    // Throws when `a` does not have the private field.
+    test(
+        "class C { #b; m(a) { f(void a.#b || c) } }",
+        "class C { #b; m(a) { f((void a.#b, c)) } }",
+    );
+}
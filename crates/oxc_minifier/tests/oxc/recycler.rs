@@ -0,0 +1,86 @@
+use oxc_allocator::Allocator;
+use oxc_ast::{ast::*, AstBuilder};
+use oxc_minifier::Recycler;
+use oxc_span::SPAN;
+use oxc_syntax::{number::NumberBase, operator::BinaryOperator};
+
+#[test]
+fn reuse_placeholders() {
+    let allocator = Allocator::default();
+    let ast = AstBuilder::new(&allocator);
+    let mut recycler = Recycler::new();
+
+    let mut expr = ast.expression_identifier_reference(SPAN, "a");
+    let taken = recycler.take_expression(ast, &mut expr);
+    let Expression::NullLiteral(placeholder) = &expr else { unreachable!() };
+    let placeholder: *const NullLiteral = &**placeholder;
+    recycler.replace_expression(&mut expr, taken);
+    assert!(matches!(&expr, Expression::Identifier(ident) if ident.name == "a"));
+
+    let taken = recycler.take_expression(ast, &mut expr);
+    let Expression::NullLiteral(reused) = &expr else { unreachable!() };
+    assert!(std::ptr::eq(placeholder, &**reused));
+    recycler.replace_expression(&mut expr, taken);
+}
+
+#[test]
+fn retain_statements() {
+    let allocator = Allocator::default();
+    let ast = AstBuilder::new(&allocator);
+    let mut recycler = Recycler::new();
+
+    let mut stmts = ast.vec_from_iter(["a", "", "b", "c"].into_iter().map(|name| {
+        if name.is_empty() {
+            ast.statement_empty(SPAN)
+        } else {
+            ast.statement_expression(SPAN, ast.expression_identifier_reference(SPAN, name))
+        }
+    }));
+    let removed: *const EmptyStatement = match &stmts[1] {
+        Statement::EmptyStatement(stmt) => &**stmt,
+        _ => unreachable!(),
+    };
+    recycler.retain_statements(&mut stmts, |stmt| !matches!(stmt, Statement::EmptyStatement(_)));
+    let names = stmts
+        .iter()
+        .map(|stmt| match stmt {
+            Statement::ExpressionStatement(stmt) => match &stmt.expression {
+                Expression::Identifier(ident) => ident.name.as_str(),
+                _ => unreachable!(),
+            },
+            _ => unreachable!(),
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["a", "b", "c"]);
+
+    let Statement::EmptyStatement(reused) = recycler.empty_statement(ast, SPAN) else {
+        unreachable!()
+    };
+    assert!(std::ptr::eq(removed, &*reused));
+}
+
+#[test]
+fn reuse_folded_literals() {
+    let allocator = Allocator::default();
+    let ast = AstBuilder::new(&allocator);
+    let mut recycler = Recycler::new();
+
+    // `1 + "a"` → `"1a"`, which keeps both operands.
+    let left = ast.expression_numeric_literal(SPAN, 1.0, "1", NumberBase::Decimal);
+    let right = ast.expression_string_literal(SPAN, "a");
+    let Expression::StringLiteral(operand) = &right else { unreachable!() };
+    let operand: *const StringLiteral = &**operand;
+    let mut expr = ast.expression_binary(SPAN, left, BinaryOperator::Addition, right);
+    let folded = recycler.string_literal(ast, SPAN, ast.atom("1a"));
+    recycler.replace_expression(&mut expr, folded);
+    assert!(matches!(&expr, Expression::StringLiteral(lit) if lit.value == "1a"));
+
+    let Expression::StringLiteral(reused) = recycler.string_literal(ast, SPAN, ast.atom("b"))
+    else {
+        unreachable!()
+    };
+    assert!(std::ptr::eq(operand, &*reused));
+    assert_eq!(reused.value, "b");
+    let reused = recycler.numeric_literal(ast, SPAN, 2.0, "2", NumberBase::Decimal);
+    assert!(matches!(reused, Expression::NumericLiteral(lit) if lit.raw == "2"));
+}
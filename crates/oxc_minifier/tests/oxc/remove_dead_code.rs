@@ -13,7 +13,7 @@ fn print(source_text: &str, remove_dead_code: bool) -> String {
         Compressor::new(&allocator, CompressOptions::dead_code_elimintation()).build(program);
     }
     CodeGenerator::new()
-        .with_options(CodegenOptions { single_quote: true })
+        .with_options(CodegenOptions { single_quote: true, ..CodegenOptions::default() })
         .build(program)
         .source_text
 }
@@ -51,9 +51,12 @@ fn dce_if_statement() {
     );
     test(
         "if (xxx) { foo } else if (false) { var a; var b; } else if (false) { var c; var d; }",
-        "if (xxx) { foo } else var c, d;",
+        "if (xxx) { foo } else { var c, d; var a, b; }",
     );
 
+    test("if (false) { var a = 1 } else { bar }", "{ { bar } var a }");
+    test("if (true) { foo } else { var a, b = 1 }", "{ { foo } var a, b }");
+
     test("if (!false) { foo }", "{ foo }");
     test("if (!true) { foo } else { bar }", "{ bar }");
 
@@ -74,6 +77,15 @@ fn dce_if_statement() {
         "function foo(undefined) { if (!undefined) { } }",
         "function foo(undefined) { if (!undefined) { } }",
     );
+    test("function foo() { return undefined }", "function foo() { }");
+    test(
+        "function foo(undefined) { return undefined }",
+        "function foo(undefined) { return undefined }",
+    );
+    test(
+        "function foo() { with (o) { (() => { return undefined })() } }",
+        "function foo() { with (o) { (() => { return undefined })() } }",
+    );
 
     test("if (true) { foo; } if (true) { foo; }", "{ foo; } { foo; }");
 
@@ -118,6 +130,70 @@ fn dce_logical_expression() {
     test("const foo = true && bar()", "const foo = bar()");
 }
 
+#[test]
+fn dce_constant_conditions() {
+    // Without `fold_constants`, as when only `process.env.NODE_ENV` is replaced.
+    let options =
+        CompressOptions { fold_constants: false, ..CompressOptions::dead_code_elimintation() };
+    let test =
+        |source_text, expected| crate::test_with_options(source_text, expected, options.clone());
+
+    test("if ('production' !== 'production') { foo() }", "");
+    test("if ('production' === 'production') { foo() } else { bar() }", "{ foo() }");
+    test("if ('production' !== 'production') { foo() } else if (x) { bar() }", "if (x) { bar() }");
+    test("if (!('a' == 'b') && true) { foo() }", "{ foo() }");
+    test("if (1 === 1 || x) { foo() }", "{ foo() }");
+    test("if (null === null) { foo() }", "{ foo() }");
+    // Conditions with side effects are evaluated.
+    test("if ((x(), true)) { foo() }", "if ((x(), true)) { foo() }");
+    test("if (x === 'production') { foo() }", "if (x === 'production') { foo() }");
+
+    test("x = 'a' === 'b' ? foo : bar", "x = bar");
+    test("x = 'a' === 'b' && foo", "x = 'a' === 'b'");
+    test("x = 'a' === 'a' && foo", "x = foo");
+    test("x = 'a' === 'a' || foo", "x = 'a' === 'a'");
+    test("x = 'a' === 'a' ?? foo", "x = 'a' === 'a' ?? foo");
+
+    // `var` declarations of the removed branch are hoisted.
+    test("if ('production' !== 'production') { var a = 1; foo() } else bar()", "{ bar(); var a }");
+    test("if ('production' === 'production') foo(); else { var a, b = 1 }", "{ foo(); var a, b }");
+    test("if ('production' !== 'production') { var a = 1 }", "var a");
+    test("if (true) bar(); else { let a = 1 }", "bar()");
+}
+
+#[test]
+fn dce_after_terminators() {
+    test("for (;;) { a(); { b(); break; c() } d() }", "for (;;) { a(); { b(); break; } }");
+    test(
+        "for (;;) { if (a) continue; else break; b() }",
+        "for (;;) { if (a) continue; else break; }",
+    );
+    test("switch (a) { case 1: b(); break; c() }", "switch (a) { case 1: b(); break; }");
+    // Function declarations are hoisted.
+    test(
+        "function f() { return g(); var a = 1; function g() {} }",
+        "function f() { return g(); function g() {} var a }",
+    );
+}
+
+#[test]
+fn dce_tail_returns() {
+    test("function f() { a(); return }", "function f() { a() }");
+    test("function f() { a(); return void 0 }", "function f() { a() }");
+    test("function* f() { yield 1; return }", "function* f() { yield 1 }");
+    test("x = () => { a(); return }", "x = () => { a() }");
+    test("function f() { if (a()) return }", "function f() { a() }");
+    test("function f() { if (a()) { return } }", "function f() { a() }");
+    test("function f() { if (a()) b(); else { return } }", "function f() { if (a()) b() }");
+    test(
+        "function f() { if (a) { b(); return } else { c(); return void 0 } }",
+        "function f() { if (a) { b() } else { c() } }",
+    );
+    test("function f() { if (a) return; b() }", "function f() { if (a) return; b() }");
+    test("function f() { return a }", "function f() { return a }");
+    test("function f() { return void a() }", "function f() { return void a() }");
+}
+
 // https://github.com/terser/terser/blob/master/test/compress/dead-code.js
 #[test]
 fn dce_from_terser() {
@@ -135,7 +211,6 @@ fn dce_from_terser() {
             a();
             b();
             x = 10;
-            return;
         }",
     );
 
@@ -182,3 +257,32 @@ fn dce_from_terser() {
         ",
     );
 }
+
+#[test]
+fn dce_class() {
+    test("class C { static { if (false) { foo() } } }", "class C {}");
+    test("class C { static { return_(); } static {} }", "class C { static { return_(); } }");
+    test(
+        "class C { static { foo(); throw 1; bar(); var x } }",
+        "class C { static { foo(); throw 1; var x } }",
+    );
+    test(
+        "class C { static x = false ? a() : b; y = true ? c : d }",
+        "class C { static x = b; y = c }",
+    );
+    test(
+        "class C { x = undefined; static y = void 0; accessor z = void 0 }",
+        "class C { x; static y; accessor z }",
+    );
+    // `undefined` may be shadowed or resolve to a property of the object of `with`.
+    test(
+        "let undefined = 1; class C { x = undefined }",
+        "let undefined = 1; class C { x = undefined }",
+    );
+    test(
+        "with (o) { class C { x = undefined; y = void 0 } }",
+        "with (o) { class C { x = undefined; y } }",
+    );
+    test("class C { @(false ? a : b) m() {} }", "class C { @b m() {} }");
+    test("@(true ? a : b) class C {}", "@a class C {}");
+}
@@ -0,0 +1,101 @@
+use oxc_allocator::Allocator;
+use oxc_minifier::{CompressOptions, Compressor, MinifierStats};
+use oxc_parser::Parser;
+use oxc_span::SourceType;
+
+use crate::run;
+
+fn options() -> CompressOptions {
+    CompressOptions { reorder_declarations: true, ..CompressOptions::all_false() }
+}
+
+fn test(source_text: &str, expected: &str) {
+    let source_type = SourceType::default().with_module(true);
+    let result = run(source_text, source_type, Some(options()));
+    let expected = run(expected, source_type, None);
+    assert_eq!(
+        result, expected,
+        "\nfor source {source_text:?}\nexpect {expected:?}\ngot    {result:?}"
+    );
+}
+
+fn test_same(source_text: &str) {
+    test(source_text, source_text);
+}
+
+fn stats(source_text: &str, options: CompressOptions, measure: bool) -> MinifierStats {
+    let allocator = Allocator::default();
+    let source_type = SourceType::default().with_module(true);
+    let ret = Parser::new(&allocator, source_text, source_type).parse();
+    let program = allocator.alloc(ret.program);
+    Compressor::new(&allocator, options).with_stats(measure).build(program).stats
+}
+
+#[test]
+fn group_similar_declarations() {
+    test(
+        "const a = {}; function f() {} const b = {}; function g() {}",
+        "const a = {}; const b = {}; function f() {} function g() {}",
+    );
+    test(
+        "const a = () => 1; let b = 1; const c = () => 2; let d = 2",
+        "const a = () => 1; const c = () => 2; let b = 1; let d = 2",
+    );
+    test(
+        "export const a = 1; const b = 1; export const c = 2",
+        "export const a = 1; export const c = 2; const b = 1",
+    );
+    test("class A {} const a = []; class B {}", "class A {} class B {} const a = []");
+    test_same("const a = {}; const b = {}; function f() {}");
+}
+
+#[test]
+fn keep_dependencies() {
+    // `b` uses `a`, `c` is used by `f`.
+    test_same("const a = 1; function f() { return c } const b = a; const c = 2");
+    test(
+        "const a = 1; function f() {} const b = a; function g() {}",
+        "const a = 1; function f() {} function g() {} const b = a;",
+    );
+    test(
+        "const x = {}; class B extends A {} const y = {}; class A {}",
+        "const x = {}; const y = {}; class B extends A {} class A {}",
+    );
+    // Redeclarations of `a` keep their order.
+    test(
+        "var b = {}; var a = 1; var c = {}; var a = {}",
+        "var b = {}; var c = {}; var a = 1; var a = {}",
+    );
+}
+
+#[test]
+fn keep_statements_with_side_effects() {
+    test_same("const a = {}; f(); const b = {}");
+    test_same("const a = {}; const x = f(); const b = {}");
+    test_same("const a = {}; const { x } = y; const b = {}");
+    test_same("const a = {}; class A { static x = f() } const b = {}");
+    test(
+        "const a = {}; function f() {} const b = {}; g(); const c = {}; function h() {} const d = {}",
+        "const a = {}; const b = {}; function f() {} g(); const c = {}; const d = {}; function h() {}",
+    );
+    // `eval` may read the bindings in any order.
+    test_same("const a = {}; function f() {} const b = {}; eval('')");
+}
+
+#[test]
+fn gzip_sizes() {
+    let source_text = "const a = { x: 1 }; function f() {} const b = { x: 1 }; function g() {}";
+    let stats = stats(source_text, options(), true);
+    let (Some(before), Some(after)) =
+        (stats.gzip_size_before_reorder, stats.gzip_size_after_reorder)
+    else {
+        panic!("expected gzip sizes, got {stats:?}");
+    };
+    assert!(before > 0 && after > 0);
+    assert_eq!(
+        self::stats(source_text, CompressOptions::all_false(), true),
+        MinifierStats::default()
+    );
+    // Only measured when asked for.
+    assert_eq!(self::stats(source_text, options(), false), MinifierStats::default());
+}
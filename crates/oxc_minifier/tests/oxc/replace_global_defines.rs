@@ -1,19 +1,30 @@
+use std::sync::Arc;
+
 use oxc_allocator::Allocator;
 use oxc_codegen::{CodeGenerator, CodegenOptions};
-use oxc_minifier::{ReplaceGlobalDefines, ReplaceGlobalDefinesConfig};
+use oxc_diagnostics::OxcDiagnostic;
+use oxc_minifier::{Env, ReplaceGlobalDefines, ReplaceGlobalDefinesConfig};
 use oxc_parser::Parser;
 use oxc_span::SourceType;
 
 use crate::run;
 
 pub(crate) fn test(source_text: &str, expected: &str, config: ReplaceGlobalDefinesConfig) {
-    let source_type = SourceType::default();
+    test_with_source_type(source_text, expected, config, SourceType::default());
+}
+
+fn test_with_source_type(
+    source_text: &str,
+    expected: &str,
+    config: ReplaceGlobalDefinesConfig,
+    source_type: SourceType,
+) {
     let allocator = Allocator::default();
     let ret = Parser::new(&allocator, source_text, source_type).parse();
     let program = allocator.alloc(ret.program);
     ReplaceGlobalDefines::new(&allocator, config).build(program);
     let result = CodeGenerator::new()
-        .with_options(CodegenOptions { single_quote: true })
+        .with_options(CodegenOptions { single_quote: true, ..CodegenOptions::default() })
         .build(program)
         .source_text;
     let expected = run(expected, source_type, None);
@@ -42,3 +53,118 @@ fn replace_global_definitions_dot() {
         test("foo.process.NODE_ENV", "foo.process.NODE_ENV", config);
     }
 }
+
+#[test]
+fn replace_global_definitions_import_meta() {
+    let test = |source_text, expected, config| {
+        test_with_source_type(
+            source_text,
+            expected,
+            config,
+            SourceType::default().with_module(true),
+        );
+    };
+    let config = ReplaceGlobalDefinesConfig::new(&[
+        ("import.meta.env.MODE", "'production'"),
+        ("import.meta.env.DEV", "false"),
+    ])
+    .unwrap();
+    test("import.meta.env.MODE", "'production'", config.clone());
+    test("if (import.meta.env.DEV) foo()", "if (false) foo()", config.clone());
+    test("(import.meta).env.DEV", "false", config.clone());
+    test("import.meta.env", "import.meta.env", config.clone());
+    test("import.meta.env.PROD", "import.meta.env.PROD", config.clone());
+    test("import.meta.url", "import.meta.url", config.clone());
+    test("meta.env.MODE", "meta.env.MODE", config);
+
+    let config = ReplaceGlobalDefinesConfig::new(&[("import.meta.env", "{}")]).unwrap();
+    test("import.meta.env.MODE", "({}).MODE", config);
+
+    let config = ReplaceGlobalDefinesConfig::new(&[("import.meta", "{}")]).unwrap();
+    test("import.meta.url", "({}).url", config);
+}
+
+fn render(diagnostics: Vec<OxcDiagnostic>, source_text: &str) -> String {
+    let source = Arc::new(source_text.to_string());
+    diagnostics
+        .into_iter()
+        .map(|diagnostic| format!("{:?}", diagnostic.with_source_code(Arc::clone(&source))))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[test]
+fn from_json() {
+    let config = ReplaceGlobalDefinesConfig::from_json(
+        r#"{ "process.env.NODE_ENV": "'production'", "DEBUG": false, "VERSION": 2 }"#,
+    )
+    .unwrap();
+    test("process.env.NODE_ENV, DEBUG, VERSION", "'production', false, 2", config);
+}
+
+#[test]
+fn from_iter() {
+    let defines = vec![(String::from("id"), String::from("text"))];
+    let config = ReplaceGlobalDefinesConfig::from_iter(defines).unwrap();
+    test("id", "text", config);
+}
+
+#[test]
+fn config_errors() {
+    let configs = [
+        "{",
+        "[]",
+        r#"{ "a-b": "1", "process..env": "1", "process.env.1": "1" }"#,
+        r#"{ "a": "", "b": "1 +", "c": "a b", "d": "(a", "e": "x; y" }"#,
+    ];
+    let snapshot = configs
+        .into_iter()
+        .map(|json| {
+            let errors = ReplaceGlobalDefinesConfig::from_json(json).unwrap_err();
+            format!("{json}\n\n{}\n", render(errors, json))
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    insta::with_settings!({ prepend_module_to_snapshot => false, omit_expression => true }, {
+        insta::assert_snapshot!("replace_global_defines_config_errors", snapshot);
+    });
+}
+
+#[test]
+fn shadowed_define_warnings() {
+    let source_text = "function f(DEBUG) { return DEBUG }\nlet process = {};\nconst x = DEBUG;";
+    let config =
+        ReplaceGlobalDefinesConfig::new(&[("DEBUG", "false"), ("process.env.NODE_ENV", "'a'")])
+            .unwrap();
+    let allocator = Allocator::default();
+    let ret = Parser::new(&allocator, source_text, SourceType::default()).parse();
+    let program = allocator.alloc(ret.program);
+    let ret = ReplaceGlobalDefines::new(&allocator, config).build(program);
+    insta::with_settings!({ prepend_module_to_snapshot => false, omit_expression => true }, {
+        insta::assert_snapshot!(
+            "replace_global_defines_shadowed",
+            render(ret.warnings, source_text)
+        );
+    });
+}
+
+#[test]
+fn env_define_warnings() {
+    let config = ReplaceGlobalDefinesConfig::new(&[
+        ("window", "undefined"),
+        ("process.env.NODE_ENV", "'a'"),
+    ])
+    .unwrap();
+    let allocator = Allocator::default();
+    let ret = Parser::new(&allocator, "x = window", SourceType::default()).parse();
+    let program = allocator.alloc(ret.program);
+    let ret =
+        ReplaceGlobalDefines::new(&allocator, config.clone()).with_env(Env::Node).build(program);
+    assert!(ret.warnings.is_empty());
+    let ret = ReplaceGlobalDefines::new(&allocator, config).with_env(Env::Browser).build(program);
+    assert_eq!(ret.warnings.len(), 1);
+    assert_eq!(
+        ret.warnings[0].to_string(),
+        "The define `window` replaces a global of the browser environment."
+    );
+}
@@ -0,0 +1,70 @@
+use oxc_minifier::ESTarget;
+
+use crate::{test_with_options, CompressOptions};
+
+fn test(source_text: &str, expected: &str) {
+    let options = CompressOptions { rest_parameters: true, ..CompressOptions::all_true() };
+    test_with_options(source_text, expected, options);
+}
+
+fn test_same(source_text: &str) {
+    test(source_text, source_text);
+}
+
+#[test]
+fn arguments_to_rest() {
+    test("function f() { return arguments[0] }", "function f(...args) { return args[0] }");
+    test(
+        "function f() { if (arguments.length > 1) g(...arguments) }",
+        "function f(...args) { if (args.length > 1) g(...args) }",
+    );
+    test(
+        "function f() { return g.apply(this, arguments) }",
+        "function f(...args) { return g.apply(this, args) }",
+    );
+    test(
+        "var f = function () { return () => arguments[0] }",
+        "var f = function (...args) { return () => args[0] }",
+    );
+    test(
+        "class C { m() { return arguments.length } }",
+        "class C { m(...args) { return args.length } }",
+    );
+    // The rest parameter does not shadow names used in the body.
+    test(
+        "function f() { return args + rest + arguments[0] }",
+        "function f(...a) { return args + rest + a[0] }",
+    );
+    // Only the outer function is rewritten.
+    test(
+        "function f() { return arguments[0] + function () { return arguments } }",
+        "function f(...args) { return args[0] + function () { return arguments } }",
+    );
+}
+
+#[test]
+fn arguments_unsafe() {
+    test_same("function f(a) { return arguments[0] }");
+    test_same("function f() { return arguments }");
+    test_same("function f() { return arguments.callee }");
+    test_same("function f() { arguments[0] = 1 }");
+    test_same("function f() { arguments[0]++ }");
+    test_same("function f() { delete arguments[0] }");
+    test_same("function f() { return arguments[0]() }");
+    test_same("function f() { 'use strict'; return arguments[0] }");
+    test_same("function f() { eval(''); return arguments[0] }");
+    test_same("function f() { var arguments; return arguments[0] }");
+    test_same("var o = { get x() { return arguments.length } }");
+    test_same("class C { set x(v) { arguments[0] } get y() { return arguments.length } }");
+}
+
+#[test]
+fn arguments_target() {
+    let options = CompressOptions {
+        rest_parameters: true,
+        target: ESTarget::ES5,
+        ..CompressOptions::all_true()
+    };
+    let source_text = "function f() { return arguments[0] }";
+    test_with_options(source_text, source_text, options);
+}
@@ -0,0 +1,74 @@
+use oxc_minifier::ESTarget;
+
+use crate::{test_with_options, CompressOptions};
+
+fn test(source_text: &str, expected: &str) {
+    let options = CompressOptions { shorthand: true, ..CompressOptions::all_true() };
+    test_with_options(source_text, expected, options);
+}
+
+fn test_same(source_text: &str) {
+    test(source_text, source_text);
+}
+
+#[test]
+fn shorthand_properties() {
+    test("x = { a: a, 'b': b, c: d }", "x = { a, b, c: d }");
+    test("x = { a: a, ...b }", "x = { a, ...b }");
+    test_same("x = { [a]: a }");
+    test_same("x = { __proto__: __proto__ }");
+    test("x = { '__proto__': __proto__ }", "x = { __proto__: __proto__ }");
+    test_same("x = { get a() { return a } }");
+}
+
+#[test]
+fn methods() {
+    test("x = { f: function () { return 1 } }", "x = { f() { return 1 } }");
+    test("x = { 'f': function (a) { return a } }", "x = { f(a) { return a } }");
+    test("x = { [f]: function () {} }", "x = { [f]() {} }");
+    test("x = { f: async function () {} }", "x = { async f() {} }");
+    test("x = { f: function* () {} }", "x = { *f() {} }");
+    test("x = { f: function () { return () => a } }", "x = { f() { return () => a } }");
+    test(
+        "x = { f: function () { return function () { return this } } }",
+        "x = { f() { return function () { return this } } }",
+    );
+    // Named functions have their own binding, and may be constructors.
+    test_same("x = { f: function f() {} }");
+    test_same("x = { f: function () { this.a = 1 } }");
+    test_same("x = { f: function () { return () => this } }");
+    test_same("x = { f: function () { return new.target } }");
+    test_same("x = { f: function (a = this) {} }");
+    test_same("x = { __proto__: function () {} }");
+    // Methods can not have duplicate parameters.
+    test_same("x = { f: function (a, a) {} }");
+    test_same("x = { f: () => {} }");
+}
+
+#[test]
+fn iterated_arrays() {
+    test("f(...[a, void 0, b])", "f(...[a, , b])");
+    test("x = [...[a, void 0]]", "x = [...[a, ,]]");
+    test("for (x of [void 0, a]) {}", "for (x of [, a]) {}");
+    test("var [a, b] = [1, void 0]", "var [a, b] = [1]");
+    test("[a, b] = [void 0, 1, void 0, void 0]", "[a, b] = [, 1]");
+    test("let [a = 1] = [void 0]", "let [a = 1] = []");
+    test("var [a, ...b] = [1, void 0]", "var [a, ...b] = [1, ,]");
+    test("[a, ...b] = [void 0, void 0]", "[a, ...b] = [, ,]");
+    // The length of arrays which are not only iterated is observable.
+    test_same("x = [a, void 0]");
+    // The value of the assignment is the array.
+    test_same("x = ([a, b] = [1, void 0])");
+    test_same("f([a] = [void 0, 1])");
+    test_same("x = { ...[a, void 0] }");
+    test_same("f([a, void 0])");
+}
+
+#[test]
+fn target() {
+    let options =
+        CompressOptions { shorthand: true, target: ESTarget::ES5, ..CompressOptions::all_true() };
+    for source in ["x = { f: function () {} }", "f(...[a, void 0])"] {
+        test_with_options(source, source, options.clone());
+    }
+}
@@ -0,0 +1,104 @@
+use oxc_minifier::CompressOptions;
+use oxc_span::SourceType;
+
+use crate::run;
+
+fn test_module(source_text: &str, expected: &str) {
+    let source_type = SourceType::default().with_module(true);
+    let result = run(source_text, source_type, Some(CompressOptions::all_true()));
+    let expected = run(expected, source_type, None);
+    assert_eq!(
+        result, expected,
+        "\nfor source {source_text:?}\nexpect {expected:?}\ngot    {result:?}"
+    );
+}
+
+fn test_module_same(source_text: &str) {
+    test_module(source_text, source_text);
+}
+
+#[test]
+fn typeof_function_declaration() {
+    test_module(
+        "function f() {} if (typeof f === 'function') a(); else b()",
+        "function f() {} a()",
+    );
+    test_module("function f() {} x = typeof f != 'function'", "function f() {} x = !1");
+    test_module("class C {} x = typeof C == 'object'", "class C {} x = !1");
+    test_module(
+        "export function f() {} x = typeof f === 'function'",
+        "export function f() {} x = !0",
+    );
+    test_module(
+        "export default function f() {} x = typeof f === 'function'",
+        "export default function f() {} x = !0",
+    );
+
+    // Reassigned or shadowed
+    test_module_same("function f() {} f = 1; x = typeof f === 'function'");
+    test_module_same("function f() {} ({ f } = o); x = typeof f === 'function'");
+    test_module_same("function f() {} function g(f) { x = typeof f === 'function' }");
+    test_module_same("function f() {} x = typeof g === 'function'");
+    // Classes throw before their declaration.
+    test_module_same("x = typeof C == 'object'; class C {}");
+    test_module_same("function f() { return typeof C == 'object' } class C {}");
+    // Direct `eval` can declare `var f` in a nested function.
+    test_module_same("function f() {} function g() { eval(s); x = typeof f === 'function' }");
+    // Other scripts can reassign top level bindings.
+    crate::test_same("function f() {} x = typeof f === 'function'");
+}
+
+#[test]
+fn typeof_unassigned_var() {
+    test_module("var window; if (typeof window === 'undefined') a(); else b()", "var window; a()");
+    test_module("export var x; y = typeof x != 'undefined'", "export var x; y = !1");
+    test_module("var x, y = 1; z = typeof x == 'object'", "var x, y = 1; z = !1");
+
+    // Initialized, reassigned, shadowed or block scoped
+    test_module_same("var x = 1; y = typeof x === 'object'");
+    test_module_same("var x; x = 1; y = typeof x === 'object'");
+    test_module_same("var x; for (x in o); y = typeof x === 'object'");
+    test_module_same("var x; function f(x) { y = typeof x === 'object' }");
+    test_module_same("let x; y = typeof x === 'object'");
+    crate::test_same("var x; y = typeof x === 'object'");
+}
+
+#[test]
+fn array_is_array() {
+    crate::test("x = Array.isArray([])", "x = !0");
+    crate::test("x = Array.isArray([1, 2])", "x = !0");
+    crate::test("x = Array.isArray({})", "x = !1");
+    crate::test("x = Array.isArray(new Array())", "x = !0");
+    crate::test("x = Array.isArray(new Map())", "x = !1");
+    crate::test("if (Array.isArray([])) a(); else b()", "a()");
+
+    crate::test_same("x = Array.isArray([a()])");
+    crate::test_same("x = Array.isArray(a)");
+    crate::test_same("x = Array.isArray(new Array(1))");
+    crate::test_same("x = Array?.isArray([])");
+    crate::test_same("var Array; x = Array.isArray([])");
+}
+
+#[test]
+fn instanceof_new() {
+    crate::test("x = new Map() instanceof Map", "x = !0");
+    crate::test("x = new Error() instanceof Error", "x = !0");
+    test_module("class C {} x = new C() instanceof C", "class C {} x = !0");
+    test_module(
+        "class C { a = 1; constructor() {} m() {} } if (new C(1) instanceof C) a(); else b()",
+        "class C { a = 1; m() {} } a()",
+    );
+
+    crate::test_same("x = new Map(a) instanceof Map");
+    crate::test_same("x = new Map() instanceof Set");
+    crate::test_same("x = new Foo() instanceof Foo");
+    crate::test_same("let Map = Foo; x = new Map() instanceof Map");
+    // Constructors may return another object.
+    test_module_same("class C { constructor() { return {} } } x = new C() instanceof C");
+    test_module_same("class C extends D {} x = new C() instanceof C");
+    test_module_same("class C { a = f() } x = new C() instanceof C");
+    test_module_same("class C { static [Symbol.hasInstance]() {} } x = new C() instanceof C");
+    test_module_same("class C {} x = new C(f()) instanceof C");
+    test_module_same("x = new C() instanceof C; class C {}");
+    crate::test_same("class C {} x = new C() instanceof C");
+}
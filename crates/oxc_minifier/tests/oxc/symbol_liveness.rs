@@ -0,0 +1,128 @@
+use std::fmt::Write;
+
+use oxc_allocator::Allocator;
+use oxc_codegen::WhitespaceRemover;
+use oxc_minifier::{CompressOptions, Compressor, SymbolLiveness};
+use oxc_parser::Parser;
+use oxc_span::SourceType;
+
+fn minify(source_text: &str, unused_exports: &[&str]) -> (String, SymbolLiveness) {
+    let allocator = Allocator::default();
+    let source_type = SourceType::default().with_module(true);
+    let ret = Parser::new(&allocator, source_text, source_type).parse();
+    let program = allocator.alloc(ret.program);
+    let options = CompressOptions {
+        unused: true,
+        unused_exports: unused_exports.iter().map(ToString::to_string).collect(),
+        ..CompressOptions::all_false()
+    };
+    let ret = Compressor::new(&allocator, options).with_symbol_liveness(true).build(program);
+    (WhitespaceRemover::new().build(program).source_text, ret.symbol_liveness.unwrap())
+}
+
+/// Symbols as `name [exports] <- import: dependencies`, with a `!` after roots.
+fn describe(liveness: &SymbolLiveness) -> Vec<String> {
+    let symbols = liveness.symbols();
+    symbols
+        .iter()
+        .map(|symbol| {
+            let mut description = symbol.name.to_string();
+            if symbol.is_root {
+                description.push('!');
+            }
+            if !symbol.exports.is_empty() {
+                write!(description, " {:?}", symbol.exports).unwrap();
+            }
+            if let Some((source, name)) = &symbol.import {
+                write!(description, " <- {source}.{name}").unwrap();
+            }
+            if !symbol.dependencies.is_empty() {
+                let names = symbol.dependencies.iter().map(|&i| symbols[i].name.as_str());
+                write!(description, ": {}", names.collect::<Vec<_>>().join(", ")).unwrap();
+            }
+            description
+        })
+        .collect()
+}
+
+#[test]
+fn symbols() {
+    let (_, liveness) = minify(
+        "import { x as y } from 'x'; import * as ns from 'ns'; import def from 'def';
+         function f() { return g() + y } function g() { return g() }
+         const a = f, b = def(); export { a as default, b, f }; ns.run();",
+        &[],
+    );
+    assert_eq!(
+        describe(&liveness),
+        [
+            "y <- x.x",
+            "ns! <- ns.*",
+            "def <- def.default",
+            "f [\"f\"]: y, g",
+            "g",
+            "a [\"default\"]: f",
+            "b! [\"b\"]: def",
+        ]
+    );
+    assert_eq!(liveness.exports().collect::<Vec<_>>(), ["f", "default", "b"]);
+}
+
+#[test]
+fn live() {
+    let (_, liveness) = minify(
+        "import { x } from 'x'; import { y } from 'y';
+         export function f() { return x } export function g() { return y } export const h = g;",
+        &[],
+    );
+    let live = liveness.live(|name| name == "h");
+    let names = liveness
+        .symbols()
+        .iter()
+        .zip(live)
+        .filter(|(_, live)| *live)
+        .map(|(symbol, _)| symbol.name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["y", "g", "h"]);
+    assert_eq!(liveness.live_imports(|name| name == "f"), [("x", "x")]);
+    assert_eq!(liveness.live_imports(|_| false), []);
+}
+
+#[test]
+fn re_exports() {
+    let (output, liveness) = minify(
+        "export { a, b as c } from 'm'; export * from 'n'; export * as ns from 'o'; export {}",
+        &["a", "c", "ns"],
+    );
+    assert_eq!(output, "export {a,b as c}from\"m\";export * from \"n\";export * as ns from \"o\";");
+    assert_eq!(describe(&liveness), ["a! <- m.a", "c! <- m.b", "ns! <- o.*", "*! <- n.*"]);
+    assert_eq!(liveness.exports().count(), 0);
+    assert_eq!(liveness.live_imports(|_| false), [("m", "a"), ("m", "b"), ("o", "*"), ("n", "*")]);
+}
+
+#[test]
+fn eval_makes_every_symbol_a_root() {
+    let (_, liveness) = minify("function f() {} export function g() {} eval('f()')", &[]);
+    assert!(liveness.symbols().iter().all(|symbol| symbol.is_root));
+}
+
+/// Two chunks, where `a` imports `used` from `b`: after exchanging liveness, `b` is minified
+/// again without its other exports.
+#[test]
+fn cross_chunk_iteration() {
+    let a = "import { used } from './b'; used();";
+    let b = "import { helper } from './c';
+             export function used() {} export function unused() { return helper() }";
+    let (_, a_liveness) = minify(a, &[]);
+    let (_, b_liveness) = minify(b, &[]);
+    let imported = a_liveness.live_imports(|_| false);
+    assert_eq!(imported, [("./b", "used")]);
+    let unused = b_liveness
+        .exports()
+        .filter(|name| !imported.iter().any(|(source, import)| *source == "./b" && import == name))
+        .collect::<Vec<_>>();
+    assert_eq!(unused, ["unused"]);
+    let (b_output, b_liveness) = minify(b, &unused);
+    assert_eq!(b_output, "import {helper} from \"./c\";export function used(){}");
+    assert_eq!(b_liveness.live_imports(|name| name == "used"), []);
+}
@@ -0,0 +1,76 @@
+use oxc_minifier::ESTarget;
+
+use crate::{test_with_options, CompressOptions};
+
+fn test(source_text: &str, expected: &str) {
+    let options = CompressOptions { template_literals: true, ..CompressOptions::all_true() };
+    test_with_options(source_text, expected, options);
+}
+
+fn test_same(source_text: &str) {
+    test(source_text, source_text);
+}
+
+#[test]
+fn concatenation_to_template() {
+    test("'a' + x + 'b'", "`a${x}b`");
+    test("'a' + x + 'b' + y + 'c'", "`a${x}b${y}c`");
+    test("x + 'a' + y + 'b' + z + 'c' + w + 'd'", "`${x}a${y}b${z}c${w}d`");
+    test_same("x + 'a' + y + 'b'");
+    test("'a' + (x ? y : z) + 'b'", "`a${x ? y : z}b`");
+    test("'a' + `b${x}c` + 'd' + y + 'e'", "`ab${x}cd${y}e`");
+    test("'`' + x + 'a' + y + 'b'", "`\\`${x}a${y}b`");
+    test("'${' + x + 'a' + y + 'b'", "`\\${${x}a${y}b`");
+    test_same("x + 'px'");
+    test_same("'a' + x");
+    test_same("x + y");
+}
+
+#[test]
+fn es5_target() {
+    let options = CompressOptions {
+        template_literals: true,
+        target: ESTarget::ES5,
+        ..CompressOptions::all_true()
+    };
+    // Template literals are ES2015 syntax, but existing ones can still become strings.
+    test_with_options("'a' + x + 'b' + y + 'c'", "'a' + x + 'b' + y + 'c'", options.clone());
+    test_with_options("`${x}px`", "x + 'px'", options);
+}
+
+#[test]
+fn numeric_addition_prefix() {
+    // `1 + x` may be a numeric addition, so it stays grouped.
+    test("1 + x + 'a' + y + 'b' + z + 'c' + w + 'd'", "`${1 + x}a${y}b${z}c${w}d`");
+    test("'a' + (1 + x) + 'b'", "`a${1 + x}b`");
+    test_same("1 + x + 'a'");
+}
+
+#[test]
+fn template_to_concatenation() {
+    test("`${x}px`", "x + 'px'");
+    test("`a${x}`", "'a' + x");
+    test("`${x}`", "'' + x");
+    test("`${x}${y}`", "'' + x + y");
+    test("`${typeof x}${y}`", "typeof x + y");
+    test("`${x}a${y}`", "x + 'a' + y");
+    test_same("`a${x}b`");
+    test_same("`${x ? y : z}px`");
+    test_same("tag`${x}px`");
+}
+
+#[test]
+fn constant_substitutions() {
+    test("`abc`", "'abc'");
+    test("`a${1}b${'c'}`", "'a1bc'");
+    test("`a${1}b${x}c`", "`a1b${x}c`");
+    test("`a${true}${null}${-1}${`b`}`", "'atruenull-1b'");
+    test("`${1}${x}`", "'1' + x");
+    test("`a${'${'}b${x}c`", "`a\\${b${x}c`");
+    test("`a${'`'}`", "'a`'");
+    test_same("`a\nb`");
+    test_same("`'\"`");
+    test_same("`a${1n}b`");
+    test_same("`a${{}}b`");
+    test_same("tag`a${1}b`");
+}
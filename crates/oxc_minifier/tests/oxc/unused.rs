@@ -0,0 +1,102 @@
+use oxc_minifier::CompressOptions;
+use oxc_span::SourceType;
+
+use crate::run;
+
+fn test(source_text: &str, expected: &str) {
+    let source_type = SourceType::default().with_module(true);
+    let options = CompressOptions { unused: true, ..CompressOptions::all_false() };
+    let result = run(source_text, source_type, Some(options));
+    let expected = run(expected, source_type, None);
+    assert_eq!(
+        result, expected,
+        "\nfor source {source_text:?}\nexpect {expected:?}\ngot    {result:?}"
+    );
+}
+
+fn test_same(source_text: &str) {
+    test(source_text, source_text);
+}
+
+#[test]
+fn remove_unused_declarations() {
+    test("function unused() {} f()", "f()");
+    test("class Unused { m() {} static x = 1 } f()", "f()");
+    test("const a = 1, b = () => {}; let c; var d = function () {}; f()", "f()");
+    test("const a = 1, b = g(); f(a)", "const a = 1, b = g(); f(a)");
+    // Recursive functions and declarations only used by removed ones.
+    test("function fact(n) { return n ? n * fact(n - 1) : 1 }", "");
+    test(
+        "const a = 1; function b() { return a } class C extends D { m() { b() } } function D() {}",
+        "",
+    );
+    test(
+        "const a = 1; function b() { return a } f(b)",
+        "const a = 1; function b() { return a } f(b)",
+    );
+}
+
+#[test]
+fn keep_used_declarations() {
+    test_same("function f() {} export { f }");
+    test_same("export function f() {} export class C {} export const a = 1");
+    test_same("function f() {} export default f");
+    test_same("const a = 1; g(() => a)");
+    test_same("let a; a = 1");
+    // `eval` may reference any declaration.
+    test_same("function f() {} eval('f()')");
+}
+
+#[test]
+fn keep_side_effects() {
+    test_same("const a = f()");
+    test_same("const { a } = b");
+    test_same("class A { static x = f() }");
+    test_same("class A { static { f() } }");
+    test_same("class A { [f()]() {} }");
+    test_same("@d class A {}");
+    test_same("class A extends B {}");
+    test_same("let B = class {}; class A extends B {}");
+    test_same("async function B() {} class A extends B {}");
+    test_same("const a = class { static { f() } }");
+}
+
+#[test]
+fn remove_unused_exports() {
+    let test = |source_text: &str, unused_exports: &[&str], expected: &str| {
+        let source_type = SourceType::default().with_module(true);
+        let unused_exports = unused_exports.iter().map(ToString::to_string).collect();
+        let options =
+            CompressOptions { unused: true, unused_exports, ..CompressOptions::all_false() };
+        assert_eq!(run(source_text, source_type, Some(options)), run(expected, source_type, None));
+    };
+    test("export function f() {} export const a = 1", &["f"], "export const a = 1");
+    test("export const a = 1, b = 2", &["a"], "export const a = 1, b = 2");
+    test("export const a = 1, b = f()", &["a", "b"], "const b = f()");
+    test("function f() {} export { f, f as g }", &["f"], "function f() {} export { f as g }");
+    test("function f() {} export { f as g }; f()", &["g"], "function f() {} f()");
+    test(
+        "export default function f() {} export { f as g }",
+        &["default"],
+        "function f() {} export { f as g }",
+    );
+    test("const a = 1; export default a", &["default"], "");
+    test("export default class {}", &["default"], "export default class {}");
+    // Re-exports are roots of the symbol liveness.
+    test(
+        "export { a } from 'a'; export * from 'b'",
+        &["a"],
+        "export { a } from 'a'; export * from 'b'",
+    );
+    test("export function f() {}", &["g"], "export function f() {}");
+}
+
+#[test]
+fn scripts_are_unchanged() {
+    let options = CompressOptions { unused: true, ..CompressOptions::all_false() };
+    let source_text = "function unused() {} f()";
+    assert_eq!(
+        run(source_text, SourceType::default(), Some(options)),
+        run(source_text, SourceType::default(), None)
+    );
+}
@@ -0,0 +1,52 @@
+use oxc_allocator::Allocator;
+use oxc_codegen::CodeGenerator;
+use oxc_minifier::{Minifier, MinifierOptions};
+use oxc_parser::Parser;
+use oxc_span::SourceType;
+
+fn minify(source_text: &str) -> (String, oxc_minifier::MinifierReturn) {
+    let allocator = Allocator::default();
+    let source_type = SourceType::default().with_module(true);
+    let ret = Parser::new(&allocator, source_text, source_type).parse();
+    let program = allocator.alloc(ret.program);
+    let options = MinifierOptions {
+        validate_output: true,
+        validate_exports: true,
+        ..MinifierOptions::default()
+    };
+    let mut ret = Minifier::new(options).build(&allocator, program);
+    let output = CodeGenerator::new().with_mangler(ret.mangler.take()).build(program).source_text;
+    (output, ret)
+}
+
+#[test]
+fn valid_output() {
+    let (output, ret) = minify(
+        "export const a = 1; export { b as c }; function b() {} export default 1; export * from 'd'; export * as e from 'e'",
+    );
+    assert!(ret.validate_output(&output).is_ok(), "{output}");
+}
+
+#[test]
+fn syntax_error() {
+    let (_, ret) = minify("export const a = 1");
+    let error = ret.validate_output("export const a = ;").unwrap_err();
+    assert!(error.message.starts_with("Minified output is not valid"), "{error}");
+}
+
+#[test]
+fn changed_exports() {
+    let (_, ret) = minify("export const a = 1; export { a as b }");
+    let error = ret.validate_output("export const a = 1;").unwrap_err();
+    assert_eq!(error.help.as_deref(), Some(r#"Missing exports: ["b"], added exports: []"#));
+}
+
+#[test]
+fn disabled() {
+    let allocator = Allocator::default();
+    let ret = Parser::new(&allocator, "a", SourceType::default()).parse();
+    let program = allocator.alloc(ret.program);
+    let ret = Minifier::new(MinifierOptions::default()).build(&allocator, program);
+    assert!(ret.validator.is_none());
+    assert!(ret.validate_output("export const a = ;").is_ok());
+}
@@ -67,7 +67,11 @@ impl TestCase {
         }
 
         let source_type = SourceType::default();
-        let options = MinifierOptions { mangle: false, compress: self.compress_options };
+        let options = MinifierOptions {
+            mangle: false,
+            compress: self.compress_options,
+            ..MinifierOptions::default()
+        };
         let minified_source_text = minify(self.input.as_ref(), source_type, options);
         assert_eq!(
             remove_whitespace(minified_source_text.as_str()),
@@ -1,4 +1,8 @@
-use oxc_syntax::identifier::is_line_terminator;
+use oxc_span::Span;
+use oxc_syntax::{
+    identifier::is_line_terminator,
+    regexp_flags::{flag_errors, FlagErrorKind},
+};
 
 use super::{Kind, Lexer, RegExpFlags, Token};
 use crate::diagnostics;
@@ -56,21 +60,29 @@ impl<'a> Lexer<'a> {
         }
 
         let pattern_end = self.offset() - 1; // -1 to exclude `/`
+        let flags_start = self.source.position();
         let mut flags = RegExpFlags::empty();
 
         while let Some(b @ (b'$' | b'_' | b'a'..=b'z' | b'A'..=b'Z' | b'0'..=b'9')) =
             self.peek_byte()
         {
             self.consume_char();
-            let Ok(flag) = RegExpFlags::try_from(b) else {
-                self.error(diagnostics::reg_exp_flag(b as char, self.current_offset()));
-                continue;
-            };
-            if flags.contains(flag) {
-                self.error(diagnostics::reg_exp_flag_twice(b as char, self.current_offset()));
-                continue;
+            if let Ok(flag) = RegExpFlags::try_from(b) {
+                flags |= flag;
+            }
+        }
+
+        let flags_offset = self.source.offset_of(flags_start);
+        for error in flag_errors(self.source.str_from_pos_to_current(flags_start)) {
+            #[allow(clippy::cast_possible_truncation)]
+            let offset = flags_offset + error.offset as u32 + 1;
+            let span = Span::new(offset, offset);
+            match error.kind {
+                FlagErrorKind::Unknown(c) => self.error(diagnostics::reg_exp_flag(c, span)),
+                FlagErrorKind::Duplicate(c) => self.error(diagnostics::reg_exp_flag_twice(c, span)),
+                // Reported by the semantic checker, for the whole literal.
+                FlagErrorKind::UnicodeAndUnicodeSets => {}
             }
-            flags |= flag;
         }
 
         (pattern_end, flags)
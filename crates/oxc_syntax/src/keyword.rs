@@ -10,6 +10,13 @@ pub fn is_reserved_keyword(s: &str) -> bool {
     RESERVED_KEYWORDS.contains(s)
 }
 
+/// Checks words which can not be used as property names after a `.` or as object literal keys in
+/// ES3, e.g. `a.class` or `{ int: 1 }`.
+#[inline]
+pub fn is_es3_reserved_word(s: &str) -> bool {
+    ES3_RESERVED_WORDS.contains(s)
+}
+
 /// Checks `Infinity`, `NaN`, `globalThis` and `undefined`
 #[inline]
 pub fn is_global_object(s: &str) -> bool {
@@ -80,3 +87,71 @@ pub const RESERVED_KEYWORDS: Set<&'static str> = phf_set! {
     "with",
     "yield",
 };
+
+/// Reserved words of ES3, which includes many future reserved words that were later removed.
+///
+/// Reference: <https://www-archive.mozilla.org/js/language/E262-3.pdf> section 7.5.1
+pub const ES3_RESERVED_WORDS: Set<&'static str> = phf_set! {
+    // keywords
+    "break",
+    "case",
+    "catch",
+    "continue",
+    "default",
+    "delete",
+    "do",
+    "else",
+    "finally",
+    "for",
+    "function",
+    "if",
+    "in",
+    "instanceof",
+    "new",
+    "return",
+    "switch",
+    "this",
+    "throw",
+    "try",
+    "typeof",
+    "var",
+    "void",
+    "while",
+    "with",
+    // future reserved words
+    "abstract",
+    "boolean",
+    "byte",
+    "char",
+    "class",
+    "const",
+    "debugger",
+    "double",
+    "enum",
+    "export",
+    "extends",
+    "final",
+    "float",
+    "goto",
+    "implements",
+    "import",
+    "int",
+    "interface",
+    "long",
+    "native",
+    "package",
+    "private",
+    "protected",
+    "public",
+    "short",
+    "static",
+    "super",
+    "synchronized",
+    "throws",
+    "transient",
+    "volatile",
+    // literals
+    "null",
+    "true",
+    "false",
+};
@@ -10,6 +10,7 @@ pub mod number;
 pub mod operator;
 pub mod precedence;
 pub mod reference;
+pub mod regexp_flags;
 pub mod scope;
 pub mod symbol;
 pub mod xml_entities;
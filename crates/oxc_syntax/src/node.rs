@@ -4,7 +4,7 @@ use nonmax::NonMaxU32;
 #[cfg(feature = "serialize")]
 use serde::{Serialize, Serializer};
 
-use oxc_index::Idx;
+use oxc_index::{Idx, IdxDomain};
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct AstNodeId(NonMaxU32);
@@ -35,6 +35,10 @@ impl AstNodeId {
     }
 }
 
+impl IdxDomain for AstNodeId {
+    type Domain = Self;
+}
+
 impl Idx for AstNodeId {
     #[allow(clippy::cast_possible_truncation)]
     fn from_usize(idx: usize) -> Self {
@@ -3,11 +3,15 @@ use nonmax::NonMaxU32;
 #[cfg(feature = "serialize")]
 use serde::{Serialize, Serializer};
 
-use oxc_index::Idx;
+use oxc_index::{Idx, IdxDomain};
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct ReferenceId(NonMaxU32);
 
+impl IdxDomain for ReferenceId {
+    type Domain = Self;
+}
+
 impl Idx for ReferenceId {
     #[allow(clippy::cast_possible_truncation)]
     fn from_usize(idx: usize) -> Self {
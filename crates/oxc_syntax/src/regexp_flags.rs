@@ -0,0 +1,142 @@
+//! Validation of the flags of a regular expression on their own, without an allocator or a
+//! parser. The lexer checks the flags of every regular expression literal, and `new RegExp()`
+//! calls with constant flags can be checked the same way.
+//!
+//! See <https://tc39.es/ecma262/#sec-regexpinitialize>
+
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlagErrorKind {
+    /// A character which is not a flag.
+    Unknown(char),
+    /// A flag which is mentioned again.
+    Duplicate(char),
+    /// Both `u` and `v`, the second of them is reported.
+    UnicodeAndUnicodeSets,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FlagError {
+    pub kind: FlagErrorKind,
+    /// Byte offset of the character in the flags.
+    pub offset: usize,
+}
+
+impl fmt::Display for FlagError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            FlagErrorKind::Unknown(c) => write!(f, "Unexpected flag {c} in regular expression"),
+            FlagErrorKind::Duplicate(c) => {
+                write!(f, "Flag {c} is mentioned twice in regular expression")
+            }
+            FlagErrorKind::UnicodeAndUnicodeSets => {
+                write!(f, "Flags u and v can not be used together in regular expression")
+            }
+        }
+    }
+}
+
+/// Whether `flags` are valid flags of a regular expression: known flags, each at most once, and
+/// not both `u` and `v`.
+pub fn is_valid_regex_flags(flags: &str) -> bool {
+    flag_errors(flags).next().is_none()
+}
+
+/// The errors in `flags`, in order.
+pub fn flag_errors(flags: &str) -> FlagErrors<'_> {
+    FlagErrors { chars: flags.char_indices(), seen: 0 }
+}
+
+/// Iterator returned by [`flag_errors`].
+#[derive(Debug, Clone)]
+pub struct FlagErrors<'a> {
+    chars: std::str::CharIndices<'a>,
+    /// Bits of the flags seen so far, see [`flag_bit`].
+    seen: u8,
+}
+
+const UNICODE: u8 = 1 << 6;
+const UNICODE_SETS: u8 = 1 << 7;
+
+fn flag_bit(c: char) -> Option<u8> {
+    let bit = match c {
+        'd' => 1 << 0,
+        'g' => 1 << 1,
+        'i' => 1 << 2,
+        'm' => 1 << 3,
+        's' => 1 << 4,
+        'y' => 1 << 5,
+        'u' => UNICODE,
+        'v' => UNICODE_SETS,
+        _ => return None,
+    };
+    Some(bit)
+}
+
+impl<'a> Iterator for FlagErrors<'a> {
+    type Item = FlagError;
+
+    fn next(&mut self) -> Option<FlagError> {
+        for (offset, c) in self.chars.by_ref() {
+            let kind = match flag_bit(c) {
+                None => FlagErrorKind::Unknown(c),
+                Some(bit) if self.seen & bit != 0 => FlagErrorKind::Duplicate(c),
+                Some(bit) => {
+                    self.seen |= bit;
+                    if bit & (UNICODE | UNICODE_SETS) != 0
+                        && self.seen & (UNICODE | UNICODE_SETS) == UNICODE | UNICODE_SETS
+                    {
+                        FlagErrorKind::UnicodeAndUnicodeSets
+                    } else {
+                        continue;
+                    }
+                }
+            };
+            return Some(FlagError { kind, offset });
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{flag_errors, is_valid_regex_flags, FlagError, FlagErrorKind};
+
+    #[test]
+    fn valid() {
+        for flags in ["", "g", "dgimsuy", "dgimsvy", "yvsmigd"] {
+            assert!(is_valid_regex_flags(flags), "{flags}");
+        }
+    }
+
+    #[test]
+    fn errors() {
+        let errors = |flags| flag_errors(flags).collect::<Vec<_>>();
+        assert_eq!(
+            errors("gxg"),
+            [
+                FlagError { kind: FlagErrorKind::Unknown('x'), offset: 1 },
+                FlagError { kind: FlagErrorKind::Duplicate('g'), offset: 2 },
+            ]
+        );
+        assert_eq!(
+            errors("uiv"),
+            [FlagError { kind: FlagErrorKind::UnicodeAndUnicodeSets, offset: 2 }]
+        );
+        // A duplicate `u` is not reported as a conflict again.
+        assert_eq!(
+            errors("vuu"),
+            [
+                FlagError { kind: FlagErrorKind::UnicodeAndUnicodeSets, offset: 1 },
+                FlagError { kind: FlagErrorKind::Duplicate('u'), offset: 2 },
+            ]
+        );
+        assert_eq!(errors("é"), [FlagError { kind: FlagErrorKind::Unknown('é'), offset: 0 }]);
+        assert!(!is_valid_regex_flags("G"));
+        assert_eq!(
+            errors("uv")[0].to_string(),
+            "Flags u and v can not be used together in regular expression"
+        );
+    }
+}
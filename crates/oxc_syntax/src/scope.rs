@@ -3,7 +3,7 @@ use nonmax::NonMaxU32;
 #[cfg(feature = "serialize")]
 use serde::{Serialize, Serializer};
 
-use oxc_index::Idx;
+use oxc_index::{Idx, IdxDomain};
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct ScopeId(NonMaxU32);
@@ -32,6 +32,10 @@ impl ScopeId {
     }
 }
 
+impl IdxDomain for ScopeId {
+    type Domain = Self;
+}
+
 impl Idx for ScopeId {
     #[allow(clippy::cast_possible_truncation)]
     fn from_usize(idx: usize) -> Self {
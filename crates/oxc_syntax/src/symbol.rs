@@ -3,11 +3,15 @@ use nonmax::NonMaxU32;
 #[cfg(feature = "serialize")]
 use serde::{Serialize, Serializer};
 
-use oxc_index::Idx;
+use oxc_index::{Idx, IdxDomain};
 
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct SymbolId(NonMaxU32);
 
+impl IdxDomain for SymbolId {
+    type Domain = Self;
+}
+
 impl Idx for SymbolId {
     #[allow(clippy::cast_possible_truncation)]
     fn from_usize(idx: usize) -> Self {
@@ -32,6 +36,10 @@ impl Serialize for SymbolId {
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct RedeclarationId(NonMaxU32);
 
+impl IdxDomain for RedeclarationId {
+    type Domain = Self;
+}
+
 impl Idx for RedeclarationId {
     #[allow(clippy::cast_possible_truncation)]
     fn from_usize(idx: usize) -> Self {
@@ -280,6 +280,7 @@ impl Oxc {
                 } else {
                     CompressOptions::all_false()
                 },
+                ..MinifierOptions::default()
             };
             Minifier::new(options).build(&allocator, program);
         }
@@ -23,7 +23,10 @@ pub fn isolated_declaration(filename: String, source_text: String) -> IsolatedDe
     let allocator = Allocator::default();
     let parser_ret = Parser::new(&allocator, &source_text, source_type).parse();
     let transformed_ret = IsolatedDeclarations::new(&allocator).build(&parser_ret.program);
-    let printed = CodeGenerator::new().build(&transformed_ret.program).source_text;
+    let printed = CodeGenerator::new()
+        .with_source_text(&source_text)
+        .build(&transformed_ret.program)
+        .source_text;
 
     let mut errors = vec![];
     if !parser_ret.errors.is_empty() || !transformed_ret.errors.is_empty() {
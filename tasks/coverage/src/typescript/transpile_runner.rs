@@ -172,6 +172,7 @@ fn transpile(path: &Path, source_text: &str) -> (String, Vec<OxcDiagnostic>) {
     let source_type = SourceType::from_path(path).unwrap();
     let ret = Parser::new(&allocator, source_text, source_type).parse();
     let ret = IsolatedDeclarations::new(&allocator).build(&ret.program);
-    let printed = CodeGenerator::new().build(&ret.program).source_text;
+    let printed =
+        CodeGenerator::new().with_source_text(source_text).build(&ret.program).source_text;
     (printed, ret.errors)
 }
@@ -98,6 +98,7 @@ fn minify_twice(file: &TestFile) -> String {
     let options = MinifierOptions {
         mangle: true,
         compress: CompressOptions { evaluate: false, ..CompressOptions::default() },
+        ..MinifierOptions::default()
     };
     // let source_text1 = minify(&file.source_text, source_type, options);
     // let source_text2 = minify(&source_text1, source_type, options);
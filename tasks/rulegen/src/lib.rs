@@ -0,0 +1,976 @@
+//! Scaffolding for porting lint rules from ESLint and its plugins.
+//!
+//! [`scaffold`] generates the rule skeleton with the tests of the original rule ported to
+//! [`Tester`](https://github.com/oxc-project/oxc/blob/main/crates/oxc_linter/src/tester.rs) cases,
+//! and the diff registering the rule in `crates/oxc_linter/src/rules.rs`.
+
+use std::{
+    collections::HashMap,
+    fmt::{self, Display, Formatter},
+    path::{Path, PathBuf},
+};
+
+use convert_case::{Case, Casing};
+use oxc_allocator::Allocator;
+use oxc_ast::{
+    ast::{
+        Argument, ArrayExpressionElement, CallExpression, ExportDefaultDeclarationKind, Expression,
+        ExpressionStatement, ObjectExpression, ObjectProperty, ObjectPropertyKind, Program,
+        PropertyKey, Statement, StaticMemberExpression, StringLiteral, TaggedTemplateExpression,
+        TemplateLiteral,
+    },
+    Visit,
+};
+use oxc_parser::Parser;
+use oxc_span::{GetSpan, SourceType, Span};
+use serde::Serialize;
+
+mod json;
+mod template;
+
+const ESLINT_TEST_PATH: &str =
+    "https://raw.githubusercontent.com/eslint/eslint/main/tests/lib/rules";
+
+const JEST_TEST_PATH: &str =
+    "https://raw.githubusercontent.com/jest-community/eslint-plugin-jest/main/src/rules/__tests__";
+
+const TYPESCRIPT_ESLINT_TEST_PATH: &str = "https://raw.githubusercontent.com/typescript-eslint/typescript-eslint/main/packages/eslint-plugin/tests/rules";
+
+const UNICORN_TEST_PATH: &str =
+    "https://raw.githubusercontent.com/sindresorhus/eslint-plugin-unicorn/main/test";
+
+const REACT_TEST_PATH: &str =
+    "https://raw.githubusercontent.com/jsx-eslint/eslint-plugin-react/master/tests/lib/rules";
+
+const JSX_A11Y_TEST_PATH: &str =
+    "https://raw.githubusercontent.com/jsx-eslint/eslint-plugin-jsx-a11y/main/__tests__/src/rules";
+
+const NEXT_JS_TEST_PATH: &str =
+    "https://raw.githubusercontent.com/vercel/next.js/canary/test/unit/eslint-plugin-next";
+
+const JSDOC_TEST_PATH: &str =
+    "https://raw.githubusercontent.com/gajus/eslint-plugin-jsdoc/main/test/rules/assertions";
+
+const REACT_PERF_TEST_PATH: &str =
+    "https://raw.githubusercontent.com/cvazac/eslint-plugin-react-perf/main/tests/lib/rules";
+
+const NODE_TEST_PATH: &str =
+    "https://raw.githubusercontent.com/eslint-community/eslint-plugin-n/master/tests/lib/rules";
+
+const TREE_SHAKING_PATH: &str =
+    "https://raw.githubusercontent.com/lukastaegert/eslint-plugin-tree-shaking/master/src/rules";
+
+const PROMISE_TEST_PATH: &str =
+    "https://raw.githubusercontent.com/eslint-community/eslint-plugin-promise/main/__tests__";
+
+struct TestCase {
+    source_text: String,
+    code: Option<String>,
+    output: Option<String>,
+    group_comment: Option<String>,
+    config: Option<String>,
+    settings: Option<String>,
+    filename: Option<String>,
+    language_options: Option<String>,
+}
+
+impl TestCase {
+    fn new(source_text: &str, arg: &Expression<'_>) -> Self {
+        let mut test_case = Self {
+            source_text: source_text.to_string(),
+            code: None,
+            output: None,
+            config: None,
+            settings: None,
+            group_comment: None,
+            filename: None,
+            language_options: None,
+        };
+        test_case.visit_expression(arg);
+        test_case
+    }
+
+    fn with_group_comment(mut self, comment: String) -> Self {
+        self.group_comment = Some(comment);
+        self
+    }
+
+    fn code(&self, need_config: bool, need_settings: bool, need_filename: bool) -> String {
+        self.code
+            .as_ref()
+            .map(|code| {
+                let code_str = format_code_snippet(code);
+                let config = self.config.as_ref().map_or_else(
+                    || "None".to_string(),
+                    |config| format!("Some(serde_json::json!({config}))"),
+                );
+                let settings = self.settings.as_ref().map_or_else(
+                    || "None".to_string(),
+                    |settings| format!(r#"Some(serde_json::json!({{ "settings": {settings} }}))"#),
+                );
+                let filename = self.filename.as_ref().map_or_else(
+                    || "None".to_string(),
+                    |filename| format!(r#"Some(PathBuf::from("{filename}"))"#),
+                );
+                let code_str = if need_filename {
+                    format!("({code_str}, {config}, {settings}, {filename})")
+                } else if need_settings {
+                    format!("({code_str}, {config}, {settings})")
+                } else if need_config {
+                    format!("({code_str}, {config})")
+                } else {
+                    code_str
+                };
+                if let Some(language_options) = &self.language_options {
+                    format!("{code_str}, // {language_options}")
+                } else {
+                    code_str
+                }
+            })
+            .unwrap_or_default()
+    }
+
+    fn group_comment(&self) -> Option<&str> {
+        self.group_comment.as_deref()
+    }
+
+    fn output(&self) -> Option<String> {
+        let code = format_code_snippet(self.code.as_ref()?);
+        let output = format_code_snippet(self.output.as_ref()?);
+        let config = self.config.as_ref().map_or_else(
+            || "None".to_string(),
+            |config| format!("Some(serde_json::json!({config}))"),
+        );
+
+        // ("null==null", "null === null", None),
+        Some(format!(r#"({code}, {output}, {config})"#))
+    }
+}
+
+fn format_code_snippet(code: &str) -> String {
+    let code = if code.contains('\n') {
+        code.replace('\n', "\n\t\t\t").replace('\\', "\\\\").replace('\"', "\\\"")
+    } else {
+        code.to_string()
+    };
+
+    // "debugger" => "debugger"
+    if !code.contains('"') {
+        return format!("\"{code}\"");
+    }
+
+    // "document.querySelector("#foo");" => r##"document.querySelector("#foo");"##
+    if code.contains("\"#") {
+        return format!("r##\"{code}\"##");
+    }
+
+    // 'import foo from "foo";' => r#"import foo from "foo";"#
+    format!("r#\"{}\"#", code.replace("\\\"", "\""))
+}
+
+impl<'a> Visit<'a> for TestCase {
+    fn visit_expression(&mut self, expr: &Expression<'a>) {
+        match expr {
+            Expression::StringLiteral(lit) => self.visit_string_literal(lit),
+            Expression::TemplateLiteral(lit) => self.visit_template_literal(lit),
+            Expression::ObjectExpression(obj_expr) => self.visit_object_expression(obj_expr),
+            Expression::CallExpression(call_expr) => self.visit_call_expression(call_expr),
+            Expression::TaggedTemplateExpression(tag_expr) => {
+                self.visit_tagged_template_expression(tag_expr);
+            }
+            _ => {}
+        }
+    }
+
+    fn visit_call_expression(&mut self, expr: &CallExpression<'a>) {
+        if let Some(member_expr) = expr.callee.as_member_expression() {
+            if let Expression::ArrayExpression(array_expr) = member_expr.object() {
+                // ['class A {', '}'].join('\n')
+                let mut code = String::new();
+                for arg in &array_expr.elements {
+                    let ArrayExpressionElement::StringLiteral(lit) = arg else {
+                        continue;
+                    };
+                    code.push_str(lit.value.as_str());
+                    code.push('\n');
+                }
+                self.code = Some(code);
+                self.config = None;
+            }
+        }
+    }
+
+    fn visit_object_expression(&mut self, expr: &ObjectExpression<'a>) {
+        for obj_prop in &expr.properties {
+            match obj_prop {
+                ObjectPropertyKind::ObjectProperty(prop) => match &prop.key {
+                    PropertyKey::StaticIdentifier(ident) if ident.name == "code" => {
+                        self.code = match &prop.value {
+                            Expression::StringLiteral(s) => Some(s.value.to_string()),
+                            Expression::TaggedTemplateExpression(tag_expr) => {
+                                // There are `dedent`(in eslint-plugin-jest), `outdent`(in eslint-plugin-unicorn) and `noFormat`(in typescript-eslint)
+                                // are known to be used to format test cases for their own purposes.
+                                // We read the quasi of tagged template directly also for the future usage.
+                                tag_expr.quasi.quasi().map(|quasi| quasi.to_string())
+                            }
+                            Expression::TemplateLiteral(tag_expr) => {
+                                tag_expr.quasi().map(|quasi| quasi.to_string())
+                            }
+                            // handle code like ["{", "a: 1", "}"].join("\n")
+                            Expression::CallExpression(call_expr) => {
+                                if !call_expr.arguments.first().is_some_and(|arg|  matches!(arg, Argument::StringLiteral(string) if string.value == "\n")) {
+                                    continue;
+                                }
+                                let Expression::StaticMemberExpression(member) = &call_expr.callee
+                                else {
+                                    continue;
+                                };
+                                if member.property.name != "join" {
+                                    continue;
+                                }
+                                let Expression::ArrayExpression(array_expr) = &member.object else {
+                                    continue;
+                                };
+                                Some(
+                                    array_expr
+                                        .elements
+                                        .iter()
+                                        .map(|arg| match arg {
+                                            ArrayExpressionElement::StringLiteral(string) => {
+                                                string.value.as_str()
+                                            }
+                                            _ => "",
+                                        })
+                                        .collect::<Vec<_>>()
+                                        .join("\n"),
+                                )
+                            }
+                            _ => continue,
+                        }
+                    }
+                    PropertyKey::StaticIdentifier(ident) if ident.name == "output" => {
+                        self.output = match &prop.value {
+                            Expression::StringLiteral(s) => Some(s.value.to_string()),
+                            Expression::TaggedTemplateExpression(tag_expr) => {
+                                tag_expr.quasi.quasi().map(|quasi| quasi.to_string())
+                            }
+                            Expression::TemplateLiteral(tag_expr) => {
+                                tag_expr.quasi().map(|quasi| quasi.to_string())
+                            }
+                            _ => None,
+                        }
+                    }
+                    PropertyKey::StaticIdentifier(ident) if ident.name == "options" => {
+                        let span = prop.value.span();
+                        let option_text = &self.source_text[span.start as usize..span.end as usize];
+                        self.config = Some(json::convert_config_to_json_literal(option_text));
+                    }
+                    PropertyKey::StaticIdentifier(ident) if ident.name == "settings" => {
+                        let span = prop.value.span();
+                        let setting_text = span.source_text(&self.source_text);
+                        self.settings = Some(json::convert_config_to_json_literal(setting_text));
+                    }
+                    PropertyKey::StaticIdentifier(ident) if ident.name == "filename" => {
+                        let span = prop.value.span();
+                        let filename = span.source_text(&self.source_text);
+                        self.filename = Some(filename.to_string());
+                    }
+                    PropertyKey::StaticIdentifier(ident) if ident.name == "languageOptions" => {
+                        let span = prop.value.span();
+                        let language_options = span.source_text(&self.source_text);
+                        let language_options =
+                            json::convert_config_to_json_literal(language_options);
+                        self.language_options = Some(language_options);
+                    }
+                    _ => continue,
+                },
+                ObjectPropertyKind::SpreadProperty(_) => continue,
+            }
+        }
+    }
+
+    fn visit_template_literal(&mut self, lit: &TemplateLiteral<'a>) {
+        self.code = Some(lit.quasi().unwrap().to_string());
+        self.config = None;
+    }
+
+    fn visit_string_literal(&mut self, lit: &StringLiteral) {
+        self.code = Some(lit.value.to_string());
+        self.config = None;
+    }
+
+    fn visit_tagged_template_expression(&mut self, expr: &TaggedTemplateExpression<'a>) {
+        let Expression::Identifier(ident) = &expr.tag else {
+            return;
+        };
+        if ident.name != "dedent" && ident.name != "outdent" {
+            return;
+        }
+        self.code = expr.quasi.quasi().map(|quasi| quasi.to_string());
+        self.config = None;
+    }
+}
+
+#[derive(Serialize)]
+pub struct Context {
+    plugin_name: String,
+    kebab_rule_name: String,
+    pascal_rule_name: String,
+    snake_rule_name: String,
+    pass_cases: String,
+    fail_cases: String,
+    fix_cases: Option<String>,
+    has_filename: bool,
+}
+
+impl Context {
+    fn new(plugin_name: String, rule_name: &str, pass_cases: String, fail_cases: String) -> Self {
+        let pascal_rule_name = rule_name.to_case(Case::Pascal);
+        let kebab_rule_name = rule_name.to_case(Case::Kebab);
+        let underscore_rule_name = rule_name.to_case(Case::Snake);
+        Self {
+            plugin_name,
+            kebab_rule_name,
+            pascal_rule_name,
+            snake_rule_name: underscore_rule_name,
+            pass_cases,
+            fail_cases,
+            fix_cases: None,
+            has_filename: false,
+        }
+    }
+
+    fn with_filename(mut self, has_filename: bool) -> Self {
+        self.has_filename = has_filename;
+        self
+    }
+
+    fn with_fix_cases(mut self, fix_cases: String) -> Self {
+        self.fix_cases = Some(fix_cases);
+        self
+    }
+
+    /// Ports the test cases of `test_file`. Also returns the number of passing and failing cases.
+    fn from_test_file(
+        plugin_name: String,
+        rule_name: &str,
+        test_file: &TestFile,
+    ) -> (Self, usize, usize) {
+        let allocator = Allocator::default();
+        let source_type = SourceType::from_path(&test_file.path).unwrap_or_default();
+        let ret = Parser::new(&allocator, &test_file.source_text, source_type).parse();
+
+        let program = allocator.alloc(ret.program);
+
+        let mut state = State::new(&test_file.source_text);
+        state.visit_program(program);
+
+        let pass_cases = state.pass_cases();
+        let fail_cases = state.fail_cases();
+        let (pass_count, fail_count) = (pass_cases.len(), fail_cases.len());
+
+        let pass_has_config = pass_cases.iter().any(|case| case.config.is_some());
+        let fail_has_config = fail_cases.iter().any(|case| case.config.is_some());
+        let has_config = pass_has_config || fail_has_config;
+
+        let pass_has_settings = pass_cases.iter().any(|case| case.settings.is_some());
+        let fail_has_settings = fail_cases.iter().any(|case| case.settings.is_some());
+        let has_settings = pass_has_settings || fail_has_settings;
+
+        let pass_has_filename = pass_cases.iter().any(|case| case.filename.is_some());
+        let fail_has_filename = fail_cases.iter().any(|case| case.filename.is_some());
+        let has_filename = pass_has_filename || fail_has_filename;
+
+        let gen_cases_string = |cases: Vec<TestCase>| {
+            let mut codes = vec![];
+            let mut fix_codes = vec![];
+            let mut last_comment = String::new();
+            for case in cases {
+                let current_comment = case.group_comment();
+                let mut code = case.code(has_config, has_settings, has_filename);
+                if code.is_empty() {
+                    continue;
+                }
+                if let Some(current_comment) = current_comment {
+                    if current_comment != last_comment {
+                        last_comment = current_comment.to_string();
+                        code = format!(
+                            "// {}\n{}",
+                            &last_comment,
+                            case.code(has_config, has_settings, has_filename)
+                        );
+                    }
+                }
+
+                if let Some(output) = case.output() {
+                    fix_codes.push(output);
+                }
+
+                codes.push(code);
+            }
+
+            (codes.join(",\n"), fix_codes.join(",\n"))
+        };
+
+        // pass cases don't need to be fixed
+        let (pass_cases, _) = gen_cases_string(pass_cases);
+        let (fail_cases, fix_cases) = gen_cases_string(fail_cases);
+
+        let context = Self::new(plugin_name, rule_name, pass_cases, fail_cases)
+            .with_filename(has_filename)
+            .with_fix_cases(fix_cases);
+        (context, pass_count, fail_count)
+    }
+}
+
+struct State<'a> {
+    source_text: &'a str,
+    valid_tests: Vec<&'a Expression<'a>>,
+    invalid_tests: Vec<&'a Expression<'a>>,
+    expression_to_group_comment_map: HashMap<Span, String>,
+    group_comment_stack: Vec<String>,
+}
+
+impl<'a> State<'a> {
+    fn new(source_text: &'a str) -> Self {
+        Self {
+            source_text,
+            valid_tests: vec![],
+            invalid_tests: vec![],
+            expression_to_group_comment_map: HashMap::new(),
+            group_comment_stack: vec![],
+        }
+    }
+
+    fn pass_cases(&self) -> Vec<TestCase> {
+        self.get_test_cases(&self.valid_tests)
+    }
+
+    fn fail_cases(&self) -> Vec<TestCase> {
+        self.get_test_cases(&self.invalid_tests)
+    }
+
+    fn get_test_cases(&self, tests: &[&'a Expression<'a>]) -> Vec<TestCase> {
+        tests
+            .iter()
+            .map(|arg| {
+                let case = TestCase::new(self.source_text, arg);
+                if let Some(group_comment) = self.expression_to_group_comment_map.get(&arg.span()) {
+                    case.with_group_comment(group_comment.to_string())
+                } else {
+                    case
+                }
+            })
+            .collect::<Vec<_>>()
+    }
+
+    fn get_comment(&self) -> String {
+        self.group_comment_stack.join(" ")
+    }
+
+    fn add_valid_test(&mut self, expr: &'a Expression<'a>) {
+        self.valid_tests.push(expr);
+        self.expression_to_group_comment_map.insert(expr.span(), self.get_comment());
+    }
+
+    fn add_invalid_test(&mut self, expr: &'a Expression<'a>) {
+        self.invalid_tests.push(expr);
+        self.expression_to_group_comment_map.insert(expr.span(), self.get_comment());
+    }
+}
+
+impl<'a> Visit<'a> for State<'a> {
+    fn visit_program(&mut self, program: &Program<'a>) {
+        for stmt in &program.body {
+            self.visit_statement(stmt);
+        }
+    }
+
+    fn visit_statement(&mut self, stmt: &Statement<'a>) {
+        match stmt {
+            Statement::ExpressionStatement(expr_stmt) => self.visit_expression_statement(expr_stmt),
+            // for eslint-plugin-jsdoc
+            Statement::ExportDefaultDeclaration(export_decl) => {
+                if let ExportDefaultDeclarationKind::ObjectExpression(obj_expr) =
+                    &export_decl.declaration
+                {
+                    self.visit_object_expression(obj_expr);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn visit_expression_statement(&mut self, stmt: &ExpressionStatement<'a>) {
+        self.visit_expression(&stmt.expression);
+    }
+
+    fn visit_call_expression(&mut self, expr: &CallExpression<'a>) {
+        let mut pushed = false;
+        if let Expression::Identifier(ident) = &expr.callee {
+            // Add describe's first parameter as part group comment
+            // e.g. for `describe('valid', () => { ... })`, the group comment will be "valid"
+            if ident.name == "describe" {
+                if let Some(Argument::StringLiteral(lit)) = expr.arguments.first() {
+                    pushed = true;
+                    self.group_comment_stack.push(lit.value.to_string());
+                }
+            }
+        }
+        for arg in &expr.arguments {
+            self.visit_argument(arg);
+        }
+
+        if pushed {
+            self.group_comment_stack.pop();
+        }
+
+        self.visit_expression(&expr.callee);
+    }
+
+    fn visit_object_property(&mut self, prop: &ObjectProperty<'a>) {
+        let PropertyKey::StaticIdentifier(ident) = &prop.key else { return };
+        match ident.name.as_str() {
+            "valid" => {
+                if let Expression::ArrayExpression(array_expr) = &prop.value {
+                    let array_expr = self.alloc(array_expr);
+                    for arg in &array_expr.elements {
+                        if let Some(expr) = arg.as_expression() {
+                            self.add_valid_test(expr);
+                        }
+                    }
+                }
+
+                // for eslint-plugin-jsx-a11y
+                if let Some(args) = find_parser_arguments(&prop.value).map(|args| self.alloc(args))
+                {
+                    for arg in args {
+                        if let Some(expr) = arg.as_expression() {
+                            self.add_valid_test(expr);
+                        }
+                    }
+                }
+
+                if let Expression::CallExpression(call_expr) = &prop.value {
+                    if call_expr.callee.is_member_expression() {
+                        // for eslint-plugin-react
+                        if let Some(Argument::ArrayExpression(array_expr)) =
+                            call_expr.arguments.first()
+                        {
+                            let array_expr = self.alloc(array_expr);
+                            for arg in &array_expr.elements {
+                                if let Some(expr) = arg.as_expression() {
+                                    self.add_valid_test(expr);
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            "invalid" => {
+                if let Expression::ArrayExpression(array_expr) = &prop.value {
+                    let array_expr = self.alloc(array_expr);
+                    for arg in &array_expr.elements {
+                        if let Some(expr) = arg.as_expression() {
+                            self.add_invalid_test(expr);
+                        }
+                    }
+                }
+
+                // for eslint-plugin-jsx-a11y
+                if let Some(args) = find_parser_arguments(&prop.value).map(|args| self.alloc(args))
+                {
+                    for arg in args {
+                        if let Some(expr) = arg.as_expression() {
+                            self.add_invalid_test(expr);
+                        }
+                    }
+                }
+
+                // for eslint-plugin-react
+                if let Expression::CallExpression(call_expr) = &prop.value {
+                    if call_expr.callee.is_member_expression() {
+                        if let Some(Argument::ArrayExpression(array_expr)) =
+                            call_expr.arguments.first()
+                        {
+                            let array_expr = self.alloc(array_expr);
+                            for arg in &array_expr.elements {
+                                if let Some(expr) = arg.as_expression() {
+                                    self.add_invalid_test(expr);
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn find_parser_arguments<'a, 'b>(
+    mut expr: &'b Expression<'a>,
+) -> Option<&'b oxc_allocator::Vec<'a, Argument<'a>>> {
+    loop {
+        let Expression::CallExpression(call_expr) = expr else { return None };
+        let Expression::StaticMemberExpression(static_member_expr) = &call_expr.callee else {
+            return None;
+        };
+        let StaticMemberExpression { object, property, .. } = &**static_member_expr;
+        if let Expression::Identifier(iden) = object {
+            if iden.name == "parsers" && property.name == "all" {
+                if let Some(arg) = call_expr.arguments.first() {
+                    if let Argument::CallExpression(call_expr) = arg {
+                        if call_expr.callee.is_member_expression() {
+                            return Some(&call_expr.arguments);
+                        }
+                        return None;
+                    }
+                    if arg.is_expression() {
+                        return None;
+                    }
+                }
+            }
+        }
+        expr = object;
+    }
+}
+
+#[derive(Clone, Copy)]
+pub enum RuleKind {
+    ESLint,
+    Jest,
+    Typescript,
+    Unicorn,
+    React,
+    ReactPerf,
+    JSXA11y,
+    Oxc,
+    NextJS,
+    JSDoc,
+    Node,
+    TreeShaking,
+    Promise,
+}
+
+impl RuleKind {
+    /// Parses the plugin argument of `just new-*-rule`, ESLint by default.
+    pub fn from(kind: &str) -> Self {
+        match kind {
+            "jest" => Self::Jest,
+            "typescript" => Self::Typescript,
+            "unicorn" => Self::Unicorn,
+            "react" => Self::React,
+            "react-perf" => Self::ReactPerf,
+            "jsx-a11y" => Self::JSXA11y,
+            "oxc" => Self::Oxc,
+            "nextjs" => Self::NextJS,
+            "jsdoc" => Self::JSDoc,
+            "n" => Self::Node,
+            "tree-shaking" => Self::TreeShaking,
+            "promise" => Self::Promise,
+            _ => Self::ESLint,
+        }
+    }
+
+    /// URL of the test file of the original rule. `None` for oxc rules, which have no original.
+    pub fn test_file_url(self, rule_name: &str) -> Option<String> {
+        let kebab_rule_name = rule_name.to_case(Case::Kebab);
+        let camel_rule_name = rule_name.to_case(Case::Camel);
+        let url = match self {
+            Self::ESLint => format!("{ESLINT_TEST_PATH}/{kebab_rule_name}.js"),
+            Self::Jest => format!("{JEST_TEST_PATH}/{kebab_rule_name}.test.ts"),
+            Self::Typescript => format!("{TYPESCRIPT_ESLINT_TEST_PATH}/{kebab_rule_name}.test.ts"),
+            Self::Unicorn => format!("{UNICORN_TEST_PATH}/{kebab_rule_name}.mjs"),
+            Self::React => format!("{REACT_TEST_PATH}/{kebab_rule_name}.js"),
+            Self::ReactPerf => format!("{REACT_PERF_TEST_PATH}/{kebab_rule_name}.test.ts"),
+            Self::JSXA11y => format!("{JSX_A11Y_TEST_PATH}/{kebab_rule_name}-test.js"),
+            Self::NextJS => format!("{NEXT_JS_TEST_PATH}/{kebab_rule_name}.test.ts"),
+            Self::JSDoc => format!("{JSDOC_TEST_PATH}/{camel_rule_name}.js"),
+            Self::Node => format!("{NODE_TEST_PATH}/{kebab_rule_name}.js"),
+            Self::TreeShaking => format!("{TREE_SHAKING_PATH}/{kebab_rule_name}.test.ts"),
+            Self::Promise => format!("{PROMISE_TEST_PATH}/{kebab_rule_name}.js"),
+            Self::Oxc => return None,
+        };
+        Some(url)
+    }
+
+    /// Module of the rules of the plugin in `crates/oxc_linter/src/rules.rs`.
+    pub fn module_name(self) -> &'static str {
+        match self {
+            Self::ESLint => "eslint",
+            Self::Jest => "jest",
+            Self::Typescript => "typescript",
+            Self::Unicorn => "unicorn",
+            Self::React => "react",
+            Self::ReactPerf => "react_perf",
+            Self::JSXA11y => "jsx_a11y",
+            Self::Oxc => "oxc",
+            Self::NextJS => "nextjs",
+            Self::JSDoc => "jsdoc",
+            Self::Node => "node",
+            Self::TreeShaking => "tree_shaking",
+            Self::Promise => "promise",
+        }
+    }
+
+    /// Directory of the rules of the plugin, relative to the repository root.
+    pub fn rules_dir(self) -> PathBuf {
+        Path::new("crates/oxc_linter/src/rules").join(self.module_name())
+    }
+}
+
+impl Display for RuleKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ESLint => write!(f, "eslint"),
+            Self::Typescript => write!(f, "typescript-eslint"),
+            Self::Jest => write!(f, "eslint-plugin-jest"),
+            Self::Unicorn => write!(f, "eslint-plugin-unicorn"),
+            Self::React => write!(f, "eslint-plugin-react"),
+            Self::ReactPerf => write!(f, "eslint-plugin-react-perf"),
+            Self::JSXA11y => write!(f, "eslint-plugin-jsx-a11y"),
+            Self::Oxc => write!(f, "oxc"),
+            Self::NextJS => write!(f, "eslint-plugin-next"),
+            Self::JSDoc => write!(f, "eslint-plugin-jsdoc"),
+            Self::Node => write!(f, "eslint-plugin-n"),
+            Self::TreeShaking => write!(f, "eslint-plugin-tree-shaking"),
+            Self::Promise => write!(f, "eslint-plugin-promise"),
+        }
+    }
+}
+
+/// Path of the file which registers all rules, relative to the repository root.
+pub const RULES_PATH: &str = "crates/oxc_linter/src/rules.rs";
+
+/// The test file of the original rule.
+pub struct TestFile {
+    /// URL or path of the file. Its extension decides how the file is parsed.
+    pub path: String,
+    pub source_text: String,
+}
+
+/// Downloads the test file of the original rule, see [`RuleKind::test_file_url`].
+///
+/// # Errors
+///
+/// If the file can not be downloaded, e.g. because the rule does not exist in the plugin.
+pub fn fetch_test_file(url: &str) -> Result<TestFile, String> {
+    let response = oxc_tasks_common::agent()
+        .get(url)
+        .call()
+        .map_err(|err| format!("Failed to download {url}: {err}"))?;
+    let source_text = response
+        .into_string()
+        .map_err(|err| format!("Failed to convert rule source code to string: {err}"))?;
+    Ok(TestFile { path: url.to_string(), source_text })
+}
+
+/// Files generated for a new rule.
+pub struct Scaffold {
+    /// Path of the rule, relative to the repository root.
+    pub rule_path: PathBuf,
+    /// The rule skeleton, with the tests of the original rule.
+    pub rule_source: String,
+    /// Unified diff of [`RULES_PATH`] which registers the rule. Empty if the rule is registered
+    /// already.
+    pub registration_diff: String,
+    /// Number of ported passing test cases.
+    pub pass_cases: usize,
+    /// Number of ported failing test cases.
+    pub fail_cases: usize,
+}
+
+/// Generates the skeleton of rule `rule_name` of the plugin `rule_kind`.
+///
+/// The tests are ported from `test_file`, or left empty without one. `rules_source` is the
+/// current content of [`RULES_PATH`], which the registration diff applies to.
+pub fn scaffold(
+    rule_name: &str,
+    rule_kind: RuleKind,
+    test_file: Option<&TestFile>,
+    rules_source: &str,
+) -> Scaffold {
+    let rule_name = rule_name.to_case(Case::Snake);
+    let plugin_name = rule_kind.to_string();
+    let (context, pass_cases, fail_cases) = match test_file {
+        Some(test_file) => Context::from_test_file(plugin_name, &rule_name, test_file),
+        None => (Context::new(plugin_name, &rule_name, String::new(), String::new()), 0, 0),
+    };
+
+    let rendered = template::Template::with_context(&context).render();
+    let rule_path = rule_kind.rules_dir().join(format!("{}.rs", context.snake_rule_name));
+
+    let registered = register_rule(rules_source, rule_kind, &rule_name);
+    let registration_diff = similar::TextDiff::from_lines(rules_source, &registered)
+        .unified_diff()
+        .header(&format!("a/{RULES_PATH}"), &format!("b/{RULES_PATH}"))
+        .to_string();
+
+    Scaffold { rule_path, rule_source: rendered, registration_diff, pass_cases, fail_cases }
+}
+
+/// Adds the `pub mod` declaration of the rule and its entry in `declare_all_lint_rules!` to
+/// `rules_source`, the content of [`RULES_PATH`].
+///
+/// Declarations are kept in alphabetical order where they are sorted, and entries are added after
+/// the other rules of the plugin. A plugin without rules gets a new module.
+pub fn register_rule(rules_source: &str, rule_kind: RuleKind, rule_name: &str) -> String {
+    let module = rule_kind.module_name();
+    let rule_name = rule_name.to_case(Case::Snake);
+    let mut lines = rules_source.lines().map(String::from).collect::<Vec<_>>();
+    let find = |lines: &[String], from: usize, line: &str| {
+        lines[from..].iter().position(|l| l == line).map(|i| from + i)
+    };
+
+    let declaration = format!("    pub mod {rule_name};");
+    let macro_start = find(&lines, 0, "oxc_macros::declare_all_lint_rules! {");
+    if let Some(start) = find(&lines, 0, &format!("mod {module} {{")) {
+        let end = find(&lines, start, "}").unwrap_or(lines.len());
+        if !lines[start..end].contains(&declaration) {
+            let index = (start + 1..end)
+                .find(|&i| lines[i].starts_with("    pub mod ") && lines[i] > declaration)
+                .unwrap_or(end);
+            lines.insert(index, declaration);
+        }
+    } else {
+        let index = macro_start.unwrap_or(lines.len());
+        let module_lines =
+            [format!("mod {module} {{"), declaration, "}".to_string(), String::new()];
+        lines.splice(index..index, module_lines);
+    }
+
+    let entry = format!("    {module}::{rule_name},");
+    if let Some(start) = find(&lines, 0, "oxc_macros::declare_all_lint_rules! {") {
+        let end = find(&lines, start, "}").unwrap_or(lines.len());
+        if !lines[start..end].contains(&entry) {
+            let prefix = format!("    {module}::");
+            let index = (start + 1..end)
+                .rev()
+                .find(|&i| lines[i].starts_with(&prefix))
+                .map_or(end, |i| i + 1);
+            lines.insert(index, entry);
+        }
+    }
+
+    let mut registered = lines.join("\n");
+    if rules_source.ends_with('\n') {
+        registered.push('\n');
+    }
+    registered
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::Path;
+
+    use super::{register_rule, scaffold, RuleKind, TestFile};
+
+    const RULES_SOURCE: &str = "\
+mod eslint {
+    pub mod no_bar;
+    pub mod no_debugger;
+}
+
+mod jest {
+    pub mod expect_expect;
+}
+
+oxc_macros::declare_all_lint_rules! {
+    eslint::no_bar,
+    eslint::no_debugger,
+    jest::expect_expect,
+}
+";
+
+    #[test]
+    fn register_rule_of_existing_plugin() {
+        let registered = register_rule(RULES_SOURCE, RuleKind::ESLint, "no-console");
+        assert_eq!(
+            registered,
+            "\
+mod eslint {
+    pub mod no_bar;
+    pub mod no_console;
+    pub mod no_debugger;
+}
+
+mod jest {
+    pub mod expect_expect;
+}
+
+oxc_macros::declare_all_lint_rules! {
+    eslint::no_bar,
+    eslint::no_debugger,
+    eslint::no_console,
+    jest::expect_expect,
+}
+"
+        );
+        // Registering a rule again does not change anything.
+        assert_eq!(register_rule(&registered, RuleKind::ESLint, "no_console"), registered);
+    }
+
+    #[test]
+    fn register_rule_of_new_plugin() {
+        let registered = register_rule(RULES_SOURCE, RuleKind::Promise, "no-nesting");
+        assert_eq!(
+            registered,
+            "\
+mod eslint {
+    pub mod no_bar;
+    pub mod no_debugger;
+}
+
+mod jest {
+    pub mod expect_expect;
+}
+
+mod promise {
+    pub mod no_nesting;
+}
+
+oxc_macros::declare_all_lint_rules! {
+    eslint::no_bar,
+    eslint::no_debugger,
+    jest::expect_expect,
+    promise::no_nesting,
+}
+"
+        );
+    }
+
+    #[test]
+    fn scaffold_with_test_file() {
+        let test_file = TestFile {
+            path: "https://example.com/no-foo.js".to_string(),
+            source_text: r#"
+ruleTester.run("no-foo", rule, {
+    valid: ["foo()", "bar"],
+    invalid: [{ code: "foo", errors: [{ messageId: "unexpected" }] }]
+});
+"#
+            .to_string(),
+        };
+        let scaffold = scaffold("no-foo", RuleKind::ESLint, Some(&test_file), RULES_SOURCE);
+        assert_eq!(scaffold.rule_path, Path::new("crates/oxc_linter/src/rules/eslint/no_foo.rs"));
+        assert_eq!((scaffold.pass_cases, scaffold.fail_cases), (2, 1));
+        assert!(scaffold.rule_source.contains("pub struct NoFoo;"));
+        assert!(scaffold.rule_source.contains(r#""foo()","#));
+        assert!(scaffold.registration_diff.starts_with("--- a/crates/oxc_linter/src/rules.rs\n"));
+        assert!(scaffold.registration_diff.contains("\n+    pub mod no_foo;\n"));
+        assert!(scaffold.registration_diff.contains("\n+    eslint::no_foo,\n"));
+    }
+
+    #[test]
+    fn scaffold_registered_rule_without_test_file() {
+        let scaffold = scaffold("expect-expect", RuleKind::Jest, None, RULES_SOURCE);
+        assert_eq!(
+            scaffold.rule_path,
+            Path::new("crates/oxc_linter/src/rules/jest/expect_expect.rs")
+        );
+        assert_eq!((scaffold.pass_cases, scaffold.fail_cases), (0, 0));
+        assert!(scaffold.rule_source.contains("pub struct ExpectExpect;"));
+        assert!(scaffold.registration_diff.is_empty());
+    }
+}